@@ -30,15 +30,33 @@ impl std::ops::Deref for DatabaseBlockRevisionPad {
 }
 
 impl DatabaseBlockRevisionPad {
-  pub fn duplicate_data(&self, duplicated_block_id: &str) -> DatabaseBlockRevision {
+  /// Duplicates the rows with new row ids. The `id_map` maps the ids of the duplicated objects to
+  /// the ids of their copies. A row uses the id in the map if there is one, otherwise a new id
+  /// is generated and inserted into the map. The cells are moved to the field ids in the map.
+  pub fn duplicate_data(
+    &self,
+    duplicated_block_id: &str,
+    id_map: &mut HashMap<String, String>,
+  ) -> DatabaseBlockRevision {
     let duplicated_rows = self
       .block
       .rows
       .iter()
       .map(|row| {
         let mut duplicated_row = row.as_ref().clone();
-        duplicated_row.id = gen_row_id();
+        duplicated_row.id = id_map
+          .entry(row.id.clone())
+          .or_insert_with(gen_row_id)
+          .clone();
         duplicated_row.block_id = duplicated_block_id.to_string();
+        duplicated_row.cells = row
+          .cells
+          .iter()
+          .map(|(field_id, cell_rev)| {
+            let field_id = id_map.get(field_id).unwrap_or(field_id).clone();
+            (field_id, cell_rev.clone())
+          })
+          .collect();
         Arc::new(duplicated_row)
       })
       .collect::<Vec<Arc<RowRevision>>>();
@@ -101,6 +119,18 @@ impl DatabaseBlockRevisionPad {
     })
   }
 
+  /// Replaces all the rows, the returned changeset is the diff between the old rows and the
+  /// new rows.
+  pub fn reset_rows(
+    &mut self,
+    row_revs: Vec<Arc<RowRevision>>,
+  ) -> SyncResult<Option<DatabaseBlockRevisionChangeset>> {
+    self.modify(|rows| {
+      *rows = row_revs;
+      Ok(Some(()))
+    })
+  }
+
   pub fn get_row_rev(&self, row_id: &str) -> Option<(usize, Arc<RowRevision>)> {
     for (index, row) in self.block.rows.iter().enumerate() {
       if row.id == row_id {
@@ -241,9 +271,11 @@ impl DatabaseBlockRevisionPad {
               type_name::<DatabaseBlockRevision>(),
               operations.json_str()
             );
+            let inverted_operations = operations.invert(&self.operations);
             self.operations = self.operations.compose(&operations)?;
             Ok(Some(DatabaseBlockRevisionChangeset {
               operations,
+              inverted_operations,
               md5: md5(&self.operations.json_bytes()),
             }))
           },
@@ -252,6 +284,22 @@ impl DatabaseBlockRevisionPad {
     }
   }
 
+  /// Applies the operations that were not generated by this pad, for example, the inverted
+  /// operations of the undo history.
+  pub fn apply_operations(
+    &mut self,
+    operations: DatabaseBlockOperations,
+  ) -> SyncResult<DatabaseBlockRevisionChangeset> {
+    let inverted_operations = operations.invert(&self.operations);
+    let new_operations = self.operations.compose(&operations)?;
+    *self = Self::from_operations(new_operations)?;
+    Ok(DatabaseBlockRevisionChangeset {
+      operations,
+      inverted_operations,
+      md5: md5(&self.operations.json_bytes()),
+    })
+  }
+
   fn modify_row<F>(
     &mut self,
     row_id: &str,
@@ -278,10 +326,26 @@ impl DatabaseBlockRevisionPad {
   pub fn operations_json_str(&self) -> String {
     self.operations.json_str()
   }
+
+  /// Replaces the content with the operations that were received from the server.
+  pub fn reset_operations(&mut self, operations: DatabaseBlockOperations) -> SyncResult<String> {
+    *self = Self::from_operations(operations)?;
+    Ok(md5(&self.operations.json_bytes()))
+  }
+
+  pub fn compose_remote_operations(
+    &mut self,
+    operations: DatabaseBlockOperations,
+  ) -> SyncResult<String> {
+    let composed_operations = self.operations.compose(&operations)?;
+    self.reset_operations(composed_operations)
+  }
 }
 
 pub struct DatabaseBlockRevisionChangeset {
   pub operations: DatabaseBlockOperations,
+  /// inverted_operations: the operations that revert the change.
+  pub inverted_operations: DatabaseBlockOperations,
   /// md5: the md5 of the grid after applying the change.
   pub md5: String,
 }
@@ -321,9 +385,10 @@ impl std::default::Default for DatabaseBlockRevisionPad {
 #[cfg(test)]
 mod tests {
   use crate::client_database::{DatabaseBlockOperations, DatabaseBlockRevisionPad};
-  use database_model::{RowChangeset, RowRevision};
+  use database_model::{CellRevision, RowChangeset, RowRevision};
 
   use std::borrow::Cow;
+  use std::collections::HashMap;
 
   #[test]
   fn block_meta_add_row() {
@@ -334,6 +399,7 @@ mod tests {
       cells: Default::default(),
       height: 0,
       visibility: false,
+      created_at: 0,
     };
 
     let change = pad.add_row_rev(row.clone(), None).unwrap().unwrap();
@@ -384,6 +450,7 @@ mod tests {
       cells: Default::default(),
       height: 0,
       visibility: false,
+      created_at: 0,
     }
   }
 
@@ -435,6 +502,7 @@ mod tests {
       cells: Default::default(),
       height: 0,
       visibility: false,
+      created_at: 0,
     };
 
     let _ = pad.add_row_rev(row.clone(), None).unwrap().unwrap();
@@ -459,6 +527,7 @@ mod tests {
       cells: Default::default(),
       height: 0,
       visibility: false,
+      created_at: 0,
     };
 
     let changeset = RowChangeset {
@@ -482,6 +551,55 @@ mod tests {
     );
   }
 
+  #[test]
+  fn block_meta_apply_inverted_operations_test() {
+    let mut pad = test_pad();
+    let row = test_row_rev("1", &pad);
+    let _ = pad.add_row_rev(row.clone(), None).unwrap().unwrap();
+    let json = pad.revision_json().unwrap();
+
+    let change = pad
+      .delete_rows(vec![Cow::Borrowed(&row.id)])
+      .unwrap()
+      .unwrap();
+    assert!(pad.rows.is_empty());
+
+    let undo_change = pad.apply_operations(change.inverted_operations).unwrap();
+    assert_eq!(pad.revision_json().unwrap(), json);
+    assert_eq!(*pad.rows[0], row);
+
+    let _ = pad
+      .apply_operations(undo_change.inverted_operations)
+      .unwrap();
+    assert!(pad.rows.is_empty());
+  }
+
+  #[test]
+  fn block_meta_duplicate_data_test() {
+    let mut pad = test_pad();
+    let mut row = test_row_rev("1", &pad);
+    row
+      .cells
+      .insert("f1".to_string(), CellRevision::new("hello".to_string()));
+    pad.add_row_rev(row, None).unwrap();
+    pad.add_row_rev(test_row_rev("2", &pad), None).unwrap();
+
+    let mut id_map = HashMap::from([
+      ("f1".to_string(), "new_f1".to_string()),
+      ("2".to_string(), "new_2".to_string()),
+    ]);
+    let block = pad.duplicate_data("new_block", &mut id_map);
+    assert_eq!(block.block_id, "new_block");
+    assert_ne!(block.rows[0].id, "1");
+    assert_eq!(id_map.get("1").unwrap(), &block.rows[0].id);
+    assert_eq!(block.rows[1].id, "new_2");
+    assert_eq!(
+      block.rows[0].cells.get("new_f1").unwrap().type_cell_data,
+      "hello"
+    );
+    assert!(block.rows.iter().all(|row| row.block_id == "new_block"));
+  }
+
   fn test_pad() -> DatabaseBlockRevisionPad {
     let operations =
       DatabaseBlockOperations::from_json(r#"[{"insert":"{\"block_id\":\"1\",\"rows\":[]}"}]"#)
@@ -1,8 +1,8 @@
 use crate::errors::{internal_sync_error, SyncError, SyncResult};
 use crate::util::cal_diff;
 use database_model::{
-  gen_block_id, gen_database_id, DatabaseBlockMetaRevision, DatabaseBlockMetaRevisionChangeset,
-  DatabaseRevision, FieldRevision, FieldTypeRevision,
+  gen_block_id, gen_database_id, gen_field_id, DatabaseBlockMetaRevision,
+  DatabaseBlockMetaRevisionChangeset, DatabaseRevision, FieldRevision, FieldTypeRevision,
 };
 use flowy_sync::util::make_operations_from_revisions;
 use lib_infra::util::md5;
@@ -30,14 +30,24 @@ impl DatabaseRevisionPad {
     self.database_rev.database_id.clone()
   }
 
+  /// Duplicates the fields and the block metas with new ids. A field uses the id in the `id_map`
+  /// if there is one, otherwise a new id is generated and inserted into the map.
   pub async fn duplicate_database_block_meta(
     &self,
+    id_map: &mut HashMap<String, String>,
   ) -> (Vec<FieldRevision>, Vec<DatabaseBlockMetaRevision>) {
     let fields = self
       .database_rev
       .fields
       .iter()
-      .map(|field_rev| field_rev.as_ref().clone())
+      .map(|field_rev| {
+        let mut duplicated_field = field_rev.as_ref().clone();
+        duplicated_field.id = id_map
+          .entry(field_rev.id.clone())
+          .or_insert_with(gen_field_id)
+          .clone();
+        duplicated_field
+      })
       .collect();
 
     let blocks = self
@@ -368,6 +378,20 @@ impl DatabaseRevisionPad {
     self.operations.json_str()
   }
 
+  /// Replaces the content with the operations that were received from the server.
+  pub fn reset_operations(&mut self, operations: DatabaseOperations) -> SyncResult<String> {
+    *self = Self::from_operations(operations)?;
+    Ok(self.database_md5())
+  }
+
+  pub fn compose_remote_operations(
+    &mut self,
+    operations: DatabaseOperations,
+  ) -> SyncResult<String> {
+    let composed_operations = self.operations.compose(&operations)?;
+    self.reset_operations(composed_operations)
+  }
+
   pub fn get_fields(&self) -> &[Arc<FieldRevision>] {
     &self.database_rev.fields
   }
@@ -385,9 +409,11 @@ impl DatabaseRevisionPad {
         match cal_diff::<EmptyAttributes>(old, new) {
           None => Ok(None),
           Some(operations) => {
+            let inverted_operations = operations.invert(&self.operations);
             self.operations = self.operations.compose(&operations)?;
             Ok(Some(DatabaseRevisionChangeset {
               operations,
+              inverted_operations,
               md5: self.database_md5(),
             }))
           },
@@ -396,6 +422,35 @@ impl DatabaseRevisionPad {
     }
   }
 
+  /// Replaces the content with the other pad's content, for example, the pad restored from a
+  /// snapshot. The returned changeset is the diff between the two contents, so it can be saved
+  /// as a new revision.
+  pub fn reset_database(
+    &mut self,
+    other: &DatabaseRevisionPad,
+  ) -> SyncResult<Option<DatabaseRevisionChangeset>> {
+    self.modify_database(|database_rev| {
+      *database_rev = other.database_rev.as_ref().clone();
+      Ok(Some(()))
+    })
+  }
+
+  /// Applies the operations that were not generated by this pad, for example, the inverted
+  /// operations of the undo history.
+  pub fn apply_operations(
+    &mut self,
+    operations: DatabaseOperations,
+  ) -> SyncResult<DatabaseRevisionChangeset> {
+    let inverted_operations = operations.invert(&self.operations);
+    let new_operations = self.operations.compose(&operations)?;
+    *self = Self::from_operations(new_operations)?;
+    Ok(DatabaseRevisionChangeset {
+      operations,
+      inverted_operations,
+      md5: self.database_md5(),
+    })
+  }
+
   fn modify_block<F>(
     &mut self,
     block_id: &str,
@@ -461,6 +516,8 @@ pub fn make_database_rev_json_str(grid_revision: &DatabaseRevision) -> SyncResul
 
 pub struct DatabaseRevisionChangeset {
   pub operations: DatabaseOperations,
+  /// inverted_operations: the operations that revert the change.
+  pub inverted_operations: DatabaseOperations,
   /// md5: the md5 of the grid after applying the change.
   pub md5: String,
 }
@@ -1,7 +1,8 @@
 use crate::errors::{internal_sync_error, SyncError, SyncResult};
 use crate::util::cal_diff;
 use database_model::{
-  DatabaseViewRevision, FieldRevision, FieldTypeRevision, FilterRevision,
+  CalculationRevision, CalendarLayoutSettingRevision, DatabaseViewRevision, FieldRevision,
+  FieldTypeRevision, FilterGroupRevision, FilterOperatorRevision, FilterRevision,
   GroupConfigurationRevision, LayoutRevision, SortRevision,
 };
 use flowy_sync::util::make_operations_from_revisions;
@@ -135,7 +136,7 @@ impl DatabaseViewRevisionPad {
     self.sorts.get_all_objects()
   }
 
-  /// For the moment, a field type only have one filter.
+  /// Returns the filters of the field with the field type. A field can have more than one filter.
   pub fn get_sorts(
     &self,
     field_id: &str,
@@ -215,11 +216,83 @@ impl DatabaseViewRevisionPad {
     })
   }
 
+  pub fn get_all_calculations(
+    &self,
+    field_revs: &[Arc<FieldRevision>],
+  ) -> Vec<Arc<CalculationRevision>> {
+    self.calculations.get_objects_by_field_revs(field_revs)
+  }
+
+  /// For the moment, a field type only have one calculation.
+  pub fn get_calculations(
+    &self,
+    field_id: &str,
+    field_type_rev: &FieldTypeRevision,
+  ) -> Vec<Arc<CalculationRevision>> {
+    self
+      .calculations
+      .get_objects(field_id, field_type_rev)
+      .unwrap_or_default()
+  }
+
+  pub fn insert_calculation(
+    &mut self,
+    field_id: &str,
+    calculation_rev: CalculationRevision,
+  ) -> SyncResult<Option<GridViewRevisionChangeset>> {
+    self.modify(|view| {
+      let field_type = calculation_rev.field_type;
+      view
+        .calculations
+        .add_object(field_id, &field_type, calculation_rev);
+      Ok(Some(()))
+    })
+  }
+
+  pub fn update_calculation(
+    &mut self,
+    field_id: &str,
+    calculation_rev: CalculationRevision,
+  ) -> SyncResult<Option<GridViewRevisionChangeset>> {
+    self.modify(|view| {
+      if let Some(calculation) =
+        view
+          .calculations
+          .get_mut_object(field_id, &calculation_rev.field_type, |calculation| {
+            calculation.id == calculation_rev.id
+          })
+      {
+        let calculation = Arc::make_mut(calculation);
+        calculation.calculation_type = calculation_rev.calculation_type;
+        Ok(Some(()))
+      } else {
+        Ok(None)
+      }
+    })
+  }
+
+  pub fn delete_calculation<T: Into<FieldTypeRevision>>(
+    &mut self,
+    calculation_id: &str,
+    field_id: &str,
+    field_type: T,
+  ) -> SyncResult<Option<GridViewRevisionChangeset>> {
+    let field_type = field_type.into();
+    self.modify(|view| {
+      if let Some(calculations) = view.calculations.get_mut_objects(field_id, &field_type) {
+        calculations.retain(|calculation| calculation.id != calculation_id);
+        Ok(Some(()))
+      } else {
+        Ok(None)
+      }
+    })
+  }
+
   pub fn get_all_filters(&self, field_revs: &[Arc<FieldRevision>]) -> Vec<Arc<FilterRevision>> {
     self.filters.get_objects_by_field_revs(field_revs)
   }
 
-  /// For the moment, a field type only have one filter.
+  /// Returns the filters of the field with the field type. A field can have more than one filter.
   pub fn get_filters(
     &self,
     field_id: &str,
@@ -242,15 +315,21 @@ impl DatabaseViewRevisionPad {
       .get_object(field_id, field_type_rev, |filter| filter.id == filter_id)
   }
 
+  /// Inserts the filter into the filter group. The filter will be inserted into the root group
+  /// if the group_id is None.
   pub fn insert_filter(
     &mut self,
+    group_id: Option<&str>,
     field_id: &str,
     filter_rev: FilterRevision,
   ) -> SyncResult<Option<GridViewRevisionChangeset>> {
     self.modify(|view| {
-      let field_type = filter_rev.field_type;
-      view.filters.add_object(field_id, &field_type, filter_rev);
-      Ok(Some(()))
+      if view.filters.add_filter(group_id, field_id, filter_rev) {
+        Ok(Some(()))
+      } else {
+        let msg = format!("Can't find the filter group: {:?}", group_id);
+        Err(SyncError::record_not_found().context(msg))
+      }
     })
   }
 
@@ -285,8 +364,7 @@ impl DatabaseViewRevisionPad {
   ) -> SyncResult<Option<GridViewRevisionChangeset>> {
     let field_type = field_type.into();
     self.modify(|view| {
-      if let Some(filters) = view.filters.get_mut_objects(field_id, &field_type) {
-        filters.retain(|filter| filter.id != filter_id);
+      if view.filters.delete_filter(field_id, &field_type, filter_id) {
         Ok(Some(()))
       } else {
         Ok(None)
@@ -294,10 +372,86 @@ impl DatabaseViewRevisionPad {
     })
   }
 
+  /// Returns the filter group with the id, or the root group if the group_id is None.
+  pub fn get_filter_group(&self, group_id: Option<&str>) -> Option<FilterGroupRevision> {
+    self.filters.get_group(group_id).cloned()
+  }
+
+  pub fn insert_filter_group(
+    &mut self,
+    parent_group_id: Option<&str>,
+    group_rev: FilterGroupRevision,
+  ) -> SyncResult<Option<GridViewRevisionChangeset>> {
+    self.modify(|view| {
+      if view.filters.insert_group(parent_group_id, group_rev) {
+        Ok(Some(()))
+      } else {
+        let msg = format!("Can't find the filter group: {:?}", parent_group_id);
+        Err(SyncError::record_not_found().context(msg))
+      }
+    })
+  }
+
+  pub fn update_filter_group_operator(
+    &mut self,
+    group_id: Option<&str>,
+    operator: FilterOperatorRevision,
+  ) -> SyncResult<Option<GridViewRevisionChangeset>> {
+    self.modify(|view| {
+      if view.filters.update_group_operator(group_id, operator) {
+        Ok(Some(()))
+      } else {
+        Ok(None)
+      }
+    })
+  }
+
+  /// Deletes the filter group and the filters in it.
+  pub fn delete_filter_group(
+    &mut self,
+    group_id: &str,
+  ) -> SyncResult<Option<GridViewRevisionChangeset>> {
+    self.modify(|view| match view.filters.delete_group(group_id) {
+      None => Ok(None),
+      Some(_) => Ok(Some(())),
+    })
+  }
+
+  pub fn get_calendar_setting(&self) -> Option<CalendarLayoutSettingRevision> {
+    self.calendar_setting.clone()
+  }
+
+  pub fn update_calendar_setting(
+    &mut self,
+    calendar_setting: CalendarLayoutSettingRevision,
+  ) -> SyncResult<Option<GridViewRevisionChangeset>> {
+    self.modify(|view| {
+      if view.calendar_setting.as_ref() == Some(&calendar_setting) {
+        return Ok(None);
+      }
+      view.calendar_setting = Some(calendar_setting);
+      Ok(Some(()))
+    })
+  }
+
   pub fn json_str(&self) -> SyncResult<String> {
     make_grid_view_rev_json_str(&self.view)
   }
 
+  /// Replaces the content with the operations that were received from the server.
+  pub fn reset_operations(&mut self, operations: GridViewOperations) -> SyncResult<String> {
+    *self = Self::from_operations(operations)?;
+    Ok(md5(&self.operations.json_bytes()))
+  }
+
+  pub fn compose_remote_operations(
+    &mut self,
+    operations: GridViewOperations,
+  ) -> SyncResult<String> {
+    let composed_operations = self.operations.compose(&operations)?;
+    self.reset_operations(composed_operations)
+  }
+
   pub fn layout(&self) -> LayoutRevision {
     self.layout.clone()
   }
@@ -0,0 +1,361 @@
+use crate::client_database::{DatabaseBlockOperations, DatabaseOperations};
+use lib_ot::core::OperationTransform;
+use lib_ot::errors::OTError;
+
+const MAX_UNDOES: usize = 20;
+
+/// The operations that revert a change of the database or one of its blocks.
+#[derive(Debug, Clone)]
+pub enum DatabaseHistoryOperations {
+  Database(DatabaseOperations),
+  Block {
+    block_id: String,
+    operations: DatabaseBlockOperations,
+  },
+}
+
+impl DatabaseHistoryOperations {
+  fn is_same_object(&self, other: &Self) -> bool {
+    match (self, other) {
+      (DatabaseHistoryOperations::Database(_), DatabaseHistoryOperations::Database(_)) => true,
+      (
+        DatabaseHistoryOperations::Block { block_id, .. },
+        DatabaseHistoryOperations::Block {
+          block_id: other_block_id,
+          ..
+        },
+      ) => block_id == other_block_id,
+      _ => false,
+    }
+  }
+
+  fn operations(&self) -> &DatabaseOperations {
+    match self {
+      DatabaseHistoryOperations::Database(operations) => operations,
+      DatabaseHistoryOperations::Block { operations, .. } => operations,
+    }
+  }
+
+  fn with_operations(&self, operations: DatabaseOperations) -> Self {
+    match self {
+      DatabaseHistoryOperations::Database(_) => DatabaseHistoryOperations::Database(operations),
+      DatabaseHistoryOperations::Block { block_id, .. } => DatabaseHistoryOperations::Block {
+        block_id: block_id.clone(),
+        operations,
+      },
+    }
+  }
+}
+
+/// A [DatabaseHistoryItem] represents a user-level action, for example, deleting a row or
+/// switching the field type. An action may change the database and several blocks, so it
+/// contains all the operations that revert the changes in the order they were made.
+#[derive(Debug, Clone, Default)]
+pub struct DatabaseHistoryItem {
+  pub operations: Vec<DatabaseHistoryOperations>,
+}
+
+impl DatabaseHistoryItem {
+  pub fn is_empty(&self) -> bool {
+    self.operations.is_empty()
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct DatabaseHistory {
+  undoes: Vec<DatabaseHistoryItem>,
+  redoes: Vec<DatabaseHistoryItem>,
+  capacity: usize,
+  /// The item that collects the operations of the current action.
+  pending: DatabaseHistoryItem,
+  /// The number of the nested actions that are not ended yet.
+  action_depth: usize,
+  /// The number of the nested untracked changes that are not ended yet.
+  untracked_depth: usize,
+}
+
+impl std::default::Default for DatabaseHistory {
+  fn default() -> Self {
+    DatabaseHistory {
+      undoes: Vec::new(),
+      redoes: Vec::new(),
+      capacity: MAX_UNDOES,
+      pending: DatabaseHistoryItem::default(),
+      action_depth: 0,
+      untracked_depth: 0,
+    }
+  }
+}
+
+impl DatabaseHistory {
+  pub fn new() -> Self {
+    DatabaseHistory::default()
+  }
+
+  pub fn can_undo(&self) -> bool {
+    !self.undoes.is_empty()
+  }
+
+  pub fn can_redo(&self) -> bool {
+    !self.redoes.is_empty()
+  }
+
+  /// Starts a user-level action. The operations recorded before the matching [end_action]
+  /// are grouped into one item, so they are undone together.
+  pub fn begin_action(&mut self) {
+    self.action_depth += 1;
+  }
+
+  pub fn end_action(&mut self) {
+    if self.action_depth == 0 {
+      return;
+    }
+
+    self.action_depth -= 1;
+    if self.action_depth == 0 {
+      let item = std::mem::take(&mut self.pending);
+      self.record(item);
+    }
+  }
+
+  /// Starts a change that is derived from the changes of the other databases, for example,
+  /// recalculating the rollup cells. The operations recorded before the matching
+  /// [end_untracked] are not undoable, the recorded items are transformed against them instead.
+  pub fn begin_untracked(&mut self) {
+    self.untracked_depth += 1;
+  }
+
+  pub fn end_untracked(&mut self) {
+    self.untracked_depth = self.untracked_depth.saturating_sub(1);
+  }
+
+  /// Records the operations of a change and the inverted operations that revert it. The change
+  /// is treated as a standalone action if there is no action in progress.
+  pub fn record_operations(
+    &mut self,
+    operations: DatabaseHistoryOperations,
+    inverted_operations: DatabaseHistoryOperations,
+  ) {
+    if self.untracked_depth > 0 {
+      if let Err(e) = self.transform_items(operations) {
+        tracing::error!("Transform the database history failed: {:?}", e);
+        self.clear();
+      }
+    } else if self.action_depth == 0 {
+      self.record(DatabaseHistoryItem {
+        operations: vec![inverted_operations],
+      });
+    } else {
+      self.pending.operations.push(inverted_operations);
+    }
+  }
+
+  pub fn add_undo(&mut self, item: DatabaseHistoryItem) {
+    self.undoes.push(item);
+    if self.undoes.len() > self.capacity {
+      self.undoes.remove(0);
+    }
+  }
+
+  pub fn add_redo(&mut self, item: DatabaseHistoryItem) {
+    self.redoes.push(item);
+  }
+
+  pub fn record(&mut self, item: DatabaseHistoryItem) {
+    if item.is_empty() {
+      return;
+    }
+
+    self.redoes.clear();
+    self.add_undo(item);
+  }
+
+  /// Transforms the recorded items against the operations that were applied without being
+  /// recorded, so the items can still be applied to the changed database.
+  fn transform_items(&mut self, operations: DatabaseHistoryOperations) -> Result<(), OTError> {
+    // The pending item is undone first, then the undo items from the newest to the oldest.
+    let mut other = transform_item(&mut self.pending, operations.clone())?;
+    for item in self.undoes.iter_mut().rev() {
+      other = transform_item(item, other)?;
+    }
+
+    let mut other = operations;
+    for item in self.redoes.iter_mut().rev() {
+      other = transform_item(item, other)?;
+    }
+    Ok(())
+  }
+
+  pub fn undo(&mut self) -> Option<DatabaseHistoryItem> {
+    self.undoes.pop()
+  }
+
+  pub fn redo(&mut self) -> Option<DatabaseHistoryItem> {
+    self.redoes.pop()
+  }
+
+  /// Removes all the undo and redo items. It should be called when the database is replaced,
+  /// because the recorded operations can't be applied to it anymore.
+  pub fn clear(&mut self) {
+    self.undoes.clear();
+    self.redoes.clear();
+    self.pending = DatabaseHistoryItem::default();
+  }
+}
+
+/// Transforms the operations of the item that change the same object as `other`. `other` is
+/// applied to the object before the item. Returns the `other` that is transformed to be applied
+/// after the item.
+fn transform_item(
+  item: &mut DatabaseHistoryItem,
+  other: DatabaseHistoryOperations,
+) -> Result<DatabaseHistoryOperations, OTError> {
+  // The operations of the item are applied in reverse order, so they are composed in reverse.
+  let mut composed: Option<(usize, DatabaseOperations)> = None;
+  for (index, operations) in item.operations.iter().enumerate().rev() {
+    if !operations.is_same_object(&other) {
+      continue;
+    }
+    composed = match composed {
+      None => Some((index, operations.operations().clone())),
+      Some((_, composed)) => Some((index, composed.compose(operations.operations())?)),
+    };
+  }
+
+  let (first_index, composed) = match composed {
+    None => return Ok(other),
+    Some(composed) => composed,
+  };
+  let (item_operations, other_operations) = composed.transform(other.operations())?;
+  let old_operations = std::mem::take(&mut item.operations);
+  for (index, operations) in old_operations.into_iter().enumerate() {
+    if index == first_index {
+      item
+        .operations
+        .push(operations.with_operations(item_operations.clone()));
+    } else if !operations.is_same_object(&other) {
+      item.operations.push(operations);
+    }
+  }
+  Ok(other.with_operations(other_operations))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::client_database::{
+    DatabaseBlockOperations, DatabaseHistory, DatabaseHistoryItem, DatabaseHistoryOperations,
+    DatabaseOperations, DatabaseOperationsBuilder,
+  };
+
+  fn database_operations() -> DatabaseHistoryOperations {
+    DatabaseHistoryOperations::Database(DatabaseOperations::default())
+  }
+
+  fn block_operations(block_id: &str) -> DatabaseHistoryOperations {
+    DatabaseHistoryOperations::Block {
+      block_id: block_id.to_string(),
+      operations: DatabaseBlockOperations::default(),
+    }
+  }
+
+  #[test]
+  fn database_history_group_action_test() {
+    let mut history = DatabaseHistory::new();
+    history.begin_action();
+    history.record_operations(database_operations(), database_operations());
+    history.begin_action();
+    history.record_operations(block_operations("1"), block_operations("1"));
+    history.end_action();
+    assert!(!history.can_undo());
+
+    history.end_action();
+    let item = history.undo().unwrap();
+    assert_eq!(item.operations.len(), 2);
+    assert!(!history.can_undo());
+  }
+
+  #[test]
+  fn database_history_record_clear_redoes_test() {
+    let mut history = DatabaseHistory::new();
+    history.record_operations(database_operations(), database_operations());
+    let item = history.undo().unwrap();
+    history.add_redo(item);
+    assert!(history.can_redo());
+
+    history.record_operations(block_operations("1"), block_operations("1"));
+    assert!(!history.can_redo());
+    assert!(history.can_undo());
+  }
+
+  #[test]
+  fn database_history_capacity_test() {
+    let mut history = DatabaseHistory::new();
+    for _ in 0..30 {
+      history.record_operations(database_operations(), database_operations());
+    }
+
+    let mut count = 0;
+    while history.undo().is_some() {
+      count += 1;
+    }
+    assert_eq!(count, 20);
+  }
+
+  #[test]
+  fn database_history_add_undo_capacity_test() {
+    let mut history = DatabaseHistory::new();
+    for _ in 0..30 {
+      history.add_undo(DatabaseHistoryItem {
+        operations: vec![database_operations()],
+      });
+    }
+
+    let mut count = 0;
+    while history.undo().is_some() {
+      count += 1;
+    }
+    assert_eq!(count, 20);
+  }
+
+  #[test]
+  fn database_history_untracked_change_test() {
+    // "ab" -> "abc"
+    let mut history = DatabaseHistory::new();
+    history.record_operations(
+      DatabaseHistoryOperations::Database(
+        DatabaseOperationsBuilder::new()
+          .retain(2)
+          .insert("c")
+          .build(),
+      ),
+      DatabaseHistoryOperations::Database(
+        DatabaseOperationsBuilder::new().retain(2).delete(1).build(),
+      ),
+    );
+
+    // "abc" -> "Xabc"
+    history.begin_untracked();
+    history.record_operations(
+      DatabaseHistoryOperations::Database(
+        DatabaseOperationsBuilder::new()
+          .insert("X")
+          .retain(3)
+          .build(),
+      ),
+      DatabaseHistoryOperations::Database(
+        DatabaseOperationsBuilder::new().delete(1).retain(3).build(),
+      ),
+    );
+    history.end_untracked();
+
+    let item = history.undo().unwrap();
+    assert_eq!(item.operations.len(), 1);
+    match &item.operations[0] {
+      DatabaseHistoryOperations::Database(operations) => {
+        assert_eq!(operations.apply("Xabc").unwrap(), "Xab");
+      },
+      _ => panic!("The undo item should revert the database"),
+    }
+    assert!(!history.can_undo());
+  }
+}
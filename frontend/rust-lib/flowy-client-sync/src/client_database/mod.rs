@@ -2,8 +2,10 @@ mod block_revision_pad;
 mod database_builder;
 mod database_revision_pad;
 mod database_view_revision_pad;
+mod history;
 
 pub use block_revision_pad::*;
 pub use database_builder::*;
 pub use database_revision_pad::*;
 pub use database_view_revision_pad::*;
+pub use history::*;
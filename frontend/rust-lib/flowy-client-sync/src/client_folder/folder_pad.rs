@@ -78,6 +78,25 @@ impl FolderPad {
     self.reset_folder(composed_operations)
   }
 
+  /// Replaces the workspaces and trash with the other's. Unlike [reset_folder], the change is
+  /// returned as the operations that transform the current content into the other's, so it
+  /// can be saved as a new revision.
+  pub fn reset_folder_rev(&mut self, other: &FolderPad) -> SyncResult<Option<FolderChangeset>> {
+    let old = self.to_json()?;
+    let new = other.to_json()?;
+    match cal_diff::<EmptyAttributes>(old, new) {
+      None => Ok(None),
+      Some(operations) => {
+        self.operations = self.operations.compose(&operations)?;
+        self.folder_rev = other.folder_rev.clone();
+        Ok(Some(FolderChangeset {
+          operations,
+          md5: self.folder_md5(),
+        }))
+      },
+    }
+  }
+
   pub fn is_empty(&self) -> bool {
     self.folder_rev.workspaces.is_empty() && self.folder_rev.trash.is_empty()
   }
@@ -224,15 +243,16 @@ impl FolderPad {
     })
   }
 
+  /// Creates the view under its `belong_to_id`, which is the id of an app or a view.
   #[tracing::instrument(level = "trace", skip(self), fields(view_name=%view_rev.name), err)]
   pub fn create_view(&mut self, view_rev: ViewRevision) -> SyncResult<Option<FolderChangeset>> {
-    let app_id = view_rev.app_id.clone();
-    self.with_app(&app_id, move |app| {
-      if app.belongings.contains(&view_rev) {
+    let belong_to_id = view_rev.app_id.clone();
+    self.with_belongings(&belong_to_id, move |belongings| {
+      if belongings.contains(&view_rev) {
         tracing::warn!("[RootFolder]: Duplicate view");
         return Ok(None);
       }
-      app.belongings.push(view_rev);
+      belongings.push(view_rev);
       Ok(Some(()))
     })
   }
@@ -240,7 +260,7 @@ impl FolderPad {
   pub fn read_view(&self, view_id: &str) -> SyncResult<ViewRevision> {
     for workspace in &self.folder_rev.workspaces {
       for app in &(*workspace.apps) {
-        if let Some(view) = app.belongings.iter().find(|b| b.id == view_id) {
+        if let Some(view) = find_view(&app.belongings, view_id) {
           return Ok(view.clone());
         }
       }
@@ -248,12 +268,17 @@ impl FolderPad {
     Err(SyncError::record_not_found().context(format!("Can't find view with id {}", view_id)))
   }
 
+  /// Returns the views that belong to the app or the view with the `belong_to_id`.
   pub fn read_views(&self, belong_to_id: &str) -> SyncResult<Vec<ViewRevision>> {
     for workspace in &self.folder_rev.workspaces {
       for app in &(*workspace.apps) {
         if app.id == belong_to_id {
           return Ok(app.belongings.to_vec());
         }
+
+        if let Some(view) = find_view(&app.belongings, belong_to_id) {
+          return Ok(view.belongings.to_vec());
+        }
       }
     }
     Ok(vec![])
@@ -284,11 +309,11 @@ impl FolderPad {
   #[tracing::instrument(level = "trace", skip(self), err)]
   pub fn delete_view(
     &mut self,
-    app_id: &str,
+    belong_to_id: &str,
     view_id: &str,
   ) -> SyncResult<Option<FolderChangeset>> {
-    self.with_app(app_id, |app| {
-      app.belongings.retain(|view| view.id != view_id);
+    self.with_belongings(belong_to_id, |belongings| {
+      belongings.retain(|view| view.id != view_id);
       Ok(Some(()))
     })
   }
@@ -301,8 +326,8 @@ impl FolderPad {
     to: usize,
   ) -> SyncResult<Option<FolderChangeset>> {
     let view = self.read_view(view_id)?;
-    self.with_app(&view.app_id, |app| {
-      match move_vec_element(&mut app.belongings, |view| view.id == view_id, from, to)
+    self.with_belongings(&view.app_id, |belongings| {
+      match move_vec_element(belongings, |view| view.id == view_id, from, to)
         .map_err(internal_sync_error)?
       {
         true => Ok(Some(())),
@@ -311,6 +336,55 @@ impl FolderPad {
     })
   }
 
+  /// Moves the view to the app or the view with the `to_belong_to_id`. The view is inserted at
+  /// the `index` of the new parent's belongings, or appended if the index is out of bounds.
+  ///
+  /// Returns error if the new parent doesn't exist, or it's the view itself or one of the
+  /// view's descendants.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub fn move_view_to(
+    &mut self,
+    view_id: &str,
+    to_belong_to_id: &str,
+    index: usize,
+  ) -> SyncResult<Option<FolderChangeset>> {
+    let view = self.read_view(view_id)?;
+    if view.id == to_belong_to_id || find_view(&view.belongings, to_belong_to_id).is_some() {
+      return Err(SyncError::internal().context(format!(
+        "Can't move the view {} into itself or its descendants",
+        view_id
+      )));
+    }
+
+    if self.read_app(to_belong_to_id).is_err() && self.read_view(to_belong_to_id).is_err() {
+      return Err(SyncError::record_not_found().context(format!(
+        "Can't find app or view with id {}",
+        to_belong_to_id
+      )));
+    }
+
+    let from_belong_to_id = view.app_id.clone();
+    self.modify_workspaces(|workspaces| {
+      let mut view = match find_belongings_mut(workspaces, &from_belong_to_id) {
+        None => return Ok(None),
+        Some(belongings) => match belongings.iter().position(|view| view.id == view_id) {
+          None => return Ok(None),
+          Some(position) => belongings.remove(position),
+        },
+      };
+
+      view.app_id = to_belong_to_id.to_owned();
+      match find_belongings_mut(workspaces, to_belong_to_id) {
+        None => Ok(None),
+        Some(belongings) => {
+          let index = index.min(belongings.len());
+          belongings.insert(index, view);
+          Ok(Some(()))
+        },
+      }
+    })
+  }
+
   pub fn create_trash(&mut self, trash: Vec<TrashRevision>) -> SyncResult<Option<FolderChangeset>> {
     self.with_trash(|original_trash| {
       let mut new_trash = trash
@@ -491,8 +565,8 @@ impl FolderPad {
   where
     F: FnOnce(&mut ViewRevision) -> SyncResult<Option<()>>,
   {
-    self.with_app(belong_to_id, |app| {
-      match app.belongings.iter_mut().find(|view| view_id == view.id) {
+    self.with_belongings(belong_to_id, |belongings| {
+      match belongings.iter_mut().find(|view| view_id == view.id) {
         None => {
           tracing::warn!("[FolderPad]: Can't find any view with id: {}", view_id);
           Ok(None)
@@ -501,6 +575,73 @@ impl FolderPad {
       }
     })
   }
+
+  /// Calls the `f` with the belongings of the app or the view with the `belong_to_id`.
+  fn with_belongings<F>(&mut self, belong_to_id: &str, f: F) -> SyncResult<Option<FolderChangeset>>
+  where
+    F: FnOnce(&mut Vec<ViewRevision>) -> SyncResult<Option<()>>,
+  {
+    self.modify_workspaces(
+      |workspaces| match find_belongings_mut(workspaces, belong_to_id) {
+        None => {
+          tracing::warn!(
+            "[FolderPad]: Can't find any app or view with id: {}",
+            belong_to_id
+          );
+          Ok(None)
+        },
+        Some(belongings) => f(belongings),
+      },
+    )
+  }
+}
+
+fn find_view<'a>(views: &'a [ViewRevision], view_id: &str) -> Option<&'a ViewRevision> {
+  for view in views {
+    if view.id == view_id {
+      return Some(view);
+    }
+
+    if let Some(view) = find_view(&view.belongings, view_id) {
+      return Some(view);
+    }
+  }
+  None
+}
+
+fn find_view_belongings_mut<'a>(
+  views: &'a mut [ViewRevision],
+  belong_to_id: &str,
+) -> Option<&'a mut Vec<ViewRevision>> {
+  for view in views.iter_mut() {
+    if view.id == belong_to_id {
+      return Some(&mut view.belongings);
+    }
+
+    if let Some(belongings) = find_view_belongings_mut(&mut view.belongings, belong_to_id) {
+      return Some(belongings);
+    }
+  }
+  None
+}
+
+/// Returns the belongings of the app or the view with the `belong_to_id`.
+fn find_belongings_mut<'a>(
+  workspaces: &'a mut [Arc<WorkspaceRevision>],
+  belong_to_id: &str,
+) -> Option<&'a mut Vec<ViewRevision>> {
+  for workspace in workspaces.iter_mut() {
+    for app in Arc::make_mut(workspace).apps.iter_mut() {
+      if app.id == belong_to_id {
+        return Some(&mut app.belongings);
+      }
+
+      if let Some(belongings) = find_view_belongings_mut(&mut app.belongings, belong_to_id) {
+        return Some(belongings);
+      }
+    }
+  }
+  None
 }
 
 pub fn default_folder_operations() -> FolderOperations {
@@ -875,6 +1016,94 @@ mod tests {
     );
   }
 
+  #[test]
+  fn folder_reset_folder_rev() {
+    let (mut folder, initial_operations, workspace) = test_folder();
+    let snapshot = folder.clone();
+    let operations_1 = folder
+      .delete_workspace(&workspace.id)
+      .unwrap()
+      .unwrap()
+      .operations;
+    let operations_2 = folder
+      .reset_folder_rev(&snapshot)
+      .unwrap()
+      .unwrap()
+      .operations;
+    assert_eq!(folder, snapshot);
+    assert_eq!(
+      folder,
+      make_folder_from_operations(initial_operations, vec![operations_1, operations_2])
+    );
+  }
+
+  #[test]
+  fn folder_move_view_to_other_app() {
+    let (mut folder, initial_operations, view) = test_view_folder();
+    let mut app_rev = AppRevision::default();
+    app_rev.id = "2".to_owned();
+    app_rev.workspace_id = "1".to_owned();
+    let operations_1 = folder.create_app(app_rev).unwrap().unwrap().operations;
+    let operations_2 = folder
+      .move_view_to(&view.id, "2", 0)
+      .unwrap()
+      .unwrap()
+      .operations;
+
+    assert!(folder.read_views(&view.app_id).unwrap().is_empty());
+    let views = folder.read_views("2").unwrap();
+    assert_eq!(views.len(), 1);
+    assert_eq!(views[0].app_id, "2");
+    assert_eq!(
+      folder,
+      make_folder_from_operations(initial_operations, vec![operations_1, operations_2])
+    );
+  }
+
+  #[test]
+  fn folder_move_view_into_view() {
+    let (mut folder, _, view) = test_view_folder();
+    let mut parent_view = ViewRevision::default();
+    parent_view.id = "parent".to_owned();
+    parent_view.app_id = view.app_id.clone();
+    folder.create_view(parent_view).unwrap();
+
+    folder.move_view_to(&view.id, "parent", 10).unwrap();
+    let moved_view = folder.read_view(&view.id).unwrap();
+    assert_eq!(moved_view.app_id, "parent");
+    assert_eq!(folder.read_views("parent").unwrap().len(), 1);
+    assert_eq!(folder.read_views(&view.app_id).unwrap().len(), 1);
+
+    // Views can be updated and moved back after being nested.
+    folder
+      .update_view(&view.id, Some("nested".to_owned()), None, 1)
+      .unwrap()
+      .unwrap();
+    assert_eq!(folder.read_view(&view.id).unwrap().name, "nested");
+    folder.move_view_to(&view.id, &view.app_id, 0).unwrap();
+    assert_eq!(folder.read_views(&view.app_id).unwrap().len(), 2);
+    assert!(folder.read_views("parent").unwrap().is_empty());
+  }
+
+  #[test]
+  fn folder_move_view_into_descendant_fail() {
+    let (mut folder, _, view) = test_view_folder();
+    let mut parent_view = ViewRevision::default();
+    parent_view.id = "parent".to_owned();
+    parent_view.app_id = view.app_id.clone();
+    folder.create_view(parent_view).unwrap();
+
+    let mut child_view = ViewRevision::default();
+    child_view.id = "child".to_owned();
+    child_view.app_id = "parent".to_owned();
+    folder.create_view(child_view).unwrap();
+
+    assert!(folder.move_view_to("parent", "parent", 0).is_err());
+    assert!(folder.move_view_to("parent", "child", 0).is_err());
+    assert!(folder.move_view_to("parent", "unknown", 0).is_err());
+    assert_eq!(folder.read_view("child").unwrap().app_id, "parent");
+  }
+
   fn test_folder() -> (FolderPad, FolderOperations, WorkspaceRevision) {
     let folder_rev = FolderRevision::default();
     let folder_json = serde_json::to_string(&folder_rev).unwrap();
@@ -4,11 +4,12 @@ use flowy_sqlite::ConnectionPool;
 use database_model::BuildDatabaseContext;
 use flowy_client_ws::FlowyWebSocketConnect;
 use flowy_database::entities::LayoutTypePB;
-use flowy_database::manager::{make_database_view_data, DatabaseManager};
+use flowy_database::manager::{make_database_view_data, DatabaseFolderDelegate, DatabaseManager};
+use flowy_database::services::database::remap_database_build_context;
 use flowy_database::util::{make_default_board, make_default_calendar, make_default_grid};
-use flowy_document::editor::make_transaction_from_document_content;
-use flowy_document::DocumentManager;
-use flowy_folder::entities::{ViewDataFormatPB, ViewLayoutTypePB, ViewPB};
+use flowy_document::editor::{make_transaction_from_document_content, remap_document_links};
+use flowy_document::{DocumentFolderDelegate, DocumentManager};
+use flowy_folder::entities::{CreateViewParams, ViewDataFormatPB, ViewLayoutTypePB};
 use flowy_folder::manager::{ViewDataProcessor, ViewDataProcessorMap};
 use flowy_folder::{
   errors::{internal_error, FlowyError},
@@ -19,13 +20,18 @@ use flowy_net::ClientServerConfiguration;
 use flowy_net::{http_server::folder::FolderHttpCloudService, local_server::LocalServer};
 use flowy_revision::{RevisionWebSocket, WSStateReceiver};
 use flowy_user::services::UserSession;
+use folder_model::gen_view_id;
 use futures_core::future::BoxFuture;
+use lib_infra::async_trait::async_trait;
 use lib_infra::future::{BoxResultFuture, FutureResult};
 use lib_ws::{WSChannel, WSMessageReceiver, WebSocketRawMessage};
 use revision_model::Revision;
 use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::{convert::TryInto, sync::Arc};
+use std::{
+  convert::TryInto,
+  sync::{Arc, Weak},
+};
 use ws_model::ws_revision::ClientRevisionWSData;
 
 pub struct FolderDepsResolver();
@@ -59,6 +65,15 @@ impl FolderDepsResolver {
       .await,
     );
 
+    database_manager.set_folder_delegate(Arc::new(DatabaseFolderDelegateImpl(Arc::downgrade(
+      &folder_manager,
+    ))));
+    text_block_manager
+      .set_folder_delegate(Arc::new(DocumentFolderDelegateImpl(Arc::downgrade(
+        &folder_manager,
+      ))))
+      .await;
+
     if let (Ok(user_id), Ok(token)) = (user.user_id(), user.token()) {
       match folder_manager.initialize(&user_id, &token).await {
         Ok(_) => {},
@@ -97,6 +112,64 @@ fn make_view_data_processor(
   Arc::new(map)
 }
 
+struct DatabaseFolderDelegateImpl(Weak<FolderManager>);
+
+#[async_trait]
+impl DatabaseFolderDelegate for DatabaseFolderDelegateImpl {
+  async fn create_grid_view(
+    &self,
+    belong_to_id: &str,
+    name: &str,
+    build_context: BuildDatabaseContext,
+  ) -> Result<String, FlowyError> {
+    let folder_manager = self
+      .0
+      .upgrade()
+      .ok_or_else(|| FlowyError::internal().context("The folder manager was dropped"))?;
+    let params = CreateViewParams {
+      belong_to_id: belong_to_id.to_owned(),
+      name: name.to_owned(),
+      desc: "".to_owned(),
+      thumbnail: "".to_owned(),
+      data_format: ViewDataFormatPB::DatabaseFormat,
+      layout: ViewLayoutTypePB::Grid,
+      view_id: gen_view_id(),
+      initial_data: Bytes::from(build_context).to_vec(),
+    };
+    let view_rev = folder_manager.create_view_with_params(params).await?;
+    Ok(view_rev.id)
+  }
+}
+
+struct DocumentFolderDelegateImpl(Weak<FolderManager>);
+
+#[async_trait]
+impl DocumentFolderDelegate for DocumentFolderDelegateImpl {
+  async fn create_document_view(
+    &self,
+    belong_to_id: &str,
+    name: &str,
+    document_content: String,
+  ) -> Result<String, FlowyError> {
+    let folder_manager = self
+      .0
+      .upgrade()
+      .ok_or_else(|| FlowyError::internal().context("The folder manager was dropped"))?;
+    let params = CreateViewParams {
+      belong_to_id: belong_to_id.to_owned(),
+      name: name.to_owned(),
+      desc: "".to_owned(),
+      thumbnail: "".to_owned(),
+      data_format: ViewDataFormatPB::NodeFormat,
+      layout: ViewLayoutTypePB::Document,
+      view_id: gen_view_id(),
+      initial_data: document_content.into_bytes(),
+    };
+    let view_rev = folder_manager.create_view_with_params(params).await?;
+    Ok(view_rev.id)
+  }
+}
+
 struct WorkspaceDatabaseImpl(Arc<UserSession>);
 impl WorkspaceDatabase for WorkspaceDatabaseImpl {
   fn db_pool(&self) -> Result<Arc<ConnectionPool>, FlowyError> {
@@ -202,13 +275,29 @@ impl ViewDataProcessor for DocumentViewDataProcessor {
     })
   }
 
-  fn get_view_data(&self, view: &ViewPB) -> FutureResult<Bytes, FlowyError> {
-    let view_id = view.id.clone();
+  fn duplicate_view_data(
+    &self,
+    view_id: &str,
+    id_map: HashMap<String, String>,
+  ) -> FutureResult<(Bytes, HashMap<String, String>), FlowyError> {
+    let view_id = view_id.to_string();
     let manager = self.0.clone();
     FutureResult::new(async move {
       let editor = manager.open_document_editor(view_id).await?;
-      let document_data = Bytes::from(editor.duplicate().await?);
-      Ok(document_data)
+      let document_content = remap_document_links(&editor.duplicate().await?, &id_map);
+      Ok((Bytes::from(document_content), id_map))
+    })
+  }
+
+  fn import_view_data(
+    &self,
+    data: Bytes,
+    id_map: HashMap<String, String>,
+  ) -> FutureResult<(Bytes, HashMap<String, String>), FlowyError> {
+    FutureResult::new(async move {
+      let document_content = String::from_utf8(data.to_vec()).map_err(internal_error)?;
+      let document_content = remap_document_links(&document_content, &id_map);
+      Ok((Bytes::from(document_content), id_map))
     })
   }
 
@@ -277,13 +366,29 @@ impl ViewDataProcessor for GridViewDataProcessor {
     })
   }
 
-  fn get_view_data(&self, view: &ViewPB) -> FutureResult<Bytes, FlowyError> {
+  fn duplicate_view_data(
+    &self,
+    view_id: &str,
+    mut id_map: HashMap<String, String>,
+  ) -> FutureResult<(Bytes, HashMap<String, String>), FlowyError> {
     let database_manager = self.0.clone();
-    let view_id = view.id.clone();
+    let view_id = view_id.to_string();
     FutureResult::new(async move {
       let editor = database_manager.open_database(view_id).await?;
-      let delta_bytes = editor.duplicate_database().await?;
-      Ok(delta_bytes.into())
+      let delta_bytes = editor.duplicate_database(&mut id_map).await?;
+      Ok((delta_bytes.into(), id_map))
+    })
+  }
+
+  fn import_view_data(
+    &self,
+    data: Bytes,
+    mut id_map: HashMap<String, String>,
+  ) -> FutureResult<(Bytes, HashMap<String, String>), FlowyError> {
+    FutureResult::new(async move {
+      let build_context = BuildDatabaseContext::try_from(data)?;
+      let build_context = remap_database_build_context(build_context, &mut id_map)?;
+      Ok((build_context.into(), id_map))
     })
   }
 
@@ -9,7 +9,7 @@ use flowy_task::TaskDispatcher;
 use flowy_user::services::UserSession;
 use futures_core::future::BoxFuture;
 use lib_infra::future::BoxResultFuture;
-use lib_ws::{WSChannel, WebSocketRawMessage};
+use lib_ws::{WSChannel, WSMessageReceiver, WebSocketRawMessage};
 use std::convert::TryInto;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -24,13 +24,15 @@ impl GridDepsResolver {
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
   ) -> Arc<DatabaseManager> {
     let user = Arc::new(GridUserImpl(user_session.clone()));
-    let rev_web_socket = Arc::new(GridRevisionWebSocket(ws_conn));
-    let grid_manager = Arc::new(DatabaseManager::new(
+    let rev_web_socket = Arc::new(GridRevisionWebSocket(ws_conn.clone()));
+    let grid_manager = DatabaseManager::new(
       user.clone(),
       rev_web_socket,
       task_scheduler,
       Arc::new(GridDatabaseImpl(user_session)),
-    ));
+    );
+    let receiver = Arc::new(GridWSMessageReceiverImpl(grid_manager.clone()));
+    ws_conn.add_ws_message_receiver(receiver).unwrap();
 
     if let (Ok(user_id), Ok(token)) = (user.user_id(), user.token()) {
       match grid_manager.initialize(&user_id, &token).await {
@@ -96,3 +98,16 @@ impl RevisionWebSocket for GridRevisionWebSocket {
     Box::pin(async move { ws_conn.subscribe_websocket_state().await })
   }
 }
+
+struct GridWSMessageReceiverImpl(Arc<DatabaseManager>);
+impl WSMessageReceiver for GridWSMessageReceiverImpl {
+  fn source(&self) -> WSChannel {
+    WSChannel::Database
+  }
+  fn receive_message(&self, msg: WebSocketRawMessage) {
+    let handler = self.0.clone();
+    tokio::spawn(async move {
+      handler.receive_ws_data(Bytes::from(msg.data)).await;
+    });
+  }
+}
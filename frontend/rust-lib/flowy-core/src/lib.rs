@@ -42,6 +42,9 @@ pub struct AppFlowyCoreConfig {
   log_filter: String,
   server_config: ClientServerConfiguration,
   pub document: DocumentConfig,
+  /// The passphrase that the key encrypting the user secrets is derived from. The key is kept in
+  /// a keyfile under the `storage_path` if it's None.
+  secret_passphrase: Option<String>,
 }
 
 impl fmt::Debug for AppFlowyCoreConfig {
@@ -62,14 +65,24 @@ impl AppFlowyCoreConfig {
       log_filter: create_log_filter("info".to_owned(), vec![]),
       server_config,
       document: DocumentConfig::default(),
+      secret_passphrase: None,
     }
   }
 
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
   pub fn with_document_version(mut self, version: DocumentVersionPB) -> Self {
     self.document.version = version;
     self
   }
 
+  pub fn with_secret_passphrase(mut self, passphrase: &str) -> Self {
+    self.secret_passphrase = Some(passphrase.to_owned());
+    self
+  }
+
   pub fn log_filter(mut self, level: &str, with_crates: Vec<String>) -> Self {
     self.log_filter = create_log_filter(level.to_owned(), with_crates);
     self
@@ -121,6 +134,16 @@ pub struct AppFlowyCore {
 
 impl AppFlowyCore {
   pub fn new(config: AppFlowyCoreConfig) -> Self {
+    Self::build(config, None)
+  }
+
+  /// Creates the [AppFlowyCore] that connects to the `local_server` of another [AppFlowyCore]
+  /// instead of running its own, so the revisions of both are synced through the same server.
+  pub fn connect_to(config: AppFlowyCoreConfig, local_server: Arc<LocalServer>) -> Self {
+    Self::build(config, Some(local_server))
+  }
+
+  fn build(config: AppFlowyCoreConfig, shared_local_server: Option<Arc<LocalServer>>) -> Self {
     init_log(&config);
     init_kv(&config.storage_path);
     tracing::debug!("🔥 {:?}", config);
@@ -129,7 +152,17 @@ impl AppFlowyCore {
     let task_dispatcher = Arc::new(RwLock::new(task_scheduler));
     runtime.spawn(TaskRunner::run(task_dispatcher.clone()));
 
-    let (local_server, ws_conn) = mk_local_server(&config.server_config);
+    let is_shared_local_server = shared_local_server.is_some();
+    let (local_server, ws_conn) = match shared_local_server {
+      None => mk_local_server(&config.server_config, &config.storage_path),
+      Some(local_server) => {
+        let _guard = runtime.enter();
+        let local_ws = Arc::new(local_server.connect());
+        let ws_addr = config.server_config.ws_addr();
+        let ws_conn = Arc::new(FlowyWebSocketConnect::from_local(ws_addr, local_ws));
+        (Some(local_server), ws_conn)
+      },
+    };
     let (user_session, document_manager, folder_manager, local_server, grid_manager) = runtime
       .block_on(async {
         let user_session = mk_user_session(&config, &local_server, &config.server_config);
@@ -159,7 +192,9 @@ impl AppFlowyCore {
         .await;
 
         if let Some(local_server) = local_server.as_ref() {
-          local_server.run();
+          if !is_shared_local_server {
+            local_server.run();
+          }
         }
         ws_conn.init().await;
         (
@@ -236,13 +271,14 @@ fn _start_listening(
 
 fn mk_local_server(
   server_config: &ClientServerConfiguration,
+  storage_path: &str,
 ) -> (Option<Arc<LocalServer>>, Arc<FlowyWebSocketConnect>) {
   let ws_addr = server_config.ws_addr();
   if cfg!(feature = "http_sync") {
     let ws_conn = Arc::new(FlowyWebSocketConnect::new(ws_addr));
     (None, ws_conn)
   } else {
-    let context = flowy_net::local_server::build_server(server_config);
+    let context = flowy_net::local_server::build_server(server_config, storage_path);
     let local_ws = Arc::new(context.local_ws);
     let ws_conn = Arc::new(FlowyWebSocketConnect::from_local(ws_addr, local_ws));
     (Some(Arc::new(context.local_server)), ws_conn)
@@ -280,7 +316,10 @@ fn mk_user_session(
   local_server: &Option<Arc<LocalServer>>,
   server_config: &ClientServerConfiguration,
 ) -> Arc<UserSession> {
-  let user_config = UserSessionConfig::new(&config.name, &config.storage_path);
+  let mut user_config = UserSessionConfig::new(&config.name, &config.storage_path);
+  if let Some(passphrase) = &config.secret_passphrase {
+    user_config = user_config.with_passphrase(passphrase);
+  }
   let cloud_service = UserDepsResolver::resolve(local_server, server_config);
   Arc::new(UserSession::new(user_config, cloud_service))
 }
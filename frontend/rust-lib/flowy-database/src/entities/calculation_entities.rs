@@ -0,0 +1,257 @@
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::FieldType;
+use std::sync::Arc;
+
+use database_model::{CalculationRevision, CalculationType, FieldTypeRevision};
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct CalculationPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub field_type: FieldType,
+
+  #[pb(index = 4)]
+  pub calculation_type: CalculationTypePB,
+}
+
+impl std::convert::From<&CalculationRevision> for CalculationPB {
+  fn from(calculation_rev: &CalculationRevision) -> Self {
+    Self {
+      id: calculation_rev.id.clone(),
+      field_id: calculation_rev.field_id.clone(),
+      field_type: calculation_rev.field_type.into(),
+      calculation_type: calculation_rev.calculation_type.into(),
+    }
+  }
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct RepeatedCalculationPB {
+  #[pb(index = 1)]
+  pub items: Vec<CalculationPB>,
+}
+
+impl std::convert::From<Vec<Arc<CalculationRevision>>> for RepeatedCalculationPB {
+  fn from(revs: Vec<Arc<CalculationRevision>>) -> Self {
+    RepeatedCalculationPB {
+      items: revs.into_iter().map(|rev| rev.as_ref().into()).collect(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum CalculationTypePB {
+  /// The number of the rows.
+  Count = 0,
+  /// The number of the rows whose cell is empty.
+  CountEmpty = 1,
+  /// The number of the distinct non-empty values.
+  CountUnique = 2,
+  Sum = 3,
+  Average = 4,
+  Median = 5,
+  Min = 6,
+  Max = 7,
+  EarliestDate = 8,
+  LatestDate = 9,
+  /// The percentage of the checked cells.
+  PercentChecked = 10,
+}
+
+impl std::default::Default for CalculationTypePB {
+  fn default() -> Self {
+    Self::Count
+  }
+}
+
+impl std::convert::From<CalculationType> for CalculationTypePB {
+  fn from(calculation_type: CalculationType) -> Self {
+    match calculation_type {
+      CalculationType::Count => CalculationTypePB::Count,
+      CalculationType::CountEmpty => CalculationTypePB::CountEmpty,
+      CalculationType::CountUnique => CalculationTypePB::CountUnique,
+      CalculationType::Sum => CalculationTypePB::Sum,
+      CalculationType::Average => CalculationTypePB::Average,
+      CalculationType::Median => CalculationTypePB::Median,
+      CalculationType::Min => CalculationTypePB::Min,
+      CalculationType::Max => CalculationTypePB::Max,
+      CalculationType::EarliestDate => CalculationTypePB::EarliestDate,
+      CalculationType::LatestDate => CalculationTypePB::LatestDate,
+      CalculationType::PercentChecked => CalculationTypePB::PercentChecked,
+    }
+  }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct AlterCalculationPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub field_type: FieldType,
+
+  /// Create a new calculation if the calculation_id is None
+  #[pb(index = 4, one_of)]
+  pub calculation_id: Option<String>,
+
+  #[pb(index = 5)]
+  pub calculation_type: CalculationTypePB,
+}
+
+impl TryInto<AlterCalculationParams> for AlterCalculationPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<AlterCalculationParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id)
+      .map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?
+      .0;
+
+    let field_id = NotEmptyStr::parse(self.field_id)
+      .map_err(|_| ErrorCode::FieldIdIsEmpty)?
+      .0;
+
+    let calculation_id = match self.calculation_id {
+      None => None,
+      Some(calculation_id) => Some(
+        NotEmptyStr::parse(calculation_id)
+          .map_err(|_| ErrorCode::CalculationIdIsEmpty)?
+          .0,
+      ),
+    };
+
+    Ok(AlterCalculationParams {
+      view_id,
+      field_id,
+      calculation_id,
+      field_type: self.field_type.into(),
+      calculation_type: self.calculation_type as u8,
+    })
+  }
+}
+
+#[derive(Debug)]
+pub struct AlterCalculationParams {
+  pub view_id: String,
+  pub field_id: String,
+  /// Create a new calculation if the calculation_id is None
+  pub calculation_id: Option<String>,
+  pub field_type: FieldTypeRevision,
+  pub calculation_type: u8,
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct DeleteCalculationPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub field_type: FieldType,
+
+  #[pb(index = 4)]
+  pub calculation_id: String,
+}
+
+impl TryInto<DeleteCalculationParams> for DeleteCalculationPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<DeleteCalculationParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id)
+      .map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?
+      .0;
+    let field_id = NotEmptyStr::parse(self.field_id)
+      .map_err(|_| ErrorCode::FieldIdIsEmpty)?
+      .0;
+
+    let calculation_id = NotEmptyStr::parse(self.calculation_id)
+      .map_err(|_| ErrorCode::CalculationIdIsEmpty)?
+      .0;
+
+    Ok(DeleteCalculationParams {
+      view_id,
+      field_id,
+      field_type: self.field_type,
+      calculation_id,
+    })
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteCalculationParams {
+  pub view_id: String,
+  pub field_id: String,
+  pub field_type: FieldType,
+  pub calculation_id: String,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct CalculationChangesetNotificationPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub insert_calculations: Vec<CalculationPB>,
+
+  #[pb(index = 3)]
+  pub delete_calculations: Vec<CalculationPB>,
+
+  #[pb(index = 4)]
+  pub update_calculations: Vec<CalculationPB>,
+}
+
+impl CalculationChangesetNotificationPB {
+  pub fn new(view_id: String) -> Self {
+    Self {
+      view_id,
+      insert_calculations: vec![],
+      delete_calculations: vec![],
+      update_calculations: vec![],
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.insert_calculations.is_empty()
+      && self.delete_calculations.is_empty()
+      && self.update_calculations.is_empty()
+  }
+}
+
+/// The calculated value of a [CalculationPB].
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct CalculationValuePB {
+  #[pb(index = 1)]
+  pub calculation_id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub calculation_type: CalculationTypePB,
+
+  /// The formatted value. It's empty if the calculation doesn't support the field type or
+  /// there is no value to calculate.
+  #[pb(index = 4)]
+  pub value: String,
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct RepeatedCalculationValuePB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub items: Vec<CalculationValuePB>,
+}
@@ -0,0 +1,167 @@
+use crate::entities::parser::NotEmptyStr;
+use database_model::CalendarLayoutSettingRevision;
+use flowy_derive::ProtoBuf;
+use flowy_error::ErrorCode;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, ProtoBuf)]
+pub struct CalendarLayoutSettingsPB {
+  /// The id of the date field that is used to place the rows in the calendar
+  #[pb(index = 1)]
+  pub layout_field_id: String,
+
+  #[pb(index = 2)]
+  pub first_day_of_week: i32,
+
+  #[pb(index = 3)]
+  pub show_weekends: bool,
+}
+
+impl std::convert::From<CalendarLayoutSettingRevision> for CalendarLayoutSettingsPB {
+  fn from(rev: CalendarLayoutSettingRevision) -> Self {
+    Self {
+      layout_field_id: rev.layout_field_id,
+      first_day_of_week: rev.first_day_of_week,
+      show_weekends: rev.show_weekends,
+    }
+  }
+}
+
+impl std::convert::From<CalendarLayoutSettingsPB> for CalendarLayoutSettingRevision {
+  fn from(setting: CalendarLayoutSettingsPB) -> Self {
+    Self {
+      layout_field_id: setting.layout_field_id,
+      first_day_of_week: setting.first_day_of_week,
+      show_weekends: setting.show_weekends,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct UpdateCalendarSettingPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub setting: CalendarLayoutSettingsPB,
+}
+
+pub struct UpdateCalendarSettingParams {
+  pub view_id: String,
+  pub setting: CalendarLayoutSettingRevision,
+}
+
+impl TryInto<UpdateCalendarSettingParams> for UpdateCalendarSettingPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<UpdateCalendarSettingParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?;
+    let _ = NotEmptyStr::parse(self.setting.layout_field_id.clone())
+      .map_err(|_| ErrorCode::FieldIdIsEmpty)?;
+
+    Ok(UpdateCalendarSettingParams {
+      view_id: view_id.0,
+      setting: self.setting.into(),
+    })
+  }
+}
+
+/// [CalendarEventRequestPB] is used to query the rows whose date falls in the range
+/// [start_timestamp, end_timestamp]. The timestamps are in seconds.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct CalendarEventRequestPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub start_timestamp: i64,
+
+  #[pb(index = 3)]
+  pub end_timestamp: i64,
+}
+
+pub struct CalendarEventRequestParams {
+  pub view_id: String,
+  pub start_timestamp: i64,
+  pub end_timestamp: i64,
+}
+
+impl TryInto<CalendarEventRequestParams> for CalendarEventRequestPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<CalendarEventRequestParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?;
+    if self.start_timestamp > self.end_timestamp {
+      return Err(ErrorCode::InvalidData);
+    }
+
+    Ok(CalendarEventRequestParams {
+      view_id: view_id.0,
+      start_timestamp: self.start_timestamp,
+      end_timestamp: self.end_timestamp,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct CalendarEventPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  /// The content of the primary field
+  #[pb(index = 2)]
+  pub title: String,
+
+  #[pb(index = 3)]
+  pub timestamp: i64,
+}
+
+/// [CalendarDayPB] contains the events of a day. The `timestamp` is the start of the day.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct CalendarDayPB {
+  #[pb(index = 1)]
+  pub timestamp: i64,
+
+  #[pb(index = 2)]
+  pub events: Vec<CalendarEventPB>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedCalendarDayPB {
+  #[pb(index = 1)]
+  pub items: Vec<CalendarDayPB>,
+}
+
+/// [MoveCalendarEventPB] is used to move the row to another date. The time of the day of the
+/// row is kept.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct MoveCalendarEventPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  pub timestamp: i64,
+}
+
+pub struct MoveCalendarEventParams {
+  pub view_id: String,
+  pub row_id: String,
+  pub timestamp: i64,
+}
+
+impl TryInto<MoveCalendarEventParams> for MoveCalendarEventPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<MoveCalendarEventParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?;
+    let row_id = NotEmptyStr::parse(self.row_id).map_err(|_| ErrorCode::RowIdIsEmpty)?;
+
+    Ok(MoveCalendarEventParams {
+      view_id: view_id.0,
+      row_id: row_id.0,
+      timestamp: self.timestamp,
+    })
+  }
+}
@@ -495,6 +495,9 @@ pub enum FieldType {
   Checkbox = 5,
   URL = 6,
   Checklist = 7,
+  Formula = 8,
+  Relation = 9,
+  Rollup = 10,
 }
 
 pub const RICH_TEXT_FIELD: FieldType = FieldType::RichText;
@@ -505,6 +508,9 @@ pub const MULTI_SELECT_FIELD: FieldType = FieldType::MultiSelect;
 pub const CHECKBOX_FIELD: FieldType = FieldType::Checkbox;
 pub const URL_FIELD: FieldType = FieldType::URL;
 pub const CHECKLIST_FIELD: FieldType = FieldType::Checklist;
+pub const FORMULA_FIELD: FieldType = FieldType::Formula;
+pub const RELATION_FIELD: FieldType = FieldType::Relation;
+pub const ROLLUP_FIELD: FieldType = FieldType::Rollup;
 
 impl std::default::Default for FieldType {
   fn default() -> Self {
@@ -572,6 +578,18 @@ impl FieldType {
     self == &CHECKLIST_FIELD
   }
 
+  pub fn is_formula(&self) -> bool {
+    self == &FORMULA_FIELD
+  }
+
+  pub fn is_relation(&self) -> bool {
+    self == &RELATION_FIELD
+  }
+
+  pub fn is_rollup(&self) -> bool {
+    self == &ROLLUP_FIELD
+  }
+
   pub fn can_be_group(&self) -> bool {
     self.is_select_option() || self.is_checkbox()
   }
@@ -606,6 +624,9 @@ impl std::convert::From<FieldTypeRevision> for FieldType {
       5 => FieldType::Checkbox,
       6 => FieldType::URL,
       7 => FieldType::Checklist,
+      8 => FieldType::Formula,
+      9 => FieldType::Relation,
+      10 => FieldType::Rollup,
       _ => {
         tracing::error!("Can't convert FieldTypeRevision: {} to FieldType", ty);
         FieldType::RichText
@@ -18,6 +18,11 @@ pub struct DateFilterPB {
 
   #[pb(index = 4, one_of)]
   pub timestamp: Option<i64>,
+
+  /// The number of days used by the [DateFilterConditionPB::DateWithinPastDays] and the
+  /// [DateFilterConditionPB::DateWithinNextDays].
+  #[pb(index = 5, one_of)]
+  pub number_of_days: Option<i64>,
 }
 
 #[derive(Deserialize, Serialize, Default, Clone, Debug)]
@@ -25,6 +30,8 @@ pub struct DateFilterContentPB {
   pub start: Option<i64>,
   pub end: Option<i64>,
   pub timestamp: Option<i64>,
+  #[serde(default)]
+  pub number_of_days: Option<i64>,
 }
 
 impl ToString for DateFilterContentPB {
@@ -52,6 +59,30 @@ pub enum DateFilterConditionPB {
   DateWithIn = 5,
   DateIsEmpty = 6,
   DateIsNotEmpty = 7,
+  // The relative conditions are evaluated against the current date when filtering the rows.
+  DateIsToday = 8,
+  DateIsYesterday = 9,
+  DateIsTomorrow = 10,
+  DateIsThisWeek = 11,
+  DateIsLastWeek = 12,
+  DateIsNextWeek = 13,
+  DateIsThisMonth = 14,
+  DateIsLastMonth = 15,
+  DateIsNextMonth = 16,
+  DateIsThisYear = 17,
+  DateIsLastYear = 18,
+  DateIsNextYear = 19,
+  /// The date is within the past `number_of_days` days, including today.
+  DateWithinPastDays = 20,
+  /// The date is within the next `number_of_days` days, including today.
+  DateWithinNextDays = 21,
+}
+
+impl DateFilterConditionPB {
+  /// Returns true if the condition depends on the current date.
+  pub fn is_relative(&self) -> bool {
+    (self.clone() as u8) >= (DateFilterConditionPB::DateIsToday as u8)
+  }
 }
 
 impl std::convert::From<DateFilterConditionPB> for u32 {
@@ -77,6 +108,21 @@ impl std::convert::TryFrom<u8> for DateFilterConditionPB {
       4 => Ok(DateFilterConditionPB::DateOnOrAfter),
       5 => Ok(DateFilterConditionPB::DateWithIn),
       6 => Ok(DateFilterConditionPB::DateIsEmpty),
+      7 => Ok(DateFilterConditionPB::DateIsNotEmpty),
+      8 => Ok(DateFilterConditionPB::DateIsToday),
+      9 => Ok(DateFilterConditionPB::DateIsYesterday),
+      10 => Ok(DateFilterConditionPB::DateIsTomorrow),
+      11 => Ok(DateFilterConditionPB::DateIsThisWeek),
+      12 => Ok(DateFilterConditionPB::DateIsLastWeek),
+      13 => Ok(DateFilterConditionPB::DateIsNextWeek),
+      14 => Ok(DateFilterConditionPB::DateIsThisMonth),
+      15 => Ok(DateFilterConditionPB::DateIsLastMonth),
+      16 => Ok(DateFilterConditionPB::DateIsNextMonth),
+      17 => Ok(DateFilterConditionPB::DateIsThisYear),
+      18 => Ok(DateFilterConditionPB::DateIsLastYear),
+      19 => Ok(DateFilterConditionPB::DateIsNextYear),
+      20 => Ok(DateFilterConditionPB::DateWithinPastDays),
+      21 => Ok(DateFilterConditionPB::DateWithinNextDays),
       _ => Err(ErrorCode::InvalidData),
     }
   }
@@ -97,6 +143,7 @@ impl FromFilterString for DateFilterPB {
       filter.start = content.start;
       filter.end = content.end;
       filter.timestamp = content.timestamp;
+      filter.number_of_days = content.number_of_days;
     };
 
     filter
@@ -115,6 +162,7 @@ impl std::convert::From<&FilterRevision> for DateFilterPB {
       filter.start = content.start;
       filter.end = content.end;
       filter.timestamp = content.timestamp;
+      filter.number_of_days = content.number_of_days;
     };
 
     filter
@@ -1,4 +1,4 @@
-use crate::entities::FilterPB;
+use crate::entities::{FilterPB, RepeatedFilterGroupPB};
 use flowy_derive::ProtoBuf;
 
 #[derive(Debug, Default, ProtoBuf)]
@@ -14,6 +14,10 @@ pub struct FilterChangesetNotificationPB {
 
   #[pb(index = 4)]
   pub update_filters: Vec<UpdatedFilter>,
+
+  /// The filter groups of the view after the change, flattened from the root group.
+  #[pb(index = 5, one_of)]
+  pub filter_groups: Option<RepeatedFilterGroupPB>,
 }
 
 #[derive(Debug, Default, ProtoBuf)]
@@ -26,12 +30,20 @@ pub struct UpdatedFilter {
 }
 
 impl FilterChangesetNotificationPB {
+  pub fn new(view_id: &str) -> Self {
+    Self {
+      view_id: view_id.to_string(),
+      ..Default::default()
+    }
+  }
+
   pub fn from_insert(view_id: &str, filters: Vec<FilterPB>) -> Self {
     Self {
       view_id: view_id.to_string(),
       insert_filters: filters,
       delete_filters: Default::default(),
       update_filters: Default::default(),
+      filter_groups: None,
     }
   }
   pub fn from_delete(view_id: &str, filters: Vec<FilterPB>) -> Self {
@@ -40,6 +52,7 @@ impl FilterChangesetNotificationPB {
       insert_filters: Default::default(),
       delete_filters: filters,
       update_filters: Default::default(),
+      filter_groups: None,
     }
   }
 
@@ -49,6 +62,7 @@ impl FilterChangesetNotificationPB {
       insert_filters: Default::default(),
       delete_filters: Default::default(),
       update_filters: filters,
+      filter_groups: None,
     }
   }
 }
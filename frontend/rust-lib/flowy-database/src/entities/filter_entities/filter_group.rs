@@ -0,0 +1,196 @@
+use crate::entities::parser::NotEmptyStr;
+use database_model::{FilterGroupRevision, FilterOperatorRevision};
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum FilterOperatorPB {
+  /// The row is visible if all the filters in the group are satisfied.
+  And = 0,
+  /// The row is visible if one of the filters in the group is satisfied.
+  Or = 1,
+}
+
+impl std::default::Default for FilterOperatorPB {
+  fn default() -> Self {
+    FilterOperatorPB::And
+  }
+}
+
+impl std::convert::From<FilterOperatorRevision> for FilterOperatorPB {
+  fn from(rev: FilterOperatorRevision) -> Self {
+    match rev {
+      FilterOperatorRevision::And => FilterOperatorPB::And,
+      FilterOperatorRevision::Or => FilterOperatorPB::Or,
+    }
+  }
+}
+
+impl std::convert::From<FilterOperatorPB> for FilterOperatorRevision {
+  fn from(operator: FilterOperatorPB) -> Self {
+    match operator {
+      FilterOperatorPB::And => FilterOperatorRevision::And,
+      FilterOperatorPB::Or => FilterOperatorRevision::Or,
+    }
+  }
+}
+
+/// [FilterGroupPB] combines the filters and the nested groups with the operator. The groups of a
+/// view are sent as a flat list, the first one is the root group whose id is empty.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct FilterGroupPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  /// The id of the parent group. None if it's the root group.
+  #[pb(index = 2, one_of)]
+  pub parent_group_id: Option<String>,
+
+  #[pb(index = 3)]
+  pub operator: FilterOperatorPB,
+
+  #[pb(index = 4)]
+  pub filter_ids: Vec<String>,
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct RepeatedFilterGroupPB {
+  #[pb(index = 1)]
+  pub items: Vec<FilterGroupPB>,
+}
+
+impl std::convert::From<&FilterGroupRevision> for RepeatedFilterGroupPB {
+  fn from(root_group: &FilterGroupRevision) -> Self {
+    fn flatten(
+      group: &FilterGroupRevision,
+      parent_group_id: Option<String>,
+      items: &mut Vec<FilterGroupPB>,
+    ) {
+      items.push(FilterGroupPB {
+        id: group.id.clone(),
+        parent_group_id,
+        operator: group.operator.into(),
+        filter_ids: group.filter_ids.clone(),
+      });
+      for child in group.groups.iter() {
+        flatten(child, Some(group.id.clone()), items);
+      }
+    }
+
+    let mut items = vec![];
+    flatten(root_group, None, &mut items);
+    Self { items }
+  }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct InsertFilterGroupPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// Insert the group into the root group if the parent_group_id is None.
+  #[pb(index = 2, one_of)]
+  pub parent_group_id: Option<String>,
+
+  #[pb(index = 3)]
+  pub operator: FilterOperatorPB,
+}
+
+impl TryInto<InsertFilterGroupParams> for InsertFilterGroupPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<InsertFilterGroupParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id)
+      .map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?
+      .0;
+    Ok(InsertFilterGroupParams {
+      view_id,
+      parent_group_id: parse_group_id(self.parent_group_id)?,
+      operator: self.operator.into(),
+    })
+  }
+}
+
+#[derive(Debug)]
+pub struct InsertFilterGroupParams {
+  pub view_id: String,
+  pub parent_group_id: Option<String>,
+  pub operator: FilterOperatorRevision,
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct UpdateFilterGroupPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// Update the root group if the group_id is None.
+  #[pb(index = 2, one_of)]
+  pub group_id: Option<String>,
+
+  #[pb(index = 3)]
+  pub operator: FilterOperatorPB,
+}
+
+impl TryInto<UpdateFilterGroupParams> for UpdateFilterGroupPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<UpdateFilterGroupParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id)
+      .map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?
+      .0;
+    Ok(UpdateFilterGroupParams {
+      view_id,
+      group_id: parse_group_id(self.group_id)?,
+      operator: self.operator.into(),
+    })
+  }
+}
+
+#[derive(Debug)]
+pub struct UpdateFilterGroupParams {
+  pub view_id: String,
+  pub group_id: Option<String>,
+  pub operator: FilterOperatorRevision,
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct DeleteFilterGroupPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub group_id: String,
+}
+
+impl TryInto<DeleteFilterGroupParams> for DeleteFilterGroupPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<DeleteFilterGroupParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id)
+      .map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?
+      .0;
+    // The root group can't be deleted.
+    let group_id = NotEmptyStr::parse(self.group_id)
+      .map_err(|_| ErrorCode::FilterIdIsEmpty)?
+      .0;
+    Ok(DeleteFilterGroupParams { view_id, group_id })
+  }
+}
+
+#[derive(Debug)]
+pub struct DeleteFilterGroupParams {
+  pub view_id: String,
+  pub group_id: String,
+}
+
+pub(crate) fn parse_group_id(group_id: Option<String>) -> Result<Option<String>, ErrorCode> {
+  match group_id {
+    None => Ok(None),
+    Some(group_id) => Ok(Some(
+      NotEmptyStr::parse(group_id)
+        .map_err(|_| ErrorCode::FilterIdIsEmpty)?
+        .0,
+    )),
+  }
+}
@@ -0,0 +1,63 @@
+use crate::entities::{
+  CheckboxFilterConditionPB, CheckboxFilterPB, NumberFilterConditionPB, NumberFilterPB,
+  TextFilterConditionPB, TextFilterPB,
+};
+use crate::services::filter::FromFilterString;
+use database_model::FilterRevision;
+use flowy_derive::ProtoBuf;
+use std::convert::TryFrom;
+
+/// The filter of the formula field. The `condition` is one of [TextFilterConditionPB],
+/// [NumberFilterConditionPB] or [CheckboxFilterConditionPB], depending on the result type of the
+/// formula.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct FormulaFilterPB {
+  #[pb(index = 1)]
+  pub condition: u32,
+
+  #[pb(index = 2)]
+  pub content: String,
+}
+
+impl FormulaFilterPB {
+  pub fn text_filter(&self) -> TextFilterPB {
+    TextFilterPB {
+      condition: TextFilterConditionPB::try_from(self.condition as u8)
+        .unwrap_or(TextFilterConditionPB::Is),
+      content: self.content.clone(),
+    }
+  }
+
+  pub fn number_filter(&self) -> NumberFilterPB {
+    NumberFilterPB {
+      condition: NumberFilterConditionPB::try_from(self.condition as u8)
+        .unwrap_or(NumberFilterConditionPB::Equal),
+      content: self.content.clone(),
+    }
+  }
+
+  pub fn checkbox_filter(&self) -> CheckboxFilterPB {
+    CheckboxFilterPB {
+      condition: CheckboxFilterConditionPB::try_from(self.condition as u8)
+        .unwrap_or(CheckboxFilterConditionPB::IsChecked),
+    }
+  }
+}
+
+impl FromFilterString for FormulaFilterPB {
+  fn from_filter_rev(filter_rev: &FilterRevision) -> Self
+  where
+    Self: Sized,
+  {
+    FormulaFilterPB::from(filter_rev)
+  }
+}
+
+impl std::convert::From<&FilterRevision> for FormulaFilterPB {
+  fn from(rev: &FilterRevision) -> Self {
+    FormulaFilterPB {
+      condition: rev.condition as u32,
+      content: rev.content.clone(),
+    }
+  }
+}
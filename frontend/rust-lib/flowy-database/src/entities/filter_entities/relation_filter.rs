@@ -0,0 +1,69 @@
+use crate::services::field::RelationCellData;
+use crate::services::filter::FromFilterString;
+use database_model::FilterRevision;
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct RelationFilterPB {
+  #[pb(index = 1)]
+  pub condition: RelationFilterConditionPB,
+
+  #[pb(index = 2)]
+  pub row_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum RelationFilterConditionPB {
+  RelationContains = 0,
+  RelationDoesNotContain = 1,
+  RelationIsEmpty = 2,
+  RelationIsNotEmpty = 3,
+}
+
+impl std::convert::From<RelationFilterConditionPB> for u32 {
+  fn from(value: RelationFilterConditionPB) -> Self {
+    value as u32
+  }
+}
+
+impl std::default::Default for RelationFilterConditionPB {
+  fn default() -> Self {
+    RelationFilterConditionPB::RelationContains
+  }
+}
+
+impl std::convert::TryFrom<u8> for RelationFilterConditionPB {
+  type Error = ErrorCode;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(RelationFilterConditionPB::RelationContains),
+      1 => Ok(RelationFilterConditionPB::RelationDoesNotContain),
+      2 => Ok(RelationFilterConditionPB::RelationIsEmpty),
+      3 => Ok(RelationFilterConditionPB::RelationIsNotEmpty),
+      _ => Err(ErrorCode::InvalidData),
+    }
+  }
+}
+
+impl FromFilterString for RelationFilterPB {
+  fn from_filter_rev(filter_rev: &FilterRevision) -> Self
+  where
+    Self: Sized,
+  {
+    RelationFilterPB::from(filter_rev)
+  }
+}
+
+impl std::convert::From<&FilterRevision> for RelationFilterPB {
+  fn from(rev: &FilterRevision) -> Self {
+    let row_ids = RelationCellData::from(rev.content.clone());
+    RelationFilterPB {
+      condition: RelationFilterConditionPB::try_from(rev.condition)
+        .unwrap_or(RelationFilterConditionPB::RelationContains),
+      row_ids: row_ids.into_inner(),
+    }
+  }
+}
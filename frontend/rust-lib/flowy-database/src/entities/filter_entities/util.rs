@@ -1,9 +1,9 @@
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::{
-  CheckboxFilterPB, ChecklistFilterPB, DateFilterContentPB, DateFilterPB, FieldType,
-  NumberFilterPB, SelectOptionFilterPB, TextFilterPB,
+  parse_group_id, CheckboxFilterPB, ChecklistFilterPB, DateFilterContentPB, DateFilterPB,
+  FieldType, FormulaFilterPB, NumberFilterPB, RelationFilterPB, SelectOptionFilterPB, TextFilterPB,
 };
-use crate::services::field::SelectOptionIds;
+use crate::services::field::{RelationCellData, SelectOptionIds};
 use crate::services::filter::FilterType;
 use bytes::Bytes;
 use database_model::{FieldRevision, FieldTypeRevision, FilterRevision};
@@ -39,6 +39,9 @@ impl std::convert::From<&FilterRevision> for FilterPB {
       FieldType::Checklist => ChecklistFilterPB::from(rev).try_into().unwrap(),
       FieldType::Checkbox => CheckboxFilterPB::from(rev).try_into().unwrap(),
       FieldType::URL => TextFilterPB::from(rev).try_into().unwrap(),
+      FieldType::Formula => FormulaFilterPB::from(rev).try_into().unwrap(),
+      FieldType::Relation => RelationFilterPB::from(rev).try_into().unwrap(),
+      FieldType::Rollup => FormulaFilterPB::from(rev).try_into().unwrap(),
     };
     Self {
       id: rev.id.clone(),
@@ -136,6 +139,11 @@ pub struct AlterFilterPayloadPB {
 
   #[pb(index = 5)]
   pub view_id: String,
+
+  /// Insert the new filter into the root group if the group_id is None. It's ignored when
+  /// updating the filter.
+  #[pb(index = 6, one_of)]
+  pub group_id: Option<String>,
 }
 
 impl AlterFilterPayloadPB {
@@ -152,6 +160,7 @@ impl AlterFilterPayloadPB {
       field_type: field_rev.ty.into(),
       filter_id: None,
       data: data.to_vec(),
+      group_id: None,
     }
   }
 }
@@ -201,6 +210,7 @@ impl TryInto<AlterFilterParams> for AlterFilterPayloadPB {
           start: filter.start,
           end: filter.end,
           timestamp: filter.timestamp,
+          number_of_days: filter.number_of_days,
         }
         .to_string();
       },
@@ -209,12 +219,23 @@ impl TryInto<AlterFilterParams> for AlterFilterPayloadPB {
         condition = filter.condition as u8;
         content = SelectOptionIds::from(filter.option_ids).to_string();
       },
+      FieldType::Formula | FieldType::Rollup => {
+        let filter = FormulaFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
+        condition = filter.condition as u8;
+        content = filter.content;
+      },
+      FieldType::Relation => {
+        let filter = RelationFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?;
+        condition = filter.condition as u8;
+        content = RelationCellData::from(filter.row_ids).to_string();
+      },
     }
 
     Ok(AlterFilterParams {
       view_id,
       field_id,
       filter_id,
+      group_id: parse_group_id(self.group_id)?,
       field_type: self.field_type.into(),
       condition,
       content,
@@ -228,6 +249,8 @@ pub struct AlterFilterParams {
   pub field_id: String,
   /// Create a new filter if the filter_id is None
   pub filter_id: Option<String>,
+  /// The group that the new filter is inserted into. None represents the root group.
+  pub group_id: Option<String>,
   pub field_type: FieldTypeRevision,
   pub condition: u8,
   pub content: String,
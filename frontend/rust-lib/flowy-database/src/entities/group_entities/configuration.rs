@@ -1,5 +1,10 @@
-use database_model::{GroupRevision, SelectOptionGroupConfigurationRevision};
+use crate::entities::parser::NotEmptyStr;
+use database_model::{
+  GroupRevision, NumberGroupCondition, NumberGroupConfigurationRevision,
+  SelectOptionGroupConfigurationRevision,
+};
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct UrlGroupConfigurationPB {
@@ -50,7 +55,111 @@ impl std::convert::From<GroupRevision> for GroupRecordPB {
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct NumberGroupConfigurationPB {
   #[pb(index = 1)]
-  hide_empty: bool,
+  pub hide_empty: bool,
+
+  #[pb(index = 2)]
+  pub condition: NumberGroupConditionPB,
+
+  /// The width of each range, used by the [NumberGroupConditionPB::Range]
+  #[pb(index = 3)]
+  pub interval: String,
+
+  /// The lower bounds of the ranges, used by the [NumberGroupConditionPB::Breakpoint]
+  #[pb(index = 4)]
+  pub breakpoints: Vec<String>,
+}
+
+impl std::convert::From<NumberGroupConfigurationRevision> for NumberGroupConfigurationPB {
+  fn from(rev: NumberGroupConfigurationRevision) -> Self {
+    Self {
+      hide_empty: rev.hide_empty,
+      condition: rev.condition.into(),
+      interval: rev.interval,
+      breakpoints: rev.breakpoints,
+    }
+  }
+}
+
+impl std::convert::From<NumberGroupConfigurationPB> for NumberGroupConfigurationRevision {
+  fn from(pb: NumberGroupConfigurationPB) -> Self {
+    Self {
+      hide_empty: pb.hide_empty,
+      condition: pb.condition.into(),
+      interval: pb.interval,
+      breakpoints: pb.breakpoints,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum NumberGroupConditionPB {
+  Range = 0,
+  Breakpoint = 1,
+  Distinct = 2,
+}
+
+impl std::default::Default for NumberGroupConditionPB {
+  fn default() -> Self {
+    NumberGroupConditionPB::Range
+  }
+}
+
+impl std::convert::From<NumberGroupCondition> for NumberGroupConditionPB {
+  fn from(condition: NumberGroupCondition) -> Self {
+    match condition {
+      NumberGroupCondition::Range => NumberGroupConditionPB::Range,
+      NumberGroupCondition::Breakpoint => NumberGroupConditionPB::Breakpoint,
+      NumberGroupCondition::Distinct => NumberGroupConditionPB::Distinct,
+    }
+  }
+}
+
+impl std::convert::From<NumberGroupConditionPB> for NumberGroupCondition {
+  fn from(condition: NumberGroupConditionPB) -> Self {
+    match condition {
+      NumberGroupConditionPB::Range => NumberGroupCondition::Range,
+      NumberGroupConditionPB::Breakpoint => NumberGroupCondition::Breakpoint,
+      NumberGroupConditionPB::Distinct => NumberGroupCondition::Distinct,
+    }
+  }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct UpdateNumberGroupConfigurationPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub configuration: NumberGroupConfigurationPB,
+}
+
+impl TryInto<UpdateNumberGroupConfigurationParams> for UpdateNumberGroupConfigurationPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<UpdateNumberGroupConfigurationParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id)
+      .map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?
+      .0;
+    let field_id = NotEmptyStr::parse(self.field_id)
+      .map_err(|_| ErrorCode::FieldIdIsEmpty)?
+      .0;
+
+    Ok(UpdateNumberGroupConfigurationParams {
+      view_id,
+      field_id,
+      configuration: self.configuration.into(),
+    })
+  }
+}
+
+pub struct UpdateNumberGroupConfigurationParams {
+  pub view_id: String,
+  pub field_id: String,
+  pub configuration: NumberGroupConfigurationRevision,
 }
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
@@ -1,3 +1,5 @@
+mod calculation_entities;
+mod calendar_entities;
 mod cell_entities;
 mod field_entities;
 pub mod filter_entities;
@@ -6,9 +8,13 @@ mod group_entities;
 pub mod parser;
 mod row_entities;
 pub mod setting_entities;
+mod share_entities;
+mod snapshot_entities;
 mod sort_entities;
 mod view_entities;
 
+pub use calculation_entities::*;
+pub use calendar_entities::*;
 pub use cell_entities::*;
 pub use field_entities::*;
 pub use filter_entities::*;
@@ -16,5 +22,7 @@ pub use grid_entities::*;
 pub use group_entities::*;
 pub use row_entities::*;
 pub use setting_entities::*;
+pub use share_entities::*;
+pub use snapshot_entities::*;
 pub use sort_entities::*;
 pub use view_entities::*;
@@ -1,9 +1,12 @@
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::{
-  AlterFilterParams, AlterFilterPayloadPB, AlterSortParams, AlterSortPayloadPB, DeleteFilterParams,
-  DeleteFilterPayloadPB, DeleteGroupParams, DeleteGroupPayloadPB, DeleteSortParams,
-  DeleteSortPayloadPB, InsertGroupParams, InsertGroupPayloadPB, RepeatedFilterPB,
-  RepeatedGroupConfigurationPB, RepeatedSortPB,
+  AlterCalculationParams, AlterCalculationPayloadPB, AlterFilterParams, AlterFilterPayloadPB,
+  AlterSortParams, AlterSortPayloadPB, DeleteCalculationParams, DeleteCalculationPayloadPB,
+  DeleteFilterGroupParams, DeleteFilterGroupPayloadPB, DeleteFilterParams, DeleteFilterPayloadPB,
+  DeleteGroupParams, DeleteGroupPayloadPB, DeleteSortParams, DeleteSortPayloadPB,
+  InsertFilterGroupParams, InsertFilterGroupPayloadPB, InsertGroupParams, InsertGroupPayloadPB,
+  RepeatedCalculationPB, RepeatedFilterGroupPB, RepeatedFilterPB, RepeatedGroupConfigurationPB,
+  RepeatedSortPB, UpdateFilterGroupParams, UpdateFilterGroupPayloadPB,
 };
 use database_model::LayoutRevision;
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
@@ -29,6 +32,12 @@ pub struct DatabaseViewSettingPB {
 
   #[pb(index = 5)]
   pub sorts: RepeatedSortPB,
+
+  #[pb(index = 6)]
+  pub filter_groups: RepeatedFilterGroupPB,
+
+  #[pb(index = 7)]
+  pub calculations: RepeatedCalculationPB,
 }
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
@@ -107,6 +116,21 @@ pub struct DatabaseSettingChangesetPB {
 
   #[pb(index = 8, one_of)]
   pub delete_sort: Option<DeleteSortPayloadPB>,
+
+  #[pb(index = 9, one_of)]
+  pub insert_filter_group: Option<InsertFilterGroupPayloadPB>,
+
+  #[pb(index = 10, one_of)]
+  pub update_filter_group: Option<UpdateFilterGroupPayloadPB>,
+
+  #[pb(index = 11, one_of)]
+  pub delete_filter_group: Option<DeleteFilterGroupPayloadPB>,
+
+  #[pb(index = 12, one_of)]
+  pub alter_calculation: Option<AlterCalculationPayloadPB>,
+
+  #[pb(index = 13, one_of)]
+  pub delete_calculation: Option<DeleteCalculationPayloadPB>,
 }
 
 impl TryInto<DatabaseSettingChangesetParams> for DatabaseSettingChangesetPB {
@@ -147,6 +171,31 @@ impl TryInto<DatabaseSettingChangesetParams> for DatabaseSettingChangesetPB {
       Some(payload) => Some(payload.try_into()?),
     };
 
+    let insert_filter_group = match self.insert_filter_group {
+      None => None,
+      Some(payload) => Some(payload.try_into()?),
+    };
+
+    let update_filter_group = match self.update_filter_group {
+      None => None,
+      Some(payload) => Some(payload.try_into()?),
+    };
+
+    let delete_filter_group = match self.delete_filter_group {
+      None => None,
+      Some(payload) => Some(payload.try_into()?),
+    };
+
+    let alter_calculation = match self.alter_calculation {
+      None => None,
+      Some(payload) => Some(payload.try_into()?),
+    };
+
+    let delete_calculation = match self.delete_calculation {
+      None => None,
+      Some(payload) => Some(payload.try_into()?),
+    };
+
     Ok(DatabaseSettingChangesetParams {
       view_id,
       layout_type: self.layout_type.into(),
@@ -156,6 +205,11 @@ impl TryInto<DatabaseSettingChangesetParams> for DatabaseSettingChangesetPB {
       delete_group,
       alert_sort,
       delete_sort,
+      insert_filter_group,
+      update_filter_group,
+      delete_filter_group,
+      alter_calculation,
+      delete_calculation,
     })
   }
 }
@@ -169,10 +223,19 @@ pub struct DatabaseSettingChangesetParams {
   pub delete_group: Option<DeleteGroupParams>,
   pub alert_sort: Option<AlterSortParams>,
   pub delete_sort: Option<DeleteSortParams>,
+  pub insert_filter_group: Option<InsertFilterGroupParams>,
+  pub update_filter_group: Option<UpdateFilterGroupParams>,
+  pub delete_filter_group: Option<DeleteFilterGroupParams>,
+  pub alter_calculation: Option<AlterCalculationParams>,
+  pub delete_calculation: Option<DeleteCalculationParams>,
 }
 
 impl DatabaseSettingChangesetParams {
   pub fn is_filter_changed(&self) -> bool {
-    self.insert_filter.is_some() || self.delete_filter.is_some()
+    self.insert_filter.is_some()
+      || self.delete_filter.is_some()
+      || self.insert_filter_group.is_some()
+      || self.update_filter_group.is_some()
+      || self.delete_filter_group.is_some()
   }
 }
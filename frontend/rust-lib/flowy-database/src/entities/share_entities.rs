@@ -0,0 +1,95 @@
+use crate::entities::parser::NotEmptyStr;
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+/// [ImportCSVPayloadPB] is used to create a new grid from the csv text. The grid is created as
+/// a child view of the [belong_to_id].
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct ImportCSVPayloadPB {
+  #[pb(index = 1)]
+  pub belong_to_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub csv: String,
+}
+
+pub struct ImportCSVParams {
+  pub belong_to_id: String,
+  pub name: String,
+  pub csv: String,
+}
+
+impl TryInto<ImportCSVParams> for ImportCSVPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ImportCSVParams, Self::Error> {
+    let belong_to_id =
+      NotEmptyStr::parse(self.belong_to_id).map_err(|_| ErrorCode::AppIdInvalid)?;
+    let name = NotEmptyStr::parse(self.name).map_err(|_| ErrorCode::ViewNameInvalid)?;
+    if self.csv.trim().is_empty() {
+      return Err(ErrorCode::InvalidCSV);
+    }
+
+    Ok(ImportCSVParams {
+      belong_to_id: belong_to_id.0,
+      name: name.0,
+      csv: self.csv,
+    })
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum ExportFormatPB {
+  CSV = 0,
+  JSON = 1,
+}
+
+impl std::default::Default for ExportFormatPB {
+  fn default() -> Self {
+    Self::CSV
+  }
+}
+
+/// [ExportDatabasePayloadPB] is used to export the rows of the view. Only the rows that pass the
+/// view's filters are exported, in the order of the view's sorts.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct ExportDatabasePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub format: ExportFormatPB,
+
+  /// Exports the strings that are stored in the cells instead of the displayed strings.
+  #[pb(index = 3)]
+  pub raw_cell: bool,
+}
+
+pub struct ExportDatabaseParams {
+  pub view_id: String,
+  pub format: ExportFormatPB,
+  pub raw_cell: bool,
+}
+
+impl TryInto<ExportDatabaseParams> for ExportDatabasePayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ExportDatabaseParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?;
+    Ok(ExportDatabaseParams {
+      view_id: view_id.0,
+      format: self.format,
+      raw_cell: self.raw_cell,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct ExportDatabaseDataPB {
+  #[pb(index = 1)]
+  pub data: String,
+}
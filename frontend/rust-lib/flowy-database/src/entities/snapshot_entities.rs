@@ -0,0 +1,77 @@
+use crate::entities::parser::NotEmptyStr;
+use flowy_derive::ProtoBuf;
+use flowy_error::ErrorCode;
+use flowy_revision::RevisionSnapshotData;
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct DatabaseSnapshotPB {
+  #[pb(index = 1)]
+  pub rev_id: i64,
+
+  /// The time when the snapshot was taken, in seconds.
+  #[pb(index = 2)]
+  pub timestamp: i64,
+}
+
+impl std::convert::From<RevisionSnapshotData> for DatabaseSnapshotPB {
+  fn from(snapshot: RevisionSnapshotData) -> Self {
+    Self {
+      rev_id: snapshot.rev_id,
+      timestamp: snapshot.timestamp,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedDatabaseSnapshotPB {
+  #[pb(index = 1)]
+  pub items: Vec<DatabaseSnapshotPB>,
+}
+
+impl std::convert::From<Vec<RevisionSnapshotData>> for RepeatedDatabaseSnapshotPB {
+  fn from(snapshots: Vec<RevisionSnapshotData>) -> Self {
+    Self {
+      items: snapshots
+        .into_iter()
+        .map(DatabaseSnapshotPB::from)
+        .collect(),
+    }
+  }
+}
+
+/// [DatabaseSnapshotIdPB] identifies a snapshot of the database that the view belongs to.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct DatabaseSnapshotIdPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub rev_id: i64,
+}
+
+pub struct DatabaseSnapshotIdParams {
+  pub view_id: String,
+  pub rev_id: i64,
+}
+
+impl TryInto<DatabaseSnapshotIdParams> for DatabaseSnapshotIdPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<DatabaseSnapshotIdParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::DatabaseViewIdIsEmpty)?;
+    Ok(DatabaseSnapshotIdParams {
+      view_id: view_id.0,
+      rev_id: self.rev_id,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct DatabaseSnapshotContentPB {
+  #[pb(index = 1)]
+  pub rev_id: i64,
+
+  /// The fields and rows of the database at the snapshot, in json.
+  #[pb(index = 2)]
+  pub data: String,
+}
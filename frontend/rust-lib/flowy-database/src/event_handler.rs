@@ -3,7 +3,8 @@ use crate::manager::DatabaseManager;
 use crate::services::cell::{FromCellString, ToCellChangesetString, TypeCellData};
 use crate::services::field::{
   default_type_option_builder_from_type, select_type_option_from_field_rev,
-  type_option_builder_from_json_str, DateCellChangeset, DateChangesetPB, SelectOptionCellChangeset,
+  type_option_builder_from_json_str, DateCellChangeset, DateChangesetPB, RelationCellChangeset,
+  RelationCellChangesetPB, RelationCellChangesetParams, SelectOptionCellChangeset,
   SelectOptionCellChangesetPB, SelectOptionCellChangesetParams, SelectOptionCellDataPB,
   SelectOptionChangeset, SelectOptionChangesetPB, SelectOptionIds, SelectOptionPB,
 };
@@ -51,6 +52,14 @@ pub(crate) async fn update_database_setting_handler(
     editor.delete_group(delete_params).await?;
   }
 
+  if let Some(insert_filter_group) = params.insert_filter_group {
+    editor.insert_filter_group(insert_filter_group).await?;
+  }
+
+  if let Some(update_filter_group) = params.update_filter_group {
+    editor.update_filter_group(update_filter_group).await?;
+  }
+
   if let Some(alter_filter) = params.insert_filter {
     editor.create_or_update_filter(alter_filter).await?;
   }
@@ -59,12 +68,26 @@ pub(crate) async fn update_database_setting_handler(
     editor.delete_filter(delete_filter).await?;
   }
 
+  if let Some(delete_filter_group) = params.delete_filter_group {
+    editor.delete_filter_group(delete_filter_group).await?;
+  }
+
   if let Some(alter_sort) = params.alert_sort {
     let _ = editor.create_or_update_sort(alter_sort).await?;
   }
   if let Some(delete_sort) = params.delete_sort {
     editor.delete_sort(delete_sort).await?;
   }
+
+  if let Some(alter_calculation) = params.alter_calculation {
+    let _ = editor
+      .create_or_update_calculation(alter_calculation)
+      .await?;
+  }
+
+  if let Some(delete_calculation) = params.delete_calculation {
+    editor.delete_calculation(delete_calculation).await?;
+  }
   Ok(())
 }
 
@@ -94,6 +117,17 @@ pub(crate) async fn get_all_sorts_handler(
   data_result(sorts)
 }
 
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_calculation_values_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> DataResult<RepeatedCalculationValuePB, FlowyError> {
+  let database_id: DatabaseViewIdPB = data.into_inner();
+  let editor = manager.open_database(database_id.as_ref()).await?;
+  let values = editor.get_calculation_values(database_id.as_ref()).await?;
+  data_result(values)
+}
+
 #[tracing::instrument(level = "trace", skip(data, manager), err)]
 pub(crate) async fn delete_all_sorts_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -517,6 +551,30 @@ pub(crate) async fn update_date_cell_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn update_relation_cell_handler(
+  data: AFPluginData<RelationCellChangesetPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let params: RelationCellChangesetParams = data.into_inner().try_into()?;
+  let cell_changeset = RelationCellChangeset {
+    inserted_row_ids: params.inserted_row_ids,
+    removed_row_ids: params.removed_row_ids,
+  };
+
+  let editor = manager
+    .get_database_editor(&params.cell_path.database_id)
+    .await?;
+  editor
+    .update_cell(
+      params.cell_path.row_id,
+      params.cell_path.field_id,
+      cell_changeset,
+    )
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 pub(crate) async fn get_groups_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -560,3 +618,139 @@ pub(crate) async fn move_group_row_handler(
   editor.move_group_row(params).await?;
   Ok(())
 }
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn update_number_group_configuration_handler(
+  data: AFPluginData<UpdateNumberGroupConfigurationPayloadPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let params: UpdateNumberGroupConfigurationParams = data.into_inner().try_into()?;
+  let editor = manager.get_database_editor(params.view_id.as_ref()).await?;
+  editor.update_number_group_configuration(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_calendar_setting_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> DataResult<CalendarLayoutSettingsPB, FlowyError> {
+  let view_id: DatabaseViewIdPB = data.into_inner();
+  let editor = manager.get_database_editor(view_id.as_ref()).await?;
+  match editor.get_calendar_setting(view_id.as_ref()).await? {
+    None => Err(FlowyError::record_not_found().context("Calendar setting not found")),
+    Some(setting) => data_result(setting),
+  }
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn update_calendar_setting_handler(
+  data: AFPluginData<UpdateCalendarSettingPayloadPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let params: UpdateCalendarSettingParams = data.into_inner().try_into()?;
+  let editor = manager.get_database_editor(&params.view_id).await?;
+  editor.update_calendar_setting(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_calendar_events_handler(
+  data: AFPluginData<CalendarEventRequestPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> DataResult<RepeatedCalendarDayPB, FlowyError> {
+  let params: CalendarEventRequestParams = data.into_inner().try_into()?;
+  let editor = manager.get_database_editor(&params.view_id).await?;
+  let items = editor.get_calendar_events(params).await?;
+  data_result(RepeatedCalendarDayPB { items })
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn move_calendar_event_handler(
+  data: AFPluginData<MoveCalendarEventPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let params: MoveCalendarEventParams = data.into_inner().try_into()?;
+  let editor = manager.get_database_editor(&params.view_id).await?;
+  editor.move_calendar_event(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn import_csv_handler(
+  data: AFPluginData<ImportCSVPayloadPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> DataResult<DatabaseViewIdPB, FlowyError> {
+  let params: ImportCSVParams = data.into_inner().try_into()?;
+  let view_id = manager.import_csv(params).await?;
+  data_result(DatabaseViewIdPB { value: view_id })
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn export_database_handler(
+  data: AFPluginData<ExportDatabasePayloadPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> DataResult<ExportDatabaseDataPB, FlowyError> {
+  let params: ExportDatabaseParams = data.into_inner().try_into()?;
+  let editor = manager.get_database_editor(&params.view_id).await?;
+  let data = editor.export_database(params).await?;
+  data_result(ExportDatabaseDataPB { data })
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn undo_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let view_id: DatabaseViewIdPB = data.into_inner();
+  let editor = manager.get_database_editor(view_id.as_ref()).await?;
+  editor.undo().await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn redo_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let view_id: DatabaseViewIdPB = data.into_inner();
+  let editor = manager.get_database_editor(view_id.as_ref()).await?;
+  editor.redo().await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_database_snapshots_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> DataResult<RepeatedDatabaseSnapshotPB, FlowyError> {
+  let view_id: DatabaseViewIdPB = data.into_inner();
+  let editor = manager.get_database_editor(view_id.as_ref()).await?;
+  let snapshots = editor.get_snapshots().await?;
+  data_result(snapshots.into())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn preview_database_snapshot_handler(
+  data: AFPluginData<DatabaseSnapshotIdPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> DataResult<DatabaseSnapshotContentPB, FlowyError> {
+  let params: DatabaseSnapshotIdParams = data.into_inner().try_into()?;
+  let editor = manager.get_database_editor(&params.view_id).await?;
+  let data = editor.preview_snapshot(params.rev_id).await?;
+  data_result(DatabaseSnapshotContentPB {
+    rev_id: params.rev_id,
+    data,
+  })
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn restore_database_snapshot_handler(
+  data: AFPluginData<DatabaseSnapshotIdPB>,
+  manager: AFPluginState<Arc<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let params: DatabaseSnapshotIdParams = data.into_inner().try_into()?;
+  let editor = manager.get_database_editor(&params.view_id).await?;
+  editor.restore_snapshot(params.rev_id).await?;
+  Ok(())
+}
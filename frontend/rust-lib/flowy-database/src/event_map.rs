@@ -17,6 +17,7 @@ pub fn init(database_manager: Arc<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::GetAllFilters, get_all_filters_handler)
         .event(DatabaseEvent::GetAllSorts, get_all_sorts_handler)
         .event(DatabaseEvent::DeleteAllSorts, delete_all_sorts_handler)
+        .event(DatabaseEvent::GetCalculationValues, get_calculation_values_handler)
         // Field
         .event(DatabaseEvent::GetFields, get_fields_handler)
         .event(DatabaseEvent::UpdateField, update_field_handler)
@@ -43,11 +44,36 @@ pub fn init(database_manager: Arc<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::UpdateSelectOptionCell, update_select_option_cell_handler)
         // Date
         .event(DatabaseEvent::UpdateDateCell, update_date_cell_handler)
+        // Relation
+        .event(DatabaseEvent::UpdateRelationCell, update_relation_cell_handler)
         // Group
         .event(DatabaseEvent::CreateBoardCard, create_board_card_handler)
         .event(DatabaseEvent::MoveGroup, move_group_handler)
         .event(DatabaseEvent::MoveGroupRow, move_group_row_handler)
-        .event(DatabaseEvent::GetGroup, get_groups_handler);
+        .event(DatabaseEvent::GetGroup, get_groups_handler)
+        .event(
+          DatabaseEvent::UpdateNumberGroupConfiguration,
+          update_number_group_configuration_handler,
+        )
+        // Calendar
+        .event(DatabaseEvent::GetCalendarSetting, get_calendar_setting_handler)
+        .event(DatabaseEvent::UpdateCalendarSetting, update_calendar_setting_handler)
+        .event(DatabaseEvent::GetCalendarEvents, get_calendar_events_handler)
+        .event(DatabaseEvent::MoveCalendarEvent, move_calendar_event_handler)
+        // Share
+        .event(DatabaseEvent::ImportCSV, import_csv_handler)
+        .event(DatabaseEvent::ExportDatabase, export_database_handler)
+        .event(DatabaseEvent::Undo, undo_handler)
+        .event(DatabaseEvent::Redo, redo_handler)
+        .event(DatabaseEvent::GetDatabaseSnapshots, get_database_snapshots_handler)
+        .event(
+          DatabaseEvent::PreviewDatabaseSnapshot,
+          preview_database_snapshot_handler,
+        )
+        .event(
+          DatabaseEvent::RestoreDatabaseSnapshot,
+          restore_database_snapshot_handler,
+        );
 
   plugin
 }
@@ -85,6 +111,11 @@ pub enum DatabaseEvent {
   #[event(input = "DatabaseViewIdPB")]
   DeleteAllSorts = 6,
 
+  /// [GetCalculationValues] event is used to get the values of the view's calculations. The
+  /// values are calculated over the rows that are visible after applying the filters.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedCalculationValuePB")]
+  GetCalculationValues = 7,
+
   /// [GetFields] event is used to get the database's settings.
   ///
   /// The event handler accepts a [GetFieldPayloadPB] and returns a [RepeatedFieldPB]
@@ -215,6 +246,12 @@ pub enum DatabaseEvent {
   #[event(input = "DateChangesetPB")]
   UpdateDateCell = 80,
 
+  /// [UpdateRelationCell] event is used to update a relation cell's data. [RelationCellChangesetPB]
+  /// contains the ids of the related rows that will be inserted or removed. The rollup cells that
+  /// depend on the relation will be recalculated.
+  #[event(input = "RelationCellChangesetPB")]
+  UpdateRelationCell = 81,
+
   #[event(input = "DatabaseViewIdPB", output = "RepeatedGroupPB")]
   GetGroup = 100,
 
@@ -229,4 +266,64 @@ pub enum DatabaseEvent {
 
   #[event(input = "MoveGroupRowPayloadPB")]
   GroupByField = 113,
+
+  /// [UpdateNumberGroupConfiguration] event is used to update the ranges of the number groups.
+  /// The rows will be regrouped by the number field with the new configuration.
+  #[event(input = "UpdateNumberGroupConfigurationPayloadPB")]
+  UpdateNumberGroupConfiguration = 114,
+
+  /// [GetCalendarSetting] event is used to get the calendar layout setting of the view. If the
+  /// setting is not set yet, the first date field will be used as the layout field.
+  #[event(input = "DatabaseViewIdPB", output = "CalendarLayoutSettingsPB")]
+  GetCalendarSetting = 120,
+
+  /// [UpdateCalendarSetting] event is used to update the calendar layout setting of the view.
+  /// The layout field must be a date field.
+  #[event(input = "UpdateCalendarSettingPayloadPB")]
+  UpdateCalendarSetting = 121,
+
+  /// [GetCalendarEvents] event is used to get the rows in the given date range, grouped by day.
+  /// The rows that are filtered out by the view's filters are not returned.
+  #[event(input = "CalendarEventRequestPB", output = "RepeatedCalendarDayPB")]
+  GetCalendarEvents = 122,
+
+  /// [MoveCalendarEvent] event is used to move the row to another day. It rewrites the row's
+  /// date cell of the layout field.
+  #[event(input = "MoveCalendarEventPB")]
+  MoveCalendarEvent = 123,
+
+  /// [ImportCSV] event is used to create a new grid from the csv text. The type of each column is
+  /// inferred from its values. Returns the id of the new grid view.
+  #[event(input = "ImportCSVPayloadPB", output = "DatabaseViewIdPB")]
+  ImportCSV = 130,
+
+  /// [ExportDatabase] event is used to export the view as csv or json. The exported rows are the
+  /// ones displayed in the view, in the same order.
+  #[event(input = "ExportDatabasePayloadPB", output = "ExportDatabaseDataPB")]
+  ExportDatabase = 131,
+
+  /// [Undo] event is used to revert the last edit of the database, for example, editing a cell,
+  /// deleting a row or switching the field type. The views are notified as if the edit was made
+  /// by the user.
+  #[event(input = "DatabaseViewIdPB")]
+  Undo = 140,
+
+  /// [Redo] event is used to apply the last edit that was reverted by [Undo] again.
+  #[event(input = "DatabaseViewIdPB")]
+  Redo = 141,
+
+  /// [GetDatabaseSnapshots] event is used to list the snapshots of the database, the latest one
+  /// comes first.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedDatabaseSnapshotPB")]
+  GetDatabaseSnapshots = 150,
+
+  /// [PreviewDatabaseSnapshot] event is used to get the fields and rows of the database at the
+  /// snapshot without changing the database.
+  #[event(input = "DatabaseSnapshotIdPB", output = "DatabaseSnapshotContentPB")]
+  PreviewDatabaseSnapshot = 151,
+
+  /// [RestoreDatabaseSnapshot] event is used to restore the database to the snapshot. The
+  /// restore is saved as a new revision.
+  #[event(input = "DatabaseSnapshotIdPB")]
+  RestoreDatabaseSnapshot = 152,
 }
@@ -1,7 +1,8 @@
-use crate::entities::LayoutTypePB;
+use crate::entities::{ImportCSVParams, LayoutTypePB};
 use crate::services::database::{
-  make_database_block_rev_manager, DatabaseRevisionCloudService, DatabaseRevisionEditor,
-  DatabaseRevisionMergeable, DatabaseRevisionSerde,
+  make_database_block_rev_manager, make_database_search_records, DatabaseBlockEvent,
+  DatabaseRelationDelegate, DatabaseRevisionCloudService, DatabaseRevisionEditor,
+  DatabaseRevisionMergeable, DatabaseRevisionSerde, RelatedDatabase,
 };
 use crate::services::database_view::make_database_view_rev_manager;
 use crate::services::persistence::block_index::BlockIndexCache;
@@ -10,11 +11,14 @@ use crate::services::persistence::migration::DatabaseMigration;
 use crate::services::persistence::rev_sqlite::{
   SQLiteDatabaseRevisionPersistence, SQLiteDatabaseRevisionSnapshotPersistence,
 };
+use crate::services::persistence::search_index::DatabaseSearchIndex;
 use crate::services::persistence::GridDatabase;
+use crate::services::share::csv::make_database_from_csv;
 use bytes::Bytes;
-use database_model::{BuildDatabaseContext, DatabaseRevision, DatabaseViewRevision};
+use database_model::{BuildDatabaseContext, DatabaseRevision, DatabaseViewRevision, RowRevision};
 use flowy_client_sync::client_database::{
   make_database_block_operations, make_database_operations, make_grid_view_operations,
+  DatabaseBlockRevisionPad, DatabaseRevisionPad,
 };
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_revision::{
@@ -26,8 +30,11 @@ use lib_infra::ref_map::{RefCountHashMap, RefCountValue};
 use revision_model::Revision;
 
 use flowy_task::TaskDispatcher;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::convert::TryFrom;
+use std::sync::{Arc, Weak};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, RwLock};
+use ws_model::ws_revision::ServerRevisionWSData;
 
 pub trait DatabaseUser: Send + Sync {
   fn user_id(&self) -> Result<String, FlowyError>;
@@ -35,35 +42,63 @@ pub trait DatabaseUser: Send + Sync {
   fn db_pool(&self) -> Result<Arc<ConnectionPool>, FlowyError>;
 }
 
+/// [DatabaseFolderDelegate] creates the views in the folder for the databases that are built by
+/// the [DatabaseManager], for example, importing a database from csv.
+#[async_trait]
+pub trait DatabaseFolderDelegate: Send + Sync {
+  /// Creates a grid view that belongs to [belong_to_id] with the [build_context] and returns the
+  /// id of the view.
+  async fn create_grid_view(
+    &self,
+    belong_to_id: &str,
+    name: &str,
+    build_context: BuildDatabaseContext,
+  ) -> FlowyResult<String>;
+}
+
 pub struct DatabaseManager {
   database_editors: RwLock<RefCountHashMap<Arc<DatabaseRevisionEditor>>>,
   database_user: Arc<dyn DatabaseUser>,
+  rev_web_socket: Arc<dyn RevisionWebSocket>,
   block_index_cache: Arc<BlockIndexCache>,
+  search_index: Arc<DatabaseSearchIndex>,
   #[allow(dead_code)]
   kv_persistence: Arc<DatabaseKVPersistence>,
   task_scheduler: Arc<RwLock<TaskDispatcher>>,
   migration: DatabaseMigration,
+  relation_delegate: Arc<dyn DatabaseRelationDelegate>,
+  folder_delegate: parking_lot::RwLock<Option<Arc<dyn DatabaseFolderDelegate>>>,
 }
 
 impl DatabaseManager {
   pub fn new(
     grid_user: Arc<dyn DatabaseUser>,
-    _rev_web_socket: Arc<dyn RevisionWebSocket>,
+    rev_web_socket: Arc<dyn RevisionWebSocket>,
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
     database: Arc<dyn GridDatabase>,
-  ) -> Self {
+  ) -> Arc<Self> {
     let grid_editors = RwLock::new(RefCountHashMap::new());
     let kv_persistence = Arc::new(DatabaseKVPersistence::new(database.clone()));
     let block_index_cache = Arc::new(BlockIndexCache::new(database.clone()));
+    let search_index = Arc::new(DatabaseSearchIndex::new(database.clone()));
     let migration = DatabaseMigration::new(grid_user.clone(), database);
-    Self {
+    Arc::new_cyclic(|manager| Self {
       database_editors: grid_editors,
       database_user: grid_user,
+      rev_web_socket,
       kv_persistence,
       block_index_cache,
+      search_index,
       task_scheduler,
       migration,
-    }
+      relation_delegate: Arc::new(DatabaseRelationDelegateImpl(manager.clone())),
+      folder_delegate: parking_lot::RwLock::new(None),
+    })
+  }
+
+  /// The folder is initialized after the [DatabaseManager], so the delegate is set afterwards.
+  pub fn set_folder_delegate(&self, folder_delegate: Arc<dyn DatabaseFolderDelegate>) {
+    *self.folder_delegate.write() = Some(folder_delegate);
   }
 
   pub async fn initialize_with_new_user(&self, _user_id: &str, _token: &str) -> FlowyResult<()> {
@@ -89,7 +124,7 @@ impl DatabaseManager {
   }
 
   #[tracing::instrument(level = "debug", skip_all, err)]
-  async fn create_database_view<T: AsRef<str>>(
+  pub async fn create_database_view<T: AsRef<str>>(
     &self,
     view_id: T,
     revisions: Vec<Revision>,
@@ -112,6 +147,20 @@ impl DatabaseManager {
     Ok(())
   }
 
+  /// Creates a new grid from the csv text and returns the id of its view.
+  #[tracing::instrument(level = "debug", skip_all, err)]
+  pub async fn import_csv(&self, params: ImportCSVParams) -> FlowyResult<String> {
+    let build_context = make_database_from_csv(&params.csv)?;
+    let folder_delegate = self
+      .folder_delegate
+      .read()
+      .clone()
+      .ok_or_else(|| FlowyError::internal().context("The folder delegate is not set"))?;
+    folder_delegate
+      .create_grid_view(&params.belong_to_id, &params.name, build_context)
+      .await
+  }
+
   pub async fn open_database<T: AsRef<str>>(
     &self,
     database_id: T,
@@ -134,6 +183,15 @@ impl DatabaseManager {
     Ok(())
   }
 
+  pub async fn is_database_opened(&self, database_id: &str) -> bool {
+    self
+      .database_editors
+      .read()
+      .await
+      .get(database_id)
+      .is_some()
+  }
+
   // #[tracing::instrument(level = "debug", skip(self), err)]
   //TODO(nathan): map the view_id to database_id
   pub async fn get_database_editor(
@@ -165,9 +223,82 @@ impl DatabaseManager {
     let editor = self.make_database_rev_editor(database_id, db_pool).await?;
     tracing::trace!("Open database: {}", database_id);
     database_editors.insert(database_id.to_string(), editor.clone());
+    listen_on_database_block_event(
+      database_id.to_owned(),
+      editor.subscribe_block_event(),
+      self.relation_delegate.clone(),
+    );
+    listen_on_database_search_index(Arc::downgrade(&editor), editor.subscribe_block_event());
+    // Release the lock before refreshing, the related databases are read through the editors.
+    drop(database_editors);
+    if let Err(err) = editor.refresh_rollup_cells().await {
+      tracing::error!(
+        "Refresh the rollup cells of {} failed: {:?}",
+        database_id,
+        err
+      );
+    }
     Ok(editor)
   }
 
+  /// Reads the database from the opened editor. Otherwise, the database is loaded from its
+  /// revisions without opening an editor, so it's not kept in memory after reading.
+  async fn get_related_database(&self, database_id: &str) -> FlowyResult<RelatedDatabase> {
+    let editor = self.database_editors.read().await.get(database_id);
+    if let Some(editor) = editor {
+      return editor.get_related_database().await;
+    }
+
+    let _ = self.migration.run_v1_migration(database_id).await;
+    let pool = self.database_user.db_pool()?;
+    let revisions = self
+      .make_database_rev_manager(database_id, pool)?
+      .load_revisions()
+      .await?;
+    let database_pad = DatabaseRevisionPad::from_revisions(revisions)?;
+    let mut row_revs = vec![];
+    for block_meta_rev in database_pad.get_block_meta_revs() {
+      let revisions =
+        make_database_block_rev_manager(&self.database_user, &block_meta_rev.block_id)?
+          .load_revisions()
+          .await?;
+      let block_pad = DatabaseBlockRevisionPad::from_revisions(revisions)?;
+      row_revs.extend(block_pad.get_row_revs::<str>(None)?);
+    }
+    Ok(RelatedDatabase {
+      field_revs: database_pad.get_field_revs(None)?,
+      row_revs,
+    })
+  }
+
+  pub async fn receive_ws_data(&self, data: Bytes) {
+    let result: Result<ServerRevisionWSData, serde_json::Error> =
+      ServerRevisionWSData::try_from(data);
+    match result {
+      Ok(data) => {
+        let editors = self.database_editors.read().await.values();
+        for editor in editors {
+          match editor.receive_ws_data(data.clone()).await {
+            Ok(true) => return,
+            Ok(false) => {},
+            Err(e) => {
+              tracing::error!("{}", e);
+              return;
+            },
+          }
+        }
+        tracing::error!(
+          "Can't find any source handler for {:?}-{:?}",
+          data.object_id,
+          data.payload
+        );
+      },
+      Err(e) => {
+        tracing::error!("Database ws data parser failed: {:?}", e);
+      },
+    }
+  }
+
   #[tracing::instrument(level = "trace", skip(self, pool), err)]
   async fn make_database_rev_editor(
     &self,
@@ -189,7 +320,10 @@ impl DatabaseManager {
       database_pad,
       rev_manager,
       self.block_index_cache.clone(),
+      self.search_index.clone(),
       self.task_scheduler.clone(),
+      self.relation_delegate.clone(),
+      self.rev_web_socket.clone(),
     )
     .await?;
     Ok(database_editor)
@@ -226,6 +360,86 @@ impl DatabaseManager {
   }
 }
 
+struct DatabaseRelationDelegateImpl(Weak<DatabaseManager>);
+
+#[async_trait]
+impl DatabaseRelationDelegate for DatabaseRelationDelegateImpl {
+  async fn get_related_database(&self, database_id: &str) -> FlowyResult<RelatedDatabase> {
+    match self.0.upgrade() {
+      None => Err(FlowyError::internal().context("The database manager was dropped")),
+      Some(manager) => manager.get_related_database(database_id).await,
+    }
+  }
+
+  async fn did_update_rows(&self, database_id: &str, row_ids: Vec<String>) {
+    let editors = match self.0.upgrade() {
+      None => return,
+      Some(manager) => manager.database_editors.read().await.values(),
+    };
+    for editor in editors {
+      if let Err(err) = editor.did_update_related_rows(database_id, &row_ids).await {
+        tracing::error!(
+          "Update the rows related to {} failed: {:?}",
+          database_id,
+          err
+        );
+      }
+    }
+  }
+}
+
+/// Refreshes the rollup cells of the opened databases when the rows of the database are changed.
+/// The closed databases refresh their rollup cells when they are opened.
+fn listen_on_database_block_event(
+  database_id: String,
+  mut block_event_rx: broadcast::Receiver<DatabaseBlockEvent>,
+  relation_delegate: Arc<dyn DatabaseRelationDelegate>,
+) {
+  tokio::spawn(async move {
+    loop {
+      let row_id = match block_event_rx.recv().await {
+        Ok(DatabaseBlockEvent::UpdateRow { row, .. }) => row.row.id,
+        Ok(DatabaseBlockEvent::DeleteRow { row_id, .. }) => row_id,
+        Ok(_) | Err(RecvError::Lagged(_)) => continue,
+        Err(RecvError::Closed) => break,
+      };
+      relation_delegate
+        .did_update_rows(&database_id, vec![row_id])
+        .await;
+    }
+  });
+}
+
+/// Keeps the search index of the database up to date with the changes of its rows. The database
+/// is indexed first if it has not been indexed yet.
+fn listen_on_database_search_index(
+  editor: Weak<DatabaseRevisionEditor>,
+  mut block_event_rx: broadcast::Receiver<DatabaseBlockEvent>,
+) {
+  tokio::spawn(async move {
+    if let Some(editor) = editor.upgrade() {
+      if let Err(err) = editor.initialize_search_index().await {
+        tracing::error!("Index the database {} failed: {}", editor.database_id, err);
+      }
+    }
+
+    loop {
+      let event = match block_event_rx.recv().await {
+        Ok(event) => event,
+        Err(RecvError::Lagged(_)) => continue,
+        Err(RecvError::Closed) => break,
+      };
+      let editor = match editor.upgrade() {
+        None => break,
+        Some(editor) => editor,
+      };
+      if let Err(err) = editor.update_search_index(event).await {
+        tracing::error!("Update the search index failed: {}", err);
+      }
+    }
+  });
+}
+
 pub async fn make_database_view_data(
   _user_id: &str,
   view_id: &str,
@@ -260,6 +474,20 @@ pub async fn make_database_view_data(
 
   // Will replace the grid_id with the value returned by the gen_grid_id()
   let database_id = view_id.to_owned();
+
+  // Index the cells, so the created, duplicated or imported database can be searched before it's
+  // opened.
+  let row_revs = blocks
+    .iter()
+    .flat_map(|block| block.rows.iter().cloned())
+    .collect::<Vec<Arc<RowRevision>>>();
+  let records = make_database_search_records(&database_id, &field_revs, &row_revs, None);
+  if let Err(err) = database_manager
+    .search_index
+    .replace_cells(&database_id, records)
+  {
+    tracing::error!("Index the database {} failed: {}", database_id, err);
+  }
   let database_rev = DatabaseRevision::from_build_context(&database_id, field_revs, block_metas);
 
   // Create grid
@@ -274,7 +502,11 @@ pub async fn make_database_view_data(
   let grid_view = if grid_view_revision_data.is_empty() {
     DatabaseViewRevision::new(database_id, view_id.to_owned(), layout.into())
   } else {
-    DatabaseViewRevision::from_json(grid_view_revision_data)?
+    // The duplicated view data still refers to the original view.
+    let mut grid_view = DatabaseViewRevision::from_json(grid_view_revision_data)?;
+    grid_view.view_id = view_id.to_owned();
+    grid_view.database_id = database_id;
+    grid_view
   };
   let database_view_ops = make_grid_view_operations(&grid_view);
   let database_view_bytes = database_view_ops.json_bytes();
@@ -29,8 +29,14 @@ pub enum DatabaseNotification {
   DidReorderRows = 65,
   /// Trigger after editing the row that hit the sort rule
   DidReorderSingleRow = 66,
+  /// Trigger after inserting/deleting/updating a calculation
+  DidUpdateCalculations = 67,
+  /// Trigger after the values of the calculations are changed
+  DidUpdateCalculationValues = 68,
   /// Trigger when the settings of the database are changed
   DidUpdateSettings = 70,
+  /// Trigger after the calendar layout setting is changed
+  DidUpdateCalendarSettings = 80,
 }
 
 impl std::default::Default for DatabaseNotification {
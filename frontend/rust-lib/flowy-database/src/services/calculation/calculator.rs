@@ -0,0 +1,143 @@
+use crate::entities::FieldType;
+use crate::services::cell::stringify_cell_data;
+use crate::services::field::{
+  formula_value_from_cell, formula_value_type, FormulaValue, FormulaValueType,
+};
+use database_model::{CalculationType, FieldRevision, RowRevision};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Calculates the value of the field over the rows. Returns the formatted value that is displayed
+/// at the bottom of the column, or an empty string if the calculation doesn't support the type of
+/// the field.
+///
+/// # Arguments
+///
+/// * `calculation_type`: the calculation of the field
+/// * `field_rev`: the field whose cells are calculated
+/// * `row_revs`: the rows that are visible after applying the filters
+///
+pub fn calculate_field(
+  calculation_type: &CalculationType,
+  field_rev: &FieldRevision,
+  row_revs: &[Arc<RowRevision>],
+) -> String {
+  let values = row_revs
+    .iter()
+    .map(|row_rev| formula_value_from_cell(field_rev, row_rev.cells.get(&field_rev.id)))
+    .collect::<Vec<FormulaValue>>();
+  let non_empty_values = || values.iter().filter(|value| !value.is_empty());
+  let value_type = formula_value_type(field_rev);
+
+  match calculation_type {
+    CalculationType::Count => values.len().to_string(),
+    CalculationType::CountEmpty => values
+      .iter()
+      .filter(|value| value.is_empty())
+      .count()
+      .to_string(),
+    CalculationType::CountUnique => non_empty_values()
+      .map(|value| value.as_text())
+      .collect::<HashSet<String>>()
+      .len()
+      .to_string(),
+    CalculationType::Sum
+    | CalculationType::Average
+    | CalculationType::Median
+    | CalculationType::Min
+    | CalculationType::Max => {
+      if value_type != FormulaValueType::Number {
+        return "".to_owned();
+      }
+      let mut numbers = non_empty_values()
+        .flat_map(|value| value.as_number())
+        .collect::<Vec<Decimal>>();
+      numbers.sort();
+      let number = match calculation_type {
+        CalculationType::Sum => sum(&numbers),
+        CalculationType::Average => average(&numbers),
+        CalculationType::Median => median(&numbers),
+        CalculationType::Min => numbers.first().cloned(),
+        _ => numbers.last().cloned(),
+      };
+      match number {
+        None => "".to_owned(),
+        Some(number) => format_number(number, field_rev),
+      }
+    },
+    CalculationType::EarliestDate | CalculationType::LatestDate => {
+      if value_type != FormulaValueType::Date {
+        return "".to_owned();
+      }
+      let timestamps = non_empty_values().flat_map(|value| match value {
+        FormulaValue::Date(timestamp) => Some(*timestamp),
+        _ => None,
+      });
+      let timestamp = if calculation_type == &CalculationType::EarliestDate {
+        timestamps.min()
+      } else {
+        timestamps.max()
+      };
+      match timestamp {
+        None => "".to_owned(),
+        Some(timestamp) => stringify_cell_data(
+          timestamp.to_string(),
+          &FieldType::DateTime,
+          &FieldType::DateTime,
+          field_rev,
+        ),
+      }
+    },
+    CalculationType::PercentChecked => {
+      if value_type != FormulaValueType::Bool || values.is_empty() {
+        return "".to_owned();
+      }
+      let checked = values.iter().filter(|value| value.as_bool()).count();
+      let percent = Decimal::from(checked * 100) / Decimal::from(values.len());
+      format!("{}%", percent.round_dp(2).normalize())
+    },
+  }
+}
+
+fn sum(numbers: &[Decimal]) -> Option<Decimal> {
+  if numbers.is_empty() {
+    return None;
+  }
+  numbers
+    .iter()
+    .try_fold(Decimal::ZERO, |acc, num| acc.checked_add(*num))
+}
+
+fn average(numbers: &[Decimal]) -> Option<Decimal> {
+  let sum = sum(numbers)?;
+  sum
+    .checked_div(Decimal::from(numbers.len()))
+    .map(|average| average.round_dp(2))
+}
+
+/// The numbers must be sorted.
+fn median(numbers: &[Decimal]) -> Option<Decimal> {
+  if numbers.is_empty() {
+    return None;
+  }
+  let mid = numbers.len() / 2;
+  if numbers.len() % 2 == 0 {
+    let sum = numbers[mid - 1].checked_add(numbers[mid])?;
+    sum.checked_div(Decimal::TWO)
+  } else {
+    Some(numbers[mid])
+  }
+}
+
+/// Formats the number with the number format of the field, the formula and rollup fields don't
+/// have a number format.
+fn format_number(number: Decimal, field_rev: &FieldRevision) -> String {
+  let number = number.normalize().to_string();
+  let field_type: FieldType = field_rev.ty.into();
+  if field_type.is_number() {
+    stringify_cell_data(number, &field_type, &field_type, field_rev)
+  } else {
+    number
+  }
+}
@@ -0,0 +1,204 @@
+use crate::entities::{CalculationChangesetNotificationPB, CalculationPB, CalculationValuePB};
+use crate::services::calculation::{calculate_field, CalculationValuesResult};
+use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
+use database_model::{CalculationRevision, FieldRevision, RowRevision};
+use flowy_error::FlowyResult;
+use flowy_task::{QualityOfService, Task, TaskContent, TaskDispatcher};
+use lib_infra::future::Fut;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub trait CalculationDelegate: Send + Sync {
+  fn get_calculation_revs(&self) -> Fut<Vec<Arc<CalculationRevision>>>;
+  /// Returns all the rows after applying grid's filter
+  fn get_row_revs(&self) -> Fut<Vec<Arc<RowRevision>>>;
+  fn get_field_revs(&self, field_ids: Option<Vec<String>>) -> Fut<Vec<Arc<FieldRevision>>>;
+}
+
+/// [CalculationController] calculates the values of the view's calculations in the background.
+/// The values are recalculated when the calculations, the filters or the rows are changed, and
+/// they are sent by the [DatabaseViewChangedNotifier] if they are changed.
+pub struct CalculationController {
+  view_id: String,
+  handler_id: String,
+  delegate: Box<dyn CalculationDelegate>,
+  task_scheduler: Arc<RwLock<TaskDispatcher>>,
+  calculations: Vec<Arc<CalculationRevision>>,
+  values: Vec<CalculationValuePB>,
+  notifier: DatabaseViewChangedNotifier,
+}
+
+impl CalculationController {
+  pub fn new<T>(
+    view_id: &str,
+    handler_id: &str,
+    calculations: Vec<Arc<CalculationRevision>>,
+    delegate: T,
+    task_scheduler: Arc<RwLock<TaskDispatcher>>,
+    notifier: DatabaseViewChangedNotifier,
+  ) -> Self
+  where
+    T: CalculationDelegate + 'static,
+  {
+    Self {
+      view_id: view_id.to_string(),
+      handler_id: handler_id.to_string(),
+      delegate: Box::new(delegate),
+      task_scheduler,
+      calculations,
+      values: vec![],
+      notifier,
+    }
+  }
+
+  pub async fn close(&self) {
+    self
+      .task_scheduler
+      .write()
+      .await
+      .unregister_handler(&self.handler_id)
+      .await;
+  }
+
+  pub async fn did_receive_row_changed(&self) {
+    self
+      .gen_task(
+        CalculationEvent::RowDidChanged,
+        QualityOfService::Background,
+      )
+      .await;
+  }
+
+  pub async fn did_receive_filter_changed(&self) {
+    self
+      .gen_task(
+        CalculationEvent::FilterDidChanged,
+        QualityOfService::Background,
+      )
+      .await;
+  }
+
+  #[tracing::instrument(name = "process_calculation_task", level = "trace", skip_all, err)]
+  pub async fn process(&mut self, predicate: &str) -> FlowyResult<()> {
+    let event_type = CalculationEvent::from_str(predicate).unwrap();
+    let values = self.calculate_values().await;
+    // The values are always sent after changing the calculations, otherwise, they are only sent
+    // if they are changed.
+    if event_type != CalculationEvent::CalculationDidChanged && values == self.values {
+      return Ok(());
+    }
+
+    self.values = values.clone();
+    let _ = self
+      .notifier
+      .send(DatabaseViewChanged::CalculationNotification(
+        CalculationValuesResult {
+          view_id: self.view_id.clone(),
+          values,
+        },
+      ));
+    Ok(())
+  }
+
+  /// Returns the values of all the calculations that calculated over the visible rows.
+  pub async fn calculate_values(&self) -> Vec<CalculationValuePB> {
+    if self.calculations.is_empty() {
+      return vec![];
+    }
+
+    let row_revs = self.delegate.get_row_revs().await;
+    let field_revs = self.delegate.get_field_revs(None).await;
+    self
+      .calculations
+      .iter()
+      .flat_map(|calculation| {
+        let field_rev = field_revs
+          .iter()
+          .find(|field_rev| field_rev.id == calculation.field_id)?;
+        Some(CalculationValuePB {
+          calculation_id: calculation.id.clone(),
+          field_id: calculation.field_id.clone(),
+          calculation_type: calculation.calculation_type.into(),
+          value: calculate_field(&calculation.calculation_type, field_rev, &row_revs),
+        })
+      })
+      .collect()
+  }
+
+  #[tracing::instrument(name = "schedule_calculation_task", level = "trace", skip(self))]
+  async fn gen_task(&self, task_type: CalculationEvent, qos: QualityOfService) {
+    let task_id = self.task_scheduler.read().await.next_task_id();
+    let task = Task::new(
+      &self.handler_id,
+      task_id,
+      TaskContent::Text(task_type.to_string()),
+      qos,
+    );
+    self.task_scheduler.write().await.add_task(task);
+  }
+
+  /// Reloads the calculations after they were inserted, updated or deleted. The calculations
+  /// of the field are reloaded too after switching the field type.
+  #[tracing::instrument(level = "trace", skip(self))]
+  pub async fn did_receive_changes(&mut self) -> CalculationChangesetNotificationPB {
+    let calculations = self.delegate.get_calculation_revs().await;
+    let mut notification = CalculationChangesetNotificationPB::new(self.view_id.clone());
+    for calculation in calculations.iter() {
+      match self
+        .calculations
+        .iter()
+        .find(|old| old.id == calculation.id)
+      {
+        None => notification
+          .insert_calculations
+          .push(CalculationPB::from(calculation.as_ref())),
+        Some(old) if old != calculation => notification
+          .update_calculations
+          .push(CalculationPB::from(calculation.as_ref())),
+        Some(_) => {},
+      }
+    }
+    for old in self.calculations.iter() {
+      if !calculations
+        .iter()
+        .any(|calculation| calculation.id == old.id)
+      {
+        notification
+          .delete_calculations
+          .push(CalculationPB::from(old.as_ref()));
+      }
+    }
+
+    self.calculations = calculations;
+    self
+      .gen_task(
+        CalculationEvent::CalculationDidChanged,
+        QualityOfService::UserInteractive,
+      )
+      .await;
+    tracing::trace!("calculation notification: {:?}", notification);
+    notification
+  }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+enum CalculationEvent {
+  CalculationDidChanged,
+  FilterDidChanged,
+  RowDidChanged,
+}
+
+impl ToString for CalculationEvent {
+  fn to_string(&self) -> String {
+    serde_json::to_string(self).unwrap()
+  }
+}
+
+impl FromStr for CalculationEvent {
+  type Err = serde_json::Error;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    serde_json::from_str(s)
+  }
+}
@@ -0,0 +1,7 @@
+use crate::entities::CalculationValuePB;
+
+#[derive(Clone)]
+pub struct CalculationValuesResult {
+  pub view_id: String,
+  pub values: Vec<CalculationValuePB>,
+}
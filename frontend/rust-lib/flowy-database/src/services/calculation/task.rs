@@ -0,0 +1,47 @@
+use crate::services::calculation::CalculationController;
+use flowy_task::{TaskContent, TaskHandler};
+use lib_infra::future::BoxResultFuture;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub struct CalculationTaskHandler {
+  handler_id: String,
+  calculation_controller: Arc<RwLock<CalculationController>>,
+}
+
+impl CalculationTaskHandler {
+  pub fn new(
+    handler_id: String,
+    calculation_controller: Arc<RwLock<CalculationController>>,
+  ) -> Self {
+    Self {
+      handler_id,
+      calculation_controller,
+    }
+  }
+}
+
+impl TaskHandler for CalculationTaskHandler {
+  fn handler_id(&self) -> &str {
+    &self.handler_id
+  }
+
+  fn handler_name(&self) -> &str {
+    "CalculationTaskHandler"
+  }
+
+  fn run(&self, content: TaskContent) -> BoxResultFuture<(), anyhow::Error> {
+    let calculation_controller = self.calculation_controller.clone();
+    Box::pin(async move {
+      if let TaskContent::Text(predicate) = content {
+        calculation_controller
+          .write()
+          .await
+          .process(&predicate)
+          .await
+          .map_err(anyhow::Error::from)?;
+      }
+      Ok(())
+    })
+  }
+}
@@ -3,13 +3,13 @@ use std::any::{type_name, Any};
 
 use std::collections::HashMap;
 
-use crate::services::filter::FilterType;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::sync::Arc;
 
 pub type AtomicCellDataCache = Arc<RwLock<AnyTypeCache<u64>>>;
-pub type AtomicCellFilterCache = Arc<RwLock<AnyTypeCache<FilterType>>>;
+/// The filters of the cells, keyed by the filter id.
+pub type AtomicCellFilterCache = Arc<RwLock<AnyTypeCache<String>>>;
 
 #[derive(Default, Debug)]
 pub struct AnyTypeCache<TypeValueKey>(HashMap<TypeValueKey, TypeValue>);
@@ -5,6 +5,7 @@ use crate::services::field::*;
 use crate::services::group::make_no_status_group;
 use database_model::{CellRevision, FieldRevision};
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use rust_decimal::Decimal;
 
 use std::fmt::Debug;
 
@@ -223,6 +224,11 @@ pub fn insert_number_cell(num: i64, field_rev: &FieldRevision) -> CellRevision {
   CellRevision::new(data)
 }
 
+pub fn insert_decimal_cell(num: Decimal, field_rev: &FieldRevision) -> CellRevision {
+  let data = apply_cell_data_changeset(num.to_string(), None, field_rev, None).unwrap();
+  CellRevision::new(data)
+}
+
 pub fn insert_url_cell(url: String, field_rev: &FieldRevision) -> CellRevision {
   // checking if url is equal to group id of no status group because everywhere
   // except group of rows with empty url the group id is equal to the url
@@ -1,14 +1,16 @@
 use crate::services::database::retry::GetRowDataRetryAction;
+use crate::services::database::DatabaseHistoryRef;
 use bytes::Bytes;
 use database_model::{CellRevision, DatabaseBlockRevision, RowChangeset, RowRevision};
 use flowy_client_sync::client_database::{
-  DatabaseBlockRevisionChangeset, DatabaseBlockRevisionPad,
+  DatabaseBlockOperations, DatabaseBlockRevisionChangeset, DatabaseBlockRevisionPad,
+  DatabaseHistoryOperations,
 };
 use flowy_client_sync::make_operations_from_revisions;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_revision::{
   RevisionCloudService, RevisionManager, RevisionMergeable, RevisionObjectDeserializer,
-  RevisionObjectSerializer,
+  RevisionObjectSerializer, RevisionWebSocket,
 };
 use flowy_sqlite::ConnectionPool;
 use lib_infra::future::FutureResult;
@@ -16,8 +18,10 @@ use lib_infra::retry::spawn_retry;
 use lib_ot::core::EmptyAttributes;
 use revision_model::Revision;
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use ws_model::ws_revision::ServerRevisionWSData;
 
 pub struct DatabaseBlockRevisionEditor {
   #[allow(dead_code)]
@@ -25,14 +29,20 @@ pub struct DatabaseBlockRevisionEditor {
   pub block_id: String,
   pad: Arc<RwLock<DatabaseBlockRevisionPad>>,
   rev_manager: Arc<RevisionManager<Arc<ConnectionPool>>>,
+  history: DatabaseHistoryRef,
+  #[cfg(feature = "sync")]
+  ws_manager: Arc<flowy_revision::RevisionWebSocketManager>,
 }
 
 impl DatabaseBlockRevisionEditor {
+  #[allow(unused_variables)]
   pub async fn new(
     user_id: &str,
     token: &str,
     block_id: &str,
     mut rev_manager: RevisionManager<Arc<ConnectionPool>>,
+    web_socket: Arc<dyn RevisionWebSocket>,
+    history: DatabaseHistoryRef,
   ) -> FlowyResult<Self> {
     let cloud = Arc::new(DatabaseBlockRevisionCloudService {
       token: token.to_owned(),
@@ -42,6 +52,17 @@ impl DatabaseBlockRevisionEditor {
       .await?;
     let pad = Arc::new(RwLock::new(block_revision_pad));
     let rev_manager = Arc::new(rev_manager);
+
+    #[cfg(feature = "sync")]
+    let ws_manager = crate::services::web_socket::make_database_ws_manager(
+      "DatabaseBlock",
+      block_id,
+      rev_manager.clone(),
+      web_socket,
+      pad.clone(),
+      Some(history.clone()),
+    );
+
     let user_id = user_id.to_owned();
     let block_id = block_id.to_owned();
     Ok(Self {
@@ -49,16 +70,44 @@ impl DatabaseBlockRevisionEditor {
       block_id,
       pad,
       rev_manager,
+      history,
+      #[cfg(feature = "sync")]
+      ws_manager,
     })
   }
 
   pub async fn close(&self) {
+    #[cfg(feature = "sync")]
+    self.ws_manager.stop();
     self.rev_manager.generate_snapshot().await;
     self.rev_manager.close().await;
   }
 
-  pub async fn duplicate_block(&self, duplicated_block_id: &str) -> DatabaseBlockRevision {
-    self.pad.read().await.duplicate_data(duplicated_block_id)
+  #[cfg(feature = "flowy_unit_test")]
+  pub fn rev_manager(&self) -> Arc<RevisionManager<Arc<ConnectionPool>>> {
+    self.rev_manager.clone()
+  }
+
+  #[cfg(feature = "sync")]
+  pub async fn receive_ws_data(&self, data: ServerRevisionWSData) -> FlowyResult<()> {
+    self.ws_manager.receive_ws_data(data).await
+  }
+
+  #[cfg(not(feature = "sync"))]
+  pub async fn receive_ws_data(&self, _data: ServerRevisionWSData) -> FlowyResult<()> {
+    Ok(())
+  }
+
+  pub async fn duplicate_block(
+    &self,
+    duplicated_block_id: &str,
+    id_map: &mut HashMap<String, String>,
+  ) -> DatabaseBlockRevision {
+    self
+      .pad
+      .read()
+      .await
+      .duplicate_data(duplicated_block_id, id_map)
   }
 
   /// Create a row after the the with prev_row_id. If prev_row_id is None, the row will be appended to the list
@@ -175,16 +224,73 @@ impl DatabaseBlockRevisionEditor {
     match changeset {
       None => {},
       Some(changeset) => {
+        let operations = DatabaseHistoryOperations::Block {
+          block_id: self.block_id.clone(),
+          operations: changeset.operations.clone(),
+        };
+        let inverted_operations = DatabaseHistoryOperations::Block {
+          block_id: self.block_id.clone(),
+          operations: changeset.inverted_operations.clone(),
+        };
         self.apply_change(changeset).await?;
+        self
+          .history
+          .lock()
+          .record_operations(operations, inverted_operations);
       },
     }
     Ok(())
   }
 
+  /// Returns the pad of the latest snapshot that was taken at or before the timestamp.
+  pub(crate) async fn read_snapshot_pad(
+    &self,
+    timestamp: i64,
+  ) -> FlowyResult<Option<DatabaseBlockRevisionPad>> {
+    let snapshot = self
+      .rev_manager
+      .read_snapshots()
+      .await?
+      .into_iter()
+      .find(|snapshot| snapshot.timestamp <= timestamp);
+    match snapshot {
+      None => Ok(None),
+      Some(snapshot) => {
+        let pad = self
+          .rev_manager
+          .read_snapshot_object::<DatabaseBlockRevisionSerde>(snapshot.rev_id)
+          .await?;
+        Ok(Some(pad))
+      },
+    }
+  }
+
+  /// Replaces all the rows of the block. The change is saved as a new revision.
+  pub(crate) async fn reset_rows(&self, row_revs: Vec<Arc<RowRevision>>) -> FlowyResult<i32> {
+    self
+      .modify(|block_pad| Ok(block_pad.reset_rows(row_revs)?))
+      .await?;
+    Ok(self.number_of_rows().await)
+  }
+
+  /// Applies the operations of the undo history without recording them. Returns the operations
+  /// that revert them.
+  pub(crate) async fn apply_history_operations(
+    &self,
+    operations: DatabaseBlockOperations,
+  ) -> FlowyResult<DatabaseBlockOperations> {
+    let mut write_guard = self.pad.write().await;
+    let changeset = write_guard.apply_operations(operations)?;
+    let inverted_operations = changeset.inverted_operations.clone();
+    self.apply_change(changeset).await?;
+    Ok(inverted_operations)
+  }
+
   async fn apply_change(&self, change: DatabaseBlockRevisionChangeset) -> FlowyResult<()> {
     let DatabaseBlockRevisionChangeset {
       operations: delta,
       md5,
+      ..
     } = change;
     let data = delta.json_bytes();
     let _ = self.rev_manager.add_local_revision(data, md5).await?;
@@ -1,7 +1,9 @@
 use crate::entities::{CellChangesetPB, InsertedRowPB, UpdatedRowPB};
 use crate::manager::DatabaseUser;
 use crate::notification::{send_notification, DatabaseNotification};
-use crate::services::database::{DatabaseBlockRevisionEditor, DatabaseBlockRevisionMergeable};
+use crate::services::database::{
+  DatabaseBlockRevisionEditor, DatabaseBlockRevisionMergeable, DatabaseHistoryRef,
+};
 use crate::services::persistence::block_index::BlockIndexCache;
 use crate::services::persistence::rev_sqlite::{
   SQLiteDatabaseBlockRevisionPersistence, SQLiteDatabaseRevisionSnapshotPersistence,
@@ -11,13 +13,17 @@ use dashmap::DashMap;
 use database_model::{
   DatabaseBlockMetaRevision, DatabaseBlockMetaRevisionChangeset, RowChangeset, RowRevision,
 };
-use flowy_error::FlowyResult;
-use flowy_revision::{RevisionManager, RevisionPersistence, RevisionPersistenceConfiguration};
+use flowy_client_sync::client_database::{DatabaseBlockOperations, DatabaseBlockRevisionPad};
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_revision::{
+  RevisionManager, RevisionPersistence, RevisionPersistenceConfiguration, RevisionWebSocket,
+};
 use flowy_sqlite::ConnectionPool;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::broadcast;
+use ws_model::ws_revision::ServerRevisionWSData;
 
 #[derive(Debug, Clone)]
 pub enum DatabaseBlockEvent {
@@ -46,6 +52,17 @@ pub(crate) struct DatabaseBlockManager {
   persistence: Arc<BlockIndexCache>,
   block_editors: DashMap<BlockId, Arc<DatabaseBlockRevisionEditor>>,
   event_notifier: broadcast::Sender<DatabaseBlockEvent>,
+  history: DatabaseHistoryRef,
+  web_socket: Arc<dyn RevisionWebSocket>,
+}
+
+/// The rows of a block that were changed without going through the row APIs, for example, by
+/// undoing an action or restoring a snapshot.
+pub(crate) struct DatabaseBlockRowsChangeset {
+  /// The ids of the inserted or updated rows, with their revisions before the change. The
+  /// revision is None if the row was inserted.
+  pub(crate) updated_rows: Vec<(Option<Arc<RowRevision>>, String)>,
+  pub(crate) deleted_rows: Vec<Arc<RowRevision>>,
 }
 
 impl DatabaseBlockManager {
@@ -54,14 +71,18 @@ impl DatabaseBlockManager {
     block_meta_revs: Vec<Arc<DatabaseBlockMetaRevision>>,
     persistence: Arc<BlockIndexCache>,
     event_notifier: broadcast::Sender<DatabaseBlockEvent>,
+    history: DatabaseHistoryRef,
+    web_socket: Arc<dyn RevisionWebSocket>,
   ) -> FlowyResult<Self> {
-    let block_editors = make_block_editors(user, block_meta_revs).await?;
+    let block_editors = make_block_editors(user, block_meta_revs, &history, &web_socket).await?;
     let user = user.clone();
     let manager = Self {
       user,
       block_editors,
       persistence,
       event_notifier,
+      history,
+      web_socket,
     };
     Ok(manager)
   }
@@ -72,6 +93,10 @@ impl DatabaseBlockManager {
     }
   }
 
+  pub(crate) fn subscribe_event(&self) -> broadcast::Receiver<DatabaseBlockEvent> {
+    self.event_notifier.subscribe()
+  }
+
   // #[tracing::instrument(level = "trace", skip(self))]
   pub(crate) async fn get_or_create_block_editor(
     &self,
@@ -84,7 +109,9 @@ impl DatabaseBlockManager {
           "This is a fatal error, block with id:{} is not exist",
           block_id
         );
-        let editor = Arc::new(make_database_block_editor(&self.user, block_id).await?);
+        let editor = Arc::new(
+          make_database_block_editor(&self.user, block_id, &self.history, &self.web_socket).await?,
+        );
         self
           .block_editors
           .insert(block_id.to_owned(), editor.clone());
@@ -98,8 +125,23 @@ impl DatabaseBlockManager {
     &self,
     row_id: &str,
   ) -> FlowyResult<Arc<DatabaseBlockRevisionEditor>> {
-    let block_id = self.persistence.get_block_id(row_id)?;
-    self.get_or_create_block_editor(&block_id).await
+    if let Ok(block_id) = self.persistence.get_block_id(row_id) {
+      return self.get_or_create_block_editor(&block_id).await;
+    }
+
+    // The rows that were synced from the other clients are not indexed yet.
+    let editors = self
+      .block_editors
+      .iter()
+      .map(|entry| (entry.key().clone(), entry.value().clone()))
+      .collect::<Vec<_>>();
+    for (block_id, editor) in editors {
+      if editor.get_row_rev(row_id).await?.is_some() {
+        self.persistence.insert(&block_id, row_id)?;
+        return Ok(editor);
+      }
+    }
+    Err(FlowyError::record_not_found().context(format!("Can't find the row: {}", row_id)))
   }
 
   #[tracing::instrument(level = "trace", skip(self, start_row_id), err)]
@@ -260,7 +302,6 @@ impl DatabaseBlockManager {
     editor.get_row_rev(row_id).await
   }
 
-  #[allow(dead_code)]
   pub async fn get_row_revs(&self) -> FlowyResult<Vec<Arc<RowRevision>>> {
     let mut row_revs = vec![];
     for iter in self.block_editors.iter() {
@@ -295,6 +336,197 @@ impl DatabaseBlockManager {
     Ok(blocks)
   }
 
+  /// Applies the operations of the undo history to the block. Returns the operations that revert
+  /// them and the changed rows.
+  pub(crate) async fn apply_history_operations(
+    &self,
+    block_id: &str,
+    operations: DatabaseBlockOperations,
+  ) -> FlowyResult<(DatabaseBlockOperations, DatabaseBlockRowsChangeset)> {
+    let editor = self.get_or_create_block_editor(block_id).await?;
+    let old_row_revs = editor.get_row_revs::<&str>(None).await?;
+    let inverted_operations = editor.apply_history_operations(operations).await?;
+    let new_row_revs = editor.get_row_revs::<&str>(None).await?;
+    let changeset = self.notify_did_change_rows(block_id, old_row_revs, new_row_revs)?;
+    Ok((inverted_operations, changeset))
+  }
+
+  /// Returns the snapshot of each block at the timestamp, which is the latest snapshot of the
+  /// block that was taken at or before the timestamp. Returns an error if any of the blocks has no
+  /// such snapshot, its current rows can't be mixed with the rows of the other snapshots.
+  pub(crate) async fn read_snapshot_pads(
+    &self,
+    block_ids: Vec<String>,
+    timestamp: i64,
+  ) -> FlowyResult<Vec<DatabaseBlockRevisionPad>> {
+    let mut pads = vec![];
+    for block_id in block_ids {
+      let editor = self.get_or_create_block_editor(&block_id).await?;
+      match editor.read_snapshot_pad(timestamp).await? {
+        None => {
+          return Err(FlowyError::record_not_found().context(format!(
+            "The block {} has no snapshot at or before {}",
+            block_id, timestamp
+          )));
+        },
+        Some(pad) => pads.push(pad),
+      }
+    }
+    Ok(pads)
+  }
+
+  /// Restores the rows of each block from its snapshot that is returned by
+  /// [Self::read_snapshot_pads].
+  pub(crate) async fn restore_blocks_from_snapshot(
+    &self,
+    pads: Vec<DatabaseBlockRevisionPad>,
+  ) -> FlowyResult<(
+    Vec<DatabaseBlockMetaRevisionChangeset>,
+    Vec<DatabaseBlockRowsChangeset>,
+  )> {
+    let mut block_changesets = vec![];
+    let mut row_changesets = vec![];
+    for pad in pads {
+      let editor = self.get_or_create_block_editor(&pad.block_id).await?;
+      let old_row_revs = editor.get_row_revs::<&str>(None).await?;
+      let row_count = editor.reset_rows(pad.rows.clone()).await?;
+      let new_row_revs = editor.get_row_revs::<&str>(None).await?;
+      row_changesets.push(self.notify_did_change_rows(
+        &editor.block_id,
+        old_row_revs,
+        new_row_revs,
+      )?);
+      block_changesets.push(DatabaseBlockMetaRevisionChangeset::from_row_count(
+        editor.block_id.clone(),
+        row_count,
+      ));
+    }
+    Ok((block_changesets, row_changesets))
+  }
+
+  /// Passes the web socket data to the block that it belongs to. Returns false if there is no
+  /// such block in the database.
+  pub(crate) async fn receive_ws_data(&self, data: ServerRevisionWSData) -> FlowyResult<bool> {
+    let block_editor = self
+      .block_editors
+      .get(&data.object_id)
+      .map(|editor| editor.clone());
+    match block_editor {
+      None => Ok(false),
+      Some(block_editor) => {
+        block_editor.receive_ws_data(data).await?;
+        Ok(true)
+      },
+    }
+  }
+
+  fn get_block_editors(&self) -> Vec<Arc<DatabaseBlockRevisionEditor>> {
+    self
+      .block_editors
+      .iter()
+      .map(|editor| editor.value().clone())
+      .collect()
+  }
+
+  /// Sends the rows that are inserted, updated, moved or deleted as [DatabaseBlockEvent]s.
+  fn notify_did_change_rows(
+    &self,
+    block_id: &str,
+    old_row_revs: Vec<Arc<RowRevision>>,
+    new_row_revs: Vec<Arc<RowRevision>>,
+  ) -> FlowyResult<DatabaseBlockRowsChangeset> {
+    let old_row_by_id = old_row_revs
+      .iter()
+      .map(|row_rev| (row_rev.id.as_str(), row_rev.clone()))
+      .collect::<HashMap<&str, Arc<RowRevision>>>();
+    let new_row_by_id = new_row_revs
+      .iter()
+      .map(|row_rev| (row_rev.id.as_str(), row_rev.clone()))
+      .collect::<HashMap<&str, Arc<RowRevision>>>();
+
+    let mut updated_rows = vec![];
+    for (index, row_rev) in new_row_revs.iter().enumerate() {
+      match old_row_by_id.get(row_rev.id.as_str()) {
+        None => {
+          self.persistence.insert(block_id, &row_rev.id)?;
+          let row = InsertedRowPB {
+            row: make_row_from_row_rev(row_rev.clone()),
+            index: Some(index as i32),
+            is_new: false,
+          };
+          let _ = self.event_notifier.send(DatabaseBlockEvent::InsertRow {
+            block_id: block_id.to_owned(),
+            row,
+          });
+          updated_rows.push((None, row_rev.id.clone()));
+        },
+        Some(old_row_rev) if old_row_rev != row_rev => {
+          let changed_field_ids = row_rev
+            .cells
+            .iter()
+            .filter(|(field_id, cell_rev)| old_row_rev.cells.get(*field_id) != Some(*cell_rev))
+            .map(|(field_id, _)| field_id.clone())
+            .collect::<Vec<String>>();
+          for field_id in changed_field_ids.iter() {
+            let id = format!("{}:{}", row_rev.id, field_id);
+            send_notification(&id, DatabaseNotification::DidUpdateCell).send();
+          }
+
+          let row = UpdatedRowPB {
+            row: make_row_from_row_rev(row_rev.clone()),
+            field_ids: changed_field_ids,
+          };
+          let _ = self.event_notifier.send(DatabaseBlockEvent::UpdateRow {
+            block_id: block_id.to_owned(),
+            row,
+          });
+          updated_rows.push((Some(old_row_rev.clone()), row_rev.id.clone()));
+        },
+        Some(_) => {},
+      }
+    }
+
+    // The rows that exist before and after the change are moved if their order is changed.
+    let old_row_ids = old_row_revs
+      .iter()
+      .filter(|row_rev| new_row_by_id.contains_key(row_rev.id.as_str()))
+      .map(|row_rev| row_rev.id.as_str());
+    let new_rows = new_row_revs
+      .iter()
+      .enumerate()
+      .filter(|(_, row_rev)| old_row_by_id.contains_key(row_rev.id.as_str()));
+    for (old_row_id, (index, row_rev)) in old_row_ids.zip(new_rows) {
+      if old_row_id != row_rev.id {
+        let inserted_row = InsertedRowPB {
+          row: make_row_from_row_rev(row_rev.clone()),
+          index: Some(index as i32),
+          is_new: false,
+        };
+        let _ = self.event_notifier.send(DatabaseBlockEvent::Move {
+          block_id: block_id.to_owned(),
+          deleted_row_id: row_rev.id.clone(),
+          inserted_row,
+        });
+      }
+    }
+
+    let deleted_rows = old_row_revs
+      .into_iter()
+      .filter(|row_rev| !new_row_by_id.contains_key(row_rev.id.as_str()))
+      .collect::<Vec<Arc<RowRevision>>>();
+    for row_rev in deleted_rows.iter() {
+      let _ = self.event_notifier.send(DatabaseBlockEvent::DeleteRow {
+        block_id: block_id.to_owned(),
+        row_id: row_rev.id.clone(),
+      });
+    }
+
+    Ok(DatabaseBlockRowsChangeset {
+      updated_rows,
+      deleted_rows,
+    })
+  }
+
   async fn notify_did_update_cell(&self, changeset: CellChangesetPB) -> FlowyResult<()> {
     let id = format!("{}:{}", changeset.row_id, changeset.field_id);
     send_notification(&id, DatabaseNotification::DidUpdateCell).send();
@@ -306,10 +538,13 @@ impl DatabaseBlockManager {
 async fn make_block_editors(
   user: &Arc<dyn DatabaseUser>,
   block_meta_revs: Vec<Arc<DatabaseBlockMetaRevision>>,
+  history: &DatabaseHistoryRef,
+  web_socket: &Arc<dyn RevisionWebSocket>,
 ) -> FlowyResult<DashMap<String, Arc<DatabaseBlockRevisionEditor>>> {
   let editor_map = DashMap::new();
   for block_meta_rev in block_meta_revs {
-    let editor = make_database_block_editor(user, &block_meta_rev.block_id).await?;
+    let editor =
+      make_database_block_editor(user, &block_meta_rev.block_id, history, web_socket).await?;
     editor_map.insert(block_meta_rev.block_id.clone(), Arc::new(editor));
   }
 
@@ -319,12 +554,22 @@ async fn make_block_editors(
 async fn make_database_block_editor(
   user: &Arc<dyn DatabaseUser>,
   block_id: &str,
+  history: &DatabaseHistoryRef,
+  web_socket: &Arc<dyn RevisionWebSocket>,
 ) -> FlowyResult<DatabaseBlockRevisionEditor> {
   tracing::trace!("Open block:{} editor", block_id);
   let token = user.token()?;
   let user_id = user.user_id()?;
   let rev_manager = make_database_block_rev_manager(user, block_id)?;
-  DatabaseBlockRevisionEditor::new(&user_id, &token, block_id, rev_manager).await
+  DatabaseBlockRevisionEditor::new(
+    &user_id,
+    &token,
+    block_id,
+    rev_manager,
+    web_socket.clone(),
+    history.clone(),
+  )
+  .await
 }
 
 pub fn make_database_block_rev_manager(
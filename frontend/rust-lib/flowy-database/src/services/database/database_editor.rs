@@ -6,29 +6,40 @@ use crate::services::cell::{
   apply_cell_data_changeset, get_type_cell_protobuf, stringify_cell_data, AnyTypeCache,
   AtomicCellDataCache, CellProtobufBlob, ToCellChangesetString, TypeCellData,
 };
-use crate::services::database::DatabaseBlockManager;
+use crate::services::database::{
+  DatabaseBlockEvent, DatabaseBlockManager, DatabaseBlockRowsChangeset, DatabaseHistoryAction,
+  DatabaseHistoryRef, DatabaseHistoryUntracked, DatabaseRelationDelegate, RelatedDatabase,
+};
 use crate::services::field::{
-  default_type_option_builder_from_type, transform_type_option, type_option_builder_from_bytes,
-  FieldBuilder, RowSingleCellData,
+  calculate_rollup, default_type_option_builder_from_type, remove_cell_data_from_cache,
+  rename_formula_field, transform_type_option, type_option_builder_from_bytes, DateCellChangeset,
+  FieldBuilder, FormulaCalculator, FormulaTypeOptionPB, RelationCellData, RelationTypeOptionPB,
+  RollupTypeOptionPB, RowSingleCellData,
 };
 
 use crate::services::database::DatabaseViewEditorDelegateImpl;
-use crate::services::database_view::{DatabaseViewChanged, DatabaseViewManager};
+use crate::services::database_view::{
+  get_row_timestamp, start_of_day, DatabaseViewChanged, DatabaseViewManager,
+};
 use crate::services::filter::FilterType;
 use crate::services::persistence::block_index::BlockIndexCache;
+use crate::services::persistence::search_index::DatabaseSearchIndex;
 use crate::services::row::{DatabaseBlockRow, DatabaseBlockRowRevision, RowRevisionBuilder};
+use crate::services::share::DatabaseExporter;
 use bytes::Bytes;
 use database_model::*;
 use flowy_client_sync::client_database::{
-  DatabaseRevisionChangeset, DatabaseRevisionPad, JsonDeserializer,
+  DatabaseBlockRevisionPad, DatabaseHistory, DatabaseHistoryItem, DatabaseHistoryOperations,
+  DatabaseOperations, DatabaseRevisionChangeset, DatabaseRevisionPad, JsonDeserializer,
 };
 use flowy_client_sync::errors::{SyncError, SyncResult};
 use flowy_client_sync::make_operations_from_revisions;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_revision::{
   RevisionCloudService, RevisionManager, RevisionMergeable, RevisionObjectDeserializer,
-  RevisionObjectSerializer,
+  RevisionObjectSerializer, RevisionSnapshotData, RevisionWebSocket,
 };
+use flowy_sqlite::search::SearchIndexRecord;
 use flowy_sqlite::ConnectionPool;
 use flowy_task::TaskDispatcher;
 use lib_infra::future::{to_fut, FutureResult};
@@ -37,6 +48,7 @@ use revision_model::Revision;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
+use ws_model::ws_revision::ServerRevisionWSData;
 
 pub struct DatabaseRevisionEditor {
   pub database_id: String,
@@ -45,6 +57,11 @@ pub struct DatabaseRevisionEditor {
   database_view_manager: Arc<DatabaseViewManager>,
   database_block_manager: Arc<DatabaseBlockManager>,
   cell_data_cache: AtomicCellDataCache,
+  relation_delegate: Arc<dyn DatabaseRelationDelegate>,
+  history: DatabaseHistoryRef,
+  search_index: Arc<DatabaseSearchIndex>,
+  #[cfg(feature = "sync")]
+  ws_manager: Arc<flowy_revision::RevisionWebSocketManager>,
 }
 
 impl Drop for DatabaseRevisionEditor {
@@ -60,16 +77,28 @@ impl DatabaseRevisionEditor {
     database_pad: Arc<RwLock<DatabaseRevisionPad>>,
     rev_manager: RevisionManager<Arc<ConnectionPool>>,
     persistence: Arc<BlockIndexCache>,
+    search_index: Arc<DatabaseSearchIndex>,
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
+    relation_delegate: Arc<dyn DatabaseRelationDelegate>,
+    web_socket: Arc<dyn RevisionWebSocket>,
   ) -> FlowyResult<Arc<Self>> {
     let rev_manager = Arc::new(rev_manager);
     let cell_data_cache = AnyTypeCache::<u64>::new();
+    let history = Arc::new(parking_lot::Mutex::new(DatabaseHistory::new()));
 
     // Block manager
     let (block_event_tx, block_event_rx) = broadcast::channel(100);
     let block_meta_revs = database_pad.read().await.get_block_meta_revs();
     let database_block_manager = Arc::new(
-      DatabaseBlockManager::new(&user, block_meta_revs, persistence, block_event_tx).await?,
+      DatabaseBlockManager::new(
+        &user,
+        block_meta_revs,
+        persistence,
+        block_event_tx,
+        history.clone(),
+        web_socket.clone(),
+      )
+      .await?,
     );
     let delegate = Arc::new(DatabaseViewEditorDelegateImpl {
       pad: database_pad.clone(),
@@ -86,10 +115,21 @@ impl DatabaseRevisionEditor {
         delegate,
         cell_data_cache.clone(),
         block_event_rx,
+        web_socket.clone(),
       )
       .await?,
     );
 
+    #[cfg(feature = "sync")]
+    let ws_manager = crate::services::web_socket::make_database_ws_manager(
+      "Database",
+      database_id,
+      rev_manager.clone(),
+      web_socket,
+      database_pad.clone(),
+      Some(history.clone()),
+    );
+
     let editor = Arc::new(Self {
       database_id: database_id.to_owned(),
       database_pad,
@@ -97,6 +137,11 @@ impl DatabaseRevisionEditor {
       database_block_manager,
       database_view_manager,
       cell_data_cache,
+      relation_delegate,
+      history,
+      search_index,
+      #[cfg(feature = "sync")]
+      ws_manager,
     });
 
     Ok(editor)
@@ -104,12 +149,48 @@ impl DatabaseRevisionEditor {
 
   #[tracing::instrument(name = "close database editor", level = "trace", skip_all)]
   pub async fn close(&self) {
+    #[cfg(feature = "sync")]
+    self.ws_manager.stop();
     self.database_block_manager.close().await;
     self.rev_manager.generate_snapshot().await;
     self.rev_manager.close().await;
     self.database_view_manager.close(&self.database_id).await;
   }
 
+  /// Dispatches the data that was pushed by the server to the database, its blocks or its views.
+  /// Returns false if the data doesn't belong to this database.
+  pub async fn receive_ws_data(&self, data: ServerRevisionWSData) -> FlowyResult<bool> {
+    if data.object_id == self.database_id {
+      self.receive_database_ws_data(data).await?;
+      return Ok(true);
+    }
+
+    if self
+      .database_block_manager
+      .receive_ws_data(data.clone())
+      .await?
+    {
+      return Ok(true);
+    }
+    self.database_view_manager.receive_ws_data(data).await
+  }
+
+  #[cfg(feature = "sync")]
+  async fn receive_database_ws_data(&self, data: ServerRevisionWSData) -> FlowyResult<()> {
+    self.ws_manager.receive_ws_data(data).await
+  }
+
+  #[cfg(not(feature = "sync"))]
+  async fn receive_database_ws_data(&self, _data: ServerRevisionWSData) -> FlowyResult<()> {
+    Ok(())
+  }
+
+  /// Subscribes the changes of the rows. The other databases use it to refresh their rollup
+  /// cells that aggregate the rows of this database.
+  pub fn subscribe_block_event(&self) -> broadcast::Receiver<DatabaseBlockEvent> {
+    self.database_block_manager.subscribe_event()
+  }
+
   /// Save the type-option data to disk and send a `DatabaseNotification::DidUpdateField` notification
   /// to dart side.
   ///
@@ -126,12 +207,14 @@ impl DatabaseRevisionEditor {
     type_option_data: Vec<u8>,
     old_field_rev: Option<Arc<FieldRevision>>,
   ) -> FlowyResult<()> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
     let result = self.get_field_rev(field_id).await;
     if result.is_none() {
       tracing::warn!("Can't find the field with id: {}", field_id);
       return Ok(());
     }
     let field_rev = result.unwrap();
+    let field_revs = self.get_field_revs(None).await?;
     self
       .modify(|pad| {
         let changeset = pad.modify_field(field_id, |field| {
@@ -139,6 +222,13 @@ impl DatabaseRevisionEditor {
           match deserializer.deserialize(type_option_data) {
             Ok(json_str) => {
               let field_type = field.ty;
+              let json_str = if FieldType::from(field_type).is_formula() {
+                let mut type_option = FormulaTypeOptionPB::from_json_str(&json_str);
+                type_option.update_result_type(&field_revs);
+                type_option.json_str()
+              } else {
+                json_str
+              };
               field.insert_type_option_str(&field_type, json_str);
             },
             Err(err) => {
@@ -151,11 +241,16 @@ impl DatabaseRevisionEditor {
       })
       .await?;
 
+    self
+      .did_update_formula_dependency(field_id, &field_rev.name, None)
+      .await?;
+    self.calculate_all_rollup_cells(field_id).await?;
     self
       .database_view_manager
       .did_update_view_field_type_option(field_id, old_field_rev)
       .await?;
     self.notify_did_update_database_field(field_id).await?;
+    self.index_field_cells(field_id).await;
     Ok(())
   }
 
@@ -203,7 +298,11 @@ impl DatabaseRevisionEditor {
   }
 
   pub async fn update_field(&self, params: FieldChangesetParams) -> FlowyResult<()> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
     let field_id = params.field_id.clone();
+    let old_field_rev = self.get_field_rev(&field_id).await;
+    let new_name = params.name.clone();
+    let is_type_changed = params.field_type.is_some();
     self
       .modify(|pad| {
         let changeset = pad.modify_field(&params.field_id, |field| {
@@ -231,6 +330,15 @@ impl DatabaseRevisionEditor {
       })
       .await?;
     self.notify_did_update_database_field(&field_id).await?;
+
+    if let Some(old_field_rev) = old_field_rev {
+      let new_name = new_name.filter(|name| name != &old_field_rev.name);
+      if new_name.is_some() || is_type_changed {
+        self
+          .did_update_formula_dependency(&field_id, &old_field_rev.name, new_name.as_deref())
+          .await?;
+      }
+    }
     Ok(())
   }
 
@@ -265,12 +373,22 @@ impl DatabaseRevisionEditor {
   }
 
   pub async fn delete_field(&self, field_id: &str) -> FlowyResult<()> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
+    let old_field_rev = self.get_field_rev(field_id).await;
     self
       .modify(|pad| Ok(pad.delete_field_rev(field_id)?))
       .await?;
     let field_order = FieldIdPB::from(field_id);
     let notified_changeset = DatabaseFieldChangesetPB::delete(&self.database_id, vec![field_order]);
     self.notify_did_update_database(notified_changeset).await?;
+    if let Some(old_field_rev) = old_field_rev {
+      self
+        .did_update_formula_dependency(field_id, &old_field_rev.name, None)
+        .await?;
+    }
+    if let Err(e) = self.search_index.delete_field(&self.database_id, field_id) {
+      tracing::error!("Delete the field from the search index failed: {}", e);
+    }
     Ok(())
   }
 
@@ -295,7 +413,8 @@ impl DatabaseRevisionEditor {
     field_id: &str,
     new_field_type: &FieldType,
   ) -> FlowyResult<()> {
-    //
+    let _action = DatabaseHistoryAction::begin(&self.history);
+    let old_field_rev = self.get_field_rev(field_id).await;
     let make_default_type_option = || -> String {
       return default_type_option_builder_from_type(new_field_type)
         .serializer()
@@ -326,6 +445,12 @@ impl DatabaseRevisionEditor {
       .await?;
 
     self.notify_did_update_database_field(field_id).await?;
+    if let Some(old_field_rev) = old_field_rev {
+      self
+        .did_update_formula_dependency(field_id, &old_field_rev.name, None)
+        .await?;
+    }
+    self.index_field_cells(field_id).await;
 
     Ok(())
   }
@@ -397,6 +522,7 @@ impl DatabaseRevisionEditor {
   }
 
   pub async fn create_row(&self, params: CreateRowParams) -> FlowyResult<RowPB> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
     let mut row_rev = self.create_row_rev().await?;
 
     self
@@ -422,6 +548,7 @@ impl DatabaseRevisionEditor {
   }
 
   pub async fn insert_rows(&self, row_revs: Vec<RowRevision>) -> FlowyResult<Vec<RowPB>> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
     let block_id = self.block_id().await?;
     let mut rows_by_block_id: HashMap<String, Vec<RowRevision>> = HashMap::new();
     let mut row_orders = vec![];
@@ -443,6 +570,7 @@ impl DatabaseRevisionEditor {
   }
 
   pub async fn update_row(&self, changeset: RowChangeset) -> FlowyResult<()> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
     let row_id = changeset.row_id.clone();
     let old_row = self.get_row_rev(&row_id).await?;
     self.database_block_manager.update_row(changeset).await?;
@@ -479,14 +607,19 @@ impl DatabaseRevisionEditor {
     Ok(all_rows)
   }
 
+  /// Returns the row with its formula cells.
   pub async fn get_row_rev(&self, row_id: &str) -> FlowyResult<Option<Arc<RowRevision>>> {
     match self.database_block_manager.get_row_rev(row_id).await? {
       None => Ok(None),
-      Some((_, row_rev)) => Ok(Some(row_rev)),
+      Some((_, row_rev)) => {
+        let calculator = FormulaCalculator::new(self.get_field_revs(None).await?);
+        Ok(Some(calculator.fill_row(row_rev)))
+      },
     }
   }
 
   pub async fn delete_row(&self, row_id: &str) -> FlowyResult<()> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
     let row_rev = self.database_block_manager.delete_row(row_id).await?;
     tracing::trace!("Did delete row:{:?}", row_rev);
     if let Some(row_rev) = row_rev {
@@ -505,7 +638,30 @@ impl DatabaseRevisionEditor {
       .await
   }
 
-  pub async fn duplicate_row(&self, _row_id: &str) -> FlowyResult<()> {
+  /// Duplicate the row with `row_id`. The new row copies all the cells of the original row and
+  /// is inserted right below it in the same block.
+  pub async fn duplicate_row(&self, row_id: &str) -> FlowyResult<()> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
+    match self.database_block_manager.get_row_rev(row_id).await? {
+      None => tracing::warn!("Duplicate row failed, can not find the row:{}", row_id),
+      Some((_, row_rev)) => {
+        let duplicated_row_rev = RowRevision {
+          id: gen_row_id(),
+          block_id: row_rev.block_id.clone(),
+          cells: row_rev.cells.clone(),
+          height: row_rev.height,
+          visibility: row_rev.visibility,
+          created_at: row_timestamp(),
+        };
+        self
+          .create_row_pb(duplicated_row_rev.clone(), Some(row_id.to_owned()))
+          .await?;
+        self
+          .database_view_manager
+          .did_duplicate_row(row_id, &duplicated_row_rev)
+          .await;
+      },
+    }
     Ok(())
   }
 
@@ -556,11 +712,7 @@ impl DatabaseRevisionEditor {
     params: &CellIdParams,
   ) -> Option<(FieldType, CellProtobufBlob)> {
     let field_rev = self.get_field_rev(&params.field_id).await?;
-    let (_, row_rev) = self
-      .database_block_manager
-      .get_row_rev(&params.row_id)
-      .await
-      .ok()??;
+    let row_rev = self.get_row_rev(&params.row_id).await.ok()??;
     let cell_rev = row_rev.cells.get(&params.field_id)?.clone();
     Some(get_type_cell_protobuf(
       cell_rev.type_cell_data,
@@ -574,9 +726,9 @@ impl DatabaseRevisionEditor {
     row_id: &str,
     field_id: &str,
   ) -> FlowyResult<Option<CellRevision>> {
-    match self.database_block_manager.get_row_rev(row_id).await? {
+    match self.get_row_rev(row_id).await? {
       None => Ok(None),
-      Some((_, row_rev)) => {
+      Some(row_rev) => {
         let cell_rev = row_rev.cells.get(field_id).cloned();
         Ok(cell_rev)
       },
@@ -600,17 +752,24 @@ impl DatabaseRevisionEditor {
     field_id: &str,
     cell_changeset: T,
   ) -> FlowyResult<()> {
-    match self.database_pad.read().await.get_field_rev(field_id) {
+    let _action = DatabaseHistoryAction::begin(&self.history);
+    // Don't hold the lock of the database pad, the rollup cells need to read the fields again.
+    match self.get_field_rev(field_id).await {
       None => {
         let msg = format!("Field with id:{} not found", &field_id);
         Err(FlowyError::internal().context(msg))
       },
-      Some((_, field_rev)) => {
+      Some(field_rev) => {
         tracing::trace!(
           "Cell changeset: id:{} / value:{:?}",
           &field_id,
           cell_changeset
         );
+        let field_type = FieldType::from(field_rev.ty);
+        // The formula cells are calculated from the other cells, they can't be updated.
+        if field_type.is_formula() {
+          return Ok(());
+        }
         let old_row_rev = self.get_row_rev(row_id).await?.clone();
         let cell_rev = self.get_cell_rev(row_id, field_id).await?;
         // Update the changeset.data property with the return value.
@@ -630,6 +789,17 @@ impl DatabaseRevisionEditor {
           .database_block_manager
           .update_cell(cell_changeset)
           .await?;
+        if let Some(old_row_rev) = old_row_rev.clone() {
+          self
+            .did_update_formula_inputs(old_row_rev, field_id)
+            .await?;
+        }
+        if field_type.is_relation() {
+          let relation_field_ids = vec![field_id.to_owned()];
+          self
+            .calculate_rollup_cells(&relation_field_ids, |row_rev, _| row_rev.id == row_id)
+            .await?;
+        }
         self
           .database_view_manager
           .did_update_row(old_row_rev, row_id)
@@ -679,6 +849,7 @@ impl DatabaseRevisionEditor {
   }
 
   pub async fn delete_rows(&self, block_rows: Vec<DatabaseBlockRow>) -> FlowyResult<()> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
     let changesets = self.database_block_manager.delete_rows(block_rows).await?;
     for changeset in changesets {
       self.update_block(changeset).await?;
@@ -711,6 +882,67 @@ impl DatabaseRevisionEditor {
     self.database_view_manager.get_setting().await
   }
 
+  pub async fn get_calendar_setting(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<Option<CalendarLayoutSettingsPB>> {
+    let setting = self
+      .database_view_manager
+      .get_calendar_setting(view_id)
+      .await?;
+    Ok(setting.map(CalendarLayoutSettingsPB::from))
+  }
+
+  pub async fn update_calendar_setting(
+    &self,
+    params: UpdateCalendarSettingParams,
+  ) -> FlowyResult<()> {
+    self
+      .database_view_manager
+      .update_calendar_setting(params)
+      .await
+  }
+
+  pub async fn get_calendar_events(
+    &self,
+    params: CalendarEventRequestParams,
+  ) -> FlowyResult<Vec<CalendarDayPB>> {
+    self.database_view_manager.get_calendar_events(params).await
+  }
+
+  /// Moves the row to the day of `params.timestamp` by rewriting the cell of the calendar's
+  /// layout field. The time of the day of the original date is kept.
+  #[tracing::instrument(level = "trace", skip_all, err)]
+  pub async fn move_calendar_event(&self, params: MoveCalendarEventParams) -> FlowyResult<()> {
+    let setting = self
+      .database_view_manager
+      .get_calendar_setting(&params.view_id)
+      .await?
+      .ok_or_else(|| FlowyError::record_not_found().context("Calendar setting not found"))?;
+
+    let timestamp = match self.get_row_rev(&params.row_id).await? {
+      None => {
+        let msg = format!("Row with id:{} not found", &params.row_id);
+        return Err(FlowyError::record_not_found().context(msg));
+      },
+      Some(row_rev) => match get_row_timestamp(&row_rev, &setting.layout_field_id) {
+        None => params.timestamp,
+        Some(old_timestamp) => {
+          start_of_day(params.timestamp) + (old_timestamp - start_of_day(old_timestamp))
+        },
+      },
+    };
+
+    let changeset = DateCellChangeset {
+      date: Some(timestamp.to_string()),
+      time: None,
+      is_utc: true,
+    };
+    self
+      .update_cell_with_changeset(&params.row_id, &setting.layout_field_id, changeset)
+      .await
+  }
+
   pub async fn get_all_filters(&self) -> FlowyResult<Vec<FilterPB>> {
     Ok(
       self
@@ -740,6 +972,30 @@ impl DatabaseRevisionEditor {
     Ok(())
   }
 
+  pub async fn insert_filter_group(&self, params: InsertFilterGroupParams) -> FlowyResult<()> {
+    self
+      .database_view_manager
+      .insert_filter_group(params)
+      .await?;
+    Ok(())
+  }
+
+  pub async fn update_filter_group(&self, params: UpdateFilterGroupParams) -> FlowyResult<()> {
+    self
+      .database_view_manager
+      .update_filter_group(params)
+      .await?;
+    Ok(())
+  }
+
+  pub async fn delete_filter_group(&self, params: DeleteFilterGroupParams) -> FlowyResult<()> {
+    self
+      .database_view_manager
+      .delete_filter_group(params)
+      .await?;
+    Ok(())
+  }
+
   pub async fn get_all_sorts(&self, view_id: &str) -> FlowyResult<Vec<SortPB>> {
     Ok(
       self
@@ -769,6 +1025,44 @@ impl DatabaseRevisionEditor {
     Ok(sort_rev)
   }
 
+  pub async fn get_all_calculations(&self, view_id: &str) -> FlowyResult<Vec<CalculationPB>> {
+    Ok(
+      self
+        .database_view_manager
+        .get_all_calculations(view_id)
+        .await?
+        .into_iter()
+        .map(|calculation| CalculationPB::from(calculation.as_ref()))
+        .collect(),
+    )
+  }
+
+  /// Returns the values of the view's calculations, they are calculated over the rows that
+  /// are visible after applying the filters.
+  pub async fn get_calculation_values(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<RepeatedCalculationValuePB> {
+    self
+      .database_view_manager
+      .get_calculation_values(view_id)
+      .await
+  }
+
+  pub async fn create_or_update_calculation(
+    &self,
+    params: AlterCalculationParams,
+  ) -> FlowyResult<CalculationRevision> {
+    self
+      .database_view_manager
+      .create_or_update_calculation(params)
+      .await
+  }
+
+  pub async fn delete_calculation(&self, params: DeleteCalculationParams) -> FlowyResult<()> {
+    self.database_view_manager.delete_calculation(params).await
+  }
+
   pub async fn insert_group(&self, params: InsertGroupParams) -> FlowyResult<()> {
     self
       .database_view_manager
@@ -780,6 +1074,16 @@ impl DatabaseRevisionEditor {
     self.database_view_manager.delete_group(params).await
   }
 
+  pub async fn update_number_group_configuration(
+    &self,
+    params: UpdateNumberGroupConfigurationParams,
+  ) -> FlowyResult<()> {
+    self
+      .database_view_manager
+      .update_number_group_configuration(params)
+      .await
+  }
+
   pub async fn move_row(&self, params: MoveRowParams) -> FlowyResult<()> {
     let MoveRowParams {
       view_id: _,
@@ -814,6 +1118,7 @@ impl DatabaseRevisionEditor {
   }
 
   pub async fn move_group_row(&self, params: MoveGroupRowParams) -> FlowyResult<()> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
     let MoveGroupRowParams {
       view_id,
       from_row_id,
@@ -885,11 +1190,38 @@ impl DatabaseRevisionEditor {
     Ok(())
   }
 
-  pub async fn duplicate_database(&self) -> FlowyResult<BuildDatabaseContext> {
+  /// Exports the visible fields and rows of the view in the [ExportFormatPB].
+  #[tracing::instrument(level = "trace", skip_all, fields(view_id = %params.view_id), err)]
+  pub async fn export_database(&self, params: ExportDatabaseParams) -> FlowyResult<String> {
+    let field_revs = self
+      .get_field_revs(None)
+      .await?
+      .into_iter()
+      .filter(|field_rev| field_rev.visibility)
+      .collect::<Vec<_>>();
+    let row_revs = self
+      .database_view_manager
+      .get_visible_row_revs(&params.view_id)
+      .await?;
+    DatabaseExporter::new(field_revs, row_revs, params.raw_cell).export(&params.format)
+  }
+
+  /// Duplicates the database with new field ids and row ids.
+  ///
+  /// The `id_map` maps the ids of the objects that are duplicated together, for example the
+  /// databases in a duplicated page tree, to the ids of their copies. The relations to those
+  /// databases are moved to the copies. The new ids of the fields and rows are inserted into the
+  /// map, so the databases that are duplicated later can remap their relations to this one.
+  pub async fn duplicate_database(
+    &self,
+    id_map: &mut HashMap<String, String>,
+  ) -> FlowyResult<BuildDatabaseContext> {
     let database_pad = self.database_pad.read().await;
     let database_view_data = self.database_view_manager.duplicate_database_view().await?;
     let original_blocks = database_pad.get_block_meta_revs();
-    let (duplicated_fields, duplicated_blocks) = database_pad.duplicate_database_block_meta().await;
+    let (mut duplicated_fields, duplicated_blocks) =
+      database_pad.duplicate_database_block_meta(id_map).await;
+    let relation_field_ids = remap_relation_type_options(&mut duplicated_fields, id_map);
 
     let mut blocks_meta_data = vec![];
     if original_blocks.len() == duplicated_blocks.len() {
@@ -901,9 +1233,10 @@ impl DatabaseRevisionEditor {
         let duplicated_block_id = &duplicated_blocks[index].block_id;
 
         tracing::trace!("Duplicate block:{} meta data", duplicated_block_id);
-        let duplicated_block_meta_data = database_block_meta_editor
-          .duplicate_block(duplicated_block_id)
+        let mut duplicated_block_meta_data = database_block_meta_editor
+          .duplicate_block(duplicated_block_id, id_map)
           .await;
+        remap_relation_cells(&mut duplicated_block_meta_data, &relation_field_ids, id_map);
         blocks_meta_data.push(duplicated_block_meta_data);
       }
     } else {
@@ -911,6 +1244,10 @@ impl DatabaseRevisionEditor {
     }
     drop(database_pad);
 
+    let mut view_rev = DatabaseViewRevision::from_json(database_view_data)?;
+    view_rev.remap_field_ids(id_map);
+    let database_view_data = serde_json::to_string(&view_rev)?;
+
     Ok(BuildDatabaseContext {
       field_revs: duplicated_fields.into_iter().map(Arc::new).collect(),
       block_metas: duplicated_blocks,
@@ -924,6 +1261,221 @@ impl DatabaseRevisionEditor {
     self.database_view_manager.load_groups().await
   }
 
+  pub async fn get_snapshots(&self) -> FlowyResult<Vec<RevisionSnapshotData>> {
+    self.rev_manager.read_snapshots().await
+  }
+
+  /// Returns the fields and rows of the database at the snapshot, in the same json format as
+  /// [Self::export_database].
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn preview_snapshot(&self, rev_id: i64) -> FlowyResult<String> {
+    let (snapshot_pad, block_pads) = self.read_snapshot(rev_id).await?;
+    let field_revs = snapshot_pad.get_field_revs(None)?;
+    let row_revs = block_pads
+      .iter()
+      .flat_map(|block_pad| block_pad.rows.clone())
+      .collect::<Vec<Arc<RowRevision>>>();
+    let row_revs = FormulaCalculator::new(field_revs.clone()).fill_rows(row_revs);
+    DatabaseExporter::new(field_revs, row_revs, false).export(&ExportFormatPB::JSON)
+  }
+
+  /// Restores the fields and rows of the database to the snapshot. Instead of rewinding the
+  /// revisions, the difference between the current content and the snapshot is saved as a new
+  /// revision, so the restore is synced and can be undone like the other edits.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn restore_snapshot(&self, rev_id: i64) -> FlowyResult<()> {
+    let _action = DatabaseHistoryAction::begin(&self.history);
+    let (snapshot_pad, block_pads) = self.read_snapshot(rev_id).await?;
+    let old_field_revs = self.database_pad.read().await.get_fields().to_vec();
+    self
+      .modify(|pad| Ok(pad.reset_database(&snapshot_pad)?))
+      .await?;
+    self.notify_did_change_fields(old_field_revs).await?;
+
+    let (block_changesets, row_changesets) = self
+      .database_block_manager
+      .restore_blocks_from_snapshot(block_pads)
+      .await?;
+    for changeset in block_changesets {
+      self.update_block(changeset).await?;
+    }
+    for changeset in row_changesets {
+      self.did_change_rows(changeset).await;
+    }
+    self.rebuild_search_index().await;
+    Ok(())
+  }
+
+  /// Reads the snapshot of the database and the snapshots of its blocks that were taken at or
+  /// before it. Fails if any of the blocks has no such snapshot.
+  async fn read_snapshot(
+    &self,
+    rev_id: i64,
+  ) -> FlowyResult<(DatabaseRevisionPad, Vec<DatabaseBlockRevisionPad>)> {
+    let snapshot = self
+      .rev_manager
+      .read_snapshot(Some(rev_id))
+      .await?
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .context(format!("Can't find the snapshot with rev_id: {}", rev_id))
+      })?;
+    let pad = self
+      .rev_manager
+      .read_snapshot_object::<DatabaseRevisionSerde>(rev_id)
+      .await?;
+    let block_ids = pad
+      .get_block_meta_revs()
+      .iter()
+      .map(|block_meta| block_meta.block_id.clone())
+      .collect::<Vec<String>>();
+    let block_pads = self
+      .database_block_manager
+      .read_snapshot_pads(block_ids, snapshot.timestamp)
+      .await?;
+    Ok((pad, block_pads))
+  }
+
+  pub fn can_undo(&self) -> bool {
+    self.history.lock().can_undo()
+  }
+
+  pub fn can_redo(&self) -> bool {
+    self.history.lock().can_redo()
+  }
+
+  /// Reverts the last user-level action, for example, editing a cell or deleting a row. The
+  /// history is cleared if the action can't be reverted.
+  #[tracing::instrument(level = "trace", skip_all, err)]
+  pub async fn undo(&self) -> FlowyResult<()> {
+    let item = self.history.lock().undo();
+    match item {
+      None => tracing::trace!("The undo stack of the database is empty"),
+      Some(item) => match self.apply_history_item(item).await {
+        Ok(redo_item) => self.history.lock().add_redo(redo_item),
+        Err(e) => {
+          self.history.lock().clear();
+          return Err(e);
+        },
+      },
+    }
+    Ok(())
+  }
+
+  #[tracing::instrument(level = "trace", skip_all, err)]
+  pub async fn redo(&self) -> FlowyResult<()> {
+    let item = self.history.lock().redo();
+    match item {
+      None => tracing::trace!("The redo stack of the database is empty"),
+      Some(item) => match self.apply_history_item(item).await {
+        Ok(undo_item) => self.history.lock().add_undo(undo_item),
+        Err(e) => {
+          self.history.lock().clear();
+          return Err(e);
+        },
+      },
+    }
+    Ok(())
+  }
+
+  /// Indexes all the cells of the database if it has not been indexed yet, for example, the
+  /// database was created before the search index was added.
+  pub(crate) async fn initialize_search_index(&self) -> FlowyResult<()> {
+    if !self.search_index.is_indexed(&self.database_id)? {
+      let row_revs = self.get_database_row_revs().await?;
+      let records = self.make_search_records(&row_revs, None).await;
+      self
+        .search_index
+        .replace_cells(&self.database_id, records)?;
+    }
+    Ok(())
+  }
+
+  /// Updates the search index with the rows that are inserted, updated or deleted. Only the
+  /// changed cells are indexed again if the event contains the ids of their fields.
+  pub(crate) async fn update_search_index(&self, event: DatabaseBlockEvent) -> FlowyResult<()> {
+    let (row_id, field_ids) = match event {
+      DatabaseBlockEvent::InsertRow { row, .. } => (row.row.id, None),
+      DatabaseBlockEvent::UpdateRow { row, .. } if row.field_ids.is_empty() => (row.row.id, None),
+      DatabaseBlockEvent::UpdateRow { row, .. } => (row.row.id, Some(row.field_ids)),
+      DatabaseBlockEvent::DeleteRow { row_id, .. } => {
+        return self.search_index.delete_row(&self.database_id, &row_id);
+      },
+      DatabaseBlockEvent::Move { .. } => return Ok(()),
+    };
+    // The formula cells are not saved in the rows, so they are indexed again with the cells they
+    // reference.
+    let field_ids = match field_ids {
+      None => None,
+      Some(mut field_ids) => {
+        let field_revs = self.get_field_revs(None).await?;
+        let field_names = field_revs
+          .iter()
+          .filter(|field_rev| field_ids.contains(&field_rev.id))
+          .map(|field_rev| field_rev.name.as_str())
+          .collect::<Vec<&str>>();
+        let formula_field_ids =
+          FormulaCalculator::new(field_revs.clone()).referencing_field_ids(&field_names);
+        field_ids.extend(formula_field_ids);
+        Some(field_ids)
+      },
+    };
+
+    if let Some(row_rev) = self.get_row_rev(&row_id).await? {
+      let records = self
+        .make_search_records(&[row_rev], field_ids.as_deref())
+        .await;
+      match field_ids {
+        None => {
+          self
+            .search_index
+            .replace_row_cells(&self.database_id, &row_id, records)?;
+        },
+        Some(_) => self.search_index.index_cells(records)?,
+      }
+    }
+    Ok(())
+  }
+
+  /// Indexes the cells of the field again, their display strings depend on the field's type
+  /// option.
+  async fn index_field_cells(&self, field_id: &str) {
+    let result = || async {
+      let row_revs = self.get_database_row_revs().await?;
+      let field_ids = [field_id.to_owned()];
+      let records = self.make_search_records(&row_revs, Some(&field_ids)).await;
+      self
+        .search_index
+        .replace_field_cells(&self.database_id, field_id, records)
+    };
+    if let Err(e) = result().await {
+      tracing::error!("Index the cells of the field failed: {}", e);
+    }
+  }
+
+  /// Replaces the indexed cells with the current ones. It's used after the changes that are not
+  /// notified by the block events, for example, undoing an edit or restoring a snapshot.
+  async fn rebuild_search_index(&self) {
+    let result = || async {
+      let row_revs = self.get_database_row_revs().await?;
+      let records = self.make_search_records(&row_revs, None).await;
+      self.search_index.replace_cells(&self.database_id, records)
+    };
+    if let Err(e) = result().await {
+      tracing::error!("Rebuild the search index of the database failed: {}", e);
+    }
+  }
+
+  /// Returns the search index records of the cells in the rows with the current fields.
+  async fn make_search_records(
+    &self,
+    row_revs: &[Arc<RowRevision>],
+    field_ids: Option<&[String]>,
+  ) -> Vec<SearchIndexRecord> {
+    let field_revs = self.database_pad.read().await.get_fields().to_vec();
+    make_database_search_records(&self.database_id, &field_revs, row_revs, field_ids)
+  }
+
   async fn create_row_rev(&self) -> FlowyResult<RowRevision> {
     let field_revs = self.database_pad.read().await.get_field_revs(None)?;
     let block_id = self.block_id().await?;
@@ -953,6 +1505,342 @@ impl DatabaseRevisionEditor {
     Ok(row_pb)
   }
 
+  /// Returns all the rows of the database with their formula cells, regardless of the views'
+  /// filters.
+  async fn get_database_row_revs(&self) -> FlowyResult<Vec<Arc<RowRevision>>> {
+    let row_revs = self.database_block_manager.get_row_revs().await?;
+    let calculator = FormulaCalculator::new(self.get_field_revs(None).await?);
+    Ok(calculator.fill_rows(row_revs))
+  }
+
+  /// Invalidates the formula cells of the row that reference the updated field, directly or
+  /// through other formulas. The cell data decoded from their old cell strings is removed from
+  /// the cell data cache, and the cells are notified to be read again.
+  async fn did_update_formula_inputs(
+    &self,
+    old_row_rev: Arc<RowRevision>,
+    updated_field_id: &str,
+  ) -> FlowyResult<()> {
+    let field_revs = self.get_field_revs(None).await?;
+    let field_name = match field_revs
+      .iter()
+      .find(|field_rev| field_rev.id == updated_field_id)
+    {
+      None => return Ok(()),
+      Some(field_rev) => field_rev.name.clone(),
+    };
+    let calculator = FormulaCalculator::new(field_revs.clone());
+    let formula_field_ids = calculator.referencing_field_ids(&[&field_name]);
+    if formula_field_ids.is_empty() {
+      return Ok(());
+    }
+
+    let old_row_rev = calculator.fill_row(old_row_rev);
+    for field_rev in field_revs
+      .iter()
+      .filter(|field_rev| formula_field_ids.contains(&field_rev.id))
+    {
+      if let Some(type_cell_data) = old_row_rev
+        .cells
+        .get(&field_rev.id)
+        .and_then(|cell_rev| TypeCellData::try_from(cell_rev).ok())
+      {
+        remove_cell_data_from_cache(
+          &self.cell_data_cache,
+          field_rev,
+          FieldType::Formula,
+          &type_cell_data.cell_str,
+        );
+      }
+      let id = format!("{}:{}", old_row_rev.id, field_rev.id);
+      send_notification(&id, DatabaseNotification::DidUpdateCell).send();
+    }
+    Ok(())
+  }
+
+  /// Updates the formulas after the field named `old_name` was changed, for example, renamed,
+  /// deleted or switched to another type. The references to the field are renamed if the
+  /// `new_name` is not None. Then the result types of the formulas are inferred again and the
+  /// formula cells are notified to be read again.
+  async fn did_update_formula_dependency(
+    &self,
+    field_id: &str,
+    old_name: &str,
+    new_name: Option<&str>,
+  ) -> FlowyResult<()> {
+    let mut field_revs = self.get_field_revs(None).await?;
+    let mut updated_field_ids = vec![];
+    // The result type of a formula depends on the formulas it references, so the result types
+    // are inferred again until none of them is changed.
+    for _ in 0..=field_revs.len() {
+      let mut is_changed = false;
+      for index in 0..field_revs.len() {
+        if !FieldType::from(field_revs[index].ty).is_formula() {
+          continue;
+        }
+        let old_type_option = FormulaTypeOptionPB::from(&field_revs[index]);
+        let mut type_option = old_type_option.clone();
+        if let Some(expression) = new_name
+          .and_then(|new_name| rename_formula_field(&type_option.expression, old_name, new_name))
+        {
+          type_option.expression = expression;
+        }
+        type_option.update_result_type(&field_revs);
+        if type_option.json_str() != old_type_option.json_str() {
+          let mut field_rev = (*field_revs[index]).clone();
+          field_rev.insert_type_option(&type_option);
+          if !updated_field_ids.contains(&field_rev.id) {
+            updated_field_ids.push(field_rev.id.clone());
+          }
+          field_revs[index] = Arc::new(field_rev);
+          is_changed = true;
+        }
+      }
+      if !is_changed {
+        break;
+      }
+    }
+
+    for field_rev in field_revs
+      .iter()
+      .filter(|field_rev| updated_field_ids.contains(&field_rev.id))
+    {
+      let type_option = FormulaTypeOptionPB::from(field_rev);
+      self
+        .modify_field_rev(&field_rev.id, |field| {
+          field.insert_type_option(&type_option);
+          Ok(Some(()))
+        })
+        .await?;
+    }
+
+    let mut field_names = vec![old_name];
+    field_names.extend(new_name);
+    let mut formula_field_ids = FormulaCalculator::new(field_revs.clone())
+      .referencing_field_ids(&field_names)
+      .into_iter()
+      .chain(updated_field_ids)
+      .collect::<Vec<String>>();
+    let is_formula = field_revs
+      .iter()
+      .any(|field_rev| field_rev.id == field_id && FieldType::from(field_rev.ty).is_formula());
+    if is_formula {
+      formula_field_ids.push(field_id.to_owned());
+    }
+    formula_field_ids.sort();
+    formula_field_ids.dedup();
+    self.did_update_formula_fields(&formula_field_ids).await
+  }
+
+  /// Notifies the formula cells of all the rows to be read again, and indexes them again. The
+  /// formula cells are not saved in the rows, so they are not notified by the block events.
+  async fn did_update_formula_fields(&self, formula_field_ids: &[String]) -> FlowyResult<()> {
+    if formula_field_ids.is_empty() {
+      return Ok(());
+    }
+
+    for row_rev in self.database_block_manager.get_row_revs().await? {
+      for field_id in formula_field_ids {
+        let id = format!("{}:{}", row_rev.id, field_id);
+        send_notification(&id, DatabaseNotification::DidUpdateCell).send();
+      }
+    }
+    for field_id in formula_field_ids {
+      self.index_field_cells(field_id).await;
+    }
+    Ok(())
+  }
+
+  /// Recalculates the rollup cells that aggregate the rows of the given database. Only the rows
+  /// that are related to one of the updated rows are recalculated.
+  pub async fn did_update_related_rows(
+    &self,
+    database_id: &str,
+    updated_row_ids: &[String],
+  ) -> FlowyResult<()> {
+    // The rollup cells are derived from the related rows, so the recalculation isn't undoable.
+    let _untracked = DatabaseHistoryUntracked::begin(&self.history);
+    let relation_field_ids = self
+      .get_field_revs(None)
+      .await?
+      .iter()
+      .filter(|field_rev| {
+        FieldType::from(field_rev.ty).is_relation()
+          && RelationTypeOptionPB::from(*field_rev).database_id == database_id
+      })
+      .map(|field_rev| field_rev.id.clone())
+      .collect::<Vec<String>>();
+    if relation_field_ids.is_empty() {
+      return Ok(());
+    }
+
+    let old_row_revs = self
+      .calculate_rollup_cells(&relation_field_ids, |_, related_row_ids| {
+        related_row_ids
+          .iter()
+          .any(|row_id| updated_row_ids.contains(row_id))
+      })
+      .await?;
+    self.did_update_rollup_rows(old_row_revs).await;
+    Ok(())
+  }
+
+  /// Recalculates all the rollup cells of the database. The related databases may be updated
+  /// while this database is closed, so the rollup cells are refreshed when it's opened.
+  pub async fn refresh_rollup_cells(&self) -> FlowyResult<()> {
+    let _untracked = DatabaseHistoryUntracked::begin(&self.history);
+    let relation_field_ids = self
+      .get_field_revs(None)
+      .await?
+      .iter()
+      .filter(|field_rev| FieldType::from(field_rev.ty).is_relation())
+      .map(|field_rev| field_rev.id.clone())
+      .collect::<Vec<String>>();
+    if relation_field_ids.is_empty() {
+      return Ok(());
+    }
+
+    let old_row_revs = self
+      .calculate_rollup_cells(&relation_field_ids, |_, _| true)
+      .await?;
+    self.did_update_rollup_rows(old_row_revs).await;
+    Ok(())
+  }
+
+  /// Returns the fields and all the rows of the database, regardless of the views' filters.
+  pub async fn get_related_database(&self) -> FlowyResult<RelatedDatabase> {
+    Ok(RelatedDatabase {
+      field_revs: self.get_field_revs(None).await?,
+      row_revs: self.get_database_row_revs().await?,
+    })
+  }
+
+  async fn did_update_rollup_rows(&self, old_row_revs: Vec<Arc<RowRevision>>) {
+    for old_row_rev in old_row_revs {
+      let row_id = old_row_rev.id.clone();
+      self
+        .database_view_manager
+        .did_update_row(Some(old_row_rev), &row_id)
+        .await;
+    }
+  }
+
+  /// Recalculates the rollup cells of all the rows if the updated field is a relation or a
+  /// rollup.
+  async fn calculate_all_rollup_cells(&self, updated_field_id: &str) -> FlowyResult<()> {
+    let field_rev = match self.get_field_rev(updated_field_id).await {
+      None => return Ok(()),
+      Some(field_rev) => field_rev,
+    };
+    let relation_field_id = match FieldType::from(field_rev.ty) {
+      FieldType::Relation => field_rev.id.clone(),
+      FieldType::Rollup => RollupTypeOptionPB::from(&field_rev).relation_field_id,
+      _ => return Ok(()),
+    };
+    self
+      .calculate_rollup_cells(&[relation_field_id], |_, _| true)
+      .await?;
+    Ok(())
+  }
+
+  /// Recalculates the rollup cells that aggregate the related rows of the relation fields.
+  /// The row is recalculated if `is_affected` returns true for the row and its related row ids.
+  /// Returns the rows, before updating, whose rollup cells were changed.
+  async fn calculate_rollup_cells<F>(
+    &self,
+    relation_field_ids: &[String],
+    is_affected: F,
+  ) -> FlowyResult<Vec<Arc<RowRevision>>>
+  where
+    F: Fn(&RowRevision, &RelationCellData) -> bool,
+  {
+    let field_revs = self.get_field_revs(None).await?;
+    let mut updated_row_revs: Vec<Arc<RowRevision>> = vec![];
+    let relation_field_revs = field_revs.iter().filter(|field_rev| {
+      relation_field_ids.contains(&field_rev.id) && FieldType::from(field_rev.ty).is_relation()
+    });
+    for relation_field_rev in relation_field_revs {
+      let rollup_field_revs = field_revs
+        .iter()
+        .filter(|field_rev| {
+          FieldType::from(field_rev.ty).is_rollup()
+            && RollupTypeOptionPB::from(*field_rev).relation_field_id == relation_field_rev.id
+        })
+        .collect::<Vec<&Arc<FieldRevision>>>();
+      let database_id = RelationTypeOptionPB::from(relation_field_rev).database_id;
+      if rollup_field_revs.is_empty() || database_id.is_empty() {
+        continue;
+      }
+
+      let RelatedDatabase {
+        field_revs: related_field_revs,
+        row_revs: related_row_revs,
+      } = self
+        .relation_delegate
+        .get_related_database(&database_id)
+        .await?;
+      let related_row_by_id = related_row_revs
+        .into_iter()
+        .map(|row_rev| (row_rev.id.clone(), row_rev))
+        .collect::<HashMap<String, Arc<RowRevision>>>();
+
+      for row_rev in self.database_block_manager.get_row_revs().await? {
+        let related_row_ids = get_cell_str(&row_rev, relation_field_rev)
+          .map(RelationCellData::from)
+          .unwrap_or_default();
+        if !is_affected(&row_rev, &related_row_ids) {
+          continue;
+        }
+
+        let related_row_revs = related_row_ids
+          .iter()
+          .flat_map(|row_id| related_row_by_id.get(row_id).cloned())
+          .collect::<Vec<Arc<RowRevision>>>();
+        let mut updated_field_ids = vec![];
+        for rollup_field_rev in rollup_field_revs.iter() {
+          let type_option = RollupTypeOptionPB::from(*rollup_field_rev);
+          let target_field_rev = related_field_revs
+            .iter()
+            .find(|field_rev| field_rev.id == type_option.target_field_id);
+          let cell_str = calculate_rollup(
+            &type_option.calculation,
+            target_field_rev.map(|field_rev| field_rev.as_ref()),
+            &related_row_revs,
+          );
+          if get_cell_str(&row_rev, rollup_field_rev).unwrap_or_default() == cell_str {
+            continue;
+          }
+
+          let cell_changeset = CellChangesetPB {
+            database_id: self.database_id.clone(),
+            row_id: row_rev.id.clone(),
+            field_id: rollup_field_rev.id.clone(),
+            type_cell_data: TypeCellData::new(cell_str, FieldType::Rollup).to_json(),
+          };
+          self
+            .database_block_manager
+            .update_cell(cell_changeset)
+            .await?;
+          updated_field_ids.push(rollup_field_rev.id.clone());
+        }
+
+        for field_id in updated_field_ids.iter() {
+          self
+            .did_update_formula_inputs(row_rev.clone(), field_id)
+            .await?;
+        }
+        if !updated_field_ids.is_empty()
+          && !updated_row_revs
+            .iter()
+            .any(|updated| updated.id == row_rev.id)
+        {
+          updated_row_revs.push(row_rev);
+        }
+      }
+    }
+    Ok(updated_row_revs)
+  }
+
   async fn modify<F>(&self, f: F) -> FlowyResult<()>
   where
     F:
@@ -960,15 +1848,182 @@ impl DatabaseRevisionEditor {
   {
     let mut write_guard = self.database_pad.write().await;
     if let Some(changeset) = f(&mut write_guard)? {
+      let operations = DatabaseHistoryOperations::Database(changeset.operations.clone());
+      let inverted_operations =
+        DatabaseHistoryOperations::Database(changeset.inverted_operations.clone());
       self.apply_change(changeset).await?;
+      self
+        .history
+        .lock()
+        .record_operations(operations, inverted_operations);
     }
     Ok(())
   }
 
+  /// Applies the operations of the item in reverse order and returns the item that reverts it.
+  async fn apply_history_item(
+    &self,
+    item: DatabaseHistoryItem,
+  ) -> FlowyResult<DatabaseHistoryItem> {
+    let mut inverted_item = DatabaseHistoryItem::default();
+    let mut changed_row_ids = vec![];
+    for operations in item.operations.into_iter().rev() {
+      let inverted_operations = match operations {
+        DatabaseHistoryOperations::Database(operations) => {
+          let inverted_operations = self.apply_database_history_operations(operations).await?;
+          DatabaseHistoryOperations::Database(inverted_operations)
+        },
+        DatabaseHistoryOperations::Block {
+          block_id,
+          operations,
+        } => {
+          let (inverted_operations, changeset) = self
+            .database_block_manager
+            .apply_history_operations(&block_id, operations)
+            .await?;
+          changed_row_ids.extend(
+            changeset
+              .updated_rows
+              .iter()
+              .map(|(_, row_id)| row_id.clone()),
+          );
+          changed_row_ids.extend(
+            changeset
+              .deleted_rows
+              .iter()
+              .map(|row_rev| row_rev.id.clone()),
+          );
+          self.did_change_rows(changeset).await;
+          DatabaseHistoryOperations::Block {
+            block_id,
+            operations: inverted_operations,
+          }
+        },
+      };
+      inverted_item.operations.push(inverted_operations);
+    }
+    self.rebuild_search_index().await;
+    // The rollup cells of the other databases aggregate the reverted rows.
+    if !changed_row_ids.is_empty() {
+      self
+        .relation_delegate
+        .did_update_rows(&self.database_id, changed_row_ids)
+        .await;
+    }
+    Ok(inverted_item)
+  }
+
+  /// Applies the operations to the database pad and notifies the fields that were changed by
+  /// them.
+  async fn apply_database_history_operations(
+    &self,
+    operations: DatabaseOperations,
+  ) -> FlowyResult<DatabaseOperations> {
+    let (old_field_revs, inverted_operations) = {
+      let mut write_guard = self.database_pad.write().await;
+      let old_field_revs = write_guard.get_fields().to_vec();
+      let changeset = write_guard.apply_operations(operations)?;
+      let inverted_operations = changeset.inverted_operations.clone();
+      self.apply_change(changeset).await?;
+      (old_field_revs, inverted_operations)
+    };
+    self.notify_did_change_fields(old_field_revs).await?;
+    Ok(inverted_operations)
+  }
+
+  async fn did_change_rows(&self, changeset: DatabaseBlockRowsChangeset) {
+    for (old_row_rev, row_id) in changeset.updated_rows {
+      self
+        .database_view_manager
+        .did_update_row(old_row_rev, &row_id)
+        .await;
+    }
+    for row_rev in changeset.deleted_rows {
+      self.database_view_manager.did_delete_row(row_rev).await;
+    }
+  }
+
+  /// Compares the current fields with the old fields and notifies the fields that were
+  /// inserted, updated, moved or deleted.
+  async fn notify_did_change_fields(
+    &self,
+    old_field_revs: Vec<Arc<FieldRevision>>,
+  ) -> FlowyResult<()> {
+    let field_revs = self.database_pad.read().await.get_fields().to_vec();
+    let mut notified_changeset = DatabaseFieldChangesetPB {
+      view_id: self.database_id.clone(),
+      inserted_fields: vec![],
+      deleted_fields: vec![],
+      updated_fields: vec![],
+    };
+    let mut updated_field_revs = vec![];
+    let mut old_index = 0;
+    for (index, field_rev) in field_revs.iter().enumerate() {
+      match old_field_revs
+        .iter()
+        .position(|old_field_rev| old_field_rev.id == field_rev.id)
+      {
+        None => notified_changeset
+          .inserted_fields
+          .push(IndexFieldPB::from_field_rev(field_rev, index)),
+        Some(position) => {
+          // The field is moved if its position is changed relative to the other fields.
+          if position < old_index {
+            notified_changeset
+              .deleted_fields
+              .push(FieldIdPB::from(field_rev.id.as_str()));
+            notified_changeset
+              .inserted_fields
+              .push(IndexFieldPB::from_field_rev(field_rev, index));
+          } else {
+            old_index = position;
+          }
+
+          let old_field_rev = &old_field_revs[position];
+          if old_field_rev != field_rev {
+            notified_changeset
+              .updated_fields
+              .push(FieldPB::from(field_rev.clone()));
+            updated_field_revs.push(old_field_rev.clone());
+          }
+        },
+      }
+    }
+    for old_field_rev in old_field_revs.iter() {
+      if !field_revs
+        .iter()
+        .any(|field_rev| field_rev.id == old_field_rev.id)
+      {
+        notified_changeset
+          .deleted_fields
+          .push(FieldIdPB::from(old_field_rev.id.as_str()));
+      }
+    }
+
+    for old_field_rev in updated_field_revs {
+      let field_id = old_field_rev.id.clone();
+      if let Err(e) = self
+        .database_view_manager
+        .did_update_view_field_type_option(&field_id, Some(old_field_rev))
+        .await
+      {
+        tracing::error!("View manager update field failed: {:?}", e);
+      }
+    }
+    for updated_field in notified_changeset.updated_fields.iter() {
+      send_notification(&updated_field.id, DatabaseNotification::DidUpdateField)
+        .payload(updated_field.clone())
+        .send();
+    }
+    self.notify_did_update_database(notified_changeset).await?;
+    Ok(())
+  }
+
   async fn apply_change(&self, change: DatabaseRevisionChangeset) -> FlowyResult<()> {
     let DatabaseRevisionChangeset {
       operations: delta,
       md5,
+      ..
     } = change;
     let data = delta.json_bytes();
     let _ = self.rev_manager.add_local_revision(data, md5).await?;
@@ -1036,6 +2091,25 @@ impl DatabaseRevisionEditor {
   pub fn database_pad(&self) -> Arc<RwLock<DatabaseRevisionPad>> {
     self.database_pad.clone()
   }
+
+  pub async fn block_rev_manager(
+    &self,
+    block_id: &str,
+  ) -> FlowyResult<Arc<RevisionManager<Arc<ConnectionPool>>>> {
+    let editor = self
+      .database_block_manager
+      .get_or_create_block_editor(block_id)
+      .await?;
+    Ok(editor.rev_manager())
+  }
+
+  pub async fn view_rev_manager(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<Arc<RevisionManager<Arc<ConnectionPool>>>> {
+    let editor = self.database_view_manager.get_view_editor(view_id).await?;
+    Ok(editor.rev_manager())
+  }
 }
 
 pub struct DatabaseRevisionSerde();
@@ -1101,3 +2175,226 @@ impl JsonDeserializer for TypeOptionJsonDeserializer {
     Ok(json)
   }
 }
+
+/// Gives the fields, blocks and rows of the database that is built from the `build_context` new
+/// ids, so the same data, for example, the data of a workspace backup, can be imported more than
+/// once. The relations to the databases in the `id_map` are moved the same way as
+/// [DatabaseRevisionEditor::duplicate_database] does, and the new ids of the fields and rows are
+/// inserted into the map.
+pub fn remap_database_build_context(
+  build_context: BuildDatabaseContext,
+  id_map: &mut HashMap<String, String>,
+) -> FlowyResult<BuildDatabaseContext> {
+  let BuildDatabaseContext {
+    field_revs,
+    block_metas,
+    blocks,
+    database_view_data,
+  } = build_context;
+
+  let mut field_revs = field_revs
+    .iter()
+    .map(|field_rev| {
+      let mut field_rev = field_rev.as_ref().clone();
+      field_rev.id = id_map
+        .entry(field_rev.id.clone())
+        .or_insert_with(gen_field_id)
+        .clone();
+      field_rev
+    })
+    .collect::<Vec<FieldRevision>>();
+  let relation_field_ids = remap_relation_type_options(&mut field_revs, id_map);
+
+  let mut block_id_map = HashMap::new();
+  let block_metas = block_metas
+    .into_iter()
+    .map(|mut block_meta| {
+      block_meta.block_id = block_id_map
+        .entry(block_meta.block_id.clone())
+        .or_insert_with(gen_block_id)
+        .clone();
+      block_meta
+    })
+    .collect::<Vec<DatabaseBlockMetaRevision>>();
+
+  let blocks = blocks
+    .into_iter()
+    .map(|block_rev| {
+      let block_id = block_id_map
+        .entry(block_rev.block_id.clone())
+        .or_insert_with(gen_block_id)
+        .clone();
+      let rows = block_rev
+        .rows
+        .iter()
+        .map(|row_rev| {
+          let mut row_rev = row_rev.as_ref().clone();
+          row_rev.id = id_map
+            .entry(row_rev.id.clone())
+            .or_insert_with(gen_row_id)
+            .clone();
+          row_rev.block_id = block_id.clone();
+          row_rev.cells = std::mem::take(&mut row_rev.cells)
+            .into_iter()
+            .map(|(field_id, cell_rev)| {
+              let field_id = id_map.get(&field_id).cloned().unwrap_or(field_id);
+              (field_id, cell_rev)
+            })
+            .collect();
+          Arc::new(row_rev)
+        })
+        .collect();
+      let mut block_rev = DatabaseBlockRevision { block_id, rows };
+      remap_relation_cells(&mut block_rev, &relation_field_ids, id_map);
+      block_rev
+    })
+    .collect::<Vec<DatabaseBlockRevision>>();
+
+  let database_view_data = if database_view_data.is_empty() {
+    database_view_data
+  } else {
+    let mut view_rev = DatabaseViewRevision::from_json(database_view_data)?;
+    view_rev.remap_field_ids(id_map);
+    serde_json::to_string(&view_rev)?
+  };
+
+  Ok(BuildDatabaseContext {
+    field_revs: field_revs.into_iter().map(Arc::new).collect(),
+    block_metas,
+    blocks,
+    database_view_data,
+  })
+}
+
+/// Moves the relations of the duplicated fields to the copies of the related databases that are
+/// in the `id_map`, and moves the rollups to the duplicated relation fields. Returns the ids of
+/// the relation fields that were moved.
+fn remap_relation_type_options(
+  field_revs: &mut [FieldRevision],
+  id_map: &mut HashMap<String, String>,
+) -> Vec<String> {
+  let mut relation_field_ids = vec![];
+  for field_rev in field_revs.iter_mut() {
+    if !FieldType::from(field_rev.ty).is_relation() {
+      continue;
+    }
+    let mut type_option = RelationTypeOptionPB::from(&*field_rev);
+    if let Some(database_id) = id_map.get(&type_option.database_id) {
+      type_option.database_id = database_id.clone();
+      field_rev.insert_type_option(&type_option);
+      relation_field_ids.push(field_rev.id.clone());
+    }
+  }
+
+  for field_rev in field_revs.iter_mut() {
+    if !FieldType::from(field_rev.ty).is_rollup() {
+      continue;
+    }
+    let mut type_option = RollupTypeOptionPB::from(&*field_rev);
+    if let Some(relation_field_id) = id_map.get(&type_option.relation_field_id) {
+      type_option.relation_field_id = relation_field_id.clone();
+    }
+    // The target field belongs to the related database, so it's only remapped if the related
+    // database is duplicated too.
+    if relation_field_ids.contains(&type_option.relation_field_id)
+      && !type_option.target_field_id.is_empty()
+    {
+      type_option.target_field_id = id_map
+        .entry(type_option.target_field_id.clone())
+        .or_insert_with(gen_field_id)
+        .clone();
+    }
+    field_rev.insert_type_option(&type_option);
+  }
+  relation_field_ids
+}
+
+/// Replaces the related row ids in the cells of the relation fields with the ids in the `id_map`.
+/// A related row that isn't duplicated yet gets a new id, which is inserted into the map, so the
+/// row uses it when its database is duplicated.
+fn remap_relation_cells(
+  block_rev: &mut DatabaseBlockRevision,
+  relation_field_ids: &[String],
+  id_map: &mut HashMap<String, String>,
+) {
+  if relation_field_ids.is_empty() {
+    return;
+  }
+
+  for row_rev in block_rev.rows.iter_mut() {
+    let row_rev = Arc::make_mut(row_rev);
+    for field_id in relation_field_ids {
+      let cell_rev = match row_rev.cells.get_mut(field_id) {
+        None => continue,
+        Some(cell_rev) => cell_rev,
+      };
+      let type_cell_data = match TypeCellData::try_from(&*cell_rev) {
+        Ok(type_cell_data) if type_cell_data.field_type.is_relation() => type_cell_data,
+        _ => continue,
+      };
+      let row_ids = RelationCellData::from(type_cell_data.cell_str)
+        .into_inner()
+        .into_iter()
+        .map(|row_id| id_map.entry(row_id).or_insert_with(gen_row_id).clone())
+        .collect::<Vec<String>>();
+      let cell_str = RelationCellData::from(row_ids).to_string();
+      *cell_rev = CellRevision::new(TypeCellData::new(cell_str, FieldType::Relation).to_json());
+    }
+  }
+}
+
+/// Returns the cell string of the field if the cell was saved by the field's current type.
+fn get_cell_str(row_rev: &RowRevision, field_rev: &FieldRevision) -> Option<String> {
+  let type_cell_data = TypeCellData::try_from(row_rev.cells.get(&field_rev.id)?).ok()?;
+  if type_cell_data.field_type != FieldType::from(field_rev.ty) {
+    return None;
+  }
+  Some(type_cell_data.cell_str)
+}
+
+/// Returns the search index records of the cells in the rows. All the fields are indexed if the
+/// `field_ids` is None. The checkbox cells are skipped, their display strings are not useful for
+/// searching.
+pub(crate) fn make_database_search_records(
+  database_id: &str,
+  field_revs: &[Arc<FieldRevision>],
+  row_revs: &[Arc<RowRevision>],
+  field_ids: Option<&[String]>,
+) -> Vec<SearchIndexRecord> {
+  let field_revs = field_revs
+    .iter()
+    .filter(|field_rev| FieldType::from(field_rev.ty) != FieldType::Checkbox)
+    .filter(|field_rev| field_ids.map_or(true, |field_ids| field_ids.contains(&field_rev.id)))
+    .collect::<Vec<&Arc<FieldRevision>>>();
+
+  let mut records = vec![];
+  for row_rev in row_revs {
+    for field_rev in field_revs.iter() {
+      records.push(SearchIndexRecord::database_cell(
+        database_id,
+        &row_rev.id,
+        &field_rev.id,
+        &get_cell_display_str(row_rev, field_rev),
+      ));
+    }
+  }
+  records
+}
+
+/// Returns the display string of the cell, the same as the exported one.
+fn get_cell_display_str(row_rev: &RowRevision, field_rev: &FieldRevision) -> String {
+  let type_cell_data = match row_rev
+    .cells
+    .get(&field_rev.id)
+    .and_then(|cell_rev| TypeCellData::try_from(cell_rev).ok())
+  {
+    None => return "".to_owned(),
+    Some(type_cell_data) => type_cell_data,
+  };
+  stringify_cell_data(
+    type_cell_data.cell_str,
+    &type_cell_data.field_type,
+    &FieldType::from(field_rev.ty),
+    field_rev,
+  )
+}
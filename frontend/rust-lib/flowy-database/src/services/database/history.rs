@@ -0,0 +1,48 @@
+use flowy_client_sync::client_database::DatabaseHistory;
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+pub type DatabaseHistoryRef = Arc<Mutex<DatabaseHistory>>;
+
+/// Groups the changes that are made while it's alive into one undo item. The action ends when it
+/// is dropped, so the early returns of the user-level operations end the action too.
+pub(crate) struct DatabaseHistoryAction {
+  history: DatabaseHistoryRef,
+}
+
+impl DatabaseHistoryAction {
+  pub(crate) fn begin(history: &DatabaseHistoryRef) -> Self {
+    history.lock().begin_action();
+    Self {
+      history: history.clone(),
+    }
+  }
+}
+
+impl Drop for DatabaseHistoryAction {
+  fn drop(&mut self) {
+    self.history.lock().end_action();
+  }
+}
+
+/// Marks the changes that are made while it's alive as derived from the changes of the other
+/// databases, so they are not undoable. For example, the rollup cells that are recalculated after
+/// the related rows were updated.
+pub(crate) struct DatabaseHistoryUntracked {
+  history: DatabaseHistoryRef,
+}
+
+impl DatabaseHistoryUntracked {
+  pub(crate) fn begin(history: &DatabaseHistoryRef) -> Self {
+    history.lock().begin_untracked();
+    Self {
+      history: history.clone(),
+    }
+  }
+}
+
+impl Drop for DatabaseHistoryUntracked {
+  fn drop(&mut self) {
+    self.history.lock().end_untracked();
+  }
+}
@@ -1,10 +1,14 @@
 mod block_editor;
 mod block_manager;
 mod database_editor;
+mod history;
+mod relation;
 mod retry;
 mod trait_impl;
 
 pub use block_editor::*;
 pub use block_manager::*;
 pub use database_editor::*;
+pub use history::*;
+pub use relation::*;
 pub use trait_impl::*;
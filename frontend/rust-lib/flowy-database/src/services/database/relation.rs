@@ -0,0 +1,23 @@
+use database_model::{FieldRevision, RowRevision};
+use flowy_error::FlowyResult;
+use lib_infra::async_trait::async_trait;
+use std::sync::Arc;
+
+/// The fields and the rows of the database that the relation field refers to.
+pub struct RelatedDatabase {
+  pub field_revs: Vec<Arc<FieldRevision>>,
+  pub row_revs: Vec<Arc<RowRevision>>,
+}
+
+/// Provides the other databases to the database editors. The rollup cells read the rows
+/// of the related database through it, and the changes of the rows are dispatched through it to
+/// the databases that relate to them.
+#[async_trait]
+pub trait DatabaseRelationDelegate: Send + Sync + 'static {
+  /// Returns the fields and the rows of the database. The database is read from the disk if it's
+  /// not opened, and it's not kept opened afterwards.
+  async fn get_related_database(&self, database_id: &str) -> FlowyResult<RelatedDatabase>;
+
+  /// Notifies the opened databases that the rows of the database were updated or deleted.
+  async fn did_update_rows(&self, database_id: &str, row_ids: Vec<String>);
+}
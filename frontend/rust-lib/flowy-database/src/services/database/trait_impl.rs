@@ -2,7 +2,7 @@ use crate::entities::FieldType;
 use crate::services::cell::AtomicCellDataCache;
 use crate::services::database::DatabaseBlockManager;
 use crate::services::database_view::DatabaseViewEditorDelegate;
-use crate::services::field::{TypeOptionCellDataHandler, TypeOptionCellExt};
+use crate::services::field::{FormulaCalculator, TypeOptionCellDataHandler, TypeOptionCellExt};
 use crate::services::row::DatabaseBlockRowRevision;
 
 use database_model::{FieldRevision, RowRevision};
@@ -50,25 +50,27 @@ impl DatabaseViewEditorDelegate for DatabaseViewEditorDelegateImpl {
   }
 
   fn get_row_rev(&self, row_id: &str) -> Fut<Option<(usize, Arc<RowRevision>)>> {
+    let pad = self.pad.clone();
     let block_manager = self.block_manager.clone();
     let row_id = row_id.to_owned();
     to_fut(async move {
-      match block_manager.get_row_rev(&row_id).await {
-        Ok(indexed_row) => indexed_row,
-        Err(_) => None,
-      }
+      let (index, row_rev) = block_manager.get_row_rev(&row_id).await.ok()??;
+      let calculator = make_formula_calculator(&pad).await;
+      Some((index, calculator.fill_row(row_rev)))
     })
   }
 
   fn get_row_revs(&self, block_id: Option<Vec<String>>) -> Fut<Vec<Arc<RowRevision>>> {
+    let pad = self.pad.clone();
     let block_manager = self.block_manager.clone();
 
     to_fut(async move {
       let blocks = block_manager.get_blocks(block_id).await.unwrap();
-      blocks
+      let row_revs = blocks
         .into_iter()
         .flat_map(|block| block.row_revs)
-        .collect::<Vec<Arc<RowRevision>>>()
+        .collect::<Vec<Arc<RowRevision>>>();
+      make_formula_calculator(&pad).await.fill_rows(row_revs)
     })
   }
 
@@ -77,8 +79,21 @@ impl DatabaseViewEditorDelegate for DatabaseViewEditorDelegateImpl {
   // }
 
   fn get_blocks(&self) -> Fut<Vec<DatabaseBlockRowRevision>> {
+    let pad = self.pad.clone();
     let block_manager = self.block_manager.clone();
-    to_fut(async move { block_manager.get_blocks(None).await.unwrap_or_default() })
+    to_fut(async move {
+      let calculator = make_formula_calculator(&pad).await;
+      block_manager
+        .get_blocks(None)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|block| DatabaseBlockRowRevision {
+          block_id: block.block_id,
+          row_revs: calculator.fill_rows(block.row_revs),
+        })
+        .collect()
+    })
   }
 
   fn get_task_scheduler(&self) -> Arc<RwLock<TaskDispatcher>> {
@@ -94,3 +109,10 @@ impl DatabaseViewEditorDelegate for DatabaseViewEditorDelegateImpl {
       .get_type_option_cell_data_handler(field_type)
   }
 }
+
+/// The rows that are read by the views are filled with their formula cells, so the formula
+/// cells can be filtered, sorted and calculated like the other cells.
+async fn make_formula_calculator(pad: &Arc<RwLock<DatabaseRevisionPad>>) -> FormulaCalculator {
+  let field_revs = pad.read().await.get_field_revs(None).unwrap_or_default();
+  FormulaCalculator::new(field_revs)
+}
@@ -1,13 +1,15 @@
 use crate::entities::*;
 use crate::notification::{send_notification, DatabaseNotification};
-use crate::services::cell::{AtomicCellDataCache, TypeCellData};
+use crate::services::calculation::{CalculationController, CalculationTaskHandler};
+use crate::services::cell::{AtomicCellDataCache, FromCellString, TypeCellData};
 use crate::services::database::DatabaseBlockEvent;
 use crate::services::database_view::notifier::DatabaseViewChangedNotifier;
 use crate::services::database_view::trait_impl::*;
 use crate::services::database_view::DatabaseViewChangedReceiverRunner;
-use crate::services::field::{RowSingleCellData, TypeOptionCellDataHandler};
+use crate::services::field::{DateCellData, RowSingleCellData, TypeOptionCellDataHandler};
 use crate::services::filter::{
-  FilterChangeset, FilterController, FilterTaskHandler, FilterType, UpdatedFilterType,
+  duration_until_next_day, FilterChangeset, FilterContext, FilterController, FilterTaskHandler,
+  FilterType, UpdatedFilterType,
 };
 use crate::services::group::{
   default_group_configuration, find_group_field, make_group_controller, Group,
@@ -18,14 +20,16 @@ use crate::services::sort::{
   DeletedSortType, SortChangeset, SortController, SortTaskHandler, SortType,
 };
 use database_model::{
-  gen_database_filter_id, gen_database_sort_id, FieldRevision, FieldTypeRevision, FilterRevision,
-  LayoutRevision, RowChangeset, RowRevision, SortRevision,
+  gen_database_calculation_id, gen_database_filter_id, gen_database_sort_id, CalculationRevision,
+  CalendarLayoutSettingRevision, DateCondition, DateGroupConfigurationRevision, FieldRevision,
+  FieldTypeRevision, FilterGroupRevision, FilterRevision, GroupConfigurationContentSerde,
+  GroupConfigurationRevision, LayoutRevision, RowChangeset, RowRevision, SortRevision,
 };
 use flowy_client_sync::client_database::{
   make_grid_view_operations, DatabaseViewRevisionPad, GridViewRevisionChangeset,
 };
-use flowy_error::FlowyResult;
-use flowy_revision::RevisionManager;
+use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
+use flowy_revision::{RevisionManager, RevisionWebSocket};
 use flowy_sqlite::ConnectionPool;
 use flowy_task::TaskDispatcher;
 use lib_infra::async_trait::async_trait;
@@ -34,9 +38,12 @@ use lib_infra::ref_map::RefCountValue;
 use nanoid::nanoid;
 use revision_model::Revision;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+use ws_model::ws_revision::ServerRevisionWSData;
 
 pub trait DatabaseViewEditorDelegate: Send + Sync + 'static {
   /// If the field_ids is None, then it will return all the field revisions
@@ -82,10 +89,15 @@ pub struct DatabaseViewRevisionEditor {
   group_controller: Arc<RwLock<Box<dyn GroupController>>>,
   filter_controller: Arc<FilterController>,
   sort_controller: Arc<RwLock<SortController>>,
+  calculation_controller: Arc<RwLock<CalculationController>>,
   pub notifier: DatabaseViewChangedNotifier,
+  day_rollover_task: parking_lot::Mutex<Option<JoinHandle<()>>>,
+  #[cfg(feature = "sync")]
+  ws_manager: Arc<flowy_revision::RevisionWebSocketManager>,
 }
 
 impl DatabaseViewRevisionEditor {
+  #[allow(unused_variables)]
   #[tracing::instrument(level = "trace", skip_all, err)]
   pub async fn new(
     user_id: &str,
@@ -94,6 +106,7 @@ impl DatabaseViewRevisionEditor {
     delegate: Arc<dyn DatabaseViewEditorDelegate>,
     cell_data_cache: AtomicCellDataCache,
     mut rev_manager: RevisionManager<Arc<ConnectionPool>>,
+    web_socket: Arc<dyn RevisionWebSocket>,
   ) -> FlowyResult<Self> {
     let (notifier, _) = broadcast::channel(100);
     tokio::spawn(DatabaseViewChangedReceiverRunner(Some(notifier.subscribe())).run());
@@ -123,6 +136,17 @@ impl DatabaseViewRevisionEditor {
 
     let view_rev_pad = Arc::new(RwLock::new(view_rev_pad));
     let rev_manager = Arc::new(rev_manager);
+
+    #[cfg(feature = "sync")]
+    let ws_manager = crate::services::web_socket::make_database_ws_manager(
+      "DatabaseView",
+      &crate::services::web_socket::database_view_ws_object_id(&view_id),
+      rev_manager.clone(),
+      web_socket,
+      view_rev_pad.clone(),
+      None,
+    );
+
     let group_controller = new_group_controller(
       user_id.to_owned(),
       view_id.clone(),
@@ -152,6 +176,15 @@ impl DatabaseViewRevisionEditor {
       cell_data_cache,
     )
     .await;
+
+    let calculation_controller = make_calculation_controller(
+      &view_id,
+      delegate.clone(),
+      notifier.clone(),
+      filter_controller.clone(),
+      view_rev_pad.clone(),
+    )
+    .await;
     Ok(Self {
       pad: view_rev_pad,
       user_id,
@@ -161,16 +194,84 @@ impl DatabaseViewRevisionEditor {
       group_controller,
       filter_controller,
       sort_controller,
+      calculation_controller,
       notifier,
+      day_rollover_task: Default::default(),
+      #[cfg(feature = "sync")]
+      ws_manager,
     })
   }
 
   #[tracing::instrument(name = "close grid view editor", level = "trace", skip_all)]
   pub async fn close(&self) {
+    #[cfg(feature = "sync")]
+    self.ws_manager.stop();
+    if let Some(day_rollover_task) = self.day_rollover_task.lock().take() {
+      day_rollover_task.abort();
+    }
     self.rev_manager.generate_snapshot().await;
     self.rev_manager.close().await;
     self.filter_controller.close().await;
     self.sort_controller.read().await.close().await;
+    self.calculation_controller.read().await.close().await;
+  }
+
+  /// The relative date groups, for example, the `Today` group, depend on the current date. So the
+  /// rows are grouped again when the day rolls over if they are grouped by the relative dates.
+  pub(crate) fn spawn_day_rollover_task(self: &Arc<Self>) {
+    let editor = Arc::downgrade(self);
+    let day_rollover_task = tokio::spawn(async move {
+      loop {
+        tokio::time::sleep(duration_until_next_day()).await;
+        let editor = match editor.upgrade() {
+          None => break,
+          Some(editor) => editor,
+        };
+        if editor.is_grouped_by_relative_date().await {
+          let field_id = editor.group_id().await;
+          if let Err(e) = editor.group_by_view_field(&field_id).await {
+            tracing::error!("Regroup the rows by the relative dates failed: {:?}", e);
+          }
+        }
+      }
+    });
+    *self.day_rollover_task.lock() = Some(day_rollover_task);
+  }
+
+  async fn is_grouped_by_relative_date(&self) -> bool {
+    let field_id = self.group_id().await;
+    let field_rev = match self.delegate.get_field_rev(&field_id).await {
+      Some(field_rev) if FieldType::from(field_rev.ty) == FieldType::DateTime => field_rev,
+      _ => return false,
+    };
+    let configuration = self
+      .pad
+      .read()
+      .await
+      .get_groups_by_field_revs(&[field_rev])
+      .pop();
+    let condition = configuration
+      .and_then(|configuration| {
+        DateGroupConfigurationRevision::from_json(&configuration.content).ok()
+      })
+      .unwrap_or_default()
+      .condition;
+    condition == DateCondition::Relative
+  }
+
+  #[cfg(feature = "flowy_unit_test")]
+  pub fn rev_manager(&self) -> Arc<RevisionManager<Arc<ConnectionPool>>> {
+    self.rev_manager.clone()
+  }
+
+  #[cfg(feature = "sync")]
+  pub async fn receive_ws_data(&self, data: ServerRevisionWSData) -> FlowyResult<()> {
+    self.ws_manager.receive_ws_data(data).await
+  }
+
+  #[cfg(not(feature = "sync"))]
+  pub async fn receive_ws_data(&self, _data: ServerRevisionWSData) -> FlowyResult<()> {
+    Ok(())
   }
 
   pub async fn handle_block_event(&self, event: Cow<'_, DatabaseBlockEvent>) {
@@ -217,6 +318,15 @@ impl DatabaseViewRevisionEditor {
     self.filter_controller.filter_row_revs(rows).await;
   }
 
+  /// Returns the rows of all the blocks that are visible in this view, the rows are sorted across
+  /// the blocks rather than within each block.
+  pub async fn get_visible_rows(&self) -> Vec<Arc<RowRevision>> {
+    let mut rows = self.delegate.get_row_revs(None).await;
+    self.filter_controller.filter_row_revs(&mut rows).await;
+    self.sort_rows(&mut rows).await;
+    rows
+  }
+
   pub async fn duplicate_view_data(&self) -> FlowyResult<String> {
     let json_str = self.pad.read().await.json_str()?;
     Ok(json_str)
@@ -259,6 +369,44 @@ impl DatabaseViewRevisionEditor {
         self.notify_did_update_group_rows(changeset).await;
       },
     }
+
+    self
+      .calculation_controller
+      .read()
+      .await
+      .did_receive_row_changed()
+      .await;
+  }
+
+  /// Put the duplicated row right below the original row in the groups of this view, and
+  /// re-evaluate the filters and sorts for the new row.
+  pub async fn did_duplicate_view_row(&self, from_row_id: &str, row_rev: &RowRevision) {
+    let row_changesets = self
+      .group_controller
+      .write()
+      .await
+      .did_duplicate_row(from_row_id, row_rev);
+    for changeset in row_changesets {
+      self.notify_did_update_group_rows(changeset).await;
+    }
+
+    let filter_controller = self.filter_controller.clone();
+    let sort_controller = self.sort_controller.clone();
+    let calculation_controller = self.calculation_controller.clone();
+    let row_id = row_rev.id.clone();
+    tokio::spawn(async move {
+      filter_controller.did_receive_row_changed(&row_id).await;
+      sort_controller
+        .read()
+        .await
+        .did_receive_row_changed(&row_id)
+        .await;
+      calculation_controller
+        .read()
+        .await
+        .did_receive_row_changed()
+        .await;
+    });
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
@@ -276,6 +424,13 @@ impl DatabaseViewRevisionEditor {
         self.notify_did_update_group_rows(changeset).await;
       }
     }
+
+    self
+      .calculation_controller
+      .read()
+      .await
+      .did_receive_row_changed()
+      .await;
   }
 
   pub async fn did_update_view_row(
@@ -315,6 +470,7 @@ impl DatabaseViewRevisionEditor {
 
     let filter_controller = self.filter_controller.clone();
     let sort_controller = self.sort_controller.clone();
+    let calculation_controller = self.calculation_controller.clone();
     let row_id = row_rev.id.clone();
     tokio::spawn(async move {
       filter_controller.did_receive_row_changed(&row_id).await;
@@ -323,6 +479,11 @@ impl DatabaseViewRevisionEditor {
         .await
         .did_receive_row_changed(&row_id)
         .await;
+      calculation_controller
+        .read()
+        .await
+        .did_receive_row_changed()
+        .await;
     });
   }
 
@@ -377,6 +538,36 @@ impl DatabaseViewRevisionEditor {
     Ok(groups.into_iter().map(GroupPB::from).collect())
   }
 
+  /// Saves the number group configuration of the field and regroups the rows with it.
+  pub async fn update_view_number_group_configuration(
+    &self,
+    params: UpdateNumberGroupConfigurationParams,
+  ) -> FlowyResult<()> {
+    let field_rev = match self.delegate.get_field_rev(&params.field_id).await {
+      None => return Err(FlowyError::record_not_found().context("Can't find the number field")),
+      Some(field_rev) => field_rev,
+    };
+    let field_type: FieldType = field_rev.ty.into();
+    if !field_type.is_number() {
+      return Err(FlowyError::internal().context("The field type must be number"));
+    }
+
+    let configuration =
+      GroupConfigurationRevision::new(field_rev.id.clone(), field_rev.ty, params.configuration)
+        .map_err(internal_error)?;
+    self
+      .modify(|pad| {
+        let changeset =
+          pad.insert_or_update_group_configuration(&field_rev.id, &field_rev.ty, configuration)?;
+        Ok(changeset)
+      })
+      .await?;
+
+    self.group_by_view_field(&params.field_id).await?;
+    self.notify_did_update_setting().await;
+    Ok(())
+  }
+
   #[tracing::instrument(level = "trace", skip(self), err)]
   pub async fn move_view_group(&self, params: MoveGroupParams) -> FlowyResult<()> {
     self
@@ -543,6 +734,72 @@ impl DatabaseViewRevisionEditor {
     Ok(())
   }
 
+  pub async fn get_all_view_calculations(&self) -> Vec<Arc<CalculationRevision>> {
+    let field_revs = self.delegate.get_field_revs(None).await;
+    self.pad.read().await.get_all_calculations(&field_revs)
+  }
+
+  pub async fn get_view_calculation_values(&self) -> RepeatedCalculationValuePB {
+    let items = self
+      .calculation_controller
+      .read()
+      .await
+      .calculate_values()
+      .await;
+    RepeatedCalculationValuePB {
+      view_id: self.view_id.clone(),
+      items,
+    }
+  }
+
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn insert_view_calculation(
+    &self,
+    params: AlterCalculationParams,
+  ) -> FlowyResult<CalculationRevision> {
+    let calculation_rev = CalculationRevision {
+      id: params
+        .calculation_id
+        .clone()
+        .unwrap_or_else(gen_database_calculation_id),
+      field_id: params.field_id.clone(),
+      field_type: params.field_type,
+      calculation_type: params.calculation_type.into(),
+    };
+
+    let mut calculation_controller = self.calculation_controller.write().await;
+    self
+      .modify(|pad| {
+        let changeset = match params.calculation_id {
+          None => pad.insert_calculation(&params.field_id, calculation_rev.clone())?,
+          Some(_) => pad.update_calculation(&params.field_id, calculation_rev.clone())?,
+        };
+        Ok(changeset)
+      })
+      .await?;
+    let notification = calculation_controller.did_receive_changes().await;
+    drop(calculation_controller);
+
+    self.notify_did_update_calculations(notification).await;
+    Ok(calculation_rev)
+  }
+
+  pub async fn delete_view_calculation(&self, params: DeleteCalculationParams) -> FlowyResult<()> {
+    let mut calculation_controller = self.calculation_controller.write().await;
+    self
+      .modify(|pad| {
+        let changeset =
+          pad.delete_calculation(&params.calculation_id, &params.field_id, params.field_type)?;
+        Ok(changeset)
+      })
+      .await?;
+    let notification = calculation_controller.did_receive_changes().await;
+    drop(calculation_controller);
+
+    self.notify_did_update_calculations(notification).await;
+    Ok(())
+  }
+
   pub async fn get_all_view_filters(&self) -> Vec<Arc<FilterRevision>> {
     let field_revs = self.delegate.get_field_revs(None).await;
     self.pad.read().await.get_all_filters(&field_revs)
@@ -573,12 +830,8 @@ impl DatabaseViewRevisionEditor {
       content: params.content,
     };
     let filter_controller = self.filter_controller.clone();
+    let filter = FilterContext::new(&filter_id, filter_type);
     let changeset = if is_exist {
-      let old_filter_type = self
-        .delegate
-        .get_field_rev(&params.field_id)
-        .await
-        .map(|field| FilterType::from(&field));
       self
         .modify(|pad| {
           let changeset = pad.update_filter(&params.field_id, filter_rev)?;
@@ -586,23 +839,27 @@ impl DatabaseViewRevisionEditor {
         })
         .await?;
       filter_controller
-        .did_receive_changes(FilterChangeset::from_update(UpdatedFilterType::new(
-          old_filter_type,
-          filter_type,
-        )))
+        .did_receive_changes(FilterChangeset::from_update(filter))
         .await
     } else {
       self
         .modify(|pad| {
-          let changeset = pad.insert_filter(&params.field_id, filter_rev)?;
+          let changeset =
+            pad.insert_filter(params.group_id.as_deref(), &params.field_id, filter_rev)?;
           Ok(changeset)
         })
         .await?;
       filter_controller
-        .did_receive_changes(FilterChangeset::from_insert(filter_type))
+        .did_receive_changes(FilterChangeset::from_insert(filter))
         .await
     };
     drop(filter_controller);
+    self
+      .calculation_controller
+      .read()
+      .await
+      .did_receive_filter_changed()
+      .await;
 
     if let Some(changeset) = changeset {
       self.notify_did_update_filter(changeset).await;
@@ -615,7 +872,10 @@ impl DatabaseViewRevisionEditor {
     let filter_type = params.filter_type;
     let changeset = self
       .filter_controller
-      .did_receive_changes(FilterChangeset::from_delete(filter_type.clone()))
+      .did_receive_changes(FilterChangeset::from_delete(FilterContext::new(
+        &params.filter_id,
+        filter_type.clone(),
+      )))
       .await;
 
     self
@@ -629,12 +889,120 @@ impl DatabaseViewRevisionEditor {
       })
       .await?;
 
+    self
+      .calculation_controller
+      .read()
+      .await
+      .did_receive_filter_changed()
+      .await;
+
     if changeset.is_some() {
       self.notify_did_update_filter(changeset.unwrap()).await;
     }
     Ok(())
   }
 
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn insert_view_filter_group(&self, params: InsertFilterGroupParams) -> FlowyResult<()> {
+    let group_rev = FilterGroupRevision::new(params.operator);
+    self
+      .modify(|pad| {
+        let changeset = pad.insert_filter_group(params.parent_group_id.as_deref(), group_rev)?;
+        Ok(changeset)
+      })
+      .await?;
+
+    self
+      .filter_controller
+      .did_receive_filter_group_changed()
+      .await;
+    self
+      .calculation_controller
+      .read()
+      .await
+      .did_receive_filter_changed()
+      .await;
+    self
+      .notify_did_update_filter(FilterChangesetNotificationPB::new(&self.view_id))
+      .await;
+    Ok(())
+  }
+
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn update_view_filter_group(&self, params: UpdateFilterGroupParams) -> FlowyResult<()> {
+    self
+      .modify(|pad| {
+        let changeset =
+          pad.update_filter_group_operator(params.group_id.as_deref(), params.operator)?;
+        Ok(changeset)
+      })
+      .await?;
+
+    self
+      .filter_controller
+      .did_receive_filter_group_changed()
+      .await;
+    self
+      .calculation_controller
+      .read()
+      .await
+      .did_receive_filter_changed()
+      .await;
+    self
+      .notify_did_update_filter(FilterChangesetNotificationPB::new(&self.view_id))
+      .await;
+    Ok(())
+  }
+
+  /// Deletes the filter group, including its filters and its nested groups.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn delete_view_filter_group(&self, params: DeleteFilterGroupParams) -> FlowyResult<()> {
+    let filter_ids = match self
+      .pad
+      .read()
+      .await
+      .get_filter_group(Some(params.group_id.as_str()))
+    {
+      None => return Ok(()),
+      Some(group_rev) => group_rev.all_filter_ids(),
+    };
+
+    let mut notification = FilterChangesetNotificationPB::new(&self.view_id);
+    for filter_rev in self.get_all_view_filters().await {
+      if !filter_ids.contains(&filter_rev.id) {
+        continue;
+      }
+      let filter = FilterContext::from(filter_rev.as_ref());
+      if let Some(changeset) = self
+        .filter_controller
+        .did_receive_changes(FilterChangeset::from_delete(filter))
+        .await
+      {
+        notification.delete_filters.extend(changeset.delete_filters);
+      }
+    }
+
+    self
+      .modify(|pad| {
+        let changeset = pad.delete_filter_group(&params.group_id)?;
+        Ok(changeset)
+      })
+      .await?;
+
+    self
+      .filter_controller
+      .did_receive_filter_group_changed()
+      .await;
+    self
+      .calculation_controller
+      .read()
+      .await
+      .did_receive_filter_changed()
+      .await;
+    self.notify_did_update_filter(notification).await;
+    Ok(())
+  }
+
   #[tracing::instrument(level = "trace", skip_all, err)]
   pub async fn did_update_view_field_type_option(
     &self,
@@ -645,7 +1013,7 @@ impl DatabaseViewRevisionEditor {
       let old = old_field_rev.map(|old_field_rev| FilterType::from(&old_field_rev));
       let new = FilterType::from(&field_rev);
       let filter_type = UpdatedFilterType::new(old, new);
-      let filter_changeset = FilterChangeset::from_update(filter_type);
+      let filter_changeset = FilterChangeset::from_update_field_type(filter_type);
 
       self
         .sort_controller
@@ -654,6 +1022,15 @@ impl DatabaseViewRevisionEditor {
         .did_update_view_field_type_option(&field_rev)
         .await;
 
+      // The calculations of the old field type are not available after switching the field type.
+      let notification = self
+        .calculation_controller
+        .write()
+        .await
+        .did_receive_changes()
+        .await;
+      self.notify_did_update_calculations(notification).await;
+
       let filter_controller = self.filter_controller.clone();
       let _ = tokio::spawn(async move {
         if let Some(notification) = filter_controller
@@ -717,6 +1094,126 @@ impl DatabaseViewRevisionEditor {
     Ok(())
   }
 
+  /// Returns the calendar layout setting of the view. If the setting was never saved, the first
+  /// date field will be used as the layout field.
+  pub async fn get_calendar_setting(&self) -> Option<CalendarLayoutSettingRevision> {
+    if let Some(setting) = self.pad.read().await.get_calendar_setting() {
+      return Some(setting);
+    }
+
+    self
+      .delegate
+      .get_field_revs(None)
+      .await
+      .into_iter()
+      .find(|field_rev| FieldType::from(field_rev.ty).is_date())
+      .map(|field_rev| CalendarLayoutSettingRevision::new(field_rev.id.clone()))
+  }
+
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn update_calendar_setting(
+    &self,
+    setting: CalendarLayoutSettingRevision,
+  ) -> FlowyResult<()> {
+    match self.delegate.get_field_rev(&setting.layout_field_id).await {
+      Some(field_rev) if FieldType::from(field_rev.ty).is_date() => {},
+      _ => {
+        return Err(FlowyError::new(
+          ErrorCode::FieldInvalidOperation,
+          "The calendar layout field must be a date field",
+        ))
+      },
+    }
+
+    self
+      .modify(|pad| {
+        let changeset = pad.update_calendar_setting(setting.clone())?;
+        Ok(changeset)
+      })
+      .await?;
+
+    send_notification(
+      &self.view_id,
+      DatabaseNotification::DidUpdateCalendarSettings,
+    )
+    .payload(CalendarLayoutSettingsPB::from(setting))
+    .send();
+    Ok(())
+  }
+
+  /// Returns the rows whose date falls in [start_timestamp, end_timestamp], grouped by day.
+  /// The rows that are filtered out by the view's filters are not included.
+  pub async fn get_calendar_events(
+    &self,
+    start_timestamp: i64,
+    end_timestamp: i64,
+  ) -> FlowyResult<Vec<CalendarDayPB>> {
+    let layout_field_rev = match self.get_calendar_setting().await {
+      None => return Ok(vec![]),
+      Some(setting) => match self.delegate.get_field_rev(&setting.layout_field_id).await {
+        None => return Ok(vec![]),
+        Some(field_rev) => field_rev,
+      },
+    };
+    let primary_field_rev = self
+      .delegate
+      .get_field_revs(None)
+      .await
+      .into_iter()
+      .find(|field_rev| field_rev.is_primary);
+
+    let mut row_revs = self.delegate.get_row_revs(None).await;
+    self.filter_controller.filter_row_revs(&mut row_revs).await;
+
+    let mut events_by_day: BTreeMap<i64, Vec<CalendarEventPB>> = BTreeMap::new();
+    for row_rev in row_revs {
+      let timestamp = match get_row_timestamp(&row_rev, &layout_field_rev.id) {
+        Some(timestamp) if timestamp >= start_timestamp && timestamp <= end_timestamp => timestamp,
+        _ => continue,
+      };
+      let title = match primary_field_rev.as_ref() {
+        None => "".to_owned(),
+        Some(field_rev) => self.stringify_row_cell(&row_rev, field_rev),
+      };
+      events_by_day
+        .entry(start_of_day(timestamp))
+        .or_default()
+        .push(CalendarEventPB {
+          row_id: row_rev.id.clone(),
+          title,
+          timestamp,
+        });
+    }
+
+    Ok(
+      events_by_day
+        .into_iter()
+        .map(|(timestamp, events)| CalendarDayPB { timestamp, events })
+        .collect(),
+    )
+  }
+
+  fn stringify_row_cell(&self, row_rev: &RowRevision, field_rev: &FieldRevision) -> String {
+    let field_type = FieldType::from(field_rev.ty);
+    let cell_str = row_rev
+      .cells
+      .get(&field_rev.id)
+      .and_then(|cell_rev| TypeCellData::try_from(cell_rev).ok())
+      .map(|type_cell_data| type_cell_data.cell_str);
+
+    match (
+      cell_str,
+      self
+        .delegate
+        .get_type_option_cell_handler(field_rev, &field_type),
+    ) {
+      (Some(cell_str), Some(handler)) => {
+        handler.stringify_cell_str(cell_str, &field_type, field_rev)
+      },
+      _ => "".to_owned(),
+    }
+  }
+
   pub(crate) async fn get_cells_for_field(
     &self,
     field_id: &str,
@@ -737,7 +1234,10 @@ impl DatabaseViewRevisionEditor {
       .send();
   }
 
-  pub async fn notify_did_update_filter(&self, notification: FilterChangesetNotificationPB) {
+  pub async fn notify_did_update_filter(&self, mut notification: FilterChangesetNotificationPB) {
+    // Attach the latest filter groups, the filters may be moved in or out of the groups.
+    let filter_groups = RepeatedFilterGroupPB::from(self.pad.read().await.filters.root_group());
+    notification.filter_groups = Some(filter_groups);
     send_notification(&notification.view_id, DatabaseNotification::DidUpdateFilter)
       .payload(notification)
       .send();
@@ -751,6 +1251,20 @@ impl DatabaseViewRevisionEditor {
     }
   }
 
+  pub async fn notify_did_update_calculations(
+    &self,
+    notification: CalculationChangesetNotificationPB,
+  ) {
+    if !notification.is_empty() {
+      send_notification(
+        &notification.view_id,
+        DatabaseNotification::DidUpdateCalculations,
+      )
+      .payload(notification)
+      .send();
+    }
+  }
+
   async fn notify_did_update_groups(&self, changeset: GroupChangesetPB) {
     send_notification(&self.view_id, DatabaseNotification::DidUpdateGroups)
       .payload(changeset)
@@ -803,6 +1317,25 @@ impl DatabaseViewRevisionEditor {
     }
   }
 }
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// Returns the timestamp of the start of the day (UTC) that the `timestamp` belongs to.
+pub(crate) fn start_of_day(timestamp: i64) -> i64 {
+  timestamp - timestamp.rem_euclid(SECONDS_PER_DAY)
+}
+
+/// Returns the timestamp stored in the date cell of the row. The cell is ignored if its data
+/// was not produced by a date field.
+pub(crate) fn get_row_timestamp(row_rev: &RowRevision, field_id: &str) -> Option<i64> {
+  let type_cell_data = TypeCellData::try_from(row_rev.cells.get(field_id)?).ok()?;
+  if !type_cell_data.is_date() {
+    return None;
+  }
+  DateCellData::from_cell_str(&type_cell_data.cell_str)
+    .ok()?
+    .0
+}
+
 /// Returns the list of cells corresponding to the given field.
 pub(crate) async fn get_cells_for_field(
   delegate: Arc<dyn DatabaseViewEditorDelegate>,
@@ -974,6 +1507,41 @@ async fn make_sort_controller(
   sort_controller
 }
 
+async fn make_calculation_controller(
+  view_id: &str,
+  delegate: Arc<dyn DatabaseViewEditorDelegate>,
+  notifier: DatabaseViewChangedNotifier,
+  filter_controller: Arc<FilterController>,
+  pad: Arc<RwLock<DatabaseViewRevisionPad>>,
+) -> Arc<RwLock<CalculationController>> {
+  let handler_id = gen_handler_id();
+  let field_revs = delegate.get_field_revs(None).await;
+  let calculations = pad.read().await.get_all_calculations(&field_revs);
+  let calculation_delegate = GridViewCalculationDelegateImpl {
+    editor_delegate: delegate.clone(),
+    view_revision_pad: pad,
+    filter_controller,
+  };
+  let task_scheduler = delegate.get_task_scheduler();
+  let calculation_controller = Arc::new(RwLock::new(CalculationController::new(
+    view_id,
+    &handler_id,
+    calculations,
+    calculation_delegate,
+    task_scheduler.clone(),
+    notifier,
+  )));
+  task_scheduler
+    .write()
+    .await
+    .register_handler(CalculationTaskHandler::new(
+      handler_id,
+      calculation_controller.clone(),
+    ));
+
+  calculation_controller
+}
+
 fn gen_handler_id() -> String {
   nanoid!(10)
 }
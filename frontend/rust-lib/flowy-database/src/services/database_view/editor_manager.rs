@@ -1,6 +1,9 @@
 use crate::entities::{
-  AlterFilterParams, AlterSortParams, CreateRowParams, DatabaseViewSettingPB, DeleteFilterParams,
-  DeleteGroupParams, DeleteSortParams, InsertGroupParams, MoveGroupParams, RepeatedGroupPB, RowPB,
+  AlterCalculationParams, AlterFilterParams, AlterSortParams, CalendarDayPB,
+  CalendarEventRequestParams, CreateRowParams, DatabaseViewSettingPB, DeleteCalculationParams,
+  DeleteFilterParams, DeleteGroupParams, DeleteSortParams, InsertGroupParams, MoveGroupParams,
+  RepeatedCalculationValuePB, RepeatedGroupPB, RowPB, UpdateCalendarSettingParams,
+  UpdateNumberGroupConfigurationParams,
 };
 use crate::manager::DatabaseUser;
 use crate::services::cell::AtomicCellDataCache;
@@ -12,15 +15,22 @@ use crate::services::filter::FilterType;
 use crate::services::persistence::rev_sqlite::{
   SQLiteDatabaseRevisionSnapshotPersistence, SQLiteGridViewRevisionPersistence,
 };
-use database_model::{FieldRevision, FilterRevision, RowChangeset, RowRevision, SortRevision};
+use crate::services::web_socket::DATABASE_VIEW_WS_PREFIX;
+use database_model::{
+  CalculationRevision, CalendarLayoutSettingRevision, FieldRevision, FilterRevision, RowChangeset,
+  RowRevision, SortRevision,
+};
 use flowy_error::FlowyResult;
-use flowy_revision::{RevisionManager, RevisionPersistence, RevisionPersistenceConfiguration};
+use flowy_revision::{
+  RevisionManager, RevisionPersistence, RevisionPersistenceConfiguration, RevisionWebSocket,
+};
 use flowy_sqlite::ConnectionPool;
 use lib_infra::future::Fut;
 use lib_infra::ref_map::RefCountHashMap;
 use std::borrow::Cow;
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
+use ws_model::ws_revision::ServerRevisionWSData;
 
 pub struct DatabaseViewManager {
   database_id: String,
@@ -28,6 +38,7 @@ pub struct DatabaseViewManager {
   delegate: Arc<dyn DatabaseViewEditorDelegate>,
   view_editors: Arc<RwLock<RefCountHashMap<Arc<DatabaseViewRevisionEditor>>>>,
   cell_data_cache: AtomicCellDataCache,
+  web_socket: Arc<dyn RevisionWebSocket>,
 }
 
 impl DatabaseViewManager {
@@ -37,6 +48,7 @@ impl DatabaseViewManager {
     delegate: Arc<dyn DatabaseViewEditorDelegate>,
     cell_data_cache: AtomicCellDataCache,
     block_event_rx: broadcast::Receiver<DatabaseBlockEvent>,
+    web_socket: Arc<dyn RevisionWebSocket>,
   ) -> FlowyResult<Self> {
     let view_editors = Arc::new(RwLock::new(RefCountHashMap::default()));
     listen_on_database_block_event(block_event_rx, view_editors.clone());
@@ -46,9 +58,26 @@ impl DatabaseViewManager {
       delegate,
       cell_data_cache,
       view_editors,
+      web_socket,
     })
   }
 
+  /// Passes the web socket data to the opened view that it belongs to. Returns false if the data
+  /// doesn't belong to any view.
+  pub async fn receive_ws_data(&self, data: ServerRevisionWSData) -> FlowyResult<bool> {
+    let view_editor = match data.object_id.strip_prefix(DATABASE_VIEW_WS_PREFIX) {
+      None => return Ok(false),
+      Some(view_id) => self.view_editors.read().await.get(view_id),
+    };
+    match view_editor {
+      None => Ok(false),
+      Some(view_editor) => {
+        view_editor.receive_ws_data(data).await?;
+        Ok(true)
+      },
+    }
+  }
+
   pub async fn close(&self, view_id: &str) {
     self.view_editors.write().await.remove(view_id).await;
   }
@@ -77,6 +106,12 @@ impl DatabaseViewManager {
     Ok(row_revs)
   }
 
+  /// Returns the rows that are visible in the view, in the order they are displayed.
+  pub async fn get_visible_row_revs(&self, view_id: &str) -> FlowyResult<Vec<Arc<RowRevision>>> {
+    let view_editor = self.get_view_editor(view_id).await?;
+    Ok(view_editor.get_visible_rows().await)
+  }
+
   pub async fn duplicate_database_view(&self) -> FlowyResult<String> {
     let editor = self.get_default_view_editor().await?;
     let view_data = editor.duplicate_view_data().await?;
@@ -97,6 +132,16 @@ impl DatabaseViewManager {
     }
   }
 
+  /// Notify the views that the row with `from_row_id` was duplicated. Each view puts the new row
+  /// into the groups that contain the original row.
+  pub async fn did_duplicate_row(&self, from_row_id: &str, row_rev: &RowRevision) {
+    for view_editor in self.view_editors.read().await.values() {
+      view_editor
+        .did_duplicate_view_row(from_row_id, row_rev)
+        .await;
+    }
+  }
+
   /// Insert/Delete the group's row if the corresponding cell data was changed.  
   pub async fn did_update_row(&self, old_row_rev: Option<Arc<RowRevision>>, row_id: &str) {
     match self.delegate.get_row_rev(row_id).await {
@@ -150,6 +195,21 @@ impl DatabaseViewManager {
     view_editor.delete_view_filter(params).await
   }
 
+  pub async fn insert_filter_group(&self, params: InsertFilterGroupParams) -> FlowyResult<()> {
+    let view_editor = self.get_view_editor(&params.view_id).await?;
+    view_editor.insert_view_filter_group(params).await
+  }
+
+  pub async fn update_filter_group(&self, params: UpdateFilterGroupParams) -> FlowyResult<()> {
+    let view_editor = self.get_view_editor(&params.view_id).await?;
+    view_editor.update_view_filter_group(params).await
+  }
+
+  pub async fn delete_filter_group(&self, params: DeleteFilterGroupParams) -> FlowyResult<()> {
+    let view_editor = self.get_view_editor(&params.view_id).await?;
+    view_editor.delete_view_filter_group(params).await
+  }
+
   pub async fn get_all_sorts(&self, view_id: &str) -> FlowyResult<Vec<Arc<SortRevision>>> {
     let view_editor = self.get_view_editor(view_id).await?;
     Ok(view_editor.get_all_view_sorts().await)
@@ -170,6 +230,61 @@ impl DatabaseViewManager {
     view_editor.delete_view_sort(params).await
   }
 
+  pub async fn get_all_calculations(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<Vec<Arc<CalculationRevision>>> {
+    let view_editor = self.get_view_editor(view_id).await?;
+    Ok(view_editor.get_all_view_calculations().await)
+  }
+
+  pub async fn get_calculation_values(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<RepeatedCalculationValuePB> {
+    let view_editor = self.get_view_editor(view_id).await?;
+    Ok(view_editor.get_view_calculation_values().await)
+  }
+
+  pub async fn create_or_update_calculation(
+    &self,
+    params: AlterCalculationParams,
+  ) -> FlowyResult<CalculationRevision> {
+    let view_editor = self.get_view_editor(&params.view_id).await?;
+    view_editor.insert_view_calculation(params).await
+  }
+
+  pub async fn delete_calculation(&self, params: DeleteCalculationParams) -> FlowyResult<()> {
+    let view_editor = self.get_view_editor(&params.view_id).await?;
+    view_editor.delete_view_calculation(params).await
+  }
+
+  pub async fn get_calendar_setting(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<Option<CalendarLayoutSettingRevision>> {
+    let view_editor = self.get_view_editor(view_id).await?;
+    Ok(view_editor.get_calendar_setting().await)
+  }
+
+  pub async fn update_calendar_setting(
+    &self,
+    params: UpdateCalendarSettingParams,
+  ) -> FlowyResult<()> {
+    let view_editor = self.get_view_editor(&params.view_id).await?;
+    view_editor.update_calendar_setting(params.setting).await
+  }
+
+  pub async fn get_calendar_events(
+    &self,
+    params: CalendarEventRequestParams,
+  ) -> FlowyResult<Vec<CalendarDayPB>> {
+    let view_editor = self.get_view_editor(&params.view_id).await?;
+    view_editor
+      .get_calendar_events(params.start_timestamp, params.end_timestamp)
+      .await
+  }
+
   pub async fn load_groups(&self) -> FlowyResult<RepeatedGroupPB> {
     let view_editor = self.get_default_view_editor().await?;
     let groups = view_editor.load_view_groups().await?;
@@ -186,6 +301,16 @@ impl DatabaseViewManager {
     view_editor.delete_view_group(params).await
   }
 
+  pub async fn update_number_group_configuration(
+    &self,
+    params: UpdateNumberGroupConfigurationParams,
+  ) -> FlowyResult<()> {
+    let view_editor = self.get_view_editor(&params.view_id).await?;
+    view_editor
+      .update_view_number_group_configuration(params)
+      .await
+  }
+
   pub async fn move_group(&self, params: MoveGroupParams) -> FlowyResult<()> {
     let view_editor = self.get_default_view_editor().await?;
     view_editor.move_view_group(params).await?;
@@ -255,6 +380,7 @@ impl DatabaseViewManager {
     tracing::trace!("{:p} create view_editor", self);
     let mut view_editors = self.view_editors.write().await;
     let editor = Arc::new(self.make_view_editor(view_id).await?);
+    editor.spawn_day_rollover_task();
     view_editors.insert(view_id.to_owned(), editor.clone());
     Ok(editor)
   }
@@ -276,6 +402,7 @@ impl DatabaseViewManager {
       self.delegate.clone(),
       self.cell_data_cache.clone(),
       rev_manager,
+      self.web_socket.clone(),
     )
     .await
   }
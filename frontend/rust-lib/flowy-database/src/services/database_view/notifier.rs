@@ -1,5 +1,8 @@
-use crate::entities::{ReorderAllRowsPB, ReorderSingleRowPB, ViewRowsVisibilityChangesetPB};
+use crate::entities::{
+  ReorderAllRowsPB, ReorderSingleRowPB, RepeatedCalculationValuePB, ViewRowsVisibilityChangesetPB,
+};
 use crate::notification::{send_notification, DatabaseNotification};
+use crate::services::calculation::CalculationValuesResult;
 use crate::services::filter::FilterResultNotification;
 use crate::services::sort::{ReorderAllRowsResult, ReorderSingleRowResult};
 use async_stream::stream;
@@ -11,6 +14,7 @@ pub enum DatabaseViewChanged {
   FilterNotification(FilterResultNotification),
   ReorderAllRowsNotification(ReorderAllRowsResult),
   ReorderSingleRowNotification(ReorderSingleRowResult),
+  CalculationNotification(CalculationValuesResult),
 }
 
 pub type DatabaseViewChangedNotifier = broadcast::Sender<DatabaseViewChanged>;
@@ -68,6 +72,18 @@ impl DatabaseViewChangedReceiverRunner {
             .payload(reorder_row)
             .send()
           },
+          DatabaseViewChanged::CalculationNotification(notification) => {
+            let values = RepeatedCalculationValuePB {
+              view_id: notification.view_id,
+              items: notification.values,
+            };
+            send_notification(
+              &values.view_id,
+              DatabaseNotification::DidUpdateCalculationValues,
+            )
+            .payload(values)
+            .send()
+          },
         }
       })
       .await;
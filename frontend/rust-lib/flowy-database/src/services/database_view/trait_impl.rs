@@ -1,14 +1,17 @@
 use crate::entities::{DatabaseViewSettingPB, LayoutTypePB, ViewLayoutPB};
+use crate::services::calculation::CalculationDelegate;
 use crate::services::database_view::{get_cells_for_field, DatabaseViewEditorDelegate};
 use crate::services::field::RowSingleCellData;
-use crate::services::filter::{FilterController, FilterDelegate, FilterType};
+use crate::services::filter::{
+  FilterContext, FilterController, FilterDelegate, FilterGroup, FilterType,
+};
 use crate::services::group::{GroupConfigurationReader, GroupConfigurationWriter};
 use crate::services::row::DatabaseBlockRowRevision;
 use crate::services::sort::{SortDelegate, SortType};
 use bytes::Bytes;
 use database_model::{
-  FieldRevision, FieldTypeRevision, FilterRevision, GroupConfigurationRevision, RowRevision,
-  SortRevision,
+  CalculationRevision, FieldRevision, FieldTypeRevision, FilterRevision,
+  GroupConfigurationRevision, RowRevision, SortRevision,
 };
 use flowy_client_sync::client_database::{DatabaseViewRevisionPad, GridViewRevisionChangeset};
 use flowy_client_sync::make_operations_from_revisions;
@@ -150,12 +153,15 @@ pub fn make_grid_setting(
   let filters = view_pad.get_all_filters(field_revs);
   let group_configurations = view_pad.get_groups_by_field_revs(field_revs);
   let sorts = view_pad.get_all_sorts(field_revs);
+  let calculations = view_pad.get_all_calculations(field_revs);
   DatabaseViewSettingPB {
     support_layouts: ViewLayoutPB::all(),
     current_layout: layout_type,
     filters: filters.into(),
     sorts: sorts.into(),
     group_configurations: group_configurations.into(),
+    filter_groups: view_pad.filters.root_group().into(),
+    calculations: calculations.into(),
   }
 }
 
@@ -165,20 +171,26 @@ pub(crate) struct GridViewFilterDelegateImpl {
 }
 
 impl FilterDelegate for GridViewFilterDelegateImpl {
-  fn get_filter_rev(&self, filter_type: FilterType) -> Fut<Option<Arc<FilterRevision>>> {
+  fn get_filter_rev(&self, filter: FilterContext) -> Fut<Option<Arc<FilterRevision>>> {
+    let pad = self.view_revision_pad.clone();
+    to_fut(async move {
+      let field_type_rev: FieldTypeRevision = filter.filter_type.field_type.into();
+      pad.read().await.get_filter(
+        &filter.filter_type.field_id,
+        &field_type_rev,
+        &filter.filter_id,
+      )
+    })
+  }
+
+  fn get_filter_revs(&self, filter_type: FilterType) -> Fut<Vec<Arc<FilterRevision>>> {
     let pad = self.view_revision_pad.clone();
     to_fut(async move {
       let field_type_rev: FieldTypeRevision = filter_type.field_type.into();
-      let mut filters = pad
+      pad
         .read()
         .await
-        .get_filters(&filter_type.field_id, &field_type_rev);
-      if filters.is_empty() {
-        None
-      } else {
-        debug_assert_eq!(filters.len(), 1);
-        filters.pop()
-      }
+        .get_filters(&filter_type.field_id, &field_type_rev)
     })
   }
 
@@ -197,6 +209,17 @@ impl FilterDelegate for GridViewFilterDelegateImpl {
   fn get_row_rev(&self, row_id: &str) -> Fut<Option<(usize, Arc<RowRevision>)>> {
     self.editor_delegate.get_row_rev(row_id)
   }
+
+  fn get_filter_group(&self) -> Fut<FilterGroup> {
+    let editor_delegate = self.editor_delegate.clone();
+    let pad = self.view_revision_pad.clone();
+    to_fut(async move {
+      let field_revs = editor_delegate.get_field_revs(None).await;
+      let pad = pad.read().await;
+      let filter_revs = pad.get_all_filters(&field_revs);
+      FilterGroup::new(pad.filters.root_group(), &filter_revs)
+    })
+  }
 }
 
 pub(crate) struct GridViewSortDelegateImpl {
@@ -242,3 +265,34 @@ impl SortDelegate for GridViewSortDelegateImpl {
     self.editor_delegate.get_field_revs(field_ids)
   }
 }
+
+pub(crate) struct GridViewCalculationDelegateImpl {
+  pub(crate) editor_delegate: Arc<dyn DatabaseViewEditorDelegate>,
+  pub(crate) view_revision_pad: Arc<RwLock<DatabaseViewRevisionPad>>,
+  pub(crate) filter_controller: Arc<FilterController>,
+}
+
+impl CalculationDelegate for GridViewCalculationDelegateImpl {
+  fn get_calculation_revs(&self) -> Fut<Vec<Arc<CalculationRevision>>> {
+    let editor_delegate = self.editor_delegate.clone();
+    let pad = self.view_revision_pad.clone();
+    to_fut(async move {
+      let field_revs = editor_delegate.get_field_revs(None).await;
+      pad.read().await.get_all_calculations(&field_revs)
+    })
+  }
+
+  fn get_row_revs(&self) -> Fut<Vec<Arc<RowRevision>>> {
+    let filter_controller = self.filter_controller.clone();
+    let editor_delegate = self.editor_delegate.clone();
+    to_fut(async move {
+      let mut row_revs = editor_delegate.get_row_revs(None).await;
+      filter_controller.filter_row_revs(&mut row_revs).await;
+      row_revs
+    })
+  }
+
+  fn get_field_revs(&self, field_ids: Option<Vec<String>>) -> Fut<Vec<Arc<FieldRevision>>> {
+    self.editor_delegate.get_field_revs(field_ids)
+  }
+}
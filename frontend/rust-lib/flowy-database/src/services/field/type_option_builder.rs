@@ -21,6 +21,9 @@ pub fn default_type_option_builder_from_type(field_type: &FieldType) -> Box<dyn
     FieldType::Checkbox => CheckboxTypeOptionPB::default().into(),
     FieldType::URL => URLTypeOptionPB::default().into(),
     FieldType::Checklist => ChecklistTypeOptionPB::default().into(),
+    FieldType::Formula => FormulaTypeOptionPB::default().into(),
+    FieldType::Relation => RelationTypeOptionPB::default().into(),
+    FieldType::Rollup => RollupTypeOptionPB::default().into(),
   };
 
   type_option_builder_from_json_str(&s, field_type)
@@ -39,6 +42,9 @@ pub fn type_option_builder_from_json_str(
     FieldType::Checkbox => Box::new(CheckboxTypeOptionBuilder::from_json_str(s)),
     FieldType::URL => Box::new(URLTypeOptionBuilder::from_json_str(s)),
     FieldType::Checklist => Box::new(ChecklistTypeOptionBuilder::from_json_str(s)),
+    FieldType::Formula => Box::new(FormulaTypeOptionBuilder::from_json_str(s)),
+    FieldType::Relation => Box::new(RelationTypeOptionBuilder::from_json_str(s)),
+    FieldType::Rollup => Box::new(RollupTypeOptionBuilder::from_json_str(s)),
   }
 }
 
@@ -56,5 +62,8 @@ pub fn type_option_builder_from_bytes<T: Into<Bytes>>(
     FieldType::Checkbox => Box::new(CheckboxTypeOptionBuilder::from_protobuf_bytes(bytes)),
     FieldType::URL => Box::new(URLTypeOptionBuilder::from_protobuf_bytes(bytes)),
     FieldType::Checklist => Box::new(ChecklistTypeOptionBuilder::from_protobuf_bytes(bytes)),
+    FieldType::Formula => Box::new(FormulaTypeOptionBuilder::from_protobuf_bytes(bytes)),
+    FieldType::Relation => Box::new(RelationTypeOptionBuilder::from_protobuf_bytes(bytes)),
+    FieldType::Rollup => Box::new(RollupTypeOptionBuilder::from_protobuf_bytes(bytes)),
   }
 }
@@ -1,8 +1,14 @@
 use crate::entities::{DateFilterConditionPB, DateFilterPB};
-use chrono::NaiveDateTime;
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime};
 
 impl DateFilterPB {
   pub fn is_visible<T: Into<Option<i64>>>(&self, cell_timestamp: T) -> bool {
+    self.is_visible_at(cell_timestamp, Local::now().date_naive())
+  }
+
+  /// Same as [DateFilterPB::is_visible], the relative conditions are evaluated against `today`
+  /// instead of the local date.
+  pub fn is_visible_at<T: Into<Option<i64>>>(&self, cell_timestamp: T, today: NaiveDate) -> bool {
     match cell_timestamp.into() {
       None => DateFilterConditionPB::DateIsEmpty == self.condition,
       Some(timestamp) => {
@@ -18,6 +24,15 @@ impl DateFilterPB {
 
         let cell_time = NaiveDateTime::from_timestamp_opt(timestamp, 0);
         let cell_date = cell_time.map(|time| time.date());
+        if self.condition.is_relative() {
+          return match (cell_date, self.relative_date_range(today)) {
+            (Some(cell_date), Some((start_date, end_date))) => {
+              cell_date >= start_date && cell_date <= end_date
+            },
+            _ => true,
+          };
+        }
+
         match self.timestamp {
           None => {
             if self.start.is_none() {
@@ -54,16 +69,72 @@ impl DateFilterPB {
       },
     }
   }
+
+  /// Returns the first and the last date of the relative condition, the range is inclusive.
+  fn relative_date_range(&self, today: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+    let day_range = |date: NaiveDate| Some((date, date));
+    let week_range = |weeks: i64| {
+      let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+      let start = monday + Duration::weeks(weeks);
+      Some((start, start + Duration::days(6)))
+    };
+    let month_range = |months: i32| {
+      let month_index = today.year() * 12 + today.month0() as i32 + months;
+      let (year, month) = (
+        month_index.div_euclid(12),
+        month_index.rem_euclid(12) as u32 + 1,
+      );
+      let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+      let next_start = match month {
+        12 => NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+        _ => NaiveDate::from_ymd_opt(year, month + 1, 1)?,
+      };
+      Some((start, next_start - Duration::days(1)))
+    };
+    let year_range = |years: i32| {
+      let year = today.year() + years;
+      Some((
+        NaiveDate::from_ymd_opt(year, 1, 1)?,
+        NaiveDate::from_ymd_opt(year, 12, 31)?,
+      ))
+    };
+
+    match self.condition {
+      DateFilterConditionPB::DateIsToday => day_range(today),
+      DateFilterConditionPB::DateIsYesterday => day_range(today - Duration::days(1)),
+      DateFilterConditionPB::DateIsTomorrow => day_range(today + Duration::days(1)),
+      DateFilterConditionPB::DateIsThisWeek => week_range(0),
+      DateFilterConditionPB::DateIsLastWeek => week_range(-1),
+      DateFilterConditionPB::DateIsNextWeek => week_range(1),
+      DateFilterConditionPB::DateIsThisMonth => month_range(0),
+      DateFilterConditionPB::DateIsLastMonth => month_range(-1),
+      DateFilterConditionPB::DateIsNextMonth => month_range(1),
+      DateFilterConditionPB::DateIsThisYear => year_range(0),
+      DateFilterConditionPB::DateIsLastYear => year_range(-1),
+      DateFilterConditionPB::DateIsNextYear => year_range(1),
+      DateFilterConditionPB::DateWithinPastDays => {
+        let days = self.number_of_days?;
+        Some((today - Duration::days(days), today))
+      },
+      DateFilterConditionPB::DateWithinNextDays => {
+        let days = self.number_of_days?;
+        Some((today, today + Duration::days(days)))
+      },
+      _ => None,
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
   #![allow(clippy::all)]
   use crate::entities::{DateFilterConditionPB, DateFilterPB};
+  use chrono::NaiveDate;
 
   #[test]
   fn date_filter_is_test() {
     let filter = DateFilterPB {
+      number_of_days: None,
       condition: DateFilterConditionPB::DateIs,
       timestamp: Some(1668387885),
       end: None,
@@ -77,6 +148,7 @@ mod tests {
   #[test]
   fn date_filter_before_test() {
     let filter = DateFilterPB {
+      number_of_days: None,
       condition: DateFilterConditionPB::DateBefore,
       timestamp: Some(1668387885),
       start: None,
@@ -91,6 +163,7 @@ mod tests {
   #[test]
   fn date_filter_before_or_on_test() {
     let filter = DateFilterPB {
+      number_of_days: None,
       condition: DateFilterConditionPB::DateOnOrBefore,
       timestamp: Some(1668387885),
       start: None,
@@ -104,6 +177,7 @@ mod tests {
   #[test]
   fn date_filter_after_test() {
     let filter = DateFilterPB {
+      number_of_days: None,
       condition: DateFilterConditionPB::DateAfter,
       timestamp: Some(1668387885),
       start: None,
@@ -118,6 +192,7 @@ mod tests {
   #[test]
   fn date_filter_within_test() {
     let filter = DateFilterPB {
+      number_of_days: None,
       condition: DateFilterConditionPB::DateWithIn,
       start: Some(1668272685), // 11/13
       end: Some(1668618285),   // 11/17
@@ -136,6 +211,7 @@ mod tests {
   #[test]
   fn date_filter_is_empty_test() {
     let filter = DateFilterPB {
+      number_of_days: None,
       condition: DateFilterConditionPB::DateIsEmpty,
       start: None,
       end: None,
@@ -146,4 +222,95 @@ mod tests {
       assert_eq!(filter.is_visible(val), visible);
     }
   }
+
+  fn relative_filter(
+    condition: DateFilterConditionPB,
+    number_of_days: Option<i64>,
+  ) -> DateFilterPB {
+    DateFilterPB {
+      condition,
+      start: None,
+      end: None,
+      timestamp: None,
+      number_of_days,
+    }
+  }
+
+  #[test]
+  fn date_filter_relative_day_test() {
+    // 1668359085 => Nov 13,2022
+    let today = NaiveDate::from_ymd_opt(2022, 11, 14).unwrap();
+    for (condition, visible) in vec![
+      (DateFilterConditionPB::DateIsToday, false),
+      (DateFilterConditionPB::DateIsYesterday, true),
+      (DateFilterConditionPB::DateIsTomorrow, false),
+    ] {
+      let filter = relative_filter(condition, None);
+      assert_eq!(filter.is_visible_at(1668359085, today), visible);
+    }
+  }
+
+  #[test]
+  fn date_filter_relative_week_test() {
+    // Nov 14,2022 is Monday, Nov 13,2022 is Sunday.
+    let today = NaiveDate::from_ymd_opt(2022, 11, 16).unwrap();
+    for (condition, visible) in vec![
+      (DateFilterConditionPB::DateIsThisWeek, false),
+      (DateFilterConditionPB::DateIsLastWeek, true),
+      (DateFilterConditionPB::DateIsNextWeek, false),
+    ] {
+      let filter = relative_filter(condition, None);
+      assert_eq!(filter.is_visible_at(1668359085, today), visible);
+    }
+
+    // The week starts on the Monday, so the filter goes stale when the day rolls over to Monday.
+    let filter = relative_filter(DateFilterConditionPB::DateIsThisWeek, None);
+    let sunday = NaiveDate::from_ymd_opt(2022, 11, 13).unwrap();
+    let monday = NaiveDate::from_ymd_opt(2022, 11, 14).unwrap();
+    assert!(filter.is_visible_at(1668359085, sunday));
+    assert!(!filter.is_visible_at(1668359085, monday));
+  }
+
+  #[test]
+  fn date_filter_relative_month_and_year_test() {
+    let today = NaiveDate::from_ymd_opt(2023, 1, 10).unwrap();
+    for (condition, visible) in vec![
+      (DateFilterConditionPB::DateIsThisMonth, false),
+      (DateFilterConditionPB::DateIsLastMonth, false),
+      (DateFilterConditionPB::DateIsNextMonth, false),
+      (DateFilterConditionPB::DateIsThisYear, false),
+      (DateFilterConditionPB::DateIsLastYear, true),
+      (DateFilterConditionPB::DateIsNextYear, false),
+    ] {
+      let filter = relative_filter(condition.clone(), None);
+      assert_eq!(
+        filter.is_visible_at(1668359085, today),
+        visible,
+        "{:?}",
+        condition
+      );
+    }
+
+    // 1671938394 => Dec 25,2022
+    let filter = relative_filter(DateFilterConditionPB::DateIsLastMonth, None);
+    assert!(filter.is_visible_at(1671938394, today));
+  }
+
+  #[test]
+  fn date_filter_within_days_test() {
+    let today = NaiveDate::from_ymd_opt(2022, 11, 20).unwrap();
+    let filter = relative_filter(DateFilterConditionPB::DateWithinPastDays, Some(7));
+    for (val, visible, msg) in vec![
+      (1668359085, true, "11/13"),
+      (1668272685, false, "11/12"),
+      (1671938394, false, "12/25"),
+    ] {
+      assert_eq!(filter.is_visible_at(val as i64, today), visible, "{}", msg);
+    }
+
+    let filter = relative_filter(DateFilterConditionPB::DateWithinNextDays, Some(40));
+    assert!(filter.is_visible_at(1671938394, today));
+    assert!(!filter.is_visible_at(1668359085, today));
+    assert!(!filter.is_visible_at(None::<i64>, today));
+  }
 }
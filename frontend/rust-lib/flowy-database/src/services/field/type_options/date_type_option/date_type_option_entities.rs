@@ -105,7 +105,7 @@ impl ToString for DateCellData {
   }
 }
 
-#[derive(Clone, Debug, Copy, EnumIter, Serialize, Deserialize, ProtoBuf_Enum)]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize, ProtoBuf_Enum)]
 pub enum DateFormat {
   Local = 0,
   US = 1,
@@ -0,0 +1,357 @@
+use crate::entities::FieldType;
+use crate::services::cell::{stringify_cell_data, FromCellString, TypeCellData};
+use crate::services::field::{
+  evaluate_formula, parse_formula, select_type_option_from_field_rev, CheckboxCellData,
+  FormulaCellInputs, FormulaExpr, FormulaFieldValues, FormulaResultTypePB, FormulaTypeOptionPB,
+  FormulaValue, FormulaValueType, NumberTypeOptionPB, RelationCellData, RollupTypeOptionPB,
+  SelectOptionIds, TypeOptionCellData,
+};
+use database_model::{CellRevision, FieldRevision, RowRevision};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Returns the type of the value that the field provides to the formula.
+pub fn formula_value_type(field_rev: &FieldRevision) -> FormulaValueType {
+  let field_type: FieldType = field_rev.ty.into();
+  match field_type {
+    FieldType::RichText | FieldType::URL => FormulaValueType::Text,
+    FieldType::Number => FormulaValueType::Number,
+    FieldType::DateTime => FormulaValueType::Date,
+    FieldType::SingleSelect | FieldType::MultiSelect | FieldType::Checklist => {
+      FormulaValueType::List
+    },
+    FieldType::Checkbox => FormulaValueType::Bool,
+    FieldType::Formula => match FormulaTypeOptionPB::from(field_rev).result_type {
+      FormulaResultTypePB::Text => FormulaValueType::Text,
+      FormulaResultTypePB::Number => FormulaValueType::Number,
+      FormulaResultTypePB::Checkbox => FormulaValueType::Bool,
+    },
+    FieldType::Relation => FormulaValueType::List,
+    FieldType::Rollup => match RollupTypeOptionPB::from(field_rev)
+      .calculation
+      .result_type()
+    {
+      FormulaResultTypePB::Text => FormulaValueType::Text,
+      FormulaResultTypePB::Number => FormulaValueType::Number,
+      FormulaResultTypePB::Checkbox => FormulaValueType::Bool,
+    },
+  }
+}
+
+/// Returns the value of the cell that is used to evaluate the formula.
+pub fn formula_value_from_cell(
+  field_rev: &FieldRevision,
+  cell_rev: Option<&CellRevision>,
+) -> FormulaValue {
+  let field_type: FieldType = field_rev.ty.into();
+  let type_cell_data = match cell_rev.map(TypeCellData::try_from) {
+    Some(Ok(type_cell_data)) => type_cell_data,
+    _ => return FormulaValue::Empty,
+  };
+  let cell_str = type_cell_data.cell_str;
+  if cell_str.is_empty() {
+    return FormulaValue::Empty;
+  }
+
+  // The cell was not transformed after switching the field type.
+  if type_cell_data.field_type != field_type {
+    let s = stringify_cell_data(cell_str, &type_cell_data.field_type, &field_type, field_rev);
+    return FormulaValue::Text(s);
+  }
+
+  match field_type {
+    FieldType::RichText => FormulaValue::Text(cell_str),
+    FieldType::URL => FormulaValue::Text(stringify_cell_data(
+      cell_str,
+      &field_type,
+      &field_type,
+      field_rev,
+    )),
+    FieldType::Number => match NumberTypeOptionPB::from(field_rev).format_cell_data(&cell_str) {
+      Ok(num_cell_data) => match num_cell_data.decimal() {
+        Some(decimal) => FormulaValue::Number(*decimal),
+        None => FormulaValue::Empty,
+      },
+      Err(_) => FormulaValue::Empty,
+    },
+    FieldType::DateTime => match cell_str.parse::<i64>() {
+      Ok(timestamp) => FormulaValue::Date(timestamp),
+      Err(_) => FormulaValue::Empty,
+    },
+    FieldType::SingleSelect | FieldType::MultiSelect | FieldType::Checklist => {
+      match (
+        select_type_option_from_field_rev(field_rev),
+        SelectOptionIds::from_cell_str(&cell_str),
+      ) {
+        (Ok(type_option), Ok(ids)) => FormulaValue::List(
+          type_option
+            .get_selected_options(ids)
+            .select_options
+            .into_iter()
+            .map(|option| option.name)
+            .collect(),
+        ),
+        _ => FormulaValue::Empty,
+      }
+    },
+    FieldType::Checkbox => {
+      let checkbox_cell_data = CheckboxCellData::from_str(&cell_str).unwrap_or_default();
+      FormulaValue::Bool(checkbox_cell_data.is_check())
+    },
+    FieldType::Formula => {
+      let type_option = FormulaTypeOptionPB::from(field_rev);
+      match type_option.decode_type_option_cell_str(cell_str) {
+        Ok(cell_data) => FormulaValue::from_cell_str(&cell_data, &type_option.result_type),
+        Err(_) => FormulaValue::Empty,
+      }
+    },
+    FieldType::Relation => FormulaValue::List(RelationCellData::from(cell_str).into_inner()),
+    FieldType::Rollup => {
+      let result_type = RollupTypeOptionPB::from(field_rev)
+        .calculation
+        .result_type();
+      FormulaValue::from_cell_str(&cell_str, &result_type)
+    },
+  }
+}
+
+struct FormulaField {
+  field_rev: Arc<FieldRevision>,
+  type_option: FormulaTypeOptionPB,
+  /// None if the expression is invalid or it references itself.
+  expr: Option<FormulaExpr>,
+}
+
+/// Fills the formula cells of the rows. The formula cells are not saved in the rows, they are
+/// filled with the [FormulaCellInputs] of the row when the rows are read.
+///
+/// The formula fields are sorted by their references, a formula is filled after the formulas it
+/// references, so it reads their calculated values. The formulas that reference each other are
+/// treated as invalid, their cells are empty.
+pub struct FormulaCalculator {
+  field_revs: Vec<Arc<FieldRevision>>,
+  formula_fields: Vec<FormulaField>,
+}
+
+impl FormulaCalculator {
+  pub fn new(field_revs: Vec<Arc<FieldRevision>>) -> Self {
+    let mut formula_fields = field_revs
+      .iter()
+      .filter(|field_rev| FieldType::from(field_rev.ty).is_formula())
+      .map(|field_rev| {
+        let type_option = FormulaTypeOptionPB::from(field_rev);
+        let expr = match parse_formula(&type_option.expression) {
+          Ok(expr) => Some(expr),
+          Err(err) => {
+            tracing::trace!("Parse the formula of {} failed: {:?}", field_rev.name, err);
+            None
+          },
+        };
+        FormulaField {
+          field_rev: field_rev.clone(),
+          type_option,
+          expr,
+        }
+      })
+      .collect::<Vec<FormulaField>>();
+    sort_formula_fields(&mut formula_fields);
+    Self {
+      field_revs,
+      formula_fields,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.formula_fields.is_empty()
+  }
+
+  /// Returns the ids of the formula fields that reference the fields with the names, directly or
+  /// through other formulas. The fields might not exist, for example, they were deleted or
+  /// renamed.
+  pub fn referencing_field_ids(&self, field_names: &[&str]) -> Vec<String> {
+    let mut names = field_names
+      .iter()
+      .map(|name| name.to_string())
+      .collect::<Vec<String>>();
+    let mut field_ids = vec![];
+    for formula_field in self.formula_fields.iter() {
+      let is_referenced = formula_field
+        .expr
+        .as_ref()
+        .map(|expr| expr.field_names().iter().any(|name| names.contains(name)))
+        .unwrap_or(false);
+      if is_referenced {
+        names.push(formula_field.field_rev.name.clone());
+        field_ids.push(formula_field.field_rev.id.clone());
+      }
+    }
+    field_ids
+  }
+
+  /// Returns the rows with their formula cells.
+  pub fn fill_rows(&self, row_revs: Vec<Arc<RowRevision>>) -> Vec<Arc<RowRevision>> {
+    if self.is_empty() {
+      return row_revs;
+    }
+    row_revs
+      .into_iter()
+      .map(|row_rev| self.fill_row(row_rev))
+      .collect()
+  }
+
+  /// Returns the row with its formula cells. The cell of a formula contains the values of the
+  /// cells it references, the formulas that reference it read its calculated value.
+  pub fn fill_row(&self, row_rev: Arc<RowRevision>) -> Arc<RowRevision> {
+    if self.is_empty() {
+      return row_rev;
+    }
+
+    let mut values = RowFormulaValues {
+      field_revs: &self.field_revs,
+      row_rev: &row_rev,
+      calculated: HashMap::new(),
+    };
+    let mut formula_cells = vec![];
+    for formula_field in self.formula_fields.iter() {
+      let field_rev = &formula_field.field_rev;
+      let (cell_str, value) = match &formula_field.expr {
+        None => ("".to_owned(), FormulaValue::Empty),
+        Some(expr) => {
+          let mut inputs = FormulaCellInputs::default();
+          for field_name in expr.field_names() {
+            if let Some(value) = values.get_value(&field_name) {
+              inputs.insert(&field_name, value);
+            }
+          }
+          let value = evaluate_formula(expr, &inputs).unwrap_or_else(|err| {
+            tracing::trace!(
+              "Evaluate the formula of {} failed: {:?}",
+              field_rev.name,
+              err
+            );
+            FormulaValue::Empty
+          });
+          (inputs.to_string(), value)
+        },
+      };
+      let type_cell_data = TypeCellData::new(cell_str, FieldType::Formula).to_json();
+      formula_cells.push((field_rev.id.clone(), CellRevision::new(type_cell_data)));
+      values.calculated.insert(
+        field_rev.id.clone(),
+        value.to_cell_str(&formula_field.type_option.result_type),
+      );
+    }
+
+    let mut row_rev = (*row_rev).clone();
+    row_rev.cells.extend(formula_cells);
+    Arc::new(row_rev)
+  }
+}
+
+struct RowFormulaValues<'a> {
+  field_revs: &'a [Arc<FieldRevision>],
+  row_rev: &'a RowRevision,
+  /// The formula cells that were calculated before.
+  calculated: HashMap<String, String>,
+}
+
+impl<'a> FormulaFieldValues for RowFormulaValues<'a> {
+  fn get_value(&self, field_name: &str) -> Option<FormulaValue> {
+    let field_rev = self
+      .field_revs
+      .iter()
+      .find(|field_rev| field_rev.name == field_name)?;
+    if let Some(cell_str) = self.calculated.get(&field_rev.id) {
+      let result_type = FormulaTypeOptionPB::from(field_rev).result_type;
+      return Some(FormulaValue::from_cell_str(cell_str, &result_type));
+    }
+    Some(formula_value_from_cell(
+      field_rev,
+      self.row_rev.cells.get(&field_rev.id),
+    ))
+  }
+}
+
+/// Sorts the formula fields in dependency order. The expressions of the formulas that are part
+/// of a reference cycle are removed.
+fn sort_formula_fields(formula_fields: &mut Vec<FormulaField>) {
+  let index_by_name = formula_fields
+    .iter()
+    .enumerate()
+    .map(|(index, formula_field)| (formula_field.field_rev.name.clone(), index))
+    .collect::<HashMap<String, usize>>();
+  let references = formula_fields
+    .iter()
+    .map(|formula_field| match &formula_field.expr {
+      None => vec![],
+      Some(expr) => expr
+        .field_names()
+        .iter()
+        .flat_map(|name| index_by_name.get(name).cloned())
+        .collect::<Vec<usize>>(),
+    })
+    .collect::<Vec<Vec<usize>>>();
+
+  #[derive(Clone, PartialEq)]
+  enum VisitState {
+    NotVisited,
+    Visiting,
+    Visited,
+  }
+
+  fn visit(
+    index: usize,
+    references: &[Vec<usize>],
+    states: &mut Vec<VisitState>,
+    stack: &mut Vec<usize>,
+    order: &mut Vec<usize>,
+    cycles: &mut Vec<usize>,
+  ) {
+    match states[index] {
+      VisitState::Visited => return,
+      VisitState::Visiting => {
+        // All the formulas from the referenced one to the current one are part of the cycle.
+        if let Some(pos) = stack.iter().position(|i| *i == index) {
+          cycles.extend_from_slice(&stack[pos..]);
+        }
+        return;
+      },
+      VisitState::NotVisited => {},
+    }
+    states[index] = VisitState::Visiting;
+    stack.push(index);
+    for reference in references[index].iter() {
+      visit(*reference, references, states, stack, order, cycles);
+    }
+    stack.pop();
+    states[index] = VisitState::Visited;
+    order.push(index);
+  }
+
+  let mut states = vec![VisitState::NotVisited; formula_fields.len()];
+  let mut stack = vec![];
+  let mut order = vec![];
+  let mut cycles = vec![];
+  for index in 0..formula_fields.len() {
+    visit(
+      index,
+      &references,
+      &mut states,
+      &mut stack,
+      &mut order,
+      &mut cycles,
+    );
+  }
+
+  for index in cycles {
+    formula_fields[index].expr = None;
+  }
+
+  let mut fields = formula_fields.drain(..).map(Some).collect::<Vec<_>>();
+  for index in order {
+    if let Some(formula_field) = fields[index].take() {
+      formula_fields.push(formula_field);
+    }
+  }
+}
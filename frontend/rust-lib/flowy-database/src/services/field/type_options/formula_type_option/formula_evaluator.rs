@@ -0,0 +1,454 @@
+use crate::services::field::{BinaryOp, FormulaExpr, FormulaResultTypePB, FormulaValue, UnaryOp};
+use chrono::{Datelike, NaiveDateTime};
+use flowy_error::{FlowyError, FlowyResult};
+use rust_decimal::prelude::{ToPrimitive, Zero};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+/// Provides the values of the fields that are referenced by the formula.
+pub trait FormulaFieldValues {
+  /// Returns the value of the field with the given name. Returns None if the field doesn't exist.
+  fn get_value(&self, field_name: &str) -> Option<FormulaValue>;
+}
+
+/// The type of the [FormulaValue]. It's used to infer the result type of the formula before
+/// evaluating it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormulaValueType {
+  Number,
+  Text,
+  Bool,
+  Date,
+  List,
+}
+
+impl std::convert::From<FormulaValueType> for FormulaResultTypePB {
+  fn from(value_type: FormulaValueType) -> Self {
+    match value_type {
+      FormulaValueType::Number => FormulaResultTypePB::Number,
+      FormulaValueType::Bool => FormulaResultTypePB::Checkbox,
+      FormulaValueType::Text | FormulaValueType::Date | FormulaValueType::List => {
+        FormulaResultTypePB::Text
+      },
+    }
+  }
+}
+
+/// Infers the type of the value that will be produced by the expression.
+///
+/// # Arguments
+///
+/// * `expr`: the parsed formula
+/// * `field_value_type`: returns the value type of the field with the given name. Returns None if
+/// the field doesn't exist.
+///
+pub fn infer_formula_value_type<F>(
+  expr: &FormulaExpr,
+  field_value_type: &F,
+) -> FlowyResult<FormulaValueType>
+where
+  F: Fn(&str) -> Option<FormulaValueType>,
+{
+  let value_type = match expr {
+    FormulaExpr::Number(_) => FormulaValueType::Number,
+    FormulaExpr::Text(_) => FormulaValueType::Text,
+    FormulaExpr::Bool(_) => FormulaValueType::Bool,
+    FormulaExpr::Field(name) => field_value_type(name).ok_or_else(|| field_not_found(name))?,
+    FormulaExpr::Unary { op, expr } => {
+      infer_formula_value_type(expr, field_value_type)?;
+      match op {
+        UnaryOp::Neg => FormulaValueType::Number,
+        UnaryOp::Not => FormulaValueType::Bool,
+      }
+    },
+    FormulaExpr::Binary { op, left, right } => {
+      let left = infer_formula_value_type(left, field_value_type)?;
+      let right = infer_formula_value_type(right, field_value_type)?;
+      match op {
+        BinaryOp::Add => match (left, right) {
+          (FormulaValueType::Text | FormulaValueType::List, _)
+          | (_, FormulaValueType::Text | FormulaValueType::List) => FormulaValueType::Text,
+          (FormulaValueType::Date, _) | (_, FormulaValueType::Date) => FormulaValueType::Date,
+          _ => FormulaValueType::Number,
+        },
+        BinaryOp::Sub => match (left, right) {
+          (FormulaValueType::Date, FormulaValueType::Date) => FormulaValueType::Number,
+          (FormulaValueType::Date, _) => FormulaValueType::Date,
+          _ => FormulaValueType::Number,
+        },
+        BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => FormulaValueType::Number,
+        _ => FormulaValueType::Bool,
+      }
+    },
+    FormulaExpr::Function { name, args } => {
+      let arg_types = args
+        .iter()
+        .map(|arg| infer_formula_value_type(arg, field_value_type))
+        .collect::<FlowyResult<Vec<FormulaValueType>>>()?;
+      match name.as_str() {
+        "if" => {
+          check_args_len(name, args, 2, 3)?;
+          match arg_types.get(2) {
+            Some(else_type) if else_type != &arg_types[1] => FormulaValueType::Text,
+            _ => arg_types[1],
+          }
+        },
+        "concat" | "join" | "lower" | "upper" | "trim" => FormulaValueType::Text,
+        "datediff" | "count" | "sum" | "average" | "min" | "max" | "len" | "abs" | "round"
+        | "floor" | "ceil" => FormulaValueType::Number,
+        "contains" | "empty" => FormulaValueType::Bool,
+        _ => return Err(unknown_function(name)),
+      }
+    },
+  };
+  Ok(value_type)
+}
+
+/// Evaluates the expression with the values of the referenced fields.
+pub fn evaluate_formula(
+  expr: &FormulaExpr,
+  values: &dyn FormulaFieldValues,
+) -> FlowyResult<FormulaValue> {
+  match expr {
+    FormulaExpr::Number(num) => Ok(FormulaValue::Number(*num)),
+    FormulaExpr::Text(s) => Ok(FormulaValue::Text(s.clone())),
+    FormulaExpr::Bool(value) => Ok(FormulaValue::Bool(*value)),
+    FormulaExpr::Field(name) => values.get_value(name).ok_or_else(|| field_not_found(name)),
+    FormulaExpr::Unary { op, expr } => {
+      let value = evaluate_formula(expr, values)?;
+      match op {
+        UnaryOp::Neg => match value {
+          FormulaValue::Empty => Ok(FormulaValue::Empty),
+          value => Ok(FormulaValue::Number(-expect_number(&value)?)),
+        },
+        UnaryOp::Not => Ok(FormulaValue::Bool(!value.as_bool())),
+      }
+    },
+    FormulaExpr::Binary { op, left, right } => {
+      let left = evaluate_formula(left, values)?;
+      match op {
+        // Short-circuit the logical operators.
+        BinaryOp::And if !left.as_bool() => Ok(FormulaValue::Bool(false)),
+        BinaryOp::Or if left.as_bool() => Ok(FormulaValue::Bool(true)),
+        _ => {
+          let right = evaluate_formula(right, values)?;
+          evaluate_binary(*op, left, right)
+        },
+      }
+    },
+    FormulaExpr::Function { name, args } => evaluate_function(name, args, values),
+  }
+}
+
+fn evaluate_binary(
+  op: BinaryOp,
+  left: FormulaValue,
+  right: FormulaValue,
+) -> FlowyResult<FormulaValue> {
+  let is_arithmetic = matches!(
+    op,
+    BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem
+  );
+  let is_concat = op == BinaryOp::Add
+    && (matches!(left, FormulaValue::Text(_) | FormulaValue::List(_))
+      || matches!(right, FormulaValue::Text(_) | FormulaValue::List(_)));
+  // The arithmetic on the empty cells produces the empty value instead of treating them as zero.
+  if is_arithmetic && !is_concat && (left == FormulaValue::Empty || right == FormulaValue::Empty) {
+    return Ok(FormulaValue::Empty);
+  }
+
+  let value = match op {
+    BinaryOp::Add => match (&left, &right) {
+      _ if is_concat => FormulaValue::Text(format!("{}{}", left.as_text(), right.as_text())),
+      (FormulaValue::Date(timestamp), other) | (other, FormulaValue::Date(timestamp)) => {
+        FormulaValue::Date(add_days(*timestamp, &expect_number(other)?)?)
+      },
+      _ => {
+        let num = expect_number(&left)?.checked_add(expect_number(&right)?);
+        FormulaValue::Number(num.ok_or_else(overflow)?)
+      },
+    },
+    BinaryOp::Sub => match (&left, &right) {
+      (FormulaValue::Date(left), FormulaValue::Date(right)) => {
+        FormulaValue::Number(Decimal::from((left - right) / SECONDS_PER_DAY))
+      },
+      (FormulaValue::Date(timestamp), other) => {
+        FormulaValue::Date(add_days(*timestamp, &-expect_number(other)?)?)
+      },
+      _ => {
+        let num = expect_number(&left)?.checked_sub(expect_number(&right)?);
+        FormulaValue::Number(num.ok_or_else(overflow)?)
+      },
+    },
+    BinaryOp::Mul => {
+      let num = expect_number(&left)?.checked_mul(expect_number(&right)?);
+      FormulaValue::Number(num.ok_or_else(overflow)?)
+    },
+    BinaryOp::Div | BinaryOp::Rem => {
+      let divisor = expect_number(&right)?;
+      if divisor.is_zero() {
+        return Err(FlowyError::invalid_formula().context("Division by zero"));
+      }
+      let dividend = expect_number(&left)?;
+      let num = if op == BinaryOp::Div {
+        dividend.checked_div(divisor)
+      } else {
+        dividend.checked_rem(divisor)
+      };
+      FormulaValue::Number(num.ok_or_else(overflow)?)
+    },
+    BinaryOp::Eq => FormulaValue::Bool(compare_values(&left, &right) == Ordering::Equal),
+    BinaryOp::NotEq => FormulaValue::Bool(compare_values(&left, &right) != Ordering::Equal),
+    BinaryOp::Lt => FormulaValue::Bool(compare_values(&left, &right) == Ordering::Less),
+    BinaryOp::LtEq => FormulaValue::Bool(compare_values(&left, &right) != Ordering::Greater),
+    BinaryOp::Gt => FormulaValue::Bool(compare_values(&left, &right) == Ordering::Greater),
+    BinaryOp::GtEq => FormulaValue::Bool(compare_values(&left, &right) != Ordering::Less),
+    BinaryOp::And | BinaryOp::Or => FormulaValue::Bool(right.as_bool()),
+  };
+  Ok(value)
+}
+
+fn evaluate_function(
+  name: &str,
+  args: &[FormulaExpr],
+  values: &dyn FormulaFieldValues,
+) -> FlowyResult<FormulaValue> {
+  // The branches of the `if` are evaluated lazily.
+  if name == "if" {
+    check_args_len(name, args, 2, 3)?;
+    let condition = evaluate_formula(&args[0], values)?;
+    return if condition.as_bool() {
+      evaluate_formula(&args[1], values)
+    } else {
+      match args.get(2) {
+        None => Ok(FormulaValue::Empty),
+        Some(arg) => evaluate_formula(arg, values),
+      }
+    };
+  }
+
+  let args = args
+    .iter()
+    .map(|arg| evaluate_formula(arg, values))
+    .collect::<FlowyResult<Vec<FormulaValue>>>()?;
+  let value = match name {
+    "concat" => FormulaValue::Text(args.iter().map(|arg| arg.as_text()).collect()),
+    "join" => {
+      check_args_len(name, &args, 1, 2)?;
+      let separator = args
+        .get(1)
+        .map(|arg| arg.as_text())
+        .unwrap_or_else(|| ", ".to_owned());
+      let items = match &args[0] {
+        FormulaValue::List(items) => items.clone(),
+        value => vec![value.as_text()],
+      };
+      FormulaValue::Text(items.join(&separator))
+    },
+    "lower" => {
+      check_args_len(name, &args, 1, 1)?;
+      FormulaValue::Text(args[0].as_text().to_lowercase())
+    },
+    "upper" => {
+      check_args_len(name, &args, 1, 1)?;
+      FormulaValue::Text(args[0].as_text().to_uppercase())
+    },
+    "trim" => {
+      check_args_len(name, &args, 1, 1)?;
+      FormulaValue::Text(args[0].as_text().trim().to_owned())
+    },
+    "len" => {
+      check_args_len(name, &args, 1, 1)?;
+      FormulaValue::Number(Decimal::from(args[0].as_text().chars().count()))
+    },
+    "contains" => {
+      check_args_len(name, &args, 2, 2)?;
+      let needle = args[1].as_text();
+      let is_contained = match &args[0] {
+        FormulaValue::List(items) => items.iter().any(|item| item == &needle),
+        value => value.as_text().contains(&needle),
+      };
+      FormulaValue::Bool(is_contained)
+    },
+    "empty" => {
+      check_args_len(name, &args, 1, 1)?;
+      FormulaValue::Bool(args[0].is_empty())
+    },
+    "datediff" => {
+      check_args_len(name, &args, 2, 3)?;
+      let unit = args
+        .get(2)
+        .map(|arg| arg.as_text().to_lowercase())
+        .unwrap_or_else(|| "days".to_owned());
+      match (args[0].as_timestamp(), args[1].as_timestamp()) {
+        (Some(start), Some(end)) => FormulaValue::Number(date_diff(start, end, &unit)?),
+        _ => FormulaValue::Empty,
+      }
+    },
+    "count" => {
+      let count: usize = args
+        .iter()
+        .map(|arg| match arg {
+          FormulaValue::Empty => 0,
+          FormulaValue::List(items) => items.len(),
+          _ => 1,
+        })
+        .sum();
+      FormulaValue::Number(Decimal::from(count))
+    },
+    "sum" | "average" | "min" | "max" => {
+      let numbers = flatten_numbers(&args);
+      if numbers.is_empty() {
+        return Ok(FormulaValue::Empty);
+      }
+      let num = match name {
+        "min" => numbers.iter().min().cloned(),
+        "max" => numbers.iter().max().cloned(),
+        _ => {
+          let sum = numbers
+            .iter()
+            .try_fold(Decimal::zero(), |acc, num| acc.checked_add(*num))
+            .ok_or_else(overflow)?;
+          if name == "sum" {
+            Some(sum)
+          } else {
+            sum.checked_div(Decimal::from(numbers.len()))
+          }
+        },
+      };
+      FormulaValue::Number(num.ok_or_else(overflow)?)
+    },
+    "abs" | "floor" | "ceil" => {
+      check_args_len(name, &args, 1, 1)?;
+      if args[0].is_empty() {
+        return Ok(FormulaValue::Empty);
+      }
+      let num = expect_number(&args[0])?;
+      FormulaValue::Number(match name {
+        "abs" => num.abs(),
+        "floor" => num.floor(),
+        _ => num.ceil(),
+      })
+    },
+    "round" => {
+      check_args_len(name, &args, 1, 2)?;
+      if args[0].is_empty() {
+        return Ok(FormulaValue::Empty);
+      }
+      let num = expect_number(&args[0])?;
+      let digits = match args.get(1) {
+        None => 0,
+        Some(arg) => expect_number(arg)?.to_u32().unwrap_or(0).min(28),
+      };
+      FormulaValue::Number(
+        num.round_dp_with_strategy(digits, RoundingStrategy::MidpointAwayFromZero),
+      )
+    },
+    _ => return Err(unknown_function(name)),
+  };
+  Ok(value)
+}
+
+/// Compares two values. The values are compared as numbers unless one of them is a text.
+pub fn compare_values(left: &FormulaValue, right: &FormulaValue) -> Ordering {
+  match (left, right) {
+    (FormulaValue::Text(_) | FormulaValue::List(_), _)
+    | (_, FormulaValue::Text(_) | FormulaValue::List(_)) => left.as_text().cmp(&right.as_text()),
+    _ => match (left.as_number(), right.as_number()) {
+      (Some(left), Some(right)) => left.cmp(&right),
+      _ => left.as_text().cmp(&right.as_text()),
+    },
+  }
+}
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+fn add_days(timestamp: i64, days: &Decimal) -> FlowyResult<i64> {
+  let seconds = days
+    .checked_mul(Decimal::from(SECONDS_PER_DAY))
+    .and_then(|seconds| seconds.trunc().to_i64())
+    .ok_or_else(overflow)?;
+  timestamp.checked_add(seconds).ok_or_else(overflow)
+}
+
+/// Returns the difference between the two dates in the given unit. The result is negative if
+/// the `end` is before the `start`.
+fn date_diff(start: i64, end: i64, unit: &str) -> FlowyResult<Decimal> {
+  let seconds = end - start;
+  let diff = match unit {
+    "minutes" => seconds / 60,
+    "hours" => seconds / 3600,
+    "days" => seconds / SECONDS_PER_DAY,
+    "weeks" => seconds / (SECONDS_PER_DAY * 7),
+    "months" | "years" => {
+      let (from, to, sign) = if start <= end {
+        (start, end, 1)
+      } else {
+        (end, start, -1)
+      };
+      let from = NaiveDateTime::from_timestamp_opt(from, 0).ok_or_else(overflow)?;
+      let to = NaiveDateTime::from_timestamp_opt(to, 0).ok_or_else(overflow)?;
+      let mut months =
+        (to.year() - from.year()) as i64 * 12 + to.month() as i64 - from.month() as i64;
+      if (to.day(), to.time()) < (from.day(), from.time()) {
+        months -= 1;
+      }
+      if unit == "years" {
+        sign * (months / 12)
+      } else {
+        sign * months
+      }
+    },
+    _ => return Err(FlowyError::invalid_formula().context(format!("Unknown date unit: {}", unit))),
+  };
+  Ok(Decimal::from(diff))
+}
+
+/// Returns the numbers of the values. The names of the selected options are parsed as numbers
+/// and the ones that are not numbers are ignored.
+fn flatten_numbers(values: &[FormulaValue]) -> Vec<Decimal> {
+  let mut numbers = vec![];
+  for value in values {
+    match value {
+      FormulaValue::Empty => {},
+      FormulaValue::List(items) => {
+        numbers.extend(
+          items
+            .iter()
+            .flat_map(|item| Decimal::from_str(item.trim()).ok()),
+        );
+      },
+      value => numbers.extend(value.as_number()),
+    }
+  }
+  numbers
+}
+
+fn expect_number(value: &FormulaValue) -> FlowyResult<Decimal> {
+  value.as_number().ok_or_else(|| {
+    FlowyError::invalid_formula().context(format!("{} is not a number", value.as_text()))
+  })
+}
+
+fn check_args_len<T>(name: &str, args: &[T], min: usize, max: usize) -> FlowyResult<()> {
+  if args.len() < min || args.len() > max {
+    return Err(
+      FlowyError::invalid_formula()
+        .context(format!("Wrong number of arguments for function: {}", name)),
+    );
+  }
+  Ok(())
+}
+
+fn field_not_found(name: &str) -> FlowyError {
+  FlowyError::invalid_formula().context(format!("Field not found: {}", name))
+}
+
+fn unknown_function(name: &str) -> FlowyError {
+  FlowyError::invalid_formula().context(format!("Unknown function: {}", name))
+}
+
+fn overflow() -> FlowyError {
+  FlowyError::invalid_formula().context("Number overflow")
+}
@@ -0,0 +1,75 @@
+use crate::entities::FormulaFilterPB;
+use crate::services::field::{CheckboxCellData, FormulaResultTypePB, NumberCellData};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+impl FormulaFilterPB {
+  /// Applies the filter according to the result type of the formula. The cell is filtered as
+  /// text, number or checkbox.
+  pub fn is_visible(&self, result_type: &FormulaResultTypePB, cell_str: &str) -> bool {
+    match result_type {
+      FormulaResultTypePB::Text => self.text_filter().is_visible(cell_str),
+      FormulaResultTypePB::Number => {
+        let num_cell_data = match Decimal::from_str(cell_str) {
+          Ok(decimal) => NumberCellData::from_decimal(decimal),
+          Err(_) => NumberCellData::new(),
+        };
+        self.number_filter().is_visible(&num_cell_data)
+      },
+      FormulaResultTypePB::Checkbox => {
+        let checkbox_cell_data = CheckboxCellData::from_str(cell_str).unwrap_or_default();
+        self.checkbox_filter().is_visible(&checkbox_cell_data)
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::{
+    CheckboxFilterConditionPB, FormulaFilterPB, NumberFilterConditionPB, TextFilterConditionPB,
+  };
+  use crate::services::field::FormulaResultTypePB;
+
+  #[test]
+  fn formula_filter_number_result_test() {
+    let filter = FormulaFilterPB {
+      condition: NumberFilterConditionPB::GreaterThan as u32,
+      content: "10".to_owned(),
+    };
+    for (cell_str, visible) in [("11", true), ("9.5", false), ("", false)] {
+      assert_eq!(
+        filter.is_visible(&FormulaResultTypePB::Number, cell_str),
+        visible
+      );
+    }
+  }
+
+  #[test]
+  fn formula_filter_text_result_test() {
+    let filter = FormulaFilterPB {
+      condition: TextFilterConditionPB::Contains as u32,
+      content: "flowy".to_owned(),
+    };
+    for (cell_str, visible) in [("AppFlowy", true), ("Notion", false), ("", false)] {
+      assert_eq!(
+        filter.is_visible(&FormulaResultTypePB::Text, cell_str),
+        visible
+      );
+    }
+  }
+
+  #[test]
+  fn formula_filter_checkbox_result_test() {
+    let filter = FormulaFilterPB {
+      condition: CheckboxFilterConditionPB::IsChecked as u32,
+      content: "".to_owned(),
+    };
+    for (cell_str, visible) in [("Yes", true), ("No", false), ("", false)] {
+      assert_eq!(
+        filter.is_visible(&FormulaResultTypePB::Checkbox, cell_str),
+        visible
+      );
+    }
+  }
+}
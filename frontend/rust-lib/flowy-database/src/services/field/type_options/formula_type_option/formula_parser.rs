@@ -0,0 +1,461 @@
+use flowy_error::{FlowyError, FlowyResult};
+use rust_decimal::Decimal;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+/// The syntax tree of the formula expression.
+///
+/// The expression supports:
+/// * number, string and boolean literals: `1.5`, `"hello"`, `true`
+/// * field references, the name of the field is wrapped in the braces: `{Price}`
+/// * arithmetic operators: `+`, `-`, `*`, `/`, `%`. The `+` concatenates the operands if one of
+/// them is a text.
+/// * comparison operators: `==`, `!=`, `<`, `<=`, `>`, `>=`
+/// * logical operators: `&&`, `||`, `!`
+/// * function calls: `if({Done}, "Yes", "No")`, `datediff({Start}, {End}, "days")`
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaExpr {
+  Number(Decimal),
+  Text(String),
+  Bool(bool),
+  Field(String),
+  Unary {
+    op: UnaryOp,
+    expr: Box<FormulaExpr>,
+  },
+  Binary {
+    op: BinaryOp,
+    left: Box<FormulaExpr>,
+    right: Box<FormulaExpr>,
+  },
+  Function {
+    name: String,
+    args: Vec<FormulaExpr>,
+  },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+  Neg,
+  Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+  Add,
+  Sub,
+  Mul,
+  Div,
+  Rem,
+  Eq,
+  NotEq,
+  Lt,
+  LtEq,
+  Gt,
+  GtEq,
+  And,
+  Or,
+}
+
+impl FormulaExpr {
+  /// Returns the names of the fields that are referenced by the expression.
+  pub fn field_names(&self) -> Vec<String> {
+    let mut names = vec![];
+    self.collect_field_names(&mut names);
+    names
+  }
+
+  fn collect_field_names(&self, names: &mut Vec<String>) {
+    match self {
+      FormulaExpr::Field(name) => {
+        if !names.contains(name) {
+          names.push(name.clone());
+        }
+      },
+      FormulaExpr::Unary { expr, .. } => expr.collect_field_names(names),
+      FormulaExpr::Binary { left, right, .. } => {
+        left.collect_field_names(names);
+        right.collect_field_names(names);
+      },
+      FormulaExpr::Function { args, .. } => {
+        for arg in args {
+          arg.collect_field_names(names);
+        }
+      },
+      FormulaExpr::Number(_) | FormulaExpr::Text(_) | FormulaExpr::Bool(_) => {},
+    }
+  }
+}
+
+/// Parses the formula expression into [FormulaExpr].
+pub fn parse_formula(s: &str) -> FlowyResult<FormulaExpr> {
+  let tokens = tokenize(s)?;
+  let mut parser = FormulaParser { tokens, pos: 0 };
+  let expr = parser.parse_or()?;
+  match parser.peek() {
+    None => Ok(expr),
+    Some(token) => Err(invalid_formula(format!("Unexpected token: {:?}", token))),
+  }
+}
+
+/// Replaces the references to the field named `old_name` with `new_name`. The rest of the
+/// expression, including the string literals, is kept as it is. Returns None if the expression
+/// doesn't reference the field.
+pub fn rename_formula_field(expression: &str, old_name: &str, new_name: &str) -> Option<String> {
+  let mut renamed = String::with_capacity(expression.len());
+  let mut is_renamed = false;
+  let mut chars = expression.chars();
+  while let Some(c) = chars.next() {
+    match c {
+      '"' | '\'' => {
+        let quote = c;
+        renamed.push(quote);
+        while let Some(c) = chars.next() {
+          renamed.push(c);
+          if c == '\\' {
+            if let Some(escaped) = chars.next() {
+              renamed.push(escaped);
+            }
+          } else if c == quote {
+            break;
+          }
+        }
+      },
+      '{' => {
+        let mut name = String::new();
+        let mut is_closed = false;
+        for c in chars.by_ref() {
+          if c == '}' {
+            is_closed = true;
+            break;
+          }
+          name.push(c);
+        }
+        if is_closed && name.trim() == old_name {
+          renamed.push_str(&format!("{{{}}}", new_name));
+          is_renamed = true;
+        } else {
+          renamed.push('{');
+          renamed.push_str(&name);
+          if is_closed {
+            renamed.push('}');
+          }
+        }
+      },
+      c => renamed.push(c),
+    }
+  }
+
+  if is_renamed {
+    Some(renamed)
+  } else {
+    None
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  Number(Decimal),
+  Text(String),
+  Ident(String),
+  Field(String),
+  LParen,
+  RParen,
+  Comma,
+  Plus,
+  Minus,
+  Star,
+  Slash,
+  Percent,
+  Eq,
+  NotEq,
+  Lt,
+  LtEq,
+  Gt,
+  GtEq,
+  And,
+  Or,
+  Not,
+}
+
+fn tokenize(s: &str) -> FlowyResult<Vec<Token>> {
+  let mut tokens = vec![];
+  let mut chars = s.chars().peekable();
+  while let Some(c) = chars.next() {
+    let token = match c {
+      c if c.is_whitespace() => continue,
+      '(' => Token::LParen,
+      ')' => Token::RParen,
+      ',' => Token::Comma,
+      '+' => Token::Plus,
+      '-' => Token::Minus,
+      '*' => Token::Star,
+      '/' => Token::Slash,
+      '%' => Token::Percent,
+      '=' => {
+        // Both `=` and `==` are treated as the equal operator.
+        next_if_eq(&mut chars, '=');
+        Token::Eq
+      },
+      '!' => {
+        if next_if_eq(&mut chars, '=') {
+          Token::NotEq
+        } else {
+          Token::Not
+        }
+      },
+      '<' => {
+        if next_if_eq(&mut chars, '=') {
+          Token::LtEq
+        } else if next_if_eq(&mut chars, '>') {
+          Token::NotEq
+        } else {
+          Token::Lt
+        }
+      },
+      '>' => {
+        if next_if_eq(&mut chars, '=') {
+          Token::GtEq
+        } else {
+          Token::Gt
+        }
+      },
+      '&' => {
+        if next_if_eq(&mut chars, '&') {
+          Token::And
+        } else {
+          return Err(invalid_formula("Expected '&&'"));
+        }
+      },
+      '|' => {
+        if next_if_eq(&mut chars, '|') {
+          Token::Or
+        } else {
+          return Err(invalid_formula("Expected '||'"));
+        }
+      },
+      '"' | '\'' => Token::Text(read_text(&mut chars, c)?),
+      '{' => {
+        let mut name = String::new();
+        loop {
+          match chars.next() {
+            None => return Err(invalid_formula("Unclosed field reference")),
+            Some('}') => break,
+            Some(c) => name.push(c),
+          }
+        }
+        let name = name.trim();
+        if name.is_empty() {
+          return Err(invalid_formula("The field name should not be empty"));
+        }
+        Token::Field(name.to_owned())
+      },
+      c if c.is_ascii_digit() || c == '.' => {
+        let mut num = c.to_string();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+          num.push(c);
+        }
+        let decimal = Decimal::from_str(&num)
+          .map_err(|_| invalid_formula(format!("Invalid number: {}", num)))?;
+        Token::Number(decimal)
+      },
+      c if c.is_alphabetic() || c == '_' => {
+        let mut ident = c.to_string();
+        while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+          ident.push(c);
+        }
+        Token::Ident(ident)
+      },
+      c => return Err(invalid_formula(format!("Unexpected character: {}", c))),
+    };
+    tokens.push(token);
+  }
+  Ok(tokens)
+}
+
+fn next_if_eq(chars: &mut Peekable<Chars>, expected: char) -> bool {
+  chars.next_if_eq(&expected).is_some()
+}
+
+fn read_text(chars: &mut Peekable<Chars>, quote: char) -> FlowyResult<String> {
+  let mut text = String::new();
+  loop {
+    match chars.next() {
+      None => return Err(invalid_formula("Unclosed string literal")),
+      Some('\\') => match chars.next() {
+        Some('n') => text.push('\n'),
+        Some('t') => text.push('\t'),
+        Some(c) => text.push(c),
+        None => return Err(invalid_formula("Unclosed string literal")),
+      },
+      Some(c) if c == quote => return Ok(text),
+      Some(c) => text.push(c),
+    }
+  }
+}
+
+struct FormulaParser {
+  tokens: Vec<Token>,
+  pos: usize,
+}
+
+impl FormulaParser {
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.pos)
+  }
+
+  fn next(&mut self) -> Option<Token> {
+    let token = self.tokens.get(self.pos).cloned();
+    if token.is_some() {
+      self.pos += 1;
+    }
+    token
+  }
+
+  fn next_if(&mut self, token: &Token) -> bool {
+    if self.peek() == Some(token) {
+      self.pos += 1;
+      true
+    } else {
+      false
+    }
+  }
+
+  fn expect(&mut self, token: Token) -> FlowyResult<()> {
+    if self.next_if(&token) {
+      Ok(())
+    } else {
+      Err(invalid_formula(format!("Expected {:?}", token)))
+    }
+  }
+
+  fn parse_or(&mut self) -> FlowyResult<FormulaExpr> {
+    let mut left = self.parse_and()?;
+    while self.next_if(&Token::Or) {
+      let right = self.parse_and()?;
+      left = binary(BinaryOp::Or, left, right);
+    }
+    Ok(left)
+  }
+
+  fn parse_and(&mut self) -> FlowyResult<FormulaExpr> {
+    let mut left = self.parse_comparison()?;
+    while self.next_if(&Token::And) {
+      let right = self.parse_comparison()?;
+      left = binary(BinaryOp::And, left, right);
+    }
+    Ok(left)
+  }
+
+  fn parse_comparison(&mut self) -> FlowyResult<FormulaExpr> {
+    let left = self.parse_additive()?;
+    let op = match self.peek() {
+      Some(Token::Eq) => BinaryOp::Eq,
+      Some(Token::NotEq) => BinaryOp::NotEq,
+      Some(Token::Lt) => BinaryOp::Lt,
+      Some(Token::LtEq) => BinaryOp::LtEq,
+      Some(Token::Gt) => BinaryOp::Gt,
+      Some(Token::GtEq) => BinaryOp::GtEq,
+      _ => return Ok(left),
+    };
+    self.pos += 1;
+    let right = self.parse_additive()?;
+    Ok(binary(op, left, right))
+  }
+
+  fn parse_additive(&mut self) -> FlowyResult<FormulaExpr> {
+    let mut left = self.parse_multiplicative()?;
+    loop {
+      let op = match self.peek() {
+        Some(Token::Plus) => BinaryOp::Add,
+        Some(Token::Minus) => BinaryOp::Sub,
+        _ => return Ok(left),
+      };
+      self.pos += 1;
+      let right = self.parse_multiplicative()?;
+      left = binary(op, left, right);
+    }
+  }
+
+  fn parse_multiplicative(&mut self) -> FlowyResult<FormulaExpr> {
+    let mut left = self.parse_unary()?;
+    loop {
+      let op = match self.peek() {
+        Some(Token::Star) => BinaryOp::Mul,
+        Some(Token::Slash) => BinaryOp::Div,
+        Some(Token::Percent) => BinaryOp::Rem,
+        _ => return Ok(left),
+      };
+      self.pos += 1;
+      let right = self.parse_unary()?;
+      left = binary(op, left, right);
+    }
+  }
+
+  fn parse_unary(&mut self) -> FlowyResult<FormulaExpr> {
+    let op = match self.peek() {
+      Some(Token::Minus) => UnaryOp::Neg,
+      Some(Token::Not) => UnaryOp::Not,
+      _ => return self.parse_primary(),
+    };
+    self.pos += 1;
+    let expr = self.parse_unary()?;
+    Ok(FormulaExpr::Unary {
+      op,
+      expr: Box::new(expr),
+    })
+  }
+
+  fn parse_primary(&mut self) -> FlowyResult<FormulaExpr> {
+    match self.next() {
+      Some(Token::Number(num)) => Ok(FormulaExpr::Number(num)),
+      Some(Token::Text(text)) => Ok(FormulaExpr::Text(text)),
+      Some(Token::Field(name)) => Ok(FormulaExpr::Field(name)),
+      Some(Token::LParen) => {
+        let expr = self.parse_or()?;
+        self.expect(Token::RParen)?;
+        Ok(expr)
+      },
+      Some(Token::Ident(ident)) => {
+        let name = ident.to_lowercase();
+        if self.next_if(&Token::LParen) {
+          let args = self.parse_args()?;
+          return Ok(FormulaExpr::Function { name, args });
+        }
+        match name.as_str() {
+          "true" => Ok(FormulaExpr::Bool(true)),
+          "false" => Ok(FormulaExpr::Bool(false)),
+          _ => Err(invalid_formula(format!("Unknown identifier: {}", ident))),
+        }
+      },
+      Some(token) => Err(invalid_formula(format!("Unexpected token: {:?}", token))),
+      None => Err(invalid_formula("Unexpected end of the formula")),
+    }
+  }
+
+  fn parse_args(&mut self) -> FlowyResult<Vec<FormulaExpr>> {
+    let mut args = vec![];
+    if self.next_if(&Token::RParen) {
+      return Ok(args);
+    }
+    loop {
+      args.push(self.parse_or()?);
+      if self.next_if(&Token::RParen) {
+        return Ok(args);
+      }
+      self.expect(Token::Comma)?;
+    }
+  }
+}
+
+fn binary(op: BinaryOp, left: FormulaExpr, right: FormulaExpr) -> FormulaExpr {
+  FormulaExpr::Binary {
+    op,
+    left: Box::new(left),
+    right: Box::new(right),
+  }
+}
+
+fn invalid_formula<T: ToString>(msg: T) -> FlowyError {
+  FlowyError::invalid_formula().context(msg.to_string())
+}
@@ -0,0 +1,321 @@
+#[cfg(test)]
+mod tests {
+  use crate::entities::FieldType;
+  use crate::services::cell::{insert_number_cell, insert_text_cell, stringify_cell_data};
+  use crate::services::cell::{AnyTypeCache, TypeCellData};
+  use crate::services::field::{
+    evaluate_formula, infer_formula_value_type, parse_formula, rename_formula_field, FieldBuilder,
+    FormulaCalculator, FormulaFieldValues, FormulaResultTypePB, FormulaTypeOptionBuilder,
+    FormulaValue, FormulaValueType, StrCellData, TypeOptionCellExt,
+  };
+  use database_model::{FieldRevision, RowRevision};
+  use rust_decimal::Decimal;
+  use std::collections::HashMap;
+  use std::str::FromStr;
+  use std::sync::Arc;
+
+  struct MockFieldValues(HashMap<String, FormulaValue>);
+
+  impl FormulaFieldValues for MockFieldValues {
+    fn get_value(&self, field_name: &str) -> Option<FormulaValue> {
+      self.0.get(field_name).cloned()
+    }
+  }
+
+  fn mock_values() -> MockFieldValues {
+    let mut values = HashMap::new();
+    values.insert("Price".to_owned(), number("12.5"));
+    values.insert("Amount".to_owned(), number("4"));
+    values.insert("Name".to_owned(), FormulaValue::Text("AppFlowy".to_owned()));
+    values.insert("Done".to_owned(), FormulaValue::Bool(true));
+    values.insert("Start".to_owned(), FormulaValue::Date(1672531200));
+    values.insert("End".to_owned(), FormulaValue::Date(1673136000));
+    values.insert(
+      "Tags".to_owned(),
+      FormulaValue::List(vec!["1".to_owned(), "2".to_owned(), "3".to_owned()]),
+    );
+    values.insert("Empty".to_owned(), FormulaValue::Empty);
+    MockFieldValues(values)
+  }
+
+  fn number(s: &str) -> FormulaValue {
+    FormulaValue::Number(Decimal::from_str(s).unwrap())
+  }
+
+  fn assert_evaluate(expression: &str, expected: FormulaValue) {
+    let expr = parse_formula(expression).unwrap();
+    assert_eq!(
+      evaluate_formula(&expr, &mock_values()).unwrap(),
+      expected,
+      "{}",
+      expression
+    );
+  }
+
+  #[test]
+  fn formula_parse_error_test() {
+    assert!(parse_formula("").is_err());
+    assert!(parse_formula("1 +").is_err());
+    assert!(parse_formula("(1 + 2").is_err());
+    assert!(parse_formula("{Price").is_err());
+    assert!(parse_formula("{}").is_err());
+    assert!(parse_formula("\"abc").is_err());
+    assert!(parse_formula("1 2").is_err());
+    assert!(parse_formula("abc").is_err());
+  }
+
+  #[test]
+  fn formula_field_names_test() {
+    let expr = parse_formula("if({Done}, {Price} * {Amount}, {Price})").unwrap();
+    assert_eq!(
+      expr.field_names(),
+      vec!["Done".to_owned(), "Price".to_owned(), "Amount".to_owned()]
+    );
+  }
+
+  #[test]
+  fn formula_rename_field_test() {
+    assert_eq!(
+      rename_formula_field("{Price} * { Price } + {Amount}", "Price", "Cost").unwrap(),
+      "{Cost} * {Cost} + {Amount}"
+    );
+    // The string literals are not renamed.
+    assert_eq!(
+      rename_formula_field(
+        r#"concat("{Price}", {Price}, '\'{Price}')"#,
+        "Price",
+        "Cost"
+      )
+      .unwrap(),
+      r#"concat("{Price}", {Cost}, '\'{Price}')"#
+    );
+    assert!(rename_formula_field("{Amount} * 2", "Price", "Cost").is_none());
+  }
+
+  #[test]
+  fn formula_evaluate_arithmetic_test() {
+    assert_evaluate("1 + 2 * 3", number("7"));
+    assert_evaluate("(1 + 2) * 3", number("9"));
+    assert_evaluate("{Price} * {Amount}", number("50"));
+    assert_evaluate("-{Amount} % 3", number("-1"));
+    assert_evaluate("{Empty} * 2", FormulaValue::Empty);
+    assert!(evaluate_formula(&parse_formula("1 / 0").unwrap(), &mock_values()).is_err());
+    assert!(evaluate_formula(&parse_formula("{Unknown}").unwrap(), &mock_values()).is_err());
+  }
+
+  #[test]
+  fn formula_evaluate_text_test() {
+    assert_evaluate(
+      "{Name} + \" \" + {Amount}",
+      FormulaValue::Text("AppFlowy 4".to_owned()),
+    );
+    assert_evaluate(
+      "concat(upper({Name}), \"!\")",
+      FormulaValue::Text("APPFLOWY!".to_owned()),
+    );
+    assert_evaluate(
+      "join({Tags}, \"-\")",
+      FormulaValue::Text("1-2-3".to_owned()),
+    );
+    assert_evaluate("len({Name})", number("8"));
+  }
+
+  #[test]
+  fn formula_evaluate_logic_test() {
+    assert_evaluate(
+      "if({Done}, \"Yes\", \"No\")",
+      FormulaValue::Text("Yes".to_owned()),
+    );
+    assert_evaluate("if(!{Done}, 1)", FormulaValue::Empty);
+    assert_evaluate("{Price} > 10 && {Amount} <= 4", FormulaValue::Bool(true));
+    assert_evaluate("{Name} == \"AppFlowy\" || 1 / 0", FormulaValue::Bool(true));
+    assert_evaluate("contains({Tags}, \"2\")", FormulaValue::Bool(true));
+    assert_evaluate("empty({Empty})", FormulaValue::Bool(true));
+  }
+
+  #[test]
+  fn formula_evaluate_function_test() {
+    assert_evaluate("count({Tags})", number("3"));
+    assert_evaluate("sum(1, 2, {Amount})", number("7"));
+    assert_evaluate("average({Price}, {Amount})", number("8.25"));
+    assert_evaluate("max({Price}, {Amount})", number("12.5"));
+    assert_evaluate("round({Price} / 3, 2)", number("4.17"));
+    assert_evaluate("datediff({Start}, {End}, \"days\")", number("7"));
+    assert_evaluate("datediff({Start}, {End}, \"weeks\")", number("1"));
+    assert_evaluate("{End} - {Start}", number("7"));
+    assert_evaluate("{Start} + 7", FormulaValue::Date(1673136000));
+  }
+
+  #[test]
+  fn formula_infer_value_type_test() {
+    let field_value_type = |name: &str| match name {
+      "Price" => Some(FormulaValueType::Number),
+      "Name" => Some(FormulaValueType::Text),
+      "Done" => Some(FormulaValueType::Bool),
+      "Start" => Some(FormulaValueType::Date),
+      _ => None,
+    };
+    let infer = |expression: &str| {
+      infer_formula_value_type(&parse_formula(expression).unwrap(), &field_value_type)
+    };
+
+    assert_eq!(infer("{Price} * 2").unwrap(), FormulaValueType::Number);
+    assert_eq!(infer("{Name} + {Price}").unwrap(), FormulaValueType::Text);
+    assert_eq!(infer("{Price} > 1").unwrap(), FormulaValueType::Bool);
+    assert_eq!(infer("{Start} + 1").unwrap(), FormulaValueType::Date);
+    assert_eq!(
+      infer("{Start} - {Start}").unwrap(),
+      FormulaValueType::Number
+    );
+    assert_eq!(infer("if({Done}, 1, 2)").unwrap(), FormulaValueType::Number);
+    assert_eq!(
+      infer("if({Done}, 1, \"a\")").unwrap(),
+      FormulaValueType::Text
+    );
+    assert!(infer("{Unknown} + 1").is_err());
+    assert!(infer("unknown(1)").is_err());
+  }
+
+  #[test]
+  fn formula_value_cell_str_test() {
+    let value = number("1.50");
+    assert_eq!(value.to_cell_str(&FormulaResultTypePB::Number), "1.5");
+    assert_eq!(
+      FormulaValue::from_cell_str("1.5", &FormulaResultTypePB::Number),
+      number("1.5")
+    );
+    let cell_str = FormulaValue::Bool(true).to_cell_str(&FormulaResultTypePB::Checkbox);
+    assert_eq!(
+      FormulaValue::from_cell_str(&cell_str, &FormulaResultTypePB::Checkbox),
+      FormulaValue::Bool(true)
+    );
+    assert_eq!(
+      FormulaValue::Empty.to_cell_str(&FormulaResultTypePB::Checkbox),
+      ""
+    );
+  }
+
+  #[test]
+  fn formula_calculator_test() {
+    let price_field_rev = FieldBuilder::from_field_type(&FieldType::Number)
+      .name("Price")
+      .build();
+    let name_field_rev = FieldBuilder::from_field_type(&FieldType::RichText)
+      .name("Name")
+      .build();
+    // The `Total` formula references the `Double` formula, so `Double` is calculated first.
+    let total_field_rev = formula_field_rev("Total", "{Double} + 1");
+    let double_field_rev = formula_field_rev("Double", "{Price} * 2");
+    let field_revs = vec![
+      Arc::new(price_field_rev.clone()),
+      Arc::new(name_field_rev.clone()),
+      Arc::new(total_field_rev.clone()),
+      Arc::new(double_field_rev.clone()),
+    ];
+
+    let calculator = FormulaCalculator::new(field_revs);
+    assert_eq!(
+      calculator.referencing_field_ids(&["Price"]),
+      vec![double_field_rev.id.clone(), total_field_rev.id.clone()]
+    );
+    assert_eq!(
+      calculator.referencing_field_ids(&["Double"]),
+      vec![total_field_rev.id.clone()]
+    );
+    assert!(calculator.referencing_field_ids(&["Name"]).is_empty());
+
+    let mut row_rev = RowRevision::new("");
+    row_rev.cells.insert(
+      price_field_rev.id.clone(),
+      insert_number_cell(10, &price_field_rev),
+    );
+    row_rev.cells.insert(
+      name_field_rev.id.clone(),
+      insert_text_cell("AppFlowy".to_owned(), &name_field_rev),
+    );
+    let row_rev = calculator.fill_row(Arc::new(row_rev));
+    assert_eq!(formula_cell_content(&row_rev, &double_field_rev), "20");
+    assert_eq!(formula_cell_content(&row_rev, &total_field_rev), "21");
+  }
+
+  #[test]
+  fn formula_calculator_cycle_test() {
+    let a_field_rev = formula_field_rev("A", "{B} + 1");
+    let b_field_rev = formula_field_rev("B", "{A} + 1");
+    let c_field_rev = formula_field_rev("C", "1 + 1");
+    let field_revs = vec![
+      Arc::new(a_field_rev.clone()),
+      Arc::new(b_field_rev.clone()),
+      Arc::new(c_field_rev.clone()),
+    ];
+
+    // The formulas that reference each other are not calculated.
+    let calculator = FormulaCalculator::new(field_revs);
+    let row_rev = calculator.fill_row(Arc::new(RowRevision::new("")));
+    assert_eq!(formula_cell_content(&row_rev, &a_field_rev), "");
+    assert_eq!(formula_cell_content(&row_rev, &b_field_rev), "");
+    assert_eq!(formula_cell_content(&row_rev, &c_field_rev), "2");
+  }
+
+  #[test]
+  fn formula_cell_data_cache_test() {
+    let price_field_rev = FieldBuilder::from_field_type(&FieldType::Number)
+      .name("Price")
+      .build();
+    let total_field_rev = formula_field_rev("Total", "{Price} + 1");
+    let calculator = FormulaCalculator::new(vec![
+      Arc::new(price_field_rev.clone()),
+      Arc::new(total_field_rev.clone()),
+    ]);
+    let cell_data_cache = AnyTypeCache::<u64>::new();
+    let handler =
+      TypeOptionCellExt::new_with_cell_data_cache(&total_field_rev, Some(cell_data_cache.clone()))
+        .get_type_option_cell_data_handler(&FieldType::Formula)
+        .unwrap();
+    let decode = |price: i64| {
+      let mut row_rev = RowRevision::new("");
+      row_rev.cells.insert(
+        price_field_rev.id.clone(),
+        insert_number_cell(price, &price_field_rev),
+      );
+      let row_rev = calculator.fill_row(Arc::new(row_rev));
+      let type_cell_data =
+        TypeCellData::try_from(row_rev.cells.get(&total_field_rev.id).unwrap()).unwrap();
+      handler
+        .get_cell_data(
+          type_cell_data.cell_str,
+          &FieldType::Formula,
+          &total_field_rev,
+        )
+        .unwrap()
+        .unbox_or_none::<StrCellData>()
+        .unwrap()
+        .to_string()
+    };
+
+    assert!(cell_data_cache.read().is_empty());
+    assert_eq!(decode(1), "2");
+    assert!(!cell_data_cache.read().is_empty());
+    // The cell string is changed with the referenced cell, so the formula is evaluated again.
+    assert_eq!(decode(2), "3");
+    assert_eq!(decode(1), "2");
+  }
+
+  /// Returns the display string of the formula cell in the row.
+  fn formula_cell_content(row_rev: &RowRevision, field_rev: &FieldRevision) -> String {
+    let type_cell_data = TypeCellData::try_from(row_rev.cells.get(&field_rev.id).unwrap()).unwrap();
+    stringify_cell_data(
+      type_cell_data.cell_str,
+      &FieldType::Formula,
+      &FieldType::Formula,
+      field_rev,
+    )
+  }
+
+  fn formula_field_rev(name: &str, expression: &str) -> FieldRevision {
+    let type_option = FormulaTypeOptionBuilder::default()
+      .expression(expression)
+      .result_type(FormulaResultTypePB::Number);
+    FieldBuilder::new(type_option).name(name).build()
+  }
+}
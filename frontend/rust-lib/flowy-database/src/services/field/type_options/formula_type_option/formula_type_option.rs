@@ -0,0 +1,190 @@
+use crate::entities::{FieldType, FormulaFilterPB};
+use crate::impl_type_option;
+use crate::services::cell::{CellDataChangeset, CellDataDecoder, FromCellString, TypeCellData};
+use crate::services::field::{
+  compare_values, evaluate_formula, formula_value_type, infer_formula_value_type, parse_formula,
+  BoxTypeOptionBuilder, FormulaCellInputs, FormulaResultTypePB, FormulaValue, StrCellData,
+  TypeOption, TypeOptionBuilder, TypeOptionCellData, TypeOptionCellDataCompare,
+  TypeOptionCellDataFilter, TypeOptionTransform,
+};
+use bytes::Bytes;
+use database_model::{FieldRevision, TypeOptionDataDeserializer, TypeOptionDataSerializer};
+use flowy_derive::ProtoBuf;
+use flowy_error::FlowyResult;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct FormulaTypeOptionBuilder(FormulaTypeOptionPB);
+impl_into_box_type_option_builder!(FormulaTypeOptionBuilder);
+impl_builder_from_json_str_and_from_bytes!(FormulaTypeOptionBuilder, FormulaTypeOptionPB);
+
+impl FormulaTypeOptionBuilder {
+  pub fn expression(mut self, expression: &str) -> Self {
+    self.0.expression = expression.to_owned();
+    self
+  }
+
+  pub fn result_type(mut self, result_type: FormulaResultTypePB) -> Self {
+    self.0.result_type = result_type;
+    self
+  }
+}
+
+impl TypeOptionBuilder for FormulaTypeOptionBuilder {
+  fn field_type(&self) -> FieldType {
+    FieldType::Formula
+  }
+
+  fn serializer(&self) -> &dyn TypeOptionDataSerializer {
+    &self.0
+  }
+}
+
+/// The formula type option calculates the cell from the other cells of the same row. The cells
+/// are not saved, the cell string is the [FormulaCellInputs] of the row, and the formula is
+/// evaluated when the cell is decoded. The decoded cells are cached in the cell data cache like
+/// the other cells, so the formula is only evaluated again when one of the referenced cells is
+/// changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ProtoBuf)]
+pub struct FormulaTypeOptionPB {
+  /// The expression of the formula, for example: `{Price} * {Amount}`. The referenced fields
+  /// are identified by their names, the references are renamed when the field is renamed.
+  #[pb(index = 1)]
+  pub expression: String,
+
+  /// The result type is inferred from the expression when the type option is updated.
+  #[pb(index = 2)]
+  #[serde(default)]
+  pub result_type: FormulaResultTypePB,
+}
+impl_type_option!(FormulaTypeOptionPB, FieldType::Formula);
+
+impl FormulaTypeOptionPB {
+  /// Infers the result type of the formula from the expression and the types of the referenced
+  /// fields. The result type falls back to text if the expression is invalid.
+  pub fn update_result_type(&mut self, field_revs: &[Arc<FieldRevision>]) {
+    let field_value_type = |name: &str| {
+      field_revs
+        .iter()
+        .find(|field_rev| field_rev.name == name)
+        .map(|field_rev| formula_value_type(field_rev))
+    };
+    self.result_type = match parse_formula(&self.expression)
+      .and_then(|expr| infer_formula_value_type(&expr, &field_value_type))
+    {
+      Ok(value_type) => value_type.into(),
+      Err(err) => {
+        tracing::warn!("Infer the result type of the formula failed: {:?}", err);
+        FormulaResultTypePB::Text
+      },
+    };
+  }
+}
+
+impl TypeOption for FormulaTypeOptionPB {
+  type CellData = StrCellData;
+  type CellChangeset = FormulaCellChangeset;
+  type CellProtobufType = StrCellData;
+  type CellFilter = FormulaFilterPB;
+}
+
+impl TypeOptionTransform for FormulaTypeOptionPB {}
+
+impl TypeOptionCellData for FormulaTypeOptionPB {
+  fn convert_to_protobuf(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    cell_data
+  }
+
+  /// Evaluates the formula with the referenced values in the cell string. The cell string is
+  /// empty if the formula can't be calculated, for example, it references itself.
+  fn decode_type_option_cell_str(
+    &self,
+    cell_str: String,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    if cell_str.is_empty() {
+      return Ok(Default::default());
+    }
+
+    let inputs = FormulaCellInputs::from_cell_str(&cell_str)?;
+    let value =
+      match parse_formula(&self.expression).and_then(|expr| evaluate_formula(&expr, &inputs)) {
+        Ok(value) => value,
+        Err(err) => {
+          tracing::trace!("Evaluate the formula failed: {:?}", err);
+          FormulaValue::Empty
+        },
+      };
+    Ok(value.to_cell_str(&self.result_type).into())
+  }
+}
+
+impl CellDataDecoder for FormulaTypeOptionPB {
+  fn decode_cell_str(
+    &self,
+    cell_str: String,
+    decoded_field_type: &FieldType,
+    _field_rev: &FieldRevision,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    // The cells of the other field types are decoded as empty. The rows are filled with the
+    // formula cells when they are read, so the cells are calculated after switching the field to
+    // the formula field.
+    if !decoded_field_type.is_formula() {
+      return Ok(Default::default());
+    }
+
+    self.decode_type_option_cell_str(cell_str)
+  }
+
+  fn decode_cell_data_to_str(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.to_string()
+  }
+}
+
+pub type FormulaCellChangeset = String;
+
+impl CellDataChangeset for FormulaTypeOptionPB {
+  /// The formula cell is read-only and it's not saved, the changeset is ignored.
+  fn apply_changeset(
+    &self,
+    _changeset: <Self as TypeOption>::CellChangeset,
+    _type_cell_data: Option<TypeCellData>,
+  ) -> FlowyResult<(String, <Self as TypeOption>::CellData)> {
+    Ok(("".to_owned(), Default::default()))
+  }
+}
+
+impl TypeOptionCellDataFilter for FormulaTypeOptionPB {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    field_type: &FieldType,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    if !field_type.is_formula() {
+      return true;
+    }
+    filter.is_visible(&self.result_type, cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for FormulaTypeOptionPB {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+  ) -> Ordering {
+    let value = FormulaValue::from_cell_str(cell_data, &self.result_type);
+    let other_value = FormulaValue::from_cell_str(other_cell_data, &self.result_type);
+    match (value.is_empty(), other_value.is_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Less,
+      (false, true) => Ordering::Greater,
+      (false, false) => compare_values(&value, &other_value),
+    }
+  }
+}
@@ -0,0 +1,166 @@
+use crate::services::cell::FromCellString;
+use crate::services::field::{FormulaFieldValues, CHECK, UNCHECK};
+use chrono::NaiveDateTime;
+use flowy_derive::ProtoBuf_Enum;
+use flowy_error::{internal_error, FlowyResult};
+use rust_decimal::prelude::{ToPrimitive, Zero};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// The type of the value that is calculated by the formula. It decides how the formula cell
+/// is displayed, filtered and sorted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ProtoBuf_Enum)]
+pub enum FormulaResultTypePB {
+  Text = 0,
+  Number = 1,
+  Checkbox = 2,
+}
+
+impl std::default::Default for FormulaResultTypePB {
+  fn default() -> Self {
+    FormulaResultTypePB::Text
+  }
+}
+
+/// The value that is produced when evaluating the formula.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FormulaValue {
+  Empty,
+  Number(Decimal),
+  Text(String),
+  Bool(bool),
+  /// The timestamp of the date, in seconds.
+  Date(i64),
+  /// The names of the selected options.
+  List(Vec<String>),
+}
+
+impl FormulaValue {
+  pub fn is_empty(&self) -> bool {
+    match self {
+      FormulaValue::Empty => true,
+      FormulaValue::Text(s) => s.is_empty(),
+      FormulaValue::List(items) => items.is_empty(),
+      _ => false,
+    }
+  }
+
+  /// Returns the number of the value. The empty value is treated as zero.
+  pub fn as_number(&self) -> Option<Decimal> {
+    match self {
+      FormulaValue::Empty => Some(Decimal::zero()),
+      FormulaValue::Number(num) => Some(*num),
+      FormulaValue::Text(s) => Decimal::from_str(s.trim()).ok(),
+      FormulaValue::Bool(value) => Some(if *value { Decimal::ONE } else { Decimal::ZERO }),
+      FormulaValue::Date(timestamp) => Some(Decimal::from(*timestamp)),
+      FormulaValue::List(_) => None,
+    }
+  }
+
+  pub fn as_bool(&self) -> bool {
+    match self {
+      FormulaValue::Empty => false,
+      FormulaValue::Number(num) => !num.is_zero(),
+      FormulaValue::Text(s) => {
+        let s = s.to_lowercase();
+        !s.is_empty() && s != "false" && s != "no" && s != "0"
+      },
+      FormulaValue::Bool(value) => *value,
+      FormulaValue::Date(_) => true,
+      FormulaValue::List(items) => !items.is_empty(),
+    }
+  }
+
+  pub fn as_text(&self) -> String {
+    match self {
+      FormulaValue::Empty => "".to_owned(),
+      FormulaValue::Number(num) => num.normalize().to_string(),
+      FormulaValue::Text(s) => s.clone(),
+      FormulaValue::Bool(value) => value.to_string(),
+      FormulaValue::Date(timestamp) => NaiveDateTime::from_timestamp_opt(*timestamp, 0)
+        .map(|date_time| date_time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default(),
+      FormulaValue::List(items) => items.join(", "),
+    }
+  }
+
+  /// Returns the string that is saved in the cell according to the result type of the formula.
+  pub fn to_cell_str(&self, result_type: &FormulaResultTypePB) -> String {
+    if matches!(self, FormulaValue::Empty) {
+      return "".to_owned();
+    }
+
+    match result_type {
+      FormulaResultTypePB::Text => self.as_text(),
+      FormulaResultTypePB::Number => match self.as_number() {
+        None => "".to_owned(),
+        Some(num) => num.normalize().to_string(),
+      },
+      FormulaResultTypePB::Checkbox => {
+        if self.as_bool() {
+          CHECK.to_owned()
+        } else {
+          UNCHECK.to_owned()
+        }
+      },
+    }
+  }
+
+  /// Parses the cell string that was saved by [FormulaValue::to_cell_str].
+  pub fn from_cell_str(s: &str, result_type: &FormulaResultTypePB) -> Self {
+    if s.is_empty() {
+      return FormulaValue::Empty;
+    }
+
+    match result_type {
+      FormulaResultTypePB::Text => FormulaValue::Text(s.to_owned()),
+      FormulaResultTypePB::Number => match Decimal::from_str(s) {
+        Ok(num) => FormulaValue::Number(num),
+        Err(_) => FormulaValue::Empty,
+      },
+      FormulaResultTypePB::Checkbox => FormulaValue::Bool(s == CHECK),
+    }
+  }
+
+  pub(crate) fn as_timestamp(&self) -> Option<i64> {
+    match self {
+      FormulaValue::Date(timestamp) => Some(*timestamp),
+      FormulaValue::Number(num) => num.to_i64(),
+      FormulaValue::Text(s) => s.trim().parse::<i64>().ok(),
+      _ => None,
+    }
+  }
+}
+
+/// The values of the cells that are referenced by the formula, keyed by the names of their
+/// fields. It's the cell string of the formula cell. The formula cells are not saved in the rows,
+/// they are filled with the referenced values when the rows are read, and the formula is
+/// evaluated when the cell is decoded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FormulaCellInputs(BTreeMap<String, FormulaValue>);
+
+impl FormulaCellInputs {
+  pub fn insert(&mut self, field_name: &str, value: FormulaValue) {
+    self.0.insert(field_name.to_owned(), value);
+  }
+}
+
+impl FormulaFieldValues for FormulaCellInputs {
+  fn get_value(&self, field_name: &str) -> Option<FormulaValue> {
+    self.0.get(field_name).cloned()
+  }
+}
+
+impl FromCellString for FormulaCellInputs {
+  fn from_cell_str(s: &str) -> FlowyResult<Self> {
+    serde_json::from_str::<FormulaCellInputs>(s).map_err(internal_error)
+  }
+}
+
+impl ToString for FormulaCellInputs {
+  fn to_string(&self) -> String {
+    serde_json::to_string(self).unwrap_or_default()
+  }
+}
@@ -0,0 +1,14 @@
+#![allow(clippy::module_inception)]
+mod formula_calculator;
+mod formula_evaluator;
+mod formula_filter;
+mod formula_parser;
+mod formula_tests;
+mod formula_type_option;
+mod formula_type_option_entities;
+
+pub use formula_calculator::*;
+pub use formula_evaluator::*;
+pub use formula_parser::*;
+pub use formula_type_option::*;
+pub use formula_type_option_entities::*;
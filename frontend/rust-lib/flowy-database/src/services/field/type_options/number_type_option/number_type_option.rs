@@ -13,6 +13,7 @@ use flowy_derive::ProtoBuf;
 use flowy_error::FlowyResult;
 use lazy_static::lazy_static;
 use rust_decimal::Decimal;
+use rusty_money::Money;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::default::Default;
@@ -137,6 +138,17 @@ impl NumberTypeOptionPB {
     self.format = format;
     self.symbol = format.symbol();
   }
+
+  /// Formats the number with the currency of the [NumberFormat]
+  pub(crate) fn format_number(&self, number: Decimal) -> String {
+    match self.format {
+      NumberFormat::Num => number.normalize().to_string(),
+      _ => {
+        let money = Money::from_decimal(number, self.format.currency());
+        NumberCellData::from_money(money).to_string()
+      },
+    }
+  }
 }
 
 pub(crate) fn strip_currency_symbol<T: ToString>(s: T) -> String {
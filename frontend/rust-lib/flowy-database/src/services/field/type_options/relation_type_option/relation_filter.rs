@@ -0,0 +1,58 @@
+use crate::entities::{RelationFilterConditionPB, RelationFilterPB};
+use crate::services::field::RelationCellData;
+
+impl RelationFilterPB {
+  pub fn is_visible(&self, cell_data: &RelationCellData) -> bool {
+    match self.condition {
+      RelationFilterConditionPB::RelationContains => {
+        self.row_ids.is_empty() || self.row_ids.iter().any(|row_id| cell_data.contains(row_id))
+      },
+      RelationFilterConditionPB::RelationDoesNotContain => {
+        !self.row_ids.iter().any(|row_id| cell_data.contains(row_id))
+      },
+      RelationFilterConditionPB::RelationIsEmpty => cell_data.is_empty(),
+      RelationFilterConditionPB::RelationIsNotEmpty => !cell_data.is_empty(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::{RelationFilterConditionPB, RelationFilterPB};
+  use crate::services::field::RelationCellData;
+
+  #[test]
+  fn relation_filter_contains_test() {
+    let filter = RelationFilterPB {
+      condition: RelationFilterConditionPB::RelationContains,
+      row_ids: vec!["a".to_owned(), "b".to_owned()],
+    };
+    assert!(filter.is_visible(&RelationCellData::from("b,c".to_owned())));
+    assert!(!filter.is_visible(&RelationCellData::from("c".to_owned())));
+    assert!(!filter.is_visible(&RelationCellData::default()));
+
+    let filter = RelationFilterPB {
+      condition: RelationFilterConditionPB::RelationDoesNotContain,
+      row_ids: vec!["a".to_owned()],
+    };
+    assert!(filter.is_visible(&RelationCellData::from("b,c".to_owned())));
+    assert!(!filter.is_visible(&RelationCellData::from("a".to_owned())));
+  }
+
+  #[test]
+  fn relation_filter_is_empty_test() {
+    let filter = RelationFilterPB {
+      condition: RelationFilterConditionPB::RelationIsEmpty,
+      row_ids: vec![],
+    };
+    assert!(filter.is_visible(&RelationCellData::default()));
+    assert!(!filter.is_visible(&RelationCellData::from("a".to_owned())));
+
+    let filter = RelationFilterPB {
+      condition: RelationFilterConditionPB::RelationIsNotEmpty,
+      row_ids: vec![],
+    };
+    assert!(!filter.is_visible(&RelationCellData::default()));
+    assert!(filter.is_visible(&RelationCellData::from("a".to_owned())));
+  }
+}
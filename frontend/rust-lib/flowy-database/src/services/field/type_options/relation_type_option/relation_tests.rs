@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+  use crate::entities::FieldType;
+  use crate::services::cell::{CellDataChangeset, CellDataDecoder, TypeCellData};
+  use crate::services::field::{
+    FieldBuilder, RelationCellChangeset, RelationTypeOptionBuilder, RelationTypeOptionPB,
+  };
+
+  #[test]
+  fn relation_type_option_insert_and_remove_row_test() {
+    let type_option = RelationTypeOptionPB {
+      database_id: "database".to_owned(),
+    };
+    let changeset =
+      RelationCellChangeset::from_insert_row_ids(vec!["a".to_owned(), "b".to_owned()]);
+    let (cell_str, _) = type_option.apply_changeset(changeset, None).unwrap();
+    assert_eq!(cell_str, "a,b");
+
+    // The row that was already related is not inserted again.
+    let changeset =
+      RelationCellChangeset::from_insert_row_ids(vec!["b".to_owned(), "c".to_owned()]);
+    let type_cell_data = TypeCellData::new(cell_str, FieldType::Relation);
+    let (cell_str, _) = type_option
+      .apply_changeset(changeset, Some(type_cell_data))
+      .unwrap();
+    assert_eq!(cell_str, "a,b,c");
+
+    let changeset = RelationCellChangeset::from_remove_row_ids(vec!["a".to_owned()]);
+    let type_cell_data = TypeCellData::new(cell_str, FieldType::Relation);
+    let (cell_str, cell_data) = type_option
+      .apply_changeset(changeset, Some(type_cell_data))
+      .unwrap();
+    assert_eq!(cell_str, "b,c");
+    assert_eq!(cell_data.len(), 2);
+  }
+
+  #[test]
+  fn relation_type_option_decode_test() {
+    let builder = RelationTypeOptionBuilder::default().database_id("database");
+    let field_rev = FieldBuilder::new(builder).build();
+    let type_option = RelationTypeOptionPB::from(&field_rev);
+    assert_eq!(type_option.database_id, "database");
+
+    let cell_data = type_option
+      .decode_cell_str("a,b".to_owned(), &FieldType::Relation, &field_rev)
+      .unwrap();
+    assert_eq!(cell_data.to_vec(), vec!["a".to_owned(), "b".to_owned()]);
+
+    // The cell of other field types can't be decoded as the relation cell.
+    let cell_data = type_option
+      .decode_cell_str("a,b".to_owned(), &FieldType::RichText, &field_rev)
+      .unwrap();
+    assert!(cell_data.is_empty());
+  }
+}
@@ -0,0 +1,141 @@
+use crate::entities::{FieldType, RelationFilterPB};
+use crate::impl_type_option;
+use crate::services::cell::{CellDataChangeset, CellDataDecoder, FromCellString, TypeCellData};
+use crate::services::field::{
+  BoxTypeOptionBuilder, RelationCellChangeset, RelationCellData, RelationCellDataPB, TypeOption,
+  TypeOptionBuilder, TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
+  TypeOptionTransform,
+};
+use bytes::Bytes;
+use database_model::{FieldRevision, TypeOptionDataDeserializer, TypeOptionDataSerializer};
+use flowy_derive::ProtoBuf;
+use flowy_error::FlowyResult;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+#[derive(Default)]
+pub struct RelationTypeOptionBuilder(RelationTypeOptionPB);
+impl_into_box_type_option_builder!(RelationTypeOptionBuilder);
+impl_builder_from_json_str_and_from_bytes!(RelationTypeOptionBuilder, RelationTypeOptionPB);
+
+impl RelationTypeOptionBuilder {
+  pub fn database_id(mut self, database_id: &str) -> Self {
+    self.0.database_id = database_id.to_owned();
+    self
+  }
+}
+
+impl TypeOptionBuilder for RelationTypeOptionBuilder {
+  fn field_type(&self) -> FieldType {
+    FieldType::Relation
+  }
+
+  fn serializer(&self) -> &dyn TypeOptionDataSerializer {
+    &self.0
+  }
+}
+
+/// The relation type option links the rows to the rows of another database. The cell saves the
+/// ids of the related rows.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ProtoBuf)]
+pub struct RelationTypeOptionPB {
+  /// The id of the database that the related rows belong to.
+  #[pb(index = 1)]
+  pub database_id: String,
+}
+impl_type_option!(RelationTypeOptionPB, FieldType::Relation);
+
+impl TypeOption for RelationTypeOptionPB {
+  type CellData = RelationCellData;
+  type CellChangeset = RelationCellChangeset;
+  type CellProtobufType = RelationCellDataPB;
+  type CellFilter = RelationFilterPB;
+}
+
+impl TypeOptionTransform for RelationTypeOptionPB {}
+
+impl TypeOptionCellData for RelationTypeOptionPB {
+  fn convert_to_protobuf(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    RelationCellDataPB {
+      database_id: self.database_id.clone(),
+      row_ids: cell_data.into_inner(),
+    }
+  }
+
+  fn decode_type_option_cell_str(
+    &self,
+    cell_str: String,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    RelationCellData::from_cell_str(&cell_str)
+  }
+}
+
+impl CellDataDecoder for RelationTypeOptionPB {
+  fn decode_cell_str(
+    &self,
+    cell_str: String,
+    decoded_field_type: &FieldType,
+    _field_rev: &FieldRevision,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    if !decoded_field_type.is_relation() {
+      return Ok(Default::default());
+    }
+
+    self.decode_type_option_cell_str(cell_str)
+  }
+
+  fn decode_cell_data_to_str(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.to_string()
+  }
+}
+
+impl CellDataChangeset for RelationTypeOptionPB {
+  fn apply_changeset(
+    &self,
+    changeset: <Self as TypeOption>::CellChangeset,
+    type_cell_data: Option<TypeCellData>,
+  ) -> FlowyResult<(String, <Self as TypeOption>::CellData)> {
+    let mut cell_data = match type_cell_data {
+      Some(type_cell_data) if type_cell_data.field_type.is_relation() => {
+        RelationCellData::from(type_cell_data.cell_str)
+      },
+      _ => RelationCellData::default(),
+    };
+
+    for row_id in changeset.inserted_row_ids {
+      if !row_id.is_empty() && !cell_data.contains(&row_id) {
+        cell_data.push(row_id);
+      }
+    }
+
+    cell_data.retain(|row_id| !changeset.removed_row_ids.contains(row_id));
+    Ok((cell_data.to_string(), cell_data))
+  }
+}
+
+impl TypeOptionCellDataFilter for RelationTypeOptionPB {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    field_type: &FieldType,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    if !field_type.is_relation() {
+      return true;
+    }
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for RelationTypeOptionPB {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+  ) -> Ordering {
+    cell_data.len().cmp(&other_cell_data.len())
+  }
+}
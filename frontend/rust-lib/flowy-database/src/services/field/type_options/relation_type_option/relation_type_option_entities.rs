@@ -0,0 +1,182 @@
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::{CellIdPB, CellIdParams};
+use crate::services::cell::{
+  CellProtobufBlobParser, DecodedCellData, FromCellChangesetString, FromCellString,
+  ToCellChangesetString,
+};
+use bytes::Bytes;
+use flowy_derive::ProtoBuf;
+use flowy_error::{internal_error, ErrorCode, FlowyResult};
+use serde::{Deserialize, Serialize};
+
+pub const RELATION_ROW_IDS_SEPARATOR: &str = ",";
+
+/// List of the related row ids. The rows belong to the database that is configured in the
+/// [RelationTypeOptionPB](crate::services::field::RelationTypeOptionPB).
+///
+/// Calls [to_string] will return a string consists list of row ids, placing a commas separator
+/// between each.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct RelationCellData(Vec<String>);
+
+impl RelationCellData {
+  pub fn into_inner(self) -> Vec<String> {
+    self.0
+  }
+}
+
+impl FromCellString for RelationCellData {
+  fn from_cell_str(s: &str) -> FlowyResult<Self>
+  where
+    Self: Sized,
+  {
+    Ok(Self::from(s.to_owned()))
+  }
+}
+
+impl std::convert::From<String> for RelationCellData {
+  fn from(s: String) -> Self {
+    if s.is_empty() {
+      return Self(vec![]);
+    }
+
+    let row_ids = s
+      .split(RELATION_ROW_IDS_SEPARATOR)
+      .map(|row_id| row_id.to_string())
+      .collect::<Vec<String>>();
+    Self(row_ids)
+  }
+}
+
+impl std::convert::From<Vec<String>> for RelationCellData {
+  fn from(row_ids: Vec<String>) -> Self {
+    let row_ids = row_ids
+      .into_iter()
+      .filter(|row_id| !row_id.is_empty())
+      .collect::<Vec<String>>();
+    Self(row_ids)
+  }
+}
+
+impl ToString for RelationCellData {
+  fn to_string(&self) -> String {
+    self.0.join(RELATION_ROW_IDS_SEPARATOR)
+  }
+}
+
+impl std::ops::Deref for RelationCellData {
+  type Target = Vec<String>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl std::ops::DerefMut for RelationCellData {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.0
+  }
+}
+
+/// [RelationCellDataPB] contains the ids of the related rows and the id of the database that
+/// the rows belong to.
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct RelationCellDataPB {
+  #[pb(index = 1)]
+  pub database_id: String,
+
+  #[pb(index = 2)]
+  pub row_ids: Vec<String>,
+}
+
+impl DecodedCellData for RelationCellDataPB {
+  type Object = RelationCellDataPB;
+
+  fn is_empty(&self) -> bool {
+    self.row_ids.is_empty()
+  }
+}
+
+pub struct RelationCellDataParser();
+impl CellProtobufBlobParser for RelationCellDataParser {
+  type Object = RelationCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    RelationCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
+
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct RelationCellChangesetPB {
+  #[pb(index = 1)]
+  pub cell_path: CellIdPB,
+
+  #[pb(index = 2)]
+  pub inserted_row_ids: Vec<String>,
+
+  #[pb(index = 3)]
+  pub removed_row_ids: Vec<String>,
+}
+
+pub struct RelationCellChangesetParams {
+  pub cell_path: CellIdParams,
+  pub inserted_row_ids: Vec<String>,
+  pub removed_row_ids: Vec<String>,
+}
+
+impl TryInto<RelationCellChangesetParams> for RelationCellChangesetPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<RelationCellChangesetParams, Self::Error> {
+    let cell_path: CellIdParams = self.cell_path.try_into()?;
+    let parse_row_ids = |row_ids: Vec<String>| {
+      row_ids
+        .into_iter()
+        .flat_map(|row_id| NotEmptyStr::parse(row_id).ok().map(|row_id| row_id.0))
+        .collect::<Vec<String>>()
+    };
+
+    Ok(RelationCellChangesetParams {
+      cell_path,
+      inserted_row_ids: parse_row_ids(self.inserted_row_ids),
+      removed_row_ids: parse_row_ids(self.removed_row_ids),
+    })
+  }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RelationCellChangeset {
+  pub inserted_row_ids: Vec<String>,
+  pub removed_row_ids: Vec<String>,
+}
+
+impl RelationCellChangeset {
+  pub fn from_insert_row_ids(row_ids: Vec<String>) -> Self {
+    Self {
+      inserted_row_ids: row_ids,
+      removed_row_ids: vec![],
+    }
+  }
+
+  pub fn from_remove_row_ids(row_ids: Vec<String>) -> Self {
+    Self {
+      inserted_row_ids: vec![],
+      removed_row_ids: row_ids,
+    }
+  }
+}
+
+impl FromCellChangesetString for RelationCellChangeset {
+  fn from_changeset(changeset: String) -> FlowyResult<Self>
+  where
+    Self: Sized,
+  {
+    serde_json::from_str::<RelationCellChangeset>(&changeset).map_err(internal_error)
+  }
+}
+
+impl ToCellChangesetString for RelationCellChangeset {
+  fn to_cell_changeset_str(&self) -> String {
+    serde_json::to_string(self).unwrap_or_default()
+  }
+}
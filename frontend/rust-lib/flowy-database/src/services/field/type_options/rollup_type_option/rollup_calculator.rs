@@ -0,0 +1,50 @@
+use crate::services::field::{formula_value_from_cell, FormulaValue, RollupCalculationPB};
+use database_model::{FieldRevision, RowRevision};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+/// Aggregates the values of the target field in the related rows. Returns the string that is
+/// saved in the rollup cell.
+///
+/// # Arguments
+///
+/// * `calculation`: the calculation of the rollup type option
+/// * `target_field_rev`: the field whose values are aggregated. None if the field doesn't exist
+/// in the related database.
+/// * `related_row_revs`: the rows that are linked by the relation cell
+///
+pub fn calculate_rollup(
+  calculation: &RollupCalculationPB,
+  target_field_rev: Option<&FieldRevision>,
+  related_row_revs: &[Arc<RowRevision>],
+) -> String {
+  let values = match target_field_rev {
+    None => vec![],
+    Some(field_rev) => related_row_revs
+      .iter()
+      .map(|row_rev| formula_value_from_cell(field_rev, row_rev.cells.get(&field_rev.id)))
+      .filter(|value| !value.is_empty())
+      .collect::<Vec<FormulaValue>>(),
+  };
+  let numbers = || values.iter().flat_map(|value| value.as_number());
+
+  let value = match calculation {
+    RollupCalculationPB::Count => FormulaValue::Number(Decimal::from(related_row_revs.len())),
+    RollupCalculationPB::Sum => numbers()
+      .try_fold(Decimal::ZERO, |acc, num| acc.checked_add(num))
+      .map(FormulaValue::Number)
+      .unwrap_or(FormulaValue::Empty),
+    RollupCalculationPB::Min => numbers()
+      .min()
+      .map(FormulaValue::Number)
+      .unwrap_or(FormulaValue::Empty),
+    RollupCalculationPB::Max => numbers()
+      .max()
+      .map(FormulaValue::Number)
+      .unwrap_or(FormulaValue::Empty),
+    RollupCalculationPB::List => {
+      FormulaValue::List(values.iter().map(|value| value.as_text()).collect())
+    },
+  };
+  value.to_cell_str(&calculation.result_type())
+}
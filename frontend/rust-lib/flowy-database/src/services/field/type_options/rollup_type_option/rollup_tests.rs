@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+  use crate::entities::FieldType;
+  use crate::services::cell::{
+    insert_number_cell, insert_text_cell, CellDataChangeset, TypeCellData,
+  };
+  use crate::services::field::{
+    calculate_rollup, FieldBuilder, RollupCalculationPB, RollupTypeOptionPB,
+  };
+  use database_model::{CellRevision, FieldRevision, RowRevision};
+  use std::sync::Arc;
+
+  fn make_rows(
+    field_rev: &FieldRevision,
+    make_cell: impl Fn(usize) -> Option<CellRevision>,
+    count: usize,
+  ) -> Vec<Arc<RowRevision>> {
+    (0..count)
+      .map(|index| {
+        let mut row_rev = RowRevision::new("");
+        if let Some(cell_rev) = make_cell(index) {
+          row_rev.cells.insert(field_rev.id.clone(), cell_rev);
+        }
+        Arc::new(row_rev)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn rollup_number_calculation_test() {
+    let field_rev = FieldBuilder::from_field_type(&FieldType::Number).build();
+    // The values are 1, 2, 3 and an empty cell.
+    let row_revs = make_rows(
+      &field_rev,
+      |index| (index < 3).then(|| insert_number_cell(index as i64 + 1, &field_rev)),
+      4,
+    );
+
+    let calculate = |calculation: RollupCalculationPB| {
+      calculate_rollup(&calculation, Some(&field_rev), &row_revs)
+    };
+    assert_eq!(calculate(RollupCalculationPB::Count), "4");
+    assert_eq!(calculate(RollupCalculationPB::Sum), "6");
+    assert_eq!(calculate(RollupCalculationPB::Min), "1");
+    assert_eq!(calculate(RollupCalculationPB::Max), "3");
+    assert_eq!(calculate(RollupCalculationPB::List), "1, 2, 3");
+  }
+
+  #[test]
+  fn rollup_text_calculation_test() {
+    let field_rev = FieldBuilder::from_field_type(&FieldType::RichText).build();
+    let names = ["A", "B", "C"];
+    let row_revs = make_rows(
+      &field_rev,
+      |index| Some(insert_text_cell(names[index].to_owned(), &field_rev)),
+      3,
+    );
+
+    let calculate = |calculation: RollupCalculationPB| {
+      calculate_rollup(&calculation, Some(&field_rev), &row_revs)
+    };
+    assert_eq!(calculate(RollupCalculationPB::List), "A, B, C");
+    assert_eq!(calculate(RollupCalculationPB::Sum), "0");
+    assert_eq!(calculate(RollupCalculationPB::Max), "");
+  }
+
+  #[test]
+  fn rollup_without_target_field_test() {
+    let field_rev = FieldBuilder::from_field_type(&FieldType::Number).build();
+    let row_revs = make_rows(&field_rev, |_| Some(insert_number_cell(1, &field_rev)), 2);
+    assert_eq!(
+      calculate_rollup(&RollupCalculationPB::Count, None, &row_revs),
+      "2"
+    );
+    assert_eq!(
+      calculate_rollup(&RollupCalculationPB::Max, None, &row_revs),
+      ""
+    );
+    assert_eq!(
+      calculate_rollup(&RollupCalculationPB::Count, None, &[]),
+      "0"
+    );
+  }
+
+  #[test]
+  fn rollup_cell_is_read_only_test() {
+    let type_option = RollupTypeOptionPB::default();
+    let type_cell_data = TypeCellData::new("10".to_owned(), FieldType::Rollup);
+    let (cell_str, _) = type_option
+      .apply_changeset("20".to_owned(), Some(type_cell_data))
+      .unwrap();
+    assert_eq!(cell_str, "10");
+  }
+}
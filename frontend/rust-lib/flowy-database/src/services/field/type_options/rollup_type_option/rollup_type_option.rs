@@ -0,0 +1,159 @@
+use crate::entities::{FieldType, FormulaFilterPB};
+use crate::impl_type_option;
+use crate::services::cell::{CellDataChangeset, CellDataDecoder, FromCellString, TypeCellData};
+use crate::services::field::{
+  compare_values, BoxTypeOptionBuilder, FormulaValue, RollupCalculationPB, StrCellData, TypeOption,
+  TypeOptionBuilder, TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
+  TypeOptionTransform,
+};
+use bytes::Bytes;
+use database_model::{FieldRevision, TypeOptionDataDeserializer, TypeOptionDataSerializer};
+use flowy_derive::ProtoBuf;
+use flowy_error::FlowyResult;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+#[derive(Default)]
+pub struct RollupTypeOptionBuilder(RollupTypeOptionPB);
+impl_into_box_type_option_builder!(RollupTypeOptionBuilder);
+impl_builder_from_json_str_and_from_bytes!(RollupTypeOptionBuilder, RollupTypeOptionPB);
+
+impl RollupTypeOptionBuilder {
+  pub fn relation_field_id(mut self, field_id: &str) -> Self {
+    self.0.relation_field_id = field_id.to_owned();
+    self
+  }
+
+  pub fn target_field_id(mut self, field_id: &str) -> Self {
+    self.0.target_field_id = field_id.to_owned();
+    self
+  }
+
+  pub fn calculation(mut self, calculation: RollupCalculationPB) -> Self {
+    self.0.calculation = calculation;
+    self
+  }
+}
+
+impl TypeOptionBuilder for RollupTypeOptionBuilder {
+  fn field_type(&self) -> FieldType {
+    FieldType::Rollup
+  }
+
+  fn serializer(&self) -> &dyn TypeOptionDataSerializer {
+    &self.0
+  }
+}
+
+/// The rollup type option aggregates the values of the target field in the rows that are linked
+/// by the relation field. Like the formula, the calculated value is saved in the cell and it is
+/// recalculated when the relation cell or one of the related rows is changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ProtoBuf)]
+pub struct RollupTypeOptionPB {
+  /// The id of the relation field in the same database.
+  #[pb(index = 1)]
+  pub relation_field_id: String,
+
+  /// The id of the field in the related database whose values are aggregated. It's not
+  /// required by the [RollupCalculationPB::Count].
+  #[pb(index = 2)]
+  pub target_field_id: String,
+
+  #[pb(index = 3)]
+  #[serde(default)]
+  pub calculation: RollupCalculationPB,
+}
+impl_type_option!(RollupTypeOptionPB, FieldType::Rollup);
+
+impl TypeOption for RollupTypeOptionPB {
+  type CellData = StrCellData;
+  type CellChangeset = RollupCellChangeset;
+  type CellProtobufType = StrCellData;
+  type CellFilter = FormulaFilterPB;
+}
+
+impl TypeOptionTransform for RollupTypeOptionPB {}
+
+impl TypeOptionCellData for RollupTypeOptionPB {
+  fn convert_to_protobuf(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    cell_data
+  }
+
+  fn decode_type_option_cell_str(
+    &self,
+    cell_str: String,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    StrCellData::from_cell_str(&cell_str)
+  }
+}
+
+impl CellDataDecoder for RollupTypeOptionPB {
+  fn decode_cell_str(
+    &self,
+    cell_str: String,
+    decoded_field_type: &FieldType,
+    _field_rev: &FieldRevision,
+  ) -> FlowyResult<<Self as TypeOption>::CellData> {
+    if !decoded_field_type.is_rollup() {
+      return Ok(Default::default());
+    }
+
+    self.decode_type_option_cell_str(cell_str)
+  }
+
+  fn decode_cell_data_to_str(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.to_string()
+  }
+}
+
+pub type RollupCellChangeset = String;
+
+impl CellDataChangeset for RollupTypeOptionPB {
+  /// The rollup cell is read-only, the changeset is ignored and the calculated value is kept.
+  fn apply_changeset(
+    &self,
+    _changeset: <Self as TypeOption>::CellChangeset,
+    type_cell_data: Option<TypeCellData>,
+  ) -> FlowyResult<(String, <Self as TypeOption>::CellData)> {
+    let cell_str = match type_cell_data {
+      Some(type_cell_data) if type_cell_data.field_type.is_rollup() => type_cell_data.cell_str,
+      _ => "".to_owned(),
+    };
+    Ok((cell_str.clone(), cell_str.into()))
+  }
+}
+
+impl TypeOptionCellDataFilter for RollupTypeOptionPB {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    field_type: &FieldType,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    if !field_type.is_rollup() {
+      return true;
+    }
+    filter.is_visible(&self.calculation.result_type(), cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for RollupTypeOptionPB {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+  ) -> Ordering {
+    let result_type = self.calculation.result_type();
+    let value = FormulaValue::from_cell_str(cell_data, &result_type);
+    let other_value = FormulaValue::from_cell_str(other_cell_data, &result_type);
+    match (value.is_empty(), other_value.is_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Less,
+      (false, true) => Ordering::Greater,
+      (false, false) => compare_values(&value, &other_value),
+    }
+  }
+}
@@ -0,0 +1,35 @@
+use crate::services::field::FormulaResultTypePB;
+use flowy_derive::ProtoBuf_Enum;
+use serde::{Deserialize, Serialize};
+
+/// The calculation that aggregates the values of the related rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ProtoBuf_Enum)]
+pub enum RollupCalculationPB {
+  /// The number of the related rows.
+  Count = 0,
+  Sum = 1,
+  Min = 2,
+  Max = 3,
+  /// The values of the related rows, separated by commas.
+  List = 4,
+}
+
+impl std::default::Default for RollupCalculationPB {
+  fn default() -> Self {
+    RollupCalculationPB::Count
+  }
+}
+
+impl RollupCalculationPB {
+  /// Returns the type of the calculated value. The rollup cell is displayed, filtered and sorted
+  /// like the formula cell with the same result type.
+  pub fn result_type(&self) -> FormulaResultTypePB {
+    match self {
+      RollupCalculationPB::Count
+      | RollupCalculationPB::Sum
+      | RollupCalculationPB::Min
+      | RollupCalculationPB::Max => FormulaResultTypePB::Number,
+      RollupCalculationPB::List => FormulaResultTypePB::Text,
+    }
+  }
+}
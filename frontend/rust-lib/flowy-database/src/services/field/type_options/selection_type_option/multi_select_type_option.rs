@@ -123,16 +123,17 @@ impl TypeOptionCellDataCompare for MultiSelectTypeOptionPB {
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
   ) -> Ordering {
+    // The options are compared by their order in the type option instead of the names.
     for i in 0..min(cell_data.len(), other_cell_data.len()) {
       let order = match (
         cell_data
           .get(i)
-          .and_then(|id| self.options.iter().find(|option| &option.id == id)),
+          .and_then(|id| self.options.iter().position(|option| &option.id == id)),
         other_cell_data
           .get(i)
-          .and_then(|id| self.options.iter().find(|option| &option.id == id)),
+          .and_then(|id| self.options.iter().position(|option| &option.id == id)),
       ) {
-        (Some(left), Some(right)) => left.name.cmp(&right.name),
+        (Some(left), Some(right)) => left.cmp(&right),
         (Some(_), None) => Ordering::Greater,
         (None, Some(_)) => Ordering::Less,
         (None, None) => default_order(),
@@ -142,7 +143,7 @@ impl TypeOptionCellDataCompare for MultiSelectTypeOptionPB {
         return order;
       }
     }
-    default_order()
+    cell_data.len().cmp(&other_cell_data.len())
   }
 }
 #[derive(Default)]
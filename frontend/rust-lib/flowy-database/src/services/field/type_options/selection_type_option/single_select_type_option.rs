@@ -117,15 +117,16 @@ impl TypeOptionCellDataCompare for SingleSelectTypeOptionPB {
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
   ) -> Ordering {
+    // The options are compared by their order in the type option instead of the names.
     match (
       cell_data
         .first()
-        .and_then(|id| self.options.iter().find(|option| &option.id == id)),
+        .and_then(|id| self.options.iter().position(|option| &option.id == id)),
       other_cell_data
         .first()
-        .and_then(|id| self.options.iter().find(|option| &option.id == id)),
+        .and_then(|id| self.options.iter().position(|option| &option.id == id)),
     ) {
-      (Some(left), Some(right)) => left.name.cmp(&right.name),
+      (Some(left), Some(right)) => left.cmp(&right),
       (Some(_), None) => Ordering::Greater,
       (None, Some(_)) => Ordering::Less,
       (None, None) => default_order(),
@@ -69,4 +69,18 @@ mod tests {
       format!("{},{}", france.name, argentina.name)
     );
   }
+
+  #[test]
+  fn text_natural_order_test() {
+    use std::cmp::Ordering;
+
+    assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+    assert_eq!(natural_cmp("file10", "file9"), Ordering::Greater);
+    assert_eq!(natural_cmp("file002", "file2"), Ordering::Less);
+    assert_eq!(natural_cmp("a", "B"), Ordering::Less);
+    assert_eq!(natural_cmp("A", "a"), Ordering::Less);
+    assert_eq!(natural_cmp("A", "AE"), Ordering::Less);
+    assert_eq!(natural_cmp("v1.2", "v1.10"), Ordering::Less);
+    assert_eq!(natural_cmp("abc", "abc"), Ordering::Equal);
+  }
 }
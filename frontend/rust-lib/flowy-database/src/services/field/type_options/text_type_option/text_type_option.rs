@@ -15,6 +15,8 @@ use flowy_error::{FlowyError, FlowyResult};
 use protobuf::ProtobufError;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
 
 #[derive(Default)]
 pub struct RichTextTypeOptionBuilder(RichTextTypeOptionPB);
@@ -154,10 +156,55 @@ impl TypeOptionCellDataCompare for RichTextTypeOptionPB {
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
   ) -> Ordering {
-    cell_data.0.cmp(&other_cell_data.0)
+    natural_cmp(&cell_data.0, &other_cell_data.0)
   }
 }
 
+/// Compares the strings in natural order. The runs of digits are compared by their numeric
+/// values, so "file2" is less than "file10". Other characters are compared case-insensitively,
+/// and the strings are compared by their raw characters if they are equal in natural order.
+pub fn natural_cmp(left: &str, right: &str) -> Ordering {
+  let mut left_chars = left.chars().peekable();
+  let mut right_chars = right.chars().peekable();
+  loop {
+    match (left_chars.peek(), right_chars.peek()) {
+      (None, None) => return left.cmp(right),
+      (None, Some(_)) => return Ordering::Less,
+      (Some(_), None) => return Ordering::Greater,
+      (Some(&l), Some(&r)) => {
+        let order = if l.is_ascii_digit() && r.is_ascii_digit() {
+          let left_digits = take_digits(&mut left_chars);
+          let right_digits = take_digits(&mut right_chars);
+          cmp_digits(&left_digits, &right_digits)
+        } else {
+          left_chars.next();
+          right_chars.next();
+          l.to_lowercase().cmp(r.to_lowercase())
+        };
+        if order.is_ne() {
+          return order;
+        }
+      },
+    }
+  }
+}
+
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+  let mut digits = String::new();
+  while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+    digits.push(c);
+  }
+  digits
+}
+
+/// Compares two runs of digits by their numeric values without parsing them, so the runs can
+/// be longer than any integer type.
+fn cmp_digits(left: &str, right: &str) -> Ordering {
+  let left = left.trim_start_matches('0');
+  let right = right.trim_start_matches('0');
+  left.len().cmp(&right.len()).then_with(|| left.cmp(right))
+}
+
 #[derive(Clone)]
 pub struct TextCellData(pub String);
 impl AsRef<str> for TextCellData {
@@ -1,15 +1,15 @@
 use crate::entities::FieldType;
 use crate::services::cell::{
   AtomicCellDataCache, AtomicCellFilterCache, CellDataChangeset, CellDataDecoder, CellProtobufBlob,
-  FromCellChangesetString, FromCellString, TypeCellData,
+  FromCellChangesetString, TypeCellData,
 };
 use crate::services::field::{
-  CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB, MultiSelectTypeOptionPB,
-  NumberTypeOptionPB, RichTextTypeOptionPB, SingleSelectTypeOptionPB, TypeOption,
-  TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionTransform,
-  URLTypeOptionPB,
+  CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB, FormulaTypeOptionPB,
+  MultiSelectTypeOptionPB, NumberTypeOptionPB, RelationTypeOptionPB, RichTextTypeOptionPB,
+  RollupTypeOptionPB, SingleSelectTypeOptionPB, TypeOption, TypeOptionCellData,
+  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionTransform, URLTypeOptionPB,
 };
-use crate::services::filter::FilterType;
+use crate::services::filter::FilterContext;
 use database_model::{FieldRevision, TypeOptionDataDeserializer, TypeOptionDataSerializer};
 use flowy_error::FlowyResult;
 use std::any::Any;
@@ -45,9 +45,15 @@ pub trait TypeOptionCellDataHandler {
     field_rev: &FieldRevision,
   ) -> Ordering;
 
+  /// Returns true if the display string of the cell data is empty. The empty cells are placed
+  /// after the non-empty cells when sorting.
+  fn handle_cell_is_empty(&self, cell_str: &str, field_rev: &FieldRevision) -> bool;
+
+  /// Returns the visibility of the cell after applying the filter. The filter is read from the
+  /// filter cache by the filter id.
   fn handle_cell_filter(
     &self,
-    filter_type: &FilterType,
+    filter: &FilterContext,
     field_rev: &FieldRevision,
     type_cell_data: TypeCellData,
   ) -> bool;
@@ -89,6 +95,19 @@ impl AsRef<u64> for CellDataCacheKey {
   }
 }
 
+/// Removes the cell data that was decoded from the `cell_str` from the cache. It's used when the
+/// `cell_str` is no longer used by the cell, for example, the formula cell after one of its
+/// referenced cells was changed.
+pub fn remove_cell_data_from_cache(
+  cell_data_cache: &AtomicCellDataCache,
+  field_rev: &FieldRevision,
+  decoded_field_type: FieldType,
+  cell_str: &str,
+) {
+  let key = CellDataCacheKey::new(field_rev, decoded_field_type, cell_str);
+  cell_data_cache.write().remove(key.as_ref());
+}
+
 struct TypeOptionCellDataHandlerImpl<T> {
   inner: T,
   cell_data_cache: Option<AtomicCellDataCache>,
@@ -247,15 +266,24 @@ where
     self.apply_cmp(&left, &right)
   }
 
+  fn handle_cell_is_empty(&self, cell_str: &str, field_rev: &FieldRevision) -> bool {
+    let field_type: FieldType = field_rev.ty.into();
+    let cell_data = self
+      .get_decoded_cell_data(cell_str.to_owned(), &field_type, field_rev)
+      .unwrap_or_default();
+    self.decode_cell_data_to_str(cell_data).is_empty()
+  }
+
   fn handle_cell_filter(
     &self,
-    filter_type: &FilterType,
+    filter: &FilterContext,
     field_rev: &FieldRevision,
     type_cell_data: TypeCellData,
   ) -> bool {
+    let filter_type = &filter.filter_type;
     let perform_filter = || {
       let filter_cache = self.cell_filter_cache.as_ref()?.read();
-      let cell_filter = filter_cache.get::<<Self as TypeOption>::CellFilter>(filter_type)?;
+      let cell_filter = filter_cache.get::<<Self as TypeOption>::CellFilter>(&filter.filter_id)?;
       let cell_data = self
         .get_decoded_cell_data(type_cell_data.cell_str, &filter_type.field_type, field_rev)
         .ok()?;
@@ -277,7 +305,7 @@ where
         return self.decode_cell_data_to_str(cell_data);
       }
     }
-    match <Self as TypeOption>::CellData::from_cell_str(&cell_str) {
+    match self.decode_type_option_cell_str(cell_str) {
       Ok(cell_data) => self.decode_cell_data_to_str(cell_data),
       Err(_) => "".to_string(),
     }
@@ -424,6 +452,36 @@ impl<'a> TypeOptionCellExt<'a> {
             self.cell_data_cache.clone(),
           )
         }),
+      FieldType::Formula => self
+        .field_rev
+        .get_type_option::<FormulaTypeOptionPB>(field_type.into())
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            self.cell_filter_cache.clone(),
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Relation => self
+        .field_rev
+        .get_type_option::<RelationTypeOptionPB>(field_type.into())
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            self.cell_filter_cache.clone(),
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Rollup => self
+        .field_rev
+        .get_type_option::<RollupTypeOptionPB>(field_type.into())
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            self.cell_filter_cache.clone(),
+            self.cell_data_cache.clone(),
+          )
+        }),
     }
   }
 }
@@ -483,6 +541,12 @@ fn get_type_option_transform_handler(
       as Box<dyn TypeOptionTransformHandler>,
     FieldType::Checklist => Box::new(ChecklistTypeOptionPB::from_json_str(type_option_data))
       as Box<dyn TypeOptionTransformHandler>,
+    FieldType::Formula => Box::new(FormulaTypeOptionPB::from_json_str(type_option_data))
+      as Box<dyn TypeOptionTransformHandler>,
+    FieldType::Relation => Box::new(RelationTypeOptionPB::from_json_str(type_option_data))
+      as Box<dyn TypeOptionTransformHandler>,
+    FieldType::Rollup => Box::new(RollupTypeOptionPB::from_json_str(type_option_data))
+      as Box<dyn TypeOptionTransformHandler>,
   }
 }
 
@@ -567,4 +631,16 @@ impl RowSingleCellData {
     into_check_list_field_cell_data,
     <CheckboxTypeOptionPB as TypeOption>::CellData
   );
+  into_cell_data!(
+    into_formula_field_cell_data,
+    <FormulaTypeOptionPB as TypeOption>::CellData
+  );
+  into_cell_data!(
+    into_relation_field_cell_data,
+    <RelationTypeOptionPB as TypeOption>::CellData
+  );
+  into_cell_data!(
+    into_rollup_field_cell_data,
+    <RollupTypeOptionPB as TypeOption>::CellData
+  );
 }
@@ -6,9 +6,10 @@ use crate::services::cell::{
 use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
 use crate::services::field::*;
 use crate::services::filter::{
-  FilterChangeset, FilterResult, FilterResultNotification, FilterType,
+  FilterChangeset, FilterContext, FilterGroup, FilterResult, FilterResultNotification, FilterType,
 };
 use crate::services::row::DatabaseBlockRowRevision;
+use chrono::Local;
 use dashmap::DashMap;
 use database_model::{CellRevision, FieldId, FieldRevision, FilterRevision, RowRevision};
 use flowy_error::FlowyResult;
@@ -18,15 +19,19 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 
 type RowId = String;
 pub trait FilterDelegate: Send + Sync + 'static {
-  fn get_filter_rev(&self, filter_type: FilterType) -> Fut<Option<Arc<FilterRevision>>>;
+  fn get_filter_rev(&self, filter: FilterContext) -> Fut<Option<Arc<FilterRevision>>>;
+  fn get_filter_revs(&self, filter_type: FilterType) -> Fut<Vec<Arc<FilterRevision>>>;
   fn get_field_rev(&self, field_id: &str) -> Fut<Option<Arc<FieldRevision>>>;
   fn get_field_revs(&self, field_ids: Option<Vec<String>>) -> Fut<Vec<Arc<FieldRevision>>>;
   fn get_blocks(&self) -> Fut<Vec<DatabaseBlockRowRevision>>;
   fn get_row_rev(&self, rows_id: &str) -> Fut<Option<(usize, Arc<RowRevision>)>>;
+  fn get_filter_group(&self) -> Fut<FilterGroup>;
 }
 
 pub trait FromFilterString {
@@ -42,8 +47,12 @@ pub struct FilterController {
   result_by_row_id: DashMap<RowId, FilterResult>,
   cell_data_cache: AtomicCellDataCache,
   cell_filter_cache: AtomicCellFilterCache,
+  /// The [FilterType] of the filters in the `cell_filter_cache`, keyed by the filter id.
+  filter_type_by_id: Arc<parking_lot::RwLock<HashMap<String, FilterType>>>,
+  filter_group: parking_lot::RwLock<FilterGroup>,
   task_scheduler: Arc<RwLock<TaskDispatcher>>,
   notifier: DatabaseViewChangedNotifier,
+  day_rollover_task: parking_lot::Mutex<Option<JoinHandle<()>>>,
 }
 
 impl FilterController {
@@ -65,15 +74,23 @@ impl FilterController {
       delegate: Box::new(delegate),
       result_by_row_id: DashMap::default(),
       cell_data_cache,
-      cell_filter_cache: AnyTypeCache::<FilterType>::new(),
+      cell_filter_cache: AnyTypeCache::<String>::new(),
+      filter_type_by_id: Default::default(),
+      filter_group: Default::default(),
       task_scheduler,
       notifier,
+      day_rollover_task: Default::default(),
     };
     this.refresh_filters(filter_revs).await;
+    this.refresh_filter_group().await;
+    this.spawn_day_rollover_task();
     this
   }
 
   pub async fn close(&self) {
+    if let Some(day_rollover_task) = self.day_rollover_task.lock().take() {
+      day_rollover_task.abort();
+    }
     self
       .task_scheduler
       .write()
@@ -84,14 +101,31 @@ impl FilterController {
 
   #[tracing::instrument(name = "schedule_filter_task", level = "trace", skip(self))]
   async fn gen_task(&self, task_type: FilterEvent, qos: QualityOfService) {
-    let task_id = self.task_scheduler.read().await.next_task_id();
-    let task = Task::new(
-      &self.handler_id,
-      task_id,
-      TaskContent::Text(task_type.to_string()),
-      qos,
-    );
-    self.task_scheduler.write().await.add_task(task);
+    schedule_filter_task(&self.task_scheduler, &self.handler_id, task_type, qos).await;
+  }
+
+  /// The relative date filters, for example, the `DateIsThisWeek`, depend on the current date. So
+  /// all the rows are filtered again when the day rolls over if there is any of them.
+  fn spawn_day_rollover_task(&self) {
+    let task_scheduler = self.task_scheduler.clone();
+    let handler_id = self.handler_id.clone();
+    let cell_filter_cache = self.cell_filter_cache.clone();
+    let filter_type_by_id = self.filter_type_by_id.clone();
+    let day_rollover_task = tokio::spawn(async move {
+      loop {
+        tokio::time::sleep(duration_until_next_day()).await;
+        if has_relative_date_filter(&cell_filter_cache, &filter_type_by_id.read()) {
+          schedule_filter_task(
+            &task_scheduler,
+            &handler_id,
+            FilterEvent::FilterDidChanged,
+            QualityOfService::Background,
+          )
+          .await;
+        }
+      }
+    });
+    *self.day_rollover_task.lock() = Some(day_rollover_task);
   }
 
   pub async fn filter_row_revs(&self, row_revs: &mut Vec<Arc<RowRevision>>) {
@@ -99,11 +133,15 @@ impl FilterController {
       return;
     }
     let field_rev_by_field_id = self.get_filter_revs_map().await;
+    let filter_group = self.filter_group.read().clone();
+    let filter_type_by_id = self.filter_type_by_id.read().clone();
     row_revs.iter().for_each(|row_rev| {
       let _ = filter_row(
         row_rev,
         &self.result_by_row_id,
         &field_rev_by_field_id,
+        &filter_group,
+        &filter_type_by_id,
         &self.cell_data_cache,
         &self.cell_filter_cache,
       );
@@ -113,7 +151,7 @@ impl FilterController {
       self
         .result_by_row_id
         .get(&row_rev.id)
-        .map(|result| result.is_visible())
+        .map(|result| result.is_visible)
         .unwrap_or(false)
     });
   }
@@ -147,12 +185,16 @@ impl FilterController {
   async fn filter_row(&self, row_id: String) -> FlowyResult<()> {
     if let Some((_, row_rev)) = self.delegate.get_row_rev(&row_id).await {
       let field_rev_by_field_id = self.get_filter_revs_map().await;
+      let filter_group = self.filter_group.read().clone();
+      let filter_type_by_id = self.filter_type_by_id.read().clone();
       let mut notification =
         FilterResultNotification::new(self.view_id.clone(), row_rev.block_id.clone());
       if let Some((row_id, is_visible)) = filter_row(
         &row_rev,
         &self.result_by_row_id,
         &field_rev_by_field_id,
+        &filter_group,
+        &filter_type_by_id,
         &self.cell_data_cache,
         &self.cell_filter_cache,
       ) {
@@ -177,6 +219,8 @@ impl FilterController {
 
   async fn filter_all_rows(&self) -> FlowyResult<()> {
     let field_rev_by_field_id = self.get_filter_revs_map().await;
+    let filter_group = self.filter_group.read().clone();
+    let filter_type_by_id = self.filter_type_by_id.read().clone();
     for block in self.delegate.get_blocks().await.into_iter() {
       // The row_ids contains the row that its visibility was changed.
       let mut visible_rows = vec![];
@@ -187,6 +231,8 @@ impl FilterController {
           row_rev,
           &self.result_by_row_id,
           &field_rev_by_field_id,
+          &filter_group,
+          &filter_type_by_id,
           &self.cell_data_cache,
           &self.cell_filter_cache,
         ) {
@@ -228,60 +274,89 @@ impl FilterController {
     changeset: FilterChangeset,
   ) -> Option<FilterChangesetNotificationPB> {
     let mut notification: Option<FilterChangesetNotificationPB> = None;
-    if let Some(filter_type) = &changeset.insert_filter {
-      if let Some(filter) = self.filter_from_filter_type(filter_type).await {
+    if let Some(filter) = &changeset.insert_filter {
+      if let Some(filter_rev) = self.delegate.get_filter_rev(filter.clone()).await {
         notification = Some(FilterChangesetNotificationPB::from_insert(
           &self.view_id,
-          vec![filter],
+          vec![FilterPB::from(filter_rev.as_ref())],
         ));
-      }
-      if let Some(filter_rev) = self.delegate.get_filter_rev(filter_type.clone()).await {
         self.refresh_filters(vec![filter_rev]).await;
       }
     }
 
-    if let Some(updated_filter_type) = changeset.update_filter {
-      if let Some(old_filter_type) = updated_filter_type.old {
-        let new_filter = self.filter_from_filter_type(&updated_filter_type.new).await;
-        let old_filter = self.filter_from_filter_type(&old_filter_type).await;
-
-        // Get the filter id
-        let mut filter_id = old_filter.map(|filter| filter.id);
-        if filter_id.is_none() {
-          filter_id = new_filter.as_ref().map(|filter| filter.id.clone());
-        }
+    if let Some(filter) = &changeset.update_filter {
+      let filter_rev = self.delegate.get_filter_rev(filter.clone()).await;
+      let new_filter = filter_rev
+        .as_ref()
+        .map(|filter_rev| FilterPB::from(filter_rev.as_ref()));
+      match filter_rev {
+        None => self.remove_filter(&filter.filter_id),
+        Some(filter_rev) => self.refresh_filters(vec![filter_rev]).await,
+      }
+      notification = Some(FilterChangesetNotificationPB::from_update(
+        &self.view_id,
+        vec![UpdatedFilter {
+          filter_id: filter.filter_id.clone(),
+          filter: new_filter,
+        }],
+      ));
+    }
 
-        // Update the corresponding filter in the cache
-        if let Some(filter_rev) = self
+    if let Some(updated_filter_type) = changeset.update_field_type {
+      if let Some(old_filter_type) = updated_filter_type.old {
+        let new_filter_revs = self
           .delegate
-          .get_filter_rev(updated_filter_type.new.clone())
-          .await
-        {
-          self.refresh_filters(vec![filter_rev]).await;
-        }
-
-        if let Some(filter_id) = filter_id {
-          notification = Some(FilterChangesetNotificationPB::from_update(
-            &self.view_id,
-            vec![UpdatedFilter {
-              filter_id,
-              filter: new_filter,
-            }],
-          ));
+          .get_filter_revs(updated_filter_type.new.clone())
+          .await;
+        if old_filter_type == updated_filter_type.new {
+          // The type option of the field was changed, the filters are refreshed.
+          let update_filters = new_filter_revs
+            .iter()
+            .map(|filter_rev| UpdatedFilter {
+              filter_id: filter_rev.id.clone(),
+              filter: Some(FilterPB::from(filter_rev.as_ref())),
+            })
+            .collect::<Vec<_>>();
+          if !update_filters.is_empty() {
+            notification = Some(FilterChangesetNotificationPB::from_update(
+              &self.view_id,
+              update_filters,
+            ));
+          }
+        } else {
+          // The filters of the old field type are replaced by the filters of the new field type.
+          let old_filter_revs = self.delegate.get_filter_revs(old_filter_type).await;
+          for filter_rev in old_filter_revs.iter() {
+            self.remove_filter(&filter_rev.id);
+          }
+          if !old_filter_revs.is_empty() || !new_filter_revs.is_empty() {
+            let mut type_notification = FilterChangesetNotificationPB::new(&self.view_id);
+            type_notification.delete_filters = old_filter_revs
+              .iter()
+              .map(|filter_rev| FilterPB::from(filter_rev.as_ref()))
+              .collect();
+            type_notification.insert_filters = new_filter_revs
+              .iter()
+              .map(|filter_rev| FilterPB::from(filter_rev.as_ref()))
+              .collect();
+            notification = Some(type_notification);
+          }
         }
+        self.refresh_filters(new_filter_revs).await;
       }
     }
 
-    if let Some(filter_type) = &changeset.delete_filter {
-      if let Some(filter) = self.filter_from_filter_type(filter_type).await {
+    if let Some(filter) = &changeset.delete_filter {
+      if let Some(filter_rev) = self.delegate.get_filter_rev(filter.clone()).await {
         notification = Some(FilterChangesetNotificationPB::from_delete(
           &self.view_id,
-          vec![filter],
+          vec![FilterPB::from(filter_rev.as_ref())],
         ));
       }
-      self.cell_filter_cache.write().remove(filter_type);
+      self.remove_filter(&filter.filter_id);
     }
 
+    self.refresh_filter_group().await;
     self
       .gen_task(FilterEvent::FilterDidChanged, QualityOfService::Background)
       .await;
@@ -289,64 +364,96 @@ impl FilterController {
     notification
   }
 
-  async fn filter_from_filter_type(&self, filter_type: &FilterType) -> Option<FilterPB> {
+  /// Re-evaluates the visibility of all the rows after the filter groups were changed.
+  pub async fn did_receive_filter_group_changed(&self) {
+    self.refresh_filter_group().await;
     self
-      .delegate
-      .get_filter_rev(filter_type.clone())
-      .await
-      .map(|filter| FilterPB::from(filter.as_ref()))
+      .gen_task(FilterEvent::FilterDidChanged, QualityOfService::Background)
+      .await;
+  }
+
+  async fn refresh_filter_group(&self) {
+    let filter_group = self.delegate.get_filter_group().await;
+    *self.filter_group.write() = filter_group;
+  }
+
+  fn remove_filter(&self, filter_id: &str) {
+    self.cell_filter_cache.write().remove(&filter_id.to_owned());
+    self.filter_type_by_id.write().remove(filter_id);
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
   async fn refresh_filters(&self, filter_revs: Vec<Arc<FilterRevision>>) {
     for filter_rev in filter_revs {
-      if let Some(field_rev) = self.delegate.get_field_rev(&filter_rev.field_id).await {
-        let filter_type = FilterType::from(&field_rev);
+      if self
+        .delegate
+        .get_field_rev(&filter_rev.field_id)
+        .await
+        .is_some()
+      {
+        let filter_id = filter_rev.id.clone();
+        let filter_type = FilterContext::from(filter_rev.as_ref()).filter_type;
         tracing::trace!("Create filter with type: {:?}", filter_type);
         match &filter_type.field_type {
           FieldType::RichText => {
             self.cell_filter_cache.write().insert(
-              &filter_type,
+              &filter_id,
               TextFilterPB::from_filter_rev(filter_rev.as_ref()),
             );
           },
           FieldType::Number => {
             self.cell_filter_cache.write().insert(
-              &filter_type,
+              &filter_id,
               NumberFilterPB::from_filter_rev(filter_rev.as_ref()),
             );
           },
           FieldType::DateTime => {
             self.cell_filter_cache.write().insert(
-              &filter_type,
+              &filter_id,
               DateFilterPB::from_filter_rev(filter_rev.as_ref()),
             );
           },
           FieldType::SingleSelect | FieldType::MultiSelect => {
             self.cell_filter_cache.write().insert(
-              &filter_type,
+              &filter_id,
               SelectOptionFilterPB::from_filter_rev(filter_rev.as_ref()),
             );
           },
           FieldType::Checkbox => {
             self.cell_filter_cache.write().insert(
-              &filter_type,
+              &filter_id,
               CheckboxFilterPB::from_filter_rev(filter_rev.as_ref()),
             );
           },
           FieldType::URL => {
             self.cell_filter_cache.write().insert(
-              &filter_type,
+              &filter_id,
               TextFilterPB::from_filter_rev(filter_rev.as_ref()),
             );
           },
           FieldType::Checklist => {
             self.cell_filter_cache.write().insert(
-              &filter_type,
+              &filter_id,
               ChecklistFilterPB::from_filter_rev(filter_rev.as_ref()),
             );
           },
+          FieldType::Formula | FieldType::Rollup => {
+            self.cell_filter_cache.write().insert(
+              &filter_id,
+              FormulaFilterPB::from_filter_rev(filter_rev.as_ref()),
+            );
+          },
+          FieldType::Relation => {
+            self.cell_filter_cache.write().insert(
+              &filter_id,
+              RelationFilterPB::from_filter_rev(filter_rev.as_ref()),
+            );
+          },
         }
+        self
+          .filter_type_by_id
+          .write()
+          .insert(filter_id, filter_type);
       }
     }
   }
@@ -358,6 +465,8 @@ fn filter_row(
   row_rev: &Arc<RowRevision>,
   result_by_row_id: &DashMap<RowId, FilterResult>,
   field_rev_by_field_id: &HashMap<FieldId, Arc<FieldRevision>>,
+  filter_group: &FilterGroup,
+  filter_type_by_id: &HashMap<String, FilterType>,
   cell_data_cache: &AtomicCellDataCache,
   cell_filter_cache: &AtomicCellFilterCache,
 ) -> Option<(String, bool)> {
@@ -365,21 +474,29 @@ fn filter_row(
   let mut filter_result = result_by_row_id
     .entry(row_rev.id.clone())
     .or_insert_with(FilterResult::default);
-  let old_is_visible = filter_result.is_visible();
-
-  // Iterate each cell of the row to check its visibility
-  for (field_id, field_rev) in field_rev_by_field_id {
-    let filter_type = FilterType::from(field_rev);
-    if !cell_filter_cache.read().contains(&filter_type) {
-      filter_result.visible_by_filter_id.remove(&filter_type);
-      continue;
-    }
+  let old_is_visible = filter_result.is_visible;
+
+  // Remove the results of the filters that were deleted
+  filter_result
+    .visible_by_filter_id
+    .retain(|filter_id, _| filter_type_by_id.contains_key(filter_id));
+
+  // Apply each filter to the cell of the field it applies to. A field can have more than one
+  // filter, so the results are keyed by the filter id.
+  for (filter_id, filter_type) in filter_type_by_id {
+    let field_rev = match field_rev_by_field_id.get(&filter_type.field_id) {
+      // The filter is not applied if its field was deleted or its field type was changed.
+      Some(field_rev) if &FilterType::from(field_rev) == filter_type => field_rev,
+      _ => {
+        filter_result.visible_by_filter_id.remove(filter_id);
+        continue;
+      },
+    };
 
-    let cell_rev = row_rev.cells.get(field_id);
-    // if the visibility of the cell_rew is changed, which means the visibility of the
-    // row is changed too.
+    let filter = FilterContext::new(filter_id, filter_type.clone());
+    let cell_rev = row_rev.cells.get(&filter_type.field_id);
     if let Some(is_visible) = filter_cell(
-      &filter_type,
+      &filter,
       field_rev,
       cell_rev,
       cell_data_cache,
@@ -387,11 +504,11 @@ fn filter_row(
     ) {
       filter_result
         .visible_by_filter_id
-        .insert(filter_type, is_visible);
+        .insert(filter.filter_id, is_visible);
     }
   }
 
-  let is_visible = filter_result.is_visible();
+  let is_visible = filter_result.evaluate(filter_group);
   if old_is_visible != is_visible {
     Some((row_rev.id.clone(), is_visible))
   } else {
@@ -404,19 +521,20 @@ fn filter_row(
 
 #[tracing::instrument(level = "trace", skip_all, fields(cell_content))]
 fn filter_cell(
-  filter_type: &FilterType,
+  filter: &FilterContext,
   field_rev: &Arc<FieldRevision>,
   cell_rev: Option<&CellRevision>,
   cell_data_cache: &AtomicCellDataCache,
   cell_filter_cache: &AtomicCellFilterCache,
 ) -> Option<bool> {
+  let field_type = &filter.filter_type.field_type;
   let type_cell_data = match cell_rev {
-    None => TypeCellData::from_field_type(&filter_type.field_type),
+    None => TypeCellData::from_field_type(field_type),
     Some(cell_rev) => match TypeCellData::try_from(cell_rev) {
       Ok(cell_data) => cell_data,
       Err(err) => {
         tracing::error!("Deserialize TypeCellData failed: {}", err);
-        TypeCellData::from_field_type(&filter_type.field_type)
+        TypeCellData::from_field_type(field_type)
       },
     },
   };
@@ -426,12 +544,55 @@ fn filter_cell(
     Some(cell_data_cache.clone()),
     Some(cell_filter_cache.clone()),
   )
-  .get_type_option_cell_data_handler(&filter_type.field_type)?;
+  .get_type_option_cell_data_handler(field_type)?;
 
-  let is_visible = handler.handle_cell_filter(filter_type, field_rev.as_ref(), type_cell_data);
+  let is_visible = handler.handle_cell_filter(filter, field_rev.as_ref(), type_cell_data);
   Some(is_visible)
 }
 
+async fn schedule_filter_task(
+  task_scheduler: &Arc<RwLock<TaskDispatcher>>,
+  handler_id: &str,
+  task_type: FilterEvent,
+  qos: QualityOfService,
+) {
+  let task_id = task_scheduler.read().await.next_task_id();
+  let task = Task::new(
+    handler_id,
+    task_id,
+    TaskContent::Text(task_type.to_string()),
+    qos,
+  );
+  task_scheduler.write().await.add_task(task);
+}
+
+/// Returns true if any of the date filters has a relative condition, for example, `DateIsToday`.
+fn has_relative_date_filter(
+  cell_filter_cache: &AtomicCellFilterCache,
+  filter_type_by_id: &HashMap<String, FilterType>,
+) -> bool {
+  let cell_filter_cache = cell_filter_cache.read();
+  filter_type_by_id
+    .iter()
+    .filter(|(_, filter_type)| filter_type.field_type == FieldType::DateTime)
+    .any(|(filter_id, _)| {
+      cell_filter_cache
+        .get::<DateFilterPB>(filter_id)
+        .map(|filter| filter.condition.is_relative())
+        .unwrap_or(false)
+    })
+}
+
+/// Returns the duration until the start of the next local day, the relative date filters compare
+/// the dates of the cells with the local date.
+pub(crate) fn duration_until_next_day() -> Duration {
+  let now = Local::now().naive_local();
+  (now.date() + chrono::Duration::days(1))
+    .and_hms_opt(0, 0, 0)
+    .and_then(|next_day| (next_day - now).to_std().ok())
+    .unwrap_or_else(|| Duration::from_secs(60))
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 enum FilterEvent {
   FilterDidChanged,
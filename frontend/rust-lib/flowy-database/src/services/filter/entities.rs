@@ -1,16 +1,49 @@
-use crate::entities::{
-  AlterFilterParams, DatabaseSettingChangesetParams, DeleteFilterParams, FieldType, InsertedRowPB,
+use crate::entities::{AlterFilterParams, DeleteFilterParams, FieldType, InsertedRowPB};
+use database_model::{
+  FieldRevision, FieldTypeRevision, FilterGroupRevision, FilterOperatorRevision, FilterRevision,
 };
-use database_model::{FieldRevision, FieldTypeRevision};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct FilterChangeset {
-  pub(crate) insert_filter: Option<FilterType>,
-  pub(crate) update_filter: Option<UpdatedFilterType>,
-  pub(crate) delete_filter: Option<FilterType>,
+  pub(crate) insert_filter: Option<FilterContext>,
+  pub(crate) update_filter: Option<FilterContext>,
+  pub(crate) update_field_type: Option<UpdatedFilterType>,
+  pub(crate) delete_filter: Option<FilterContext>,
 }
 
+/// Identifies a filter. A field can have more than one filter, so the filter is identified by its
+/// id, and the [FilterType] tells which field the filter applies to.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+pub struct FilterContext {
+  pub filter_id: String,
+  pub filter_type: FilterType,
+}
+
+impl FilterContext {
+  pub fn new(filter_id: &str, filter_type: FilterType) -> Self {
+    Self {
+      filter_id: filter_id.to_owned(),
+      filter_type,
+    }
+  }
+}
+
+impl std::convert::From<&FilterRevision> for FilterContext {
+  fn from(filter_rev: &FilterRevision) -> Self {
+    Self {
+      filter_id: filter_rev.id.clone(),
+      filter_type: FilterType {
+        field_id: filter_rev.field_id.clone(),
+        field_type: filter_rev.field_type.into(),
+      },
+    }
+  }
+}
+
+/// The field type of the field was changed. The filters of the old field type are no longer
+/// applied, and the filters of the new field type, if any, are applied instead.
 #[derive(Debug)]
 pub struct UpdatedFilterType {
   pub old: Option<FilterType>,
@@ -24,48 +57,39 @@ impl UpdatedFilterType {
 }
 
 impl FilterChangeset {
-  pub fn from_insert(filter_type: FilterType) -> Self {
+  pub fn from_insert(filter: FilterContext) -> Self {
     Self {
-      insert_filter: Some(filter_type),
+      insert_filter: Some(filter),
       update_filter: None,
+      update_field_type: None,
       delete_filter: None,
     }
   }
 
-  pub fn from_update(filter_type: UpdatedFilterType) -> Self {
+  pub fn from_update(filter: FilterContext) -> Self {
     Self {
       insert_filter: None,
-      update_filter: Some(filter_type),
+      update_filter: Some(filter),
+      update_field_type: None,
       delete_filter: None,
     }
   }
-  pub fn from_delete(filter_type: FilterType) -> Self {
+
+  pub fn from_update_field_type(filter_type: UpdatedFilterType) -> Self {
     Self {
       insert_filter: None,
       update_filter: None,
-      delete_filter: Some(filter_type),
+      update_field_type: Some(filter_type),
+      delete_filter: None,
     }
   }
-}
 
-impl std::convert::From<&DatabaseSettingChangesetParams> for FilterChangeset {
-  fn from(params: &DatabaseSettingChangesetParams) -> Self {
-    let insert_filter = params
-      .insert_filter
-      .as_ref()
-      .map(|insert_filter_params| FilterType {
-        field_id: insert_filter_params.field_id.clone(),
-        field_type: insert_filter_params.field_type.into(),
-      });
-
-    let delete_filter = params
-      .delete_filter
-      .as_ref()
-      .map(|delete_filter_params| delete_filter_params.filter_type.clone());
-    FilterChangeset {
-      insert_filter,
+  pub fn from_delete(filter: FilterContext) -> Self {
+    Self {
+      insert_filter: None,
       update_filter: None,
-      delete_filter,
+      update_field_type: None,
+      delete_filter: Some(filter),
     }
   }
 }
@@ -106,6 +130,60 @@ impl std::convert::From<&DeleteFilterParams> for FilterType {
   }
 }
 
+/// The [FilterGroupRevision] that only contains the filters that are applied to the fields. The
+/// filters of the deleted fields, or of the field types the fields are no longer using, are
+/// skipped. A field can have more than one filter, so the filters are looked up by their ids.
+#[derive(Clone, Debug, Default)]
+pub struct FilterGroup {
+  pub operator: FilterOperatorRevision,
+  pub filter_ids: Vec<String>,
+  pub groups: Vec<FilterGroup>,
+}
+
+impl FilterGroup {
+  pub fn new(group_rev: &FilterGroupRevision, filter_revs: &[Arc<FilterRevision>]) -> Self {
+    let filter_ids = group_rev
+      .filter_ids
+      .iter()
+      .filter(|filter_id| {
+        filter_revs
+          .iter()
+          .any(|filter_rev| &filter_rev.id == *filter_id)
+      })
+      .cloned()
+      .collect();
+    let groups = group_rev
+      .groups
+      .iter()
+      .map(|group_rev| FilterGroup::new(group_rev, filter_revs))
+      .collect();
+    Self {
+      operator: group_rev.operator,
+      filter_ids,
+      groups,
+    }
+  }
+
+  /// Combines the visibility of the filters in this group. Returns None if none of the filters
+  /// in this group was applied, so an empty group doesn't affect the visibility of the row.
+  pub(crate) fn evaluate(&self, visible_by_filter_id: &HashMap<String, bool>) -> Option<bool> {
+    let filter_results = self
+      .filter_ids
+      .iter()
+      .flat_map(|filter_id| visible_by_filter_id.get(filter_id).copied());
+    let group_results = self
+      .groups
+      .iter()
+      .flat_map(|group| group.evaluate(visible_by_filter_id));
+    let mut results = filter_results.chain(group_results).peekable();
+    results.peek()?;
+    match self.operator {
+      FilterOperatorRevision::And => Some(results.all(|is_visible| is_visible)),
+      FilterOperatorRevision::Or => Some(results.any(|is_visible| is_visible)),
+    }
+  }
+}
+
 #[derive(Clone, Debug)]
 pub struct FilterResultNotification {
   pub view_id: String,
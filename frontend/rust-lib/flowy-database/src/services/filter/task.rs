@@ -1,4 +1,4 @@
-use crate::services::filter::{FilterController, FilterType};
+use crate::services::filter::{FilterController, FilterGroup};
 use flowy_task::{TaskContent, TaskHandler};
 use lib_infra::future::BoxResultFuture;
 use std::collections::HashMap;
@@ -41,20 +41,26 @@ impl TaskHandler for FilterTaskHandler {
   }
 }
 /// Refresh the filter according to the field id.
-#[derive(Default)]
 pub(crate) struct FilterResult {
-  pub(crate) visible_by_filter_id: HashMap<FilterType, bool>,
+  pub(crate) visible_by_filter_id: HashMap<String, bool>,
+  /// The visibility of the row that combines the results of the filters by the filter groups.
+  pub(crate) is_visible: bool,
 }
 
-impl FilterResult {
-  pub(crate) fn is_visible(&self) -> bool {
-    let mut is_visible = true;
-    for visible in self.visible_by_filter_id.values() {
-      if !is_visible {
-        break;
-      }
-      is_visible = *visible;
+impl std::default::Default for FilterResult {
+  fn default() -> Self {
+    Self {
+      visible_by_filter_id: HashMap::new(),
+      is_visible: true,
     }
-    is_visible
+  }
+}
+
+impl FilterResult {
+  pub(crate) fn evaluate(&mut self, filter_group: &FilterGroup) -> bool {
+    self.is_visible = filter_group
+      .evaluate(&self.visible_by_filter_id)
+      .unwrap_or(true);
+    self.is_visible
   }
 }
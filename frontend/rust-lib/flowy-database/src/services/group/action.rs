@@ -103,6 +103,14 @@ pub trait GroupControllerActions: Send + Sync {
     field_rev: &FieldRevision,
   ) -> FlowyResult<DidMoveGroupRowResult>;
 
+  /// Insert the duplicated row right below the original row in every group that contains
+  /// the row with `from_row_id`
+  fn did_duplicate_row(
+    &mut self,
+    from_row_id: &str,
+    row_rev: &RowRevision,
+  ) -> Vec<GroupRowsNotificationPB>;
+
   /// Move the row from one group to another group
   fn move_group_row(&mut self, context: MoveGroupRowContext) -> FlowyResult<DidMoveGroupRowResult>;
 
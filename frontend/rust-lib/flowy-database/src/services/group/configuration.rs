@@ -371,6 +371,11 @@ where
     Ok(())
   }
 
+  /// Returns the json content of the group configuration, see [GroupConfigurationContentSerde]
+  pub(crate) fn get_setting_content(&self) -> String {
+    self.configuration.content.clone()
+  }
+
   pub(crate) async fn get_all_cells(&self) -> Vec<RowSingleCellData> {
     self
       .reader
@@ -315,6 +315,25 @@ where
     Ok(result)
   }
 
+  fn did_duplicate_row(
+    &mut self,
+    from_row_id: &str,
+    row_rev: &RowRevision,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    let row_pb = RowPB::from(row_rev);
+    self.group_ctx.iter_mut_groups(|group| {
+      if let Some(index) = group.insert_row_below(from_row_id, row_pb.clone()) {
+        let inserted_row = InsertedRowPB::with_index(row_pb.clone(), index as i32);
+        changesets.push(GroupRowsNotificationPB::insert(
+          group.id.clone(),
+          vec![inserted_row],
+        ));
+      }
+    });
+    changesets
+  }
+
   #[tracing::instrument(level = "trace", skip_all, err)]
   fn move_group_row(&mut self, context: MoveGroupRowContext) -> FlowyResult<DidMoveGroupRowResult> {
     let mut result = DidMoveGroupRowResult {
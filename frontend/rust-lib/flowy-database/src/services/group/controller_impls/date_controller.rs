@@ -0,0 +1,442 @@
+use crate::entities::{GroupPB, GroupRowsNotificationPB, InsertedGroupPB, InsertedRowPB, RowPB};
+use crate::services::cell::insert_date_cell;
+use crate::services::field::{DateCellDataPB, DateCellDataParser, DateTypeOptionPB};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupContext;
+use crate::services::group::controller::{
+  GenericGroupController, GroupController, GroupGenerator, MoveGroupRowContext,
+};
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroupConfig, GeneratedGroupContext,
+};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime};
+use database_model::{
+  DateCondition, DateGroupConfigurationRevision, FieldRevision, GroupConfigurationContentSerde,
+  GroupRevision, RowRevision,
+};
+use flowy_error::FlowyResult;
+
+const TODAY: &str = "today";
+const YESTERDAY: &str = "yesterday";
+const TOMORROW: &str = "tomorrow";
+const LAST_7_DAYS: &str = "last_7_days";
+const NEXT_7_DAYS: &str = "next_7_days";
+const LAST_30_DAYS: &str = "last_30_days";
+const NEXT_30_DAYS: &str = "next_30_days";
+
+pub type DateGroupController = GenericGroupController<
+  DateGroupConfigurationRevision,
+  DateTypeOptionPB,
+  DateGroupGenerator,
+  DateCellDataParser,
+>;
+
+pub type DateGroupContext = GroupContext<DateGroupConfigurationRevision>;
+
+impl DateGroupController {
+  fn date_condition(&self) -> DateCondition {
+    date_condition_from_context(&self.group_ctx)
+  }
+
+  /// Returns the id of the group that the cell belongs to, or None if the cell is empty.
+  fn group_id_from_cell_data(&self, cell_data: &DateCellDataPB) -> Option<String> {
+    if cell_data.date.is_empty() {
+      return None;
+    }
+    let group_rev =
+      make_group_from_timestamp(cell_data.timestamp, &self.date_condition(), today())?;
+    Some(group_rev.id)
+  }
+}
+
+impl GroupCustomize for DateGroupController {
+  type CellData = DateCellDataPB;
+
+  fn can_group(&self, content: &str, cell_data: &Self::CellData) -> bool {
+    match self.group_id_from_cell_data(cell_data) {
+      None => false,
+      Some(group_id) => group_id == content,
+    }
+  }
+
+  fn create_or_delete_group_when_cell_changed(
+    &mut self,
+    row_rev: &RowRevision,
+    old_cell_data: Option<&Self::CellData>,
+    cell_data: &Self::CellData,
+  ) -> FlowyResult<(Option<InsertedGroupPB>, Option<GroupPB>)> {
+    // Create the group of the date if it doesn't exist
+    let mut inserted_group = None;
+    if !cell_data.date.is_empty() {
+      let group_rev =
+        make_group_from_timestamp(cell_data.timestamp, &self.date_condition(), today());
+      if let Some(group_rev) = group_rev {
+        if self.group_ctx.get_group(&group_rev.id).is_none() {
+          let mut new_group = self.group_ctx.add_new_group(group_rev)?;
+          new_group.group.rows.push(RowPB::from(row_rev));
+          inserted_group = Some(new_group);
+        }
+      }
+    }
+
+    // Delete the old date group if this row is the last row in that group. The row might be
+    // removed from the old group already if it was moved by dragging.
+    let new_group_id = self.group_id_from_cell_data(cell_data);
+    let deleted_group = match old_cell_data
+      .and_then(|old_cell_data| self.group_id_from_cell_data(old_cell_data))
+      .filter(|old_group_id| Some(old_group_id) != new_group_id.as_ref())
+      .and_then(|old_group_id| self.group_ctx.get_group(&old_group_id))
+    {
+      Some((_, group)) if group.rows.len() == 1 && group.contains_row(&row_rev.id) => {
+        Some(group.clone())
+      },
+      _ => None,
+    };
+
+    let deleted_group = match deleted_group {
+      None => None,
+      Some(group) => {
+        self.group_ctx.delete_group(&group.id)?;
+        Some(GroupPB::from(group))
+      },
+    };
+
+    Ok((inserted_group, deleted_group))
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row_rev: &RowRevision,
+    cell_data: &Self::CellData,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    let group_id = self.group_id_from_cell_data(cell_data);
+    self.group_ctx.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if Some(&group.id) == group_id.as_ref() {
+        if !group.contains_row(&row_rev.id) {
+          let row_pb = RowPB::from(row_rev);
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(row_pb.clone()));
+          group.add_row(row_pb);
+        }
+      } else if group.contains_row(&row_rev.id) {
+        changeset.deleted_rows.push(row_rev.id.clone());
+        group.remove_row(&row_rev.id);
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row_rev: &RowRevision,
+    _cell_data: &Self::CellData,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    self.group_ctx.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row_rev.id) {
+        changeset.deleted_rows.push(row_rev.id.clone());
+        group.remove_row(&row_rev.id);
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  /// Moving the row to another group rewrites the timestamp of the cell to the first day of
+  /// the group. See [timestamp_from_date_group] for more details.
+  fn move_row(
+    &mut self,
+    _cell_data: &Self::CellData,
+    mut context: MoveGroupRowContext,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut group_changeset = vec![];
+    self.group_ctx.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  fn delete_group_when_move_row(
+    &mut self,
+    _row_rev: &RowRevision,
+    cell_data: &Self::CellData,
+  ) -> Option<GroupPB> {
+    let group_id = self.group_id_from_cell_data(cell_data)?;
+    let mut deleted_group = None;
+    if let Some((_, group)) = self.group_ctx.get_group(&group_id) {
+      if group.rows.len() == 1 {
+        deleted_group = Some(GroupPB::from(group.clone()));
+      }
+    }
+    if deleted_group.is_some() {
+      let _ = self
+        .group_ctx
+        .delete_group(&deleted_group.as_ref().unwrap().group_id);
+    }
+    deleted_group
+  }
+}
+
+impl GroupController for DateGroupController {
+  fn will_create_row(
+    &mut self,
+    row_rev: &mut RowRevision,
+    field_rev: &FieldRevision,
+    group_id: &str,
+  ) {
+    match self.group_ctx.get_group(group_id) {
+      None => tracing::warn!("Can not find the group: {}", group_id),
+      Some((_, group)) => match timestamp_from_date_group(&group.id) {
+        None => tracing::warn!("Invalid date group id: {}", group.id),
+        Some(timestamp) => {
+          let cell_rev = insert_date_cell(timestamp, field_rev);
+          row_rev.cells.insert(field_rev.id.clone(), cell_rev);
+        },
+      },
+    }
+  }
+
+  fn did_create_row(&mut self, row_pb: &RowPB, group_id: &str) {
+    if let Some(group) = self.group_ctx.get_mut_group(group_id) {
+      group.add_row(row_pb.clone())
+    }
+  }
+}
+
+pub struct DateGroupGenerator();
+impl GroupGenerator for DateGroupGenerator {
+  type Context = DateGroupContext;
+  type TypeOptionType = DateTypeOptionPB;
+
+  fn generate_groups(
+    field_rev: &FieldRevision,
+    group_ctx: &Self::Context,
+    _type_option: &Option<Self::TypeOptionType>,
+  ) -> GeneratedGroupContext {
+    // Read all the cells for the grouping field
+    let cells = futures::executor::block_on(group_ctx.get_all_cells());
+
+    // Generate the groups in chronological order, each group is generated once.
+    let condition = date_condition_from_context(group_ctx);
+    let today = today();
+    let mut timestamps = cells
+      .into_iter()
+      .flat_map(|value| value.into_date_field_cell_data())
+      .flat_map(|cell_data| cell_data.0)
+      .filter(|timestamp| *timestamp != 0)
+      .collect::<Vec<i64>>();
+    timestamps.sort_unstable();
+
+    let mut group_configs: Vec<GeneratedGroupConfig> = vec![];
+    for timestamp in timestamps {
+      if let Some(group_rev) = make_group_from_timestamp(timestamp, &condition, today) {
+        if group_configs
+          .iter()
+          .all(|config| config.group_rev.id != group_rev.id)
+        {
+          group_configs.push(GeneratedGroupConfig {
+            filter_content: group_rev.id.clone(),
+            group_rev,
+          });
+        }
+      }
+    }
+
+    let no_status_group = Some(make_no_status_group(field_rev));
+    GeneratedGroupContext {
+      no_status_group,
+      group_configs,
+    }
+  }
+}
+
+fn date_condition_from_context(group_ctx: &DateGroupContext) -> DateCondition {
+  DateGroupConfigurationRevision::from_json(&group_ctx.get_setting_content())
+    .unwrap_or_default()
+    .condition
+}
+
+/// The relative groups compare the dates of the cells with the local date, the same as the
+/// relative date filters.
+fn today() -> NaiveDate {
+  Local::now().date_naive()
+}
+
+/// Returns the group that the timestamp belongs to.
+///
+/// The [DateCondition::Relative] groups the dates near today into the today, yesterday,
+/// tomorrow, last/next 7 days and last/next 30 days groups, the other dates are grouped
+/// by month.
+///
+/// # Arguments
+///
+/// * `timestamp`: the timestamp of the date cell, in seconds
+/// * `condition`: the date condition of the group configuration
+/// * `today`: the date used to calculate the relative groups
+///
+fn make_group_from_timestamp(
+  timestamp: i64,
+  condition: &DateCondition,
+  today: NaiveDate,
+) -> Option<GroupRevision> {
+  let date = NaiveDateTime::from_timestamp_opt(timestamp, 0)?.date();
+  let (group_id, group_name) = match condition {
+    DateCondition::Relative => {
+      let days = (date - today).num_days();
+      match days {
+        0 => (TODAY.to_owned(), "Today".to_owned()),
+        -1 => (YESTERDAY.to_owned(), "Yesterday".to_owned()),
+        1 => (TOMORROW.to_owned(), "Tomorrow".to_owned()),
+        -7..=-2 => (LAST_7_DAYS.to_owned(), "Last 7 days".to_owned()),
+        2..=7 => (NEXT_7_DAYS.to_owned(), "Next 7 days".to_owned()),
+        -30..=-8 => (LAST_30_DAYS.to_owned(), "Last 30 days".to_owned()),
+        8..=30 => (NEXT_30_DAYS.to_owned(), "Next 30 days".to_owned()),
+        _ => month_group(date),
+      }
+    },
+    DateCondition::Day => (
+      date.format("%Y/%m/%d").to_string(),
+      date.format("%b %d, %Y").to_string(),
+    ),
+    DateCondition::Week => {
+      let first_day = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+      (
+        first_day.format("%Y/%m/%d").to_string(),
+        format!("Week of {}", first_day.format("%b %d, %Y")),
+      )
+    },
+    DateCondition::Month => month_group(date),
+    DateCondition::Year => (date.format("%Y").to_string(), date.format("%Y").to_string()),
+  };
+  Some(GroupRevision::new(group_id, group_name))
+}
+
+fn month_group(date: NaiveDate) -> (String, String) {
+  (
+    date.format("%Y/%m").to_string(),
+    date.format("%b %Y").to_string(),
+  )
+}
+
+/// Returns the timestamp of the first day of the group, it's used to rewrite the date cell
+/// when the row is moved or created in the group. The relative groups take the day that is
+/// nearest to today but not covered by the narrower groups. For example, the
+/// `next_7_days` group takes the day after tomorrow.
+fn timestamp_from_date_group_id(group_id: &str, today: NaiveDate) -> Option<i64> {
+  let date = match group_id {
+    TODAY => today,
+    YESTERDAY => today - Duration::days(1),
+    TOMORROW => today + Duration::days(1),
+    LAST_7_DAYS => today - Duration::days(2),
+    NEXT_7_DAYS => today + Duration::days(2),
+    LAST_30_DAYS => today - Duration::days(8),
+    NEXT_30_DAYS => today + Duration::days(8),
+    _ => NaiveDate::parse_from_str(group_id, "%Y/%m/%d")
+      .or_else(|_| NaiveDate::parse_from_str(&format!("{}/01", group_id), "%Y/%m/%d"))
+      .or_else(|_| NaiveDate::parse_from_str(&format!("{}/01/01", group_id), "%Y/%m/%d"))
+      .ok()?,
+  };
+  Some(date.and_hms_opt(0, 0, 0)?.timestamp())
+}
+
+/// Returns the timestamp of the first day of the group with today's date.
+pub fn timestamp_from_date_group(group_id: &str) -> Option<i64> {
+  timestamp_from_date_group_id(group_id, today())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::group::controller_impls::date_controller::{
+    make_group_from_timestamp, timestamp_from_date_group_id,
+  };
+  use chrono::NaiveDate;
+  use database_model::DateCondition;
+
+  // 1647251762 => Mar 14, 2022 (Monday)
+  // 1647475200 => Mar 17, 2022 00:00:00
+  const TIMESTAMP: i64 = 1647251762;
+
+  #[test]
+  fn date_group_relative_test() {
+    let test = |today: NaiveDate, expected: &str| {
+      let group_rev = make_group_from_timestamp(TIMESTAMP, &DateCondition::Relative, today);
+      assert_eq!(group_rev.unwrap().id, expected);
+    };
+    test(NaiveDate::from_ymd_opt(2022, 3, 14).unwrap(), "today");
+    test(NaiveDate::from_ymd_opt(2022, 3, 15).unwrap(), "yesterday");
+    test(NaiveDate::from_ymd_opt(2022, 3, 13).unwrap(), "tomorrow");
+    test(NaiveDate::from_ymd_opt(2022, 3, 20).unwrap(), "last_7_days");
+    test(NaiveDate::from_ymd_opt(2022, 3, 10).unwrap(), "next_7_days");
+    test(
+      NaiveDate::from_ymd_opt(2022, 4, 10).unwrap(),
+      "last_30_days",
+    );
+    test(NaiveDate::from_ymd_opt(2022, 3, 1).unwrap(), "next_30_days");
+    test(NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(), "2022/03");
+  }
+
+  #[test]
+  fn date_group_condition_test() {
+    let today = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    let test = |condition: DateCondition, expected_id: &str, expected_name: &str| {
+      let group_rev = make_group_from_timestamp(1647475200, &condition, today).unwrap();
+      assert_eq!(group_rev.id, expected_id);
+      assert_eq!(group_rev.name, expected_name);
+    };
+    test(DateCondition::Day, "2022/03/17", "Mar 17, 2022");
+    test(DateCondition::Week, "2022/03/14", "Week of Mar 14, 2022");
+    test(DateCondition::Month, "2022/03", "Mar 2022");
+    test(DateCondition::Year, "2022", "2022");
+  }
+
+  #[test]
+  fn date_group_timestamp_test() {
+    let today = NaiveDate::from_ymd_opt(2022, 3, 17).unwrap();
+    assert_eq!(
+      timestamp_from_date_group_id("2022/03/17", today),
+      Some(1647475200)
+    );
+    assert_eq!(
+      timestamp_from_date_group_id("today", today),
+      Some(1647475200)
+    );
+    assert_eq!(
+      timestamp_from_date_group_id("2022/03", today),
+      Some(1646092800)
+    );
+    assert_eq!(
+      timestamp_from_date_group_id("2022", today),
+      Some(1640995200)
+    );
+    assert_eq!(timestamp_from_date_group_id("unknown", today), None);
+
+    // The rewritten timestamp belongs to the same group
+    for condition in [
+      DateCondition::Relative,
+      DateCondition::Day,
+      DateCondition::Week,
+      DateCondition::Month,
+      DateCondition::Year,
+    ] {
+      let group_id = make_group_from_timestamp(TIMESTAMP, &condition, today)
+        .unwrap()
+        .id;
+      let timestamp = timestamp_from_date_group_id(&group_id, today).unwrap();
+      let group_rev = make_group_from_timestamp(timestamp, &condition, today).unwrap();
+      assert_eq!(group_rev.id, group_id);
+    }
+  }
+}
@@ -1,4 +1,4 @@
-use crate::entities::{GroupChangesetPB, RowPB};
+use crate::entities::{GroupChangesetPB, GroupRowsNotificationPB, InsertedRowPB, RowPB};
 use crate::services::group::action::{
   DidMoveGroupRowResult, DidUpdateGroupRowResult, GroupControllerActions,
 };
@@ -85,6 +85,24 @@ impl GroupControllerActions for DefaultGroupController {
     })
   }
 
+  fn did_duplicate_row(
+    &mut self,
+    from_row_id: &str,
+    row_rev: &RowRevision,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let row_pb = RowPB::from(row_rev);
+    match self.group.insert_row_below(from_row_id, row_pb.clone()) {
+      None => vec![],
+      Some(index) => {
+        let inserted_row = InsertedRowPB::with_index(row_pb, index as i32);
+        vec![GroupRowsNotificationPB::insert(
+          self.group.id.clone(),
+          vec![inserted_row],
+        )]
+      },
+    }
+  }
+
   fn move_group_row(
     &mut self,
     _context: MoveGroupRowContext,
@@ -0,0 +1,459 @@
+use crate::entities::{GroupPB, GroupRowsNotificationPB, InsertedGroupPB, InsertedRowPB, RowPB};
+use crate::services::cell::insert_decimal_cell;
+use crate::services::field::{
+  NumberCellData, NumberFormat, NumberTypeOptionPB, TextCellData, TextCellDataParser,
+};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupContext;
+use crate::services::group::controller::{
+  GenericGroupController, GroupController, GroupGenerator, MoveGroupRowContext,
+};
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroupConfig, GeneratedGroupContext,
+};
+use database_model::{
+  FieldRevision, GroupConfigurationContentSerde, GroupRevision, NumberGroupCondition,
+  NumberGroupConfigurationRevision, RowRevision,
+};
+use flowy_error::FlowyResult;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+pub type NumberGroupController = GenericGroupController<
+  NumberGroupConfigurationRevision,
+  NumberTypeOptionPB,
+  NumberGroupGenerator,
+  TextCellDataParser,
+>;
+
+pub type NumberGroupContext = GroupContext<NumberGroupConfigurationRevision>;
+
+impl NumberGroupController {
+  fn number_ranges(&self) -> NumberRanges {
+    NumberRanges::from_context(&self.group_ctx)
+  }
+
+  /// Returns the group that the cell belongs to, or None if the cell is empty.
+  fn group_from_cell_data(&self, cell_data: &TextCellData) -> Option<GroupRevision> {
+    let number = parse_number(&cell_data.0, &self.type_option)?;
+    self.number_ranges().make_group(number, &self.type_option)
+  }
+
+  fn group_id_from_cell_data(&self, cell_data: &TextCellData) -> Option<String> {
+    self
+      .group_from_cell_data(cell_data)
+      .map(|group_rev| group_rev.id)
+  }
+
+  /// The groups of the breakpoints are generated up front, they are not deleted when they
+  /// become empty unless the configuration hides the empty groups.
+  fn keep_empty_groups(&self) -> bool {
+    self.number_ranges().keep_empty_groups
+  }
+}
+
+impl GroupCustomize for NumberGroupController {
+  type CellData = TextCellData;
+
+  fn can_group(&self, content: &str, cell_data: &Self::CellData) -> bool {
+    match self.group_id_from_cell_data(cell_data) {
+      None => false,
+      Some(group_id) => group_id == content,
+    }
+  }
+
+  fn create_or_delete_group_when_cell_changed(
+    &mut self,
+    row_rev: &RowRevision,
+    old_cell_data: Option<&Self::CellData>,
+    cell_data: &Self::CellData,
+  ) -> FlowyResult<(Option<InsertedGroupPB>, Option<GroupPB>)> {
+    // Create the group of the number if it doesn't exist
+    let mut inserted_group = None;
+    let new_group_rev = self.group_from_cell_data(cell_data);
+    let new_group_id = new_group_rev.as_ref().map(|group_rev| group_rev.id.clone());
+    if let Some(group_rev) = new_group_rev {
+      if self.group_ctx.get_group(&group_rev.id).is_none() {
+        let mut new_group = self.group_ctx.add_new_group(group_rev)?;
+        new_group.group.rows.push(RowPB::from(row_rev));
+        inserted_group = Some(new_group);
+      }
+    }
+
+    // Delete the old number group if this row is the last row in that group. The row might be
+    // removed from the old group already if it was moved by dragging.
+    if self.keep_empty_groups() {
+      return Ok((inserted_group, None));
+    }
+    let deleted_group = match old_cell_data
+      .and_then(|old_cell_data| self.group_id_from_cell_data(old_cell_data))
+      .filter(|old_group_id| Some(old_group_id) != new_group_id.as_ref())
+      .and_then(|old_group_id| self.group_ctx.get_group(&old_group_id))
+    {
+      Some((_, group)) if group.rows.len() == 1 && group.contains_row(&row_rev.id) => {
+        Some(group.clone())
+      },
+      _ => None,
+    };
+
+    let deleted_group = match deleted_group {
+      None => None,
+      Some(group) => {
+        self.group_ctx.delete_group(&group.id)?;
+        Some(GroupPB::from(group))
+      },
+    };
+
+    Ok((inserted_group, deleted_group))
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row_rev: &RowRevision,
+    cell_data: &Self::CellData,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    let group_id = self.group_id_from_cell_data(cell_data);
+    self.group_ctx.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if Some(&group.id) == group_id.as_ref() {
+        if !group.contains_row(&row_rev.id) {
+          let row_pb = RowPB::from(row_rev);
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(row_pb.clone()));
+          group.add_row(row_pb);
+        }
+      } else if group.contains_row(&row_rev.id) {
+        changeset.deleted_rows.push(row_rev.id.clone());
+        group.remove_row(&row_rev.id);
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row_rev: &RowRevision,
+    _cell_data: &Self::CellData,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    self.group_ctx.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row_rev.id) {
+        changeset.deleted_rows.push(row_rev.id.clone());
+        group.remove_row(&row_rev.id);
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  /// Moving the row to another group sets the number of the cell to the lower bound of the
+  /// group, the id of the group is its lower bound.
+  fn move_row(
+    &mut self,
+    _cell_data: &Self::CellData,
+    mut context: MoveGroupRowContext,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut group_changeset = vec![];
+    self.group_ctx.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  fn delete_group_when_move_row(
+    &mut self,
+    _row_rev: &RowRevision,
+    cell_data: &Self::CellData,
+  ) -> Option<GroupPB> {
+    if self.keep_empty_groups() {
+      return None;
+    }
+    let group_id = self.group_id_from_cell_data(cell_data)?;
+    let mut deleted_group = None;
+    if let Some((_, group)) = self.group_ctx.get_group(&group_id) {
+      if group.rows.len() == 1 {
+        deleted_group = Some(GroupPB::from(group.clone()));
+      }
+    }
+    if deleted_group.is_some() {
+      let _ = self
+        .group_ctx
+        .delete_group(&deleted_group.as_ref().unwrap().group_id);
+    }
+    deleted_group
+  }
+}
+
+impl GroupController for NumberGroupController {
+  fn will_create_row(
+    &mut self,
+    row_rev: &mut RowRevision,
+    field_rev: &FieldRevision,
+    group_id: &str,
+  ) {
+    match self.group_ctx.get_group(group_id) {
+      None => tracing::warn!("Can not find the group: {}", group_id),
+      Some((_, group)) => match Decimal::from_str(&group.id) {
+        Err(_) => tracing::warn!("Invalid number group id: {}", group.id),
+        Ok(number) => {
+          let cell_rev = insert_decimal_cell(number, field_rev);
+          row_rev.cells.insert(field_rev.id.clone(), cell_rev);
+        },
+      },
+    }
+  }
+
+  fn did_create_row(&mut self, row_pb: &RowPB, group_id: &str) {
+    if let Some(group) = self.group_ctx.get_mut_group(group_id) {
+      group.add_row(row_pb.clone())
+    }
+  }
+}
+
+pub struct NumberGroupGenerator();
+impl GroupGenerator for NumberGroupGenerator {
+  type Context = NumberGroupContext;
+  type TypeOptionType = NumberTypeOptionPB;
+
+  fn generate_groups(
+    field_rev: &FieldRevision,
+    group_ctx: &Self::Context,
+    type_option: &Option<Self::TypeOptionType>,
+  ) -> GeneratedGroupContext {
+    let number_ranges = NumberRanges::from_context(group_ctx);
+
+    // Read all the cells for the grouping field
+    let cells = futures::executor::block_on(group_ctx.get_all_cells());
+    let mut numbers = cells
+      .into_iter()
+      .flat_map(|value| value.into_number_field_cell_data())
+      .flat_map(|cell_data| parse_number(&cell_data, type_option))
+      .collect::<Vec<Decimal>>();
+
+    // The groups of all the breakpoints are generated if the empty groups are kept.
+    if number_ranges.keep_empty_groups {
+      if let NumberGroupRule::Breakpoint(breakpoints) = &number_ranges.rule {
+        numbers.extend(breakpoints.iter().cloned());
+      }
+    }
+    numbers.sort();
+
+    // Generate the groups in ascending order, each group is generated once.
+    let mut group_configs: Vec<GeneratedGroupConfig> = vec![];
+    for number in numbers {
+      if let Some(group_rev) = number_ranges.make_group(number, type_option) {
+        if group_configs
+          .iter()
+          .all(|config| config.group_rev.id != group_rev.id)
+        {
+          group_configs.push(GeneratedGroupConfig {
+            filter_content: group_rev.id.clone(),
+            group_rev,
+          });
+        }
+      }
+    }
+
+    let no_status_group = Some(make_no_status_group(field_rev));
+    GeneratedGroupContext {
+      no_status_group,
+      group_configs,
+    }
+  }
+}
+
+fn parse_number(s: &str, type_option: &Option<NumberTypeOptionPB>) -> Option<Decimal> {
+  let cell_data = match type_option {
+    None => NumberCellData::from_format_str(s, true, &NumberFormat::Num),
+    Some(type_option) => type_option.format_cell_data(s),
+  };
+  cell_data.ok().and_then(|cell_data| *cell_data.decimal())
+}
+
+fn format_number(number: Decimal, type_option: &Option<NumberTypeOptionPB>) -> String {
+  match type_option {
+    None => number.normalize().to_string(),
+    Some(type_option) => type_option.format_number(number),
+  }
+}
+
+enum NumberGroupRule {
+  Range(Decimal),
+  /// The breakpoints are sorted in ascending order
+  Breakpoint(Vec<Decimal>),
+  Distinct,
+}
+
+/// Splits the numbers into the groups by the [NumberGroupConfigurationRevision]. The id of each
+/// group is the lower bound of the range, it's the number itself in the distinct mode.
+struct NumberRanges {
+  rule: NumberGroupRule,
+  keep_empty_groups: bool,
+}
+
+impl NumberRanges {
+  fn from_context(group_ctx: &NumberGroupContext) -> Self {
+    let configuration =
+      NumberGroupConfigurationRevision::from_json(&group_ctx.get_setting_content())
+        .unwrap_or_default();
+    Self::from_configuration(&configuration)
+  }
+
+  fn from_configuration(configuration: &NumberGroupConfigurationRevision) -> Self {
+    let rule = match configuration.condition {
+      NumberGroupCondition::Range => match Decimal::from_str(&configuration.interval) {
+        Ok(interval) if interval > Decimal::ZERO => NumberGroupRule::Range(interval),
+        _ => {
+          tracing::warn!("Invalid number group interval: {}", configuration.interval);
+          NumberGroupRule::Distinct
+        },
+      },
+      NumberGroupCondition::Breakpoint => {
+        let mut breakpoints = configuration
+          .breakpoints
+          .iter()
+          .flat_map(|breakpoint| Decimal::from_str(breakpoint).ok())
+          .collect::<Vec<Decimal>>();
+        breakpoints.sort();
+        breakpoints.dedup();
+        if breakpoints.is_empty() {
+          NumberGroupRule::Distinct
+        } else {
+          NumberGroupRule::Breakpoint(breakpoints)
+        }
+      },
+      NumberGroupCondition::Distinct => NumberGroupRule::Distinct,
+    };
+    let keep_empty_groups =
+      matches!(rule, NumberGroupRule::Breakpoint(_)) && !configuration.hide_empty;
+    Self {
+      rule,
+      keep_empty_groups,
+    }
+  }
+
+  /// Returns the group that the number belongs to.
+  ///
+  /// The breakpoints split the numbers into the ranges: `< b2`, `b2 - b3`, ..., `≥ bn`. The
+  /// first range takes the first breakpoint as its lower bound, so the numbers that are less
+  /// than the first breakpoint are in the first range.
+  fn make_group(
+    &self,
+    number: Decimal,
+    type_option: &Option<NumberTypeOptionPB>,
+  ) -> Option<GroupRevision> {
+    let format = |number: Decimal| format_number(number, type_option);
+    let (lower, name) = match &self.rule {
+      NumberGroupRule::Range(interval) => {
+        let lower = number
+          .checked_div(*interval)?
+          .floor()
+          .checked_mul(*interval)?;
+        let upper = lower.checked_add(*interval)?;
+        (lower, format!("{} - {}", format(lower), format(upper)))
+      },
+      NumberGroupRule::Breakpoint(breakpoints) => {
+        let index = breakpoints
+          .iter()
+          .rposition(|breakpoint| breakpoint <= &number)
+          .unwrap_or(0);
+        let lower = breakpoints[index];
+        let name = match (index, breakpoints.get(index + 1)) {
+          (0, Some(upper)) => format!("< {}", format(*upper)),
+          (0, None) => format(lower),
+          (_, Some(upper)) => format!("{} - {}", format(lower), format(*upper)),
+          (_, None) => format!("≥ {}", format(lower)),
+        };
+        (lower, name)
+      },
+      NumberGroupRule::Distinct => (number, format(number)),
+    };
+    Some(GroupRevision::new(lower.normalize().to_string(), name))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::field::{NumberFormat, NumberTypeOptionPB};
+  use crate::services::group::controller_impls::number_controller::NumberRanges;
+  use database_model::{NumberGroupCondition, NumberGroupConfigurationRevision};
+  use rust_decimal::Decimal;
+  use std::str::FromStr;
+
+  fn assert_group(
+    configuration: &NumberGroupConfigurationRevision,
+    type_option: &Option<NumberTypeOptionPB>,
+    number: &str,
+    expected_id: &str,
+    expected_name: &str,
+  ) {
+    let ranges = NumberRanges::from_configuration(configuration);
+    let number = Decimal::from_str(number).unwrap();
+    let group_rev = ranges.make_group(number, type_option).unwrap();
+    assert_eq!(group_rev.id, expected_id);
+    assert_eq!(group_rev.name, expected_name);
+  }
+
+  #[test]
+  fn number_group_range_test() {
+    let configuration = NumberGroupConfigurationRevision::default();
+    assert_group(&configuration, &None, "0", "0", "0 - 10");
+    assert_group(&configuration, &None, "9.99", "0", "0 - 10");
+    assert_group(&configuration, &None, "10", "10", "10 - 20");
+    assert_group(&configuration, &None, "-1", "-10", "-10 - 0");
+
+    let configuration = NumberGroupConfigurationRevision {
+      interval: "2.5".to_owned(),
+      ..Default::default()
+    };
+    assert_group(&configuration, &None, "6", "5", "5 - 7.5");
+  }
+
+  #[test]
+  fn number_group_breakpoint_test() {
+    let configuration = NumberGroupConfigurationRevision {
+      condition: NumberGroupCondition::Breakpoint,
+      breakpoints: vec!["100".to_owned(), "0".to_owned(), "10".to_owned()],
+      ..Default::default()
+    };
+    assert_group(&configuration, &None, "-5", "0", "< 10");
+    assert_group(&configuration, &None, "5", "0", "< 10");
+    assert_group(&configuration, &None, "10", "10", "10 - 100");
+    assert_group(&configuration, &None, "1000", "100", "≥ 100");
+  }
+
+  #[test]
+  fn number_group_distinct_test() {
+    let configuration = NumberGroupConfigurationRevision {
+      condition: NumberGroupCondition::Distinct,
+      ..Default::default()
+    };
+    assert_group(&configuration, &None, "1.50", "1.5", "1.5");
+  }
+
+  #[test]
+  fn number_group_format_test() {
+    let mut type_option = NumberTypeOptionPB::default();
+    type_option.set_format(NumberFormat::USD);
+    let configuration = NumberGroupConfigurationRevision::default();
+    assert_group(
+      &configuration,
+      &Some(type_option),
+      "18",
+      "10",
+      "$10.00 - $20.00",
+    );
+  }
+}
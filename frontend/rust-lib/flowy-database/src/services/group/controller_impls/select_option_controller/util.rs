@@ -1,12 +1,17 @@
 use crate::entities::{FieldType, GroupRowsNotificationPB, InsertedRowPB, RowPB};
-use crate::services::cell::{insert_checkbox_cell, insert_select_option_cell, insert_url_cell};
+use crate::services::cell::{
+  insert_checkbox_cell, insert_date_cell, insert_decimal_cell, insert_select_option_cell,
+  insert_url_cell,
+};
 use crate::services::field::{SelectOptionCellDataPB, SelectOptionPB, CHECK};
 use crate::services::group::configuration::GroupContext;
 use crate::services::group::controller::MoveGroupRowContext;
-use crate::services::group::{GeneratedGroupConfig, Group};
+use crate::services::group::{timestamp_from_date_group, GeneratedGroupConfig, Group};
 use database_model::{
   CellRevision, FieldRevision, GroupRevision, RowRevision, SelectOptionGroupConfigurationRevision,
 };
+use rust_decimal::Decimal;
+use std::str::FromStr;
 
 pub type SelectOptionGroupContext = GroupContext<SelectOptionGroupConfigurationRevision>;
 
@@ -156,6 +161,17 @@ pub fn make_inserted_cell_rev(group_id: &str, field_rev: &FieldRevision) -> Opti
       let cell_rev = insert_url_cell(group_id.to_owned(), field_rev);
       Some(cell_rev)
     },
+    FieldType::Number => {
+      // The id of the number group is the lower bound of the group
+      let number = Decimal::from_str(group_id).ok()?;
+      let cell_rev = insert_decimal_cell(number, field_rev);
+      Some(cell_rev)
+    },
+    FieldType::DateTime => {
+      let timestamp = timestamp_from_date_group(group_id)?;
+      let cell_rev = insert_date_cell(timestamp, field_rev);
+      Some(cell_rev)
+    },
     _ => {
       tracing::warn!("Unknown field type: {:?}", field_type);
       None
@@ -61,6 +61,17 @@ impl Group {
     }
   }
 
+  /// Inserts the row right below the row with `row_id`. Returns the index of the inserted row,
+  /// or None if the group doesn't contain the row with `row_id`.
+  pub fn insert_row_below(&mut self, row_id: &str, row_pb: RowPB) -> Option<usize> {
+    if self.contains_row(&row_pb.id) {
+      return None;
+    }
+    let index = self.index_of_row(row_id)? + 1;
+    self.rows.insert(index, row_pb);
+    Some(index)
+  }
+
   pub fn index_of_row(&self, row_id: &str) -> Option<usize> {
     self.rows.iter().position(|row| row.id == row_id)
   }
@@ -2,9 +2,10 @@ use crate::entities::FieldType;
 use crate::services::group::configuration::GroupConfigurationReader;
 use crate::services::group::controller::GroupController;
 use crate::services::group::{
-  CheckboxGroupContext, CheckboxGroupController, DefaultGroupController, GroupConfigurationWriter,
-  MultiSelectGroupController, SelectOptionGroupContext, SingleSelectGroupController,
-  URLGroupContext, URLGroupController,
+  CheckboxGroupContext, CheckboxGroupController, DateGroupContext, DateGroupController,
+  DefaultGroupController, GroupConfigurationWriter, MultiSelectGroupController, NumberGroupContext,
+  NumberGroupController, SelectOptionGroupContext, SingleSelectGroupController, URLGroupContext,
+  URLGroupController,
 };
 use database_model::{
   CheckboxGroupConfigurationRevision, DateGroupConfigurationRevision, FieldRevision,
@@ -89,6 +90,28 @@ where
       let controller = URLGroupController::new(&field_rev, configuration).await?;
       group_controller = Box::new(controller);
     },
+    FieldType::Number => {
+      let configuration = NumberGroupContext::new(
+        view_id,
+        field_rev.clone(),
+        configuration_reader,
+        configuration_writer,
+      )
+      .await?;
+      let controller = NumberGroupController::new(&field_rev, configuration).await?;
+      group_controller = Box::new(controller);
+    },
+    FieldType::DateTime => {
+      let configuration = DateGroupContext::new(
+        view_id,
+        field_rev.clone(),
+        configuration_reader,
+        configuration_writer,
+      )
+      .await?;
+      let controller = DateGroupController::new(&field_rev, configuration).await?;
+      group_controller = Box::new(controller);
+    },
     _ => {
       group_controller = Box::new(DefaultGroupController::new(&field_rev));
     },
@@ -172,6 +195,14 @@ pub fn default_group_configuration(field_rev: &FieldRevision) -> GroupConfigurat
       URLGroupConfigurationRevision::default(),
     )
     .unwrap(),
+    FieldType::Formula | FieldType::Relation | FieldType::Rollup => {
+      GroupConfigurationRevision::new(
+        field_id,
+        field_type_rev,
+        TextGroupConfigurationRevision::default(),
+      )
+      .unwrap()
+    },
   }
 }
 
@@ -1,5 +1,6 @@
 mod util;
 
+pub mod calculation;
 pub mod cell;
 pub mod database;
 pub mod database_view;
@@ -9,4 +10,8 @@ pub mod group;
 pub mod persistence;
 pub mod row;
 pub mod setting;
+pub mod share;
 pub mod sort;
+pub(crate) mod web_socket;
+
+pub const DATABASE_SYNC_INTERVAL_IN_MILLIS: u64 = 1000;
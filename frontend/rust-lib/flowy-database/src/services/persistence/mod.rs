@@ -6,6 +6,7 @@ pub mod block_index;
 pub mod kv;
 pub mod migration;
 pub mod rev_sqlite;
+pub mod search_index;
 
 pub trait GridDatabase: Send + Sync {
   fn db_pool(&self) -> Result<Arc<ConnectionPool>, FlowyError>;
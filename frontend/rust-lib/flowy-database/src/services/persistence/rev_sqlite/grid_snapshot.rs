@@ -92,6 +92,15 @@ impl RevisionSnapshotPersistence for SQLiteDatabaseRevisionSnapshotPersistence {
             .first::<GridSnapshotRecord>(&*conn)?;
     Ok(Some(latest_record.into()))
   }
+
+  fn read_snapshots(&self) -> FlowyResult<Vec<RevisionSnapshotData>> {
+    let conn = self.pool.get().map_err(internal_error)?;
+    let records = dsl::grid_rev_snapshot
+      .filter(dsl::object_id.eq(&self.object_id))
+      .order((dsl::timestamp.desc(), dsl::rev_id.desc()))
+      .load::<GridSnapshotRecord>(&*conn)?;
+    Ok(records.into_iter().map(|record| record.into()).collect())
+  }
 }
 
 #[derive(PartialEq, Clone, Debug, Queryable, Identifiable, Insertable, Associations)]
@@ -0,0 +1,80 @@
+use crate::services::persistence::GridDatabase;
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::search::{SearchIndexRecord, SearchIndexSql, SearchObjectType};
+use std::sync::Arc;
+
+/// Stores the display strings of the cells in the search index, so the rows can be found by the
+/// content of their cells.
+pub struct DatabaseSearchIndex {
+  database: Arc<dyn GridDatabase>,
+}
+
+impl DatabaseSearchIndex {
+  pub fn new(database: Arc<dyn GridDatabase>) -> Self {
+    Self { database }
+  }
+
+  pub fn is_indexed(&self, database_id: &str) -> FlowyResult<bool> {
+    let conn = self.database.db_connection()?;
+    let is_indexed =
+      SearchIndexSql::has_objects(database_id, SearchObjectType::DatabaseCell, &conn)?;
+    Ok(is_indexed)
+  }
+
+  pub fn index_cells(&self, records: Vec<SearchIndexRecord>) -> FlowyResult<()> {
+    let conn = self.database.db_connection()?;
+    conn.immediate_transaction::<_, FlowyError, _>(|| {
+      for record in records {
+        SearchIndexSql::upsert(record, &conn)?;
+      }
+      Ok(())
+    })
+  }
+
+  /// Replaces all the indexed cells of the row with the records.
+  pub fn replace_row_cells(
+    &self,
+    database_id: &str,
+    row_id: &str,
+    records: Vec<SearchIndexRecord>,
+  ) -> FlowyResult<()> {
+    let conn = self.database.db_connection()?;
+    SearchIndexSql::replace_object(database_id, row_id, records, &conn)?;
+    Ok(())
+  }
+
+  /// Replaces all the indexed cells of the field with the records.
+  pub fn replace_field_cells(
+    &self,
+    database_id: &str,
+    field_id: &str,
+    records: Vec<SearchIndexRecord>,
+  ) -> FlowyResult<()> {
+    let conn = self.database.db_connection()?;
+    SearchIndexSql::replace_field(database_id, field_id, records, &conn)?;
+    Ok(())
+  }
+
+  /// Replaces all the indexed cells of the database with the records.
+  pub fn replace_cells(
+    &self,
+    database_id: &str,
+    records: Vec<SearchIndexRecord>,
+  ) -> FlowyResult<()> {
+    let conn = self.database.db_connection()?;
+    SearchIndexSql::replace_objects(database_id, SearchObjectType::DatabaseCell, records, &conn)?;
+    Ok(())
+  }
+
+  pub fn delete_row(&self, database_id: &str, row_id: &str) -> FlowyResult<()> {
+    let conn = self.database.db_connection()?;
+    SearchIndexSql::delete_object(database_id, row_id, "", &conn)?;
+    Ok(())
+  }
+
+  pub fn delete_field(&self, database_id: &str, field_id: &str) -> FlowyResult<()> {
+    let conn = self.database.db_connection()?;
+    SearchIndexSql::delete_field(database_id, field_id, &conn)?;
+    Ok(())
+  }
+}
@@ -1,10 +1,13 @@
 use crate::services::cell::{
-  insert_checkbox_cell, insert_date_cell, insert_number_cell, insert_select_option_cell,
-  insert_text_cell, insert_url_cell,
+  insert_checkbox_cell, insert_date_cell, insert_decimal_cell, insert_number_cell,
+  insert_select_option_cell, insert_text_cell, insert_url_cell,
 };
 
-use database_model::{gen_row_id, CellRevision, FieldRevision, RowRevision, DEFAULT_ROW_HEIGHT};
+use database_model::{
+  gen_row_id, row_timestamp, CellRevision, FieldRevision, RowRevision, DEFAULT_ROW_HEIGHT,
+};
 use indexmap::IndexMap;
+use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -73,6 +76,18 @@ impl<'a> RowRevisionBuilder<'a> {
     }
   }
 
+  pub fn insert_decimal_cell(&mut self, field_id: &str, num: Decimal) {
+    match self.field_rev_map.get(&field_id.to_owned()) {
+      None => tracing::warn!("Can't find the number field with id: {}", field_id),
+      Some(field_rev) => {
+        self
+          .payload
+          .cell_by_field_id
+          .insert(field_id.to_owned(), insert_decimal_cell(num, field_rev));
+      },
+    }
+  }
+
   pub fn insert_checkbox_cell(&mut self, field_id: &str, is_check: bool) {
     match self.field_rev_map.get(&field_id.to_owned()) {
       None => tracing::warn!("Can't find the checkbox field with id: {}", field_id),
@@ -128,6 +143,7 @@ impl<'a> RowRevisionBuilder<'a> {
       cells: self.payload.cell_by_field_id,
       height: self.payload.height,
       visibility: self.payload.visibility,
+      created_at: row_timestamp(),
     }
   }
 }
@@ -1,5 +1,7 @@
 use crate::entities::{
-  AlterFilterParams, DatabaseSettingChangesetParams, DeleteFilterParams, LayoutTypePB,
+  AlterCalculationParams, AlterFilterParams, DatabaseSettingChangesetParams,
+  DeleteCalculationParams, DeleteFilterGroupParams, DeleteFilterParams, InsertFilterGroupParams,
+  LayoutTypePB, UpdateFilterGroupParams,
 };
 
 pub struct GridSettingChangesetBuilder {
@@ -17,6 +19,11 @@ impl GridSettingChangesetBuilder {
       delete_group: None,
       alert_sort: None,
       delete_sort: None,
+      insert_filter_group: None,
+      update_filter_group: None,
+      delete_filter_group: None,
+      alter_calculation: None,
+      delete_calculation: None,
     };
     Self { params }
   }
@@ -31,6 +38,31 @@ impl GridSettingChangesetBuilder {
     self
   }
 
+  pub fn insert_filter_group(mut self, params: InsertFilterGroupParams) -> Self {
+    self.params.insert_filter_group = Some(params);
+    self
+  }
+
+  pub fn update_filter_group(mut self, params: UpdateFilterGroupParams) -> Self {
+    self.params.update_filter_group = Some(params);
+    self
+  }
+
+  pub fn delete_filter_group(mut self, params: DeleteFilterGroupParams) -> Self {
+    self.params.delete_filter_group = Some(params);
+    self
+  }
+
+  pub fn alter_calculation(mut self, params: AlterCalculationParams) -> Self {
+    self.params.alter_calculation = Some(params);
+    self
+  }
+
+  pub fn delete_calculation(mut self, params: DeleteCalculationParams) -> Self {
+    self.params.delete_calculation = Some(params);
+    self
+  }
+
   pub fn build(self) -> DatabaseSettingChangesetParams {
     self.params
   }
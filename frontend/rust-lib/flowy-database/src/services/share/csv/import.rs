@@ -0,0 +1,427 @@
+use crate::entities::FieldType;
+use crate::services::field::{
+  new_select_option_color, CheckboxTypeOptionBuilder, DateFormat, DateTypeOptionBuilder,
+  FieldBuilder, NumberFormat, NumberTypeOptionBuilder, RichTextTypeOptionBuilder, SelectOptionPB,
+  SingleSelectTypeOptionBuilder, URLTypeOptionBuilder,
+};
+use crate::services::row::RowRevisionBuilder;
+use chrono::NaiveDate;
+use database_model::{BuildDatabaseContext, FieldRevision};
+use flowy_client_sync::client_database::DatabaseBuilder;
+use flowy_error::{FlowyError, FlowyResult};
+use lazy_static::lazy_static;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+/// A column is imported as a single select field if it has at most this number of distinct values.
+const MAX_SELECT_OPTION_COUNT: usize = 10;
+
+lazy_static! {
+  /// The currency formats that are used to detect the format of the number columns. The formats
+  /// with the longer symbol are tried first, so "CA$" wins over "$".
+  static ref CURRENCY_FORMATS: Vec<NumberFormat> = {
+    let mut formats = NumberFormat::iter()
+      .filter(|format| format != &NumberFormat::Num)
+      .collect::<Vec<NumberFormat>>();
+    formats.sort_by_key(|format| std::cmp::Reverse(format.symbol().chars().count()));
+    formats
+  };
+}
+
+/// Builds a new database from the csv text. The first line of the csv is the header that is used
+/// as the names of the fields, each of the following lines is imported as a row.
+///
+/// The first column is always imported as the primary text field. The types of the other columns
+/// are inferred from their values, see [infer_column_type] for the details.
+pub fn make_database_from_csv(csv: &str) -> FlowyResult<BuildDatabaseContext> {
+  let content = csv.trim_start_matches('\u{feff}');
+  let mut reader = csv::ReaderBuilder::new()
+    .flexible(true)
+    .from_reader(content.as_bytes());
+
+  let header = reader
+    .headers()
+    .map_err(|err| FlowyError::invalid_csv().context(err))?
+    .iter()
+    .enumerate()
+    .map(|(index, name)| match name.trim() {
+      "" => format!("Column {}", index + 1),
+      name => name.to_owned(),
+    })
+    .collect::<Vec<String>>();
+  if header.is_empty() {
+    return Err(FlowyError::invalid_csv().context("The csv doesn't have any column"));
+  }
+
+  let mut rows = vec![];
+  for record in reader.records() {
+    let record = record.map_err(|err| FlowyError::invalid_csv().context(err))?;
+    let mut row = record
+      .iter()
+      .take(header.len())
+      .map(|value| value.trim().to_owned())
+      .collect::<Vec<String>>();
+    row.resize(header.len(), "".to_owned());
+    rows.push(row);
+  }
+
+  let mut database_builder = DatabaseBuilder::new();
+  let mut column_types = vec![];
+  for (index, name) in header.iter().enumerate() {
+    let column_type = if index == 0 {
+      CSVColumnType::RichText
+    } else {
+      infer_column_type(rows.iter().map(|row| row[index].as_str()))
+    };
+    database_builder.add_field(column_type.make_field(name, index == 0));
+    column_types.push(column_type);
+  }
+
+  let field_ids = database_builder
+    .field_revs()
+    .iter()
+    .map(|field_rev| field_rev.id.clone())
+    .collect::<Vec<String>>();
+  for row in rows {
+    let mut row_builder =
+      RowRevisionBuilder::new(database_builder.block_id(), database_builder.field_revs());
+    for ((value, field_id), column_type) in row.into_iter().zip(&field_ids).zip(&column_types) {
+      if value.is_empty() {
+        continue;
+      }
+      column_type.insert_cell(&mut row_builder, field_id, value);
+    }
+    let row_rev = row_builder.build();
+    database_builder.add_row(row_rev);
+  }
+
+  Ok(database_builder.build())
+}
+
+/// The field type of the csv column that is inferred from the values of the column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CSVColumnType {
+  RichText,
+  Number(NumberFormat),
+  DateTime(DateFormat),
+  Checkbox,
+  URL,
+  SingleSelect(Vec<SelectOptionPB>),
+}
+
+impl CSVColumnType {
+  pub fn field_type(&self) -> FieldType {
+    match self {
+      CSVColumnType::RichText => FieldType::RichText,
+      CSVColumnType::Number(_) => FieldType::Number,
+      CSVColumnType::DateTime(_) => FieldType::DateTime,
+      CSVColumnType::Checkbox => FieldType::Checkbox,
+      CSVColumnType::URL => FieldType::URL,
+      CSVColumnType::SingleSelect(_) => FieldType::SingleSelect,
+    }
+  }
+
+  fn make_field(&self, name: &str, is_primary: bool) -> FieldRevision {
+    let field_builder = match self {
+      CSVColumnType::RichText => FieldBuilder::new(RichTextTypeOptionBuilder::default()),
+      CSVColumnType::Number(format) => {
+        FieldBuilder::new(NumberTypeOptionBuilder::default().set_format(*format))
+      },
+      CSVColumnType::DateTime(format) => {
+        FieldBuilder::new(DateTypeOptionBuilder::default().date_format(*format))
+      },
+      CSVColumnType::Checkbox => FieldBuilder::new(CheckboxTypeOptionBuilder::default()),
+      CSVColumnType::URL => FieldBuilder::new(URLTypeOptionBuilder::default()),
+      CSVColumnType::SingleSelect(options) => {
+        let type_option = options.iter().fold(
+          SingleSelectTypeOptionBuilder::default(),
+          |builder, option| builder.add_option(option.clone()),
+        );
+        FieldBuilder::new(type_option)
+      },
+    };
+    field_builder
+      .name(name)
+      .visibility(true)
+      .primary(is_primary)
+      .build()
+  }
+
+  fn insert_cell(&self, row_builder: &mut RowRevisionBuilder, field_id: &str, value: String) {
+    match self {
+      CSVColumnType::RichText => row_builder.insert_text_cell(field_id, value),
+      CSVColumnType::Number(format) => match parse_number(&value, format) {
+        None => tracing::warn!("Can't parse {} as number", value),
+        Some(num) => row_builder.insert_decimal_cell(field_id, num),
+      },
+      CSVColumnType::DateTime(format) => match parse_date(&value, format) {
+        None => tracing::warn!("Can't parse {} as date", value),
+        Some(timestamp) => row_builder.insert_date_cell(field_id, timestamp),
+      },
+      CSVColumnType::Checkbox => {
+        row_builder.insert_checkbox_cell(field_id, parse_bool(&value).unwrap_or(false))
+      },
+      CSVColumnType::URL => row_builder.insert_url_cell(field_id, value),
+      CSVColumnType::SingleSelect(options) => {
+        if let Some(option) = options.iter().find(|option| option.name == value) {
+          row_builder.insert_select_option_cell(field_id, vec![option.id.clone()]);
+        }
+      },
+    }
+  }
+}
+
+/// Infers the type of the column from its non-empty values. The types are tried in order:
+/// Checkbox, Number, DateTime, URL and SingleSelect, the column falls back to RichText if none of
+/// them matches all the values.
+pub fn infer_column_type<'a>(values: impl Iterator<Item = &'a str>) -> CSVColumnType {
+  let values = values
+    .map(|value| value.trim())
+    .filter(|value| !value.is_empty())
+    .collect::<Vec<&str>>();
+  if values.is_empty() {
+    return CSVColumnType::RichText;
+  }
+
+  if values.iter().all(|value| parse_bool(value).is_some()) {
+    return CSVColumnType::Checkbox;
+  }
+
+  if let Some(format) = infer_number_format(&values) {
+    return CSVColumnType::Number(format);
+  }
+
+  if let Some(format) = infer_date_format(&values) {
+    return CSVColumnType::DateTime(format);
+  }
+
+  if values.iter().all(|value| is_url(value)) {
+    return CSVColumnType::URL;
+  }
+
+  let mut options: Vec<SelectOptionPB> = vec![];
+  for value in values.iter() {
+    if options.iter().any(|option| &option.name == value) {
+      continue;
+    }
+    if options.len() == MAX_SELECT_OPTION_COUNT {
+      return CSVColumnType::RichText;
+    }
+    let color = new_select_option_color(&options);
+    options.push(SelectOptionPB::with_color(value, color));
+  }
+  // Each value is unique, it's more likely to be a text column.
+  if options.len() == values.len() {
+    return CSVColumnType::RichText;
+  }
+  CSVColumnType::SingleSelect(options)
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+  match s.to_lowercase().as_str() {
+    "true" | "yes" => Some(true),
+    "false" | "no" => Some(false),
+    _ => None,
+  }
+}
+
+fn is_url(s: &str) -> bool {
+  match url::Url::parse(s) {
+    Ok(url) => url.scheme() == "http" || url.scheme() == "https",
+    Err(_) => false,
+  }
+}
+
+/// Returns the number format that all the values share, the values that have a currency symbol
+/// decide the format, the plain numbers match any format.
+fn infer_number_format(values: &[&str]) -> Option<NumberFormat> {
+  let mut format = NumberFormat::Num;
+  for value in values {
+    let value_format = detect_number_format(value)?;
+    if value_format == NumberFormat::Num {
+      continue;
+    }
+    if format == NumberFormat::Num {
+      format = value_format;
+    } else if format != value_format {
+      return None;
+    }
+  }
+  Some(format)
+}
+
+fn detect_number_format(s: &str) -> Option<NumberFormat> {
+  if parse_number(s, &NumberFormat::Num).is_some() {
+    return Some(NumberFormat::Num);
+  }
+  CURRENCY_FORMATS
+    .iter()
+    .find(|format| parse_number(s, format).is_some())
+    .cloned()
+}
+
+fn parse_number(s: &str, format: &NumberFormat) -> Option<Decimal> {
+  let (is_negative, s) = match s.trim().strip_prefix('-') {
+    None => (false, s.trim()),
+    Some(s) => (true, s.trim()),
+  };
+  let s = match format {
+    NumberFormat::Num => s,
+    _ => {
+      let currency = format.currency();
+      let s = if currency.symbol_first {
+        s.strip_prefix(currency.symbol)?
+      } else {
+        s.strip_suffix(currency.symbol)?
+      };
+      s.trim()
+    },
+  };
+  if s.is_empty() || !s.starts_with(|c: char| c.is_ascii_digit()) {
+    return None;
+  }
+  let mut decimal = Decimal::from_str(&s.replace(',', "")).ok()?;
+  decimal.set_sign_negative(is_negative);
+  Some(decimal)
+}
+
+/// Returns the date format that all the values share.
+fn infer_date_format(values: &[&str]) -> Option<DateFormat> {
+  DateFormat::iter().find(|format| {
+    values
+      .iter()
+      .all(|value| parse_date(value, format).is_some())
+  })
+}
+
+fn parse_date(s: &str, format: &DateFormat) -> Option<i64> {
+  let s = match format {
+    // The friendly format displays the date like "Mar 14,2022", accept "Mar 14, 2022" too.
+    DateFormat::Friendly => s.replace(", ", ","),
+    _ => s.to_owned(),
+  };
+  let date = NaiveDate::parse_from_str(&s, format.format_str()).ok()?;
+  Some(date.and_hms_opt(0, 0, 0)?.timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::FieldType;
+  use crate::services::field::{DateFormat, NumberFormat};
+  use crate::services::share::csv::{infer_column_type, make_database_from_csv, CSVColumnType};
+
+  fn infer(values: &[&str]) -> CSVColumnType {
+    infer_column_type(values.iter().cloned())
+  }
+
+  #[test]
+  fn infer_checkbox_and_url_column_test() {
+    assert_eq!(infer(&["Yes", "no", "", "TRUE"]), CSVColumnType::Checkbox);
+    assert_eq!(
+      infer(&["https://appflowy.io", "http://github.com/AppFlowy-IO"]),
+      CSVColumnType::URL
+    );
+    assert_eq!(infer(&["", " "]), CSVColumnType::RichText);
+  }
+
+  #[test]
+  fn infer_number_column_test() {
+    assert_eq!(
+      infer(&["1", "-2.5", "1,000"]),
+      CSVColumnType::Number(NumberFormat::Num)
+    );
+    assert_eq!(
+      infer(&["$1.5", "2", "-$3"]),
+      CSVColumnType::Number(NumberFormat::USD)
+    );
+    assert_eq!(
+      infer(&["€1", "€2"]),
+      CSVColumnType::Number(NumberFormat::EUR)
+    );
+    assert_eq!(
+      infer(&["12%", "50%"]),
+      CSVColumnType::Number(NumberFormat::Percent)
+    );
+    // Different currencies in the same column
+    assert_eq!(infer(&["$1", "€2", "£3"]).field_type(), FieldType::RichText);
+  }
+
+  #[test]
+  fn infer_date_column_test() {
+    assert_eq!(
+      infer(&["2022-03-14", "2023-01-01"]),
+      CSVColumnType::DateTime(DateFormat::ISO)
+    );
+    assert_eq!(
+      infer(&["03/14/2022", "12/31/2022"]),
+      CSVColumnType::DateTime(DateFormat::Local)
+    );
+    assert_eq!(
+      infer(&["Mar 14, 2022", "Dec 31,2022"]),
+      CSVColumnType::DateTime(DateFormat::Friendly)
+    );
+  }
+
+  #[test]
+  fn infer_select_option_column_test() {
+    match infer(&["Done", "To Do", "Done", "Doing", "To Do"]) {
+      CSVColumnType::SingleSelect(options) => {
+        let names = options
+          .iter()
+          .map(|option| option.name.as_str())
+          .collect::<Vec<&str>>();
+        assert_eq!(names, vec!["Done", "To Do", "Doing"]);
+      },
+      ty => panic!("Expected single select, but receive {:?}", ty),
+    }
+
+    // All the values are unique
+    assert_eq!(infer(&["a", "b", "c"]), CSVColumnType::RichText);
+    // Too many distinct values
+    let values = (0..20)
+      .map(|i| format!("value {}", i % 11))
+      .collect::<Vec<String>>();
+    assert_eq!(
+      infer_column_type(values.iter().map(|value| value.as_str())),
+      CSVColumnType::RichText
+    );
+  }
+
+  #[test]
+  fn make_database_from_csv_test() {
+    let csv = "\u{feff}Name,Amount,Date,Done,Status,\n\
+               Apple,$1.5,2022-03-14,yes,Done,a\n\
+               \"Banana, yellow\",$2,2022-03-15,no,Done\n\
+               Cherry,,,true,Doing,b,extra\n";
+    let context = make_database_from_csv(csv).unwrap();
+    let field_types = context
+      .field_revs
+      .iter()
+      .map(|field_rev| field_rev.ty.into())
+      .collect::<Vec<FieldType>>();
+    assert_eq!(
+      field_types,
+      vec![
+        FieldType::RichText,
+        FieldType::Number,
+        FieldType::DateTime,
+        FieldType::Checkbox,
+        FieldType::SingleSelect,
+        FieldType::RichText,
+      ]
+    );
+    assert!(context.field_revs[0].is_primary);
+    assert_eq!(context.field_revs[5].name, "Column 6");
+
+    let row_revs = &context.blocks[0].rows;
+    assert_eq!(row_revs.len(), 3);
+    // The empty cells are not inserted and the extra value is dropped
+    assert_eq!(row_revs[2].cells.len(), 4);
+  }
+
+  #[test]
+  fn make_database_from_empty_csv_test() {
+    assert!(make_database_from_csv("").is_err());
+  }
+}
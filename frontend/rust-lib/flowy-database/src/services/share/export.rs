@@ -0,0 +1,128 @@
+use crate::entities::{ExportFormatPB, FieldType};
+use crate::services::cell::{stringify_cell_data, TypeCellData};
+use database_model::{FieldRevision, RowRevision};
+use flowy_error::{internal_error, FlowyResult};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// [DatabaseExporter] writes the rows of a view as csv or json. The rows should be the ones that
+/// are visible in the view, in the order they are displayed.
+///
+/// The cells are written as the strings that are displayed in the grid, for example, the date
+/// cell is written as "Mar 14,2022" and the select option cell is written as the option's name.
+/// If `raw_cell` is true, the cells are written as the strings that are stored in the cell
+/// revisions, which can be applied to the cells again.
+pub struct DatabaseExporter {
+  field_revs: Vec<Arc<FieldRevision>>,
+  row_revs: Vec<Arc<RowRevision>>,
+  raw_cell: bool,
+}
+
+impl DatabaseExporter {
+  pub fn new(
+    field_revs: Vec<Arc<FieldRevision>>,
+    row_revs: Vec<Arc<RowRevision>>,
+    raw_cell: bool,
+  ) -> Self {
+    Self {
+      field_revs,
+      row_revs,
+      raw_cell,
+    }
+  }
+
+  pub fn export(&self, format: &ExportFormatPB) -> FlowyResult<String> {
+    match format {
+      ExportFormatPB::CSV => self.export_csv(),
+      ExportFormatPB::JSON => self.export_json(),
+    }
+  }
+
+  /// The first line is the names of the fields, each of the following lines is a row.
+  fn export_csv(&self) -> FlowyResult<String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer
+      .write_record(self.field_revs.iter().map(|field_rev| &field_rev.name))
+      .map_err(internal_error)?;
+    for row_rev in self.row_revs.iter() {
+      writer
+        .write_record(self.row_cells(row_rev))
+        .map_err(internal_error)?;
+    }
+    let bytes = writer.into_inner().map_err(internal_error)?;
+    String::from_utf8(bytes).map_err(internal_error)
+  }
+
+  /// The cells of each row are in the same order as the fields, because the names of the fields
+  /// are not unique.
+  fn export_json(&self) -> FlowyResult<String> {
+    let fields = self
+      .field_revs
+      .iter()
+      .map(|field_rev| ExportedField {
+        id: field_rev.id.clone(),
+        name: field_rev.name.clone(),
+        field_type: field_rev.ty.into(),
+      })
+      .collect();
+    let rows = self
+      .row_revs
+      .iter()
+      .map(|row_rev| ExportedRow {
+        id: row_rev.id.clone(),
+        cells: self.row_cells(row_rev),
+      })
+      .collect();
+    let database = ExportedDatabase { fields, rows };
+    serde_json::to_string_pretty(&database).map_err(internal_error)
+  }
+
+  fn row_cells(&self, row_rev: &RowRevision) -> Vec<String> {
+    self
+      .field_revs
+      .iter()
+      .map(|field_rev| self.cell_str(field_rev, row_rev))
+      .collect()
+  }
+
+  fn cell_str(&self, field_rev: &FieldRevision, row_rev: &RowRevision) -> String {
+    let type_cell_data = match row_rev
+      .cells
+      .get(&field_rev.id)
+      .and_then(|cell_rev| TypeCellData::try_from(cell_rev).ok())
+    {
+      None => return "".to_owned(),
+      Some(type_cell_data) => type_cell_data,
+    };
+    // The formula cells are not stored, they are written as the calculated values.
+    if self.raw_cell && !type_cell_data.field_type.is_formula() {
+      return type_cell_data.cell_str;
+    }
+    let field_type: FieldType = field_rev.ty.into();
+    stringify_cell_data(
+      type_cell_data.cell_str,
+      &type_cell_data.field_type,
+      &field_type,
+      field_rev,
+    )
+  }
+}
+
+#[derive(Serialize)]
+struct ExportedDatabase {
+  fields: Vec<ExportedField>,
+  rows: Vec<ExportedRow>,
+}
+
+#[derive(Serialize)]
+struct ExportedField {
+  id: String,
+  name: String,
+  field_type: FieldType,
+}
+
+#[derive(Serialize)]
+struct ExportedRow {
+  id: String,
+  cells: Vec<String>,
+}
@@ -0,0 +1,4 @@
+pub mod csv;
+mod export;
+
+pub use export::*;
@@ -6,7 +6,7 @@ use crate::services::field::{default_order, TypeOptionCellExt};
 use crate::services::sort::{
   ReorderAllRowsResult, ReorderSingleRowResult, SortChangeset, SortType,
 };
-use database_model::{CellRevision, FieldRevision, RowRevision, SortCondition, SortRevision};
+use database_model::{FieldRevision, RowRevision, SortCondition, SortRevision};
 use flowy_error::FlowyResult;
 use flowy_task::{QualityOfService, Task, TaskContent, TaskDispatcher};
 use lib_infra::future::Fut;
@@ -145,10 +145,9 @@ impl SortController {
     }
 
     let field_revs = self.delegate.get_field_revs(None).await;
-    for sort in self.sorts.iter() {
-      rows
-        .par_sort_by(|left, right| cmp_row(left, right, sort, &field_revs, &self.cell_data_cache));
-    }
+    rows.par_sort_by(|left, right| {
+      cmp_rows(left, right, &self.sorts, &field_revs, &self.cell_data_cache)
+    });
     rows.iter().enumerate().for_each(|(index, row)| {
       self.row_index_cache.insert(row.id.to_string(), index);
     });
@@ -212,72 +211,80 @@ impl SortController {
   }
 }
 
-fn cmp_row(
+/// Compares the rows by the sorts in order, the later sort is only used if the rows are equal by
+/// the former sorts. The rows are compared by their creation time at last. The `par_sort_by` is
+/// stable, so the rows that are still equal keep their original order.
+fn cmp_rows(
   left: &Arc<RowRevision>,
   right: &Arc<RowRevision>,
-  sort: &Arc<SortRevision>,
+  sorts: &[Arc<SortRevision>],
   field_revs: &[Arc<FieldRevision>],
   cell_data_cache: &AtomicCellDataCache,
 ) -> Ordering {
-  let order = match (
-    left.cells.get(&sort.field_id),
-    right.cells.get(&sort.field_id),
-  ) {
-    (Some(left_cell), Some(right_cell)) => {
-      let field_type: FieldType = sort.field_type.into();
-      match field_revs
-        .iter()
-        .find(|field_rev| field_rev.id == sort.field_id)
-      {
-        None => default_order(),
-        Some(field_rev) => cmp_cell(
-          left_cell,
-          right_cell,
-          field_rev,
-          field_type,
-          cell_data_cache,
-        ),
-      }
-    },
-    (Some(_), None) => Ordering::Greater,
-    (None, Some(_)) => Ordering::Less,
-    _ => default_order(),
-  };
-
-  // The order is calculated by Ascending. So reverse the order if the SortCondition is descending.
-  match sort.condition {
-    SortCondition::Ascending => order,
-    SortCondition::Descending => order.reverse(),
+  for sort in sorts.iter() {
+    let order = cmp_row(left, right, sort, field_revs, cell_data_cache);
+    if order.is_ne() {
+      return order;
+    }
   }
+  left.created_at.cmp(&right.created_at)
 }
 
-fn cmp_cell(
-  left_cell: &CellRevision,
-  right_cell: &CellRevision,
-  field_rev: &Arc<FieldRevision>,
-  field_type: FieldType,
+fn cmp_row(
+  left: &Arc<RowRevision>,
+  right: &Arc<RowRevision>,
+  sort: &Arc<SortRevision>,
+  field_revs: &[Arc<FieldRevision>],
   cell_data_cache: &AtomicCellDataCache,
 ) -> Ordering {
-  match TypeOptionCellExt::new_with_cell_data_cache(
+  let field_rev = match field_revs
+    .iter()
+    .find(|field_rev| field_rev.id == sort.field_id)
+  {
+    None => return default_order(),
+    Some(field_rev) => field_rev,
+  };
+  let field_type: FieldType = sort.field_type.into();
+  let handler = match TypeOptionCellExt::new_with_cell_data_cache(
     field_rev.as_ref(),
     Some(cell_data_cache.clone()),
   )
   .get_type_option_cell_data_handler(&field_type)
   {
-    None => default_order(),
-    Some(handler) => {
-      let cal_order = || {
-        let left_cell_str = TypeCellData::try_from(left_cell).ok()?.into_inner();
-        let right_cell_str = TypeCellData::try_from(right_cell).ok()?.into_inner();
-        let order =
-          handler.handle_cell_compare(&left_cell_str, &right_cell_str, field_rev.as_ref());
-        Option::<Ordering>::Some(order)
-      };
+    None => return default_order(),
+    Some(handler) => handler,
+  };
+
+  let get_cell_str = |row_rev: &Arc<RowRevision>| {
+    let cell_str = row_rev
+      .cells
+      .get(&sort.field_id)
+      .and_then(|cell_rev| TypeCellData::try_from(cell_rev).ok())
+      .map(|type_cell_data| type_cell_data.into_inner());
+    // The checkbox is unchecked if its cell is empty, so it's never treated as an empty cell.
+    if field_type.is_checkbox() {
+      return Some(cell_str.unwrap_or_default());
+    }
+    cell_str.filter(|cell_str| !handler.handle_cell_is_empty(cell_str, field_rev))
+  };
 
-      cal_order().unwrap_or_else(default_order)
+  // The empty cells are always placed after the non-empty cells no matter the sort condition.
+  match (get_cell_str(left), get_cell_str(right)) {
+    (Some(left_cell_str), Some(right_cell_str)) => {
+      let order = handler.handle_cell_compare(&left_cell_str, &right_cell_str, field_rev);
+      // The order is calculated by Ascending. So reverse the order if the SortCondition is
+      // descending.
+      match sort.condition {
+        SortCondition::Ascending => order,
+        SortCondition::Descending => order.reverse(),
+      }
     },
+    (Some(_), None) => Ordering::Less,
+    (None, Some(_)) => Ordering::Greater,
+    (None, None) => default_order(),
   }
 }
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 enum SortEvent {
   SortDidChanged,
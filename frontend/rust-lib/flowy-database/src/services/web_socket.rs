@@ -0,0 +1,300 @@
+use crate::services::database::{DatabaseHistoryRef, DatabaseHistoryUntracked};
+use crate::services::DATABASE_SYNC_INTERVAL_IN_MILLIS;
+use flowy_client_sync::client_database::{
+  DatabaseBlockRevisionPad, DatabaseHistoryOperations, DatabaseRevisionPad, DatabaseViewRevisionPad,
+};
+use flowy_client_sync::errors::SyncResult;
+use flowy_client_sync::make_operations_from_revisions;
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_revision::*;
+use flowy_sqlite::ConnectionPool;
+use lib_infra::future::{BoxResultFuture, FutureResult};
+use lib_ot::core::{DeltaOperations, EmptyAttributes, OperationTransform};
+use revision_model::{Revision, RevisionRange};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::RwLock;
+use ws_model::ws_revision::{ClientRevisionWSData, NewDocumentUser};
+
+/// The prefix of the web socket object id of the database view. The view's id is the same as the
+/// database's id, so the prefix keeps their revisions apart on the server.
+pub(crate) const DATABASE_VIEW_WS_PREFIX: &str = "view:";
+
+#[allow(dead_code)]
+pub(crate) fn database_view_ws_object_id(view_id: &str) -> String {
+  format!("{}{}", DATABASE_VIEW_WS_PREFIX, view_id)
+}
+
+/// The change of the pad that is made by the remote operations.
+pub(crate) struct RemoteChange {
+  /// md5: the md5 of the pad after applying the change.
+  md5: String,
+  /// The operations of the change and the operations that revert it. It's None if the changes of
+  /// the pad are not recorded in the database's history.
+  history_operations: Option<(DatabaseHistoryOperations, DatabaseHistoryOperations)>,
+}
+
+/// The pads of the database, the database block and the database view are all serialized to json
+/// text, so the remote revisions are composed in the same way.
+pub(crate) trait DatabaseSyncPad: Send + Sync + 'static {
+  fn compose_remote_operations(
+    &mut self,
+    operations: DeltaOperations<EmptyAttributes>,
+  ) -> SyncResult<RemoteChange>;
+}
+
+impl DatabaseSyncPad for DatabaseRevisionPad {
+  fn compose_remote_operations(
+    &mut self,
+    operations: DeltaOperations<EmptyAttributes>,
+  ) -> SyncResult<RemoteChange> {
+    let changeset = self.apply_operations(operations)?;
+    Ok(RemoteChange {
+      md5: changeset.md5,
+      history_operations: Some((
+        DatabaseHistoryOperations::Database(changeset.operations),
+        DatabaseHistoryOperations::Database(changeset.inverted_operations),
+      )),
+    })
+  }
+}
+
+impl DatabaseSyncPad for DatabaseBlockRevisionPad {
+  fn compose_remote_operations(
+    &mut self,
+    operations: DeltaOperations<EmptyAttributes>,
+  ) -> SyncResult<RemoteChange> {
+    let block_id = self.block_id.clone();
+    let changeset = self.apply_operations(operations)?;
+    Ok(RemoteChange {
+      md5: changeset.md5,
+      history_operations: Some((
+        DatabaseHistoryOperations::Block {
+          block_id: block_id.clone(),
+          operations: changeset.operations,
+        },
+        DatabaseHistoryOperations::Block {
+          block_id,
+          operations: changeset.inverted_operations,
+        },
+      )),
+    })
+  }
+}
+
+impl DatabaseSyncPad for DatabaseViewRevisionPad {
+  fn compose_remote_operations(
+    &mut self,
+    operations: DeltaOperations<EmptyAttributes>,
+  ) -> SyncResult<RemoteChange> {
+    let md5 = self.compose_remote_operations(operations)?;
+    Ok(RemoteChange {
+      md5,
+      history_operations: None,
+    })
+  }
+}
+
+/// Syncs the revisions of the `rev_manager` with the server. The `ws_object_id` identifies the
+/// object on the server, it's different from the `rev_manager`'s object id if the object id is
+/// shared with other objects, for example, the database view. The recorded items of the `history`
+/// are transformed against the remote changes, so they can still be undone after syncing.
+#[allow(dead_code)]
+pub(crate) fn make_database_ws_manager<P>(
+  object_name: &str,
+  ws_object_id: &str,
+  rev_manager: Arc<RevisionManager<Arc<ConnectionPool>>>,
+  web_socket: Arc<dyn RevisionWebSocket>,
+  pad: Arc<RwLock<P>>,
+  history: Option<DatabaseHistoryRef>,
+) -> Arc<RevisionWebSocketManager>
+where
+  P: DatabaseSyncPad,
+{
+  let ws_data_provider = Arc::new(WSDataProvider::new(
+    ws_object_id,
+    Arc::new(rev_manager.clone()),
+  ));
+  let ws_data_stream = Arc::new(DatabaseRevisionWSDataStream {
+    rev_manager,
+    ws_data_provider: ws_data_provider.clone(),
+    pad,
+    history,
+  });
+  let ws_data_sink = Arc::new(DatabaseWSDataSink {
+    ws_object_id: ws_object_id.to_owned(),
+    ws_data_provider,
+  });
+  let ping_duration = Duration::from_millis(DATABASE_SYNC_INTERVAL_IN_MILLIS);
+  Arc::new(RevisionWebSocketManager::new(
+    object_name,
+    ws_object_id,
+    web_socket,
+    ws_data_sink,
+    ws_data_stream,
+    ping_duration,
+  ))
+}
+
+struct DatabaseWSDataSink {
+  ws_object_id: String,
+  ws_data_provider: Arc<WSDataProvider>,
+}
+
+impl RevisionWebSocketSink for DatabaseWSDataSink {
+  fn next(&self) -> FutureResult<Option<ClientRevisionWSData>, FlowyError> {
+    let ws_object_id = self.ws_object_id.clone();
+    let ws_data_provider = self.ws_data_provider.clone();
+    FutureResult::new(async move {
+      let data = ws_data_provider.next().await?.map(|mut data| {
+        // The server saves the revisions by their object id.
+        data.revisions.iter_mut().for_each(|revision| {
+          revision.object_id = ws_object_id.clone();
+        });
+        data
+      });
+      Ok(data)
+    })
+  }
+}
+
+struct DatabaseRevisionWSDataStream<P> {
+  rev_manager: Arc<RevisionManager<Arc<ConnectionPool>>>,
+  ws_data_provider: Arc<WSDataProvider>,
+  pad: Arc<RwLock<P>>,
+  history: Option<DatabaseHistoryRef>,
+}
+
+impl<P> Clone for DatabaseRevisionWSDataStream<P> {
+  fn clone(&self) -> Self {
+    Self {
+      rev_manager: self.rev_manager.clone(),
+      ws_data_provider: self.ws_data_provider.clone(),
+      pad: self.pad.clone(),
+      history: self.history.clone(),
+    }
+  }
+}
+
+impl<P> DatabaseRevisionWSDataStream<P>
+where
+  P: DatabaseSyncPad,
+{
+  /// Applies the revisions that were pushed by the server. The local revisions that conflict with
+  /// them were made concurrently by this client, so they are transformed against the server's
+  /// revisions and then saved as a new local revision that will be pushed to the server.
+  async fn receive_revisions(&self, revisions: Vec<Revision>) -> FlowyResult<()> {
+    let object_id = self.rev_manager.object_id.clone();
+    let mut revisions = revisions
+      .into_iter()
+      .map(|mut revision| {
+        revision.object_id = object_id.clone();
+        revision
+      })
+      .collect::<Vec<Revision>>();
+
+    // Skip the revisions that were applied before.
+    while let Some(first_revision) = revisions.first() {
+      match self.rev_manager.get_revision(first_revision.rev_id).await {
+        Some(local_revision) if local_revision.md5 == first_revision.md5 => {
+          revisions.remove(0);
+        },
+        _ => break,
+      }
+    }
+    let first_rev_id = match revisions.first() {
+      None => return Ok(()),
+      Some(first_revision) => first_revision.rev_id,
+    };
+
+    // Hold the pad's lock so that the local changes are not saved in the meantime.
+    let mut pad = self.pad.write().await;
+    let local_rev_id = self.rev_manager.rev_id();
+    let server_operations: DeltaOperations<EmptyAttributes> =
+      make_operations_from_revisions(revisions.clone())?;
+    if first_rev_id > local_rev_id {
+      if first_rev_id != local_rev_id + 1 {
+        tracing::warn!(
+          "{}: the pushed revision {} doesn't follow the local revision {}",
+          object_id,
+          first_rev_id,
+          local_rev_id
+        );
+        return Ok(());
+      }
+      let change = pad.compose_remote_operations(server_operations)?;
+      self.record_remote_change(change);
+      for revision in &revisions {
+        self.rev_manager.add_remote_revision(revision).await?;
+      }
+    } else {
+      let range = RevisionRange {
+        start: first_rev_id,
+        end: local_rev_id,
+      };
+      let local_revisions = self.rev_manager.get_revisions_in_range(range).await?;
+      let local_operations: DeltaOperations<EmptyAttributes> =
+        make_operations_from_revisions(local_revisions)?;
+      let (server_prime, local_prime) = server_operations.transform(&local_operations)?;
+      let change = pad.compose_remote_operations(server_prime)?;
+      let md5 = change.md5.clone();
+      self.record_remote_change(change);
+
+      let mut new_revisions = self.rev_manager.load_revisions().await?;
+      new_revisions.retain(|revision| revision.rev_id < first_rev_id);
+      new_revisions.extend(revisions);
+      self.rev_manager.reset_object(new_revisions).await?;
+      if !local_prime.is_empty() {
+        self
+          .rev_manager
+          .add_local_revision(local_prime.json_bytes(), md5)
+          .await?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Records the remote change as untracked, so the undo and redo items are transformed against
+  /// it instead of being applied to the outdated content.
+  fn record_remote_change(&self, change: RemoteChange) {
+    if let (Some(history), Some((operations, inverted_operations))) =
+      (&self.history, change.history_operations)
+    {
+      let _untracked = DatabaseHistoryUntracked::begin(history);
+      history
+        .lock()
+        .record_operations(operations, inverted_operations);
+    }
+  }
+}
+
+impl<P> RevisionWSDataStream for DatabaseRevisionWSDataStream<P>
+where
+  P: DatabaseSyncPad,
+{
+  fn receive_push_revision(&self, revisions: Vec<Revision>) -> BoxResultFuture<(), FlowyError> {
+    let stream = self.clone();
+    Box::pin(async move { stream.receive_revisions(revisions).await })
+  }
+
+  fn receive_ack(&self, rev_id: i64) -> BoxResultFuture<(), FlowyError> {
+    let ws_data_provider = self.ws_data_provider.clone();
+    Box::pin(async move { ws_data_provider.ack_data(rev_id).await })
+  }
+
+  fn receive_new_user_connect(
+    &self,
+    _new_user: NewDocumentUser,
+  ) -> BoxResultFuture<(), FlowyError> {
+    // Do nothing by now, just a placeholder for future extension.
+    Box::pin(async move { Ok(()) })
+  }
+
+  fn pull_revisions_in_range(&self, range: RevisionRange) -> BoxResultFuture<(), FlowyError> {
+    let rev_manager = self.rev_manager.clone();
+    let ws_data_provider = self.ws_data_provider.clone();
+    Box::pin(async move {
+      let revisions = rev_manager.get_revisions_in_range(range).await?;
+      ws_data_provider.send(revisions).await
+    })
+  }
+}
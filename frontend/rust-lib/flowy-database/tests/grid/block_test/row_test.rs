@@ -65,6 +65,31 @@ async fn grid_delete_row() {
   test.run_scripts(scripts).await;
 }
 
+#[tokio::test]
+async fn grid_duplicate_row() {
+  let mut test = DatabaseRowTest::new().await;
+  let row_count = test.row_revs.len();
+  let original_row = test.row_revs[0].clone();
+  let scripts = vec![
+    DuplicateRow {
+      row_id: original_row.id.clone(),
+    },
+    AssertRowCount(row_count + 1),
+    AssertBlock {
+      block_index: 0,
+      row_count: row_count as i32 + 1,
+      start_row_index: 0,
+    },
+  ];
+  test.run_scripts(scripts).await;
+
+  // The duplicated row is inserted right below the original row
+  let duplicated_row = test.row_revs[1].clone();
+  assert_ne!(duplicated_row.id, original_row.id);
+  assert_eq!(duplicated_row.block_id, original_row.block_id);
+  assert_eq!(duplicated_row.cells, original_row.cells);
+}
+
 #[tokio::test]
 async fn grid_row_add_cells_test() {
   let mut test = DatabaseRowTest::new().await;
@@ -25,6 +25,9 @@ pub enum RowScript {
   DeleteRows {
     row_ids: Vec<String>,
   },
+  DuplicateRow {
+    row_id: String,
+  },
   AssertCell {
     row_id: String,
     field_id: String,
@@ -112,6 +115,11 @@ impl DatabaseRowTest {
         self.row_revs = self.get_row_revs().await;
         self.block_meta_revs = self.editor.get_block_meta_revs().await.unwrap();
       },
+      RowScript::DuplicateRow { row_id } => {
+        self.editor.duplicate_row(&row_id).await.unwrap();
+        self.row_revs = self.get_row_revs().await;
+        self.block_meta_revs = self.editor.get_block_meta_revs().await.unwrap();
+      },
       RowScript::AssertCell {
         row_id,
         field_id,
@@ -282,6 +290,28 @@ impl DatabaseRowTest {
         assert_eq!(cell_data.content, expected);
         // assert_eq!(cell_data.url, expected);
       },
+      FieldType::Formula | FieldType::Rollup => {
+        let cell_data = self
+          .editor
+          .get_cell_protobuf(&cell_id)
+          .await
+          .unwrap()
+          .parser::<TextCellDataParser>()
+          .unwrap();
+
+        assert_eq!(cell_data.as_ref(), &expected);
+      },
+      FieldType::Relation => {
+        let cell_data = self
+          .editor
+          .get_cell_protobuf(&cell_id)
+          .await
+          .unwrap()
+          .parser::<RelationCellDataParser>()
+          .unwrap();
+
+        assert_eq!(cell_data.row_ids.join(RELATION_ROW_IDS_SEPARATOR), expected);
+      },
     }
   }
 }
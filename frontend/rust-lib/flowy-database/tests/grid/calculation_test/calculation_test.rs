@@ -0,0 +1,102 @@
+use crate::grid::calculation_test::script::CalculationScript::*;
+use crate::grid::calculation_test::script::*;
+use database_model::CalculationType;
+use flowy_database::entities::{FieldType, TextFilterConditionPB};
+
+// The texts of the initial rows are: "A", "", "C", "DA", "AE", "AE"
+// The checkboxes of the initial rows are: true, true, false, false, false, true
+// The numbers of the initial rows are: 1, 2, 3, 4, "", 5
+
+#[tokio::test]
+async fn grid_calculation_count_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let scripts = vec![
+    InsertCalculation {
+      field_type: FieldType::RichText,
+      calculation_type: CalculationType::Count,
+    },
+    AssertCalculationCount { count: 1 },
+    AssertCalculationValue { expected: "6" },
+    UpdateCalculation {
+      calculation_type: CalculationType::CountEmpty,
+    },
+    AssertCalculationCount { count: 1 },
+    AssertCalculationValue { expected: "1" },
+    UpdateCalculation {
+      calculation_type: CalculationType::CountUnique,
+    },
+    AssertCalculationValue { expected: "4" },
+    DeleteCalculation,
+    AssertCalculationCount { count: 0 },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_calculation_number_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let scripts = vec![
+    InsertCalculation {
+      field_type: FieldType::Number,
+      calculation_type: CalculationType::Sum,
+    },
+    AssertCalculationValue { expected: "$15.00" },
+    UpdateCalculation {
+      calculation_type: CalculationType::Average,
+    },
+    AssertCalculationValue { expected: "$3.00" },
+    UpdateCalculation {
+      calculation_type: CalculationType::Median,
+    },
+    AssertCalculationValue { expected: "$3.00" },
+    UpdateCalculation {
+      calculation_type: CalculationType::Max,
+    },
+    AssertCalculationValue { expected: "$5.00" },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_calculation_unsupported_field_type_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let scripts = vec![
+    InsertCalculation {
+      field_type: FieldType::RichText,
+      calculation_type: CalculationType::Sum,
+    },
+    AssertCalculationValue { expected: "" },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_calculation_percent_checked_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let scripts = vec![
+    InsertCalculation {
+      field_type: FieldType::Checkbox,
+      calculation_type: CalculationType::PercentChecked,
+    },
+    AssertCalculationValue { expected: "50%" },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_calculation_over_filtered_rows_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let scripts = vec![
+    InsertCalculation {
+      field_type: FieldType::Number,
+      calculation_type: CalculationType::Sum,
+    },
+    // The visible rows are "A", "DA", "AE", "AE"
+    CreateTextFilter {
+      condition: TextFilterConditionPB::Contains,
+      content: "A".to_string(),
+    },
+    AssertCalculationValue { expected: "$10.00" },
+  ];
+  test.run_scripts(scripts).await;
+}
@@ -0,0 +1,144 @@
+use crate::grid::database_editor::DatabaseEditorTest;
+use database_model::CalculationType;
+use flowy_database::entities::{
+  AlterCalculationParams, AlterFilterPayloadPB, CalculationPB, DeleteCalculationParams, FieldType,
+  TextFilterConditionPB, TextFilterPB,
+};
+
+pub enum CalculationScript {
+  InsertCalculation {
+    field_type: FieldType,
+    calculation_type: CalculationType,
+  },
+  UpdateCalculation {
+    calculation_type: CalculationType,
+  },
+  DeleteCalculation,
+  CreateTextFilter {
+    condition: TextFilterConditionPB,
+    content: String,
+  },
+  AssertCalculationCount {
+    count: usize,
+  },
+  AssertCalculationValue {
+    expected: &'static str,
+  },
+}
+
+pub struct DatabaseCalculationTest {
+  inner: DatabaseEditorTest,
+  current_calculation: Option<CalculationPB>,
+}
+
+impl DatabaseCalculationTest {
+  pub async fn new() -> Self {
+    let editor_test = DatabaseEditorTest::new_table().await;
+    Self {
+      inner: editor_test,
+      current_calculation: None,
+    }
+  }
+
+  pub async fn run_scripts(&mut self, scripts: Vec<CalculationScript>) {
+    for script in scripts {
+      self.run_script(script).await;
+    }
+  }
+
+  pub async fn run_script(&mut self, script: CalculationScript) {
+    match script {
+      CalculationScript::InsertCalculation {
+        field_type,
+        calculation_type,
+      } => {
+        let field_rev = self.get_first_field_rev(field_type).clone();
+        let params = AlterCalculationParams {
+          view_id: self.view_id.clone(),
+          field_id: field_rev.id.clone(),
+          calculation_id: None,
+          field_type: field_rev.ty,
+          calculation_type: calculation_type.into(),
+        };
+        let calculation_rev = self
+          .editor
+          .create_or_update_calculation(params)
+          .await
+          .unwrap();
+        self.current_calculation = Some(CalculationPB::from(&calculation_rev));
+      },
+      CalculationScript::UpdateCalculation { calculation_type } => {
+        let calculation = self.current_calculation.clone().unwrap();
+        let params = AlterCalculationParams {
+          view_id: self.view_id.clone(),
+          field_id: calculation.field_id.clone(),
+          calculation_id: Some(calculation.id.clone()),
+          field_type: calculation.field_type.into(),
+          calculation_type: calculation_type.into(),
+        };
+        let calculation_rev = self
+          .editor
+          .create_or_update_calculation(params)
+          .await
+          .unwrap();
+        self.current_calculation = Some(CalculationPB::from(&calculation_rev));
+      },
+      CalculationScript::DeleteCalculation => {
+        let calculation = self.current_calculation.take().unwrap();
+        let params = DeleteCalculationParams {
+          view_id: self.view_id.clone(),
+          field_id: calculation.field_id,
+          field_type: calculation.field_type,
+          calculation_id: calculation.id,
+        };
+        self.editor.delete_calculation(params).await.unwrap();
+      },
+      CalculationScript::CreateTextFilter { condition, content } => {
+        let field_rev = self.get_first_field_rev(FieldType::RichText).clone();
+        let text_filter = TextFilterPB { condition, content };
+        let payload = AlterFilterPayloadPB::new(&self.view_id, &field_rev, text_filter);
+        self
+          .editor
+          .create_or_update_filter(payload.try_into().unwrap())
+          .await
+          .unwrap();
+      },
+      CalculationScript::AssertCalculationCount { count } => {
+        let calculations = self
+          .editor
+          .get_all_calculations(&self.view_id)
+          .await
+          .unwrap();
+        assert_eq!(calculations.len(), count);
+      },
+      CalculationScript::AssertCalculationValue { expected } => {
+        let calculation = self.current_calculation.as_ref().unwrap();
+        let values = self
+          .editor
+          .get_calculation_values(&self.view_id)
+          .await
+          .unwrap();
+        let value = values
+          .items
+          .into_iter()
+          .find(|value| value.calculation_id == calculation.id)
+          .unwrap();
+        assert_eq!(value.value, expected);
+      },
+    }
+  }
+}
+
+impl std::ops::Deref for DatabaseCalculationTest {
+  type Target = DatabaseEditorTest;
+
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+impl std::ops::DerefMut for DatabaseCalculationTest {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.inner
+  }
+}
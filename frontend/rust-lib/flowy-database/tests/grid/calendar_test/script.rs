@@ -0,0 +1,171 @@
+use crate::grid::database_editor::DatabaseEditorTest;
+use flowy_database::entities::{
+  AlterFilterParams, AlterFilterPayloadPB, CalendarDayPB, CalendarEventRequestParams,
+  CalendarLayoutSettingsPB, CheckboxFilterConditionPB, CheckboxFilterPB, FieldType, LayoutTypePB,
+  MoveCalendarEventParams, UpdateCalendarSettingParams,
+};
+
+pub enum CalendarScript {
+  AssertLayoutFieldType(FieldType),
+  UpdateLayoutField {
+    field_type: FieldType,
+  },
+  AssertEventDays {
+    start_timestamp: i64,
+    end_timestamp: i64,
+    expected: Vec<(i64, Vec<&'static str>)>,
+  },
+  MoveEvent {
+    title: &'static str,
+    timestamp: i64,
+  },
+  AssertEventTimestamp {
+    title: &'static str,
+    timestamp: i64,
+  },
+  CreateCheckboxFilter {
+    condition: CheckboxFilterConditionPB,
+  },
+}
+
+pub struct DatabaseCalendarTest {
+  inner: DatabaseEditorTest,
+}
+
+impl DatabaseCalendarTest {
+  pub async fn new() -> Self {
+    let editor_test = DatabaseEditorTest::new(LayoutTypePB::Calendar).await;
+    Self { inner: editor_test }
+  }
+
+  pub async fn run_scripts(&mut self, scripts: Vec<CalendarScript>) {
+    for script in scripts {
+      self.run_script(script).await;
+    }
+  }
+
+  pub async fn run_script(&mut self, script: CalendarScript) {
+    match script {
+      CalendarScript::AssertLayoutFieldType(field_type) => {
+        let setting = self.calendar_setting().await;
+        let field_rev = self
+          .field_revs
+          .iter()
+          .find(|field_rev| field_rev.id == setting.layout_field_id)
+          .unwrap();
+        assert_eq!(FieldType::from(field_rev.ty), field_type);
+      },
+      CalendarScript::UpdateLayoutField { field_type } => {
+        let field_id = self.get_first_field_rev(field_type).id.clone();
+        let setting = CalendarLayoutSettingsPB {
+          layout_field_id: field_id,
+          first_day_of_week: 0,
+          show_weekends: true,
+        };
+        let params = UpdateCalendarSettingParams {
+          view_id: self.view_id.clone(),
+          setting: setting.into(),
+        };
+        let _ = self.editor.update_calendar_setting(params).await;
+      },
+      CalendarScript::AssertEventDays {
+        start_timestamp,
+        end_timestamp,
+        expected,
+      } => {
+        let days = self.calendar_days(start_timestamp, end_timestamp).await;
+        let days = days
+          .into_iter()
+          .map(|day| {
+            let titles = day
+              .events
+              .into_iter()
+              .map(|event| event.title)
+              .collect::<Vec<String>>();
+            (day.timestamp, titles)
+          })
+          .collect::<Vec<_>>();
+        let expected = expected
+          .into_iter()
+          .map(|(timestamp, titles)| {
+            let titles = titles
+              .into_iter()
+              .map(|title| title.to_owned())
+              .collect::<Vec<String>>();
+            (timestamp, titles)
+          })
+          .collect::<Vec<_>>();
+        assert_eq!(days, expected);
+      },
+      CalendarScript::MoveEvent { title, timestamp } => {
+        let row_id = self.row_id_with_title(title).await;
+        let params = MoveCalendarEventParams {
+          view_id: self.view_id.clone(),
+          row_id,
+          timestamp,
+        };
+        self.editor.move_calendar_event(params).await.unwrap();
+      },
+      CalendarScript::AssertEventTimestamp { title, timestamp } => {
+        let event = self
+          .calendar_days(i64::MIN, i64::MAX)
+          .await
+          .into_iter()
+          .flat_map(|day| day.events)
+          .find(|event| event.title == title)
+          .unwrap();
+        assert_eq!(event.timestamp, timestamp);
+      },
+      CalendarScript::CreateCheckboxFilter { condition } => {
+        let field_rev = self.get_first_field_rev(FieldType::Checkbox);
+        let payload =
+          AlterFilterPayloadPB::new(&self.view_id, field_rev, CheckboxFilterPB { condition });
+        let params: AlterFilterParams = payload.try_into().unwrap();
+        self.editor.create_or_update_filter(params).await.unwrap();
+      },
+    }
+  }
+
+  async fn calendar_setting(&self) -> CalendarLayoutSettingsPB {
+    self
+      .editor
+      .get_calendar_setting(&self.view_id)
+      .await
+      .unwrap()
+      .unwrap()
+  }
+
+  async fn calendar_days(&self, start_timestamp: i64, end_timestamp: i64) -> Vec<CalendarDayPB> {
+    let params = CalendarEventRequestParams {
+      view_id: self.view_id.clone(),
+      start_timestamp,
+      end_timestamp,
+    };
+    self.editor.get_calendar_events(params).await.unwrap()
+  }
+
+  async fn row_id_with_title(&self, title: &str) -> String {
+    self
+      .calendar_days(i64::MIN, i64::MAX)
+      .await
+      .into_iter()
+      .flat_map(|day| day.events)
+      .find(|event| event.title == title)
+      .map(|event| event.row_id)
+      .unwrap()
+  }
+}
+
+impl std::ops::Deref for DatabaseCalendarTest {
+  type Target = DatabaseEditorTest;
+
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+impl std::ops::DerefMut for DatabaseCalendarTest {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.inner
+  }
+}
@@ -0,0 +1,104 @@
+use crate::grid::calendar_test::script::CalendarScript::*;
+use crate::grid::calendar_test::script::DatabaseCalendarTest;
+
+use flowy_database::entities::{CheckboxFilterConditionPB, FieldType};
+
+// 1677628800 => Mar 1,2023 00:00 UTC
+const MARCH_START: i64 = 1677628800;
+// 1680307199 => Mar 31,2023 23:59:59 UTC
+const MARCH_END: i64 = 1680307199;
+// 1678060800 => Mar 6,2023 00:00 UTC
+const MARCH_6: i64 = 1678060800;
+// 1678233600 => Mar 8,2023 00:00 UTC
+const MARCH_8: i64 = 1678233600;
+// 1678406400 => Mar 10,2023 00:00 UTC
+const MARCH_10: i64 = 1678406400;
+// 1679270400 => Mar 20,2023 00:00 UTC
+const MARCH_20: i64 = 1679270400;
+
+#[tokio::test]
+async fn calendar_default_layout_field_test() {
+  let mut test = DatabaseCalendarTest::new().await;
+  let scripts = vec![AssertLayoutFieldType(FieldType::DateTime)];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn calendar_update_layout_field_with_non_date_field_test() {
+  let mut test = DatabaseCalendarTest::new().await;
+  let scripts = vec![
+    UpdateLayoutField {
+      field_type: FieldType::RichText,
+    },
+    AssertLayoutFieldType(FieldType::DateTime),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn calendar_get_events_test() {
+  let mut test = DatabaseCalendarTest::new().await;
+  let scripts = vec![AssertEventDays {
+    start_timestamp: MARCH_START,
+    end_timestamp: MARCH_END,
+    expected: vec![
+      (MARCH_6, vec!["A", "B"]),
+      (MARCH_8, vec!["C"]),
+      (MARCH_20, vec!["D"]),
+    ],
+  }];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn calendar_get_events_in_range_test() {
+  let mut test = DatabaseCalendarTest::new().await;
+  let scripts = vec![AssertEventDays {
+    start_timestamp: MARCH_8,
+    end_timestamp: MARCH_20 - 1,
+    expected: vec![(MARCH_8, vec!["C"])],
+  }];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn calendar_get_events_with_filter_test() {
+  let mut test = DatabaseCalendarTest::new().await;
+  let scripts = vec![
+    CreateCheckboxFilter {
+      condition: CheckboxFilterConditionPB::IsChecked,
+    },
+    AssertEventDays {
+      start_timestamp: MARCH_START,
+      end_timestamp: MARCH_END,
+      expected: vec![(MARCH_6, vec!["A"]), (MARCH_20, vec!["D"])],
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn calendar_move_event_test() {
+  let mut test = DatabaseCalendarTest::new().await;
+  let scripts = vec![
+    // Move the event to any time of Mar 10, the time of the day is kept.
+    MoveEvent {
+      title: "C",
+      timestamp: MARCH_10 + 3600,
+    },
+    AssertEventTimestamp {
+      title: "C",
+      timestamp: MARCH_10 + 9 * 3600,
+    },
+    AssertEventDays {
+      start_timestamp: MARCH_START,
+      end_timestamp: MARCH_END,
+      expected: vec![
+        (MARCH_6, vec!["A", "B"]),
+        (MARCH_10, vec!["C"]),
+        (MARCH_20, vec!["D"]),
+      ],
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
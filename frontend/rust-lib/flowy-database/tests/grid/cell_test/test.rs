@@ -5,7 +5,7 @@ use flowy_database::entities::{CellChangesetPB, FieldType};
 use flowy_database::services::cell::ToCellChangesetString;
 use flowy_database::services::field::selection_type_option::SelectOptionCellChangeset;
 use flowy_database::services::field::{
-  ChecklistTypeOptionPB, MultiSelectTypeOptionPB, SingleSelectTypeOptionPB,
+  ChecklistTypeOptionPB, MultiSelectTypeOptionPB, RelationCellChangeset, SingleSelectTypeOptionPB,
 };
 
 #[tokio::test]
@@ -43,6 +43,10 @@ async fn grid_cell_update() {
         },
         FieldType::Checkbox => "1".to_string(),
         FieldType::URL => "1".to_string(),
+        FieldType::Formula => "".to_string(),
+        FieldType::Relation => RelationCellChangeset::from_insert_row_ids(vec![row_rev.id.clone()])
+          .to_cell_changeset_str(),
+        FieldType::Rollup => "".to_string(),
       };
 
       scripts.push(UpdateCell {
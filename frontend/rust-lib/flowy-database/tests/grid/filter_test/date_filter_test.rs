@@ -106,3 +106,38 @@ async fn grid_filter_date_within_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn grid_filter_date_is_this_year_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.row_revs.len();
+  // All the dates are in 2022, they are evaluated against the current date.
+  let expected = 0;
+  let scripts = vec![
+    CreateRelativeDateFilter {
+      condition: DateFilterConditionPB::DateIsThisYear,
+      number_of_days: None,
+      changed: Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    },
+    AssertNumberOfVisibleRows { expected },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_date_within_past_days_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let expected = test.row_revs.len();
+  let scripts = vec![
+    CreateRelativeDateFilter {
+      condition: DateFilterConditionPB::DateWithinPastDays,
+      number_of_days: Some(365 * 100),
+      changed: None,
+    },
+    AssertNumberOfVisibleRows { expected },
+  ];
+  test.run_scripts(scripts).await;
+}
@@ -0,0 +1,152 @@
+use crate::grid::filter_test::script::FilterScript::*;
+use crate::grid::filter_test::script::*;
+use flowy_database::entities::{
+  AlterFilterPayloadPB, CheckboxFilterConditionPB, CheckboxFilterPB, FieldType, FilterOperatorPB,
+  NumberFilterConditionPB, NumberFilterPB, TextFilterConditionPB,
+};
+use flowy_database::services::filter::FilterType;
+
+// The texts of the initial rows are: "A", "", "C", "DA", "AE", "AE"
+// The checkboxes of the initial rows are: true, true, false, false, false, true
+// The numbers of the initial rows are: 1, 2, 3, 4, "", 5
+
+#[tokio::test]
+async fn grid_filter_group_or_operator_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let scripts = vec![
+    UpdateFilterGroupOperator {
+      group_id: None,
+      operator: FilterOperatorPB::Or,
+    },
+    CreateTextFilter {
+      condition: TextFilterConditionPB::TextIsEmpty,
+      content: "".to_string(),
+      changed: None,
+    },
+    CreateCheckboxFilter {
+      condition: CheckboxFilterConditionPB::IsChecked,
+      changed: None,
+    },
+    AssertFilterCount { count: 2 },
+    AssertNumberOfVisibleRows { expected: 3 },
+    UpdateFilterGroupOperator {
+      group_id: None,
+      operator: FilterOperatorPB::And,
+    },
+    AssertNumberOfVisibleRows { expected: 1 },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_group_same_field_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  test
+    .run_scripts(vec![
+      UpdateFilterGroupOperator {
+        group_id: None,
+        operator: FilterOperatorPB::Or,
+      },
+      CreateTextFilter {
+        condition: TextFilterConditionPB::Is,
+        content: "A".to_string(),
+        changed: None,
+      },
+    ])
+    .await;
+  let filter_a = test.grid_filters().await.pop().unwrap();
+
+  // The text is "A" or the text is "C"
+  test
+    .run_scripts(vec![
+      CreateTextFilter {
+        condition: TextFilterConditionPB::Is,
+        content: "C".to_string(),
+        changed: None,
+      },
+      AssertFilterCount { count: 2 },
+      AssertNumberOfVisibleRows { expected: 2 },
+      UpdateFilterGroupOperator {
+        group_id: None,
+        operator: FilterOperatorPB::And,
+      },
+      AssertNumberOfVisibleRows { expected: 0 },
+    ])
+    .await;
+
+  // Deleting one of the filters keeps the other filter of the same field
+  let field_rev = test.get_first_field_rev(FieldType::RichText).clone();
+  let filter_c = test
+    .grid_filters()
+    .await
+    .into_iter()
+    .find(|filter| filter.id != filter_a.id)
+    .unwrap();
+  test
+    .run_scripts(vec![
+      DeleteFilter {
+        filter_id: filter_c.id,
+        filter_type: FilterType::from(&field_rev),
+        changed: None,
+      },
+      AssertFilterCount { count: 1 },
+      AssertNumberOfVisibleRows { expected: 1 },
+    ])
+    .await;
+}
+
+#[tokio::test]
+async fn grid_filter_nested_group_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  test
+    .run_scripts(vec![
+      CreateTextFilter {
+        condition: TextFilterConditionPB::Contains,
+        content: "A".to_string(),
+        changed: None,
+      },
+      InsertFilterGroup {
+        parent_group_id: None,
+        operator: FilterOperatorPB::Or,
+      },
+      AssertFilterGroupCount { count: 2 },
+    ])
+    .await;
+
+  let group = test.get_filter_groups().await.pop().unwrap();
+  assert_eq!(group.parent_group_id, Some("".to_string()));
+
+  let field_rev = test.get_first_field_rev(FieldType::Checkbox).clone();
+  let filter = CheckboxFilterPB {
+    condition: CheckboxFilterConditionPB::IsChecked,
+  };
+  let mut checkbox_payload = AlterFilterPayloadPB::new(&test.view_id(), &field_rev, filter);
+  checkbox_payload.group_id = Some(group.id.clone());
+
+  let field_rev = test.get_first_field_rev(FieldType::Number).clone();
+  let filter = NumberFilterPB {
+    condition: NumberFilterConditionPB::GreaterThan,
+    content: "3".to_string(),
+  };
+  let mut number_payload = AlterFilterPayloadPB::new(&test.view_id(), &field_rev, filter);
+  number_payload.group_id = Some(group.id.clone());
+
+  // The text contains "A" and (the checkbox is checked or the number is greater than 3)
+  test
+    .run_scripts(vec![
+      InsertFilter {
+        payload: checkbox_payload,
+      },
+      InsertFilter {
+        payload: number_payload,
+      },
+      AssertFilterCount { count: 3 },
+      AssertNumberOfVisibleRows { expected: 3 },
+      // Deleting the group deletes the filters in it
+      DeleteFilterGroup { group_id: group.id },
+      AssertFilterGroupCount { count: 1 },
+      AssertFilterCount { count: 1 },
+      AssertNumberOfVisibleRows { expected: 4 },
+    ])
+    .await;
+}
@@ -7,7 +7,7 @@ use std::time::Duration;
 use bytes::Bytes;
 use futures::TryFutureExt;
 use tokio::sync::broadcast::Receiver;
-use flowy_database::entities::{AlterFilterParams, AlterFilterPayloadPB, DeleteFilterParams, LayoutTypePB, DatabaseSettingChangesetParams, DatabaseViewSettingPB, RowPB, TextFilterConditionPB, FieldType, NumberFilterConditionPB, CheckboxFilterConditionPB, DateFilterConditionPB, DateFilterContentPB, SelectOptionConditionPB, TextFilterPB, NumberFilterPB, CheckboxFilterPB, DateFilterPB, SelectOptionFilterPB, CellChangesetPB, FilterPB, ChecklistFilterConditionPB, ChecklistFilterPB};
+use flowy_database::entities::{AlterFilterParams, AlterFilterPayloadPB, DeleteFilterParams, LayoutTypePB, DatabaseSettingChangesetParams, DatabaseViewSettingPB, RowPB, TextFilterConditionPB, FieldType, NumberFilterConditionPB, CheckboxFilterConditionPB, DateFilterConditionPB, DateFilterContentPB, SelectOptionConditionPB, TextFilterPB, NumberFilterPB, CheckboxFilterPB, DateFilterPB, SelectOptionFilterPB, CellChangesetPB, FilterPB, ChecklistFilterConditionPB, ChecklistFilterPB, FilterOperatorPB, FilterGroupPB, InsertFilterGroupPayloadPB, UpdateFilterGroupPayloadPB, DeleteFilterGroupPayloadPB};
 use flowy_database::services::field::{SelectOptionCellChangeset, SelectOptionIds};
 use flowy_database::services::setting::GridSettingChangesetBuilder;
 use database_model::{FieldRevision, FieldTypeRevision};
@@ -63,6 +63,11 @@ pub enum FilterScript {
         timestamp: Option<i64>,
         changed: Option<FilterRowChanged>,
     },
+    CreateRelativeDateFilter {
+        condition: DateFilterConditionPB,
+        number_of_days: Option<i64>,
+        changed: Option<FilterRowChanged>,
+    },
     CreateMultiSelectFilter {
         condition: SelectOptionConditionPB,
         option_ids: Vec<String>,
@@ -92,6 +97,20 @@ pub enum FilterScript {
     AssertNumberOfVisibleRows {
         expected: usize,
     },
+    InsertFilterGroup {
+        parent_group_id: Option<String>,
+        operator: FilterOperatorPB,
+    },
+    UpdateFilterGroupOperator {
+        group_id: Option<String>,
+        operator: FilterOperatorPB,
+    },
+    DeleteFilterGroup {
+        group_id: String,
+    },
+    AssertFilterGroupCount {
+        count: usize,
+    },
     #[allow(dead_code)]
     AssertGridSetting {
         expected_setting: DatabaseViewSettingPB,
@@ -121,6 +140,11 @@ impl DatabaseFilterTest {
         self.editor.get_all_filters().await.unwrap()
     }
 
+    /// Returns the filter groups of the view, the first one is the root group.
+    pub async fn get_filter_groups(&self) -> Vec<FilterGroupPB> {
+        self.editor.get_setting().await.unwrap().filter_groups.items
+    }
+
     pub async fn run_scripts(&mut self, scripts: Vec<FilterScript>) {
         for script in scripts {
             self.run_script(script).await;
@@ -166,7 +190,8 @@ impl DatabaseFilterTest {
                     filter_id: Some(filter.id),
                     field_type: filter.field_type.into(),
                     condition: condition as u8,
-                    content
+                    content,
+                    group_id: None,
                 };
                 self.editor.create_or_update_filter(params).await.unwrap();
             }
@@ -203,7 +228,22 @@ impl DatabaseFilterTest {
                     condition,
                     start,
                     end,
-                    timestamp
+                    timestamp,
+                    number_of_days: None,
+                };
+
+                let payload =
+                    AlterFilterPayloadPB::new( &self.view_id(), field_rev, date_filter);
+                self.insert_filter(payload).await;
+            }
+            FilterScript::CreateRelativeDateFilter { condition, number_of_days, changed} => {
+                self.recv = Some(self.editor.subscribe_view_changed(&self.view_id()).await.unwrap());
+                self.assert_future_changed(changed).await;
+                let field_rev = self.get_first_field_rev(FieldType::DateTime);
+                let date_filter = DateFilterPB {
+                    condition,
+                    number_of_days,
+                    ..Default::default()
                 };
 
                 let payload =
@@ -261,6 +301,21 @@ impl DatabaseFilterTest {
                 let grid = self.editor.get_database(&self.view_id()).await.unwrap();
                 assert_eq!(grid.rows.len(), expected);
             }
+            FilterScript::InsertFilterGroup { parent_group_id, operator } => {
+                let payload = InsertFilterGroupPayloadPB { view_id: self.view_id(), parent_group_id, operator };
+                self.editor.insert_filter_group(payload.try_into().unwrap()).await.unwrap();
+            }
+            FilterScript::UpdateFilterGroupOperator { group_id, operator } => {
+                let payload = UpdateFilterGroupPayloadPB { view_id: self.view_id(), group_id, operator };
+                self.editor.update_filter_group(payload.try_into().unwrap()).await.unwrap();
+            }
+            FilterScript::DeleteFilterGroup { group_id } => {
+                let payload = DeleteFilterGroupPayloadPB { view_id: self.view_id(), group_id };
+                self.editor.delete_filter_group(payload.try_into().unwrap()).await.unwrap();
+            }
+            FilterScript::AssertFilterGroupCount { count } => {
+                assert_eq!(self.get_filter_groups().await.len(), count);
+            }
             FilterScript::Wait { millisecond } => {
                 tokio::time::sleep(Duration::from_millis(millisecond)).await;
             }
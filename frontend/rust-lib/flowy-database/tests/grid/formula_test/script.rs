@@ -0,0 +1,126 @@
+use crate::grid::database_editor::DatabaseEditorTest;
+use bytes::Bytes;
+use flowy_database::entities::{CellIdParams, FieldChangesetParams, FieldType};
+use flowy_database::services::field::{FormulaResultTypePB, FormulaTypeOptionPB};
+
+pub enum FormulaScript {
+  UpdateExpression {
+    expression: &'static str,
+  },
+  UpdatePrice {
+    row_index: usize,
+    content: &'static str,
+  },
+  RenamePrice {
+    name: &'static str,
+  },
+  DeletePrice,
+  SwitchPriceToText,
+  Undo,
+  AssertExpression(&'static str),
+  AssertResultType(FormulaResultTypePB),
+  AssertCellContents(Vec<&'static str>),
+}
+
+pub struct DatabaseFormulaTest {
+  inner: DatabaseEditorTest,
+}
+
+impl DatabaseFormulaTest {
+  pub async fn new() -> Self {
+    let inner = DatabaseEditorTest::new_table().await;
+    Self { inner }
+  }
+
+  pub async fn run_scripts(&mut self, scripts: Vec<FormulaScript>) {
+    for script in scripts {
+      self.run_script(script).await;
+    }
+  }
+
+  pub async fn run_script(&mut self, script: FormulaScript) {
+    let formula_field_id = self.get_first_field_rev(FieldType::Formula).id.clone();
+    match script {
+      FormulaScript::UpdateExpression { expression } => {
+        let type_option = FormulaTypeOptionPB {
+          expression: expression.to_owned(),
+          ..Default::default()
+        };
+        let bytes: Bytes = type_option.try_into().unwrap();
+        self
+          .editor
+          .update_field_type_option(&formula_field_id, bytes.to_vec(), None)
+          .await
+          .unwrap();
+      },
+      FormulaScript::UpdatePrice { row_index, content } => {
+        let field_id = self.get_first_field_rev(FieldType::Number).id.clone();
+        let row_id = self.row_revs[row_index].id.clone();
+        self
+          .update_cell(&field_id, row_id, content.to_owned())
+          .await;
+      },
+      FormulaScript::RenamePrice { name } => {
+        let changeset = FieldChangesetParams {
+          field_id: self.get_first_field_rev(FieldType::Number).id.clone(),
+          database_id: self.view_id.clone(),
+          name: Some(name.to_owned()),
+          ..Default::default()
+        };
+        self.editor.update_field(changeset).await.unwrap();
+      },
+      FormulaScript::DeletePrice => {
+        let field_id = self.get_first_field_rev(FieldType::Number).id.clone();
+        self.editor.delete_field(&field_id).await.unwrap();
+      },
+      FormulaScript::SwitchPriceToText => {
+        let field_id = self.get_first_field_rev(FieldType::Number).id.clone();
+        self
+          .editor
+          .switch_to_field_type(&field_id, &FieldType::RichText)
+          .await
+          .unwrap();
+      },
+      FormulaScript::Undo => {
+        self.editor.undo().await.unwrap();
+      },
+      FormulaScript::AssertExpression(expected) => {
+        let field_rev = self.editor.get_field_rev(&formula_field_id).await.unwrap();
+        let type_option = FormulaTypeOptionPB::from(field_rev.as_ref());
+        assert_eq!(type_option.expression, expected);
+      },
+      FormulaScript::AssertResultType(expected) => {
+        let field_rev = self.editor.get_field_rev(&formula_field_id).await.unwrap();
+        let type_option = FormulaTypeOptionPB::from(field_rev.as_ref());
+        assert_eq!(type_option.result_type, expected);
+      },
+      FormulaScript::AssertCellContents(expected) => {
+        let row_revs = self.get_row_revs().await;
+        let mut contents = vec![];
+        for row_rev in row_revs {
+          let params = CellIdParams {
+            database_id: self.view_id.clone(),
+            field_id: formula_field_id.clone(),
+            row_id: row_rev.id.clone(),
+          };
+          contents.push(self.editor.get_cell_display_str(&params).await);
+        }
+        assert_eq!(contents, expected);
+      },
+    }
+  }
+}
+
+impl std::ops::Deref for DatabaseFormulaTest {
+  type Target = DatabaseEditorTest;
+
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+impl std::ops::DerefMut for DatabaseFormulaTest {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.inner
+  }
+}
@@ -0,0 +1,127 @@
+use crate::grid::formula_test::script::DatabaseFormulaTest;
+use crate::grid::formula_test::script::FormulaScript::*;
+use flowy_database::services::field::FormulaResultTypePB;
+
+#[tokio::test]
+async fn grid_formula_calculate_all_cells_test() {
+  let mut test = DatabaseFormulaTest::new().await;
+  let scripts = vec![
+    UpdateExpression {
+      expression: "{Price} * 2",
+    },
+    AssertResultType(FormulaResultTypePB::Number),
+    AssertCellContents(vec!["2", "4", "6", "8", "", "10"]),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_recalculate_after_updating_cell_test() {
+  let mut test = DatabaseFormulaTest::new().await;
+  let scripts = vec![
+    UpdateExpression {
+      expression: "{Price} + 1",
+    },
+    UpdatePrice {
+      row_index: 0,
+      content: "10",
+    },
+    UpdatePrice {
+      row_index: 4,
+      content: "1.5",
+    },
+    AssertCellContents(vec!["11", "3", "4", "5", "2.5", "6"]),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_undo_referenced_cell_test() {
+  let mut test = DatabaseFormulaTest::new().await;
+  let scripts = vec![
+    UpdateExpression {
+      expression: "{Price} * 2",
+    },
+    UpdatePrice {
+      row_index: 0,
+      content: "10",
+    },
+    AssertCellContents(vec!["20", "4", "6", "8", "", "10"]),
+    // The formula cells are not saved, so undoing the edit of the price reverts them too.
+    Undo,
+    AssertCellContents(vec!["2", "4", "6", "8", "", "10"]),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_text_result_test() {
+  let mut test = DatabaseFormulaTest::new().await;
+  let scripts = vec![
+    UpdateExpression {
+      expression: "if({Price} > 3, \"High\", \"Low\")",
+    },
+    AssertResultType(FormulaResultTypePB::Text),
+    AssertCellContents(vec!["Low", "Low", "Low", "High", "Low", "High"]),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_invalid_expression_test() {
+  let mut test = DatabaseFormulaTest::new().await;
+  let scripts = vec![
+    UpdateExpression {
+      expression: "{Price} *",
+    },
+    AssertResultType(FormulaResultTypePB::Text),
+    AssertCellContents(vec!["", "", "", "", "", ""]),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_rename_referenced_field_test() {
+  let mut test = DatabaseFormulaTest::new().await;
+  let scripts = vec![
+    UpdateExpression {
+      expression: "{Price} * 2",
+    },
+    RenamePrice { name: "Cost" },
+    AssertExpression("{Cost} * 2"),
+    UpdatePrice {
+      row_index: 0,
+      content: "10",
+    },
+    AssertCellContents(vec!["20", "4", "6", "8", "", "10"]),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_delete_referenced_field_test() {
+  let mut test = DatabaseFormulaTest::new().await;
+  let scripts = vec![
+    UpdateExpression {
+      expression: "{Price} * 2",
+    },
+    DeletePrice,
+    AssertResultType(FormulaResultTypePB::Text),
+    AssertCellContents(vec!["", "", "", "", "", ""]),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_switch_referenced_field_type_test() {
+  let mut test = DatabaseFormulaTest::new().await;
+  let scripts = vec![
+    UpdateExpression {
+      expression: "{Price} + 1",
+    },
+    AssertResultType(FormulaResultTypePB::Number),
+    SwitchPriceToText,
+    AssertResultType(FormulaResultTypePB::Text),
+  ];
+  test.run_scripts(scripts).await;
+}
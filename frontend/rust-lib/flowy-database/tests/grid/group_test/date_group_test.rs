@@ -0,0 +1,127 @@
+use crate::grid::group_test::script::DatabaseGroupTest;
+use crate::grid::group_test::script::GroupScript::*;
+
+// The dates of the initial rows are: Mar 14,2022, Mar 14,2022, Mar 14,2022, Nov 17,2022,
+// Nov 13,2022. They are far from today, so they are grouped by month.
+
+#[tokio::test]
+async fn group_group_by_date_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let date_field = test.get_date_field().await;
+  let scripts = vec![
+    GroupByField {
+      field_id: date_field.id.clone(),
+    },
+    // no status group
+    AssertGroupRowCount {
+      group_index: 0,
+      row_count: 0,
+    },
+    // Mar 2022
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 3,
+    },
+    // Nov 2022
+    AssertGroupRowCount {
+      group_index: 2,
+      row_count: 2,
+    },
+    AssertGroupCount(3),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_alter_date_to_new_month_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let date_field = test.get_date_field().await;
+  let scripts = vec![
+    GroupByField {
+      field_id: date_field.id.clone(),
+    },
+    // 1675209600 => Feb 1,2023
+    UpdateGroupedCellWithData {
+      from_group_index: 1,
+      row_index: 0,
+      cell_data: "1675209600".to_string(),
+    },
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 2,
+    },
+    AssertGroupRowCount {
+      group_index: 3,
+      row_count: 1,
+    },
+    AssertGroupCount(4),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_move_date_group_row_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let date_field = test.get_date_field().await;
+  let scripts = vec![
+    GroupByField {
+      field_id: date_field.id.clone(),
+    },
+    MoveRow {
+      from_group_index: 1,
+      from_row_index: 0,
+      to_group_index: 2,
+      to_row_index: 0,
+    },
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 2,
+    },
+    AssertGroupRowCount {
+      group_index: 2,
+      row_count: 3,
+    },
+  ];
+  test.run_scripts(scripts).await;
+
+  // The timestamp of the moved row is rewritten to the first day of the month
+  let row = test.row_at_index(2, 0).await;
+  let row_rev = test
+    .get_row_revs()
+    .await
+    .into_iter()
+    .find(|row_rev| row_rev.id == row.id)
+    .unwrap();
+  let cell_rev = row_rev.cells.get(&date_field.id).unwrap();
+  // 1667260800 => Nov 1,2022
+  assert!(cell_rev.type_cell_data.contains("1667260800"));
+}
+
+#[tokio::test]
+async fn group_move_last_row_deletes_date_group_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let date_field = test.get_date_field().await;
+  let scripts = vec![
+    GroupByField {
+      field_id: date_field.id.clone(),
+    },
+    MoveRow {
+      from_group_index: 2,
+      from_row_index: 0,
+      to_group_index: 1,
+      to_row_index: 0,
+    },
+    MoveRow {
+      from_group_index: 2,
+      from_row_index: 0,
+      to_group_index: 1,
+      to_row_index: 0,
+    },
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 5,
+    },
+    AssertGroupCount(2),
+  ];
+  test.run_scripts(scripts).await;
+}
@@ -1,3 +1,5 @@
+mod date_group_test;
+mod number_group_test;
 mod script;
 mod test;
 mod url_group_test;
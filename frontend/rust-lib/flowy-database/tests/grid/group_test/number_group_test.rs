@@ -0,0 +1,143 @@
+use crate::grid::group_test::script::DatabaseGroupTest;
+use crate::grid::group_test::script::GroupScript::*;
+use flowy_database::entities::{NumberGroupConditionPB, NumberGroupConfigurationPB};
+
+// The numbers of the initial rows are: 1, 2, 3, 4 and an empty cell.
+
+#[tokio::test]
+async fn group_group_by_number_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let number_field = test.get_number_field().await;
+  let scripts = vec![
+    GroupByField {
+      field_id: number_field.id.clone(),
+    },
+    // no status group
+    AssertGroupRowCount {
+      group_index: 0,
+      row_count: 1,
+    },
+    // $0.00 - $10.00
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 4,
+    },
+    AssertGroupCount(2),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_update_number_group_interval_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let number_field = test.get_number_field().await;
+  let scripts = vec![
+    UpdateNumberGroupConfiguration {
+      field_id: number_field.id.clone(),
+      configuration: NumberGroupConfigurationPB {
+        condition: NumberGroupConditionPB::Range,
+        interval: "2".to_owned(),
+        ..Default::default()
+      },
+    },
+    // 0 - 2
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 1,
+    },
+    // 2 - 4
+    AssertGroupRowCount {
+      group_index: 2,
+      row_count: 2,
+    },
+    // 4 - 6
+    AssertGroupRowCount {
+      group_index: 3,
+      row_count: 1,
+    },
+    AssertGroupCount(4),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_update_number_group_breakpoints_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let number_field = test.get_number_field().await;
+  let scripts = vec![
+    UpdateNumberGroupConfiguration {
+      field_id: number_field.id.clone(),
+      configuration: NumberGroupConfigurationPB {
+        condition: NumberGroupConditionPB::Breakpoint,
+        breakpoints: vec!["2".to_owned(), "4".to_owned(), "8".to_owned()],
+        ..Default::default()
+      },
+    },
+    // < 4
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 3,
+    },
+    // 4 - 8
+    AssertGroupRowCount {
+      group_index: 2,
+      row_count: 1,
+    },
+    // ≥ 8, the empty group is kept because the hide_empty is false
+    AssertGroupRowCount {
+      group_index: 3,
+      row_count: 0,
+    },
+    AssertGroupCount(4),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_update_number_group_distinct_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let number_field = test.get_number_field().await;
+  let scripts = vec![
+    UpdateNumberGroupConfiguration {
+      field_id: number_field.id.clone(),
+      configuration: NumberGroupConfigurationPB {
+        condition: NumberGroupConditionPB::Distinct,
+        ..Default::default()
+      },
+    },
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 1,
+    },
+    AssertGroupCount(5),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_move_number_group_row_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let number_field = test.get_number_field().await;
+  let scripts = vec![
+    UpdateNumberGroupConfiguration {
+      field_id: number_field.id.clone(),
+      configuration: NumberGroupConfigurationPB {
+        condition: NumberGroupConditionPB::Distinct,
+        ..Default::default()
+      },
+    },
+    MoveRow {
+      from_group_index: 1,
+      from_row_index: 0,
+      to_group_index: 2,
+      to_row_index: 0,
+    },
+    // The group of the number 1 is removed after moving its only row
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 2,
+    },
+    AssertGroupCount(4),
+  ];
+  test.run_scripts(scripts).await;
+}
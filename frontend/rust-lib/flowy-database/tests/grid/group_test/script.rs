@@ -1,10 +1,11 @@
 use crate::grid::database_editor::DatabaseEditorTest;
 use database_model::{FieldRevision, RowChangeset};
 use flowy_database::entities::{
-  CreateRowParams, FieldType, GroupPB, LayoutTypePB, MoveGroupParams, MoveGroupRowParams, RowPB,
+  CreateRowParams, FieldType, GroupPB, LayoutTypePB, MoveGroupParams, MoveGroupRowParams,
+  NumberGroupConfigurationPB, RowPB, UpdateNumberGroupConfigurationParams,
 };
 use flowy_database::services::cell::{
-  delete_select_option_cell, insert_select_option_cell, insert_url_cell,
+  delete_select_option_cell, insert_date_cell, insert_select_option_cell, insert_url_cell,
 };
 use flowy_database::services::field::{
   edit_single_select_type_option, SelectOptionPB, SelectTypeOptionSharedAction,
@@ -40,6 +41,10 @@ pub enum GroupScript {
     group_index: usize,
     row_index: usize,
   },
+  DuplicateRow {
+    group_index: usize,
+    row_index: usize,
+  },
   UpdateGroupedCell {
     from_group_index: usize,
     row_index: usize,
@@ -60,6 +65,10 @@ pub enum GroupScript {
   GroupByField {
     field_id: String,
   },
+  UpdateNumberGroupConfiguration {
+    field_id: String,
+    configuration: NumberGroupConfigurationPB,
+  },
 }
 
 pub struct DatabaseGroupTest {
@@ -141,6 +150,13 @@ impl DatabaseGroupTest {
         let row = self.row_at_index(group_index, row_index).await;
         self.editor.delete_row(&row.id).await.unwrap();
       },
+      GroupScript::DuplicateRow {
+        group_index,
+        row_index,
+      } => {
+        let row = self.row_at_index(group_index, row_index).await;
+        self.editor.duplicate_row(&row.id).await.unwrap();
+      },
       GroupScript::UpdateGroupedCell {
         from_group_index,
         row_index,
@@ -195,6 +211,7 @@ impl DatabaseGroupTest {
         let field_type: FieldType = field_rev.ty.into();
         let cell_rev = match field_type {
           FieldType::URL => insert_url_cell(cell_data, &field_rev),
+          FieldType::DateTime => insert_date_cell(cell_data.parse::<i64>().unwrap(), &field_rev),
           _ => {
             panic!("Unsupported group field type");
           },
@@ -239,6 +256,21 @@ impl DatabaseGroupTest {
       GroupScript::GroupByField { field_id } => {
         self.editor.group_by_field(&field_id).await.unwrap();
       },
+      GroupScript::UpdateNumberGroupConfiguration {
+        field_id,
+        configuration,
+      } => {
+        let params = UpdateNumberGroupConfigurationParams {
+          view_id: self.view_id.clone(),
+          field_id,
+          configuration: configuration.into(),
+        };
+        self
+          .editor
+          .update_number_group_configuration(params)
+          .await
+          .unwrap();
+      },
     }
   }
 
@@ -290,6 +322,32 @@ impl DatabaseGroupTest {
       .unwrap();
   }
 
+  pub async fn get_date_field(&self) -> Arc<FieldRevision> {
+    self
+      .inner
+      .field_revs
+      .iter()
+      .find(|field_rev| {
+        let field_type: FieldType = field_rev.ty.into();
+        field_type.is_date()
+      })
+      .unwrap()
+      .clone()
+  }
+
+  pub async fn get_number_field(&self) -> Arc<FieldRevision> {
+    self
+      .inner
+      .field_revs
+      .iter()
+      .find(|field_rev| {
+        let field_type: FieldType = field_rev.ty.into();
+        field_type.is_number()
+      })
+      .unwrap()
+      .clone()
+  }
+
   pub async fn get_url_field(&self) -> Arc<FieldRevision> {
     self
       .inner
@@ -237,6 +237,37 @@ async fn group_delete_row_test() {
   test.run_scripts(scripts).await;
 }
 
+#[tokio::test]
+async fn group_duplicate_row_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let group = test.group_at_index(1).await;
+  let scripts = vec![
+    DuplicateRow {
+      group_index: 1,
+      row_index: 0,
+    },
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 3,
+    },
+    AssertGroupRowCount {
+      group_index: 2,
+      row_count: 2,
+    },
+    AssertRow {
+      group_index: 1,
+      row_index: 0,
+      row: group.rows.get(0).unwrap().clone(),
+    },
+    AssertRow {
+      group_index: 1,
+      row_index: 2,
+      row: group.rows.get(1).unwrap().clone(),
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
 #[tokio::test]
 async fn group_delete_all_row_test() {
   let mut test = DatabaseGroupTest::new().await;
@@ -0,0 +1,139 @@
+use crate::grid::database_editor::DatabaseEditorTest;
+use flowy_database::entities::{CellIdParams, CreateRowParams, FieldType, LayoutTypePB};
+
+pub enum HistoryScript {
+  UpdateTextCell {
+    row_index: usize,
+    content: &'static str,
+  },
+  CreateEmptyRow,
+  DeleteRow {
+    row_index: usize,
+  },
+  DeleteField {
+    field_type: FieldType,
+  },
+  SwitchToFieldType {
+    field_id: String,
+    field_type: FieldType,
+  },
+  Undo,
+  Redo,
+  AssertTextCell {
+    row_index: usize,
+    expected: &'static str,
+  },
+  AssertRowCount(usize),
+  AssertFieldCount(usize),
+  AssertFieldType {
+    field_id: String,
+    field_type: FieldType,
+  },
+  AssertCanUndo(bool),
+  AssertCanRedo(bool),
+}
+
+pub struct DatabaseHistoryTest {
+  inner: DatabaseEditorTest,
+}
+
+impl DatabaseHistoryTest {
+  pub async fn new() -> Self {
+    let editor_test = DatabaseEditorTest::new_table().await;
+    Self { inner: editor_test }
+  }
+
+  pub async fn run_scripts(&mut self, scripts: Vec<HistoryScript>) {
+    for script in scripts {
+      self.run_script(script).await;
+    }
+  }
+
+  pub async fn run_script(&mut self, script: HistoryScript) {
+    match script {
+      HistoryScript::UpdateTextCell { row_index, content } => {
+        let row_id = self.get_row_revs().await[row_index].id.clone();
+        self.update_text_cell(row_id, content).await;
+      },
+      HistoryScript::CreateEmptyRow => {
+        let params = CreateRowParams {
+          view_id: self.view_id.clone(),
+          start_row_id: None,
+          group_id: None,
+          layout: LayoutTypePB::Grid,
+        };
+        self.editor.create_row(params).await.unwrap();
+      },
+      HistoryScript::DeleteRow { row_index } => {
+        let row_id = self.get_row_revs().await[row_index].id.clone();
+        self.editor.delete_row(&row_id).await.unwrap();
+      },
+      HistoryScript::DeleteField { field_type } => {
+        let field_id = self.get_first_field_rev(field_type).id.clone();
+        self.editor.delete_field(&field_id).await.unwrap();
+      },
+      HistoryScript::SwitchToFieldType {
+        field_id,
+        field_type,
+      } => {
+        self
+          .editor
+          .switch_to_field_type(&field_id, &field_type)
+          .await
+          .unwrap();
+      },
+      HistoryScript::Undo => {
+        self.editor.undo().await.unwrap();
+      },
+      HistoryScript::Redo => {
+        self.editor.redo().await.unwrap();
+      },
+      HistoryScript::AssertTextCell {
+        row_index,
+        expected,
+      } => {
+        let params = CellIdParams {
+          database_id: self.view_id.clone(),
+          field_id: self.get_first_field_rev(FieldType::RichText).id.clone(),
+          row_id: self.get_row_revs().await[row_index].id.clone(),
+        };
+        let content = self.editor.get_cell_display_str(&params).await;
+        assert_eq!(content, expected);
+      },
+      HistoryScript::AssertRowCount(expected) => {
+        assert_eq!(self.get_row_revs().await.len(), expected);
+      },
+      HistoryScript::AssertFieldCount(expected) => {
+        let field_revs = self.editor.get_field_revs(None).await.unwrap();
+        assert_eq!(field_revs.len(), expected);
+      },
+      HistoryScript::AssertFieldType {
+        field_id,
+        field_type,
+      } => {
+        let field_rev = self.editor.get_field_rev(&field_id).await.unwrap();
+        assert_eq!(FieldType::from(field_rev.ty), field_type);
+      },
+      HistoryScript::AssertCanUndo(expected) => {
+        assert_eq!(self.editor.can_undo(), expected);
+      },
+      HistoryScript::AssertCanRedo(expected) => {
+        assert_eq!(self.editor.can_redo(), expected);
+      },
+    }
+  }
+}
+
+impl std::ops::Deref for DatabaseHistoryTest {
+  type Target = DatabaseEditorTest;
+
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+impl std::ops::DerefMut for DatabaseHistoryTest {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.inner
+  }
+}
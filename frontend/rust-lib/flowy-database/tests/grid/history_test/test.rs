@@ -0,0 +1,156 @@
+use crate::grid::history_test::script::DatabaseHistoryTest;
+use crate::grid::history_test::script::HistoryScript::*;
+use flowy_database::entities::FieldType;
+
+#[tokio::test]
+async fn undo_redo_update_cell_test() {
+  let mut test = DatabaseHistoryTest::new().await;
+  let scripts = vec![
+    AssertCanUndo(false),
+    UpdateTextCell {
+      row_index: 0,
+      content: "hello",
+    },
+    AssertTextCell {
+      row_index: 0,
+      expected: "hello",
+    },
+    Undo,
+    AssertTextCell {
+      row_index: 0,
+      expected: "A",
+    },
+    AssertCanRedo(true),
+    Redo,
+    AssertTextCell {
+      row_index: 0,
+      expected: "hello",
+    },
+    AssertCanRedo(false),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn undo_multiple_cell_updates_test() {
+  let mut test = DatabaseHistoryTest::new().await;
+  let scripts = vec![
+    UpdateTextCell {
+      row_index: 0,
+      content: "hello",
+    },
+    UpdateTextCell {
+      row_index: 1,
+      content: "world",
+    },
+    Undo,
+    AssertTextCell {
+      row_index: 1,
+      expected: "",
+    },
+    AssertTextCell {
+      row_index: 0,
+      expected: "hello",
+    },
+    Undo,
+    AssertTextCell {
+      row_index: 0,
+      expected: "A",
+    },
+    AssertCanUndo(false),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn undo_redo_create_row_test() {
+  let mut test = DatabaseHistoryTest::new().await;
+  let scripts = vec![
+    CreateEmptyRow,
+    AssertRowCount(7),
+    Undo,
+    AssertRowCount(6),
+    AssertCanUndo(false),
+    Redo,
+    AssertRowCount(7),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn undo_delete_row_test() {
+  let mut test = DatabaseHistoryTest::new().await;
+  let scripts = vec![
+    DeleteRow { row_index: 0 },
+    AssertRowCount(5),
+    AssertTextCell {
+      row_index: 0,
+      expected: "",
+    },
+    Undo,
+    AssertRowCount(6),
+    AssertTextCell {
+      row_index: 0,
+      expected: "A",
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn undo_redo_delete_field_test() {
+  let mut test = DatabaseHistoryTest::new().await;
+  let field_count = test.field_count;
+  let scripts = vec![
+    DeleteField {
+      field_type: FieldType::Checkbox,
+    },
+    AssertFieldCount(field_count - 1),
+    Undo,
+    AssertFieldCount(field_count),
+    Redo,
+    AssertFieldCount(field_count - 1),
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn undo_switch_field_type_test() {
+  let mut test = DatabaseHistoryTest::new().await;
+  let field_id = test.get_first_field_rev(FieldType::Checkbox).id.clone();
+  let scripts = vec![
+    SwitchToFieldType {
+      field_id: field_id.clone(),
+      field_type: FieldType::RichText,
+    },
+    AssertFieldType {
+      field_id: field_id.clone(),
+      field_type: FieldType::RichText,
+    },
+    Undo,
+    AssertFieldType {
+      field_id,
+      field_type: FieldType::Checkbox,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn new_edit_clears_redo_test() {
+  let mut test = DatabaseHistoryTest::new().await;
+  let scripts = vec![
+    UpdateTextCell {
+      row_index: 0,
+      content: "hello",
+    },
+    Undo,
+    AssertCanRedo(true),
+    UpdateTextCell {
+      row_index: 0,
+      content: "world",
+    },
+    AssertCanRedo(false),
+  ];
+  test.run_scripts(scripts).await;
+}
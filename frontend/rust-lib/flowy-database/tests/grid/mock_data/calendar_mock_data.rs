@@ -1,6 +1,191 @@
-use database_model::BuildDatabaseContext;
+use crate::grid::block_test::util::GridRowTestBuilder;
+use crate::grid::mock_data::{
+  COMPLETED, FACEBOOK, FIRST_THING, GOOGLE, PAUSED, PLANNED, SECOND_THING, THIRD_THING, TWITTER,
+};
+
+use flowy_client_sync::client_database::DatabaseBuilder;
+use flowy_database::entities::*;
+
+use flowy_database::services::field::SelectOptionPB;
+use flowy_database::services::field::*;
+
+use database_model::*;
+
+use strum::IntoEnumIterator;
 
 // Calendar unit test mock data
 pub fn make_test_calendar() -> BuildDatabaseContext {
-  todo!()
+  let mut grid_builder = DatabaseBuilder::new();
+  // Iterate through the FieldType to create the corresponding Field.
+  for field_type in FieldType::iter() {
+    match field_type {
+      FieldType::RichText => {
+        let text_field = FieldBuilder::new(RichTextTypeOptionBuilder::default())
+          .name("Title")
+          .visibility(true)
+          .primary(true)
+          .build();
+        grid_builder.add_field(text_field);
+      },
+      FieldType::Number => {
+        let number = NumberTypeOptionBuilder::default().set_format(NumberFormat::USD);
+        let number_field = FieldBuilder::new(number)
+          .name("Price")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(number_field);
+      },
+      FieldType::DateTime => {
+        let date = DateTypeOptionBuilder::default()
+          .date_format(DateFormat::US)
+          .time_format(TimeFormat::TwentyFourHour);
+        let date_field = FieldBuilder::new(date)
+          .name("Date")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(date_field);
+      },
+      FieldType::SingleSelect => {
+        let single_select = SingleSelectTypeOptionBuilder::default()
+          .add_option(SelectOptionPB::new(COMPLETED))
+          .add_option(SelectOptionPB::new(PLANNED))
+          .add_option(SelectOptionPB::new(PAUSED));
+        let single_select_field = FieldBuilder::new(single_select)
+          .name("Status")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(single_select_field);
+      },
+      FieldType::MultiSelect => {
+        let multi_select = MultiSelectTypeOptionBuilder::default()
+          .add_option(SelectOptionPB::new(GOOGLE))
+          .add_option(SelectOptionPB::new(FACEBOOK))
+          .add_option(SelectOptionPB::new(TWITTER));
+        let multi_select_field = FieldBuilder::new(multi_select)
+          .name("Platform")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(multi_select_field);
+      },
+      FieldType::Checkbox => {
+        let checkbox = CheckboxTypeOptionBuilder::default();
+        let checkbox_field = FieldBuilder::new(checkbox)
+          .name("is urgent")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(checkbox_field);
+      },
+      FieldType::URL => {
+        let url = URLTypeOptionBuilder::default();
+        let url_field = FieldBuilder::new(url).name("link").visibility(true).build();
+        grid_builder.add_field(url_field);
+      },
+      FieldType::Checklist => {
+        let checklist = ChecklistTypeOptionBuilder::default()
+          .add_option(SelectOptionPB::new(FIRST_THING))
+          .add_option(SelectOptionPB::new(SECOND_THING))
+          .add_option(SelectOptionPB::new(THIRD_THING));
+        let checklist_field = FieldBuilder::new(checklist)
+          .name("TODO")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(checklist_field);
+      },
+      FieldType::Formula => {
+        let formula = FormulaTypeOptionBuilder::default()
+          .expression("{Price} * 2")
+          .result_type(FormulaResultTypePB::Number);
+        let formula_field = FieldBuilder::new(formula)
+          .name("Total")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(formula_field);
+      },
+      FieldType::Relation => {
+        // The related database is set by the relation tests.
+        let relation = RelationTypeOptionBuilder::default();
+        let relation_field = FieldBuilder::new(relation)
+          .name("Related")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(relation_field);
+      },
+      FieldType::Rollup => {
+        let rollup = RollupTypeOptionBuilder::default();
+        let rollup_field = FieldBuilder::new(rollup)
+          .name("Rollup")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(rollup_field);
+      },
+    }
+  }
+
+  // The calendar tests rely on the dates of the rows, so do not change them.
+  for i in 0..5 {
+    let block_id = grid_builder.block_id().to_owned();
+    let field_revs = grid_builder.field_revs();
+    let mut row_builder = GridRowTestBuilder::new(&block_id, field_revs);
+    match i {
+      0 => {
+        for field_type in FieldType::iter() {
+          match field_type {
+            FieldType::RichText => row_builder.insert_text_cell("A"),
+            // 1678089600 => Mar 6,2023 08:00 UTC
+            FieldType::DateTime => row_builder.insert_date_cell("1678089600"),
+            FieldType::Checkbox => row_builder.insert_checkbox_cell("true"),
+            _ => "".to_owned(),
+          };
+        }
+      },
+      1 => {
+        for field_type in FieldType::iter() {
+          match field_type {
+            FieldType::RichText => row_builder.insert_text_cell("B"),
+            // 1678114800 => Mar 6,2023 15:00 UTC
+            FieldType::DateTime => row_builder.insert_date_cell("1678114800"),
+            FieldType::Checkbox => row_builder.insert_checkbox_cell("false"),
+            _ => "".to_owned(),
+          };
+        }
+      },
+      2 => {
+        for field_type in FieldType::iter() {
+          match field_type {
+            FieldType::RichText => row_builder.insert_text_cell("C"),
+            // 1678266000 => Mar 8,2023 09:00 UTC
+            FieldType::DateTime => row_builder.insert_date_cell("1678266000"),
+            FieldType::Checkbox => row_builder.insert_checkbox_cell("false"),
+            _ => "".to_owned(),
+          };
+        }
+      },
+      3 => {
+        for field_type in FieldType::iter() {
+          match field_type {
+            FieldType::RichText => row_builder.insert_text_cell("D"),
+            // 1679306400 => Mar 20,2023 10:00 UTC
+            FieldType::DateTime => row_builder.insert_date_cell("1679306400"),
+            FieldType::Checkbox => row_builder.insert_checkbox_cell("true"),
+            _ => "".to_owned(),
+          };
+        }
+      },
+      4 => {
+        for field_type in FieldType::iter() {
+          match field_type {
+            // The row without date should not be displayed in the calendar.
+            FieldType::RichText => row_builder.insert_text_cell("E"),
+            FieldType::Checkbox => row_builder.insert_checkbox_cell("false"),
+            _ => "".to_owned(),
+          };
+        }
+      },
+      _ => {},
+    }
+
+    let row_rev = row_builder.build();
+    grid_builder.add_row(row_rev);
+  }
+  grid_builder.build()
 }
@@ -102,6 +102,33 @@ pub fn make_test_grid() -> BuildDatabaseContext {
           .build();
         grid_builder.add_field(checklist_field);
       },
+      FieldType::Formula => {
+        let formula = FormulaTypeOptionBuilder::default()
+          .expression("{Price} * 2")
+          .result_type(FormulaResultTypePB::Number);
+        let formula_field = FieldBuilder::new(formula)
+          .name("Total")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(formula_field);
+      },
+      FieldType::Relation => {
+        // The related database is set by the relation tests.
+        let relation = RelationTypeOptionBuilder::default();
+        let relation_field = FieldBuilder::new(relation)
+          .name("Related")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(relation_field);
+      },
+      FieldType::Rollup => {
+        let rollup = RollupTypeOptionBuilder::default();
+        let rollup_field = FieldBuilder::new(rollup)
+          .name("Rollup")
+          .visibility(true)
+          .build();
+        grid_builder.add_field(rollup_field);
+      },
     }
   }
 
@@ -1,10 +1,17 @@
 mod block_test;
+mod calculation_test;
+mod calendar_test;
 mod cell_test;
 mod database_editor;
 mod field_test;
 mod filter_test;
+mod formula_test;
 mod group_test;
+mod history_test;
+mod relation_test;
+mod share_test;
 mod snapshot_test;
 mod sort_test;
+mod sync_test;
 
 mod mock_data;
@@ -0,0 +1,356 @@
+use crate::grid::database_editor::DatabaseEditorTest;
+use crate::grid::mock_data::make_test_grid;
+use bytes::Bytes;
+use database_model::FieldRevision;
+use flowy_database::entities::{CellIdParams, FieldType};
+use flowy_database::services::cell::TypeCellData;
+use flowy_database::services::database::{remap_database_build_context, DatabaseRevisionEditor};
+use flowy_database::services::field::{
+  RelationCellChangeset, RelationCellData, RelationTypeOptionPB, RollupCalculationPB,
+  RollupTypeOptionPB,
+};
+use flowy_revision::REVISION_WRITE_INTERVAL_IN_MILLIS;
+use flowy_test::helper::ViewTest;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub enum RelationScript {
+  UpdateCalculation(RollupCalculationPB),
+  InsertRelatedRows {
+    row_index: usize,
+    related_row_indexes: Vec<usize>,
+  },
+  RemoveRelatedRows {
+    row_index: usize,
+    related_row_indexes: Vec<usize>,
+  },
+  /// Updates the price of the row in the related database.
+  UpdateRelatedPrice {
+    row_index: usize,
+    content: &'static str,
+  },
+  Wait {
+    millis: u64,
+  },
+  Undo,
+  /// Undoes the last change of the related database.
+  UndoRelated,
+  /// Closes the database, the rollup cells of a closed database aren't refreshed.
+  CloseDatabase,
+  OpenDatabase,
+  /// Closes the related database, and checks that it is not opened again when the rollup cells
+  /// are recalculated.
+  CloseRelatedDatabase,
+  AssertRelatedDatabaseClosed,
+  AssertRollupCell {
+    row_index: usize,
+    expected: &'static str,
+  },
+  /// Duplicates the database together with the related database, and checks that the relation
+  /// of the copy refers to the rows of the related database's copy.
+  AssertDuplicatedRelation {
+    row_index: usize,
+    related_row_indexes: Vec<usize>,
+  },
+  /// Exports the database together with the related database, then imports them with new ids,
+  /// and checks that the relation of the imported database refers to the imported rows.
+  AssertImportedRelation {
+    row_index: usize,
+    related_row_indexes: Vec<usize>,
+  },
+}
+
+pub struct DatabaseRelationTest {
+  inner: DatabaseEditorTest,
+  related_editor: Arc<DatabaseRevisionEditor>,
+  related_view_id: String,
+  calculation: RollupCalculationPB,
+}
+
+impl DatabaseRelationTest {
+  pub async fn new() -> Self {
+    let inner = DatabaseEditorTest::new_table().await;
+    let view_data: Bytes = make_test_grid().into();
+    let related_view = ViewTest::new_grid_view(&inner.sdk, view_data.to_vec()).await;
+    let related_view_id = related_view.view.id;
+    let related_editor = inner
+      .sdk
+      .grid_manager
+      .open_database(&related_view_id)
+      .await
+      .unwrap();
+
+    let mut test = Self {
+      inner,
+      related_editor,
+      related_view_id,
+      calculation: RollupCalculationPB::Count,
+    };
+    let type_option = RelationTypeOptionPB {
+      database_id: test.related_view_id.clone(),
+    };
+    let relation_field_id = test.relation_field_rev().id.clone();
+    test
+      .update_type_option(&relation_field_id, type_option)
+      .await;
+    test.update_rollup_type_option().await;
+    test
+  }
+
+  pub async fn run_scripts(&mut self, scripts: Vec<RelationScript>) {
+    for script in scripts {
+      self.run_script(script).await;
+    }
+  }
+
+  pub async fn run_script(&mut self, script: RelationScript) {
+    match script {
+      RelationScript::UpdateCalculation(calculation) => {
+        self.calculation = calculation;
+        self.update_rollup_type_option().await;
+      },
+      RelationScript::InsertRelatedRows {
+        row_index,
+        related_row_indexes,
+      } => {
+        let row_ids = self.get_related_row_ids(related_row_indexes).await;
+        let changeset = RelationCellChangeset::from_insert_row_ids(row_ids);
+        self.update_relation_cell(row_index, changeset).await;
+      },
+      RelationScript::RemoveRelatedRows {
+        row_index,
+        related_row_indexes,
+      } => {
+        let row_ids = self.get_related_row_ids(related_row_indexes).await;
+        let changeset = RelationCellChangeset::from_remove_row_ids(row_ids);
+        self.update_relation_cell(row_index, changeset).await;
+      },
+      RelationScript::UpdateRelatedPrice { row_index, content } => {
+        let field_revs = self.related_editor.get_field_revs(None).await.unwrap();
+        let field_id = get_field_id(&field_revs, FieldType::Number);
+        let row_id = self.get_related_row_ids(vec![row_index]).await.remove(0);
+        self
+          .related_editor
+          .update_cell_with_changeset(&row_id, &field_id, content.to_owned())
+          .await
+          .unwrap();
+      },
+      RelationScript::Wait { millis } => {
+        tokio::time::sleep(Duration::from_millis(millis)).await;
+      },
+      RelationScript::Undo => {
+        self.editor.undo().await.unwrap();
+      },
+      RelationScript::UndoRelated => {
+        self.related_editor.undo().await.unwrap();
+      },
+      RelationScript::CloseDatabase => {
+        let view_id = self.view_id.clone();
+        self.close_database(&view_id).await;
+      },
+      RelationScript::OpenDatabase => {
+        self.inner.editor = self
+          .sdk
+          .grid_manager
+          .open_database(&self.view_id)
+          .await
+          .unwrap();
+      },
+      RelationScript::CloseRelatedDatabase => {
+        let related_view_id = self.related_view_id.clone();
+        self.close_database(&related_view_id).await;
+      },
+      RelationScript::AssertRelatedDatabaseClosed => {
+        let is_opened = self
+          .sdk
+          .grid_manager
+          .is_database_opened(&self.related_view_id)
+          .await;
+        assert!(!is_opened);
+      },
+      RelationScript::AssertRollupCell {
+        row_index,
+        expected,
+      } => {
+        let row_revs = self.get_row_revs().await;
+        let params = CellIdParams {
+          database_id: self.view_id.clone(),
+          field_id: self.get_first_field_rev(FieldType::Rollup).id.clone(),
+          row_id: row_revs[row_index].id.clone(),
+        };
+        let content = self.editor.get_cell_display_str(&params).await;
+        assert_eq!(content, expected);
+      },
+      RelationScript::AssertDuplicatedRelation {
+        row_index,
+        related_row_indexes,
+      } => {
+        let mut id_map = HashMap::from([
+          (self.view_id.clone(), "copy".to_owned()),
+          (self.related_view_id.clone(), "related_copy".to_owned()),
+        ]);
+        let context = self.editor.duplicate_database(&mut id_map).await.unwrap();
+        let related_context = self
+          .related_editor
+          .duplicate_database(&mut id_map)
+          .await
+          .unwrap();
+
+        let relation_field_rev = get_field_rev(&context.field_revs, FieldType::Relation);
+        assert_ne!(relation_field_rev.id, self.relation_field_rev().id);
+        let type_option = RelationTypeOptionPB::from(relation_field_rev);
+        assert_eq!(type_option.database_id, "related_copy");
+
+        let type_option =
+          RollupTypeOptionPB::from(get_field_rev(&context.field_revs, FieldType::Rollup));
+        assert_eq!(type_option.relation_field_id, relation_field_rev.id);
+        assert_eq!(
+          type_option.target_field_id,
+          get_field_id(&related_context.field_revs, FieldType::Number)
+        );
+
+        let related_row_revs = related_context
+          .blocks
+          .iter()
+          .flat_map(|block| block.rows.iter())
+          .collect::<Vec<_>>();
+        let expected_row_ids = related_row_indexes
+          .into_iter()
+          .map(|index| related_row_revs[index].id.clone())
+          .collect::<Vec<String>>();
+        let row_rev = &context.blocks[0].rows[row_index];
+        let cell_rev = row_rev.cells.get(&relation_field_rev.id).unwrap();
+        let type_cell_data = TypeCellData::try_from(cell_rev).unwrap();
+        let row_ids = RelationCellData::from(type_cell_data.cell_str).into_inner();
+        assert_eq!(row_ids, expected_row_ids);
+      },
+      RelationScript::AssertImportedRelation {
+        row_index,
+        related_row_indexes,
+      } => {
+        // The exported databases keep their ids, so the relation still refers to the related one.
+        let mut id_map = HashMap::from([
+          (self.view_id.clone(), self.view_id.clone()),
+          (self.related_view_id.clone(), self.related_view_id.clone()),
+        ]);
+        let exported = self.editor.duplicate_database(&mut id_map).await.unwrap();
+        let related_exported = self
+          .related_editor
+          .duplicate_database(&mut id_map)
+          .await
+          .unwrap();
+
+        let mut id_map = HashMap::from([
+          (self.view_id.clone(), "imported".to_owned()),
+          (self.related_view_id.clone(), "related_imported".to_owned()),
+        ]);
+        let context = remap_database_build_context(exported.clone(), &mut id_map).unwrap();
+        let related_context =
+          remap_database_build_context(related_exported.clone(), &mut id_map).unwrap();
+        assert_ne!(context.blocks[0].block_id, exported.blocks[0].block_id);
+        assert_eq!(context.block_metas[0].block_id, context.blocks[0].block_id);
+
+        let relation_field_rev = get_field_rev(&context.field_revs, FieldType::Relation);
+        let type_option = RelationTypeOptionPB::from(relation_field_rev);
+        assert_eq!(type_option.database_id, "related_imported");
+
+        let related_row_revs = related_context
+          .blocks
+          .iter()
+          .flat_map(|block| block.rows.iter())
+          .collect::<Vec<_>>();
+        let expected_row_ids = related_row_indexes
+          .into_iter()
+          .map(|index| related_row_revs[index].id.clone())
+          .collect::<Vec<String>>();
+        let row_rev = &context.blocks[0].rows[row_index];
+        assert_ne!(row_rev.id, exported.blocks[0].rows[row_index].id);
+        assert_eq!(row_rev.block_id, context.blocks[0].block_id);
+        let cell_rev = row_rev.cells.get(&relation_field_rev.id).unwrap();
+        let type_cell_data = TypeCellData::try_from(cell_rev).unwrap();
+        let row_ids = RelationCellData::from(type_cell_data.cell_str).into_inner();
+        assert_eq!(row_ids, expected_row_ids);
+      },
+    }
+  }
+
+  async fn close_database(&self, database_id: &str) {
+    self
+      .sdk
+      .grid_manager
+      .close_database(database_id)
+      .await
+      .unwrap();
+    // Wait for the revisions of the closed database to be written to the disk.
+    tokio::time::sleep(Duration::from_millis(2 * REVISION_WRITE_INTERVAL_IN_MILLIS)).await;
+  }
+
+  fn relation_field_rev(&self) -> &Arc<FieldRevision> {
+    self.get_first_field_rev(FieldType::Relation)
+  }
+
+  /// Aggregates the prices of the related rows.
+  async fn update_rollup_type_option(&mut self) {
+    let field_revs = self.related_editor.get_field_revs(None).await.unwrap();
+    let type_option = RollupTypeOptionPB {
+      relation_field_id: self.relation_field_rev().id.clone(),
+      target_field_id: get_field_id(&field_revs, FieldType::Number),
+      calculation: self.calculation,
+    };
+    let rollup_field_id = self.get_first_field_rev(FieldType::Rollup).id.clone();
+    self.update_type_option(&rollup_field_id, type_option).await;
+  }
+
+  async fn update_type_option<T: TryInto<Bytes>>(&mut self, field_id: &str, type_option: T) {
+    let bytes: Bytes = type_option.try_into().ok().unwrap();
+    self
+      .editor
+      .update_field_type_option(field_id, bytes.to_vec(), None)
+      .await
+      .unwrap();
+  }
+
+  async fn update_relation_cell(&mut self, row_index: usize, changeset: RelationCellChangeset) {
+    let field_id = self.relation_field_rev().id.clone();
+    let row_id = self.get_row_revs().await[row_index].id.clone();
+    self.update_cell(&field_id, row_id, changeset).await;
+  }
+
+  async fn get_related_row_ids(&self, row_indexes: Vec<usize>) -> Vec<String> {
+    let row_revs = self
+      .related_editor
+      .get_all_row_revs(&self.related_view_id)
+      .await
+      .unwrap();
+    row_indexes
+      .into_iter()
+      .map(|index| row_revs[index].id.clone())
+      .collect()
+  }
+}
+
+fn get_field_rev(field_revs: &[Arc<FieldRevision>], field_type: FieldType) -> &Arc<FieldRevision> {
+  field_revs
+    .iter()
+    .find(|field_rev| FieldType::from(field_rev.ty) == field_type)
+    .unwrap()
+}
+
+fn get_field_id(field_revs: &[Arc<FieldRevision>], field_type: FieldType) -> String {
+  get_field_rev(field_revs, field_type).id.clone()
+}
+
+impl std::ops::Deref for DatabaseRelationTest {
+  type Target = DatabaseEditorTest;
+
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+impl std::ops::DerefMut for DatabaseRelationTest {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.inner
+  }
+}
@@ -0,0 +1,234 @@
+use crate::grid::relation_test::script::DatabaseRelationTest;
+use crate::grid::relation_test::script::RelationScript::*;
+use flowy_database::services::field::RollupCalculationPB;
+
+#[tokio::test]
+async fn grid_rollup_count_related_rows_test() {
+  let mut test = DatabaseRelationTest::new().await;
+  let scripts = vec![
+    AssertRollupCell {
+      row_index: 0,
+      expected: "0",
+    },
+    InsertRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![0, 1, 2],
+    },
+    AssertRollupCell {
+      row_index: 0,
+      expected: "3",
+    },
+    AssertRollupCell {
+      row_index: 1,
+      expected: "0",
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_rollup_calculations_test() {
+  let mut test = DatabaseRelationTest::new().await;
+  let scripts = vec![
+    // The prices of the related rows are 1, 2, 3 and an empty cell.
+    InsertRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![0, 1, 2, 4],
+    },
+    UpdateCalculation(RollupCalculationPB::Sum),
+    AssertRollupCell {
+      row_index: 0,
+      expected: "6",
+    },
+    UpdateCalculation(RollupCalculationPB::Min),
+    AssertRollupCell {
+      row_index: 0,
+      expected: "1",
+    },
+    UpdateCalculation(RollupCalculationPB::Max),
+    AssertRollupCell {
+      row_index: 0,
+      expected: "3",
+    },
+    UpdateCalculation(RollupCalculationPB::List),
+    AssertRollupCell {
+      row_index: 0,
+      expected: "1, 2, 3",
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_rollup_after_removing_related_rows_test() {
+  let mut test = DatabaseRelationTest::new().await;
+  let scripts = vec![
+    UpdateCalculation(RollupCalculationPB::Sum),
+    InsertRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![0, 1],
+    },
+    RemoveRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![0],
+    },
+    AssertRollupCell {
+      row_index: 0,
+      expected: "2",
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_rollup_refresh_after_updating_related_row_test() {
+  let mut test = DatabaseRelationTest::new().await;
+  let scripts = vec![
+    UpdateCalculation(RollupCalculationPB::Sum),
+    InsertRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![0, 1],
+    },
+    AssertRollupCell {
+      row_index: 0,
+      expected: "3",
+    },
+    UpdateRelatedPrice {
+      row_index: 0,
+      content: "10",
+    },
+    // The rollup cells are refreshed asynchronously.
+    Wait { millis: 300 },
+    AssertRollupCell {
+      row_index: 0,
+      expected: "12",
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_rollup_refresh_is_not_undoable_test() {
+  let mut test = DatabaseRelationTest::new().await;
+  let scripts = vec![
+    UpdateCalculation(RollupCalculationPB::Sum),
+    InsertRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![0, 1],
+    },
+    UpdateRelatedPrice {
+      row_index: 0,
+      content: "10",
+    },
+    Wait { millis: 300 },
+    AssertRollupCell {
+      row_index: 0,
+      expected: "12",
+    },
+    // Reverts the inserted related rows instead of the refreshed rollup cell.
+    Undo,
+    AssertRollupCell {
+      row_index: 0,
+      expected: "0",
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_rollup_refresh_after_undoing_related_row_test() {
+  let mut test = DatabaseRelationTest::new().await;
+  let scripts = vec![
+    UpdateCalculation(RollupCalculationPB::Sum),
+    InsertRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![0, 1],
+    },
+    UpdateRelatedPrice {
+      row_index: 0,
+      content: "10",
+    },
+    Wait { millis: 300 },
+    UndoRelated,
+    Wait { millis: 300 },
+    AssertRollupCell {
+      row_index: 0,
+      expected: "3",
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_rollup_refresh_after_reopening_test() {
+  let mut test = DatabaseRelationTest::new().await;
+  let scripts = vec![
+    UpdateCalculation(RollupCalculationPB::Sum),
+    InsertRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![0, 1],
+    },
+    CloseDatabase,
+    UpdateRelatedPrice {
+      row_index: 0,
+      content: "10",
+    },
+    OpenDatabase,
+    AssertRollupCell {
+      row_index: 0,
+      expected: "12",
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_rollup_read_closed_related_database_test() {
+  let mut test = DatabaseRelationTest::new().await;
+  let scripts = vec![
+    UpdateCalculation(RollupCalculationPB::Sum),
+    CloseRelatedDatabase,
+    InsertRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![0, 1],
+    },
+    AssertRollupCell {
+      row_index: 0,
+      expected: "3",
+    },
+    AssertRelatedDatabaseClosed,
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_duplicate_relation_test() {
+  let mut test = DatabaseRelationTest::new().await;
+  let scripts = vec![
+    InsertRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![0, 2],
+    },
+    AssertDuplicatedRelation {
+      row_index: 0,
+      related_row_indexes: vec![0, 2],
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_import_relation_test() {
+  let mut test = DatabaseRelationTest::new().await;
+  let scripts = vec![
+    InsertRelatedRows {
+      row_index: 0,
+      related_row_indexes: vec![1, 2],
+    },
+    AssertImportedRelation {
+      row_index: 0,
+      related_row_indexes: vec![1, 2],
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
@@ -0,0 +1,134 @@
+use crate::grid::database_editor::DatabaseEditorTest;
+use database_model::SortCondition;
+use flowy_database::entities::{
+  AlterFilterParams, AlterFilterPayloadPB, AlterSortParams, CheckboxFilterConditionPB,
+  CheckboxFilterPB, ExportDatabaseParams, ExportFormatPB, FieldType,
+};
+
+pub enum ShareScript {
+  InsertSort {
+    field_type: FieldType,
+    condition: SortCondition,
+  },
+  CreateCheckboxFilter {
+    condition: CheckboxFilterConditionPB,
+  },
+  AssertExportedCSV {
+    field_type: FieldType,
+    raw_cell: bool,
+    expected: Vec<&'static str>,
+  },
+  AssertExportedJSON {
+    field_type: FieldType,
+    raw_cell: bool,
+    expected: Vec<&'static str>,
+  },
+}
+
+pub struct DatabaseShareTest {
+  inner: DatabaseEditorTest,
+}
+
+impl DatabaseShareTest {
+  pub async fn new() -> Self {
+    let editor_test = DatabaseEditorTest::new_table().await;
+    Self { inner: editor_test }
+  }
+
+  pub async fn run_scripts(&mut self, scripts: Vec<ShareScript>) {
+    for script in scripts {
+      self.run_script(script).await;
+    }
+  }
+
+  pub async fn run_script(&mut self, script: ShareScript) {
+    match script {
+      ShareScript::InsertSort {
+        field_type,
+        condition,
+      } => {
+        let field_rev = self.get_first_field_rev(field_type);
+        let params = AlterSortParams {
+          view_id: self.view_id.clone(),
+          field_id: field_rev.id.clone(),
+          sort_id: None,
+          field_type: field_rev.ty,
+          condition: condition.into(),
+        };
+        self.editor.create_or_update_sort(params).await.unwrap();
+      },
+      ShareScript::CreateCheckboxFilter { condition } => {
+        let field_rev = self.get_first_field_rev(FieldType::Checkbox);
+        let payload =
+          AlterFilterPayloadPB::new(&self.view_id, field_rev, CheckboxFilterPB { condition });
+        let params: AlterFilterParams = payload.try_into().unwrap();
+        self.editor.create_or_update_filter(params).await.unwrap();
+      },
+      ShareScript::AssertExportedCSV {
+        field_type,
+        raw_cell,
+        expected,
+      } => {
+        let data = self.export(ExportFormatPB::CSV, raw_cell).await;
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+        let field_name = &self.get_first_field_rev(field_type).name;
+        let index = reader
+          .headers()
+          .unwrap()
+          .iter()
+          .position(|name| name == field_name)
+          .unwrap();
+        let cells = reader
+          .records()
+          .map(|record| record.unwrap()[index].to_owned())
+          .collect::<Vec<String>>();
+        assert_eq!(cells, expected);
+      },
+      ShareScript::AssertExportedJSON {
+        field_type,
+        raw_cell,
+        expected,
+      } => {
+        let data = self.export(ExportFormatPB::JSON, raw_cell).await;
+        let value: serde_json::Value = serde_json::from_str(&data).unwrap();
+        let field_id = &self.get_first_field_rev(field_type).id;
+        let index = value["fields"]
+          .as_array()
+          .unwrap()
+          .iter()
+          .position(|field| field["id"].as_str() == Some(field_id))
+          .unwrap();
+        let cells = value["rows"]
+          .as_array()
+          .unwrap()
+          .iter()
+          .map(|row| row["cells"][index].as_str().unwrap().to_owned())
+          .collect::<Vec<String>>();
+        assert_eq!(cells, expected);
+      },
+    }
+  }
+
+  async fn export(&self, format: ExportFormatPB, raw_cell: bool) -> String {
+    let params = ExportDatabaseParams {
+      view_id: self.view_id.clone(),
+      format,
+      raw_cell,
+    };
+    self.editor.export_database(params).await.unwrap()
+  }
+}
+
+impl std::ops::Deref for DatabaseShareTest {
+  type Target = DatabaseEditorTest;
+
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+impl std::ops::DerefMut for DatabaseShareTest {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.inner
+  }
+}
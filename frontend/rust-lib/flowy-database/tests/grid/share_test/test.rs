@@ -0,0 +1,76 @@
+use crate::grid::share_test::script::DatabaseShareTest;
+use crate::grid::share_test::script::ShareScript::*;
+use database_model::SortCondition;
+use flowy_database::entities::{CheckboxFilterConditionPB, FieldType};
+
+#[tokio::test]
+async fn export_csv_test() {
+  let mut test = DatabaseShareTest::new().await;
+  let scripts = vec![
+    AssertExportedCSV {
+      field_type: FieldType::RichText,
+      raw_cell: false,
+      expected: vec!["A", "", "C", "DA", "AE", "AE"],
+    },
+    AssertExportedCSV {
+      field_type: FieldType::Number,
+      raw_cell: false,
+      expected: vec!["$1", "$2", "$3", "$4", "", "$5"],
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn export_csv_with_filter_and_sort_test() {
+  let mut test = DatabaseShareTest::new().await;
+  let scripts = vec![
+    CreateCheckboxFilter {
+      condition: CheckboxFilterConditionPB::IsChecked,
+    },
+    InsertSort {
+      field_type: FieldType::RichText,
+      condition: SortCondition::Descending,
+    },
+    AssertExportedCSV {
+      field_type: FieldType::RichText,
+      raw_cell: false,
+      expected: vec!["AE", "A", ""],
+    },
+    AssertExportedCSV {
+      field_type: FieldType::Number,
+      raw_cell: false,
+      expected: vec!["$5", "$1", "$2"],
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn export_json_with_raw_cell_test() {
+  let mut test = DatabaseShareTest::new().await;
+  let scripts = vec![
+    AssertExportedJSON {
+      field_type: FieldType::Number,
+      raw_cell: false,
+      expected: vec!["$1", "$2", "$3", "$4", "", "$5"],
+    },
+    AssertExportedJSON {
+      field_type: FieldType::Number,
+      raw_cell: true,
+      expected: vec!["1", "2", "3", "4", "", "5"],
+    },
+    AssertExportedJSON {
+      field_type: FieldType::Checkbox,
+      raw_cell: true,
+      expected: vec!["Yes", "Yes", "No", "No", "No", "Yes"],
+    },
+    // The formula cells are not saved, they are exported as the calculated values.
+    AssertExportedJSON {
+      field_type: FieldType::Formula,
+      raw_cell: true,
+      expected: vec!["2", "4", "6", "8", "", "10"],
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
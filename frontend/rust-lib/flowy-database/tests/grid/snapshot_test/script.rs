@@ -8,7 +8,10 @@ use std::time::Duration;
 use tokio::time::sleep;
 
 pub enum SnapshotScript {
+  /// Writes the snapshots of the blocks and then the snapshot of the database.
   WriteSnapshot,
+  /// Writes the snapshot of the database without writing the snapshots of its blocks.
+  WriteDatabaseSnapshot,
   #[allow(dead_code)]
   AssertSnapshot {
     rev_id: i64,
@@ -24,6 +27,11 @@ pub enum SnapshotScript {
   DeleteField {
     field_rev: FieldRevision,
   },
+  AssertSnapshotCount(usize),
+  RestoreSnapshot {
+    rev_id: i64,
+  },
+  AssertFieldCount(usize),
 }
 
 pub struct DatabaseSnapshotTest {
@@ -64,6 +72,19 @@ impl DatabaseSnapshotTest {
     let rev_manager = self.editor.rev_manager();
     match script {
       SnapshotScript::WriteSnapshot => {
+        sleep(Duration::from_millis(2 * REVISION_WRITE_INTERVAL_IN_MILLIS)).await;
+        for block_meta_rev in self.block_meta_revs.iter() {
+          let block_rev_manager = self
+            .editor
+            .block_rev_manager(&block_meta_rev.block_id)
+            .await
+            .unwrap();
+          block_rev_manager.generate_snapshot().await;
+        }
+        rev_manager.generate_snapshot().await;
+        self.current_snapshot = rev_manager.read_snapshot(None).await.unwrap();
+      },
+      SnapshotScript::WriteDatabaseSnapshot => {
         sleep(Duration::from_millis(2 * REVISION_WRITE_INTERVAL_IN_MILLIS)).await;
         rev_manager.generate_snapshot().await;
         self.current_snapshot = rev_manager.read_snapshot(None).await.unwrap();
@@ -85,6 +106,17 @@ impl DatabaseSnapshotTest {
       SnapshotScript::DeleteField { field_rev } => {
         self.editor.delete_field(&field_rev.id).await.unwrap();
       },
+      SnapshotScript::AssertSnapshotCount(expected) => {
+        let snapshots = self.editor.get_snapshots().await.unwrap();
+        assert_eq!(snapshots.len(), expected);
+      },
+      SnapshotScript::RestoreSnapshot { rev_id } => {
+        self.editor.restore_snapshot(rev_id).await.unwrap();
+      },
+      SnapshotScript::AssertFieldCount(expected) => {
+        let field_revs = self.editor.get_field_revs(None).await.unwrap();
+        assert_eq!(field_revs.len(), expected);
+      },
     }
   }
 }
@@ -45,3 +45,88 @@ async fn snapshot_multi_version_test() {
     }])
     .await;
 }
+
+#[tokio::test]
+async fn snapshot_list_latest_first_test() {
+  let mut test = DatabaseSnapshotTest::new().await;
+  let (_, field_rev) = create_text_field(&test.grid_id());
+  let scripts = vec![
+    CreateField {
+      field_rev: field_rev.clone(),
+    },
+    WriteSnapshot,
+    DeleteField { field_rev },
+    WriteSnapshot,
+    AssertSnapshotCount(2),
+  ];
+  test.run_scripts(scripts).await;
+
+  let snapshots = test.editor.get_snapshots().await.unwrap();
+  assert!(snapshots[0].rev_id > snapshots[1].rev_id);
+}
+
+#[tokio::test]
+async fn snapshot_preview_test() {
+  let mut test = DatabaseSnapshotTest::new().await;
+  let (_, field_rev) = create_text_field(&test.grid_id());
+  let field_id = field_rev.id.clone();
+  test
+    .run_scripts(vec![CreateField { field_rev }, WriteSnapshot])
+    .await;
+
+  let rev_id = test.current_snapshot.as_ref().unwrap().rev_id;
+  let content = test.editor.preview_snapshot(rev_id).await.unwrap();
+  assert!(content.contains(&field_id));
+}
+
+#[tokio::test]
+async fn snapshot_restore_write_forward_revision_test() {
+  let mut test = DatabaseSnapshotTest::new().await;
+  let field_count = test.field_count;
+  let (_, field_rev) = create_text_field(&test.grid_id());
+  let scripts = vec![
+    CreateField {
+      field_rev: field_rev.clone(),
+    },
+    WriteSnapshot,
+    DeleteField { field_rev },
+    AssertFieldCount(field_count),
+  ];
+  test.run_scripts(scripts).await;
+
+  let snapshot = test.current_snapshot.clone().unwrap();
+  let rev_id_before_restore = test.editor.rev_manager().rev_id();
+  test
+    .run_scripts(vec![
+      RestoreSnapshot {
+        rev_id: snapshot.rev_id,
+      },
+      AssertFieldCount(field_count + 1),
+    ])
+    .await;
+
+  // Restoring doesn't rewind the history, it's saved as a new revision.
+  assert!(test.editor.rev_manager().rev_id() > rev_id_before_restore);
+}
+
+#[tokio::test]
+async fn snapshot_restore_without_block_snapshot_test() {
+  let mut test = DatabaseSnapshotTest::new().await;
+  let field_count = test.field_count;
+  let (_, field_rev) = create_text_field(&test.grid_id());
+  let scripts = vec![
+    CreateField {
+      field_rev: field_rev.clone(),
+    },
+    WriteDatabaseSnapshot,
+    DeleteField { field_rev },
+  ];
+  test.run_scripts(scripts).await;
+
+  // The rows of the blocks at the snapshot are unknown, so the snapshot can't be previewed or
+  // restored.
+  let rev_id = test.current_snapshot.as_ref().unwrap().rev_id;
+  assert!(test.editor.preview_snapshot(rev_id).await.is_err());
+  assert!(test.editor.restore_snapshot(rev_id).await.is_err());
+  test.run_scripts(vec![AssertFieldCount(field_count)]).await;
+}
@@ -29,10 +29,10 @@ async fn sort_checkbox_and_then_text_by_descending_test() {
       field_id: text_field.id.clone(),
       orders: vec!["A", "", "AE", "C", "DA", "AE"],
     },
-    // Insert text sort. After inserting the text sort, the order of the rows
-    // will be changed.
+    // Insert text sort. The text sort only orders the rows that have the same checkbox
+    // value, and the empty cell is placed after the non-empty cells.
     // before: ["A", "", "AE", "C", "DA", "AE"]
-    // after: ["", "A", "AE", "AE", "C", "DA"]
+    // after: ["A", "AE", "", "AE", "C", "DA"]
     InsertSort {
       field_rev: text_field.clone(),
       condition: SortCondition::Ascending,
@@ -43,7 +43,7 @@ async fn sort_checkbox_and_then_text_by_descending_test() {
     },
     AssertCellContentOrder {
       field_id: text_field.id.clone(),
-      orders: vec!["", "A", "AE", "AE", "C", "DA"],
+      orders: vec!["A", "AE", "", "AE", "C", "DA"],
     },
   ];
   test.run_scripts(scripts).await;
@@ -23,7 +23,7 @@ async fn sort_text_with_checkbox_by_ascending_test() {
     },
     AssertCellContentOrder {
       field_id: text_field.id.clone(),
-      orders: vec!["", "A", "AE", "AE", "C", "DA"],
+      orders: vec!["A", "AE", "AE", "C", "DA", ""],
     },
   ];
   test.run_scripts(scripts).await;
@@ -35,11 +35,11 @@ async fn sort_text_with_checkbox_by_ascending_test() {
     },
     AssertCellContentOrder {
       field_id: text_field.id.clone(),
-      orders: vec!["", "A", "AE", "AE", "C", "DA"],
+      orders: vec!["A", "AE", "AE", "C", "DA", ""],
     },
     AssertCellContentOrder {
       field_id: checkbox_field.id.clone(),
-      orders: vec!["Yes", "Yes", "Yes", "No", "No"],
+      orders: vec!["Yes", "Yes", "No", "No", "No", "Yes"],
     },
   ];
   test.run_scripts(scripts).await;
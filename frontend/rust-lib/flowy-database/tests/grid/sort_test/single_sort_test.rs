@@ -17,7 +17,7 @@ async fn sort_text_by_ascending_test() {
     },
     AssertCellContentOrder {
       field_id: text_field.id.clone(),
-      orders: vec!["", "A", "AE", "AE", "C", "DA"],
+      orders: vec!["A", "AE", "AE", "C", "DA", ""],
     },
   ];
   test.run_scripts(scripts).await;
@@ -34,7 +34,7 @@ async fn sort_change_notification_by_update_text_test() {
     },
     AssertCellContentOrder {
       field_id: text_field.id.clone(),
-      orders: vec!["", "A", "AE", "AE", "C", "DA"],
+      orders: vec!["A", "AE", "AE", "C", "DA", ""],
     },
     // Wait the insert task to finish. The cost of time should be less than 200 milliseconds.
     Wait { millis: 200 },
@@ -48,8 +48,8 @@ async fn sort_change_notification_by_update_text_test() {
       text: "E".to_string(),
     },
     AssertSortChanged {
-      old_row_orders: vec!["", "A", "E", "AE", "C", "DA"],
-      new_row_orders: vec!["", "A", "AE", "C", "DA", "E"],
+      old_row_orders: vec!["A", "AE", "E", "C", "DA", ""],
+      new_row_orders: vec!["A", "AE", "C", "DA", "E", ""],
     },
   ];
   test.run_scripts(scripts).await;
@@ -262,7 +262,55 @@ async fn sort_multi_select_by_ascending_test() {
     },
     AssertCellContentOrder {
       field_id: multi_select.id.clone(),
-      orders: vec!["", "", "", "Facebook", "Google,Facebook", "Google,Twitter"],
+      orders: vec!["Google,Facebook", "Google,Twitter", "Facebook", "", "", ""],
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn sort_single_select_by_option_order_test() {
+  let mut test = DatabaseSortTest::new().await;
+  let single_select = test.get_first_field_rev(FieldType::SingleSelect);
+  // The options are ordered as: Completed, Planned, Paused
+  let scripts = vec![
+    InsertSort {
+      field_rev: single_select.clone(),
+      condition: SortCondition::Ascending,
+    },
+    AssertCellContentOrder {
+      field_id: single_select.id.clone(),
+      orders: vec!["Completed", "Completed", "Planned", "Planned", "", ""],
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn sort_text_by_natural_order_test() {
+  let mut test = DatabaseSortTest::new().await;
+  let text_field = test.get_first_field_rev(FieldType::RichText).clone();
+  let row_revs = test.get_row_revs().await;
+  let scripts = vec![
+    UpdateTextCell {
+      row_id: row_revs[0].id.clone(),
+      text: "file10".to_string(),
+    },
+    UpdateTextCell {
+      row_id: row_revs[2].id.clone(),
+      text: "file2".to_string(),
+    },
+    UpdateTextCell {
+      row_id: row_revs[3].id.clone(),
+      text: "File1".to_string(),
+    },
+    InsertSort {
+      field_rev: text_field.clone(),
+      condition: SortCondition::Ascending,
+    },
+    AssertCellContentOrder {
+      field_id: text_field.id.clone(),
+      orders: vec!["AE", "AE", "File1", "file2", "file10", ""],
     },
   ];
   test.run_scripts(scripts).await;
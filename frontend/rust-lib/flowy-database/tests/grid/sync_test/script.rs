@@ -0,0 +1,206 @@
+use crate::grid::database_editor::DatabaseEditorTest;
+use flowy_database::entities::{CellIdParams, FieldChangesetParams, FieldType};
+use flowy_database::services::database::DatabaseRevisionEditor;
+use flowy_database::services::DATABASE_SYNC_INTERVAL_IN_MILLIS;
+use flowy_revision::REVISION_WRITE_INTERVAL_IN_MILLIS;
+use flowy_test::FlowySDKTest;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[derive(Clone, Copy)]
+pub enum Client {
+  Local,
+  Remote,
+}
+
+pub enum SyncScript {
+  UpdateTextCell {
+    client: Client,
+    row_index: usize,
+    content: String,
+  },
+  UpdateFieldName {
+    client: Client,
+    field_type: FieldType,
+    name: String,
+  },
+  Undo {
+    client: Client,
+  },
+  WaitForSync,
+  AssertDatabaseEqual,
+  AssertRowsEqual,
+  AssertFieldName {
+    field_type: FieldType,
+    expected: String,
+  },
+  AssertTextCell {
+    row_index: usize,
+    expected: String,
+  },
+}
+
+/// Opens the same database in two sdks that are connected to the same local server. The
+/// `Remote` client is initialized with the revisions of the `Local` client.
+pub struct DatabaseSyncTest {
+  inner: DatabaseEditorTest,
+  #[allow(dead_code)]
+  remote_sdk: FlowySDKTest,
+  remote_editor: Arc<DatabaseRevisionEditor>,
+}
+
+impl DatabaseSyncTest {
+  pub async fn new() -> Self {
+    let inner = DatabaseEditorTest::new_table().await;
+    // Wait for the revisions to be written to disk and pushed to the server.
+    sleep(Duration::from_millis(2 * REVISION_WRITE_INTERVAL_IN_MILLIS)).await;
+    wait_for_sync().await;
+
+    let remote_sdk = FlowySDKTest::connect_to(&inner.sdk);
+    let _ = remote_sdk.init_user().await;
+    let database_id = inner.view_id.clone();
+    let grid_manager = remote_sdk.grid_manager.clone();
+
+    let revisions = inner.editor.rev_manager().load_revisions().await.unwrap();
+    grid_manager
+      .create_database(&database_id, revisions)
+      .await
+      .unwrap();
+    for block_meta_rev in inner.block_meta_revs.iter() {
+      let rev_manager = inner
+        .editor
+        .block_rev_manager(&block_meta_rev.block_id)
+        .await
+        .unwrap();
+      let revisions = rev_manager.load_revisions().await.unwrap();
+      grid_manager
+        .create_database_block(&block_meta_rev.block_id, revisions)
+        .await
+        .unwrap();
+    }
+    let rev_manager = inner.editor.view_rev_manager(&database_id).await.unwrap();
+    let revisions = rev_manager.load_revisions().await.unwrap();
+    grid_manager
+      .create_database_view(&database_id, revisions)
+      .await
+      .unwrap();
+
+    let remote_editor = grid_manager.open_database(&database_id).await.unwrap();
+    let _ = remote_editor.get_all_row_revs(&database_id).await.unwrap();
+    Self {
+      inner,
+      remote_sdk,
+      remote_editor,
+    }
+  }
+
+  pub async fn run_scripts(&mut self, scripts: Vec<SyncScript>) {
+    for script in scripts {
+      self.run_script(script).await;
+    }
+  }
+
+  pub async fn run_script(&mut self, script: SyncScript) {
+    match script {
+      SyncScript::UpdateTextCell {
+        client,
+        row_index,
+        content,
+      } => {
+        let field_id = self.get_first_field_rev(FieldType::RichText).id.clone();
+        let row_id = self.row_revs[row_index].id.clone();
+        self
+          .editor_of(client)
+          .update_cell(row_id, field_id, content)
+          .await
+          .unwrap();
+      },
+      SyncScript::UpdateFieldName {
+        client,
+        field_type,
+        name,
+      } => {
+        let changeset = FieldChangesetParams {
+          field_id: self.get_first_field_rev(field_type).id.clone(),
+          database_id: self.view_id.clone(),
+          name: Some(name),
+          ..Default::default()
+        };
+        self
+          .editor_of(client)
+          .update_field(changeset)
+          .await
+          .unwrap();
+      },
+      SyncScript::Undo { client } => {
+        self.editor_of(client).undo().await.unwrap();
+      },
+      SyncScript::WaitForSync => {
+        wait_for_sync().await;
+      },
+      SyncScript::AssertDatabaseEqual => {
+        let local = self.editor.database_pad().read().await.json_str().unwrap();
+        let remote = self
+          .remote_editor
+          .database_pad()
+          .read()
+          .await
+          .json_str()
+          .unwrap();
+        assert_eq!(local, remote);
+      },
+      SyncScript::AssertRowsEqual => {
+        let local = self.editor.get_all_row_revs(&self.view_id).await.unwrap();
+        let remote = self
+          .remote_editor
+          .get_all_row_revs(&self.view_id)
+          .await
+          .unwrap();
+        assert_eq!(local, remote);
+      },
+      SyncScript::AssertFieldName {
+        field_type,
+        expected,
+      } => {
+        let field_id = self.get_first_field_rev(field_type).id.clone();
+        for editor in [&self.editor, &self.remote_editor] {
+          let field_rev = editor.get_field_rev(&field_id).await.unwrap();
+          assert_eq!(field_rev.name, expected);
+        }
+      },
+      SyncScript::AssertTextCell {
+        row_index,
+        expected,
+      } => {
+        let params = CellIdParams {
+          database_id: self.view_id.clone(),
+          field_id: self.get_first_field_rev(FieldType::RichText).id.clone(),
+          row_id: self.row_revs[row_index].id.clone(),
+        };
+        for editor in [&self.editor, &self.remote_editor] {
+          assert_eq!(editor.get_cell_display_str(&params).await, expected);
+        }
+      },
+    }
+  }
+
+  fn editor_of(&self, client: Client) -> Arc<DatabaseRevisionEditor> {
+    match client {
+      Client::Local => self.editor.clone(),
+      Client::Remote => self.remote_editor.clone(),
+    }
+  }
+}
+
+async fn wait_for_sync() {
+  sleep(Duration::from_millis(5 * DATABASE_SYNC_INTERVAL_IN_MILLIS)).await;
+}
+
+impl std::ops::Deref for DatabaseSyncTest {
+  type Target = DatabaseEditorTest;
+
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
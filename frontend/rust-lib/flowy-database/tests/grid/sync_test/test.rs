@@ -0,0 +1,92 @@
+use crate::grid::sync_test::script::{Client, DatabaseSyncTest, SyncScript::*};
+use flowy_database::entities::FieldType;
+
+#[tokio::test]
+async fn sync_concurrent_cell_changes_test() {
+  let mut test = DatabaseSyncTest::new().await;
+  let scripts = vec![
+    UpdateTextCell {
+      client: Client::Local,
+      row_index: 0,
+      content: "local".to_owned(),
+    },
+    UpdateTextCell {
+      client: Client::Remote,
+      row_index: 1,
+      content: "remote".to_owned(),
+    },
+    WaitForSync,
+    AssertRowsEqual,
+    AssertTextCell {
+      row_index: 0,
+      expected: "local".to_owned(),
+    },
+    AssertTextCell {
+      row_index: 1,
+      expected: "remote".to_owned(),
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn sync_concurrent_field_changes_test() {
+  let mut test = DatabaseSyncTest::new().await;
+  let scripts = vec![
+    UpdateFieldName {
+      client: Client::Local,
+      field_type: FieldType::RichText,
+      name: "local name".to_owned(),
+    },
+    UpdateFieldName {
+      client: Client::Remote,
+      field_type: FieldType::Number,
+      name: "remote name".to_owned(),
+    },
+    WaitForSync,
+    AssertDatabaseEqual,
+    AssertFieldName {
+      field_type: FieldType::RichText,
+      expected: "local name".to_owned(),
+    },
+    AssertFieldName {
+      field_type: FieldType::Number,
+      expected: "remote name".to_owned(),
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn sync_remote_cell_change_then_undo_test() {
+  let mut test = DatabaseSyncTest::new().await;
+  let scripts = vec![
+    UpdateTextCell {
+      client: Client::Local,
+      row_index: 0,
+      content: "local".to_owned(),
+    },
+    WaitForSync,
+    UpdateTextCell {
+      client: Client::Remote,
+      row_index: 1,
+      content: "remote".to_owned(),
+    },
+    WaitForSync,
+    // The undo item of the local change is transformed against the remote change.
+    Undo {
+      client: Client::Local,
+    },
+    WaitForSync,
+    AssertRowsEqual,
+    AssertTextCell {
+      row_index: 0,
+      expected: "A".to_owned(),
+    },
+    AssertTextCell {
+      row_index: 1,
+      expected: "remote".to_owned(),
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
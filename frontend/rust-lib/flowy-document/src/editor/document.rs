@@ -1,8 +1,10 @@
 use bytes::Bytes;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_revision::{RevisionMergeable, RevisionObjectDeserializer, RevisionObjectSerializer};
+use lib_ot::codec::markdown::markdown_decoder;
 use lib_ot::core::{
-  Extension, NodeDataBuilder, NodeOperation, NodeTree, NodeTreeContext, Selection, Transaction,
+  Extension, NodeData, NodeDataBuilder, NodeOperation, NodeTree, NodeTreeContext, Selection,
+  Transaction,
 };
 use lib_ot::text_delta::DeltaTextOperationBuilder;
 use revision_model::Revision;
@@ -22,6 +24,13 @@ impl Document {
     Ok(Self { tree })
   }
 
+  /// Creates the document from the Markdown. Check out the [markdown_decoder] for the supported
+  /// Markdown syntax.
+  pub fn from_markdown(markdown: &str) -> FlowyResult<Self> {
+    let tree = NodeTree::from_node_data(markdown_decoder(markdown), make_tree_context())?;
+    Ok(Self { tree })
+  }
+
   pub fn get_content(&self, pretty: bool) -> FlowyResult<String> {
     if pretty {
       serde_json::to_string_pretty(self).map_err(|err| FlowyError::serde().context(err))
@@ -38,6 +47,36 @@ impl Document {
   pub fn get_tree(&self) -> &NodeTree {
     &self.tree
   }
+
+  /// Returns the transaction that replaces the content of the document with the other's. The
+  /// top-level nodes are deleted and then the other's top-level nodes are inserted.
+  pub fn make_reset_transaction(&self, other: &Document) -> Transaction {
+    let mut operations = vec![];
+    let nodes = top_level_node_data(&self.tree);
+    if !nodes.is_empty() {
+      operations.push(NodeOperation::Delete {
+        path: vec![0].into(),
+        nodes,
+      });
+    }
+
+    let nodes = top_level_node_data(&other.tree);
+    if !nodes.is_empty() {
+      operations.push(NodeOperation::Insert {
+        path: vec![0].into(),
+        nodes,
+      });
+    }
+    Transaction::from_operations(operations)
+  }
+}
+
+fn top_level_node_data(tree: &NodeTree) -> Vec<NodeData> {
+  tree
+    .get_children_ids(tree.root_node_id())
+    .into_iter()
+    .flat_map(|node_id| tree.get_node_data(node_id))
+    .collect()
 }
 
 pub(crate) fn make_tree_context() -> NodeTreeContext {
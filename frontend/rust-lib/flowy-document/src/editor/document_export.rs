@@ -0,0 +1,234 @@
+use crate::editor::document::Document;
+use crate::editor::document_serde::DocumentNode;
+use lib_ot::core::{AttributeHashMap, NodeId};
+use lib_ot::text_delta::DeltaTextOperations;
+use std::collections::HashMap;
+
+const EDITOR_NODE_TYPE: &str = "editor";
+const TEXT_NODE_TYPE: &str = "text";
+const IMAGE_NODE_TYPE: &str = "image";
+const DOCUMENT_LINK_PREFIX: &str = "appflowy://document/";
+
+/// Returns the link that is used to share the document with the given id.
+pub fn make_document_link(doc_id: &str) -> String {
+  format!("{}{}", DOCUMENT_LINK_PREFIX, doc_id)
+}
+
+/// Replaces the links to the documents in the `id_map` with the links to their copies. The links
+/// to the other documents are kept as they are.
+pub fn remap_document_links(content: &str, id_map: &HashMap<String, String>) -> String {
+  let mut remapped = String::with_capacity(content.len());
+  let mut rest = content;
+  while let Some(index) = rest.find(DOCUMENT_LINK_PREFIX) {
+    let (link_prefix, after) = rest.split_at(index + DOCUMENT_LINK_PREFIX.len());
+    remapped.push_str(link_prefix);
+
+    let id_len = after
+      .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+      .unwrap_or(after.len());
+    let (doc_id, after) = after.split_at(id_len);
+    remapped.push_str(id_map.get(doc_id).map(|id| id.as_str()).unwrap_or(doc_id));
+    rest = after;
+  }
+  remapped.push_str(rest);
+  remapped
+}
+
+impl Document {
+  /// Exports the document as Markdown. Each block of the document is encoded in one line and
+  /// the nested blocks are indented.
+  pub fn get_markdown(&self) -> String {
+    let mut lines = vec![];
+    for node in self.get_block_nodes() {
+      node_to_markdown(&node, 0, &mut lines);
+    }
+    lines.join("\n")
+  }
+
+  /// Exports the document as plain text. The block structure is flattened, each block is
+  /// encoded in one line without any style.
+  pub fn get_plain_text(&self) -> String {
+    let mut lines = vec![];
+    for node in self.get_block_nodes() {
+      node_to_plain_text(&node, &mut lines);
+    }
+    lines.join("\n")
+  }
+
+  /// Returns the top level blocks of the document. The blocks are the children of the `editor`
+  /// node.
+  fn get_block_nodes(&self) -> Vec<DocumentNode> {
+    let tree = self.get_tree();
+    let get_document_node = |node_id: NodeId| tree.get_node_data(node_id).map(DocumentNode::from);
+
+    let mut nodes = vec![];
+    for node_id in tree.get_children_ids(tree.root_node_id()) {
+      if let Some(node) = get_document_node(node_id) {
+        if node.node_type == EDITOR_NODE_TYPE {
+          nodes.extend(node.children);
+        } else {
+          nodes.push(node);
+        }
+      }
+    }
+    nodes
+  }
+}
+
+fn node_to_markdown(node: &DocumentNode, depth: usize, lines: &mut Vec<String>) {
+  let indent = "  ".repeat(depth);
+  match node.node_type.as_str() {
+    TEXT_NODE_TYPE => {
+      let prefix = markdown_block_prefix(&node.attributes);
+      lines.push(format!(
+        "{}{}{}",
+        indent,
+        prefix,
+        delta_to_markdown(&node.delta)
+      ));
+    },
+    IMAGE_NODE_TYPE => {
+      let src = get_str_attribute(&node.attributes, "image_src").unwrap_or_default();
+      lines.push(format!("{}![]({})", indent, src));
+    },
+    _ => {
+      if !node.delta.is_empty() {
+        lines.push(format!("{}{}", indent, delta_to_markdown(&node.delta)));
+      }
+    },
+  }
+
+  for child in node.children.iter() {
+    node_to_markdown(child, depth + 1, lines);
+  }
+}
+
+fn node_to_plain_text(node: &DocumentNode, lines: &mut Vec<String>) {
+  if node.node_type == TEXT_NODE_TYPE || !node.delta.is_empty() {
+    lines.push(delta_to_plain_text(&node.delta));
+  }
+
+  for child in node.children.iter() {
+    node_to_plain_text(child, lines);
+  }
+}
+
+/// Returns the Markdown prefix of the text block according to its `subtype`.
+fn markdown_block_prefix(attributes: &AttributeHashMap) -> String {
+  match get_str_attribute(attributes, "subtype").as_deref() {
+    Some("heading") => {
+      let level = get_str_attribute(attributes, "heading")
+        .and_then(|heading| heading.trim_start_matches('h').parse::<usize>().ok())
+        .unwrap_or(1)
+        .clamp(1, 6);
+      format!("{} ", "#".repeat(level))
+    },
+    Some("checkbox") => {
+      let is_checked = attributes
+        .get("checkbox")
+        .and_then(|value| value.bool_value())
+        .unwrap_or(false);
+      if is_checked {
+        "- [x] ".to_owned()
+      } else {
+        "- [ ] ".to_owned()
+      }
+    },
+    Some("bulleted-list") => "* ".to_owned(),
+    Some("number-list") => {
+      let number = attributes
+        .get("number")
+        .and_then(|value| value.int_value())
+        .unwrap_or(1);
+      format!("{}. ", number)
+    },
+    Some("quote") => "> ".to_owned(),
+    _ => "".to_owned(),
+  }
+}
+
+/// Encodes the inline styles of the text node as Markdown.
+fn delta_to_markdown(delta: &DeltaTextOperations) -> String {
+  let mut markdown = String::new();
+  for op in delta.ops.iter() {
+    let text = op.get_data();
+    if text.is_empty() {
+      continue;
+    }
+    let attributes = op.get_attributes();
+    let mut s = text.to_owned();
+    if is_attribute_enabled(&attributes, "code") {
+      s = wrap_text(&s, "`", "`");
+    }
+    if is_attribute_enabled(&attributes, "bold") {
+      s = wrap_text(&s, "**", "**");
+    }
+    if is_attribute_enabled(&attributes, "italic") {
+      s = wrap_text(&s, "_", "_");
+    }
+    if is_attribute_enabled(&attributes, "strikethrough") {
+      s = wrap_text(&s, "~~", "~~");
+    }
+    if is_attribute_enabled(&attributes, "underline") {
+      s = wrap_text(&s, "<u>", "</u>");
+    }
+    if let Some(href) = get_str_attribute(&attributes, "href") {
+      s = wrap_text(&s, "[", &format!("]({})", href));
+    }
+    markdown.push_str(&s);
+  }
+  markdown
+}
+
+/// Wraps the text with the given marks. The leading and trailing whitespaces are kept outside
+/// the marks, otherwise the Markdown parsers won't recognize the style.
+fn wrap_text(text: &str, start: &str, end: &str) -> String {
+  let trimmed = text.trim();
+  if trimmed.is_empty() {
+    return text.to_owned();
+  }
+  let leading_len = text.len() - text.trim_start().len();
+  let trailing_start = leading_len + trimmed.len();
+  format!(
+    "{}{}{}{}{}",
+    &text[..leading_len],
+    start,
+    trimmed,
+    end,
+    &text[trailing_start..]
+  )
+}
+
+pub(crate) fn delta_to_plain_text(delta: &DeltaTextOperations) -> String {
+  delta.ops.iter().map(|op| op.get_data()).collect::<String>()
+}
+
+fn is_attribute_enabled(attributes: &AttributeHashMap, key: &str) -> bool {
+  attributes
+    .get(key)
+    .and_then(|value| value.bool_value())
+    .unwrap_or(false)
+}
+
+fn get_str_attribute(attributes: &AttributeHashMap, key: &str) -> Option<String> {
+  attributes
+    .get(key)
+    .and_then(|value| value.str_value())
+    .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::editor::remap_document_links;
+  use std::collections::HashMap;
+
+  #[test]
+  fn document_remap_links_test() {
+    let content = r#"[{"insert":"a","attributes":{"href":"appflowy://document/abc"}},{"insert":"b","attributes":{"href":"appflowy://document/abcdef"}}]"#;
+    let id_map = HashMap::from([("abc".to_string(), "xyz".to_string())]);
+    assert_eq!(
+      remap_document_links(content, &id_map),
+      r#"[{"insert":"a","attributes":{"href":"appflowy://document/xyz"}},{"insert":"b","attributes":{"href":"appflowy://document/abcdef"}}]"#
+    );
+  }
+}
@@ -0,0 +1,134 @@
+use crate::editor::document::Document;
+use crate::editor::document_export::delta_to_plain_text;
+use flowy_error::FlowyResult;
+use flowy_sqlite::search::{SearchIndexRecord, SearchIndexSql, SearchObjectType};
+use flowy_sqlite::SqliteConnection;
+use lib_ot::core::{Body, NodeId, NodeOperation, Path, Transaction};
+
+impl Document {
+  /// Returns the search index records of all the blocks that contain text.
+  pub fn get_search_records(&self, doc_id: &str) -> Vec<SearchIndexRecord> {
+    let tree = self.get_tree();
+    let mut records = vec![];
+    let mut node_ids = tree.get_children_ids(tree.root_node_id());
+    while let Some(node_id) = node_ids.pop() {
+      if let Some(record) = self.get_search_record(doc_id, node_id) {
+        records.push(record);
+      }
+      node_ids.extend(tree.get_children_ids(node_id));
+    }
+    records
+  }
+
+  /// Replaces the indexed blocks of the document with its current blocks.
+  pub fn replace_search_index(&self, doc_id: &str, conn: &SqliteConnection) -> FlowyResult<()> {
+    let records = self.get_search_records(doc_id);
+    SearchIndexSql::replace_objects(doc_id, SearchObjectType::DocumentBlock, records, conn)?;
+    Ok(())
+  }
+
+  /// Returns the search index record of the block at the path. The record's content is empty if
+  /// the block doesn't exist anymore, so it's removed from the index.
+  pub fn get_block_search_record(&self, doc_id: &str, path: &Path) -> SearchIndexRecord {
+    self
+      .get_tree()
+      .node_id_at_path(path)
+      .and_then(|node_id| self.get_search_record(doc_id, node_id))
+      .unwrap_or_else(|| SearchIndexRecord::document_block(doc_id, &path_to_string(path), ""))
+  }
+
+  fn get_search_record(&self, doc_id: &str, node_id: NodeId) -> Option<SearchIndexRecord> {
+    let tree = self.get_tree();
+    match &tree.get_node(node_id)?.body {
+      Body::Delta(delta) if !delta.is_empty() => {
+        let path = tree.path_from_node_id(node_id);
+        let text = delta_to_plain_text(delta);
+        Some(SearchIndexRecord::document_block(
+          doc_id,
+          &path_to_string(&path),
+          &text,
+        ))
+      },
+      _ => None,
+    }
+  }
+}
+
+/// Returns the paths of the blocks whose text is changed by the transaction. Returns None if the
+/// transaction inserts, deletes or moves blocks, because the paths of the following blocks are
+/// changed too, then the whole document should be indexed again.
+pub fn get_changed_block_paths(transaction: &Transaction) -> Option<Vec<Path>> {
+  let mut paths: Vec<Path> = vec![];
+  for operation in transaction.operations.values() {
+    match operation.as_ref() {
+      NodeOperation::Update { path, changeset } => {
+        if changeset.is_delta() && !paths.contains(path) {
+          paths.push(path.clone());
+        }
+      },
+      _ => return None,
+    }
+  }
+  Some(paths)
+}
+
+/// The path of the block is stored in the search index as the indexes joined with commas, for
+/// example, `0,2,1`.
+fn path_to_string(path: &Path) -> String {
+  path
+    .iter()
+    .map(|index| index.to_string())
+    .collect::<Vec<String>>()
+    .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::editor::document::Document;
+  use crate::editor::get_changed_block_paths;
+  use lib_ot::core::{Changeset, NodeOperation, Transaction};
+  use lib_ot::text_delta::DeltaTextOperationBuilder;
+
+  #[test]
+  fn document_search_records_test() {
+    let json = r#"{"document":{"type":"editor","children":[{"type":"text","delta":[{"insert":"parent"}],"children":[{"type":"text","delta":[{"insert":"child"}]}]},{"type":"image"}]}}"#;
+    let document: Document = serde_json::from_str(json).unwrap();
+    let mut records = document
+      .get_search_records("doc")
+      .into_iter()
+      .map(|record| (record.object_id, record.content))
+      .collect::<Vec<_>>();
+    records.sort();
+    assert_eq!(
+      records,
+      vec![
+        ("0,0".to_owned(), "parent".to_owned()),
+        ("0,0,0".to_owned(), "child".to_owned())
+      ]
+    );
+  }
+
+  #[test]
+  fn document_changed_block_paths_test() {
+    let delta = DeltaTextOperationBuilder::new().insert("a").build();
+    let update = NodeOperation::Update {
+      path: vec![0, 1].into(),
+      changeset: Changeset::Delta {
+        delta: delta.clone(),
+        inverted: delta,
+      },
+    };
+    let transaction = Transaction::from_operations(vec![update.clone(), update]);
+    assert_eq!(
+      get_changed_block_paths(&transaction),
+      Some(vec![vec![0, 1].into()])
+    );
+
+    let delete = NodeOperation::Delete {
+      path: vec![0, 1].into(),
+      nodes: vec![],
+    };
+    let transaction = Transaction::from_operations(vec![delete]);
+    assert_eq!(get_changed_block_paths(&transaction), None);
+  }
+}
@@ -184,6 +184,9 @@ pub enum DocumentOperation {
     delta: DeltaTextOperations,
     inverted: DeltaTextOperations,
   },
+
+  #[serde(rename = "move")]
+  Move { from: Path, to: Path },
 }
 
 impl std::convert::From<DocumentOperation> for NodeOperation {
@@ -217,6 +220,7 @@ impl std::convert::From<DocumentOperation> for NodeOperation {
         path,
         changeset: Changeset::Delta { delta, inverted },
       },
+      DocumentOperation::Move { from, to } => NodeOperation::Move { from, to },
     }
   }
 }
@@ -245,6 +249,7 @@ impl std::convert::From<&NodeOperation> for DocumentOperation {
         path,
         nodes: nodes.into_iter().map(|node| node.into()).collect(),
       },
+      NodeOperation::Move { from, to } => DocumentOperation::Move { from, to },
     }
   }
 }
@@ -381,6 +386,12 @@ mod tests {
     let _transaction = serde_json::from_str::<DocumentTransaction>(json).unwrap();
   }
 
+  #[test]
+  fn transaction_deserialize_move_operation_test() {
+    let json = r#"{"operations":[{"op":"move","from":[0],"to":[2]}],"after_selection":{"start":{"path":[2],"offset":0},"end":{"path":[2],"offset":0}},"before_selection":{"start":{"path":[0],"offset":0},"end":{"path":[0],"offset":0}}}"#;
+    let _ = serde_json::from_str::<DocumentTransaction>(json).unwrap();
+  }
+
   #[test]
   fn transaction_deserialize_update_attribute_operation_test() {
     // let json = r#"{"operations":[{"op":"update","path":[0],"attributes":{"retain":3,"attributes":{"bold":true}},"oldAttributes":{"retain":3,"attributes":{"bold":null}}}]}"#;
@@ -396,6 +407,50 @@ mod tests {
     let _ = serde_json::to_string_pretty(&document).unwrap();
   }
 
+  #[test]
+  fn document_export_markdown_test() {
+    let document: Document = serde_json::from_str(EXPORT_EXAMPLE_DOCUMENT).unwrap();
+    assert_eq!(document.get_markdown(), EXPECTED_MARKDOWN);
+  }
+  #[test]
+  fn document_export_plain_text_test() {
+    let document: Document = serde_json::from_str(EXPORT_EXAMPLE_DOCUMENT).unwrap();
+    assert_eq!(document.get_plain_text(), EXPECTED_PLAIN_TEXT);
+  }
+  #[test]
+  fn document_export_after_serde_round_trip_test() {
+    let document: Document = serde_json::from_str(EXPORT_EXAMPLE_DOCUMENT).unwrap();
+    let json = document.get_content(false).unwrap();
+    let document: Document = serde_json::from_str(&json).unwrap();
+    assert_eq!(document.get_markdown(), EXPECTED_MARKDOWN);
+    assert_eq!(document.get_plain_text(), EXPECTED_PLAIN_TEXT);
+  }
+  #[test]
+  fn document_export_nested_blocks_test() {
+    let json = r#"{"document":{"type":"editor","children":[{"type":"text","attributes":{"subtype":"bulleted-list"},"delta":[{"insert":"parent"}],"children":[{"type":"text","attributes":{"subtype":"bulleted-list"},"delta":[{"insert":"child"}]}]}]}}"#;
+    let document: Document = serde_json::from_str(json).unwrap();
+    assert_eq!(document.get_markdown(), "* parent\n  * child");
+    assert_eq!(document.get_plain_text(), "parent\nchild");
+  }
+  #[test]
+  fn document_markdown_round_trip_test() {
+    let document = Document::from_markdown(EXPECTED_MARKDOWN).unwrap();
+    assert_eq!(document.get_markdown(), EXPECTED_MARKDOWN);
+    assert_eq!(document.get_plain_text(), EXPECTED_PLAIN_TEXT);
+
+    let markdown = "* parent\n  * child\n* sibling";
+    let document = Document::from_markdown(markdown).unwrap();
+    assert_eq!(document.get_markdown(), markdown);
+  }
+  #[test]
+  fn document_markdown_import_content_test() {
+    let document = Document::from_markdown("# AppFlowy").unwrap();
+    let json = document.get_content(false).unwrap();
+    assert_eq!(
+      json,
+      r#"{"document":{"type":"editor","children":[{"type":"text","attributes":{"subtype":"heading","heading":"h1"},"delta":[{"insert":"AppFlowy"}]}]}}"#
+    );
+  }
   // #[test]
   // fn document_operation_compose_test() {
   //     let json = include_str!("./test.json");
@@ -510,5 +565,104 @@ mod tests {
     ]
   }
 }
+"#;
+
+  const EXPECTED_MARKDOWN: &str = r#"![](https://s1.ax1x.com/2022/08/26/v2sSbR.jpg)
+# 👋 **Welcome to** [_**AppFlowy Editor**_](appflowy.io)
+
+AppFlowy Editor is a **highly customizable** _rich-text editor_ for <u>Flutter</u>
+- [x] Customizable
+- [ ] more to come!
+> Here is an example you can give a try
+## Features
+1. Use / to insert blocks
+2. `Select text` to trigger the ~~toolbar~~"#;
+
+  const EXPECTED_PLAIN_TEXT: &str = r#"👋 Welcome to AppFlowy Editor
+
+AppFlowy Editor is a highly customizable rich-text editor for Flutter
+Customizable
+more to come!
+Here is an example you can give a try
+Features
+Use / to insert blocks
+Select text to trigger the toolbar"#;
+
+  const EXPORT_EXAMPLE_DOCUMENT: &str = r#"{
+  "document": {
+    "type": "editor",
+    "children": [
+      {
+        "type": "image",
+        "attributes": {
+          "image_src": "https://s1.ax1x.com/2022/08/26/v2sSbR.jpg",
+          "align": "center"
+        }
+      },
+      {
+        "type": "text",
+        "attributes": { "subtype": "heading", "heading": "h1" },
+        "delta": [
+          { "insert": "👋 " },
+          { "insert": "Welcome to ", "attributes": { "bold": true } },
+          {
+            "insert": "AppFlowy Editor",
+            "attributes": {
+              "href": "appflowy.io",
+              "italic": true,
+              "bold": true
+            }
+          }
+        ]
+      },
+      { "type": "text", "delta": [] },
+      {
+        "type": "text",
+        "delta": [
+          { "insert": "AppFlowy Editor is a " },
+          { "insert": "highly customizable", "attributes": { "bold": true } },
+          { "insert": " " },
+          { "insert": "rich-text editor", "attributes": { "italic": true } },
+          { "insert": " for " },
+          { "insert": "Flutter", "attributes": { "underline": true } }
+        ]
+      },
+      {
+        "type": "text",
+        "attributes": { "checkbox": true, "subtype": "checkbox" },
+        "delta": [{ "insert": "Customizable" }]
+      },
+      {
+        "type": "text",
+        "attributes": { "checkbox": null, "subtype": "checkbox" },
+        "delta": [{ "insert": "more to come!" }]
+      },
+      {
+        "type": "text",
+        "attributes": { "subtype": "quote" },
+        "delta": [{ "insert": "Here is an example you can give a try" }]
+      },
+      {
+        "type": "text",
+        "attributes": { "subtype": "heading", "heading": "h2" },
+        "delta": [{ "insert": "Features" }]
+      },
+      {
+        "type": "text",
+        "attributes": { "subtype": "number-list", "number": 1 },
+        "delta": [{ "insert": "Use / to insert blocks" }]
+      },
+      {
+        "type": "text",
+        "attributes": { "subtype": "number-list", "number": 2 },
+        "delta": [
+          { "insert": "Select text", "attributes": { "code": true } },
+          { "insert": " to trigger the " },
+          { "insert": "toolbar", "attributes": { "strikethrough": true } }
+        ]
+      }
+    ]
+  }
+}
 "#;
 }
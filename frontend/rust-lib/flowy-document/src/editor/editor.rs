@@ -1,11 +1,12 @@
 use crate::editor::document::{Document, DocumentRevisionSerde};
 use crate::editor::document_serde::DocumentTransaction;
-use crate::editor::make_transaction_from_revisions;
 use crate::editor::queue::{Command, CommandSender, DocumentQueue};
+use crate::editor::{make_document_link, make_transaction_from_revisions};
+use crate::entities::ExportType;
 use crate::{DocumentEditor, DocumentUser};
 use bytes::Bytes;
 use flowy_error::{internal_error, FlowyError, FlowyResult};
-use flowy_revision::{RevisionCloudService, RevisionManager};
+use flowy_revision::{RevisionCloudService, RevisionManager, RevisionSnapshotData};
 use flowy_sqlite::ConnectionPool;
 use lib_infra::async_trait::async_trait;
 use lib_infra::future::FutureResult;
@@ -17,7 +18,6 @@ use tokio::sync::{mpsc, oneshot};
 use ws_model::ws_revision::ServerRevisionWSData;
 
 pub struct AppFlowyDocumentEditor {
-  #[allow(dead_code)]
   doc_id: String,
   command_sender: CommandSender,
   rev_manager: Arc<RevisionManager<Arc<ConnectionPool>>>,
@@ -28,13 +28,14 @@ impl AppFlowyDocumentEditor {
     doc_id: &str,
     user: Arc<dyn DocumentUser>,
     mut rev_manager: RevisionManager<Arc<ConnectionPool>>,
+    pool: Arc<ConnectionPool>,
     cloud_service: Arc<dyn RevisionCloudService>,
   ) -> FlowyResult<Arc<Self>> {
     let document = rev_manager
       .initialize::<DocumentRevisionSerde>(Some(cloud_service))
       .await?;
     let rev_manager = Arc::new(rev_manager);
-    let command_sender = spawn_edit_queue(user, rev_manager.clone(), document);
+    let command_sender = spawn_edit_queue(user, rev_manager.clone(), pool, document);
     let doc_id = doc_id.to_string();
     let editor = Arc::new(Self {
       doc_id,
@@ -64,12 +65,56 @@ impl AppFlowyDocumentEditor {
     Ok(content)
   }
 
+  pub async fn export_document(&self, export_type: ExportType) -> FlowyResult<String> {
+    if export_type == ExportType::Link {
+      return Ok(make_document_link(&self.doc_id));
+    }
+
+    let (ret, rx) = oneshot::channel::<FlowyResult<String>>();
+    let _ = self
+      .command_sender
+      .send(Command::ExportDocument { export_type, ret })
+      .await;
+    let content = rx.await.map_err(internal_error)??;
+    Ok(content)
+  }
+
   pub async fn duplicate_document(&self) -> FlowyResult<String> {
     let transaction = self.document_transaction().await?;
     let json = transaction.to_json()?;
     Ok(json)
   }
 
+  /// Returns the snapshots of the document, the latest one comes first.
+  pub async fn get_snapshots(&self) -> FlowyResult<Vec<RevisionSnapshotData>> {
+    self.rev_manager.read_snapshots().await
+  }
+
+  pub async fn preview_snapshot(&self, rev_id: i64) -> FlowyResult<String> {
+    let document = self
+      .rev_manager
+      .read_snapshot_object::<DocumentRevisionSerde>(rev_id)
+      .await?;
+    document.get_content(false)
+  }
+
+  /// Restores the document to the snapshot. The difference is applied as a new transaction, so
+  /// the revisions are not rewound.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn restore_snapshot(&self, rev_id: i64) -> FlowyResult<()> {
+    let document = self
+      .rev_manager
+      .read_snapshot_object::<DocumentRevisionSerde>(rev_id)
+      .await?;
+    let (ret, rx) = oneshot::channel::<FlowyResult<()>>();
+    let _ = self
+      .command_sender
+      .send(Command::RestoreDocument { document, ret })
+      .await;
+    rx.await.map_err(internal_error)??;
+    Ok(())
+  }
+
   pub async fn document_transaction(&self) -> FlowyResult<Transaction> {
     let revisions = self.rev_manager.load_revisions().await?;
     make_transaction_from_revisions(&revisions)
@@ -79,10 +124,11 @@ impl AppFlowyDocumentEditor {
 fn spawn_edit_queue(
   user: Arc<dyn DocumentUser>,
   rev_manager: Arc<RevisionManager<Arc<ConnectionPool>>>,
+  pool: Arc<ConnectionPool>,
   document: Document,
 ) -> CommandSender {
   let (sender, receiver) = mpsc::channel(1000);
-  let queue = DocumentQueue::new(user, rev_manager, document, receiver);
+  let queue = DocumentQueue::new(user, rev_manager, pool, document, receiver);
   tokio::spawn(queue.run());
   sender
 }
@@ -100,6 +146,11 @@ impl DocumentEditor for Arc<AppFlowyDocumentEditor> {
     FutureResult::new(async move { this.get_content(false).await })
   }
 
+  fn export_as(&self, export_type: ExportType) -> FutureResult<String, FlowyError> {
+    let this = self.clone();
+    FutureResult::new(async move { this.export_document(export_type).await })
+  }
+
   fn duplicate(&self) -> FutureResult<String, FlowyError> {
     let this = self.clone();
     FutureResult::new(async move { this.duplicate_document().await })
@@ -120,6 +171,21 @@ impl DocumentEditor for Arc<AppFlowyDocumentEditor> {
     })
   }
 
+  fn get_snapshots(&self) -> FutureResult<Vec<RevisionSnapshotData>, FlowyError> {
+    let this = self.clone();
+    FutureResult::new(async move { AppFlowyDocumentEditor::get_snapshots(&this).await })
+  }
+
+  fn preview_snapshot(&self, rev_id: i64) -> FutureResult<String, FlowyError> {
+    let this = self.clone();
+    FutureResult::new(async move { AppFlowyDocumentEditor::preview_snapshot(&this, rev_id).await })
+  }
+
+  fn restore_snapshot(&self, rev_id: i64) -> FutureResult<(), FlowyError> {
+    let this = self.clone();
+    FutureResult::new(async move { AppFlowyDocumentEditor::restore_snapshot(&this, rev_id).await })
+  }
+
   fn as_any(&self) -> &dyn Any {
     self
   }
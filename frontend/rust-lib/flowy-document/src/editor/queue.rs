@@ -1,12 +1,15 @@
 use crate::editor::document::Document;
+use crate::editor::get_changed_block_paths;
+use crate::entities::ExportType;
 use crate::DocumentUser;
 use async_stream::stream;
 use bytes::Bytes;
-use flowy_error::FlowyError;
+use flowy_error::{internal_error, FlowyError, FlowyResult};
 use flowy_revision::RevisionManager;
 use futures::stream::StreamExt;
 use lib_ot::core::Transaction;
 
+use flowy_sqlite::search::{SearchIndexSql, SearchObjectType};
 use flowy_sqlite::ConnectionPool;
 use std::sync::Arc;
 use tokio::sync::mpsc::{Receiver, Sender};
@@ -18,6 +21,7 @@ pub struct DocumentQueue {
   document: Arc<RwLock<Document>>,
   #[allow(dead_code)]
   rev_manager: Arc<RevisionManager<Arc<ConnectionPool>>>,
+  pool: Arc<ConnectionPool>,
   receiver: Option<CommandReceiver>,
 }
 
@@ -25,6 +29,7 @@ impl DocumentQueue {
   pub fn new(
     user: Arc<dyn DocumentUser>,
     rev_manager: Arc<RevisionManager<Arc<ConnectionPool>>>,
+    pool: Arc<ConnectionPool>,
     document: Document,
     receiver: CommandReceiver,
   ) -> Self {
@@ -33,11 +38,16 @@ impl DocumentQueue {
       user,
       document,
       rev_manager,
+      pool,
       receiver: Some(receiver),
     }
   }
 
   pub async fn run(mut self) {
+    if let Err(e) = self.initialize_search_index().await {
+      tracing::error!("[DocumentQueue]: index document failed: {}", e);
+    }
+
     let mut receiver = self.receiver.take().expect("Only take once");
     let stream = stream! {
         loop {
@@ -65,6 +75,20 @@ impl DocumentQueue {
           .write()
           .await
           .apply_transaction(transaction.clone())?;
+        self.update_search_index(&transaction).await;
+        let _ = self
+          .save_local_operations(transaction, self.document.read().await.document_md5())
+          .await?;
+        let _ = ret.send(Ok(()));
+      },
+      Command::RestoreDocument { document, ret } => {
+        let transaction = self.document.read().await.make_reset_transaction(&document);
+        self
+          .document
+          .write()
+          .await
+          .apply_transaction(transaction.clone())?;
+        self.update_search_index(&transaction).await;
         let _ = self
           .save_local_operations(transaction, self.document.read().await.document_md5())
           .await?;
@@ -74,10 +98,61 @@ impl DocumentQueue {
         let content = self.document.read().await.get_content(pretty)?;
         let _ = ret.send(Ok(content));
       },
+      Command::ExportDocument { export_type, ret } => {
+        let document = self.document.read().await;
+        let result = match export_type {
+          ExportType::Text => Ok(document.get_plain_text()),
+          ExportType::Markdown => Ok(document.get_markdown()),
+          ExportType::Link => {
+            Err(FlowyError::internal().context("The link of the document is built by the editor"))
+          },
+        };
+        let _ = ret.send(result);
+      },
     }
     Ok(())
   }
 
+  /// Indexes all the blocks of the document if it has not been indexed yet, for example, the
+  /// document was created before the search index was added.
+  async fn initialize_search_index(&self) -> FlowyResult<()> {
+    let doc_id = &self.rev_manager.object_id;
+    let conn = self.pool.get().map_err(internal_error)?;
+    if !SearchIndexSql::has_objects(doc_id, SearchObjectType::DocumentBlock, &conn)? {
+      self
+        .document
+        .read()
+        .await
+        .replace_search_index(doc_id, &conn)?;
+    }
+    Ok(())
+  }
+
+  /// Updates the search index with the blocks that are changed by the transaction. The whole
+  /// document is indexed again if the blocks are inserted, deleted or moved. The document is
+  /// saved even if indexing fails, so the error is only logged.
+  async fn update_search_index(&self, transaction: &Transaction) {
+    let doc_id = &self.rev_manager.object_id;
+    let document = self.document.read().await;
+    let result = || -> FlowyResult<()> {
+      let conn = self.pool.get().map_err(internal_error)?;
+      match get_changed_block_paths(transaction) {
+        None => document.replace_search_index(doc_id, &conn)?,
+        Some(paths) => {
+          for path in paths {
+            let record = document.get_block_search_record(doc_id, &path);
+            SearchIndexSql::upsert(record, &conn)?;
+          }
+        },
+      }
+      Ok(())
+    };
+
+    if let Err(e) = result() {
+      tracing::error!("[DocumentQueue]: update search index failed: {}", e);
+    }
+  }
+
   #[tracing::instrument(level = "trace", skip(self, transaction, md5), err)]
   async fn save_local_operations(
     &self,
@@ -99,8 +174,17 @@ pub enum Command {
     transaction: Transaction,
     ret: Ret<()>,
   },
+  /// Replaces the content with the document's. The change is saved as a new revision.
+  RestoreDocument {
+    document: Document,
+    ret: Ret<()>,
+  },
   GetDocumentContent {
     pretty: bool,
     ret: Ret<String>,
   },
+  ExportDocument {
+    export_type: ExportType,
+    ret: Ret<String>,
+  },
 }
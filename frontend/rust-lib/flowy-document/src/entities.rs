@@ -1,5 +1,6 @@
 use crate::errors::ErrorCode;
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_revision::RevisionSnapshotData;
 use std::convert::TryInto;
 
 #[derive(PartialEq, Eq, Debug, ProtoBuf_Enum, Clone)]
@@ -130,3 +131,111 @@ pub struct ExportDataPB {
   #[pb(index = 2)]
   pub export_type: ExportType,
 }
+
+/// [ImportMarkdownPayloadPB] is used to create a document view from the Markdown. The view is
+/// created as a child view of the [belong_to_id].
+#[derive(Default, ProtoBuf)]
+pub struct ImportMarkdownPayloadPB {
+  #[pb(index = 1)]
+  pub belong_to_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub markdown: String,
+}
+
+/// [ImportMarkdownPayloadPB] is used to create a node-based document from the Markdown. The
+/// document is created as a child view of the [belong_to_id].
+#[derive(Default, ProtoBuf)]
+pub struct ImportMarkdownPayloadPB {
+  #[pb(index = 1)]
+  pub belong_to_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub markdown: String,
+}
+
+#[derive(Debug)]
+pub struct ImportMarkdownParams {
+  pub belong_to_id: String,
+  pub name: String,
+  pub markdown: String,
+}
+
+impl TryInto<ImportMarkdownParams> for ImportMarkdownPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ImportMarkdownParams, Self::Error> {
+    if self.belong_to_id.trim().is_empty() {
+      return Err(ErrorCode::AppIdInvalid);
+    }
+    if self.name.trim().is_empty() {
+      return Err(ErrorCode::ViewNameInvalid);
+    }
+    Ok(ImportMarkdownParams {
+      belong_to_id: self.belong_to_id,
+      name: self.name,
+      markdown: self.markdown,
+    })
+  }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct DocumentSnapshotPB {
+  #[pb(index = 1)]
+  pub rev_id: i64,
+
+  /// The time when the snapshot was taken, in seconds.
+  #[pb(index = 2)]
+  pub timestamp: i64,
+}
+
+impl std::convert::From<RevisionSnapshotData> for DocumentSnapshotPB {
+  fn from(snapshot: RevisionSnapshotData) -> Self {
+    Self {
+      rev_id: snapshot.rev_id,
+      timestamp: snapshot.timestamp,
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct RepeatedDocumentSnapshotPB {
+  #[pb(index = 1)]
+  pub items: Vec<DocumentSnapshotPB>,
+}
+
+impl std::convert::From<Vec<RevisionSnapshotData>> for RepeatedDocumentSnapshotPB {
+  fn from(snapshots: Vec<RevisionSnapshotData>) -> Self {
+    Self {
+      items: snapshots
+        .into_iter()
+        .map(|snapshot| snapshot.into())
+        .collect(),
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct DocumentSnapshotIdPB {
+  #[pb(index = 1)]
+  pub document_id: String,
+
+  #[pb(index = 2)]
+  pub rev_id: i64,
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct DocumentSnapshotContentPB {
+  #[pb(index = 1)]
+  pub rev_id: i64,
+
+  /// Encode in JSON format
+  #[pb(index = 2)]
+  pub content: String,
+}
@@ -1,6 +1,7 @@
 use crate::entities::{
-  DocumentDataPB, EditParams, EditPayloadPB, ExportDataPB, ExportParams, ExportPayloadPB,
-  OpenDocumentPayloadPB,
+  DocumentDataPB, DocumentSnapshotContentPB, DocumentSnapshotIdPB, EditParams, EditPayloadPB,
+  ExportDataPB, ExportParams, ExportPayloadPB, ImportMarkdownParams, ImportMarkdownPayloadPB,
+  OpenDocumentPayloadPB, RepeatedDocumentSnapshotPB,
 };
 use crate::DocumentManager;
 use flowy_error::FlowyError;
@@ -38,9 +39,58 @@ pub(crate) async fn export_handler(
 ) -> DataResult<ExportDataPB, FlowyError> {
   let params: ExportParams = data.into_inner().try_into()?;
   let editor = manager.open_document_editor(&params.view_id).await?;
-  let document_data = editor.export().await?;
+  let document_data = editor.export_as(params.export_type.clone()).await?;
   data_result(ExportDataPB {
     data: document_data,
     export_type: params.export_type,
   })
 }
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn import_markdown_handler(
+  data: AFPluginData<ImportMarkdownPayloadPB>,
+  manager: AFPluginState<Arc<DocumentManager>>,
+) -> DataResult<DocumentDataPB, FlowyError> {
+  let params: ImportMarkdownParams = data.into_inner().try_into()?;
+  let (view_id, content) = manager.import_markdown(params).await?;
+  data_result(DocumentDataPB {
+    doc_id: view_id,
+    content,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn get_document_snapshots_handler(
+  data: AFPluginData<OpenDocumentPayloadPB>,
+  manager: AFPluginState<Arc<DocumentManager>>,
+) -> DataResult<RepeatedDocumentSnapshotPB, FlowyError> {
+  let context: OpenDocumentPayloadPB = data.into_inner();
+  let editor = manager.open_document_editor(&context.document_id).await?;
+  let snapshots = editor.get_snapshots().await?;
+  data_result(snapshots.into())
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn preview_document_snapshot_handler(
+  data: AFPluginData<DocumentSnapshotIdPB>,
+  manager: AFPluginState<Arc<DocumentManager>>,
+) -> DataResult<DocumentSnapshotContentPB, FlowyError> {
+  let params: DocumentSnapshotIdPB = data.into_inner();
+  let editor = manager.open_document_editor(&params.document_id).await?;
+  let content = editor.preview_snapshot(params.rev_id).await?;
+  data_result(DocumentSnapshotContentPB {
+    rev_id: params.rev_id,
+    content,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn restore_document_snapshot_handler(
+  data: AFPluginData<DocumentSnapshotIdPB>,
+  manager: AFPluginState<Arc<DocumentManager>>,
+) -> Result<(), FlowyError> {
+  let params: DocumentSnapshotIdPB = data.into_inner();
+  let editor = manager.open_document_editor(&params.document_id).await?;
+  editor.restore_snapshot(params.rev_id).await?;
+  Ok(())
+}
@@ -13,7 +13,20 @@ pub fn init(document_manager: Arc<DocumentManager>) -> AFPlugin {
   plugin = plugin
     .event(DocumentEvent::GetDocument, get_document_handler)
     .event(DocumentEvent::ApplyEdit, apply_edit_handler)
-    .event(DocumentEvent::ExportDocument, export_handler);
+    .event(DocumentEvent::ExportDocument, export_handler)
+    .event(DocumentEvent::ImportMarkdown, import_markdown_handler)
+    .event(
+      DocumentEvent::GetDocumentSnapshots,
+      get_document_snapshots_handler,
+    )
+    .event(
+      DocumentEvent::PreviewDocumentSnapshot,
+      preview_document_snapshot_handler,
+    )
+    .event(
+      DocumentEvent::RestoreDocumentSnapshot,
+      restore_document_snapshot_handler,
+    );
 
   plugin
 }
@@ -29,4 +42,24 @@ pub enum DocumentEvent {
 
   #[event(input = "ExportPayloadPB", output = "ExportDataPB")]
   ExportDocument = 2,
+
+  /// [ImportMarkdown] event is used to create a node-based document view from the Markdown.
+  /// Returns the document of the new view, the [doc_id] is the id of the view.
+  #[event(input = "ImportMarkdownPayloadPB", output = "DocumentDataPB")]
+  ImportMarkdown = 3,
+
+  /// [GetDocumentSnapshots] event is used to list the snapshots of the document, the latest
+  /// one comes first.
+  #[event(input = "OpenDocumentPayloadPB", output = "RepeatedDocumentSnapshotPB")]
+  GetDocumentSnapshots = 4,
+
+  /// [PreviewDocumentSnapshot] event is used to get the document content at the snapshot
+  /// without changing the document.
+  #[event(input = "DocumentSnapshotIdPB", output = "DocumentSnapshotContentPB")]
+  PreviewDocumentSnapshot = 5,
+
+  /// [RestoreDocumentSnapshot] event is used to restore the document to the snapshot. The
+  /// restore is saved as a new revision.
+  #[event(input = "DocumentSnapshotIdPB")]
+  RestoreDocumentSnapshot = 6,
 }
@@ -1,5 +1,8 @@
-use crate::editor::{initial_document_content, AppFlowyDocumentEditor, DocumentRevisionMergeable};
-use crate::entities::{DocumentVersionPB, EditParams};
+use crate::editor::{
+  initial_document_content, AppFlowyDocumentEditor, Document, DocumentRevisionMergeable,
+  DocumentRevisionSerde,
+};
+use crate::entities::{DocumentVersionPB, EditParams, ExportType, ImportMarkdownParams};
 use crate::old_editor::editor::{DeltaDocumentEditor, DeltaDocumentRevisionMergeable};
 use crate::old_editor::snapshot::DeltaDocumentSnapshotPersistence;
 use crate::services::rev_sqlite::{
@@ -11,10 +14,10 @@ use crate::{errors::FlowyError, DocumentCloudService};
 use bytes::Bytes;
 use document_model::document::DocumentId;
 use flowy_client_sync::client_document::initial_delta_document_content;
-use flowy_error::FlowyResult;
+use flowy_error::{internal_error, FlowyResult};
 use flowy_revision::{
-  RevisionCloudService, RevisionManager, RevisionPersistence, RevisionPersistenceConfiguration,
-  RevisionWebSocket,
+  RevisionCloudService, RevisionManager, RevisionObjectDeserializer, RevisionPersistence,
+  RevisionPersistenceConfiguration, RevisionSnapshotData, RevisionWebSocket,
 };
 use flowy_sqlite::ConnectionPool;
 use lib_infra::async_trait::async_trait;
@@ -39,6 +42,20 @@ pub trait DocumentDatabase: Send + Sync {
   fn db_pool(&self) -> Result<Arc<ConnectionPool>, FlowyError>;
 }
 
+/// [DocumentFolderDelegate] creates the views in the folder for the documents that are built by
+/// the [DocumentManager], for example, importing a document from Markdown.
+#[async_trait]
+pub trait DocumentFolderDelegate: Send + Sync {
+  /// Creates a node-based document view that belongs to [belong_to_id] with the
+  /// [document_content] and returns the id of the view. The content is encoded in JSON format.
+  async fn create_document_view(
+    &self,
+    belong_to_id: &str,
+    name: &str,
+    document_content: String,
+  ) -> FlowyResult<String>;
+}
+
 #[async_trait]
 pub trait DocumentEditor: Send + Sync {
   /// Called when the document get closed
@@ -48,6 +65,9 @@ pub trait DocumentEditor: Send + Sync {
   /// editor data format.
   fn export(&self) -> FutureResult<String, FlowyError>;
 
+  /// Exports the document in the format of the [ExportType].
+  fn export_as(&self, export_type: ExportType) -> FutureResult<String, FlowyError>;
+
   /// Duplicate the document inner data into String
   fn duplicate(&self) -> FutureResult<String, FlowyError>;
 
@@ -59,6 +79,17 @@ pub trait DocumentEditor: Send + Sync {
   /// in binary format.
   fn compose_local_operations(&self, data: Bytes) -> FutureResult<(), FlowyError>;
 
+  /// Returns the snapshots of the document, the latest one comes first.
+  fn get_snapshots(&self) -> FutureResult<Vec<RevisionSnapshotData>, FlowyError>;
+
+  /// Returns the document content at the snapshot. The content is encoded in the same
+  /// format as the [DocumentEditor::export].
+  fn preview_snapshot(&self, rev_id: i64) -> FutureResult<String, FlowyError>;
+
+  /// Restores the document to the snapshot. The restore is saved as a new revision instead
+  /// of rewinding the revisions, so it's synced like the other edits.
+  fn restore_snapshot(&self, rev_id: i64) -> FutureResult<(), FlowyError>;
+
   /// Returns the `Any` reference that can be used to downcast back to the original,
   /// concrete type.
   ///
@@ -91,6 +122,7 @@ pub struct DocumentManager {
   persistence: Arc<DocumentPersistence>,
   #[allow(dead_code)]
   config: DocumentConfig,
+  folder_delegate: RwLock<Option<Arc<dyn DocumentFolderDelegate>>>,
 }
 
 impl DocumentManager {
@@ -108,9 +140,35 @@ impl DocumentManager {
       user: document_user,
       persistence: Arc::new(DocumentPersistence::new(database)),
       config,
+      folder_delegate: RwLock::new(None),
     }
   }
 
+  /// The folder is initialized after the [DocumentManager], so the delegate is set afterwards.
+  pub async fn set_folder_delegate(&self, folder_delegate: Arc<dyn DocumentFolderDelegate>) {
+    *self.folder_delegate.write().await = Some(folder_delegate);
+  }
+
+  /// Creates a new node-based document from the Markdown. Returns the id of its view and the
+  /// document content.
+  #[tracing::instrument(level = "debug", skip_all, err)]
+  pub async fn import_markdown(
+    &self,
+    params: ImportMarkdownParams,
+  ) -> FlowyResult<(String, String)> {
+    let content = Document::from_markdown(&params.markdown)?.get_content(false)?;
+    let folder_delegate = self
+      .folder_delegate
+      .read()
+      .await
+      .clone()
+      .ok_or_else(|| FlowyError::internal().context("The folder delegate is not set"))?;
+    let view_id = folder_delegate
+      .create_document_view(&params.belong_to_id, &params.name, content.clone())
+      .await?;
+    Ok((view_id, content))
+  }
+
   /// Called immediately after the application launched with the user sign in/sign up.
   #[tracing::instrument(level = "trace", skip_all, err)]
   pub async fn initialize(&self, user_id: &str) -> FlowyResult<()> {
@@ -157,8 +215,21 @@ impl DocumentManager {
     let doc_id = doc_id.as_ref().to_owned();
     let db_pool = self.persistence.database.db_pool()?;
     // Maybe we could save the document to disk without creating the RevisionManager
-    let rev_manager = self.make_rev_manager(&doc_id, db_pool)?;
-    rev_manager.reset_object(revisions).await?;
+    let rev_manager = self.make_rev_manager(&doc_id, db_pool.clone())?;
+    rev_manager.reset_object(revisions.clone()).await?;
+
+    // Index the blocks, so the created, duplicated or imported document can be searched before
+    // it's opened. Only the node-based documents are indexed.
+    if self.config.version == DocumentVersionPB::V1 {
+      let result = || -> FlowyResult<()> {
+        let document = DocumentRevisionSerde::deserialize_revisions(&doc_id, revisions)?;
+        let conn = db_pool.get().map_err(internal_error)?;
+        document.replace_search_index(&doc_id, &conn)
+      };
+      if let Err(e) = result() {
+        tracing::error!("Index the document {} failed: {}", doc_id, e);
+      }
+    }
     Ok(())
   }
 
@@ -255,8 +326,9 @@ impl DocumentManager {
       },
       DocumentVersionPB::V1 => {
         let rev_manager = self.make_document_rev_manager(doc_id, pool.clone())?;
-        let editor: Arc<dyn DocumentEditor> =
-          Arc::new(AppFlowyDocumentEditor::new(doc_id, user, rev_manager, cloud_service).await?);
+        let editor: Arc<dyn DocumentEditor> = Arc::new(
+          AppFlowyDocumentEditor::new(doc_id, user, rev_manager, pool, cloud_service).await?,
+        );
         self
           .editor_map
           .write()
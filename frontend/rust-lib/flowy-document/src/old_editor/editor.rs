@@ -1,6 +1,8 @@
 #![allow(unused_attributes)]
 #![allow(unused_attributes)]
 
+use crate::editor::make_document_link;
+use crate::entities::ExportType;
 use crate::old_editor::queue::{EditDocumentQueue, EditorCommand, EditorCommandSender};
 use crate::{errors::FlowyError, DocumentEditor, DocumentUser};
 use bytes::Bytes;
@@ -10,13 +12,14 @@ use flowy_client_sync::make_operations_from_revisions;
 use flowy_error::{internal_error, FlowyResult};
 use flowy_revision::{
   RevisionCloudService, RevisionManager, RevisionMergeable, RevisionObjectDeserializer,
-  RevisionObjectSerializer, RevisionWebSocket,
+  RevisionObjectSerializer, RevisionSnapshotData, RevisionWebSocket,
 };
 use flowy_sqlite::ConnectionPool;
 use lib_infra::async_trait::async_trait;
 use lib_infra::future::FutureResult;
 use lib_ot::core::{AttributeEntry, AttributeHashMap};
 use lib_ot::{
+  codec::markdown::markdown_encoder,
   core::{DeltaOperation, Interval},
   text_delta::DeltaTextOperations,
 };
@@ -29,7 +32,6 @@ use ws_model::ws_revision::ServerRevisionWSData;
 
 pub struct DeltaDocumentEditor {
   pub doc_id: String,
-  #[allow(dead_code)]
   rev_manager: Arc<RevisionManager<Arc<ConnectionPool>>>,
   #[cfg(feature = "sync")]
   ws_manager: Arc<flowy_revision::RevisionWebSocketManager>,
@@ -170,6 +172,24 @@ impl DocumentEditor for Arc<DeltaDocumentEditor> {
     })
   }
 
+  fn export_as(&self, export_type: ExportType) -> FutureResult<String, FlowyError> {
+    let doc_id = self.doc_id.clone();
+    let edit_cmd_tx = self.edit_cmd_tx.clone();
+    FutureResult::new(async move {
+      if export_type == ExportType::Link {
+        return Ok(make_document_link(&doc_id));
+      }
+
+      let (ret, rx) = oneshot::channel::<SyncResult<DeltaTextOperations>>();
+      let _ = edit_cmd_tx.send(EditorCommand::GetOperations { ret }).await;
+      let operations = rx.await.map_err(internal_error)??;
+      match export_type {
+        ExportType::Markdown => Ok(markdown_encoder(&operations)),
+        _ => Ok(operations.content()?),
+      }
+    })
+  }
+
   fn duplicate(&self) -> FutureResult<String, FlowyError> {
     self.export()
   }
@@ -204,6 +224,23 @@ impl DocumentEditor for Arc<DeltaDocumentEditor> {
     })
   }
 
+  fn get_snapshots(&self) -> FutureResult<Vec<RevisionSnapshotData>, FlowyError> {
+    let rev_manager = self.rev_manager.clone();
+    FutureResult::new(async move { rev_manager.read_snapshots().await })
+  }
+
+  fn preview_snapshot(&self, _rev_id: i64) -> FutureResult<String, FlowyError> {
+    FutureResult::new(async move {
+      Err(FlowyError::internal().context("The delta document doesn't support snapshots"))
+    })
+  }
+
+  fn restore_snapshot(&self, _rev_id: i64) -> FutureResult<(), FlowyError> {
+    FutureResult::new(async move {
+      Err(FlowyError::internal().context("The delta document doesn't support snapshots"))
+    })
+  }
+
   fn as_any(&self) -> &dyn Any {
     self
   }
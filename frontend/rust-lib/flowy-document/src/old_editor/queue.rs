@@ -249,7 +249,6 @@ pub(crate) enum EditorCommand {
   GetOperationsString {
     ret: Ret<String>,
   },
-  #[allow(dead_code)]
   GetOperations {
     ret: Ret<DeltaTextOperations>,
   },
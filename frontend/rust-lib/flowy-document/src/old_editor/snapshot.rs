@@ -15,4 +15,8 @@ impl RevisionSnapshotPersistence for DeltaDocumentSnapshotPersistence {
   fn read_last_snapshot(&self) -> FlowyResult<Option<RevisionSnapshotData>> {
     Ok(None)
   }
+
+  fn read_snapshots(&self) -> FlowyResult<Vec<RevisionSnapshotData>> {
+    Ok(vec![])
+  }
 }
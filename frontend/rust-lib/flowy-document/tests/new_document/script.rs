@@ -16,7 +16,6 @@ pub enum EditScript {
     path: Path,
     delta: DeltaTextOperations,
   },
-  #[allow(dead_code)]
   ComposeTransaction {
     transaction: Transaction,
   },
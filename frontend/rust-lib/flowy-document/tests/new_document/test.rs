@@ -1,6 +1,7 @@
 use crate::new_document::script::DocumentEditorTest;
 use crate::new_document::script::EditScript::*;
 
+use flowy_document::editor::Document;
 use lib_ot::text_delta::DeltaTextOperationBuilder;
 
 #[tokio::test]
@@ -162,3 +163,32 @@ async fn document_delete_node_test() {
 
   DocumentEditorTest::new().await.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn document_reset_to_snapshot_test() {
+  let test = DocumentEditorTest::new().await;
+  let transaction = test.editor.document_transaction().await.unwrap();
+  let snapshot = Document::from_transaction(transaction).unwrap();
+
+  let delta = DeltaTextOperationBuilder::new()
+    .insert("Hello world")
+    .build();
+  test
+    .run_scripts(vec![InsertText {
+      path: vec![0, 0].into(),
+      delta,
+    }])
+    .await;
+
+  let transaction = test.editor.document_transaction().await.unwrap();
+  let document = Document::from_transaction(transaction).unwrap();
+  let scripts = vec![
+    ComposeTransaction {
+      transaction: document.make_reset_transaction(&snapshot),
+    },
+    AssertContent {
+      expected: r#"{"document":{"type":"editor","children":[{"type":"text"}]}}"#,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
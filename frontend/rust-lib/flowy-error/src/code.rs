@@ -186,6 +186,21 @@ pub enum ErrorCode {
 
   #[error("Payload should not be empty")]
   UnexpectedEmptyPayload = 60,
+
+  #[error("Invalid formula")]
+  InvalidFormula = 61,
+
+  #[error("Calculation id is empty")]
+  CalculationIdIsEmpty = 62,
+
+  #[error("Invalid CSV")]
+  InvalidCSV = 63,
+
+  #[error("Invalid backup")]
+  InvalidBackup = 64,
+
+  #[error("Decrypt the secret failed")]
+  DecryptSecretFailed = 65,
 }
 
 impl ErrorCode {
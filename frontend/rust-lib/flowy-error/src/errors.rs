@@ -82,6 +82,10 @@ impl FlowyError {
   static_flowy_error!(field_record_not_found, ErrorCode::FieldRecordNotFound);
   static_flowy_error!(payload_none, ErrorCode::UnexpectedEmptyPayload);
   static_flowy_error!(http, ErrorCode::HttpError);
+  static_flowy_error!(invalid_formula, ErrorCode::InvalidFormula);
+  static_flowy_error!(invalid_csv, ErrorCode::InvalidCSV);
+  static_flowy_error!(invalid_backup, ErrorCode::InvalidBackup);
+  static_flowy_error!(decrypt_secret_failed, ErrorCode::DecryptSecretFailed);
 }
 
 impl std::convert::From<ErrorCode> for FlowyError {
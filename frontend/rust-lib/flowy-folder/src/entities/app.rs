@@ -8,7 +8,7 @@ use crate::{
   impl_def_and_def_mut,
 };
 use flowy_derive::ProtoBuf;
-use folder_model::AppRevision;
+use folder_model::{AppRevision, ColorStyleRevision};
 use std::convert::TryInto;
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
@@ -36,6 +36,9 @@ pub struct AppPB {
 
   #[pb(index = 8)]
   pub create_time: i64,
+
+  #[pb(index = 9)]
+  pub color_style: ColorStylePB,
 }
 
 impl std::convert::From<AppRevision> for AppPB {
@@ -49,6 +52,7 @@ impl std::convert::From<AppRevision> for AppPB {
       version: app_serde.version,
       modified_time: app_serde.modified_time,
       create_time: app_serde.create_time,
+      color_style: app_serde.color_style.into(),
     }
   }
 }
@@ -84,7 +88,7 @@ pub struct CreateAppPayloadPB {
   pub color_style: ColorStylePB,
 }
 
-#[derive(ProtoBuf, Default, Debug, Clone)]
+#[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
 pub struct ColorStylePB {
   #[pb(index = 1)]
   pub theme_color: String,
@@ -123,6 +127,22 @@ impl std::convert::From<AppColorStyle> for ColorStylePB {
   }
 }
 
+impl std::convert::From<ColorStyleRevision> for ColorStylePB {
+  fn from(rev: ColorStyleRevision) -> Self {
+    ColorStylePB {
+      theme_color: rev.theme_color,
+    }
+  }
+}
+
+impl std::convert::From<ColorStylePB> for ColorStyleRevision {
+  fn from(color_style: ColorStylePB) -> Self {
+    ColorStyleRevision {
+      theme_color: color_style.theme_color,
+    }
+  }
+}
+
 #[derive(ProtoBuf, Default, Clone, Debug)]
 pub struct AppIdPB {
   #[pb(index = 1)]
@@ -0,0 +1,61 @@
+use crate::{entities::parser::workspace::WorkspaceIdentify, errors::ErrorCode};
+use flowy_derive::ProtoBuf;
+use std::convert::TryInto;
+
+/// [ExportWorkspacePayloadPB] is used to write the workspace, including its apps, views and the
+/// data of the views, to a zip archive at the `path`. The apps and views in the trash are skipped.
+#[derive(Default, ProtoBuf)]
+pub struct ExportWorkspacePayloadPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub path: String,
+}
+
+#[derive(Debug)]
+pub struct ExportWorkspaceParams {
+  pub workspace_id: String,
+  pub path: String,
+}
+
+impl TryInto<ExportWorkspaceParams> for ExportWorkspacePayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ExportWorkspaceParams, Self::Error> {
+    let workspace_id = WorkspaceIdentify::parse(self.workspace_id)?;
+    if self.path.trim().is_empty() {
+      return Err(ErrorCode::UnexpectedEmptyString);
+    }
+
+    Ok(ExportWorkspaceParams {
+      workspace_id: workspace_id.0,
+      path: self.path,
+    })
+  }
+}
+
+/// [ImportWorkspacePayloadPB] is used to create a new workspace from the zip archive at the
+/// `path` that was written by exporting a workspace. The workspace, apps and views get new ids.
+#[derive(Default, ProtoBuf)]
+pub struct ImportWorkspacePayloadPB {
+  #[pb(index = 1)]
+  pub path: String,
+}
+
+#[derive(Debug)]
+pub struct ImportWorkspaceParams {
+  pub path: String,
+}
+
+impl TryInto<ImportWorkspaceParams> for ImportWorkspacePayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<ImportWorkspaceParams, Self::Error> {
+    if self.path.trim().is_empty() {
+      return Err(ErrorCode::UnexpectedEmptyString);
+    }
+
+    Ok(ImportWorkspaceParams { path: self.path })
+  }
+}
@@ -1,10 +1,16 @@
 pub mod app;
+pub mod backup;
 mod parser;
+pub mod search;
+pub mod snapshot;
 pub mod trash;
 pub mod view;
 pub mod workspace;
 
 pub use app::*;
+pub use backup::*;
+pub use search::*;
+pub use snapshot::*;
 pub use trash::*;
 pub use view::*;
 pub use workspace::*;
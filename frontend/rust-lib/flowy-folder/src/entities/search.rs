@@ -0,0 +1,122 @@
+use crate::{errors::ErrorCode, impl_def_and_def_mut};
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_sqlite::search::{SearchHitRecord, SearchObjectType};
+use std::convert::TryInto;
+
+const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+#[derive(Default, ProtoBuf)]
+pub struct SearchPayloadPB {
+  #[pb(index = 1)]
+  pub query: String,
+
+  /// The maximum number of the hits, 50 by default.
+  #[pb(index = 2, one_of)]
+  pub limit: Option<i32>,
+
+  /// The apps and views in the trash, and the content of them, are excluded unless it's true.
+  #[pb(index = 3)]
+  pub include_trash: bool,
+}
+
+#[derive(Debug)]
+pub struct SearchParams {
+  pub query: String,
+  pub limit: usize,
+  pub include_trash: bool,
+}
+
+impl TryInto<SearchParams> for SearchPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<SearchParams, Self::Error> {
+    let limit = match self.limit {
+      None => DEFAULT_SEARCH_LIMIT,
+      Some(limit) if limit > 0 => limit as usize,
+      Some(_) => return Err(ErrorCode::InvalidData),
+    };
+
+    Ok(SearchParams {
+      query: self.query,
+      limit,
+      include_trash: self.include_trash,
+    })
+  }
+}
+
+#[derive(Eq, PartialEq, Debug, ProtoBuf_Enum, Clone)]
+pub enum SearchObjectTypePB {
+  /// The name of the app
+  App = 0,
+  /// The name of the view
+  View = 1,
+  /// The text of a block in the document
+  DocumentBlock = 2,
+  /// The content of a cell in the database
+  DatabaseCell = 3,
+}
+
+impl std::default::Default for SearchObjectTypePB {
+  fn default() -> Self {
+    SearchObjectTypePB::View
+  }
+}
+
+impl std::convert::From<SearchObjectType> for SearchObjectTypePB {
+  fn from(object_type: SearchObjectType) -> Self {
+    match object_type {
+      SearchObjectType::App => SearchObjectTypePB::App,
+      SearchObjectType::View => SearchObjectTypePB::View,
+      SearchObjectType::DocumentBlock => SearchObjectTypePB::DocumentBlock,
+      SearchObjectType::DatabaseCell => SearchObjectTypePB::DatabaseCell,
+    }
+  }
+}
+
+#[derive(PartialEq, Debug, Default, ProtoBuf, Clone)]
+pub struct SearchHitPB {
+  /// The id of the view that contains the match, or the id of the app if the app's name matches.
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub object_type: SearchObjectTypePB,
+
+  /// The path of the block in the document, the indexes are joined with commas, for example,
+  /// `0,2,1`. It's the row id for the database cells, and the view id for the names.
+  #[pb(index = 3)]
+  pub object_id: String,
+
+  /// The field id of the database cell. It's empty for the other objects.
+  #[pb(index = 4)]
+  pub field_id: String,
+
+  /// The content around the matched terms, the terms are wrapped in `<b>` and `</b>`.
+  #[pb(index = 5)]
+  pub snippet: String,
+
+  /// The lower the rank, the better the match.
+  #[pb(index = 6)]
+  pub rank: f64,
+}
+
+impl std::convert::From<SearchHitRecord> for SearchHitPB {
+  fn from(record: SearchHitRecord) -> Self {
+    SearchHitPB {
+      view_id: record.view_id,
+      object_type: SearchObjectType::from(record.object_type).into(),
+      object_id: record.object_id,
+      field_id: record.field_id,
+      snippet: record.snippet,
+      rank: record.rank,
+    }
+  }
+}
+
+#[derive(PartialEq, Debug, Default, ProtoBuf, Clone)]
+pub struct RepeatedSearchHitPB {
+  #[pb(index = 1)]
+  pub items: Vec<SearchHitPB>,
+}
+
+impl_def_and_def_mut!(RepeatedSearchHitPB, SearchHitPB);
@@ -0,0 +1,54 @@
+use flowy_derive::ProtoBuf;
+use flowy_revision::RevisionSnapshotData;
+
+#[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
+pub struct FolderSnapshotPB {
+  #[pb(index = 1)]
+  pub rev_id: i64,
+
+  /// The time when the snapshot was taken, in seconds.
+  #[pb(index = 2)]
+  pub timestamp: i64,
+}
+
+impl std::convert::From<RevisionSnapshotData> for FolderSnapshotPB {
+  fn from(snapshot: RevisionSnapshotData) -> Self {
+    FolderSnapshotPB {
+      rev_id: snapshot.rev_id,
+      timestamp: snapshot.timestamp,
+    }
+  }
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
+pub struct RepeatedFolderSnapshotPB {
+  #[pb(index = 1)]
+  pub items: Vec<FolderSnapshotPB>,
+}
+
+impl std::convert::From<Vec<RevisionSnapshotData>> for RepeatedFolderSnapshotPB {
+  fn from(snapshots: Vec<RevisionSnapshotData>) -> Self {
+    RepeatedFolderSnapshotPB {
+      items: snapshots
+        .into_iter()
+        .map(|snapshot| snapshot.into())
+        .collect(),
+    }
+  }
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
+pub struct FolderSnapshotIdPB {
+  #[pb(index = 1)]
+  pub rev_id: i64,
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
+pub struct FolderSnapshotContentPB {
+  #[pb(index = 1)]
+  pub rev_id: i64,
+
+  /// The workspaces and trash of the folder at the snapshot, in json.
+  #[pb(index = 2)]
+  pub data: String,
+}
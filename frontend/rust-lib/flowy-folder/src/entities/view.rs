@@ -32,6 +32,10 @@ pub struct ViewPB {
 
   #[pb(index = 7)]
   pub layout: ViewLayoutTypePB,
+
+  /// The views that are nested in this view.
+  #[pb(index = 8)]
+  pub belongings: RepeatedViewPB,
 }
 
 impl std::convert::From<ViewRevision> for ViewPB {
@@ -44,6 +48,7 @@ impl std::convert::From<ViewRevision> for ViewPB {
       modified_time: rev.modified_time,
       create_time: rev.create_time,
       layout: rev.layout.into(),
+      belongings: rev.belongings.into(),
     }
   }
 }
@@ -337,6 +342,73 @@ impl TryInto<MoveFolderItemParams> for MoveFolderItemPayloadPB {
   }
 }
 
+#[derive(Default, ProtoBuf)]
+pub struct MoveViewPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// The id of the app or the view that the view will belong to.
+  #[pb(index = 2)]
+  pub to_belong_to_id: String,
+
+  /// The position in the new parent's views. The view is appended if it's out of bounds.
+  #[pb(index = 3)]
+  pub index: i32,
+}
+
+pub struct MoveViewParams {
+  pub view_id: String,
+  pub to_belong_to_id: String,
+  pub index: usize,
+}
+
+impl TryInto<MoveViewParams> for MoveViewPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<MoveViewParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    let to_belong_to_id = ViewIdentify::parse(self.to_belong_to_id)?.0;
+    Ok(MoveViewParams {
+      view_id,
+      to_belong_to_id,
+      index: self.index.max(0) as usize,
+    })
+  }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct DuplicateViewPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// The id of the app or the view that the copy will belong to. The copy is added next to the
+  /// view if it's None.
+  #[pb(index = 2, one_of)]
+  pub to_belong_to_id: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct DuplicateViewParams {
+  pub view_id: String,
+  pub to_belong_to_id: Option<String>,
+}
+
+impl TryInto<DuplicateViewParams> for DuplicateViewPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<DuplicateViewParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    let to_belong_to_id = match self.to_belong_to_id {
+      None => None,
+      Some(to_belong_to_id) => Some(ViewIdentify::parse(to_belong_to_id)?.0),
+    };
+    Ok(DuplicateViewParams {
+      view_id,
+      to_belong_to_id,
+    })
+  }
+}
+
 // impl<'de> Deserialize<'de> for ViewDataType {
 //     fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
 //     where
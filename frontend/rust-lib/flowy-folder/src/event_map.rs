@@ -8,8 +8,8 @@ use crate::{
   errors::FlowyError,
   manager::FolderManager,
   services::{
-    app::event_handler::*, trash::event_handler::*, view::event_handler::*,
-    workspace::event_handler::*,
+    app::event_handler::*, backup::event_handler::*, search::event_handler::*,
+    trash::event_handler::*, view::event_handler::*, workspace::event_handler::*,
   },
 };
 use flowy_derive::{Flowy_Event, ProtoBuf_Enum};
@@ -44,6 +44,8 @@ pub fn init(folder: Arc<FolderManager>) -> AFPlugin {
     .state(folder.app_controller.clone())
     .state(folder.view_controller.clone())
     .state(folder.trash_controller.clone())
+    .state(folder.search_controller.clone())
+    .state(folder.backup_controller.clone())
     .state(folder.clone());
 
   // Workspace
@@ -73,7 +75,9 @@ pub fn init(folder: Arc<FolderManager>) -> AFPlugin {
     .event(FolderEvent::DuplicateView, duplicate_view_handler)
     .event(FolderEvent::SetLatestView, set_latest_view_handler)
     .event(FolderEvent::CloseView, close_view_handler)
-    .event(FolderEvent::MoveItem, move_item_handler);
+    .event(FolderEvent::MoveItem, move_item_handler)
+    .event(FolderEvent::MoveView, move_view_handler)
+    .event(FolderEvent::DuplicateViewTo, duplicate_view_to_handler);
 
   // Trash
   plugin = plugin
@@ -83,6 +87,29 @@ pub fn init(folder: Arc<FolderManager>) -> AFPlugin {
     .event(FolderEvent::RestoreAllTrash, restore_all_trash_handler)
     .event(FolderEvent::DeleteAllTrash, delete_all_trash_handler);
 
+  // Snapshot
+  plugin = plugin
+    .event(
+      FolderEvent::ReadFolderSnapshots,
+      read_folder_snapshots_handler,
+    )
+    .event(
+      FolderEvent::PreviewFolderSnapshot,
+      preview_folder_snapshot_handler,
+    )
+    .event(
+      FolderEvent::RestoreFolderSnapshot,
+      restore_folder_snapshot_handler,
+    );
+
+  // Search
+  plugin = plugin.event(FolderEvent::Search, search_handler);
+
+  // Backup
+  plugin = plugin
+    .event(FolderEvent::ExportWorkspace, export_workspace_handler)
+    .event(FolderEvent::ImportWorkspace, import_workspace_handler);
+
   plugin
 }
 
@@ -145,7 +172,7 @@ pub enum FolderEvent {
   #[event(input = "RepeatedViewIdPB")]
   DeleteView = 204,
 
-  /// Duplicate the view
+  /// Duplicate the view and the views that belong to it
   #[event(input = "ViewPB")]
   DuplicateView = 205,
 
@@ -165,6 +192,15 @@ pub enum FolderEvent {
   #[event(input = "MoveFolderItemPayloadPB")]
   MoveItem = 230,
 
+  /// Move the view to another app or into another view
+  #[event(input = "MoveViewPayloadPB")]
+  MoveView = 231,
+
+  /// Duplicate the view and the views that belong to it into the app or the view. Returns the
+  /// copy of the view
+  #[event(input = "DuplicateViewPayloadPB", output = "ViewPB")]
+  DuplicateViewTo = 232,
+
   /// Read the trash that was deleted by the user
   #[event(output = "RepeatedTrashPB")]
   ReadTrash = 300,
@@ -184,6 +220,31 @@ pub enum FolderEvent {
   /// Delete all the trash from the disk
   #[event()]
   DeleteAllTrash = 304,
+
+  /// Read the snapshots of the folder, the latest one comes first
+  #[event(output = "RepeatedFolderSnapshotPB")]
+  ReadFolderSnapshots = 400,
+
+  /// Read the workspaces and trash at the snapshot without changing the folder
+  #[event(input = "FolderSnapshotIdPB", output = "FolderSnapshotContentPB")]
+  PreviewFolderSnapshot = 401,
+
+  /// Restore the workspaces and trash to the snapshot. It's saved as a new revision
+  #[event(input = "FolderSnapshotIdPB")]
+  RestoreFolderSnapshot = 402,
+
+  /// Search the names of the apps and views, the text of the documents and the cells of the
+  /// databases. The best match comes first
+  #[event(input = "SearchPayloadPB", output = "RepeatedSearchHitPB")]
+  Search = 500,
+
+  /// Write the workspace, its apps and views and the data of the views to a zip archive
+  #[event(input = "ExportWorkspacePayloadPB")]
+  ExportWorkspace = 600,
+
+  /// Create a new workspace from the zip archive that was written by exporting a workspace
+  #[event(input = "ImportWorkspacePayloadPB", output = "WorkspacePB")]
+  ImportWorkspace = 601,
 }
 
 pub trait FolderCouldServiceV1: Send + Sync {
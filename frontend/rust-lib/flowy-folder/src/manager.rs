@@ -1,23 +1,25 @@
 use crate::entities::view::ViewDataFormatPB;
-use crate::entities::{ViewLayoutTypePB, ViewPB};
+use crate::entities::{CreateViewParams, RepeatedAppPB, RepeatedTrashPB, ViewLayoutTypePB};
 use crate::services::folder_editor::FolderRevisionMergeable;
 use crate::{
   entities::workspace::RepeatedWorkspacePB,
   errors::FlowyResult,
   event_map::{FolderCouldServiceV1, WorkspaceDatabase, WorkspaceUser},
-  notification::{send_notification, FolderNotification},
+  notification::{send_anonymous_notification, send_notification, FolderNotification},
   services::{
-    folder_editor::FolderEditor, persistence::FolderPersistence, set_current_workspace,
-    AppController, TrashController, ViewController, WorkspaceController,
+    folder_editor::FolderEditor, get_current_workspace, persistence::FolderPersistence,
+    read_workspace_apps, set_current_workspace, AppController, BackupController, SearchController,
+    TrashController, ViewController, WorkspaceController,
   },
 };
 use bytes::Bytes;
 use flowy_document::editor::initial_read_me;
 use flowy_error::FlowyError;
 use flowy_revision::{
-  RevisionManager, RevisionPersistence, RevisionPersistenceConfiguration, RevisionWebSocket,
+  RevisionManager, RevisionPersistence, RevisionPersistenceConfiguration, RevisionSnapshotData,
+  RevisionWebSocket,
 };
-use folder_model::user_default;
+use folder_model::{user_default, ViewRevision};
 use lazy_static::lazy_static;
 use lib_infra::future::FutureResult;
 
@@ -69,6 +71,8 @@ pub struct FolderManager {
   pub(crate) app_controller: Arc<AppController>,
   pub(crate) view_controller: Arc<ViewController>,
   pub(crate) trash_controller: Arc<TrashController>,
+  pub(crate) search_controller: Arc<SearchController>,
+  pub(crate) backup_controller: Arc<BackupController>,
   web_socket: Arc<dyn RevisionWebSocket>,
   folder_editor: Arc<TokioRwLock<Option<Arc<FolderEditor>>>>,
 }
@@ -124,6 +128,21 @@ impl FolderManager {
       cloud_service.clone(),
     ));
 
+    let search_controller = Arc::new(SearchController::new(
+      user.clone(),
+      persistence.clone(),
+      trash_controller.clone(),
+    ));
+
+    let backup_controller = Arc::new(BackupController::new(
+      user.clone(),
+      persistence.clone(),
+      workspace_controller.clone(),
+      app_controller.clone(),
+      view_controller.clone(),
+      trash_controller.clone(),
+    ));
+
     Self {
       user,
       persistence,
@@ -131,6 +150,8 @@ impl FolderManager {
       app_controller,
       view_controller,
       trash_controller,
+      search_controller,
+      backup_controller,
       web_socket,
       folder_editor,
     }
@@ -204,6 +225,7 @@ impl FolderManager {
 
     self.app_controller.initialize()?;
     self.view_controller.initialize()?;
+    let _ = self.search_controller.initialize().await;
     write_guard.insert(user_id.to_owned(), true);
     Ok(())
   }
@@ -225,6 +247,63 @@ impl FolderManager {
     self.initialize(user_id, token).await
   }
 
+  /// Returns the snapshots of the folder, the latest one comes first.
+  pub async fn get_folder_snapshots(&self) -> FlowyResult<Vec<RevisionSnapshotData>> {
+    self.get_folder_editor().await?.get_snapshots().await
+  }
+
+  /// Returns the json of the folder at the snapshot without changing the current folder.
+  pub async fn preview_folder_snapshot(&self, rev_id: i64) -> FlowyResult<String> {
+    let snapshot_pad = self
+      .get_folder_editor()
+      .await?
+      .read_snapshot(rev_id)
+      .await?;
+    let json = snapshot_pad.to_json()?;
+    Ok(json)
+  }
+
+  /// Restores the workspaces and trash to the snapshot. The restore is saved as a new revision,
+  /// so it will be synced like the other changes.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn restore_folder_snapshot(&self, rev_id: i64) -> FlowyResult<()> {
+    self
+      .get_folder_editor()
+      .await?
+      .restore_snapshot(rev_id)
+      .await?;
+
+    let user_id = self.user.user_id()?;
+    let workspace_id = get_current_workspace(&user_id)?;
+    let trash_controller = self.trash_controller.clone();
+    let (app_revs, trash_revs) = self
+      .persistence
+      .begin_transaction(|transaction| {
+        let app_revs = read_workspace_apps(&workspace_id, trash_controller, &transaction)?;
+        let trash_revs = transaction.read_trash(None)?;
+        Ok((app_revs, trash_revs))
+      })
+      .await?;
+
+    let items = app_revs.into_iter().map(|app_rev| app_rev.into()).collect();
+    send_notification(&workspace_id, FolderNotification::DidUpdateWorkspaceApps)
+      .payload(RepeatedAppPB { items })
+      .send();
+    send_anonymous_notification(FolderNotification::DidUpdateTrash)
+      .payload(RepeatedTrashPB::from(trash_revs))
+      .send();
+    Ok(())
+  }
+
+  async fn get_folder_editor(&self) -> FlowyResult<Arc<FolderEditor>> {
+    match self.folder_editor.read().await.clone() {
+      None => Err(
+        FlowyError::internal().context("FolderEditor should be initialized after user login in."),
+      ),
+      Some(editor) => Ok(editor),
+    }
+  }
+
   /// Called when the current user logout
   ///
   pub async fn clear(&self, user_id: &str) {
@@ -232,6 +311,15 @@ impl FolderManager {
     clear_current_workspace(user_id);
     *self.folder_editor.write().await = None;
   }
+
+  /// Creates the view with the [CreateViewParams], it's used by other modules that build the
+  /// view's data themselves, for example, importing a grid from csv.
+  pub async fn create_view_with_params(
+    &self,
+    params: CreateViewParams,
+  ) -> FlowyResult<ViewRevision> {
+    self.view_controller.create_view_from_params(params).await
+  }
 }
 
 struct DefaultFolderBuilder();
@@ -293,7 +381,24 @@ pub trait ViewDataProcessor {
 
   fn close_view(&self, view_id: &str) -> FutureResult<(), FlowyError>;
 
-  fn get_view_data(&self, view: &ViewPB) -> FutureResult<Bytes, FlowyError>;
+  /// Returns the data for the copy of the view. The `id_map` maps the ids of the objects that are
+  /// duplicated together to the ids of their copies, the references to them in the data are
+  /// replaced. The ids that are generated while duplicating are inserted into the returned map.
+  fn duplicate_view_data(
+    &self,
+    view_id: &str,
+    id_map: HashMap<String, String>,
+  ) -> FutureResult<(Bytes, HashMap<String, String>), FlowyError>;
+
+  /// Returns the data for creating the view from the data that was returned by
+  /// `duplicate_view_data`, for example, the data in a workspace backup. The objects in the data
+  /// get new ids, so the same data can be imported more than once. The references to the objects
+  /// in the `id_map` are replaced, and the generated ids are inserted into the returned map.
+  fn import_view_data(
+    &self,
+    data: Bytes,
+    id_map: HashMap<String, String>,
+  ) -> FutureResult<(Bytes, HashMap<String, String>), FlowyError>;
 
   fn create_default_view(
     &self,
@@ -27,6 +27,8 @@ pub(crate) enum FolderNotification {
   DidMoveViewToTrash = 33,
   /// Trigger when the number of trash is changed
   DidUpdateTrash = 34,
+  /// Trigger when the views that are nested in the view are changed
+  DidUpdateChildViews = 35,
 }
 
 impl std::default::Default for FolderNotification {
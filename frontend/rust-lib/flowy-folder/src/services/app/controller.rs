@@ -12,6 +12,7 @@ use crate::{
   },
 };
 
+use flowy_sqlite::search::SearchIndexRecord;
 use folder_model::AppRevision;
 use futures::{FutureExt, StreamExt};
 use std::{collections::HashSet, sync::Arc};
@@ -65,6 +66,11 @@ impl AppController {
         Ok(())
       })
       .await?;
+
+    let record = SearchIndexRecord::app(&app.id, &app.name);
+    if let Err(e) = self.persistence.index_names(vec![record]) {
+      tracing::error!("Index the name of the app failed: {}", e);
+    }
     Ok(app.into())
   }
 
@@ -87,15 +93,21 @@ impl AppController {
     let changeset = AppChangeset::new(params.clone());
     let app_id = changeset.id.clone();
 
-    let app: AppPB = self
+    let app = self
       .persistence
       .begin_transaction(|transaction| {
         transaction.update_app(changeset)?;
         let app = transaction.read_app(&app_id)?;
         Ok(app)
       })
-      .await?
-      .into();
+      .await?;
+
+    let record = SearchIndexRecord::app(&app.id, &app.name);
+    if let Err(e) = self.persistence.index_names(vec![record]) {
+      tracing::error!("Index the name of the app failed: {}", e);
+    }
+
+    let app: AppPB = app.into();
     send_notification(&app_id, FolderNotification::DidUpdateApp)
       .payload(app)
       .send();
@@ -205,18 +217,26 @@ async fn handle_trash_event(
       let result = persistence
         .begin_transaction(|transaction| {
           let mut notify_ids = HashSet::new();
+          let mut deleted_ids = vec![];
           for identifier in identifiers.items {
             let app = transaction.read_app(&identifier.id)?;
             let _ = transaction.delete_app(&identifier.id)?;
+            deleted_ids.push(app.id);
+            let mut views = app.belongings;
+            while let Some(view) = views.pop() {
+              deleted_ids.push(view.id);
+              views.extend(view.belongings);
+            }
             notify_ids.insert(app.workspace_id);
           }
 
           for notify_id in notify_ids {
             notify_apps_changed(&notify_id, trash_controller.clone(), &transaction)?;
           }
-          Ok(())
+          Ok(deleted_ids)
         })
-        .await;
+        .await
+        .and_then(|deleted_ids| persistence.delete_from_search_index(deleted_ids));
       let _ = ret.send(result).await;
     },
   }
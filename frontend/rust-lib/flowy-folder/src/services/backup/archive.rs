@@ -0,0 +1,181 @@
+use crate::errors::{FlowyError, FlowyResult};
+use folder_model::WorkspaceRevision;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// The version of the archive layout. It's increased when the layout is changed in a way that the
+/// older versions can't read, so they refuse to import the archive instead of importing it wrong.
+pub(crate) const BACKUP_VERSION: u32 = 1;
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+const VIEW_DATA_DIR: &str = "views";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BackupManifest {
+  pub version: u32,
+
+  /// The time when the backup was made, in seconds.
+  pub created_at: i64,
+
+  /// The workspace with its apps and views. The data of each view is stored in a separate file
+  /// in the archive, the file is named by the view id.
+  pub workspace: WorkspaceRevision,
+}
+
+/// The content of the zip archive of a workspace backup.
+pub(crate) struct WorkspaceBackup {
+  pub manifest: BackupManifest,
+
+  /// The data of the views, keyed by the view id.
+  pub view_data: HashMap<String, Vec<u8>>,
+}
+
+impl WorkspaceBackup {
+  pub(crate) fn write_to(&self, path: &str) -> FlowyResult<()> {
+    let manifest =
+      serde_json::to_vec_pretty(&self.manifest).map_err(|e| FlowyError::internal().context(e))?;
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut writer = ZipWriter::new(File::create(path)?);
+    writer
+      .start_file(MANIFEST_FILE_NAME, options)
+      .map_err(|e| FlowyError::internal().context(e))?;
+    writer.write_all(&manifest)?;
+
+    for (view_id, data) in self.view_data.iter() {
+      writer
+        .start_file(view_data_file_name(view_id), options)
+        .map_err(|e| FlowyError::internal().context(e))?;
+      writer.write_all(data)?;
+    }
+    writer
+      .finish()
+      .map_err(|e| FlowyError::internal().context(e))?;
+    Ok(())
+  }
+
+  pub(crate) fn read_from(path: &str) -> FlowyResult<Self> {
+    let mut archive =
+      ZipArchive::new(File::open(path)?).map_err(|e| FlowyError::invalid_backup().context(e))?;
+    let manifest: BackupManifest = {
+      let file = archive
+        .by_name(MANIFEST_FILE_NAME)
+        .map_err(|e| FlowyError::invalid_backup().context(e))?;
+      serde_json::from_reader(file).map_err(|e| FlowyError::invalid_backup().context(e))?
+    };
+    if manifest.version > BACKUP_VERSION {
+      return Err(FlowyError::invalid_backup().context(format!(
+        "The backup version {} is not supported",
+        manifest.version
+      )));
+    }
+
+    let mut view_data = HashMap::new();
+    let mut view_ids = vec![];
+    collect_view_ids(&manifest.workspace, &mut view_ids);
+    for view_id in view_ids {
+      let mut file = archive
+        .by_name(&view_data_file_name(&view_id))
+        .map_err(|e| FlowyError::invalid_backup().context(e))?;
+      let mut data = vec![];
+      file.read_to_end(&mut data)?;
+      view_data.insert(view_id, data);
+    }
+
+    Ok(Self {
+      manifest,
+      view_data,
+    })
+  }
+}
+
+fn view_data_file_name(view_id: &str) -> String {
+  format!("{}/{}", VIEW_DATA_DIR, view_id)
+}
+
+fn collect_view_ids(workspace: &WorkspaceRevision, view_ids: &mut Vec<String>) {
+  for app in workspace.apps.iter() {
+    let mut views = app.belongings.iter().collect::<Vec<_>>();
+    while let Some(view) = views.pop() {
+      view_ids.push(view.id.clone());
+      views.extend(view.belongings.iter());
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{BackupManifest, WorkspaceBackup, BACKUP_VERSION};
+  use folder_model::{gen_view_id, AppRevision, ViewRevision, WorkspaceRevision};
+  use std::collections::HashMap;
+
+  fn make_backup(version: u32) -> WorkspaceBackup {
+    let child_view = ViewRevision {
+      id: "child".to_owned(),
+      name: "Child".to_owned(),
+      ..Default::default()
+    };
+    let view = ViewRevision {
+      id: "view".to_owned(),
+      name: "View".to_owned(),
+      belongings: vec![child_view],
+      ..Default::default()
+    };
+    let app = AppRevision {
+      id: "app".to_owned(),
+      name: "App".to_owned(),
+      belongings: vec![view],
+      ..Default::default()
+    };
+    let workspace = WorkspaceRevision {
+      id: "workspace".to_owned(),
+      name: "Workspace".to_owned(),
+      apps: vec![app],
+      ..Default::default()
+    };
+    WorkspaceBackup {
+      manifest: BackupManifest {
+        version,
+        created_at: 0,
+        workspace,
+      },
+      view_data: HashMap::from([
+        ("view".to_owned(), b"view data".to_vec()),
+        ("child".to_owned(), b"child data".to_vec()),
+      ]),
+    }
+  }
+
+  fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{}-{}.zip", name, gen_view_id()));
+    path.to_str().unwrap().to_owned()
+  }
+
+  #[test]
+  fn backup_write_then_read_test() {
+    let path = temp_path("backup");
+    make_backup(BACKUP_VERSION).write_to(&path).unwrap();
+
+    let backup = WorkspaceBackup::read_from(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(backup.manifest.version, BACKUP_VERSION);
+    assert_eq!(backup.manifest.workspace.name, "Workspace");
+    let view = &backup.manifest.workspace.apps[0].belongings[0];
+    assert_eq!(view.belongings[0].id, "child");
+    assert_eq!(backup.view_data.get("view").unwrap(), b"view data");
+    assert_eq!(backup.view_data.get("child").unwrap(), b"child data");
+  }
+
+  #[test]
+  fn backup_newer_version_test() {
+    let path = temp_path("backup");
+    make_backup(BACKUP_VERSION + 1).write_to(&path).unwrap();
+
+    let result = WorkspaceBackup::read_from(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(result.is_err());
+  }
+}
@@ -0,0 +1,254 @@
+use crate::{
+  entities::{
+    app::CreateAppParams,
+    backup::{ExportWorkspaceParams, ImportWorkspaceParams},
+    view::CreateViewParams,
+    workspace::{CreateWorkspaceParams, WorkspacePB},
+  },
+  errors::{FlowyError, FlowyResult},
+  event_map::WorkspaceUser,
+  services::{
+    backup::archive::{BackupManifest, WorkspaceBackup, BACKUP_VERSION},
+    clear_current_workspace, get_current_workspace,
+    persistence::FolderPersistence,
+    set_current_workspace, AppController, TrashController, ViewController, WorkspaceController,
+  },
+};
+use bytes::Bytes;
+use folder_model::{gen_view_id, AppRevision, ViewRevision};
+use lib_infra::util::timestamp;
+use std::{collections::HashMap, sync::Arc};
+
+pub(crate) struct BackupController {
+  user: Arc<dyn WorkspaceUser>,
+  persistence: Arc<FolderPersistence>,
+  workspace_controller: Arc<WorkspaceController>,
+  app_controller: Arc<AppController>,
+  view_controller: Arc<ViewController>,
+  trash_controller: Arc<TrashController>,
+}
+
+impl BackupController {
+  pub(crate) fn new(
+    user: Arc<dyn WorkspaceUser>,
+    persistence: Arc<FolderPersistence>,
+    workspace_controller: Arc<WorkspaceController>,
+    app_controller: Arc<AppController>,
+    view_controller: Arc<ViewController>,
+    trash_controller: Arc<TrashController>,
+  ) -> Self {
+    Self {
+      user,
+      persistence,
+      workspace_controller,
+      app_controller,
+      view_controller,
+      trash_controller,
+    }
+  }
+
+  /// Writes the workspace and the data of its views to the zip archive. The apps and views in the
+  /// trash are skipped.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn export_workspace(&self, params: ExportWorkspaceParams) -> FlowyResult<()> {
+    let user_id = self.user.user_id()?;
+    let (workspace, trash_ids) = self
+      .persistence
+      .begin_transaction(|transaction| {
+        let workspace = transaction
+          .read_workspaces(&user_id, Some(params.workspace_id.clone()))?
+          .pop();
+        let trash_ids = self.trash_controller.read_trash_ids(&transaction)?;
+        Ok((workspace, trash_ids))
+      })
+      .await?;
+    let mut workspace = workspace.ok_or_else(|| {
+      FlowyError::record_not_found().context(format!("{} workspace not found", params.workspace_id))
+    })?;
+
+    workspace.apps.retain(|app| !trash_ids.contains(&app.id));
+    for app in workspace.apps.iter_mut() {
+      retain_views(&mut app.belongings, &trash_ids);
+    }
+
+    // The views keep their ids in the archive, so the references between them are kept as they
+    // are. They get new ids when they are imported.
+    let mut view_revs = workspace
+      .apps
+      .iter()
+      .flat_map(|app| app.belongings.iter())
+      .collect::<Vec<&ViewRevision>>();
+    let mut id_map = HashMap::new();
+    let mut views = view_revs.clone();
+    while let Some(view_rev) = views.pop() {
+      id_map.insert(view_rev.id.clone(), view_rev.id.clone());
+      views.extend(view_rev.belongings.iter());
+    }
+
+    let mut view_data = HashMap::new();
+    while let Some(view_rev) = view_revs.pop() {
+      let (data, exported_ids) = self
+        .view_controller
+        .export_view_data(view_rev, id_map)
+        .await?;
+      id_map = exported_ids;
+      view_data.insert(view_rev.id.clone(), data.to_vec());
+      view_revs.extend(view_rev.belongings.iter());
+    }
+
+    let backup = WorkspaceBackup {
+      manifest: BackupManifest {
+        version: BACKUP_VERSION,
+        created_at: timestamp(),
+        workspace,
+      },
+      view_data,
+    };
+    backup.write_to(&params.path)
+  }
+
+  /// Creates a new workspace from the zip archive. The workspace, apps and views get new ids, and
+  /// the views are created in the same hierarchy as they were exported. The created workspace is
+  /// removed if any of its apps or views fails to import.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn import_workspace(
+    &self,
+    params: ImportWorkspaceParams,
+  ) -> FlowyResult<WorkspacePB> {
+    let WorkspaceBackup {
+      manifest,
+      view_data,
+    } = WorkspaceBackup::read_from(&params.path)?;
+    let user_id = self.user.user_id()?;
+    let current_workspace_id = get_current_workspace(&user_id).ok();
+    let workspace_rev = manifest.workspace;
+    let workspace = self
+      .workspace_controller
+      .create_workspace_from_params(CreateWorkspaceParams {
+        name: workspace_rev.name.clone(),
+        desc: workspace_rev.desc.clone(),
+      })
+      .await?;
+
+    let mut imported_ids = vec![];
+    let result = self
+      .import_apps(
+        &workspace.id,
+        workspace_rev.apps,
+        view_data,
+        &mut imported_ids,
+      )
+      .await;
+    if let Err(err) = result {
+      if let Err(e) = self
+        .workspace_controller
+        .delete_workspace(&workspace.id)
+        .await
+      {
+        tracing::error!("Delete the imported workspace failed: {:?}", e);
+      }
+      if let Err(e) = self.persistence.delete_from_search_index(imported_ids) {
+        tracing::error!(
+          "Delete the imported views from the search index failed: {:?}",
+          e
+        );
+      }
+      match current_workspace_id {
+        None => clear_current_workspace(&user_id),
+        Some(workspace_id) => set_current_workspace(&user_id, &workspace_id),
+      }
+      return Err(err);
+    }
+
+    self
+      .persistence
+      .begin_transaction(|transaction| {
+        self
+          .workspace_controller
+          .read_workspace(workspace.id.clone(), &user_id, &transaction)
+      })
+      .await
+  }
+
+  /// Creates the apps and their views in the workspace. The ids of the created apps and views are
+  /// pushed to `imported_ids`, so they can be removed if the import fails.
+  async fn import_apps(
+    &self,
+    workspace_id: &str,
+    app_revs: Vec<AppRevision>,
+    mut view_data: HashMap<String, Vec<u8>>,
+    imported_ids: &mut Vec<String>,
+  ) -> FlowyResult<()> {
+    // The ids of the views are generated before importing the data, so the references between
+    // the imported views can be replaced with the new ids.
+    let mut id_map = HashMap::new();
+    for app_rev in app_revs.iter() {
+      let mut views = app_rev.belongings.iter().collect::<Vec<_>>();
+      while let Some(view_rev) = views.pop() {
+        id_map.insert(view_rev.id.clone(), gen_view_id());
+        views.extend(view_rev.belongings.iter());
+      }
+    }
+
+    for app_rev in app_revs {
+      let app = self
+        .app_controller
+        .create_app_from_params(CreateAppParams {
+          workspace_id: workspace_id.to_owned(),
+          name: app_rev.name,
+          desc: app_rev.desc,
+          color_style: app_rev.color_style.into(),
+        })
+        .await?;
+      imported_ids.push(app.id.clone());
+
+      // Push the views in reverse order, so they are created in their original order.
+      let mut views = app_rev
+        .belongings
+        .into_iter()
+        .rev()
+        .map(|view_rev| (view_rev, app.id.clone()))
+        .collect::<Vec<_>>();
+      while let Some((view_rev, belong_to_id)) = views.pop() {
+        let data = view_data.remove(&view_rev.id).ok_or_else(|| {
+          FlowyError::invalid_backup().context(format!("The data of {} is missing", view_rev.id))
+        })?;
+        let (data, new_id_map) = self
+          .view_controller
+          .import_view_data(&view_rev, Bytes::from(data), id_map)
+          .await?;
+        id_map = new_id_map;
+
+        let view_id = id_map
+          .get(&view_rev.id)
+          .cloned()
+          .unwrap_or_else(gen_view_id);
+        let params = CreateViewParams {
+          belong_to_id,
+          name: view_rev.name,
+          desc: view_rev.desc,
+          thumbnail: view_rev.thumbnail,
+          data_format: view_rev.data_format.into(),
+          layout: view_rev.layout.into(),
+          initial_data: data.to_vec(),
+          view_id: view_id.clone(),
+        };
+        self.view_controller.create_view_from_params(params).await?;
+        imported_ids.push(view_id.clone());
+
+        for child_view_rev in view_rev.belongings.into_iter().rev() {
+          views.push((child_view_rev, view_id.clone()));
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Removes the views in the trash, and the views that belong to them.
+fn retain_views(view_revs: &mut Vec<ViewRevision>, trash_ids: &[String]) {
+  view_revs.retain(|view_rev| !trash_ids.contains(&view_rev.id));
+  for view_rev in view_revs.iter_mut() {
+    retain_views(&mut view_rev.belongings, trash_ids);
+  }
+}
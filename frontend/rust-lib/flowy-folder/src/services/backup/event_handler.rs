@@ -0,0 +1,33 @@
+use crate::{
+  entities::{
+    backup::{
+      ExportWorkspaceParams, ExportWorkspacePayloadPB, ImportWorkspaceParams,
+      ImportWorkspacePayloadPB,
+    },
+    workspace::WorkspacePB,
+  },
+  errors::FlowyError,
+  services::BackupController,
+};
+use lib_dispatch::prelude::{data_result, AFPluginData, AFPluginState, DataResult};
+use std::{convert::TryInto, sync::Arc};
+
+#[tracing::instrument(level = "debug", skip(data, controller), err)]
+pub(crate) async fn export_workspace_handler(
+  data: AFPluginData<ExportWorkspacePayloadPB>,
+  controller: AFPluginState<Arc<BackupController>>,
+) -> Result<(), FlowyError> {
+  let params: ExportWorkspaceParams = data.into_inner().try_into()?;
+  controller.export_workspace(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, controller), err)]
+pub(crate) async fn import_workspace_handler(
+  data: AFPluginData<ImportWorkspacePayloadPB>,
+  controller: AFPluginState<Arc<BackupController>>,
+) -> DataResult<WorkspacePB, FlowyError> {
+  let params: ImportWorkspaceParams = data.into_inner().try_into()?;
+  let workspace = controller.import_workspace(params).await?;
+  data_result(workspace)
+}
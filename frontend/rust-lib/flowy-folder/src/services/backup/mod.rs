@@ -0,0 +1,3 @@
+mod archive;
+pub mod controller;
+pub mod event_handler;
@@ -6,7 +6,7 @@ use flowy_client_sync::util::recover_operation_from_revisions;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_revision::{
   RevisionCloudService, RevisionManager, RevisionMergeable, RevisionObjectDeserializer,
-  RevisionObjectSerializer, RevisionWebSocket,
+  RevisionObjectSerializer, RevisionSnapshotData, RevisionWebSocket,
 };
 use flowy_sqlite::ConnectionPool;
 use lib_infra::future::FutureResult;
@@ -101,6 +101,30 @@ impl FolderEditor {
     Ok(())
   }
 
+  /// Returns the snapshots of the folder, the latest one comes first.
+  pub async fn get_snapshots(&self) -> FlowyResult<Vec<RevisionSnapshotData>> {
+    self.rev_manager.read_snapshots().await
+  }
+
+  pub async fn read_snapshot(&self, rev_id: i64) -> FlowyResult<FolderPad> {
+    self
+      .rev_manager
+      .read_snapshot_object::<FolderRevisionSerde>(rev_id)
+      .await
+  }
+
+  /// Restores the folder to the snapshot. The difference between the current folder and the
+  /// snapshot is saved as a new revision instead of rewinding the revisions.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn restore_snapshot(&self, rev_id: i64) -> FlowyResult<()> {
+    let snapshot_pad = self.read_snapshot(rev_id).await?;
+    let changeset = self.folder.write().reset_folder_rev(&snapshot_pad)?;
+    if let Some(changeset) = changeset {
+      self.apply_change(changeset)?;
+    }
+    Ok(())
+  }
+
   #[allow(dead_code)]
   pub fn folder_json(&self) -> FlowyResult<String> {
     let json = self.folder.read().to_json()?;
@@ -12,6 +12,7 @@ use crate::{
 use flowy_client_sync::client_folder::{FolderOperationsBuilder, FolderPad};
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_revision_persistence::{RevisionDiskCache, RevisionState, SyncRecord};
+use flowy_sqlite::search::{SearchHitRecord, SearchIndexRecord, SearchIndexSql};
 use flowy_sqlite::ConnectionPool;
 use folder_model::{AppRevision, TrashRevision, ViewRevision, WorkspaceRevision};
 use revision_model::Revision;
@@ -44,6 +45,7 @@ pub trait FolderPersistenceTransaction {
   fn update_view(&self, changeset: ViewChangeset) -> FlowyResult<()>;
   fn delete_view(&self, view_id: &str) -> FlowyResult<ViewRevision>;
   fn move_view(&self, view_id: &str, from: usize, to: usize) -> FlowyResult<()>;
+  fn move_view_to(&self, view_id: &str, to_belong_to_id: &str, index: usize) -> FlowyResult<()>;
 
   fn create_trash(&self, trashes: Vec<TrashRevision>) -> FlowyResult<()>;
   fn read_trash(&self, trash_id: Option<String>) -> FlowyResult<Vec<TrashRevision>>;
@@ -106,6 +108,34 @@ impl FolderPersistence {
     self.database.db_pool()
   }
 
+  /// Indexes the names of the apps and views, so they can be found by searching.
+  pub fn index_names(&self, records: Vec<SearchIndexRecord>) -> FlowyResult<()> {
+    let conn = self.database.db_connection()?;
+    conn.immediate_transaction::<_, FlowyError, _>(|| {
+      for record in records {
+        SearchIndexSql::upsert(record, &conn)?;
+      }
+      Ok(())
+    })
+  }
+
+  /// Removes the names and the content of the apps and views from the search index.
+  pub fn delete_from_search_index(&self, ids: Vec<String>) -> FlowyResult<()> {
+    let conn = self.database.db_connection()?;
+    conn.immediate_transaction::<_, FlowyError, _>(|| {
+      for id in ids {
+        SearchIndexSql::delete_view(&id, &conn)?;
+      }
+      Ok(())
+    })
+  }
+
+  pub fn search(&self, query: &str) -> FlowyResult<Vec<SearchHitRecord>> {
+    let conn = self.database.db_connection()?;
+    let hits = SearchIndexSql::search(query, &conn)?;
+    Ok(hits)
+  }
+
   pub async fn initialize(&self, user_id: &str, folder_id: &FolderId) -> FlowyResult<()> {
     let migrations = FolderMigration::new(user_id, self.database.clone());
     if let Some(migrated_folder) = migrations.run_v1_migration()? {
@@ -164,6 +164,7 @@ impl std::convert::From<AppTable> for AppRevision {
       version: table.version,
       modified_time: table.modified_time,
       create_time: table.create_time,
+      color_style: Default::default(),
     }
   }
 }
@@ -107,6 +107,10 @@ impl<'a> FolderPersistenceTransaction for V1Transaction<'a> {
     Ok(())
   }
 
+  fn move_view_to(&self, _view_id: &str, _to_belong_to_id: &str, _index: usize) -> FlowyResult<()> {
+    Ok(())
+  }
+
   fn create_trash(&self, trashes: Vec<TrashRevision>) -> FlowyResult<()> {
     TrashTableSql::create_trash(trashes, self.0)?;
     Ok(())
@@ -208,6 +212,10 @@ where
     Ok(())
   }
 
+  fn move_view_to(&self, _view_id: &str, _to_belong_to_id: &str, _index: usize) -> FlowyResult<()> {
+    Ok(())
+  }
+
   fn create_trash(&self, trashes: Vec<TrashRevision>) -> FlowyResult<()> {
     (**self).create_trash(trashes)
   }
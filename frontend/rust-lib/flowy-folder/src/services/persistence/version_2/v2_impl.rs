@@ -139,6 +139,17 @@ impl FolderPersistenceTransaction for FolderEditor {
     Ok(())
   }
 
+  fn move_view_to(&self, view_id: &str, to_belong_to_id: &str, index: usize) -> FlowyResult<()> {
+    if let Some(change) = self
+      .folder
+      .write()
+      .move_view_to(view_id, to_belong_to_id, index)?
+    {
+      self.apply_change(change)?;
+    }
+    Ok(())
+  }
+
   fn create_trash(&self, trashes: Vec<TrashRevision>) -> FlowyResult<()> {
     if let Some(change) = self.folder.write().create_trash(trashes)? {
       self.apply_change(change)?;
@@ -231,6 +242,10 @@ where
     (**self).move_view(view_id, from, to)
   }
 
+  fn move_view_to(&self, view_id: &str, to_belong_to_id: &str, index: usize) -> FlowyResult<()> {
+    (**self).move_view_to(view_id, to_belong_to_id, index)
+  }
+
   fn create_trash(&self, trashes: Vec<TrashRevision>) -> FlowyResult<()> {
     (**self).create_trash(trashes)
   }
@@ -0,0 +1,123 @@
+use crate::{
+  entities::search::{SearchHitPB, SearchParams},
+  errors::FlowyResult,
+  event_map::WorkspaceUser,
+  services::{persistence::FolderPersistence, TrashController},
+};
+use flowy_sqlite::search::SearchIndexRecord;
+use folder_model::{ViewRevision, WorkspaceRevision};
+use std::{collections::HashSet, sync::Arc};
+
+pub(crate) struct SearchController {
+  user: Arc<dyn WorkspaceUser>,
+  persistence: Arc<FolderPersistence>,
+  trash_controller: Arc<TrashController>,
+}
+
+impl SearchController {
+  pub(crate) fn new(
+    user: Arc<dyn WorkspaceUser>,
+    persistence: Arc<FolderPersistence>,
+    trash_controller: Arc<TrashController>,
+  ) -> Self {
+    Self {
+      user,
+      persistence,
+      trash_controller,
+    }
+  }
+
+  /// Indexes the names of all the apps and views. The names are indexed when they are created or
+  /// updated, it makes sure the ones that were created before the search index was added can be
+  /// found too.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub(crate) async fn initialize(&self) -> FlowyResult<()> {
+    let workspaces = self.read_workspaces().await?;
+    let mut records = vec![];
+    for workspace in workspaces {
+      for app in workspace.apps {
+        records.push(SearchIndexRecord::app(&app.id, &app.name));
+        let mut views = app.belongings;
+        while let Some(view) = views.pop() {
+          records.push(SearchIndexRecord::view(&view.id, &view.name));
+          views.extend(view.belongings);
+        }
+      }
+    }
+    self.persistence.index_names(records)
+  }
+
+  /// Returns the best matches of the query. The hits of the apps and views that don't exist
+  /// anymore are skipped, and so are the ones in the trash unless `include_trash` is true.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn search(&self, params: SearchParams) -> FlowyResult<Vec<SearchHitPB>> {
+    let workspaces = self.read_workspaces().await?;
+    let trash_ids = self
+      .persistence
+      .begin_transaction(|transaction| self.trash_controller.read_trash_ids(&transaction))
+      .await?;
+
+    let mut existing_ids = HashSet::new();
+    let mut trashed_ids = HashSet::new();
+    for workspace in workspaces.iter() {
+      for app in workspace.apps.iter() {
+        let is_trashed = trash_ids.contains(&app.id);
+        existing_ids.insert(app.id.clone());
+        if is_trashed {
+          trashed_ids.insert(app.id.clone());
+        }
+        collect_view_ids(
+          &app.belongings,
+          is_trashed,
+          &trash_ids,
+          &mut existing_ids,
+          &mut trashed_ids,
+        );
+      }
+    }
+
+    let hits = self
+      .persistence
+      .search(&params.query)?
+      .into_iter()
+      .filter(|hit| existing_ids.contains(&hit.view_id))
+      .filter(|hit| params.include_trash || !trashed_ids.contains(&hit.view_id))
+      .take(params.limit)
+      .map(SearchHitPB::from)
+      .collect::<Vec<SearchHitPB>>();
+    Ok(hits)
+  }
+
+  async fn read_workspaces(&self) -> FlowyResult<Vec<WorkspaceRevision>> {
+    let user_id = self.user.user_id()?;
+    self
+      .persistence
+      .begin_transaction(|transaction| transaction.read_workspaces(&user_id, None))
+      .await
+  }
+}
+
+/// Collects the ids of the views and the views that belong to them. A view is in the trash if it
+/// or one of its ancestors is.
+fn collect_view_ids(
+  views: &[ViewRevision],
+  is_parent_trashed: bool,
+  trash_ids: &[String],
+  existing_ids: &mut HashSet<String>,
+  trashed_ids: &mut HashSet<String>,
+) {
+  for view in views {
+    let is_trashed = is_parent_trashed || trash_ids.contains(&view.id);
+    existing_ids.insert(view.id.clone());
+    if is_trashed {
+      trashed_ids.insert(view.id.clone());
+    }
+    collect_view_ids(
+      &view.belongings,
+      is_trashed,
+      trash_ids,
+      existing_ids,
+      trashed_ids,
+    );
+  }
+}
@@ -0,0 +1,17 @@
+use crate::{
+  entities::search::{RepeatedSearchHitPB, SearchParams, SearchPayloadPB},
+  errors::FlowyError,
+  services::SearchController,
+};
+use lib_dispatch::prelude::{data_result, AFPluginData, AFPluginState, DataResult};
+use std::{convert::TryInto, sync::Arc};
+
+#[tracing::instrument(level = "debug", skip(data, controller), err)]
+pub(crate) async fn search_handler(
+  data: AFPluginData<SearchPayloadPB>,
+  controller: AFPluginState<Arc<SearchController>>,
+) -> DataResult<RepeatedSearchHitPB, FlowyError> {
+  let params: SearchParams = data.into_inner().try_into()?;
+  let items = controller.search(params).await?;
+  data_result(RepeatedSearchHitPB { items })
+}
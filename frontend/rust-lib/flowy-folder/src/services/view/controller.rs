@@ -4,7 +4,7 @@ use crate::manager::{ViewDataProcessor, ViewDataProcessorMap};
 use crate::{
   entities::{
     trash::{RepeatedTrashIdPB, TrashType},
-    view::{CreateViewParams, UpdateViewParams, ViewPB},
+    view::{CreateViewParams, DuplicateViewParams, RepeatedViewPB, UpdateViewParams, ViewPB},
   },
   errors::{FlowyError, FlowyResult},
   event_map::{FolderCouldServiceV1, WorkspaceUser},
@@ -16,9 +16,13 @@ use crate::{
 };
 use bytes::Bytes;
 use flowy_sqlite::kv::KV;
+use flowy_sqlite::search::SearchIndexRecord;
 use folder_model::{gen_view_id, ViewRevision};
 use futures::{FutureExt, StreamExt};
-use std::{collections::HashSet, sync::Arc};
+use std::{
+  collections::{HashMap, HashSet},
+  sync::Arc,
+};
 
 const LATEST_VIEW_ID: &str = "latest_view_id";
 
@@ -119,6 +123,7 @@ impl ViewController {
     view_rev: ViewRevision,
   ) -> Result<(), FlowyError> {
     let trash_controller = self.trash_controller.clone();
+    let record = SearchIndexRecord::view(&view_rev.id, &view_rev.name);
     self
       .persistence
       .begin_transaction(|transaction| {
@@ -127,7 +132,12 @@ impl ViewController {
         notify_views_changed(&belong_to_id, trash_controller, &transaction)?;
         Ok(())
       })
-      .await
+      .await?;
+
+    if let Err(e) = self.persistence.index_names(vec![record]) {
+      tracing::error!("Index the name of the view failed: {}", e);
+    }
+    Ok(())
   }
 
   #[tracing::instrument(level = "debug", skip(self, view_id), err)]
@@ -234,28 +244,123 @@ impl ViewController {
     Ok(())
   }
 
+  /// Moves the view to the app or the view with the `to_belong_to_id`. Both the old and the new
+  /// parent are notified.
   #[tracing::instrument(level = "debug", skip(self), err)]
-  pub(crate) async fn duplicate_view(&self, view: ViewPB) -> Result<(), FlowyError> {
-    let view_rev = self
+  pub(crate) async fn move_view_to(
+    &self,
+    view_id: &str,
+    to_belong_to_id: &str,
+    index: usize,
+  ) -> Result<(), FlowyError> {
+    self
+      .persistence
+      .begin_transaction(|transaction| {
+        let from_belong_to_id = transaction.read_view(view_id)?.app_id;
+        transaction.move_view_to(view_id, to_belong_to_id, index)?;
+        notify_views_changed(
+          &from_belong_to_id,
+          self.trash_controller.clone(),
+          &transaction,
+        )?;
+        if from_belong_to_id != to_belong_to_id {
+          notify_views_changed(to_belong_to_id, self.trash_controller.clone(), &transaction)?;
+        }
+
+        let view: ViewPB = transaction.read_view(view_id)?.into();
+        notify_dart(view, FolderNotification::DidUpdateView);
+        Ok(())
+      })
+      .await?;
+    Ok(())
+  }
+
+  /// Duplicates the view and all the views that belong to it. The copy is added to the
+  /// `to_belong_to_id` of the params, or next to the view if it's None. Returns the copy of the
+  /// view.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub(crate) async fn duplicate_view(
+    &self,
+    params: DuplicateViewParams,
+  ) -> Result<ViewRevision, FlowyError> {
+    let (view_rev, trash_ids) = self
       .persistence
-      .begin_transaction(|transaction| transaction.read_view(&view.id))
+      .begin_transaction(|transaction| {
+        let view_rev = transaction.read_view(&params.view_id)?;
+        let trash_ids = self.trash_controller.read_trash_ids(&transaction)?;
+        Ok((view_rev, trash_ids))
+      })
       .await?;
 
+    // The ids of the copies are generated before duplicating the data, so the references between
+    // the views in the tree can be replaced with the copies.
+    let mut id_map = HashMap::new();
+    gen_duplicated_view_ids(&view_rev, &trash_ids, &mut id_map);
+
+    let to_belong_to_id = params
+      .to_belong_to_id
+      .unwrap_or_else(|| view_rev.app_id.clone());
+    let name = format!("{} (copy)", &view_rev.name);
+    let mut duplicated_root_view = None;
+    let mut views = vec![(view_rev, to_belong_to_id, name)];
+    while let Some((view_rev, belong_to_id, name)) = views.pop() {
+      let processor = self.get_data_processor(view_rev.data_format.clone())?;
+      let (view_data, duplicated_ids) = processor.duplicate_view_data(&view_rev.id, id_map).await?;
+      id_map = duplicated_ids;
+
+      let view_id = id_map
+        .get(&view_rev.id)
+        .cloned()
+        .unwrap_or_else(gen_view_id);
+      let duplicate_params = CreateViewParams {
+        belong_to_id,
+        name,
+        desc: view_rev.desc.clone(),
+        thumbnail: view_rev.thumbnail.clone(),
+        data_format: view_rev.data_format.clone().into(),
+        layout: view_rev.layout.clone().into(),
+        initial_data: view_data.to_vec(),
+        view_id: view_id.clone(),
+      };
+      let duplicated_view_rev = self.create_view_from_params(duplicate_params).await?;
+      duplicated_root_view.get_or_insert(duplicated_view_rev);
+
+      // Push the children in reverse order, so they are created in their original order.
+      for child_view_rev in view_rev.belongings.iter().rev() {
+        if !trash_ids.contains(&child_view_rev.id) {
+          views.push((
+            child_view_rev.clone(),
+            view_id.clone(),
+            child_view_rev.name.clone(),
+          ));
+        }
+      }
+    }
+
+    duplicated_root_view.ok_or_else(|| FlowyError::record_not_found().context("View not found"))
+  }
+
+  /// Returns the data of the view that is written to the backup. The references to the objects
+  /// in the `id_map` are replaced, see [ViewDataProcessor::duplicate_view_data].
+  pub(crate) async fn export_view_data(
+    &self,
+    view_rev: &ViewRevision,
+    id_map: HashMap<String, String>,
+  ) -> FlowyResult<(Bytes, HashMap<String, String>)> {
     let processor = self.get_data_processor(view_rev.data_format.clone())?;
-    let view_data = processor.get_view_data(&view).await?;
-    let duplicate_params = CreateViewParams {
-      belong_to_id: view_rev.app_id.clone(),
-      name: format!("{} (copy)", &view_rev.name),
-      desc: view_rev.desc,
-      thumbnail: view_rev.thumbnail,
-      data_format: view_rev.data_format.into(),
-      layout: view_rev.layout.into(),
-      initial_data: view_data.to_vec(),
-      view_id: gen_view_id(),
-    };
+    processor.duplicate_view_data(&view_rev.id, id_map).await
+  }
 
-    let _ = self.create_view_from_params(duplicate_params).await?;
-    Ok(())
+  /// Returns the data for creating the view from the data in the backup, see
+  /// [ViewDataProcessor::import_view_data].
+  pub(crate) async fn import_view_data(
+    &self,
+    view_rev: &ViewRevision,
+    data: Bytes,
+    id_map: HashMap<String, String>,
+  ) -> FlowyResult<(Bytes, HashMap<String, String>)> {
+    let processor = self.get_data_processor(view_rev.data_format.clone())?;
+    processor.import_view_data(data, id_map).await
   }
 
   // belong_to_id will be the app_id or view_id.
@@ -297,6 +402,11 @@ impl ViewController {
       })
       .await?;
 
+    let record = SearchIndexRecord::view(&view_rev.id, &view_rev.name);
+    if let Err(e) = self.persistence.index_names(vec![record]) {
+      tracing::error!("Index the name of the view failed: {}", e);
+    }
+
     let _ = self.update_view_on_server(params);
     Ok(view_rev)
   }
@@ -449,6 +559,14 @@ async fn handle_trash_event(
           })
           .await?;
 
+        let mut deleted_ids = vec![];
+        let mut deleted_views = views.iter().collect::<Vec<_>>();
+        while let Some(view) = deleted_views.pop() {
+          deleted_ids.push(view.id.clone());
+          deleted_views.extend(view.belongings.iter());
+        }
+        persistence.delete_from_search_index(deleted_ids)?;
+
         for view in views {
           let data_type = view.data_format.clone().into();
           match get_data_processor(data_processors.clone(), &data_type) {
@@ -506,6 +624,16 @@ fn notify_views_changed<'a>(
   trash_controller: Arc<TrashController>,
   transaction: &'a (dyn FolderPersistenceTransaction + 'a),
 ) -> FlowyResult<()> {
+  // The views can be nested in a view, the view gets notified with its child views.
+  if transaction.read_view(belong_to_id).is_ok() {
+    let view_revs = read_belonging_views_on_local(belong_to_id, trash_controller, transaction)?;
+    tracing::Span::current().record("view_count", view_revs.len());
+    send_notification(belong_to_id, FolderNotification::DidUpdateChildViews)
+      .payload(RepeatedViewPB::from(view_revs))
+      .send();
+    return Ok(());
+  }
+
   let mut app_rev = transaction.read_app(belong_to_id)?;
   let trash_ids = trash_controller.read_trash_ids(transaction)?;
   app_rev
@@ -520,6 +648,21 @@ fn notify_views_changed<'a>(
   Ok(())
 }
 
+/// Generates the ids of the copies of the view and the views that belong to it. The views in the
+/// trash are skipped.
+fn gen_duplicated_view_ids(
+  view_rev: &ViewRevision,
+  trash_ids: &[String],
+  id_map: &mut HashMap<String, String>,
+) {
+  id_map.insert(view_rev.id.clone(), gen_view_id());
+  for child_view_rev in view_rev.belongings.iter() {
+    if !trash_ids.contains(&child_view_rev.id) {
+      gen_duplicated_view_ids(child_view_rev, trash_ids, id_map);
+    }
+  }
+}
+
 fn read_belonging_views_on_local<'a>(
   belong_to_id: &str,
   trash_controller: Arc<TrashController>,
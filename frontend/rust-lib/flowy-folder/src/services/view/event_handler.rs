@@ -1,4 +1,7 @@
-use crate::entities::view::{MoveFolderItemParams, MoveFolderItemPayloadPB, MoveFolderItemType};
+use crate::entities::view::{
+  DuplicateViewParams, DuplicateViewPayloadPB, MoveFolderItemParams, MoveFolderItemPayloadPB,
+  MoveFolderItemType, MoveViewParams, MoveViewPayloadPB,
+};
 use crate::manager::FolderManager;
 use crate::services::{notify_workspace_setting_did_change, AppController};
 use crate::{
@@ -111,12 +114,38 @@ pub(crate) async fn move_item_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, controller), err)]
+pub(crate) async fn move_view_handler(
+  data: AFPluginData<MoveViewPayloadPB>,
+  controller: AFPluginState<Arc<ViewController>>,
+) -> Result<(), FlowyError> {
+  let params: MoveViewParams = data.into_inner().try_into()?;
+  controller
+    .move_view_to(&params.view_id, &params.to_belong_to_id, params.index)
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip(data, controller), err)]
 pub(crate) async fn duplicate_view_handler(
   data: AFPluginData<ViewPB>,
   controller: AFPluginState<Arc<ViewController>>,
 ) -> Result<(), FlowyError> {
   let view: ViewPB = data.into_inner();
-  controller.duplicate_view(view).await?;
+  let params = DuplicateViewParams {
+    view_id: view.id,
+    to_belong_to_id: None,
+  };
+  controller.duplicate_view(params).await?;
   Ok(())
 }
+
+#[tracing::instrument(level = "debug", skip(data, controller), err)]
+pub(crate) async fn duplicate_view_to_handler(
+  data: AFPluginData<DuplicateViewPayloadPB>,
+  controller: AFPluginState<Arc<ViewController>>,
+) -> DataResult<ViewPB, FlowyError> {
+  let params: DuplicateViewParams = data.into_inner().try_into()?;
+  let view_rev = controller.duplicate_view(params).await?;
+  data_result(view_rev.into())
+}
@@ -84,7 +84,6 @@ impl WorkspaceController {
     Ok(())
   }
 
-  #[allow(dead_code)]
   pub(crate) async fn delete_workspace(&self, workspace_id: &str) -> Result<(), FlowyError> {
     let user_id = self.user.user_id()?;
     let token = self.user.token()?;
@@ -1,5 +1,6 @@
 use crate::entities::{
   app::RepeatedAppPB,
+  snapshot::{FolderSnapshotContentPB, FolderSnapshotIdPB, RepeatedFolderSnapshotPB},
   view::ViewPB,
   workspace::{RepeatedWorkspacePB, WorkspaceIdPB, WorkspaceSettingPB, *},
 };
@@ -101,3 +102,32 @@ pub async fn read_cur_workspace_handler(
   };
   data_result(setting)
 }
+
+#[tracing::instrument(level = "debug", skip(folder), err)]
+pub(crate) async fn read_folder_snapshots_handler(
+  folder: AFPluginState<Arc<FolderManager>>,
+) -> DataResult<RepeatedFolderSnapshotPB, FlowyError> {
+  let snapshots = folder.get_folder_snapshots().await?;
+  data_result(snapshots.into())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn preview_folder_snapshot_handler(
+  data: AFPluginData<FolderSnapshotIdPB>,
+  folder: AFPluginState<Arc<FolderManager>>,
+) -> DataResult<FolderSnapshotContentPB, FlowyError> {
+  let rev_id = data.into_inner().rev_id;
+  let data = folder.preview_folder_snapshot(rev_id).await?;
+  data_result(FolderSnapshotContentPB { rev_id, data })
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn restore_folder_snapshot_handler(
+  data: AFPluginData<FolderSnapshotIdPB>,
+  folder: AFPluginState<Arc<FolderManager>>,
+) -> Result<(), FlowyError> {
+  folder
+    .restore_folder_snapshot(data.into_inner().rev_id)
+    .await?;
+  Ok(())
+}
@@ -1,8 +1,18 @@
-use crate::script::{invalid_workspace_name_test_case, FolderScript::*, FolderTest};
+use crate::script::{
+  create_app, create_workspace, import_csv, import_markdown, invalid_workspace_name_test_case,
+  search, FolderScript::*, FolderTest,
+};
+use flowy_document::editor::Document;
+use flowy_document::entities::{DocumentVersionPB, ExportType};
+use flowy_document::old_editor::editor::DeltaDocumentEditor;
+use flowy_document::DocumentEditor;
+use flowy_folder::entities::search::SearchObjectTypePB;
 use flowy_folder::entities::view::ViewDataFormatPB;
 use flowy_folder::entities::workspace::CreateWorkspacePayloadPB;
+use flowy_folder::entities::ViewLayoutTypePB;
 use flowy_revision_persistence::RevisionState;
 use flowy_test::{event_builder::*, FlowySDKTest};
+use std::sync::Arc;
 
 #[tokio::test]
 async fn workspace_read_all() {
@@ -200,6 +210,179 @@ async fn view_delete_then_restore() {
     .await;
 }
 
+#[tokio::test]
+async fn view_move_to_other_app() {
+  let mut test = FolderTest::new().await;
+  let view = test.view.clone();
+  let old_app = test.app.clone();
+  test
+    .run_scripts(vec![CreateApp {
+      name: "App B".to_owned(),
+      desc: "App B description".to_owned(),
+    }])
+    .await;
+
+  let new_app = test.app.clone();
+  test
+    .run_scripts(vec![
+      MoveView {
+        view_id: view.id.clone(),
+        to_belong_to_id: new_app.id.clone(),
+        index: 0,
+      },
+      ReadApp(new_app.id.clone()),
+    ])
+    .await;
+  assert_eq!(test.app.belongings.len(), 1);
+  assert_eq!(test.app.belongings[0].id, view.id);
+  assert_eq!(test.app.belongings[0].app_id, new_app.id);
+
+  test.run_scripts(vec![ReadApp(old_app.id)]).await;
+  assert!(test.app.belongings.is_empty());
+}
+
+#[tokio::test]
+async fn view_move_into_view() {
+  let mut test = FolderTest::new().await;
+  let view = test.view.clone();
+  let app = test.app.clone();
+  test
+    .run_scripts(vec![CreateView {
+      name: "View B".to_owned(),
+      desc: "View B description".to_owned(),
+      data_type: ViewDataFormatPB::DeltaFormat,
+    }])
+    .await;
+
+  let parent_view = test.view.clone();
+  test
+    .run_scripts(vec![
+      MoveView {
+        view_id: view.id.clone(),
+        to_belong_to_id: parent_view.id.clone(),
+        index: 0,
+      },
+      ReadView(view.id.clone()),
+    ])
+    .await;
+  assert_eq!(test.view.app_id, parent_view.id);
+
+  test.run_scripts(vec![ReadApp(app.id)]).await;
+  assert_eq!(test.app.belongings.len(), 1);
+  assert_eq!(test.app.belongings[0].belongings.items[0].id, view.id);
+}
+
+#[tokio::test]
+async fn view_move_into_descendant_fail() {
+  let mut test = FolderTest::new().await;
+  let view = test.view.clone();
+  test
+    .run_scripts(vec![CreateView {
+      name: "View B".to_owned(),
+      desc: "View B description".to_owned(),
+      data_type: ViewDataFormatPB::DeltaFormat,
+    }])
+    .await;
+
+  let child_view = test.view.clone();
+  test
+    .run_scripts(vec![
+      MoveView {
+        view_id: child_view.id.clone(),
+        to_belong_to_id: view.id.clone(),
+        index: 0,
+      },
+      AssertMoveViewError {
+        view_id: view.id.clone(),
+        to_belong_to_id: view.id.clone(),
+      },
+      AssertMoveViewError {
+        view_id: view.id.clone(),
+        to_belong_to_id: child_view.id.clone(),
+      },
+      ReadView(child_view.id.clone()),
+    ])
+    .await;
+  assert_eq!(test.view.app_id, view.id);
+}
+
+#[tokio::test]
+async fn view_duplicate_with_children() {
+  let mut test = FolderTest::new().await;
+  let view = test.view.clone();
+  let app = test.app.clone();
+  test
+    .run_scripts(vec![CreateView {
+      name: "View B".to_owned(),
+      desc: "View B description".to_owned(),
+      data_type: ViewDataFormatPB::DeltaFormat,
+    }])
+    .await;
+
+  let child_view = test.view.clone();
+  test
+    .run_scripts(vec![
+      MoveView {
+        view_id: child_view.id.clone(),
+        to_belong_to_id: view.id.clone(),
+        index: 0,
+      },
+      DuplicateView {
+        view_id: view.id.clone(),
+        to_belong_to_id: None,
+      },
+    ])
+    .await;
+  let duplicated_view = test.view.clone();
+  assert_ne!(duplicated_view.id, view.id);
+  assert_eq!(duplicated_view.name, "Folder View (copy)");
+  assert_eq!(duplicated_view.app_id, app.id);
+
+  test
+    .run_scripts(vec![ReadView(duplicated_view.id.clone())])
+    .await;
+  assert_eq!(test.view.belongings.len(), 1);
+  let duplicated_child_view = &test.view.belongings.items[0];
+  assert_ne!(duplicated_child_view.id, child_view.id);
+  assert_eq!(duplicated_child_view.name, "View B");
+  assert_eq!(duplicated_child_view.app_id, duplicated_view.id);
+
+  test.run_scripts(vec![ReadApp(app.id)]).await;
+  assert_eq!(test.app.belongings.len(), 2);
+  assert_eq!(test.app.belongings[0].belongings.items[0].id, child_view.id);
+}
+
+#[tokio::test]
+async fn view_duplicate_to_other_app() {
+  let mut test = FolderTest::new().await;
+  let view = test.view.clone();
+  let old_app = test.app.clone();
+  test
+    .run_scripts(vec![CreateApp {
+      name: "App B".to_owned(),
+      desc: "App B description".to_owned(),
+    }])
+    .await;
+
+  let new_app = test.app.clone();
+  test
+    .run_scripts(vec![
+      DuplicateView {
+        view_id: view.id.clone(),
+        to_belong_to_id: Some(new_app.id.clone()),
+      },
+      ReadApp(new_app.id.clone()),
+    ])
+    .await;
+  assert_eq!(test.app.belongings.len(), 1);
+  assert_ne!(test.app.belongings[0].id, view.id);
+  assert_eq!(test.app.belongings[0].app_id, new_app.id);
+
+  test.run_scripts(vec![ReadApp(old_app.id)]).await;
+  assert_eq!(test.app.belongings.len(), 1);
+  assert_eq!(test.app.belongings[0].id, view.id);
+}
+
 #[tokio::test]
 async fn view_delete_all() {
   let mut test = FolderTest::new().await;
@@ -269,6 +452,338 @@ async fn view_delete_all_permanent() {
   assert_eq!(test.trash.len(), 0);
 }
 
+#[tokio::test]
+async fn search_view_by_name() {
+  let mut test = FolderTest::new().await;
+  let view = test.view.clone();
+  test
+    .run_scripts(vec![
+      UpdateView {
+        name: Some("Weekly meeting notes".to_owned()),
+        desc: None,
+      },
+      Search {
+        query: "meet".to_owned(),
+        include_trash: false,
+      },
+    ])
+    .await;
+
+  assert_eq!(test.search_hits.len(), 1);
+  let hit = &test.search_hits[0];
+  assert_eq!(hit.view_id, view.id);
+  assert_eq!(hit.object_type, SearchObjectTypePB::View);
+  assert_eq!(hit.snippet, "Weekly <b>meeting</b> notes");
+
+  test
+    .run_scripts(vec![Search {
+      query: "Folder View".to_owned(),
+      include_trash: false,
+    }])
+    .await;
+  assert!(test.search_hits.is_empty());
+}
+
+#[tokio::test]
+async fn search_view_in_trash() {
+  let mut test = FolderTest::new().await;
+  let view = test.view.clone();
+  test
+    .run_scripts(vec![
+      DeleteView,
+      Search {
+        query: "Folder View".to_owned(),
+        include_trash: false,
+      },
+    ])
+    .await;
+  assert!(test.search_hits.is_empty());
+
+  test
+    .run_scripts(vec![Search {
+      query: "Folder View".to_owned(),
+      include_trash: true,
+    }])
+    .await;
+  assert_eq!(test.search_hits.len(), 1);
+  assert_eq!(test.search_hits[0].view_id, view.id);
+
+  test
+    .run_scripts(vec![
+      DeleteAllTrash,
+      Search {
+        query: "Folder View".to_owned(),
+        include_trash: true,
+      },
+    ])
+    .await;
+  assert!(test.search_hits.is_empty());
+}
+
+#[tokio::test]
+async fn search_imported_document_block() {
+  let sdk = FlowySDKTest::new(DocumentVersionPB::V1);
+  let _ = sdk.init_user().await;
+  let workspace = create_workspace(&sdk, "Workspace", "").await;
+  let app = create_app(&sdk, &workspace.id, "App", "").await;
+  let markdown = "* parent\n  * child\n* searchable sibling";
+  let view = import_markdown(&sdk, &app.id, "Imported", markdown).await;
+
+  // The blocks are searchable before the document is opened.
+  let hits = search(&sdk, "searchable", false).await.into_inner();
+  assert_eq!(hits.len(), 1);
+  let hit = &hits[0];
+  assert_eq!(hit.view_id, view.id);
+  assert_eq!(hit.object_type, SearchObjectTypePB::DocumentBlock);
+  assert!(hit.field_id.is_empty());
+  assert_eq!(hit.snippet, "<b>searchable</b> sibling");
+
+  let expected_path = Document::from_markdown(markdown)
+    .unwrap()
+    .get_search_records(&view.id)
+    .into_iter()
+    .find(|record| record.content.contains("searchable"))
+    .unwrap()
+    .object_id;
+  assert_eq!(hit.object_id, expected_path);
+}
+
+#[tokio::test]
+async fn search_imported_database_cell() {
+  let sdk = FlowySDKTest::default();
+  let _ = sdk.init_user().await;
+  let workspace = create_workspace(&sdk, "Workspace", "").await;
+  let app = create_app(&sdk, &workspace.id, "App", "").await;
+  let csv = "Name,Price\nsearchable apple,1\nbanana,2";
+  let view = import_csv(&sdk, &app.id, "Imported", csv).await;
+
+  // The cells are searchable before the database is opened.
+  let hits = search(&sdk, "searchable", false).await.into_inner();
+  assert_eq!(hits.len(), 1);
+  let hit = &hits[0];
+  assert_eq!(hit.view_id, view.id);
+  assert_eq!(hit.object_type, SearchObjectTypePB::DatabaseCell);
+  assert_eq!(hit.snippet, "<b>searchable</b> apple");
+
+  let editor = sdk.grid_manager.open_database(&view.id).await.unwrap();
+  let row_revs = editor.get_all_row_revs(&view.id).await.unwrap();
+  let field_revs = editor.get_field_revs(None).await.unwrap();
+  let name_field = field_revs
+    .iter()
+    .find(|field_rev| field_rev.name == "Name")
+    .unwrap();
+  assert_eq!(hit.object_id, row_revs[0].id);
+  assert_eq!(hit.field_id, name_field.id);
+}
+
+#[tokio::test]
+async fn view_import_markdown() {
+  let sdk = FlowySDKTest::new(DocumentVersionPB::V1);
+  let _ = sdk.init_user().await;
+  let workspace = create_workspace(&sdk, "Workspace", "").await;
+  let app = create_app(&sdk, &workspace.id, "App", "").await;
+  let markdown = "* parent\n  * child\n* sibling";
+  let view = import_markdown(&sdk, &app.id, "Imported", markdown).await;
+  assert_eq!(view.name, "Imported");
+  assert_eq!(view.app_id, app.id);
+  assert_eq!(view.data_format, ViewDataFormatPB::NodeFormat);
+  assert_eq!(view.layout, ViewLayoutTypePB::Document);
+
+  let editor = sdk
+    .document_manager
+    .open_document_editor(&view.id)
+    .await
+    .unwrap();
+  let expected = Document::from_markdown(markdown)
+    .unwrap()
+    .get_content(false)
+    .unwrap();
+  assert_eq!(editor.export().await.unwrap(), expected);
+  assert_eq!(
+    editor.export_as(ExportType::Markdown).await.unwrap(),
+    markdown
+  );
+}
+
+#[tokio::test]
+async fn workspace_export_then_import() {
+  let mut test = FolderTest::new().await;
+  let workspace = test.workspace.clone();
+  let app = test.app.clone();
+  let view = test.view.clone();
+  test
+    .run_scripts(vec![CreateView {
+      name: "Grid".to_owned(),
+      desc: "Grid description".to_owned(),
+      data_type: ViewDataFormatPB::DatabaseFormat,
+    }])
+    .await;
+
+  let grid_view = test.view.clone();
+  let sdk = test.sdk.clone();
+  let grid_editor = sdk.grid_manager.open_database(&grid_view.id).await.unwrap();
+  let primary_field = grid_editor
+    .get_field_revs(None)
+    .await
+    .unwrap()
+    .into_iter()
+    .find(|field_rev| field_rev.is_primary)
+    .unwrap();
+  let row_revs = grid_editor.get_all_row_revs(&grid_view.id).await.unwrap();
+  grid_editor
+    .update_cell(
+      row_revs[0].id.clone(),
+      primary_field.id.clone(),
+      "exported row".to_owned(),
+    )
+    .await
+    .unwrap();
+  let row_revs = grid_editor.get_all_row_revs(&grid_view.id).await.unwrap();
+
+  let document_editor = sdk
+    .document_manager
+    .open_document_editor(&view.id)
+    .await
+    .unwrap();
+  document_editor
+    .as_any()
+    .downcast_ref::<Arc<DeltaDocumentEditor>>()
+    .unwrap()
+    .insert(0, "exported content")
+    .await
+    .unwrap();
+  let document_content = document_editor.export().await.unwrap();
+
+  test
+    .run_scripts(vec![
+      MoveView {
+        view_id: grid_view.id.clone(),
+        to_belong_to_id: view.id.clone(),
+        index: 0,
+      },
+      CreateView {
+        name: "Trashed View".to_owned(),
+        desc: "".to_owned(),
+        data_type: ViewDataFormatPB::NodeFormat,
+      },
+      DeleteView,
+    ])
+    .await;
+
+  let path = std::env::temp_dir()
+    .join(format!("{}.zip", workspace.id))
+    .to_str()
+    .unwrap()
+    .to_owned();
+  test
+    .run_scripts(vec![
+      ExportWorkspace { path: path.clone() },
+      ImportWorkspace { path: path.clone() },
+    ])
+    .await;
+  std::fs::remove_file(&path).unwrap();
+
+  let imported_workspace = test.workspace.clone();
+  assert_ne!(imported_workspace.id, workspace.id);
+  assert_eq!(imported_workspace.name, workspace.name);
+  assert_eq!(imported_workspace.apps.len(), 1);
+
+  let imported_app = &imported_workspace.apps.items[0];
+  assert_ne!(imported_app.id, app.id);
+  assert_eq!(imported_app.name, app.name);
+  assert_eq!(imported_app.color_style, app.color_style);
+  assert_eq!(imported_app.belongings.len(), 1);
+
+  let imported_view = &imported_app.belongings.items[0];
+  assert_ne!(imported_view.id, view.id);
+  assert_eq!(imported_view.name, view.name);
+  assert_eq!(imported_view.app_id, imported_app.id);
+  test
+    .run_scripts(vec![ReadView(imported_view.id.clone())])
+    .await;
+  assert_eq!(test.view.belongings.len(), 1);
+  let imported_grid_view = &test.view.belongings.items[0];
+  assert_ne!(imported_grid_view.id, grid_view.id);
+  assert_eq!(imported_grid_view.name, grid_view.name);
+  assert_eq!(imported_grid_view.app_id, imported_view.id);
+
+  // The rows of the grid and the content of the document are imported.
+  let imported_grid_editor = sdk
+    .grid_manager
+    .open_database(&imported_grid_view.id)
+    .await
+    .unwrap();
+  let imported_primary_field = imported_grid_editor
+    .get_field_revs(None)
+    .await
+    .unwrap()
+    .into_iter()
+    .find(|field_rev| field_rev.is_primary)
+    .unwrap();
+  let imported_row_revs = imported_grid_editor
+    .get_all_row_revs(&imported_grid_view.id)
+    .await
+    .unwrap();
+  assert_eq!(imported_row_revs.len(), row_revs.len());
+  assert_eq!(
+    imported_row_revs[0].cells.get(&imported_primary_field.id),
+    row_revs[0].cells.get(&primary_field.id)
+  );
+
+  let imported_document_editor = sdk
+    .document_manager
+    .open_document_editor(&imported_view.id)
+    .await
+    .unwrap();
+  assert_eq!(
+    imported_document_editor.export().await.unwrap(),
+    document_content
+  );
+}
+
+#[tokio::test]
+async fn folder_restore_snapshot() {
+  let mut test = FolderTest::new().await;
+  let workspace_id = test.workspace.id.clone();
+  let app = test.app.clone();
+  let view = test.view.clone();
+  test
+    .run_scripts(vec![
+      WriteSnapshot,
+      UpdateView {
+        name: Some("Renamed View".to_owned()),
+        desc: None,
+      },
+      CreateView {
+        name: "View A".to_owned(),
+        desc: "View A description".to_owned(),
+        data_type: ViewDataFormatPB::DeltaFormat,
+      },
+      ReadApp(app.id.clone()),
+    ])
+    .await;
+  assert_eq!(test.app.belongings.len(), 2);
+  assert_eq!(test.app.belongings[0].name, "Renamed View");
+
+  test
+    .run_scripts(vec![
+      RestoreSnapshot,
+      ReadWorkspace(Some(workspace_id)),
+      ReadApp(app.id.clone()),
+      ReadView(view.id.clone()),
+    ])
+    .await;
+  assert!(test
+    .workspace
+    .apps
+    .items
+    .iter()
+    .any(|item| item.id == app.id));
+  assert_eq!(test.app.belongings.len(), 1);
+  assert_eq!(test.app.belongings[0].id, view.id);
+  assert_eq!(test.view.name, view.name);
+}
+
 #[tokio::test]
 async fn folder_sync_revision_state() {
   let mut test = FolderTest::new().await;
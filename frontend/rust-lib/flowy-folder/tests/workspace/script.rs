@@ -1,9 +1,16 @@
+use flowy_database::entities::{DatabaseViewIdPB, ImportCSVPayloadPB};
+use flowy_database::event_map::DatabaseEvent;
+use flowy_document::entities::{DocumentDataPB, ImportMarkdownPayloadPB};
+use flowy_document::event_map::DocumentEvent;
 use flowy_folder::entities::view::{RepeatedViewIdPB, ViewIdPB};
 use flowy_folder::entities::workspace::WorkspaceIdPB;
 use flowy_folder::entities::{
   app::{AppIdPB, CreateAppPayloadPB, UpdateAppPayloadPB},
+  backup::{ExportWorkspacePayloadPB, ImportWorkspacePayloadPB},
+  search::{RepeatedSearchHitPB, SearchHitPB, SearchPayloadPB},
+  snapshot::{FolderSnapshotIdPB, FolderSnapshotPB, RepeatedFolderSnapshotPB},
   trash::{RepeatedTrashPB, TrashIdPB, TrashType},
-  view::{CreateViewPayloadPB, UpdateViewPayloadPB},
+  view::{CreateViewPayloadPB, DuplicateViewPayloadPB, MoveViewPayloadPB, UpdateViewPayloadPB},
   workspace::{CreateWorkspacePayloadPB, RepeatedWorkspacePB},
   ViewLayoutTypePB,
 };
@@ -60,6 +67,19 @@ pub enum FolderScript {
   },
   DeleteView,
   DeleteViews(Vec<String>),
+  MoveView {
+    view_id: String,
+    to_belong_to_id: String,
+    index: i32,
+  },
+  AssertMoveViewError {
+    view_id: String,
+    to_belong_to_id: String,
+  },
+  DuplicateView {
+    view_id: String,
+    to_belong_to_id: Option<String>,
+  },
 
   // Trash
   RestoreAppFromTrash,
@@ -67,6 +87,24 @@ pub enum FolderScript {
   ReadTrash,
   DeleteAllTrash,
 
+  // Search
+  Search {
+    query: String,
+    include_trash: bool,
+  },
+
+  // Backup
+  ExportWorkspace {
+    path: String,
+  },
+  ImportWorkspace {
+    path: String,
+  },
+
+  // Snapshot
+  WriteSnapshot,
+  RestoreSnapshot,
+
   // Sync
   #[allow(dead_code)]
   AssertCurrentRevId(i64),
@@ -84,6 +122,8 @@ pub struct FolderTest {
   pub app: AppPB,
   pub view: ViewPB,
   pub trash: Vec<TrashPB>,
+  pub search_hits: Vec<SearchHitPB>,
+  pub snapshot: Option<FolderSnapshotPB>,
   // pub folder_editor:
 }
 
@@ -116,6 +156,8 @@ impl FolderTest {
       app,
       view,
       trash: vec![],
+      search_hits: vec![],
+      snapshot: None,
     }
   }
 
@@ -208,6 +250,26 @@ impl FolderTest {
       FolderScript::DeleteViews(view_ids) => {
         delete_view(sdk, view_ids).await;
       },
+      FolderScript::MoveView {
+        view_id,
+        to_belong_to_id,
+        index,
+      } => {
+        move_view(sdk, &view_id, &to_belong_to_id, index).await;
+      },
+      FolderScript::AssertMoveViewError {
+        view_id,
+        to_belong_to_id,
+      } => {
+        let _ = move_view(sdk, &view_id, &to_belong_to_id, 0).await.error();
+      },
+      FolderScript::DuplicateView {
+        view_id,
+        to_belong_to_id,
+      } => {
+        let view = duplicate_view(sdk, &view_id, to_belong_to_id).await;
+        self.view = view;
+      },
       FolderScript::RestoreAppFromTrash => {
         restore_app_from_trash(sdk, &self.app.id).await;
       },
@@ -222,6 +284,29 @@ impl FolderTest {
         delete_all_trash(sdk).await;
         self.trash = vec![];
       },
+      FolderScript::Search {
+        query,
+        include_trash,
+      } => {
+        let mut hits = search(sdk, &query, include_trash).await;
+        self.search_hits = hits.into_inner();
+      },
+      FolderScript::ExportWorkspace { path } => {
+        export_workspace(sdk, &self.workspace.id, &path).await;
+      },
+      FolderScript::ImportWorkspace { path } => {
+        let workspace = import_workspace(sdk, &path).await;
+        self.workspace = workspace;
+      },
+      FolderScript::WriteSnapshot => {
+        sleep(Duration::from_millis(2 * REVISION_WRITE_INTERVAL_IN_MILLIS)).await;
+        rev_manager.generate_snapshot().await;
+        self.snapshot = read_folder_snapshots(sdk).await.items.into_iter().next();
+      },
+      FolderScript::RestoreSnapshot => {
+        let rev_id = self.snapshot.as_ref().unwrap().rev_id;
+        restore_folder_snapshot(sdk, rev_id).await;
+      },
       FolderScript::AssertRevisionState { rev_id, state } => {
         let record = cache.get(rev_id).await.unwrap();
         assert_eq!(record.state, state, "Revision state is not match");
@@ -430,6 +515,77 @@ pub async fn delete_view(sdk: &FlowySDKTest, view_ids: Vec<String>) {
     .await;
 }
 
+pub async fn move_view(
+  sdk: &FlowySDKTest,
+  view_id: &str,
+  to_belong_to_id: &str,
+  index: i32,
+) -> FolderEventBuilder {
+  let request = MoveViewPayloadPB {
+    view_id: view_id.to_string(),
+    to_belong_to_id: to_belong_to_id.to_string(),
+    index,
+  };
+  FolderEventBuilder::new(sdk.clone())
+    .event(MoveView)
+    .payload(request)
+    .async_send()
+    .await
+}
+
+pub async fn duplicate_view(
+  sdk: &FlowySDKTest,
+  view_id: &str,
+  to_belong_to_id: Option<String>,
+) -> ViewPB {
+  let request = DuplicateViewPayloadPB {
+    view_id: view_id.to_string(),
+    to_belong_to_id,
+  };
+  FolderEventBuilder::new(sdk.clone())
+    .event(DuplicateViewTo)
+    .payload(request)
+    .async_send()
+    .await
+    .parse::<ViewPB>()
+}
+
+pub async fn import_markdown(
+  sdk: &FlowySDKTest,
+  belong_to_id: &str,
+  name: &str,
+  markdown: &str,
+) -> ViewPB {
+  let request = ImportMarkdownPayloadPB {
+    belong_to_id: belong_to_id.to_owned(),
+    name: name.to_owned(),
+    markdown: markdown.to_owned(),
+  };
+  let document = FolderEventBuilder::new(sdk.clone())
+    .event(DocumentEvent::ImportMarkdown)
+    .payload(request)
+    .async_send()
+    .await
+    .parse::<DocumentDataPB>();
+  read_view(sdk, &document.doc_id).await
+}
+
+pub async fn import_csv(sdk: &FlowySDKTest, belong_to_id: &str, name: &str, csv: &str) -> ViewPB {
+  let request = ImportCSVPayloadPB {
+    belong_to_id: belong_to_id.to_owned(),
+    name: name.to_owned(),
+    csv: csv.to_owned(),
+  };
+  let view_id = FolderEventBuilder::new(sdk.clone())
+    .event(DatabaseEvent::ImportCSV)
+    .payload(request)
+    .async_send()
+    .await
+    .parse::<DatabaseViewIdPB>()
+    .value;
+  read_view(sdk, &view_id).await
+}
+
 pub async fn read_trash(sdk: &FlowySDKTest) -> RepeatedTrashPB {
   FolderEventBuilder::new(sdk.clone())
     .event(ReadTrash)
@@ -468,3 +624,57 @@ pub async fn delete_all_trash(sdk: &FlowySDKTest) {
     .async_send()
     .await;
 }
+
+pub async fn search(sdk: &FlowySDKTest, query: &str, include_trash: bool) -> RepeatedSearchHitPB {
+  let request = SearchPayloadPB {
+    query: query.to_owned(),
+    limit: None,
+    include_trash,
+  };
+  FolderEventBuilder::new(sdk.clone())
+    .event(Search)
+    .payload(request)
+    .async_send()
+    .await
+    .parse::<RepeatedSearchHitPB>()
+}
+
+pub async fn export_workspace(sdk: &FlowySDKTest, workspace_id: &str, path: &str) {
+  let request = ExportWorkspacePayloadPB {
+    workspace_id: workspace_id.to_owned(),
+    path: path.to_owned(),
+  };
+  FolderEventBuilder::new(sdk.clone())
+    .event(ExportWorkspace)
+    .payload(request)
+    .async_send()
+    .await;
+}
+
+pub async fn import_workspace(sdk: &FlowySDKTest, path: &str) -> WorkspacePB {
+  let request = ImportWorkspacePayloadPB {
+    path: path.to_owned(),
+  };
+  FolderEventBuilder::new(sdk.clone())
+    .event(ImportWorkspace)
+    .payload(request)
+    .async_send()
+    .await
+    .parse::<WorkspacePB>()
+}
+
+pub async fn read_folder_snapshots(sdk: &FlowySDKTest) -> RepeatedFolderSnapshotPB {
+  FolderEventBuilder::new(sdk.clone())
+    .event(ReadFolderSnapshots)
+    .async_send()
+    .await
+    .parse::<RepeatedFolderSnapshotPB>()
+}
+
+pub async fn restore_folder_snapshot(sdk: &FlowySDKTest, rev_id: i64) {
+  FolderEventBuilder::new(sdk.clone())
+    .event(RestoreFolderSnapshot)
+    .payload(FolderSnapshotIdPB { rev_id })
+    .async_send()
+    .await;
+}
@@ -1,8 +1,12 @@
+use crate::local_server::persistence::{MemoryDocumentCloudStorage, RevisionCloudStorage};
+use crate::local_server::sqlite_storage::SQLiteRevisionCloudStorage;
 use flowy_client_network_config::ClientServerConfiguration;
+use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc};
 
 mod persistence;
 mod server;
+mod sqlite_storage;
 mod ws;
 
 pub use server::*;
@@ -13,7 +17,7 @@ pub struct LocalServerContext {
   pub local_server: LocalServer,
 }
 
-pub fn build_server(_config: &ClientServerConfiguration) -> LocalServerContext {
+pub fn build_server(_config: &ClientServerConfiguration, storage_path: &str) -> LocalServerContext {
   let (client_ws_sender, server_ws_receiver) = mpsc::unbounded_channel();
   let (server_ws_sender, _) = broadcast::channel(16);
 
@@ -21,7 +25,17 @@ pub fn build_server(_config: &ClientServerConfiguration) -> LocalServerContext {
   // server_ws_receiver <- client_ws_sender
   let local_ws = LocalWebSocket::new(server_ws_receiver, server_ws_sender.clone());
   let client_ws_receiver = server_ws_sender;
-  let local_server = LocalServer::new(client_ws_sender, client_ws_receiver);
+  let storage: Arc<dyn RevisionCloudStorage> = match SQLiteRevisionCloudStorage::new(storage_path) {
+    Ok(storage) => Arc::new(storage),
+    Err(e) => {
+      tracing::error!(
+        "Open local server storage failed, fallback to memory: {:?}",
+        e
+      );
+      Arc::new(MemoryDocumentCloudStorage::default())
+    },
+  };
+  let local_server = LocalServer::new(client_ws_sender, client_ws_receiver, storage);
 
   LocalServerContext {
     local_ws,
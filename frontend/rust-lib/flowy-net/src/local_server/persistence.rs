@@ -1,7 +1,9 @@
+use database_model::DatabaseInfo;
 use document_model::document::DocumentInfo;
 use flowy_client_sync::{errors::SyncError, util::make_document_info_from_revisions};
+use flowy_server_sync::server_database::make_database_from_revisions;
 use flowy_server_sync::server_folder::make_folder_from_revisions;
-use flowy_sync::ext::{DocumentCloudPersistence, FolderCloudPersistence};
+use flowy_sync::ext::{DatabaseCloudPersistence, DocumentCloudPersistence, FolderCloudPersistence};
 use folder_model::folder::FolderInfo;
 use lib_infra::future::BoxResultFuture;
 use revision_model::Revision;
@@ -10,7 +12,7 @@ use std::{
   sync::Arc,
 };
 
-// For the moment, we use memory to cache the data, it will be implemented with
+// The revisions are stored in the sqlite by default, it could be implemented with
 // other storage. Like the Firestore,Dropbox.etc.
 pub trait RevisionCloudStorage: Send + Sync {
   fn set_revisions(&self, revisions: Vec<Revision>) -> BoxResultFuture<(), SyncError>;
@@ -36,11 +38,9 @@ impl Debug for LocalDocumentCloudPersistence {
   }
 }
 
-impl std::default::Default for LocalDocumentCloudPersistence {
-  fn default() -> Self {
-    LocalDocumentCloudPersistence {
-      storage: Arc::new(MemoryDocumentCloudStorage::default()),
-    }
+impl LocalDocumentCloudPersistence {
+  pub(crate) fn new(storage: Arc<dyn RevisionCloudStorage>) -> Self {
+    LocalDocumentCloudPersistence { storage }
   }
 }
 
@@ -161,8 +161,66 @@ impl DocumentCloudPersistence for LocalDocumentCloudPersistence {
   }
 }
 
+impl DatabaseCloudPersistence for LocalDocumentCloudPersistence {
+  fn read_database(&self, object_id: &str) -> BoxResultFuture<DatabaseInfo, SyncError> {
+    let storage = self.storage.clone();
+    let object_id = object_id.to_owned();
+    Box::pin(async move {
+      let revisions = storage.get_revisions(&object_id, None).await?;
+      match make_database_from_revisions(&object_id, revisions)? {
+        Some(database_info) => Ok(database_info),
+        None => Err(SyncError::record_not_found()),
+      }
+    })
+  }
+
+  fn create_database(
+    &self,
+    object_id: &str,
+    revisions: Vec<Revision>,
+  ) -> BoxResultFuture<Option<DatabaseInfo>, SyncError> {
+    let object_id = object_id.to_owned();
+    let storage = self.storage.clone();
+    Box::pin(async move {
+      storage.set_revisions(revisions.clone()).await?;
+      make_database_from_revisions(&object_id, revisions)
+    })
+  }
+
+  fn read_database_revisions(
+    &self,
+    object_id: &str,
+    rev_ids: Option<Vec<i64>>,
+  ) -> BoxResultFuture<Vec<Revision>, SyncError> {
+    let object_id = object_id.to_owned();
+    let storage = self.storage.clone();
+    Box::pin(async move { storage.get_revisions(&object_id, rev_ids).await })
+  }
+
+  fn save_database_revisions(&self, revisions: Vec<Revision>) -> BoxResultFuture<(), SyncError> {
+    let storage = self.storage.clone();
+    Box::pin(async move {
+      storage.set_revisions(revisions).await?;
+      Ok(())
+    })
+  }
+
+  fn reset_database(
+    &self,
+    object_id: &str,
+    revisions: Vec<Revision>,
+  ) -> BoxResultFuture<(), SyncError> {
+    let storage = self.storage.clone();
+    let object_id = object_id.to_owned();
+    Box::pin(async move {
+      storage.reset_object(&object_id, revisions).await?;
+      Ok(())
+    })
+  }
+}
+
 #[derive(Default)]
-struct MemoryDocumentCloudStorage {}
+pub(crate) struct MemoryDocumentCloudStorage {}
 impl RevisionCloudStorage for MemoryDocumentCloudStorage {
   fn set_revisions(&self, _revisions: Vec<Revision>) -> BoxResultFuture<(), SyncError> {
     Box::pin(async move { Ok(()) })
@@ -1,4 +1,5 @@
-use crate::local_server::persistence::LocalDocumentCloudPersistence;
+use crate::local_server::persistence::{LocalDocumentCloudPersistence, RevisionCloudStorage};
+use crate::local_server::LocalWebSocket;
 use async_stream::stream;
 use bytes::Bytes;
 use document_model::document::{
@@ -14,6 +15,7 @@ use flowy_folder::entities::{
   workspace::{CreateWorkspaceParams, UpdateWorkspaceParams, WorkspaceIdPB},
 };
 use flowy_folder::event_map::FolderCouldServiceV1;
+use flowy_server_sync::server_database::ServerDatabaseManager;
 use flowy_server_sync::server_document::ServerDocumentManager;
 use flowy_server_sync::server_folder::ServerFolderManager;
 use flowy_sync::{RevisionSyncResponse, RevisionUser};
@@ -39,7 +41,8 @@ use ws_model::ws_revision::{ClientRevisionWSData, ClientRevisionWSDataType};
 pub struct LocalServer {
   doc_manager: Arc<ServerDocumentManager>,
   folder_manager: Arc<ServerFolderManager>,
-  stop_tx: RwLock<Option<mpsc::Sender<()>>>,
+  database_manager: Arc<ServerDatabaseManager>,
+  stop_txs: RwLock<Vec<mpsc::Sender<()>>>,
   client_ws_sender: mpsc::UnboundedSender<WebSocketRawMessage>,
   client_ws_receiver: broadcast::Sender<WebSocketRawMessage>,
 }
@@ -48,37 +51,62 @@ impl LocalServer {
   pub fn new(
     client_ws_sender: mpsc::UnboundedSender<WebSocketRawMessage>,
     client_ws_receiver: broadcast::Sender<WebSocketRawMessage>,
+    storage: Arc<dyn RevisionCloudStorage>,
   ) -> Self {
-    let persistence = Arc::new(LocalDocumentCloudPersistence::default());
+    let persistence = Arc::new(LocalDocumentCloudPersistence::new(storage));
     let doc_manager = Arc::new(ServerDocumentManager::new(persistence.clone()));
-    let folder_manager = Arc::new(ServerFolderManager::new(persistence));
-    let stop_tx = RwLock::new(None);
+    let folder_manager = Arc::new(ServerFolderManager::new(persistence.clone()));
+    let database_manager = Arc::new(ServerDatabaseManager::new(persistence));
+    let stop_txs = RwLock::new(vec![]);
 
     LocalServer {
       doc_manager,
       folder_manager,
-      stop_tx,
+      database_manager,
+      stop_txs,
       client_ws_sender,
       client_ws_receiver,
     }
   }
 
   pub async fn stop(&self) {
-    let sender = self.stop_tx.read().clone();
-    if let Some(stop_tx) = sender {
+    let senders = self.stop_txs.read().clone();
+    for stop_tx in senders {
       let _ = stop_tx.send(()).await;
     }
   }
 
   pub fn run(&self) {
+    self.spawn_runner(
+      self.client_ws_sender.clone(),
+      self.client_ws_receiver.subscribe(),
+    );
+  }
+
+  /// Returns a new [LocalWebSocket] that connects to this server. The clients that connect to the
+  /// same server sync their revisions with each other.
+  pub fn connect(&self) -> LocalWebSocket {
+    let (client_ws_sender, server_ws_receiver) = mpsc::unbounded_channel();
+    let (server_ws_sender, _) = broadcast::channel(16);
+    let local_ws = LocalWebSocket::new(server_ws_receiver, server_ws_sender.clone());
+    self.spawn_runner(client_ws_sender, server_ws_sender.subscribe());
+    local_ws
+  }
+
+  fn spawn_runner(
+    &self,
+    client_ws_sender: mpsc::UnboundedSender<WebSocketRawMessage>,
+    client_ws_receiver: broadcast::Receiver<WebSocketRawMessage>,
+  ) {
     let (stop_tx, stop_rx) = mpsc::channel(1);
-    *self.stop_tx.write() = Some(stop_tx);
+    self.stop_txs.write().push(stop_tx);
     let runner = LocalWebSocketRunner {
       doc_manager: self.doc_manager.clone(),
       folder_manager: self.folder_manager.clone(),
+      database_manager: self.database_manager.clone(),
       stop_rx: Some(stop_rx),
-      client_ws_sender: self.client_ws_sender.clone(),
-      client_ws_receiver: Some(self.client_ws_receiver.subscribe()),
+      client_ws_sender,
+      client_ws_receiver: Some(client_ws_receiver),
     };
     tokio::spawn(runner.run());
   }
@@ -87,6 +115,7 @@ impl LocalServer {
 struct LocalWebSocketRunner {
   doc_manager: Arc<ServerDocumentManager>,
   folder_manager: Arc<ServerFolderManager>,
+  database_manager: Arc<ServerDatabaseManager>,
   stop_rx: Option<mpsc::Receiver<()>>,
   client_ws_sender: mpsc::UnboundedSender<WebSocketRawMessage>,
   client_ws_receiver: Option<broadcast::Receiver<WebSocketRawMessage>>,
@@ -139,7 +168,10 @@ impl LocalWebSocketRunner {
         Ok(())
       },
       WSChannel::Database => {
-        todo!("Implement database web socket channel")
+        self
+          .handle_database_client_data(client_data, "".to_owned())
+          .await?;
+        Ok(())
       },
     }
   }
@@ -179,6 +211,41 @@ impl LocalWebSocketRunner {
     Ok(())
   }
 
+  pub async fn handle_database_client_data(
+    &self,
+    client_data: ClientRevisionWSData,
+    user_id: String,
+  ) -> Result<(), SyncError> {
+    tracing::trace!(
+      "[LocalDatabaseServer] receive: {}:{}-{:?} ",
+      client_data.object_id,
+      client_data.rev_id,
+      client_data.ty,
+    );
+    let client_ws_sender = self.client_ws_sender.clone();
+    let user = Arc::new(LocalRevisionUser {
+      user_id,
+      client_ws_sender,
+      channel: WSChannel::Database,
+    });
+    let ty = client_data.ty.clone();
+    match ty {
+      ClientRevisionWSDataType::ClientPushRev => {
+        self
+          .database_manager
+          .handle_client_revisions(user, client_data)
+          .await?;
+      },
+      ClientRevisionWSDataType::ClientPing => {
+        self
+          .database_manager
+          .handle_client_ping(user, client_data)
+          .await?;
+      },
+    }
+    Ok(())
+  }
+
   pub async fn handle_document_client_data(
     &self,
     client_data: ClientRevisionWSData,
@@ -362,6 +429,7 @@ impl FolderCouldServiceV1 for LocalServer {
       version: 0,
       modified_time: time,
       create_time: time,
+      color_style: params.color_style.into(),
     };
     FutureResult::new(async { Ok(app) })
   }
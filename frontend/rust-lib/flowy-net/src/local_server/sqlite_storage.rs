@@ -0,0 +1,218 @@
+use crate::local_server::persistence::RevisionCloudStorage;
+use bytes::Bytes;
+use flowy_client_sync::errors::{internal_sync_error, SyncError};
+use flowy_sqlite::{
+  prelude::*,
+  server_schema::{server_rev_table, server_rev_table::dsl},
+  Database,
+};
+use lib_infra::future::BoxResultFuture;
+use revision_model::Revision;
+use std::path::PathBuf;
+
+/// Stores the revisions of the local server in its own sqlite database that is located in the
+/// `local_server` directory of the storage path, so the revisions survive restarting the app. The
+/// md5 of the revisions is stored as well, so the synchronizer can tell which revisions were
+/// applied before.
+pub(crate) struct SQLiteRevisionCloudStorage {
+  database: Database,
+}
+
+impl SQLiteRevisionCloudStorage {
+  pub(crate) fn new(storage_path: &str) -> Result<Self, SyncError> {
+    let mut dir = PathBuf::new();
+    dir.push(storage_path);
+    dir.push("local_server");
+    let dir = dir.to_str().unwrap().to_owned();
+
+    tracing::trace!("open local server db at path: {}", dir);
+    let database = flowy_sqlite::init_server(&dir).map_err(internal_sync_error)?;
+    Ok(Self { database })
+  }
+}
+
+impl RevisionCloudStorage for SQLiteRevisionCloudStorage {
+  fn set_revisions(&self, revisions: Vec<Revision>) -> BoxResultFuture<(), SyncError> {
+    let pool = self.database.get_pool();
+    Box::pin(async move {
+      let conn = pool.get().map_err(internal_sync_error)?;
+      ServerRevisionSql::create(revisions, &conn).map_err(internal_sync_error)?;
+      Ok(())
+    })
+  }
+
+  fn get_revisions(
+    &self,
+    object_id: &str,
+    rev_ids: Option<Vec<i64>>,
+  ) -> BoxResultFuture<Vec<Revision>, SyncError> {
+    let pool = self.database.get_pool();
+    let object_id = object_id.to_owned();
+    Box::pin(async move {
+      let conn = pool.get().map_err(internal_sync_error)?;
+      let revisions =
+        ServerRevisionSql::read(&object_id, rev_ids, &conn).map_err(internal_sync_error)?;
+      Ok(revisions)
+    })
+  }
+
+  fn reset_object(
+    &self,
+    object_id: &str,
+    revisions: Vec<Revision>,
+  ) -> BoxResultFuture<(), SyncError> {
+    let pool = self.database.get_pool();
+    let object_id = object_id.to_owned();
+    Box::pin(async move {
+      let conn = pool.get().map_err(internal_sync_error)?;
+      conn
+        .immediate_transaction::<_, Error, _>(|| {
+          ServerRevisionSql::delete(&object_id, &conn)?;
+          ServerRevisionSql::create(revisions, &conn)?;
+          Ok(())
+        })
+        .map_err(internal_sync_error)?;
+      Ok(())
+    })
+  }
+}
+
+struct ServerRevisionSql {}
+
+impl ServerRevisionSql {
+  fn create(revisions: Vec<Revision>, conn: &SqliteConnection) -> Result<(), Error> {
+    let records = revisions
+      .into_iter()
+      .map(|revision| {
+        (
+          dsl::object_id.eq(revision.object_id),
+          dsl::base_rev_id.eq(revision.base_rev_id),
+          dsl::rev_id.eq(revision.rev_id),
+          dsl::data.eq(revision.bytes),
+          dsl::md5.eq(revision.md5),
+        )
+      })
+      .collect::<Vec<_>>();
+
+    // Replace the revision that has the same object_id and rev_id.
+    let _ = replace_into(dsl::server_rev_table)
+      .values(&records)
+      .execute(conn)?;
+    Ok(())
+  }
+
+  fn read(
+    object_id: &str,
+    rev_ids: Option<Vec<i64>>,
+    conn: &SqliteConnection,
+  ) -> Result<Vec<Revision>, Error> {
+    let mut sql = dsl::server_rev_table
+      .select((dsl::base_rev_id, dsl::rev_id, dsl::data, dsl::md5))
+      .filter(dsl::object_id.eq(object_id))
+      .order(dsl::rev_id.asc())
+      .into_boxed();
+    if let Some(rev_ids) = rev_ids {
+      sql = sql.filter(dsl::rev_id.eq_any(rev_ids));
+    }
+    let rows = sql.load::<(i64, i64, Vec<u8>, String)>(conn)?;
+    let revisions = rows
+      .into_iter()
+      .map(|(base_rev_id, rev_id, data, md5)| {
+        Revision::new(object_id, base_rev_id, rev_id, Bytes::from(data), md5)
+      })
+      .collect::<Vec<_>>();
+    Ok(revisions)
+  }
+
+  fn delete(object_id: &str, conn: &SqliteConnection) -> Result<(), Error> {
+    let sql = dsl::server_rev_table.filter(dsl::object_id.eq(object_id));
+    let affected_row = delete(sql).execute(conn)?;
+    tracing::trace!("[ServerRevisionSql] Delete {} rows", affected_row);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::local_server::persistence::RevisionCloudStorage;
+  use crate::local_server::sqlite_storage::SQLiteRevisionCloudStorage;
+  use bytes::Bytes;
+  use lib_infra::util::md5;
+  use revision_model::Revision;
+
+  fn make_revision(object_id: &str, rev_id: i64, data: &str) -> Revision {
+    let bytes = Bytes::from(data.to_owned());
+    let md5 = md5(&bytes);
+    Revision::new(object_id, rev_id - 1, rev_id, bytes, md5)
+  }
+
+  /// Removes the directory of the storage when the test finishes, even if the test fails.
+  struct TempDir(String);
+
+  impl TempDir {
+    fn new() -> Self {
+      Self(format!("./temp/{}", nanoid::nanoid!(6)))
+    }
+  }
+
+  impl Drop for TempDir {
+    fn drop(&mut self) {
+      let _ = std::fs::remove_dir_all(&self.0);
+    }
+  }
+
+  #[tokio::test]
+  async fn sqlite_storage_survives_reopen_test() {
+    let dir = TempDir::new();
+    let storage = SQLiteRevisionCloudStorage::new(&dir.0).unwrap();
+    storage
+      .set_revisions(vec![
+        make_revision("a", 1, "1"),
+        make_revision("a", 2, "2"),
+        make_revision("b", 1, "3"),
+      ])
+      .await
+      .unwrap();
+    drop(storage);
+
+    let storage = SQLiteRevisionCloudStorage::new(&dir.0).unwrap();
+    let revisions = storage.get_revisions("a", None).await.unwrap();
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[1].bytes, b"2".to_vec());
+    assert_eq!(revisions[1].md5, md5(b"2"));
+
+    // The md5 is read from the database instead of being computed from the data.
+    let mut revision = make_revision("c", 1, "5");
+    revision.md5 = "md5".to_owned();
+    storage.set_revisions(vec![revision]).await.unwrap();
+    let revisions = storage.get_revisions("c", None).await.unwrap();
+    assert_eq!(revisions[0].md5, "md5");
+
+    let revisions = storage.get_revisions("a", Some(vec![1])).await.unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].rev_id, 1);
+  }
+
+  #[tokio::test]
+  async fn sqlite_storage_reset_object_test() {
+    let dir = TempDir::new();
+    let storage = SQLiteRevisionCloudStorage::new(&dir.0).unwrap();
+    storage
+      .set_revisions(vec![
+        make_revision("a", 1, "1"),
+        make_revision("a", 2, "2"),
+        make_revision("b", 1, "3"),
+      ])
+      .await
+      .unwrap();
+
+    storage
+      .reset_object("a", vec![make_revision("a", 1, "4")])
+      .await
+      .unwrap();
+    let revisions = storage.get_revisions("a", None).await.unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].bytes, b"4".to_vec());
+    assert_eq!(storage.get_revisions("b", None).await.unwrap().len(), 1);
+  }
+}
@@ -1,22 +1,149 @@
-use crate::{RevisionChangeset, RevisionDiskCache, SyncRecord};
-use flowy_error::FlowyResult;
-use revision_model::RevisionRange;
+use crate::{RevisionChangeset, RevisionDiskCache, RevisionState, SyncRecord};
+use flowy_error::{internal_error, FlowyError, FlowyResult};
+use revision_model::{Revision, RevisionRange};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
+const LOG_FILE_EXTENSION: &str = "rev";
+const COMPACTING_FILE_EXTENSION: &str = "compacting";
+
+/// [FileRevisionDiskCache] stores the revisions of each object in its own append-only log file
+/// under the `path` directory. Each change is appended to the log as one line of JSON and the
+/// records are rebuilt by replaying the log.
+///
+/// `delete_and_insert_records` rewrites the whole log into a temporary file and then renames it
+/// to the log file, so the change is either fully applied or not applied at all.
 pub struct FileRevisionDiskCache {
   path: String,
+  lock: Mutex<()>,
 }
 
 pub type FileRevisionDiskCacheConnection = ();
 
+impl FileRevisionDiskCache {
+  /// Opens the cache in the `path` directory. The directory will be created if it doesn't exist.
+  ///
+  /// The files left behind by a crash are recovered when opening: the unfinished compaction
+  /// files are removed and the torn entries at the end of the log files are truncated.
+  pub fn new<T: Into<String>>(path: T) -> FlowyResult<Self> {
+    let path = path.into();
+    fs::create_dir_all(&path)?;
+    let cache = Self {
+      path,
+      lock: Mutex::new(()),
+    };
+    cache.recover()?;
+    Ok(cache)
+  }
+
+  fn recover(&self) -> FlowyResult<()> {
+    for entry in fs::read_dir(&self.path)? {
+      let file_path = entry?.path();
+      match file_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+      {
+        Some(COMPACTING_FILE_EXTENSION) => {
+          tracing::warn!("Remove the unfinished compaction file: {:?}", file_path);
+          fs::remove_file(&file_path)?;
+        },
+        Some(LOG_FILE_EXTENSION) => {
+          let (_, valid_len) = read_log_file(&file_path)?;
+          let file = OpenOptions::new().write(true).open(&file_path)?;
+          if file.metadata()?.len() > valid_len {
+            tracing::warn!("Truncate the torn entries of the log file: {:?}", file_path);
+            file.set_len(valid_len)?;
+            file.sync_all()?;
+          }
+        },
+        _ => {},
+      }
+    }
+    Ok(())
+  }
+
+  fn log_file_path(&self, object_id: &str) -> PathBuf {
+    Path::new(&self.path).join(object_file_name(object_id, LOG_FILE_EXTENSION))
+  }
+
+  fn compacting_file_path(&self, object_id: &str) -> PathBuf {
+    Path::new(&self.path).join(object_file_name(object_id, COMPACTING_FILE_EXTENSION))
+  }
+
+  /// Returns the records of the object ordered by rev_id
+  fn read_records(&self, object_id: &str) -> FlowyResult<BTreeMap<i64, SyncRecord>> {
+    let (entries, _) = read_log_file(&self.log_file_path(object_id))?;
+    let mut records = BTreeMap::new();
+    for entry in entries {
+      entry.apply(&mut records);
+    }
+    Ok(records)
+  }
+
+  fn append_entries(&self, object_id: &str, entries: Vec<RevisionLogEntry>) -> FlowyResult<()> {
+    if entries.is_empty() {
+      return Ok(());
+    }
+
+    let data = encode_entries(&entries)?;
+    let mut file = OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(self.log_file_path(object_id))?;
+
+    // Truncate the partially written entries, otherwise the next appended entry will be
+    // concatenated with them.
+    let len = file.metadata()?.len();
+    if let Err(e) = file.write_all(&data).and_then(|_| file.sync_data()) {
+      let _ = file.set_len(len);
+      return Err(e.into());
+    }
+    Ok(())
+  }
+
+  /// Rewrites the log of the object with the given records. The records are written to the
+  /// compaction file first and then the compaction file is renamed to the log file.
+  fn compact(&self, object_id: &str, records: BTreeMap<i64, SyncRecord>) -> FlowyResult<()> {
+    let entries = records
+      .into_values()
+      .map(RevisionLogEntry::from)
+      .collect::<Vec<_>>();
+    let data = encode_entries(&entries)?;
+
+    let compacting_file_path = self.compacting_file_path(object_id);
+    let mut file = File::create(&compacting_file_path)?;
+    file.write_all(&data)?;
+    file.sync_all()?;
+    fs::rename(&compacting_file_path, self.log_file_path(object_id))?;
+    Ok(())
+  }
+}
+
 impl RevisionDiskCache<FileRevisionDiskCacheConnection> for FileRevisionDiskCache {
-  type Error = ();
+  type Error = FlowyError;
 
   fn create_revision_records(&self, revision_records: Vec<SyncRecord>) -> Result<(), Self::Error> {
+    let _guard = self.lock.lock().map_err(internal_error)?;
+    let mut entries_by_object: HashMap<String, Vec<RevisionLogEntry>> = HashMap::new();
+    for record in revision_records {
+      entries_by_object
+        .entry(record.revision.object_id.clone())
+        .or_default()
+        .push(record.into());
+    }
+
+    for (object_id, entries) in entries_by_object {
+      self.append_entries(&object_id, entries)?;
+    }
     Ok(())
   }
 
   fn get_connection(&self) -> Result<FileRevisionDiskCacheConnection, Self::Error> {
-    return Ok(());
+    Ok(())
   }
 
   fn read_revision_records(
@@ -24,7 +151,16 @@ impl RevisionDiskCache<FileRevisionDiskCacheConnection> for FileRevisionDiskCach
     object_id: &str,
     rev_ids: Option<Vec<i64>>,
   ) -> Result<Vec<SyncRecord>, Self::Error> {
-    Ok(vec![])
+    let _guard = self.lock.lock().map_err(internal_error)?;
+    let records = self.read_records(object_id)?.into_values();
+    match rev_ids {
+      None => Ok(records.collect()),
+      Some(rev_ids) => Ok(
+        records
+          .filter(|record| rev_ids.contains(&record.revision.rev_id))
+          .collect(),
+      ),
+    }
   }
 
   fn read_revision_records_with_range(
@@ -32,10 +168,35 @@ impl RevisionDiskCache<FileRevisionDiskCacheConnection> for FileRevisionDiskCach
     object_id: &str,
     range: &RevisionRange,
   ) -> Result<Vec<SyncRecord>, Self::Error> {
-    Ok(vec![])
+    if range.start > range.end {
+      return Ok(vec![]);
+    }
+
+    let _guard = self.lock.lock().map_err(internal_error)?;
+    let records = self
+      .read_records(object_id)?
+      .range(range.start..=range.end)
+      .map(|(_, record)| record.clone())
+      .collect();
+    Ok(records)
   }
 
   fn update_revision_record(&self, changesets: Vec<RevisionChangeset>) -> FlowyResult<()> {
+    let _guard = self.lock.lock().map_err(internal_error)?;
+    let mut entries_by_object: HashMap<String, Vec<RevisionLogEntry>> = HashMap::new();
+    for changeset in changesets {
+      entries_by_object
+        .entry(changeset.object_id)
+        .or_default()
+        .push(RevisionLogEntry::Update {
+          rev_id: changeset.rev_id,
+          state: changeset.state as i32,
+        });
+    }
+
+    for (object_id, entries) in entries_by_object {
+      self.append_entries(&object_id, entries)?;
+    }
     Ok(())
   }
 
@@ -44,7 +205,8 @@ impl RevisionDiskCache<FileRevisionDiskCacheConnection> for FileRevisionDiskCach
     object_id: &str,
     rev_ids: Option<Vec<i64>>,
   ) -> Result<(), Self::Error> {
-    Ok(())
+    let _guard = self.lock.lock().map_err(internal_error)?;
+    self.append_entries(object_id, vec![RevisionLogEntry::Delete { rev_ids }])
   }
 
   fn delete_and_insert_records(
@@ -53,6 +215,288 @@ impl RevisionDiskCache<FileRevisionDiskCacheConnection> for FileRevisionDiskCach
     deleted_rev_ids: Option<Vec<i64>>,
     inserted_records: Vec<SyncRecord>,
   ) -> Result<(), Self::Error> {
-    todo!()
+    let _guard = self.lock.lock().map_err(internal_error)?;
+    let mut records = self.read_records(object_id)?;
+    RevisionLogEntry::Delete {
+      rev_ids: deleted_rev_ids,
+    }
+    .apply(&mut records);
+    for record in inserted_records {
+      RevisionLogEntry::from(record).apply(&mut records);
+    }
+    self.compact(object_id, records)
+  }
+}
+
+/// Returns the name of the file of the object. The object id is hex encoded, so the ids that
+/// contain the path separators or the characters that are invalid in the file names stay in the
+/// directory, and the ids that only differ in case don't share a file on the case-insensitive
+/// file systems.
+fn object_file_name(object_id: &str, extension: &str) -> String {
+  let encoded_id = object_id
+    .bytes()
+    .map(|byte| format!("{:02x}", byte))
+    .collect::<String>();
+  format!("{}.{}", encoded_id, extension)
+}
+
+/// The entry of the log file. Each entry is encoded as one line of JSON.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op")]
+enum RevisionLogEntry {
+  #[serde(rename = "insert")]
+  Insert { revision: Revision, state: i32 },
+
+  #[serde(rename = "update")]
+  Update { rev_id: i64, state: i32 },
+
+  /// Deletes all the records if the rev_ids is None
+  #[serde(rename = "delete")]
+  Delete { rev_ids: Option<Vec<i64>> },
+}
+
+impl RevisionLogEntry {
+  fn apply(self, records: &mut BTreeMap<i64, SyncRecord>) {
+    match self {
+      RevisionLogEntry::Insert { revision, state } => {
+        // Same as the `insert or ignore` of the SQLite implementations.
+        records.entry(revision.rev_id).or_insert(SyncRecord {
+          revision,
+          state: revision_state_from_i32(state),
+          write_to_disk: false,
+        });
+      },
+      RevisionLogEntry::Update { rev_id, state } => {
+        if let Some(record) = records.get_mut(&rev_id) {
+          record.state = revision_state_from_i32(state);
+        }
+      },
+      RevisionLogEntry::Delete { rev_ids } => match rev_ids {
+        None => records.clear(),
+        Some(rev_ids) => {
+          for rev_id in rev_ids {
+            records.remove(&rev_id);
+          }
+        },
+      },
+    }
+  }
+}
+
+impl std::convert::From<SyncRecord> for RevisionLogEntry {
+  fn from(record: SyncRecord) -> Self {
+    RevisionLogEntry::Insert {
+      revision: record.revision,
+      state: record.state as i32,
+    }
+  }
+}
+
+fn revision_state_from_i32(value: i32) -> RevisionState {
+  match value {
+    1 => RevisionState::Ack,
+    _ => RevisionState::Sync,
+  }
+}
+
+fn encode_entries(entries: &[RevisionLogEntry]) -> FlowyResult<Vec<u8>> {
+  let mut data = vec![];
+  for entry in entries {
+    serde_json::to_writer(&mut data, entry).map_err(internal_error)?;
+    data.push(b'\n');
+  }
+  Ok(data)
+}
+
+/// Reads the entries of the log file. Returns the entries and the length of the data that
+/// contains them. The data after the length is torn, it's the last entry that was partially
+/// written when crashing.
+fn read_log_file(file_path: &Path) -> FlowyResult<(Vec<RevisionLogEntry>, u64)> {
+  let mut data = vec![];
+  match File::open(file_path) {
+    Ok(mut file) => {
+      file.read_to_end(&mut data)?;
+    },
+    Err(e) if e.kind() == ErrorKind::NotFound => return Ok((vec![], 0)),
+    Err(e) => return Err(e.into()),
+  }
+
+  let mut entries = vec![];
+  let mut valid_len = 0;
+  while let Some(line_len) = data[valid_len..].iter().position(|byte| *byte == b'\n') {
+    let line = &data[valid_len..valid_len + line_len];
+    match serde_json::from_slice::<RevisionLogEntry>(line) {
+      Ok(entry) => entries.push(entry),
+      Err(e) => {
+        tracing::error!("Deserialize revision log entry failed: {:?}", e);
+        break;
+      },
+    }
+    valid_len += line_len + 1;
+  }
+  Ok((entries, valid_len as u64))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::disk_cache_impl::file_persistence::FileRevisionDiskCache;
+  use crate::{RevisionChangeset, RevisionDiskCache, RevisionState, SyncRecord};
+  use revision_model::{Revision, RevisionRange};
+  use std::fs::OpenOptions;
+  use std::io::Write;
+
+  const OBJECT_ID: &str = "object_id";
+
+  fn make_cache_dir(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("file_revision_disk_cache_{}", name));
+    let _ = std::fs::remove_dir_all(&path);
+    path.to_str().unwrap().to_owned()
+  }
+
+  fn make_record(rev_id: i64) -> SyncRecord {
+    let bytes = format!("revision {}", rev_id).into_bytes();
+    SyncRecord::new(Revision::new(
+      OBJECT_ID,
+      rev_id - 1,
+      rev_id,
+      bytes.into(),
+      "",
+    ))
+  }
+
+  fn rev_ids(records: Vec<SyncRecord>) -> Vec<i64> {
+    records
+      .into_iter()
+      .map(|record| record.revision.rev_id)
+      .collect()
+  }
+
+  #[test]
+  fn file_disk_cache_create_and_read_test() {
+    let path = make_cache_dir("create_and_read");
+    let cache = FileRevisionDiskCache::new(&path).unwrap();
+    cache
+      .create_revision_records(vec![make_record(3), make_record(1), make_record(2)])
+      .unwrap();
+
+    let records = cache.read_revision_records(OBJECT_ID, None).unwrap();
+    assert_eq!(rev_ids(records.clone()), vec![1, 2, 3]);
+    assert_eq!(records[0].revision.bytes, b"revision 1".to_vec());
+    assert!(!records[0].write_to_disk);
+
+    let records = cache
+      .read_revision_records(OBJECT_ID, Some(vec![1, 3]))
+      .unwrap();
+    assert_eq!(rev_ids(records), vec![1, 3]);
+
+    let range = RevisionRange { start: 2, end: 3 };
+    let records = cache
+      .read_revision_records_with_range(OBJECT_ID, &range)
+      .unwrap();
+    assert_eq!(rev_ids(records), vec![2, 3]);
+
+    assert!(cache
+      .read_revision_records("other_object_id", None)
+      .unwrap()
+      .is_empty());
+  }
+
+  #[test]
+  fn file_disk_cache_update_and_delete_test() {
+    let path = make_cache_dir("update_and_delete");
+    let cache = FileRevisionDiskCache::new(&path).unwrap();
+    cache
+      .create_revision_records(vec![make_record(1), make_record(2), make_record(3)])
+      .unwrap();
+    cache
+      .update_revision_record(vec![RevisionChangeset {
+        object_id: OBJECT_ID.to_owned(),
+        rev_id: 2,
+        state: RevisionState::Ack,
+      }])
+      .unwrap();
+    cache
+      .delete_revision_records(OBJECT_ID, Some(vec![1]))
+      .unwrap();
+
+    let records = cache.read_revision_records(OBJECT_ID, None).unwrap();
+    assert_eq!(rev_ids(records.clone()), vec![2, 3]);
+    assert_eq!(records[0].state, RevisionState::Ack);
+    assert_eq!(records[1].state, RevisionState::Sync);
+
+    cache.delete_revision_records(OBJECT_ID, None).unwrap();
+    assert!(cache
+      .read_revision_records(OBJECT_ID, None)
+      .unwrap()
+      .is_empty());
+  }
+
+  #[test]
+  fn file_disk_cache_delete_and_insert_test() {
+    let path = make_cache_dir("delete_and_insert");
+    let cache = FileRevisionDiskCache::new(&path).unwrap();
+    cache
+      .create_revision_records(vec![make_record(1), make_record(2), make_record(3)])
+      .unwrap();
+    cache
+      .delete_and_insert_records(OBJECT_ID, Some(vec![2, 3]), vec![make_record(4)])
+      .unwrap();
+    let records = cache.read_revision_records(OBJECT_ID, None).unwrap();
+    assert_eq!(rev_ids(records), vec![1, 4]);
+
+    cache
+      .delete_and_insert_records(OBJECT_ID, None, vec![make_record(5)])
+      .unwrap();
+    let records = cache.read_revision_records(OBJECT_ID, None).unwrap();
+    assert_eq!(rev_ids(records), vec![5]);
+
+    // Reopen the cache
+    let cache = FileRevisionDiskCache::new(&path).unwrap();
+    let records = cache.read_revision_records(OBJECT_ID, None).unwrap();
+    assert_eq!(rev_ids(records), vec![5]);
+  }
+
+  #[test]
+  fn file_disk_cache_recover_test() {
+    let path = make_cache_dir("recover");
+    let cache = FileRevisionDiskCache::new(&path).unwrap();
+    cache
+      .create_revision_records(vec![make_record(1), make_record(2)])
+      .unwrap();
+
+    // Simulate crashing while appending the entry and compacting the log.
+    let mut file = OpenOptions::new()
+      .append(true)
+      .open(cache.log_file_path(OBJECT_ID))
+      .unwrap();
+    file.write_all(br#"{"op":"insert","revision":{"#).unwrap();
+    let compacting_file_path = cache.compacting_file_path(OBJECT_ID);
+    std::fs::write(&compacting_file_path, b"").unwrap();
+
+    let cache = FileRevisionDiskCache::new(&path).unwrap();
+    assert!(!compacting_file_path.exists());
+    cache.create_revision_records(vec![make_record(3)]).unwrap();
+    let records = cache.read_revision_records(OBJECT_ID, None).unwrap();
+    assert_eq!(rev_ids(records), vec![1, 2, 3]);
+  }
+
+  #[test]
+  fn file_disk_cache_encode_object_id_test() {
+    let path = make_cache_dir("encode_object_id");
+    let cache = FileRevisionDiskCache::new(&path).unwrap();
+    for object_id in ["../escaped", "grid_block:abc", "Abc", "abc"] {
+      let revision = Revision::new(object_id, 0, 1, object_id.as_bytes().to_vec().into(), "");
+      cache
+        .create_revision_records(vec![SyncRecord::new(revision)])
+        .unwrap();
+      let file_path = cache.log_file_path(object_id);
+      assert_eq!(file_path.parent().unwrap(), std::path::Path::new(&path));
+      assert!(file_path.exists());
+    }
+
+    // The ids that only differ in case are stored in different files.
+    let records = cache.read_revision_records("Abc", None).unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].revision.bytes, b"Abc".to_vec());
   }
 }
@@ -1,5 +1,7 @@
 mod disk_cache_impl;
 
+pub use disk_cache_impl::*;
+
 use flowy_error::{FlowyError, FlowyResult};
 use revision_model::{Revision, RevisionRange};
 use std::fmt::Debug;
@@ -216,6 +216,34 @@ impl<Connection: 'static> RevisionManager<Connection> {
     }
   }
 
+  pub async fn read_snapshots(&self) -> FlowyResult<Vec<RevisionSnapshotData>> {
+    self.rev_snapshot.read_snapshots()
+  }
+
+  /// Deserializes the snapshot with the rev_id into the object. It's used to preview or restore
+  /// the content of the object at that revision.
+  pub async fn read_snapshot_object<De>(&self, rev_id: i64) -> FlowyResult<De::Output>
+  where
+    De: RevisionObjectDeserializer + 'static,
+  {
+    match self.rev_snapshot.read_snapshot(rev_id)? {
+      None => Err(
+        FlowyError::record_not_found()
+          .context(format!("Can't find the snapshot with rev_id: {}", rev_id)),
+      ),
+      Some(snapshot) => {
+        let revision = Revision::new(
+          &self.object_id,
+          snapshot.base_rev_id,
+          snapshot.rev_id,
+          snapshot.data,
+          "".to_owned(),
+        );
+        De::deserialize_revisions(&self.object_id, vec![revision])
+      },
+    }
+  }
+
   pub async fn load_revisions(&self) -> FlowyResult<Vec<Revision>> {
     let revisions = RevisionLoader {
       object_id: self.object_id.clone(),
@@ -19,6 +19,9 @@ pub trait RevisionSnapshotPersistence: Send + Sync {
   fn read_snapshot(&self, rev_id: i64) -> FlowyResult<Option<RevisionSnapshotData>>;
 
   fn read_last_snapshot(&self) -> FlowyResult<Option<RevisionSnapshotData>>;
+
+  /// Returns all the snapshots of the object, the latest one comes first.
+  fn read_snapshots(&self) -> FlowyResult<Vec<RevisionSnapshotData>>;
 }
 
 pub trait RevisionSnapshotDataGenerator: Send + Sync {
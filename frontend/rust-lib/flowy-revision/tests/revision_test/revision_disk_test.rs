@@ -92,3 +92,136 @@ async fn revision_read_from_disk_with_invalid_record_test() {
     }])
     .await;
 }
+
+#[tokio::test]
+async fn revision_write_to_file_disk_test() {
+  let test = RevisionTest::new_with_file_disk_cache(2).await;
+  test
+    .run_scripts(vec![
+      AddLocalRevision {
+        content: "123".to_string(),
+      },
+      AssertNumberOfRevisionsInDisk { num: 0 },
+      WaitWhenWriteToDisk,
+      AssertNumberOfRevisionsInDisk { num: 1 },
+    ])
+    .await;
+}
+
+#[tokio::test]
+async fn revision_write_to_file_disk_with_merge_test() {
+  let test = RevisionTest::new_with_file_disk_cache(100).await;
+  for i in 0..1000 {
+    test
+      .run_script(AddLocalRevision {
+        content: format!("{}", i),
+      })
+      .await;
+  }
+
+  test
+    .run_scripts(vec![
+      AssertNumberOfRevisionsInDisk { num: 0 },
+      AssertNumberOfSyncRevisions { num: 10 },
+      WaitWhenWriteToDisk,
+      AssertNumberOfRevisionsInDisk { num: 10 },
+    ])
+    .await;
+}
+
+#[tokio::test]
+async fn revision_read_from_file_disk_test() {
+  let test = RevisionTest::new_with_file_disk_cache(2).await;
+  test
+    .run_scripts(vec![
+      AddLocalRevision {
+        content: "123".to_string(),
+      },
+      AssertNumberOfRevisionsInDisk { num: 0 },
+      WaitWhenWriteToDisk,
+      AssertNumberOfRevisionsInDisk { num: 1 },
+    ])
+    .await;
+
+  let test = RevisionTest::new_with_other(test).await;
+  test
+    .run_scripts(vec![
+      AssertNumberOfRevisionsInDisk { num: 1 },
+      AssertNextSyncRevisionId { rev_id: Some(1) },
+      AssertNextSyncRevisionContent {
+        expected: "123".to_string(),
+      },
+      AddLocalRevision {
+        content: "456".to_string(),
+      },
+      AckRevision { rev_id: 1 },
+      AssertNextSyncRevisionId { rev_id: Some(2) },
+      WaitWhenWriteToDisk,
+    ])
+    .await;
+
+  // The acked revision is not synced again after reopening.
+  let test = RevisionTest::new_with_other(test).await;
+  test
+    .run_scripts(vec![
+      AssertNumberOfRevisionsInDisk { num: 2 },
+      AssertNextSyncRevisionId { rev_id: Some(2) },
+    ])
+    .await;
+}
+
+#[tokio::test]
+async fn revision_read_from_file_disk_with_invalid_record_test() {
+  let test = RevisionTest::new_with_file_disk_cache(2).await;
+  test
+    .run_scripts(vec![AddLocalRevision {
+      content: "123".to_string(),
+    }])
+    .await;
+
+  test
+    .run_scripts(vec![
+      AddInvalidLocalRevision {
+        bytes: InvalidRevisionObject::new().to_bytes(),
+      },
+      WaitWhenWriteToDisk,
+    ])
+    .await;
+
+  let test = RevisionTest::new_with_other(test).await;
+  test
+    .run_scripts(vec![AssertNextSyncRevisionContent {
+      expected: "123".to_string(),
+    }])
+    .await;
+}
+
+#[tokio::test]
+async fn revision_reset_file_disk_test() {
+  let test = RevisionTest::new_with_file_disk_cache(2).await;
+  test
+    .run_scripts(vec![
+      AddLocalRevision {
+        content: "123".to_string(),
+      },
+      AddLocalRevision {
+        content: "456".to_string(),
+      },
+      WaitWhenWriteToDisk,
+      ResetObject {
+        content: "789".to_string(),
+      },
+      AssertNumberOfRevisionsInDisk { num: 1 },
+    ])
+    .await;
+
+  let test = RevisionTest::new_with_other(test).await;
+  test
+    .run_scripts(vec![
+      AssertNumberOfRevisionsInDisk { num: 1 },
+      AssertNextSyncRevisionContent {
+        expected: "789".to_string(),
+      },
+    ])
+    .await;
+}
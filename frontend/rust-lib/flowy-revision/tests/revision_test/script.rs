@@ -5,7 +5,10 @@ use flowy_revision::{
   RevisionPersistenceConfiguration, RevisionSnapshotData, RevisionSnapshotPersistence,
   REVISION_WRITE_INTERVAL_IN_MILLIS,
 };
-use flowy_revision_persistence::{RevisionChangeset, RevisionDiskCache, SyncRecord};
+use flowy_revision_persistence::{
+  FileRevisionDiskCache, FileRevisionDiskCacheConnection, RevisionChangeset, RevisionDiskCache,
+  SyncRecord,
+};
 
 use lib_infra::util::md5;
 use nanoid::nanoid;
@@ -20,6 +23,7 @@ pub enum RevisionScript {
   AddLocalRevision2 { content: String },
   AddInvalidLocalRevision { bytes: Vec<u8> },
   AckRevision { rev_id: i64 },
+  ResetObject { content: String },
   AssertNextSyncRevisionId { rev_id: Option<i64> },
   AssertNumberOfSyncRevisions { num: usize },
   AssertNumberOfRevisionsInDisk { num: usize },
@@ -32,6 +36,17 @@ pub struct RevisionTest {
   object_id: String,
   configuration: RevisionPersistenceConfiguration,
   rev_manager: Arc<RevisionManager<RevisionConnectionMock>>,
+  /// The directory of the [FileRevisionDiskCache]. Uses the [RevisionDiskCacheMock] if it's None.
+  file_cache_path: Option<FileCachePath>,
+}
+
+/// Removes the directory of the [FileRevisionDiskCache] when the test is dropped.
+struct FileCachePath(String);
+
+impl Drop for FileCachePath {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_dir_all(&self.0);
+  }
 }
 
 impl RevisionTest {
@@ -40,57 +55,51 @@ impl RevisionTest {
   }
 
   pub async fn new_with_configuration(max_merge_len: i64) -> Self {
+    Self::new_with_disk_cache(max_merge_len, None).await
+  }
+
+  /// Uses the [FileRevisionDiskCache] that saves the revisions in a temporary directory.
+  pub async fn new_with_file_disk_cache(max_merge_len: i64) -> Self {
+    let path = std::env::temp_dir().join(format!("flowy_revision_test_{}", nanoid!(10)));
+    let file_cache_path = FileCachePath(path.to_str().unwrap().to_owned());
+    Self::new_with_disk_cache(max_merge_len, Some(file_cache_path)).await
+  }
+
+  async fn new_with_disk_cache(max_merge_len: i64, file_cache_path: Option<FileCachePath>) -> Self {
     let user_id = nanoid!(10);
     let object_id = nanoid!(6);
     let configuration = RevisionPersistenceConfiguration::new(max_merge_len as usize, false);
-    let disk_cache = RevisionDiskCacheMock::new(vec![]);
-    let persistence =
-      RevisionPersistence::new(&user_id, &object_id, disk_cache, configuration.clone());
-    let compress = RevisionMergeableMock {};
-    let snapshot = RevisionSnapshotMock {};
-    let mut rev_manager =
-      RevisionManager::new(&user_id, &object_id, persistence, compress, snapshot);
-    rev_manager
-      .initialize::<RevisionObjectMockSerde>(None)
-      .await
-      .unwrap();
+    let disk_cache = make_disk_cache(file_cache_path.as_ref(), vec![]);
+    let rev_manager =
+      make_rev_manager(&user_id, &object_id, disk_cache, configuration.clone()).await;
     Self {
       user_id,
       object_id,
       configuration,
       rev_manager: Arc::new(rev_manager),
+      file_cache_path,
     }
   }
 
+  /// Reopens the object of the `old_test`. The [FileRevisionDiskCache] reads the revisions from
+  /// the files written by the `old_test`.
   pub async fn new_with_other(old_test: RevisionTest) -> Self {
     let records = old_test.rev_manager.get_all_revision_records().unwrap();
-    let disk_cache = RevisionDiskCacheMock::new(records);
+    let disk_cache = make_disk_cache(old_test.file_cache_path.as_ref(), records);
     let configuration = old_test.configuration;
-    let persistence = RevisionPersistence::new(
+    let rev_manager = make_rev_manager(
       &old_test.user_id,
       &old_test.object_id,
       disk_cache,
       configuration.clone(),
-    );
-
-    let compress = RevisionMergeableMock {};
-    let snapshot = RevisionSnapshotMock {};
-    let mut rev_manager = RevisionManager::new(
-      &old_test.user_id,
-      &old_test.object_id,
-      persistence,
-      compress,
-      snapshot,
-    );
-    rev_manager
-      .initialize::<RevisionObjectMockSerde>(None)
-      .await
-      .unwrap();
+    )
+    .await;
     Self {
       user_id: old_test.user_id,
       object_id: old_test.object_id,
       configuration,
       rev_manager: Arc::new(rev_manager),
+      file_cache_path: old_test.file_cache_path,
     }
   }
   pub async fn run_scripts(&self, scripts: Vec<RevisionScript>) {
@@ -133,6 +142,12 @@ impl RevisionTest {
         //
         self.rev_manager.ack_revision(rev_id).await.unwrap()
       },
+      RevisionScript::ResetObject { content } => {
+        let bytes = RevisionObjectMock::new(&content).to_bytes();
+        let md5 = md5(&bytes);
+        let revision = Revision::new(&self.object_id, 0, 1, Bytes::from(bytes), md5);
+        self.rev_manager.reset_object(vec![revision]).await.unwrap();
+      },
       RevisionScript::AssertNextSyncRevisionId { rev_id } => {
         assert_eq!(self.rev_manager.next_sync_rev_id().await, rev_id)
       },
@@ -157,6 +172,34 @@ impl RevisionTest {
   }
 }
 
+fn make_disk_cache(
+  file_cache_path: Option<&FileCachePath>,
+  records: Vec<SyncRecord>,
+) -> Arc<dyn RevisionDiskCache<RevisionConnectionMock, Error = FlowyError>> {
+  match file_cache_path {
+    None => Arc::new(RevisionDiskCacheMock::new(records)),
+    Some(path) => Arc::new(FileRevisionDiskCache::new(&path.0).unwrap()),
+  }
+}
+
+async fn make_rev_manager(
+  user_id: &str,
+  object_id: &str,
+  disk_cache: Arc<dyn RevisionDiskCache<RevisionConnectionMock, Error = FlowyError>>,
+  configuration: RevisionPersistenceConfiguration,
+) -> RevisionManager<RevisionConnectionMock> {
+  let persistence =
+    RevisionPersistence::from_disk_cache(user_id, object_id, disk_cache, configuration);
+  let compress = RevisionMergeableMock {};
+  let snapshot = RevisionSnapshotMock {};
+  let mut rev_manager = RevisionManager::new(user_id, object_id, persistence, compress, snapshot);
+  rev_manager
+    .initialize::<RevisionObjectMockSerde>(None)
+    .await
+    .unwrap();
+  rev_manager
+}
+
 pub struct RevisionDiskCacheMock {
   records: RwLock<Vec<SyncRecord>>,
 }
@@ -265,7 +308,9 @@ impl RevisionDiskCache<RevisionConnectionMock> for RevisionDiskCacheMock {
   }
 }
 
-pub struct RevisionConnectionMock {}
+/// Shares the connection type with the [FileRevisionDiskCache], so the [RevisionTest] can run
+/// with both of them.
+pub type RevisionConnectionMock = FileRevisionDiskCacheConnection;
 pub struct RevisionSnapshotMock {}
 impl RevisionSnapshotPersistence for RevisionSnapshotMock {
   fn write_snapshot(&self, _rev_id: i64, _data: Vec<u8>) -> FlowyResult<()> {
@@ -279,6 +324,10 @@ impl RevisionSnapshotPersistence for RevisionSnapshotMock {
   fn read_last_snapshot(&self) -> FlowyResult<Option<RevisionSnapshotData>> {
     Ok(None)
   }
+
+  fn read_snapshots(&self) -> FlowyResult<Vec<RevisionSnapshotData>> {
+    Ok(vec![])
+  }
 }
 
 pub struct RevisionMergeableMock {}
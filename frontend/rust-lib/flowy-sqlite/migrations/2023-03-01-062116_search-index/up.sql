@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE VIRTUAL TABLE search_index_table USING fts5(
+    view_id UNINDEXED,
+    object_type UNINDEXED,
+    object_id UNINDEXED,
+    field_id UNINDEXED,
+    content,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
@@ -0,0 +1,10 @@
+-- The tables of the local server database. Run by `init_server`, not by the migrations.
+CREATE TABLE IF NOT EXISTS server_rev_table (
+   id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+   object_id TEXT NOT NULL DEFAULT '',
+   base_rev_id BIGINT NOT NULL DEFAULT 0,
+   rev_id BIGINT NOT NULL DEFAULT 0,
+   data BLOB NOT NULL DEFAULT (x''),
+   md5 TEXT NOT NULL DEFAULT '',
+   UNIQUE(object_id, rev_id)
+);
@@ -1,7 +1,7 @@
 use crate::kv::schema::{kv_table, kv_table::dsl, KV_SQL};
 use crate::sqlite::{DBConnection, Database, PoolConfig};
 use ::diesel::{query_dsl::*, ExpressionMethods};
-use diesel::{Connection, SqliteConnection};
+use diesel::{connection::SimpleConnection, Connection, SqliteConnection};
 use lazy_static::lazy_static;
 use std::{path::Path, sync::RwLock};
 
@@ -80,6 +80,16 @@ impl KV {
     Ok(())
   }
 
+  /// Rebuilds the database and truncates its write-ahead log, so the overwritten values don't stay
+  /// in the freed pages or the log.
+  pub fn vacuum() -> Result<(), String> {
+    let conn = get_connection()?;
+    conn
+      .batch_execute("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")
+      .map_err(|e| format!("KV vacuum error: {:?}", e))?;
+    Ok(())
+  }
+
   #[tracing::instrument(level = "trace", err)]
   pub fn init(root: &str) -> Result<(), String> {
     if !Path::new(root).exists() {
@@ -1,14 +1,17 @@
+use diesel::connection::SimpleConnection;
 pub use diesel::*;
 pub use diesel_derives::*;
 use diesel_migrations::*;
 use std::{fmt::Debug, io, path::Path};
 pub mod kv;
+pub mod search;
 mod sqlite;
 
 use crate::sqlite::PoolConfig;
 pub use crate::sqlite::{ConnectionPool, DBConnection, Database};
 
 pub mod schema;
+pub mod server_schema;
 
 #[macro_use]
 pub mod macros;
@@ -42,6 +45,23 @@ pub fn init(storage_path: &str) -> Result<Database, io::Error> {
   Ok(database)
 }
 
+/// The sql that creates the tables of the local server. It is kept out of the migrations, so the
+/// tables are only created in the database of the local server and the migrations of the app are
+/// not run in it.
+const SERVER_SQL: &str = include_str!("../server_sql/up.sql");
+pub const SERVER_DB_NAME: &str = "flowy-server.db";
+
+pub fn init_server(storage_path: &str) -> Result<Database, io::Error> {
+  if !Path::new(storage_path).exists() {
+    std::fs::create_dir_all(storage_path)?;
+  }
+  let pool_config = PoolConfig::default();
+  let database = Database::new(storage_path, SERVER_DB_NAME, pool_config).map_err(as_io_error)?;
+  let conn = database.get_connection().map_err(as_io_error)?;
+  conn.batch_execute(SERVER_SQL).map_err(as_io_error)?;
+  Ok(database)
+}
+
 fn as_io_error<E>(e: E) -> io::Error
 where
   E: Into<crate::sqlite::Error> + Debug,
@@ -127,6 +127,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    search_index_table (rowid) {
+        rowid -> BigInt,
+        view_id -> Text,
+        object_type -> Integer,
+        object_id -> Text,
+        field_id -> Text,
+        content -> Text,
+    }
+}
+
 diesel::table! {
     trash_table (id) {
         id -> Text,
@@ -191,6 +202,7 @@ diesel::allow_tables_to_appear_in_same_query!(
   kv_table,
   rev_snapshot,
   rev_table,
+  search_index_table,
   trash_table,
   user_table,
   view_table,
@@ -0,0 +1,5 @@
+#![allow(clippy::module_inception)]
+
+mod search;
+
+pub use search::*;
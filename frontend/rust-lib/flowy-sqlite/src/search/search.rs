@@ -0,0 +1,281 @@
+use crate::schema::{search_index_table, search_index_table::dsl};
+use crate::Error;
+use diesel::sql_types::{Double, Integer, Text};
+use diesel::{query_dsl::*, Connection, ExpressionMethods, SqliteConnection};
+
+/// The matched terms in the snippet of the [SearchHitRecord] are wrapped in these marks.
+pub const SEARCH_HIGHLIGHT_START: &str = "<b>";
+pub const SEARCH_HIGHLIGHT_END: &str = "</b>";
+const SNIPPET_ELLIPSIS: &str = "...";
+const SNIPPET_MAX_TOKENS: i32 = 16;
+
+/// The column index of the `content` in the `search_index_table`.
+const CONTENT_COLUMN: i32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchObjectType {
+  App = 0,
+  View = 1,
+  DocumentBlock = 2,
+  DatabaseCell = 3,
+}
+
+impl std::convert::From<i32> for SearchObjectType {
+  fn from(value: i32) -> Self {
+    match value {
+      0 => SearchObjectType::App,
+      2 => SearchObjectType::DocumentBlock,
+      3 => SearchObjectType::DatabaseCell,
+      _ => SearchObjectType::View,
+    }
+  }
+}
+
+/// The text of an object that can be searched.
+///
+/// The `view_id` is the id of the view that the object belongs to, or the id of the app. The
+/// `object_id` is the view id for the names of the apps and views, the path of the block for the
+/// documents, or the row id for the databases. The `field_id` is only used by the database cells.
+#[derive(PartialEq, Clone, Debug, Insertable)]
+#[table_name = "search_index_table"]
+pub struct SearchIndexRecord {
+  pub view_id: String,
+  pub object_type: i32,
+  pub object_id: String,
+  pub field_id: String,
+  pub content: String,
+}
+
+impl SearchIndexRecord {
+  pub fn app(app_id: &str, name: &str) -> Self {
+    Self::new(app_id, SearchObjectType::App, app_id, "", name)
+  }
+
+  pub fn view(view_id: &str, name: &str) -> Self {
+    Self::new(view_id, SearchObjectType::View, view_id, "", name)
+  }
+
+  pub fn document_block(view_id: &str, path: &str, text: &str) -> Self {
+    Self::new(view_id, SearchObjectType::DocumentBlock, path, "", text)
+  }
+
+  pub fn database_cell(view_id: &str, row_id: &str, field_id: &str, text: &str) -> Self {
+    Self::new(
+      view_id,
+      SearchObjectType::DatabaseCell,
+      row_id,
+      field_id,
+      text,
+    )
+  }
+
+  fn new(
+    view_id: &str,
+    object_type: SearchObjectType,
+    object_id: &str,
+    field_id: &str,
+    content: &str,
+  ) -> Self {
+    Self {
+      view_id: view_id.to_owned(),
+      object_type: object_type as i32,
+      object_id: object_id.to_owned(),
+      field_id: field_id.to_owned(),
+      content: content.to_owned(),
+    }
+  }
+}
+
+#[derive(Clone, Debug, QueryableByName)]
+pub struct SearchHitRecord {
+  #[sql_type = "Text"]
+  pub view_id: String,
+
+  #[sql_type = "Integer"]
+  pub object_type: i32,
+
+  #[sql_type = "Text"]
+  pub object_id: String,
+
+  #[sql_type = "Text"]
+  pub field_id: String,
+
+  /// The part of the content around the matched terms, the terms are wrapped in the
+  /// [SEARCH_HIGHLIGHT_START] and [SEARCH_HIGHLIGHT_END].
+  #[sql_type = "Text"]
+  pub snippet: String,
+
+  /// The bm25 score of the hit. The better match has the lower rank.
+  #[sql_type = "Double"]
+  pub rank: f64,
+}
+
+pub struct SearchIndexSql();
+impl SearchIndexSql {
+  /// Replaces the indexed text of the object. The object is removed from the index if the text is
+  /// empty. The unindexed columns are filtered by scanning the table, so prefer the bulk
+  /// replacements when updating many objects.
+  pub fn upsert(record: SearchIndexRecord, conn: &SqliteConnection) -> Result<(), Error> {
+    conn.transaction::<_, Error, _>(|| {
+      Self::delete_object(&record.view_id, &record.object_id, &record.field_id, conn)?;
+      Self::insert(vec![record], conn)
+    })
+  }
+
+  /// Replaces all the indexed objects of the view with the given type.
+  pub fn replace_objects(
+    view_id: &str,
+    object_type: SearchObjectType,
+    records: Vec<SearchIndexRecord>,
+    conn: &SqliteConnection,
+  ) -> Result<(), Error> {
+    conn.transaction::<_, Error, _>(|| {
+      let filter = dsl::search_index_table
+        .filter(search_index_table::view_id.eq(view_id))
+        .filter(search_index_table::object_type.eq(object_type as i32));
+      diesel::delete(filter).execute(conn)?;
+      Self::insert(records, conn)
+    })
+  }
+
+  /// Replaces all the indexed fields of the object with the given records.
+  pub fn replace_object(
+    view_id: &str,
+    object_id: &str,
+    records: Vec<SearchIndexRecord>,
+    conn: &SqliteConnection,
+  ) -> Result<(), Error> {
+    conn.transaction::<_, Error, _>(|| {
+      Self::delete_object(view_id, object_id, "", conn)?;
+      Self::insert(records, conn)
+    })
+  }
+
+  /// Replaces all the indexed cells of the field with the given records.
+  pub fn replace_field(
+    view_id: &str,
+    field_id: &str,
+    records: Vec<SearchIndexRecord>,
+    conn: &SqliteConnection,
+  ) -> Result<(), Error> {
+    conn.transaction::<_, Error, _>(|| {
+      Self::delete_field(view_id, field_id, conn)?;
+      Self::insert(records, conn)
+    })
+  }
+
+  /// Inserts the records in one statement. The records with empty text are skipped.
+  fn insert(records: Vec<SearchIndexRecord>, conn: &SqliteConnection) -> Result<(), Error> {
+    let records = records
+      .into_iter()
+      .filter(|record| !record.content.trim().is_empty())
+      .collect::<Vec<_>>();
+    if !records.is_empty() {
+      diesel::insert_into(search_index_table::table)
+        .values(&records)
+        .execute(conn)?;
+    }
+    Ok(())
+  }
+
+  pub fn has_objects(
+    view_id: &str,
+    object_type: SearchObjectType,
+    conn: &SqliteConnection,
+  ) -> Result<bool, Error> {
+    let count = dsl::search_index_table
+      .filter(search_index_table::view_id.eq(view_id))
+      .filter(search_index_table::object_type.eq(object_type as i32))
+      .count()
+      .get_result::<i64>(conn)?;
+    Ok(count > 0)
+  }
+
+  /// Removes the object from the index. If the `field_id` is empty, all the fields of the object
+  /// are removed.
+  pub fn delete_object(
+    view_id: &str,
+    object_id: &str,
+    field_id: &str,
+    conn: &SqliteConnection,
+  ) -> Result<(), Error> {
+    let filter = dsl::search_index_table
+      .filter(search_index_table::view_id.eq(view_id))
+      .filter(search_index_table::object_id.eq(object_id));
+    if field_id.is_empty() {
+      diesel::delete(filter).execute(conn)?;
+    } else {
+      diesel::delete(filter.filter(search_index_table::field_id.eq(field_id))).execute(conn)?;
+    }
+    Ok(())
+  }
+
+  /// Removes the cells of the field from the index.
+  pub fn delete_field(view_id: &str, field_id: &str, conn: &SqliteConnection) -> Result<(), Error> {
+    let filter = dsl::search_index_table
+      .filter(search_index_table::view_id.eq(view_id))
+      .filter(search_index_table::field_id.eq(field_id));
+    diesel::delete(filter).execute(conn)?;
+    Ok(())
+  }
+
+  /// Removes the view and all of its objects from the index.
+  pub fn delete_view(view_id: &str, conn: &SqliteConnection) -> Result<(), Error> {
+    let filter = dsl::search_index_table.filter(search_index_table::view_id.eq(view_id));
+    diesel::delete(filter).execute(conn)?;
+    Ok(())
+  }
+
+  /// Returns the objects that match all the terms of the query, the best match comes first.
+  pub fn search(query: &str, conn: &SqliteConnection) -> Result<Vec<SearchHitRecord>, Error> {
+    let query = match make_match_query(query) {
+      None => return Ok(vec![]),
+      Some(query) => query,
+    };
+
+    let sql = "SELECT view_id, object_type, object_id, field_id, \
+               snippet(search_index_table, ?, ?, ?, ?, ?) AS snippet, rank \
+               FROM search_index_table WHERE search_index_table MATCH ? ORDER BY rank";
+    let hits = diesel::sql_query(sql)
+      .bind::<Integer, _>(CONTENT_COLUMN)
+      .bind::<Text, _>(SEARCH_HIGHLIGHT_START)
+      .bind::<Text, _>(SEARCH_HIGHLIGHT_END)
+      .bind::<Text, _>(SNIPPET_ELLIPSIS)
+      .bind::<Integer, _>(SNIPPET_MAX_TOKENS)
+      .bind::<Text, _>(query)
+      .load::<SearchHitRecord>(conn)?;
+    Ok(hits)
+  }
+}
+
+/// Builds the FTS5 query from the user input. Each term is quoted, so the FTS5 operators in the
+/// input are matched as text, and is matched as a prefix, so the hits show up while typing.
+fn make_match_query(input: &str) -> Option<String> {
+  let terms = input
+    .split_whitespace()
+    .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+    .collect::<Vec<String>>();
+  if terms.is_empty() {
+    None
+  } else {
+    Some(terms.join(" "))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::make_match_query;
+
+  #[test]
+  fn search_make_match_query_test() {
+    assert_eq!(make_match_query("  "), None);
+    assert_eq!(
+      make_match_query("hello wor"),
+      Some("\"hello\"* \"wor\"*".to_owned())
+    );
+    assert_eq!(
+      make_match_query("a\"b OR"),
+      Some("\"a\"\"b\"* \"OR\"*".to_owned())
+    );
+  }
+}
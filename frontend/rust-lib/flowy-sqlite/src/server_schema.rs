@@ -0,0 +1,12 @@
+// The tables of the local server database. They are kept out of `schema.rs`, so they are not
+// created in the database of the user.
+diesel::table! {
+    server_rev_table (id) {
+        id -> Integer,
+        object_id -> Text,
+        base_rev_id -> BigInt,
+        rev_id -> BigInt,
+        data -> Binary,
+        md5 -> Text,
+    }
+}
@@ -39,6 +39,19 @@ impl FlowySDKTest {
     let config = AppFlowyCoreConfig::new(&root_dir(), nanoid!(6), server_config)
       .with_document_version(document_version)
       .log_filter("info", vec![]);
+    Self::from_config(config)
+  }
+
+  /// Creates a new sdk that derives the key encrypting the user secrets from the `passphrase`.
+  pub fn with_secret_passphrase(passphrase: &str) -> Self {
+    let server_config = get_client_server_configuration().unwrap();
+    let config = AppFlowyCoreConfig::new(&root_dir(), nanoid!(6), server_config)
+      .with_secret_passphrase(passphrase)
+      .log_filter("info", vec![]);
+    Self::from_config(config)
+  }
+
+  fn from_config(config: AppFlowyCoreConfig) -> Self {
     let sdk = std::thread::spawn(|| AppFlowyCore::new(config))
       .join()
       .unwrap();
@@ -46,6 +59,24 @@ impl FlowySDKTest {
     Self { inner: sdk }
   }
 
+  /// Creates a new sdk that connects to the local server of `other`, so the two sdks sync their
+  /// revisions with each other.
+  pub fn connect_to(other: &FlowySDKTest) -> Self {
+    let local_server = other
+      .local_server
+      .clone()
+      .expect("The sdk should run with the local server");
+    let server_config = get_client_server_configuration().unwrap();
+    let config = AppFlowyCoreConfig::new(&root_dir(), nanoid!(6), server_config)
+      .with_document_version(other.document_version())
+      .log_filter("info", vec![]);
+    let sdk = std::thread::spawn(|| AppFlowyCore::connect_to(config, local_server))
+      .join()
+      .unwrap();
+    std::mem::forget(sdk.dispatcher());
+    Self { inner: sdk }
+  }
+
   pub async fn sign_up(&self) -> SignUpContext {
     async_sign_up(self.inner.dispatcher()).await
   }
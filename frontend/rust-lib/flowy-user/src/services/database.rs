@@ -1,4 +1,5 @@
-use flowy_error::{ErrorCode, FlowyError};
+use crate::services::SecretStore;
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 use flowy_sqlite::ConnectionPool;
 use flowy_sqlite::{schema::user_table, DBConnection, Database};
 use lazy_static::lazy_static;
@@ -104,6 +105,26 @@ impl UserTable {
     self.workspace = workspace;
     self
   }
+
+  /// The token and the openai key are encrypted before they are stored in the database.
+  pub(crate) fn encrypt_secrets(mut self, secret_store: &SecretStore) -> FlowyResult<Self> {
+    self.token = secret_store.encrypt(&self.token)?;
+    self.openai_key = secret_store.encrypt(&self.openai_key)?;
+    Ok(self)
+  }
+
+  pub(crate) fn decrypt_secrets(mut self, secret_store: &SecretStore) -> FlowyResult<Self> {
+    self.token = secret_store.decrypt(&self.token)?;
+    self.openai_key = secret_store.decrypt(&self.openai_key)?;
+    Ok(self)
+  }
+
+  /// Returns true if the token or the openai key was stored in plain text by the older versions.
+  pub(crate) fn has_plain_secrets(&self) -> bool {
+    [&self.token, &self.openai_key]
+      .iter()
+      .any(|value| !value.is_empty() && !SecretStore::is_encrypted(value))
+  }
 }
 
 impl std::convert::From<SignUpResponse> for UserTable {
@@ -153,4 +174,12 @@ impl UserTableChangeset {
       openai_key: params.openai_key,
     }
   }
+
+  pub(crate) fn encrypt_secrets(mut self, secret_store: &SecretStore) -> FlowyResult<Self> {
+    self.openai_key = self
+      .openai_key
+      .map(|openai_key| secret_store.encrypt(&openai_key))
+      .transpose()?;
+    Ok(self)
+  }
 }
@@ -1,3 +1,5 @@
 pub mod database;
+mod secret_store;
 mod user_session;
+pub use secret_store::*;
 pub use user_session::*;
@@ -0,0 +1,196 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use flowy_error::{FlowyError, FlowyResult};
+use rand::RngCore;
+use sha2::Sha256;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// The prefix of the encrypted values. The values without it were stored in plain text by the
+/// older versions, they are returned as they are when decrypting.
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const SALT_LEN: usize = 16;
+const PBKDF2_ROUNDS: u32 = 100_000;
+
+/// Where the key of the [SecretStore] comes from.
+#[derive(Clone, Debug)]
+pub enum SecretKeySource {
+  /// The key is read from the file. A random key is written to the file if it doesn't exist.
+  KeyFile(PathBuf),
+
+  /// The key is derived from the passphrase. The salt is read from the `salt_path`, a random
+  /// salt is written to it if it doesn't exist.
+  Passphrase {
+    passphrase: String,
+    salt_path: PathBuf,
+  },
+}
+
+/// Encrypts the secrets, such as the token and the openai key, before they are stored in the
+/// database, and decrypts them after they are read.
+pub struct SecretStore {
+  cipher: Aes256Gcm,
+}
+
+impl SecretStore {
+  pub fn new(source: &SecretKeySource) -> FlowyResult<Self> {
+    let key = match source {
+      SecretKeySource::KeyFile(path) => read_or_create_random_file(path, KEY_LEN)?,
+      SecretKeySource::Passphrase {
+        passphrase,
+        salt_path,
+      } => {
+        let salt = read_or_create_random_file(salt_path, SALT_LEN)?;
+        let mut key = vec![0; KEY_LEN];
+        pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), &salt, PBKDF2_ROUNDS, &mut key);
+        key
+      },
+    };
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| FlowyError::internal().context(e))?;
+    Ok(Self { cipher })
+  }
+
+  pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+  }
+
+  /// Returns the encrypted value. The empty value is kept empty, and the value that is already
+  /// encrypted is returned as it is.
+  pub fn encrypt(&self, value: &str) -> FlowyResult<String> {
+    if value.is_empty() || Self::is_encrypted(value) {
+      return Ok(value.to_owned());
+    }
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = self
+      .cipher
+      .encrypt(Nonce::from_slice(&nonce), value.as_bytes())
+      .map_err(|e| FlowyError::internal().context(e))?;
+
+    let mut data = nonce.to_vec();
+    data.extend(ciphertext);
+    Ok(format!("{}{}", ENCRYPTED_PREFIX, base64::encode(data)))
+  }
+
+  /// Returns the decrypted value. The value that is not encrypted is returned as it is.
+  pub fn decrypt(&self, value: &str) -> FlowyResult<String> {
+    let encoded = match value.strip_prefix(ENCRYPTED_PREFIX) {
+      None => return Ok(value.to_owned()),
+      Some(encoded) => encoded,
+    };
+
+    let data =
+      base64::decode(encoded).map_err(|e| FlowyError::decrypt_secret_failed().context(e))?;
+    if data.len() < NONCE_LEN {
+      return Err(FlowyError::decrypt_secret_failed().context("The secret is too short"));
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let plaintext = self
+      .cipher
+      .decrypt(Nonce::from_slice(nonce), ciphertext)
+      .map_err(|e| FlowyError::decrypt_secret_failed().context(e))?;
+    String::from_utf8(plaintext).map_err(|e| FlowyError::decrypt_secret_failed().context(e))
+  }
+}
+
+/// Reads the file that contains `len` bytes. If the file doesn't exist, it's created with random
+/// bytes. The file is written to a temporary file first and then linked to the `path`, so the
+/// stores that are created at the same time end up with the same content.
+fn read_or_create_random_file(path: &Path, len: usize) -> FlowyResult<Vec<u8>> {
+  if !path.exists() {
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    let mut bytes = vec![0; len];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let temp_path = path.with_extension(format!("{}.tmp", rand::thread_rng().next_u32()));
+    fs::write(&temp_path, &bytes)?;
+    set_owner_only_permissions(&temp_path)?;
+    let result = fs::hard_link(&temp_path, path);
+    fs::remove_file(&temp_path)?;
+    match result {
+      Ok(_) => return Ok(bytes),
+      Err(e) if e.kind() == ErrorKind::AlreadyExists => {},
+      Err(e) => return Err(e.into()),
+    }
+  }
+
+  let bytes = fs::read(path)?;
+  if bytes.len() != len {
+    return Err(FlowyError::internal().context(format!("{} is corrupted", path.display())));
+  }
+  Ok(bytes)
+}
+
+#[cfg(unix)]
+fn set_owner_only_permissions(path: &Path) -> FlowyResult<()> {
+  use std::os::unix::fs::PermissionsExt;
+  fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+  Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_owner_only_permissions(_path: &Path) -> FlowyResult<()> {
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{SecretKeySource, SecretStore};
+  use std::path::PathBuf;
+
+  fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}-{}", name, nanoid::nanoid!(6)))
+  }
+
+  #[test]
+  fn secret_encrypt_then_decrypt_test() {
+    let path = temp_path("secret_key");
+    let store = SecretStore::new(&SecretKeySource::KeyFile(path.clone())).unwrap();
+    let encrypted = store.encrypt("sk-123456").unwrap();
+    assert!(SecretStore::is_encrypted(&encrypted));
+    assert!(!encrypted.contains("sk-123456"));
+    assert_eq!(store.decrypt(&encrypted).unwrap(), "sk-123456");
+
+    // The key is read from the file when the store is created again.
+    let store = SecretStore::new(&SecretKeySource::KeyFile(path.clone())).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(store.decrypt(&encrypted).unwrap(), "sk-123456");
+  }
+
+  #[test]
+  fn secret_plain_text_test() {
+    let path = temp_path("secret_key");
+    let store = SecretStore::new(&SecretKeySource::KeyFile(path.clone())).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(store.encrypt("").unwrap(), "");
+    assert_eq!(store.decrypt("sk-123456").unwrap(), "sk-123456");
+
+    let encrypted = store.encrypt("sk-123456").unwrap();
+    assert_eq!(store.encrypt(&encrypted).unwrap(), encrypted);
+  }
+
+  #[test]
+  fn secret_passphrase_test() {
+    let salt_path = temp_path("secret_salt");
+    let source = |passphrase: &str| SecretKeySource::Passphrase {
+      passphrase: passphrase.to_owned(),
+      salt_path: salt_path.clone(),
+    };
+    let store = SecretStore::new(&source("hello world")).unwrap();
+    let encrypted = store.encrypt("sk-123456").unwrap();
+
+    let store = SecretStore::new(&source("hello world")).unwrap();
+    assert_eq!(store.decrypt(&encrypted).unwrap(), "sk-123456");
+
+    let store = SecretStore::new(&source("wrong passphrase")).unwrap();
+    std::fs::remove_file(&salt_path).unwrap();
+    assert!(store.decrypt(&encrypted).is_err());
+  }
+}
@@ -5,15 +5,20 @@ use crate::{
   event_map::UserCloudService,
   notification::*,
   services::database::{UserDB, UserTable, UserTableChangeset},
+  services::{SecretKeySource, SecretStore},
 };
+use flowy_error::FlowyResult;
 use flowy_sqlite::ConnectionPool;
 use flowy_sqlite::{
+  connection::SimpleConnection,
   kv::KV,
   query_dsl::*,
   schema::{user_table, user_table::dsl},
   DBConnection, ExpressionMethods, UserDatabaseConnection,
 };
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use user_model::{
@@ -25,6 +30,10 @@ pub struct UserSessionConfig {
 
   /// Used as the key of `Session` when saving session information to KV.
   session_cache_key: String,
+
+  /// The key that encrypts the secrets in the user databases and the session. It's kept in the
+  /// keyfile under the `root_dir` by default.
+  secret_key_source: SecretKeySource,
 }
 
 impl UserSessionConfig {
@@ -32,18 +41,34 @@ impl UserSessionConfig {
   /// users.
   pub fn new(name: &str, root_dir: &str) -> Self {
     let session_cache_key = format!("{}_session_cache", name);
+    let secret_key_source = SecretKeySource::KeyFile(Path::new(root_dir).join(SECRET_KEY_FILE));
     Self {
       root_dir: root_dir.to_owned(),
       session_cache_key,
+      secret_key_source,
     }
   }
+
+  /// Derives the key that encrypts the secrets from the `passphrase` instead of keeping it in the
+  /// keyfile. The secrets that were encrypted with another key can't be decrypted.
+  pub fn with_passphrase(mut self, passphrase: &str) -> Self {
+    self.secret_key_source = SecretKeySource::Passphrase {
+      passphrase: passphrase.to_owned(),
+      salt_path: Path::new(&self.root_dir).join(SECRET_SALT_FILE),
+    };
+    self
+  }
 }
 
+const SECRET_KEY_FILE: &str = "secret.key";
+const SECRET_SALT_FILE: &str = "secret.salt";
+
 pub struct UserSession {
   database: UserDB,
   config: UserSessionConfig,
   cloud_service: Arc<dyn UserCloudService>,
   user_status_callback: RwLock<Option<Arc<dyn UserStatusCallback>>>,
+  secret_store: OnceCell<SecretStore>,
 }
 
 impl UserSession {
@@ -55,10 +80,15 @@ impl UserSession {
       config,
       cloud_service,
       user_status_callback,
+      secret_store: OnceCell::new(),
     }
   }
 
   pub async fn init<C: UserStatusCallback + 'static>(&self, user_status_callback: C) {
+    if let Err(e) = self.encrypt_plain_secrets() {
+      tracing::error!("Encrypt the user secrets failed: {:?}", e);
+    }
+
     if let Ok(session) = self.get_session() {
       let _ = user_status_callback
         .did_sign_in(&session.token, &session.user_id)
@@ -163,7 +193,8 @@ impl UserSession {
     params: UpdateUserProfileParams,
   ) -> Result<(), FlowyError> {
     let session = self.get_session()?;
-    let changeset = UserTableChangeset::new(params.clone());
+    let changeset =
+      UserTableChangeset::new(params.clone()).encrypt_secrets(self.secret_store()?)?;
     diesel_update_table!(user_table, changeset, &*self.db_connection()?);
 
     let user_profile = self.get_user_profile().await?;
@@ -187,7 +218,7 @@ impl UserSession {
       .first::<UserTable>(&*(self.db_connection()?))?;
 
     self.read_user_profile_on_server(&token)?;
-    Ok(user.into())
+    Ok(user.decrypt_secrets(self.secret_store()?)?.into())
   }
 
   pub async fn get_user_profile(&self) -> Result<UserProfile, FlowyError> {
@@ -197,7 +228,51 @@ impl UserSession {
       .first::<UserTable>(&*(self.db_connection()?))?;
 
     self.read_user_profile_on_server(&token)?;
-    Ok(user.into())
+    Ok(user.decrypt_secrets(self.secret_store()?)?.into())
+  }
+
+  /// Encrypts the token and the openai key that were stored in plain text by the older versions,
+  /// both in the database of the current user and in the session.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub fn encrypt_plain_secrets(&self) -> Result<(), FlowyError> {
+    let session = match KV::get_str(&self.config.session_cache_key) {
+      None => return Ok(()),
+      Some(s) => Session::from(s),
+    };
+    if !session.token.is_empty() && !SecretStore::is_encrypted(&session.token) {
+      self.set_session(Some(session))?;
+      // The plain token is still in the freed pages and the write-ahead log of the kv database.
+      KV::vacuum().map_err(|e| FlowyError::new(ErrorCode::Internal, &e))?;
+    }
+
+    let secret_store = self.secret_store()?;
+    let conn = self.db_connection()?;
+    let users = dsl::user_table
+      .load::<UserTable>(&*conn)?
+      .into_iter()
+      .filter(|user| user.has_plain_secrets())
+      .collect::<Vec<UserTable>>();
+    if users.is_empty() {
+      return Ok(());
+    }
+
+    conn.immediate_transaction::<_, FlowyError, _>(|| {
+      for user in users {
+        let user = user.encrypt_secrets(secret_store)?;
+        let _ = diesel::update(dsl::user_table.filter(dsl::id.eq(&user.id)))
+          .set((
+            dsl::token.eq(&user.token),
+            dsl::openai_key.eq(&user.openai_key),
+          ))
+          .execute(&*conn)?;
+      }
+      Ok(())
+    })?;
+
+    // The plain text is still in the freed pages and the write-ahead log after updating, so the
+    // database is rebuilt and the log is truncated.
+    conn.batch_execute("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")?;
+    Ok(())
   }
 
   pub fn user_dir(&self) -> Result<String, FlowyError> {
@@ -226,6 +301,12 @@ impl UserSession {
 }
 
 impl UserSession {
+  fn secret_store(&self) -> FlowyResult<&SecretStore> {
+    self
+      .secret_store
+      .get_or_try_init(|| SecretStore::new(&self.config.secret_key_source))
+  }
+
   fn read_user_profile_on_server(&self, _token: &str) -> Result<(), FlowyError> {
     Ok(())
   }
@@ -266,7 +347,7 @@ impl UserSession {
   async fn save_user(&self, user: UserTable) -> Result<UserTable, FlowyError> {
     let conn = self.db_connection()?;
     let _ = diesel::insert_into(user_table::table)
-      .values(user.clone())
+      .values(user.clone().encrypt_secrets(self.secret_store()?)?)
       .execute(&*conn)?;
     Ok(user)
   }
@@ -276,7 +357,11 @@ impl UserSession {
     match &session {
       None => KV::remove(&self.config.session_cache_key)
         .map_err(|e| FlowyError::new(ErrorCode::Internal, &e))?,
-      Some(session) => KV::set_str(&self.config.session_cache_key, session.clone().into()),
+      Some(session) => {
+        let mut session = session.clone();
+        session.token = self.secret_store()?.encrypt(&session.token)?;
+        KV::set_str(&self.config.session_cache_key, session.into())
+      },
     }
     Ok(())
   }
@@ -284,7 +369,11 @@ impl UserSession {
   fn get_session(&self) -> Result<Session, FlowyError> {
     match KV::get_str(&self.config.session_cache_key) {
       None => Err(FlowyError::unauthorized()),
-      Some(s) => Ok(Session::from(s)),
+      Some(s) => {
+        let mut session = Session::from(s);
+        session.token = self.secret_store()?.decrypt(&session.token)?;
+        Ok(session)
+      },
     }
   }
 
@@ -299,9 +388,10 @@ impl UserSession {
 pub async fn update_user(
   _cloud_service: Arc<dyn UserCloudService>,
   pool: Arc<ConnectionPool>,
+  secret_store: &SecretStore,
   params: UpdateUserProfileParams,
 ) -> Result<(), FlowyError> {
-  let changeset = UserTableChangeset::new(params);
+  let changeset = UserTableChangeset::new(params).encrypt_secrets(secret_store)?;
   let conn = pool.get()?;
   diesel_update_table!(user_table, changeset, &*conn);
   Ok(())
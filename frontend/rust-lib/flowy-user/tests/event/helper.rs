@@ -1,7 +1,9 @@
+use flowy_test::FlowySDKTest;
 pub use flowy_test::{
   event_builder::*,
   prelude::{login_password, random_email},
 };
+use std::path::PathBuf;
 
 pub(crate) fn invalid_email_test_case() -> Vec<String> {
   // https://gist.github.com/cjaoude/fd9910626629b53c4d25
@@ -40,3 +42,22 @@ pub(crate) fn invalid_password_test_case() -> Vec<String> {
 pub(crate) fn valid_name() -> String {
   "AppFlowy".to_string()
 }
+
+/// Returns true if one of the files in the user folder or in the root folder, such as the
+/// databases, the kv database and their write-ahead logs, contains the `secret` in plain text.
+pub(crate) fn user_files_contain(sdk: &FlowySDKTest, secret: &str) -> bool {
+  let user_dir = PathBuf::from(sdk.user_session.user_dir().unwrap());
+  let root_dir = user_dir.parent().unwrap().to_path_buf();
+  [user_dir, root_dir].iter().any(|dir| {
+    std::fs::read_dir(dir)
+      .unwrap()
+      .flatten()
+      .filter(|entry| entry.path().is_file())
+      .any(|entry| {
+        let bytes = std::fs::read(entry.path()).unwrap();
+        bytes
+          .windows(secret.len())
+          .any(|window| window == secret.as_bytes())
+      })
+  })
+}
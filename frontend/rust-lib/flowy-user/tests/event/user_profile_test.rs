@@ -1,4 +1,5 @@
 use crate::helper::*;
+use flowy_sqlite::{kv::KV, query_dsl::*, schema::user_table::dsl, ExpressionMethods};
 use flowy_test::{event_builder::UserModuleEventBuilder, FlowySDKTest};
 use flowy_user::entities::{UpdateUserProfilePayloadPB, UserProfilePB};
 use flowy_user::{errors::ErrorCode, event_map::UserEvent::*};
@@ -67,6 +68,73 @@ async fn user_update_with_email() {
   assert_eq!(user_profile.email, new_email,);
 }
 
+#[tokio::test]
+async fn user_update_with_openai_key() {
+  let sdk = FlowySDKTest::default();
+  let user = sdk.init_user().await;
+  let openai_key = format!("sk-{}", nanoid!(20));
+  let request = UpdateUserProfilePayloadPB::new(&user.id).openai_key(&openai_key);
+  let _ = UserModuleEventBuilder::new(sdk.clone())
+    .event(UpdateUserProfile)
+    .payload(request)
+    .sync_send()
+    .assert_success();
+  let user_profile = UserModuleEventBuilder::new(sdk.clone())
+    .event(GetUserProfile)
+    .sync_send()
+    .parse::<UserProfilePB>();
+
+  assert_eq!(user_profile.openai_key, openai_key);
+  assert_eq!(user_profile.token, user.token);
+  assert!(!user_files_contain(&sdk, &openai_key));
+  assert!(!user_files_contain(&sdk, &user.token));
+}
+
+#[tokio::test]
+async fn user_encrypt_plain_secrets() {
+  let sdk = FlowySDKTest::default();
+  let user = sdk.init_user().await;
+  let openai_key = format!("sk-{}", nanoid!(20));
+
+  // Store the secrets in plain text as the older versions did.
+  let conn = sdk.user_session.db_connection().unwrap();
+  diesel::update(dsl::user_table.filter(dsl::id.eq(&user.id)))
+    .set((dsl::token.eq(&user.token), dsl::openai_key.eq(&openai_key)))
+    .execute(&*conn)
+    .unwrap();
+  drop(conn);
+  let session_cache_key = format!("{}_session_cache", sdk.config.name());
+  let mut session: serde_json::Value =
+    serde_json::from_str(&KV::get_str(&session_cache_key).unwrap()).unwrap();
+  session["token"] = serde_json::Value::String(user.token.clone());
+  KV::set_str(&session_cache_key, session.to_string());
+  assert!(user_files_contain(&sdk, &openai_key));
+  assert!(user_files_contain(&sdk, &user.token));
+
+  sdk.user_session.encrypt_plain_secrets().unwrap();
+  assert!(!user_files_contain(&sdk, &openai_key));
+  assert!(!user_files_contain(&sdk, &user.token));
+
+  let user_profile = UserModuleEventBuilder::new(sdk.clone())
+    .event(GetUserProfile)
+    .sync_send()
+    .parse::<UserProfilePB>();
+  assert_eq!(user_profile.openai_key, openai_key);
+  assert_eq!(user_profile.token, user.token);
+}
+
+#[tokio::test]
+async fn user_sign_up_with_secret_passphrase() {
+  let sdk = FlowySDKTest::with_secret_passphrase("AppFlowy passphrase");
+  let user = sdk.init_user().await;
+  let user_profile = UserModuleEventBuilder::new(sdk.clone())
+    .event(GetUserProfile)
+    .sync_send()
+    .parse::<UserProfilePB>();
+  assert_eq!(user_profile.token, user.token);
+  assert!(!user_files_contain(&sdk, &user.token));
+}
+
 #[tokio::test]
 async fn user_update_with_password() {
   let sdk = FlowySDKTest::default();
@@ -3,6 +3,7 @@ use nanoid::nanoid;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn gen_row_id() -> String {
   nanoid!(6)
@@ -28,6 +29,10 @@ pub struct RowRevision {
   pub cells: IndexMap<FieldId, CellRevision>,
   pub height: i32,
   pub visibility: bool,
+  /// The timestamp when the row was created. It's zero for the rows that were created before
+  /// recording the creation time.
+  #[serde(default, skip_serializing_if = "is_zero")]
+  pub created_at: i64,
 }
 
 impl RowRevision {
@@ -38,9 +43,22 @@ impl RowRevision {
       cells: Default::default(),
       height: DEFAULT_ROW_HEIGHT,
       visibility: true,
+      created_at: row_timestamp(),
     }
   }
 }
+
+/// Returns the current timestamp in seconds.
+pub fn row_timestamp() -> i64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_secs() as i64)
+    .unwrap_or_default()
+}
+
+fn is_zero(value: &i64) -> bool {
+  *value == 0
+}
 #[derive(Debug, Clone, Default)]
 pub struct RowChangeset {
   pub row_id: String,
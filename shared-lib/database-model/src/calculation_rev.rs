@@ -0,0 +1,60 @@
+use crate::FieldTypeRevision;
+use serde::{Deserialize, Serialize};
+use serde_repr::*;
+
+/// The calculation of a field that is displayed at the bottom of the column. It's calculated
+/// over the rows that are visible after applying the view's filters.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, Hash)]
+pub struct CalculationRevision {
+  pub id: String,
+  pub field_id: String,
+  pub field_type: FieldTypeRevision,
+  pub calculation_type: CalculationType,
+}
+
+#[derive(Serialize_repr, Deserialize_repr, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[repr(u8)]
+pub enum CalculationType {
+  Count = 0,
+  CountEmpty = 1,
+  CountUnique = 2,
+  Sum = 3,
+  Average = 4,
+  Median = 5,
+  Min = 6,
+  Max = 7,
+  EarliestDate = 8,
+  LatestDate = 9,
+  PercentChecked = 10,
+}
+
+impl std::convert::From<u8> for CalculationType {
+  fn from(num: u8) -> Self {
+    match num {
+      0 => CalculationType::Count,
+      1 => CalculationType::CountEmpty,
+      2 => CalculationType::CountUnique,
+      3 => CalculationType::Sum,
+      4 => CalculationType::Average,
+      5 => CalculationType::Median,
+      6 => CalculationType::Min,
+      7 => CalculationType::Max,
+      8 => CalculationType::EarliestDate,
+      9 => CalculationType::LatestDate,
+      10 => CalculationType::PercentChecked,
+      _ => CalculationType::Count,
+    }
+  }
+}
+
+impl std::default::Default for CalculationType {
+  fn default() -> Self {
+    Self::Count
+  }
+}
+
+impl std::convert::From<CalculationType> for u8 {
+  fn from(calculation_type: CalculationType) -> Self {
+    calculation_type as u8
+  }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// The synced text of one of the database's objects. The database, its views and its blocks are
+/// synced separately, so the `object_id` is the id of the database, the view or the block.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+pub struct DatabaseInfo {
+  pub object_id: String,
+  pub text: String,
+  pub rev_id: i64,
+  pub base_rev_id: i64,
+}
@@ -1,5 +1,8 @@
-use crate::FieldTypeRevision;
+use crate::{gen_database_filter_id, Configuration, FieldTypeRevision};
 use serde::{Deserialize, Serialize};
+use serde_repr::*;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, Hash)]
 pub struct FilterRevision {
@@ -10,3 +13,355 @@ pub struct FilterRevision {
   #[serde(default)]
   pub content: String,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum FilterOperatorRevision {
+  And = 0,
+  Or = 1,
+}
+
+impl std::default::Default for FilterOperatorRevision {
+  fn default() -> Self {
+    FilterOperatorRevision::And
+  }
+}
+
+/// A group of filters that are combined by the operator. The group can contain nested groups,
+/// each nested group is evaluated as a single filter of its parent.
+///
+/// The root group of the [FilterConfiguration] has an empty id.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct FilterGroupRevision {
+  pub id: String,
+
+  #[serde(default)]
+  pub operator: FilterOperatorRevision,
+
+  /// The ids of the [FilterRevision]s that belong to this group.
+  #[serde(default)]
+  pub filter_ids: Vec<String>,
+
+  #[serde(default)]
+  pub groups: Vec<FilterGroupRevision>,
+}
+
+impl FilterGroupRevision {
+  pub fn new(operator: FilterOperatorRevision) -> Self {
+    Self {
+      id: gen_database_filter_id(),
+      operator,
+      filter_ids: vec![],
+      groups: vec![],
+    }
+  }
+
+  pub fn get_group(&self, group_id: &str) -> Option<&FilterGroupRevision> {
+    if self.id == group_id {
+      return Some(self);
+    }
+    self
+      .groups
+      .iter()
+      .find_map(|group| group.get_group(group_id))
+  }
+
+  pub fn get_mut_group(&mut self, group_id: &str) -> Option<&mut FilterGroupRevision> {
+    if self.id == group_id {
+      return Some(self);
+    }
+    self
+      .groups
+      .iter_mut()
+      .find_map(|group| group.get_mut_group(group_id))
+  }
+
+  /// Returns the ids of the filters in this group and its nested groups.
+  pub fn all_filter_ids(&self) -> Vec<String> {
+    let mut filter_ids = self.filter_ids.clone();
+    for group in self.groups.iter() {
+      filter_ids.extend(group.all_filter_ids());
+    }
+    filter_ids
+  }
+
+  fn remove_group(&mut self, group_id: &str) -> Option<FilterGroupRevision> {
+    match self.groups.iter().position(|group| group.id == group_id) {
+      Some(index) => Some(self.groups.remove(index)),
+      None => self
+        .groups
+        .iter_mut()
+        .find_map(|group| group.remove_group(group_id)),
+    }
+  }
+
+  fn retain_filters(&mut self, f: &impl Fn(&str) -> bool) {
+    self.filter_ids.retain(|filter_id| f(filter_id));
+    for group in self.groups.iter_mut() {
+      group.retain_filters(f);
+    }
+  }
+}
+
+/// The filters of the view. The filters are stored by field, and the [FilterGroupRevision] tree
+/// describes how they are combined. A filter that isn't in any nested group belongs to the root
+/// group.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "FilterConfigurationSerde", into = "FilterConfigurationSerde")]
+pub struct FilterConfiguration {
+  filters: Configuration<FilterRevision>,
+  root_group: FilterGroupRevision,
+}
+
+impl FilterConfiguration {
+  pub fn root_group(&self) -> &FilterGroupRevision {
+    &self.root_group
+  }
+
+  /// Returns the group with the id, or the root group if the group_id is None.
+  pub fn get_group(&self, group_id: Option<&str>) -> Option<&FilterGroupRevision> {
+    match group_id {
+      None => Some(&self.root_group),
+      Some(group_id) => self.root_group.get_group(group_id),
+    }
+  }
+
+  fn get_mut_group(&mut self, group_id: Option<&str>) -> Option<&mut FilterGroupRevision> {
+    match group_id {
+      None => Some(&mut self.root_group),
+      Some(group_id) => self.root_group.get_mut_group(group_id),
+    }
+  }
+
+  pub fn get_mut_object(
+    &mut self,
+    field_id: &str,
+    field_type: &FieldTypeRevision,
+    predicate: impl Fn(&Arc<FilterRevision>) -> bool,
+  ) -> Option<&mut Arc<FilterRevision>> {
+    self.filters.get_mut_object(field_id, field_type, predicate)
+  }
+
+  /// Adds the filter to the group. Returns false if the group doesn't exist.
+  pub fn add_filter(
+    &mut self,
+    group_id: Option<&str>,
+    field_id: &str,
+    filter_rev: FilterRevision,
+  ) -> bool {
+    match self.get_mut_group(group_id) {
+      None => return false,
+      Some(group) => group.filter_ids.push(filter_rev.id.clone()),
+    }
+    let field_type = filter_rev.field_type;
+    self.filters.add_object(field_id, &field_type, filter_rev);
+    true
+  }
+
+  /// Deletes the filter and removes it from the group it belongs to. Returns false if the filter
+  /// doesn't exist.
+  pub fn delete_filter(
+    &mut self,
+    field_id: &str,
+    field_type: &FieldTypeRevision,
+    filter_id: &str,
+  ) -> bool {
+    match self.filters.get_mut_objects(field_id, field_type) {
+      None => false,
+      Some(filters) => {
+        filters.retain(|filter| filter.id != filter_id);
+        self.root_group.retain_filters(&|id| id != filter_id);
+        true
+      },
+    }
+  }
+
+  /// Inserts the group into the parent group. Returns false if the parent doesn't exist.
+  pub fn insert_group(
+    &mut self,
+    parent_group_id: Option<&str>,
+    group: FilterGroupRevision,
+  ) -> bool {
+    match self.get_mut_group(parent_group_id) {
+      None => false,
+      Some(parent) => {
+        parent.groups.push(group);
+        true
+      },
+    }
+  }
+
+  /// Updates the operator of the group. Returns false if the group doesn't exist or the operator
+  /// is not changed.
+  pub fn update_group_operator(
+    &mut self,
+    group_id: Option<&str>,
+    operator: FilterOperatorRevision,
+  ) -> bool {
+    match self.get_mut_group(group_id) {
+      Some(group) if group.operator != operator => {
+        group.operator = operator;
+        true
+      },
+      _ => false,
+    }
+  }
+
+  /// Deletes the nested group and all the filters in it. Returns the deleted group.
+  pub fn delete_group(&mut self, group_id: &str) -> Option<FilterGroupRevision> {
+    let group = self.root_group.remove_group(group_id)?;
+    let filter_ids = group.all_filter_ids();
+    self
+      .filters
+      .retain_objects(|filter| !filter_ids.contains(&filter.id));
+    Some(group)
+  }
+
+  /// Replaces the field ids of the filters with the ids in the map.
+  pub fn remap_field_ids(&mut self, field_id_map: &HashMap<String, String>) {
+    self
+      .filters
+      .remap_field_ids(field_id_map, |filter, field_id| {
+        filter.field_id = field_id.to_owned();
+      });
+  }
+}
+
+impl std::ops::Deref for FilterConfiguration {
+  type Target = Configuration<FilterRevision>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.filters
+  }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum FilterConfigurationSerde {
+  /// The filters are saved as a flat list if they are all combined by [FilterOperatorRevision::And]
+  /// in the root group. It's the format before the filter groups were introduced, so the filters
+  /// saved by the old versions are migrated to the root group when they're deserialized.
+  Flat(Configuration<FilterRevision>),
+  Grouped {
+    filters: Configuration<FilterRevision>,
+    #[serde(default)]
+    root_group: FilterGroupRevision,
+  },
+}
+
+impl std::convert::From<FilterConfigurationSerde> for FilterConfiguration {
+  fn from(serde: FilterConfigurationSerde) -> Self {
+    let (filters, mut root_group) = match serde {
+      FilterConfigurationSerde::Flat(filters) => (filters, FilterGroupRevision::default()),
+      FilterConfigurationSerde::Grouped {
+        filters,
+        root_group,
+      } => (filters, root_group),
+    };
+
+    // Make sure each filter belongs to exactly one group.
+    let filter_ids = filters
+      .get_all_objects()
+      .iter()
+      .map(|filter| filter.id.clone())
+      .collect::<Vec<String>>();
+    root_group.retain_filters(&|filter_id| filter_ids.iter().any(|id| id == filter_id));
+    let grouped_filter_ids = root_group.all_filter_ids();
+    for filter_id in filter_ids {
+      if !grouped_filter_ids.contains(&filter_id) {
+        root_group.filter_ids.push(filter_id);
+      }
+    }
+    root_group.id = "".to_owned();
+    Self {
+      filters,
+      root_group,
+    }
+  }
+}
+
+impl std::convert::From<FilterConfiguration> for FilterConfigurationSerde {
+  fn from(configuration: FilterConfiguration) -> Self {
+    let FilterConfiguration {
+      filters,
+      root_group,
+    } = configuration;
+    if root_group.operator == FilterOperatorRevision::And && root_group.groups.is_empty() {
+      FilterConfigurationSerde::Flat(filters)
+    } else {
+      FilterConfigurationSerde::Grouped {
+        filters,
+        root_group,
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{FilterConfiguration, FilterGroupRevision, FilterOperatorRevision, FilterRevision};
+
+  fn make_filter(id: &str, field_id: &str) -> FilterRevision {
+    FilterRevision {
+      id: id.to_owned(),
+      field_id: field_id.to_owned(),
+      field_type: 0,
+      condition: 0,
+      content: "".to_owned(),
+    }
+  }
+
+  #[test]
+  fn filter_configuration_migrate_flat_filters_test() {
+    let mut configuration = FilterConfiguration::default();
+    configuration.add_filter(None, "f1", make_filter("a", "f1"));
+    configuration.add_filter(None, "f2", make_filter("b", "f2"));
+
+    // The filters that are all combined by And are saved in the old format.
+    let s = serde_json::to_string(&configuration).unwrap();
+    assert!(s.starts_with('['));
+
+    let configuration: FilterConfiguration = serde_json::from_str(&s).unwrap();
+    let root_group = configuration.root_group();
+    assert_eq!(root_group.operator, FilterOperatorRevision::And);
+    assert_eq!(root_group.filter_ids, vec!["a".to_owned(), "b".to_owned()]);
+  }
+
+  #[test]
+  fn filter_configuration_nested_group_serde_test() {
+    let mut configuration = FilterConfiguration::default();
+    configuration.add_filter(None, "f1", make_filter("a", "f1"));
+    let group = FilterGroupRevision::new(FilterOperatorRevision::Or);
+    let group_id = group.id.clone();
+    assert!(configuration.insert_group(None, group));
+    assert!(configuration.add_filter(Some(&group_id), "f2", make_filter("b", "f2")));
+    assert!(configuration.add_filter(Some(&group_id), "f3", make_filter("c", "f3")));
+    assert!(!configuration.add_filter(Some("unknown"), "f4", make_filter("d", "f4")));
+
+    let s = serde_json::to_string(&configuration).unwrap();
+    let configuration: FilterConfiguration = serde_json::from_str(&s).unwrap();
+    let root_group = configuration.root_group();
+    assert_eq!(root_group.filter_ids, vec!["a".to_owned()]);
+    let group = configuration.get_group(Some(&group_id)).unwrap();
+    assert_eq!(group.operator, FilterOperatorRevision::Or);
+    assert_eq!(group.filter_ids, vec!["b".to_owned(), "c".to_owned()]);
+  }
+
+  #[test]
+  fn filter_configuration_delete_group_test() {
+    let mut configuration = FilterConfiguration::default();
+    configuration.add_filter(None, "f1", make_filter("a", "f1"));
+    let group = FilterGroupRevision::new(FilterOperatorRevision::Or);
+    let group_id = group.id.clone();
+    configuration.insert_group(None, group);
+    configuration.add_filter(Some(&group_id), "f2", make_filter("b", "f2"));
+
+    let group = configuration.delete_group(&group_id).unwrap();
+    assert_eq!(group.filter_ids, vec!["b".to_owned()]);
+    assert_eq!(configuration.get_all_objects().len(), 1);
+    assert!(configuration.get_group(Some(&group_id)).is_none());
+
+    assert!(configuration.delete_filter("f1", &0, "a"));
+    assert!(configuration.root_group().filter_ids.is_empty());
+  }
+}
@@ -52,9 +52,35 @@ impl GroupConfigurationContentSerde for TextGroupConfigurationRevision {
   }
 }
 
-#[derive(Default, Serialize, Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub struct NumberGroupConfigurationRevision {
   pub hide_empty: bool,
+
+  #[serde(default)]
+  pub condition: NumberGroupCondition,
+
+  /// The width of each range, used by the [NumberGroupCondition::Range]
+  #[serde(default = "default_number_group_interval")]
+  pub interval: String,
+
+  /// The lower bounds of the ranges, used by the [NumberGroupCondition::Breakpoint]
+  #[serde(default)]
+  pub breakpoints: Vec<String>,
+}
+
+fn default_number_group_interval() -> String {
+  "10".to_owned()
+}
+
+impl std::default::Default for NumberGroupConfigurationRevision {
+  fn default() -> Self {
+    Self {
+      hide_empty: false,
+      condition: NumberGroupCondition::default(),
+      interval: default_number_group_interval(),
+      breakpoints: vec![],
+    }
+  }
 }
 
 impl GroupConfigurationContentSerde for NumberGroupConfigurationRevision {
@@ -66,6 +92,23 @@ impl GroupConfigurationContentSerde for NumberGroupConfigurationRevision {
   }
 }
 
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum NumberGroupCondition {
+  /// Groups the numbers into ranges with the same width, the ranges start from zero.
+  Range = 0,
+  /// Groups the numbers into the ranges that are split by the user-defined breakpoints.
+  Breakpoint = 1,
+  /// Each distinct number has its own group.
+  Distinct = 2,
+}
+
+impl std::default::Default for NumberGroupCondition {
+  fn default() -> Self {
+    NumberGroupCondition::Range
+  }
+}
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct URLGroupConfigurationRevision {
   pub hide_empty: bool,
@@ -160,7 +203,7 @@ impl GroupConfigurationContentSerde for DateGroupConfigurationRevision {
   }
 }
 
-#[derive(Serialize_repr, Deserialize_repr)]
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum DateCondition {
   Relative = 0,
@@ -178,7 +221,18 @@ impl std::default::Default for DateCondition {
 
 #[cfg(test)]
 mod tests {
-  use crate::{GroupConfigurationRevision, SelectOptionGroupConfigurationRevision};
+  use crate::{
+    GroupConfigurationContentSerde, GroupConfigurationRevision, NumberGroupCondition,
+    NumberGroupConfigurationRevision, SelectOptionGroupConfigurationRevision,
+  };
+
+  #[test]
+  fn number_group_configuration_default_test() {
+    let rev = NumberGroupConfigurationRevision::from_json(r#"{"hide_empty":false}"#).unwrap();
+    assert_eq!(rev.condition, NumberGroupCondition::Range);
+    assert_eq!(rev.interval, "10");
+    assert!(rev.breakpoints.is_empty());
+  }
 
   #[test]
   fn group_configuration_serde_test() {
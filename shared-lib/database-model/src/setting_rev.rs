@@ -1,9 +1,10 @@
 use crate::{
-  FieldRevision, FieldTypeRevision, FilterRevision, GroupConfigurationRevision, SortRevision,
+  CalculationRevision, FieldRevision, FieldTypeRevision, GroupConfigurationRevision, SortRevision,
 };
 use indexmap::IndexMap;
 use nanoid::nanoid;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -19,12 +20,16 @@ pub fn gen_database_sort_id() -> String {
   nanoid!(6)
 }
 
-pub type FilterConfiguration = Configuration<FilterRevision>;
+pub fn gen_database_calculation_id() -> String {
+  nanoid!(6)
+}
 
 pub type GroupConfiguration = Configuration<GroupConfigurationRevision>;
 
 pub type SortConfiguration = Configuration<SortRevision>;
 
+pub type CalculationConfiguration = Configuration<CalculationRevision>;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(transparent)]
 pub struct Configuration<T>
@@ -133,9 +138,48 @@ where
       .push(Arc::new(object))
   }
 
+  /// Retains only the objects specified by the predicate.
+  pub fn retain_objects(&mut self, f: impl Fn(&Arc<T>) -> bool) {
+    for object_map in self.inner.values_mut() {
+      for objects in object_map.values_mut() {
+        objects.retain(|object| f(object));
+      }
+    }
+  }
+
+  /// Replaces the field ids of the objects with the ids in the map. It's used when the fields are
+  /// duplicated with new ids. The objects of the fields that aren't in the map are kept as they
+  /// are.
+  pub fn remap_field_ids(
+    &mut self,
+    field_id_map: &HashMap<String, String>,
+    set_field_id: impl Fn(&mut T, &str),
+  ) {
+    self.inner = std::mem::take(&mut self.inner)
+      .into_iter()
+      .map(
+        |(field_id, mut object_map)| match field_id_map.get(&field_id) {
+          None => (field_id, object_map),
+          Some(new_field_id) => {
+            for objects in object_map.values_mut() {
+              for object in objects.iter_mut() {
+                set_field_id(Arc::make_mut(object), new_field_id);
+              }
+            }
+            (new_field_id.clone(), object_map)
+          },
+        },
+      )
+      .collect();
+  }
+
   pub fn clear(&mut self) {
     self.inner.clear()
   }
+
+  pub fn is_empty(&self) -> bool {
+    self.inner.is_empty()
+  }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -1,7 +1,8 @@
-use crate::{FilterConfiguration, GroupConfiguration, SortConfiguration};
+use crate::{CalculationConfiguration, FilterConfiguration, GroupConfiguration, SortConfiguration};
 use nanoid::nanoid;
 use serde::{Deserialize, Serialize};
 use serde_repr::*;
+use std::collections::HashMap;
 
 #[allow(dead_code)]
 pub fn gen_grid_view_id() -> String {
@@ -46,6 +47,12 @@ pub struct DatabaseViewRevision {
 
   #[serde(default)]
   pub sorts: SortConfiguration,
+
+  #[serde(default, skip_serializing_if = "CalculationConfiguration::is_empty")]
+  pub calculations: CalculationConfiguration,
+
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub calendar_setting: Option<CalendarLayoutSettingRevision>,
 }
 
 impl DatabaseViewRevision {
@@ -57,12 +64,65 @@ impl DatabaseViewRevision {
       filters: Default::default(),
       groups: Default::default(),
       sorts: Default::default(),
+      calculations: Default::default(),
+      calendar_setting: None,
     }
   }
 
   pub fn from_json(json: String) -> Result<Self, serde_json::Error> {
     serde_json::from_str(&json)
   }
+
+  /// Replaces the field ids that the settings of the view refer to with the ids in the map. It's
+  /// used when the database is duplicated with new field ids.
+  pub fn remap_field_ids(&mut self, field_id_map: &HashMap<String, String>) {
+    self.filters.remap_field_ids(field_id_map);
+    self
+      .groups
+      .remap_field_ids(field_id_map, |group, field_id| {
+        group.field_id = field_id.to_owned();
+      });
+    self.sorts.remap_field_ids(field_id_map, |sort, field_id| {
+      sort.field_id = field_id.to_owned();
+    });
+    self
+      .calculations
+      .remap_field_ids(field_id_map, |calculation, field_id| {
+        calculation.field_id = field_id.to_owned();
+      });
+    if let Some(calendar_setting) = self.calendar_setting.as_mut() {
+      if let Some(field_id) = field_id_map.get(&calendar_setting.layout_field_id) {
+        calendar_setting.layout_field_id = field_id.clone();
+      }
+    }
+  }
+}
+
+/// The settings of the calendar layout. A calendar places each row at the date stored in the
+/// date field with id `layout_field_id`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalendarLayoutSettingRevision {
+  pub layout_field_id: String,
+
+  #[serde(default)]
+  pub first_day_of_week: i32,
+
+  #[serde(default = "default_show_weekends")]
+  pub show_weekends: bool,
+}
+
+fn default_show_weekends() -> bool {
+  true
+}
+
+impl CalendarLayoutSettingRevision {
+  pub fn new(layout_field_id: String) -> Self {
+    Self {
+      layout_field_id,
+      first_day_of_week: 0,
+      show_weekends: default_show_weekends(),
+    }
+  }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -72,7 +132,11 @@ pub struct RowOrderRevision {
 
 #[cfg(test)]
 mod tests {
-  use crate::DatabaseViewRevision;
+  use crate::{
+    CalculationRevision, CalculationType, CalendarLayoutSettingRevision, DatabaseViewRevision,
+    SortRevision,
+  };
+  use std::collections::HashMap;
 
   #[test]
   fn grid_view_revision_serde_test() {
@@ -83,6 +147,8 @@ mod tests {
       filters: Default::default(),
       groups: Default::default(),
       sorts: Default::default(),
+      calculations: Default::default(),
+      calendar_setting: None,
     };
     let s = serde_json::to_string(&grid_view_revision).unwrap();
     assert_eq!(
@@ -90,4 +156,92 @@ mod tests {
       r#"{"view_id":"1","grid_id":"1","layout":0,"filters":[],"groups":[],"sorts":[]}"#
     );
   }
+
+  #[test]
+  fn calendar_view_revision_serde_test() {
+    let mut calendar_view_revision = DatabaseViewRevision::new(
+      "1".to_string(),
+      "1".to_string(),
+      crate::LayoutRevision::Calendar,
+    );
+    calendar_view_revision.calendar_setting =
+      Some(CalendarLayoutSettingRevision::new("date".to_string()));
+    let s = serde_json::to_string(&calendar_view_revision).unwrap();
+    assert_eq!(
+      s,
+      r#"{"view_id":"1","grid_id":"1","layout":2,"filters":[],"groups":[],"sorts":[],"calendar_setting":{"layout_field_id":"date","first_day_of_week":0,"show_weekends":true}}"#
+    );
+
+    let json =
+      r#"{"view_id":"1","grid_id":"1","layout":2,"calendar_setting":{"layout_field_id":"date"}}"#;
+    let view_rev = DatabaseViewRevision::from_json(json.to_string()).unwrap();
+    assert_eq!(
+      view_rev.calendar_setting,
+      Some(CalendarLayoutSettingRevision::new("date".to_string()))
+    );
+  }
+
+  #[test]
+  fn calculation_view_revision_serde_test() {
+    let mut view_rev = DatabaseViewRevision::new(
+      "1".to_string(),
+      "1".to_string(),
+      crate::LayoutRevision::Grid,
+    );
+    let calculation_rev = CalculationRevision {
+      id: "c1".to_string(),
+      field_id: "f1".to_string(),
+      field_type: 1,
+      calculation_type: CalculationType::Sum,
+    };
+    view_rev
+      .calculations
+      .add_object("f1", &1, calculation_rev.clone());
+    let s = serde_json::to_string(&view_rev).unwrap();
+    let view_rev = DatabaseViewRevision::from_json(s).unwrap();
+    let calculations = view_rev.calculations.get_objects("f1", &1).unwrap();
+    assert_eq!(calculations[0].as_ref(), &calculation_rev);
+  }
+
+  #[test]
+  fn view_revision_remap_field_ids_test() {
+    let mut view_rev = DatabaseViewRevision::new(
+      "1".to_string(),
+      "1".to_string(),
+      crate::LayoutRevision::Calendar,
+    );
+    view_rev.calendar_setting = Some(CalendarLayoutSettingRevision::new("date".to_string()));
+    let sort_rev = SortRevision {
+      id: "s1".to_string(),
+      field_id: "f1".to_string(),
+      field_type: 1,
+      condition: Default::default(),
+    };
+    view_rev.sorts.add_object("f1", &1, sort_rev);
+    view_rev.sorts.add_object(
+      "f2",
+      &1,
+      SortRevision {
+        id: "s2".to_string(),
+        field_id: "f2".to_string(),
+        field_type: 1,
+        condition: Default::default(),
+      },
+    );
+
+    let field_id_map = HashMap::from([
+      ("f1".to_string(), "new_f1".to_string()),
+      ("date".to_string(), "new_date".to_string()),
+    ]);
+    view_rev.remap_field_ids(&field_id_map);
+
+    assert!(view_rev.sorts.get_objects("f1", &1).is_none());
+    let sorts = view_rev.sorts.get_objects("new_f1", &1).unwrap();
+    assert_eq!(sorts[0].field_id, "new_f1");
+    assert_eq!(view_rev.sorts.get_objects("f2", &1).unwrap().len(), 1);
+    assert_eq!(
+      view_rev.calendar_setting.unwrap().layout_field_id,
+      "new_date"
+    );
+  }
 }
@@ -1,2 +1,3 @@
+pub mod server_database;
 pub mod server_document;
 pub mod server_folder;
@@ -0,0 +1,496 @@
+use crate::server_database::database_pad::{DatabaseOperations, DatabaseRevisionSynchronizer};
+use crate::server_database::ServerDatabase;
+use async_stream::stream;
+use database_model::DatabaseInfo;
+use flowy_sync::errors::{internal_sync_error, SyncError, SyncResult};
+use flowy_sync::ext::DatabaseCloudPersistence;
+use flowy_sync::{RevisionSyncResponse, RevisionUser};
+use futures::stream::StreamExt;
+use revision_model::Revision;
+use std::{collections::HashMap, sync::Arc};
+use tokio::{
+  sync::{mpsc, oneshot, RwLock},
+  task::spawn_blocking,
+};
+use ws_model::ws_revision::{ClientRevisionWSData, ServerRevisionWSDataBuilder};
+
+/// Merges the revisions of the database, the database views and the database blocks that are
+/// sent by the clients. Each of them is an independent object that is identified by its id.
+pub struct ServerDatabaseManager {
+  database_handlers: Arc<RwLock<HashMap<String, Arc<OpenDatabaseHandler>>>>,
+  persistence: Arc<dyn DatabaseCloudPersistence>,
+}
+
+impl ServerDatabaseManager {
+  pub fn new(persistence: Arc<dyn DatabaseCloudPersistence>) -> Self {
+    Self {
+      database_handlers: Arc::new(RwLock::new(HashMap::new())),
+      persistence,
+    }
+  }
+
+  pub async fn handle_client_revisions(
+    &self,
+    user: Arc<dyn RevisionUser>,
+    client_data: ClientRevisionWSData,
+  ) -> Result<(), SyncError> {
+    let cloned_user = user.clone();
+    let ack_id = client_data.rev_id;
+    let object_id = client_data.object_id;
+
+    let result = match self.get_database_handler(&object_id).await {
+      None => {
+        let _ = self
+          .create_database(&object_id, client_data.revisions)
+          .await
+          .map_err(|e| {
+            SyncError::internal().context(format!("Server create database failed: {:?}", e))
+          })?;
+        Ok(())
+      },
+      Some(handler) => {
+        handler.apply_revisions(user, client_data.revisions).await?;
+        Ok(())
+      },
+    };
+
+    if result.is_ok() {
+      cloned_user.receive(RevisionSyncResponse::Ack(
+        ServerRevisionWSDataBuilder::build_ack_message(&object_id, ack_id),
+      ));
+    }
+    result
+  }
+
+  pub async fn handle_client_ping(
+    &self,
+    user: Arc<dyn RevisionUser>,
+    client_data: ClientRevisionWSData,
+  ) -> Result<(), SyncError> {
+    let rev_id = client_data.rev_id;
+    let object_id = client_data.object_id.clone();
+    match self.get_database_handler(&object_id).await {
+      None => {
+        tracing::trace!("Database:{} doesn't exist, ignore client ping", object_id);
+        Ok(())
+      },
+      Some(handler) => {
+        handler.apply_ping(rev_id, user).await?;
+        Ok(())
+      },
+    }
+  }
+
+  async fn get_database_handler(&self, object_id: &str) -> Option<Arc<OpenDatabaseHandler>> {
+    let object_id = object_id.to_owned();
+    if let Some(handler) = self.database_handlers.read().await.get(&object_id).cloned() {
+      return Some(handler);
+    }
+
+    let mut write_guard = self.database_handlers.write().await;
+    match self.persistence.read_database(&object_id).await {
+      Ok(database_info) => {
+        let handler = self
+          .create_database_handler(database_info)
+          .await
+          .map_err(internal_sync_error)
+          .unwrap();
+        write_guard.insert(object_id, handler.clone());
+        drop(write_guard);
+        Some(handler)
+      },
+      Err(_) => None,
+    }
+  }
+
+  async fn create_database_handler(
+    &self,
+    database_info: DatabaseInfo,
+  ) -> Result<Arc<OpenDatabaseHandler>, SyncError> {
+    let persistence = self.persistence.clone();
+    let handle = spawn_blocking(|| OpenDatabaseHandler::new(database_info, persistence))
+      .await
+      .map_err(|e| {
+        SyncError::internal().context(format!("Create database handler failed: {}", e))
+      })?;
+    Ok(Arc::new(handle?))
+  }
+
+  #[tracing::instrument(level = "debug", skip(self, revisions), err)]
+  async fn create_database(
+    &self,
+    object_id: &str,
+    revisions: Vec<Revision>,
+  ) -> Result<Arc<OpenDatabaseHandler>, SyncError> {
+    match self
+      .persistence
+      .create_database(object_id, revisions)
+      .await?
+    {
+      Some(database_info) => {
+        let handler = self.create_database_handler(database_info).await?;
+        self
+          .database_handlers
+          .write()
+          .await
+          .insert(object_id.to_owned(), handler.clone());
+        Ok(handler)
+      },
+      None => Err(SyncError::internal().context(String::new())),
+    }
+  }
+}
+
+struct OpenDatabaseHandler {
+  object_id: String,
+  sender: mpsc::Sender<DatabaseCommand>,
+}
+
+impl OpenDatabaseHandler {
+  fn new(
+    database_info: DatabaseInfo,
+    persistence: Arc<dyn DatabaseCloudPersistence>,
+  ) -> SyncResult<Self> {
+    let (sender, receiver) = mpsc::channel(1000);
+    let object_id = database_info.object_id.clone();
+    let operations = DatabaseOperations::from_bytes(&database_info.text)?;
+    let sync_object = ServerDatabase::from_operations(&object_id, operations);
+    let synchronizer = Arc::new(DatabaseRevisionSynchronizer::new(
+      database_info.rev_id,
+      sync_object,
+      persistence,
+    ));
+
+    let queue = DatabaseCommandRunner::new(&object_id, receiver, synchronizer);
+    tokio::task::spawn(queue.run());
+
+    Ok(Self { object_id, sender })
+  }
+
+  #[tracing::instrument(
+    name = "server_database_apply_revision",
+    level = "trace",
+    skip(self, user, revisions),
+    err
+  )]
+  async fn apply_revisions(
+    &self,
+    user: Arc<dyn RevisionUser>,
+    revisions: Vec<Revision>,
+  ) -> SyncResult<()> {
+    let (ret, rx) = oneshot::channel();
+    let msg = DatabaseCommand::ApplyRevisions {
+      user,
+      revisions,
+      ret,
+    };
+
+    self.send(msg, rx).await?
+  }
+
+  async fn apply_ping(&self, rev_id: i64, user: Arc<dyn RevisionUser>) -> Result<(), SyncError> {
+    let (ret, rx) = oneshot::channel();
+    let msg = DatabaseCommand::Ping { user, rev_id, ret };
+    self.send(msg, rx).await?
+  }
+
+  async fn send<T>(&self, msg: DatabaseCommand, rx: oneshot::Receiver<T>) -> SyncResult<T> {
+    self
+      .sender
+      .send(msg)
+      .await
+      .map_err(|e| SyncError::internal().context(format!("Send database command failed: {}", e)))?;
+    rx.await.map_err(internal_sync_error)
+  }
+}
+
+impl std::ops::Drop for OpenDatabaseHandler {
+  fn drop(&mut self) {
+    tracing::trace!("{} OpenDatabaseHandler was dropped", self.object_id);
+  }
+}
+
+enum DatabaseCommand {
+  ApplyRevisions {
+    user: Arc<dyn RevisionUser>,
+    revisions: Vec<Revision>,
+    ret: oneshot::Sender<SyncResult<()>>,
+  },
+  Ping {
+    user: Arc<dyn RevisionUser>,
+    rev_id: i64,
+    ret: oneshot::Sender<SyncResult<()>>,
+  },
+}
+
+struct DatabaseCommandRunner {
+  object_id: String,
+  receiver: Option<mpsc::Receiver<DatabaseCommand>>,
+  synchronizer: Arc<DatabaseRevisionSynchronizer>,
+}
+impl DatabaseCommandRunner {
+  fn new(
+    object_id: &str,
+    receiver: mpsc::Receiver<DatabaseCommand>,
+    synchronizer: Arc<DatabaseRevisionSynchronizer>,
+  ) -> Self {
+    Self {
+      object_id: object_id.to_owned(),
+      receiver: Some(receiver),
+      synchronizer,
+    }
+  }
+
+  async fn run(mut self) {
+    let mut receiver = self
+      .receiver
+      .take()
+      .expect("DatabaseCommandRunner's receiver should only take one time");
+
+    let stream = stream! {
+        loop {
+            match receiver.recv().await {
+                Some(msg) => yield msg,
+                None => break,
+            }
+        }
+    };
+    stream.for_each(|msg| self.handle_message(msg)).await;
+  }
+
+  async fn handle_message(&self, msg: DatabaseCommand) {
+    match msg {
+      DatabaseCommand::ApplyRevisions {
+        user,
+        revisions,
+        ret,
+      } => {
+        let result = self
+          .synchronizer
+          .sync_revisions(user, revisions)
+          .await
+          .map_err(internal_sync_error);
+        let _ = ret.send(result);
+      },
+      DatabaseCommand::Ping { user, rev_id, ret } => {
+        let result = self
+          .synchronizer
+          .pong(user, rev_id)
+          .await
+          .map_err(internal_sync_error);
+        let _ = ret.send(result);
+      },
+    }
+  }
+}
+
+impl std::ops::Drop for DatabaseCommandRunner {
+  fn drop(&mut self) {
+    tracing::trace!("{} DatabaseCommandRunner was dropped", self.object_id);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::server_database::{
+    make_database_from_revisions, DatabaseOperations, ServerDatabaseManager,
+  };
+  use database_model::DatabaseInfo;
+  use flowy_sync::errors::SyncError;
+  use flowy_sync::ext::DatabaseCloudPersistence;
+  use flowy_sync::{RevisionSyncResponse, RevisionUser};
+  use lib_infra::future::BoxResultFuture;
+  use lib_ot::core::{DeltaOperationBuilder, EmptyAttributes, OperationTransform};
+  use revision_model::Revision;
+  use std::sync::{Arc, Mutex};
+  use ws_model::ws_revision::{ClientRevisionWSData, WSRevisionPayload};
+
+  const OBJECT_ID: &str = "grid_view";
+  const BASE_TEXT: &str = r#"{"view_id":"grid_view"}"#;
+
+  #[tokio::test]
+  async fn concurrent_edits_of_two_clients_converge_test() {
+    let persistence = Arc::new(MemoryDatabasePersistence::default());
+    let manager = ServerDatabaseManager::new(persistence.clone());
+    let client_a = Arc::new(MockRevisionUser::default());
+    let client_b = Arc::new(MockRevisionUser::default());
+
+    let base = build_operations(|builder| builder.insert(BASE_TEXT));
+    push(&manager, &client_a, make_revision(0, 1, &base, "base")).await;
+
+    // Both clients edit the first revision at the same time.
+    let len = BASE_TEXT.len();
+    let a_operations = build_operations(|builder| builder.retain(len - 1).insert(r#","a":1"#));
+    let b_operations = build_operations(|builder| builder.retain(1).insert(r#""b":2,"#));
+    push(&manager, &client_a, make_revision(1, 2, &a_operations, "a")).await;
+    push(&manager, &client_b, make_revision(1, 2, &b_operations, "b")).await;
+
+    // The server pushes the revision of client A to client B, client B transforms its own
+    // operations and then pushes the prime operations.
+    let pushed = client_b.take_pushed_revisions();
+    assert_eq!(pushed.len(), 1);
+    let server_operations = DatabaseOperations::from_bytes(&pushed[0].bytes).unwrap();
+    let (server_prime, b_prime) = server_operations.transform(&b_operations).unwrap();
+    let client_b_operations = base
+      .compose(&b_operations)
+      .unwrap()
+      .compose(&server_prime)
+      .unwrap();
+    push(
+      &manager,
+      &client_b,
+      make_revision(2, 3, &b_prime, "b_prime"),
+    )
+    .await;
+
+    // Client A receives the prime operations of client B after pinging.
+    manager
+      .handle_client_ping(client_a.clone(), ClientRevisionWSData::ping(OBJECT_ID, 2))
+      .await
+      .unwrap();
+    let pushed = client_a.take_pushed_revisions();
+    let revision = pushed.iter().find(|revision| revision.rev_id == 3).unwrap();
+    let client_a_operations = base
+      .compose(&a_operations)
+      .unwrap()
+      .compose(&DatabaseOperations::from_bytes(&revision.bytes).unwrap())
+      .unwrap();
+
+    let server_text = persistence.read_text();
+    let server_operations = DatabaseOperations::from_json(&server_text).unwrap();
+    assert_eq!(
+      server_operations.content().unwrap(),
+      r#"{"b":2,"view_id":"grid_view","a":1}"#
+    );
+    assert_eq!(
+      client_a_operations.content().unwrap(),
+      server_operations.content().unwrap()
+    );
+    assert_eq!(
+      client_b_operations.content().unwrap(),
+      server_operations.content().unwrap()
+    );
+  }
+
+  fn build_operations<F>(f: F) -> DatabaseOperations
+  where
+    F: FnOnce(DeltaOperationBuilder<EmptyAttributes>) -> DeltaOperationBuilder<EmptyAttributes>,
+  {
+    f(DeltaOperationBuilder::new()).build()
+  }
+
+  fn make_revision(
+    base_rev_id: i64,
+    rev_id: i64,
+    operations: &DatabaseOperations,
+    md5: &str,
+  ) -> Revision {
+    Revision::new(OBJECT_ID, base_rev_id, rev_id, operations.json_bytes(), md5)
+  }
+
+  async fn push(manager: &ServerDatabaseManager, user: &Arc<MockRevisionUser>, revision: Revision) {
+    let client_data = ClientRevisionWSData::from_revisions(OBJECT_ID, vec![revision]);
+    manager
+      .handle_client_revisions(user.clone(), client_data)
+      .await
+      .unwrap();
+  }
+
+  #[derive(Debug, Default)]
+  struct MockRevisionUser {
+    pushed_revisions: Mutex<Vec<Revision>>,
+  }
+
+  impl MockRevisionUser {
+    fn take_pushed_revisions(&self) -> Vec<Revision> {
+      std::mem::take(&mut *self.pushed_revisions.lock().unwrap())
+    }
+  }
+
+  impl RevisionUser for MockRevisionUser {
+    fn user_id(&self) -> String {
+      "user".to_owned()
+    }
+
+    fn receive(&self, resp: RevisionSyncResponse) {
+      if let RevisionSyncResponse::Push(data) = resp {
+        if let WSRevisionPayload::ServerPushRev { revisions } = data.payload {
+          self.pushed_revisions.lock().unwrap().extend(revisions);
+        }
+      }
+    }
+  }
+
+  #[derive(Debug, Default)]
+  struct MemoryDatabasePersistence {
+    revisions: Mutex<Vec<Revision>>,
+  }
+
+  impl MemoryDatabasePersistence {
+    fn get_revisions(&self, object_id: &str, rev_ids: Option<Vec<i64>>) -> Vec<Revision> {
+      self
+        .revisions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|revision| revision.object_id == object_id)
+        .filter(|revision| match &rev_ids {
+          None => true,
+          Some(rev_ids) => rev_ids.contains(&revision.rev_id),
+        })
+        .cloned()
+        .collect()
+    }
+
+    fn read_text(&self) -> String {
+      let revisions = self.get_revisions(OBJECT_ID, None);
+      make_database_from_revisions(OBJECT_ID, revisions)
+        .unwrap()
+        .unwrap()
+        .text
+    }
+  }
+
+  impl DatabaseCloudPersistence for MemoryDatabasePersistence {
+    fn read_database(&self, object_id: &str) -> BoxResultFuture<DatabaseInfo, SyncError> {
+      let result = make_database_from_revisions(object_id, self.get_revisions(object_id, None))
+        .and_then(|info| info.ok_or_else(SyncError::record_not_found));
+      Box::pin(async move { result })
+    }
+
+    fn create_database(
+      &self,
+      object_id: &str,
+      revisions: Vec<Revision>,
+    ) -> BoxResultFuture<Option<DatabaseInfo>, SyncError> {
+      self.revisions.lock().unwrap().extend(revisions.clone());
+      let result = make_database_from_revisions(object_id, revisions);
+      Box::pin(async move { result })
+    }
+
+    fn read_database_revisions(
+      &self,
+      object_id: &str,
+      rev_ids: Option<Vec<i64>>,
+    ) -> BoxResultFuture<Vec<Revision>, SyncError> {
+      let revisions = self.get_revisions(object_id, rev_ids);
+      Box::pin(async move { Ok(revisions) })
+    }
+
+    fn save_database_revisions(&self, revisions: Vec<Revision>) -> BoxResultFuture<(), SyncError> {
+      self.revisions.lock().unwrap().extend(revisions);
+      Box::pin(async move { Ok(()) })
+    }
+
+    fn reset_database(
+      &self,
+      object_id: &str,
+      revisions: Vec<Revision>,
+    ) -> BoxResultFuture<(), SyncError> {
+      let mut guard = self.revisions.lock().unwrap();
+      guard.retain(|revision| revision.object_id != object_id);
+      guard.extend(revisions);
+      Box::pin(async move { Ok(()) })
+    }
+  }
+}
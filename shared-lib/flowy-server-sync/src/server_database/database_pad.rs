@@ -0,0 +1,149 @@
+use database_model::DatabaseInfo;
+use flowy_sync::errors::SyncError;
+use flowy_sync::ext::DatabaseCloudPersistence;
+use flowy_sync::{
+  RevisionOperations, RevisionSyncObject, RevisionSyncResponse, RevisionSynchronizer, RevisionUser,
+};
+use lib_ot::core::{DeltaOperations, EmptyAttributes, OperationTransform};
+use revision_model::Revision;
+use std::sync::Arc;
+use ws_model::ws_revision::ServerRevisionWSDataBuilder;
+
+pub type DatabaseOperations = DeltaOperations<EmptyAttributes>;
+
+/// Wraps the [RevisionSynchronizer] of the database. The database is edited by many clients at
+/// the same time, so a client may push a revision whose rev_id was already taken by the revision
+/// of another client.
+pub struct DatabaseRevisionSynchronizer {
+  object_id: String,
+  synchronizer: RevisionSynchronizer<EmptyAttributes>,
+  persistence: Arc<dyn DatabaseCloudPersistence>,
+}
+
+impl DatabaseRevisionSynchronizer {
+  pub fn new(
+    rev_id: i64,
+    sync_object: ServerDatabase,
+    persistence: Arc<dyn DatabaseCloudPersistence>,
+  ) -> Self {
+    let object_id = sync_object.object_id().to_owned();
+    let synchronizer = RevisionSynchronizer::new(rev_id, sync_object, persistence.clone());
+    Self {
+      object_id,
+      synchronizer,
+      persistence,
+    }
+  }
+
+  pub async fn sync_revisions(
+    &self,
+    user: Arc<dyn RevisionUser>,
+    revisions: Vec<Revision>,
+  ) -> Result<(), SyncError> {
+    let server_rev_id = self.synchronizer.rev_id();
+    if let Some(first_revision) = revisions.first() {
+      if first_revision.rev_id == server_rev_id {
+        let rev_ids = (first_revision.rev_id..=server_rev_id).collect::<Vec<_>>();
+        let server_revisions = self
+          .persistence
+          .read_database_revisions(&self.object_id, Some(rev_ids))
+          .await?;
+        let is_applied_before = server_revisions
+          .iter()
+          .any(|revision| revision.md5 == first_revision.md5);
+        if !is_applied_before {
+          // The revision was made by another client concurrently. Push the server revision to the
+          // client, the client transforms its own revision and then pushes the prime revision.
+          let data =
+            ServerRevisionWSDataBuilder::build_push_message(&self.object_id, server_revisions);
+          user.receive(RevisionSyncResponse::Push(data));
+          return Ok(());
+        }
+      }
+    }
+    self.synchronizer.sync_revisions(user, revisions).await
+  }
+
+  pub async fn pong(
+    &self,
+    user: Arc<dyn RevisionUser>,
+    client_rev_id: i64,
+  ) -> Result<(), SyncError> {
+    self.synchronizer.pong(user, client_rev_id).await
+  }
+}
+
+/// The server side of the database, the database view or the database block. All of them are
+/// serialized to json text on the client, so the server merges their operations in the same way.
+pub struct ServerDatabase {
+  object_id: String,
+  operations: DatabaseOperations,
+}
+
+impl ServerDatabase {
+  pub fn from_operations(object_id: &str, operations: DatabaseOperations) -> Self {
+    Self {
+      object_id: object_id.to_owned(),
+      operations,
+    }
+  }
+}
+
+impl RevisionSyncObject<EmptyAttributes> for ServerDatabase {
+  fn object_id(&self) -> &str {
+    &self.object_id
+  }
+
+  fn object_json(&self) -> String {
+    self.operations.json_str()
+  }
+
+  fn compose(&mut self, other: &DatabaseOperations) -> Result<(), SyncError> {
+    let operations = self.operations.compose(other)?;
+    self.operations = operations;
+    Ok(())
+  }
+
+  fn transform(
+    &self,
+    other: &DatabaseOperations,
+  ) -> Result<(DatabaseOperations, DatabaseOperations), SyncError> {
+    let value = self.operations.transform(other)?;
+    Ok(value)
+  }
+
+  fn set_operations(&mut self, operations: RevisionOperations<EmptyAttributes>) {
+    self.operations = operations;
+  }
+}
+
+#[inline]
+pub fn make_database_from_revisions(
+  object_id: &str,
+  revisions: Vec<Revision>,
+) -> Result<Option<DatabaseInfo>, SyncError> {
+  if revisions.is_empty() {
+    return Ok(None);
+  }
+
+  let mut database_delta = DatabaseOperations::new();
+  let mut base_rev_id = 0;
+  let mut rev_id = 0;
+  for revision in revisions {
+    base_rev_id = revision.base_rev_id;
+    rev_id = revision.rev_id;
+    if revision.bytes.is_empty() {
+      tracing::warn!("revision delta_data is empty");
+    }
+    let delta = DatabaseOperations::from_bytes(revision.bytes)?;
+    database_delta = database_delta.compose(&delta)?;
+  }
+
+  let text = database_delta.json_str();
+  Ok(Some(DatabaseInfo {
+    object_id: object_id.to_string(),
+    text,
+    rev_id,
+    base_rev_id,
+  }))
+}
@@ -0,0 +1,5 @@
+mod database_manager;
+mod database_pad;
+
+pub use database_manager::*;
+pub use database_pad::*;
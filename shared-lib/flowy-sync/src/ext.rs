@@ -1,5 +1,6 @@
 use crate::errors::SyncError;
 use crate::RevisionSyncPersistence;
+use database_model::DatabaseInfo;
 use document_model::document::DocumentInfo;
 use folder_model::FolderInfo;
 use lib_infra::future::BoxResultFuture;
@@ -99,3 +100,49 @@ impl RevisionSyncPersistence for Arc<dyn DocumentCloudPersistence> {
     (**self).reset_document(object_id, revisions)
   }
 }
+
+pub trait DatabaseCloudPersistence: Send + Sync + Debug {
+  fn read_database(&self, object_id: &str) -> BoxResultFuture<DatabaseInfo, SyncError>;
+
+  fn create_database(
+    &self,
+    object_id: &str,
+    revisions: Vec<Revision>,
+  ) -> BoxResultFuture<Option<DatabaseInfo>, SyncError>;
+
+  fn read_database_revisions(
+    &self,
+    object_id: &str,
+    rev_ids: Option<Vec<i64>>,
+  ) -> BoxResultFuture<Vec<Revision>, SyncError>;
+
+  fn save_database_revisions(&self, revisions: Vec<Revision>) -> BoxResultFuture<(), SyncError>;
+
+  fn reset_database(
+    &self,
+    object_id: &str,
+    revisions: Vec<Revision>,
+  ) -> BoxResultFuture<(), SyncError>;
+}
+
+impl RevisionSyncPersistence for Arc<dyn DatabaseCloudPersistence> {
+  fn read_revisions(
+    &self,
+    object_id: &str,
+    rev_ids: Option<Vec<i64>>,
+  ) -> BoxResultFuture<Vec<Revision>, SyncError> {
+    (**self).read_database_revisions(object_id, rev_ids)
+  }
+
+  fn save_revisions(&self, revisions: Vec<Revision>) -> BoxResultFuture<(), SyncError> {
+    (**self).save_database_revisions(revisions)
+  }
+
+  fn reset_object(
+    &self,
+    object_id: &str,
+    revisions: Vec<Revision>,
+  ) -> BoxResultFuture<(), SyncError> {
+    (**self).reset_database(object_id, revisions)
+  }
+}
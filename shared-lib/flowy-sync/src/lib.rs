@@ -260,7 +260,7 @@ where
     Ok(())
   }
 
-  pub(crate) fn rev_id(&self) -> i64 {
+  pub fn rev_id(&self) -> i64 {
     self.rev_id.load(SeqCst)
   }
 
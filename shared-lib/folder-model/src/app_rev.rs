@@ -25,6 +25,14 @@ pub struct AppRevision {
 
   #[serde(default)]
   pub create_time: i64,
+
+  #[serde(default)]
+  pub color_style: ColorStyleRevision,
+}
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ColorStyleRevision {
+  pub theme_color: String,
 }
 
 impl std::convert::From<AppRevision> for TrashRevision {
@@ -38,6 +38,7 @@ fn create_default_app(workspace_id: String, time: chrono::DateTime<Utc>) -> AppR
     version: 0,
     modified_time: time.timestamp(),
     create_time: time.timestamp(),
+    color_style: Default::default(),
   }
 }
 
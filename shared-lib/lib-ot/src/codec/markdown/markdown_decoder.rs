@@ -0,0 +1,449 @@
+use crate::core::{AttributeHashMap, NodeData, NodeDataBuilder};
+use crate::text_delta::DeltaTextOperations;
+
+const EDITOR_NODE_TYPE: &str = "editor";
+const TEXT_NODE_TYPE: &str = "text";
+const IMAGE_NODE_TYPE: &str = "image";
+
+/// The number of spaces that represents one level of the nested blocks.
+const INDENT_SPACES: usize = 2;
+
+/// Decodes the Markdown into an `editor` node. Each line of the Markdown is decoded into a block
+/// node, `text` or `image`, whose style is stored in the `subtype` attribute. The inline styles,
+/// bold, italic, code, etc, are stored in the text delta of the node.
+///
+/// The indented lines are treated as the children of the previous block.
+///
+/// # Examples
+///
+/// ```
+/// use lib_ot::codec::markdown::markdown_decoder;
+/// let node = markdown_decoder("# AppFlowy");
+/// assert_eq!(node.node_type, "editor");
+/// assert_eq!(node.children.len(), 1);
+/// ```
+pub fn markdown_decoder(markdown: &str) -> NodeData {
+  let mut nodes: Vec<NodeData> = vec![];
+  // The stack of the blocks that may have children. Each element is the (depth, node) pair.
+  let mut stack: Vec<(usize, NodeData)> = vec![];
+  let mut lines = markdown.lines();
+
+  while let Some(line) = lines.next() {
+    let trimmed_line = line.trim_start();
+    let depth = match stack.last() {
+      None => 0,
+      Some((parent_depth, _)) => indent_depth(line).min(parent_depth + 1),
+    };
+
+    let node = if trimmed_line.starts_with("```") {
+      // The lines of the code block are decoded into one text node with the code style.
+      let mut code_lines = vec![];
+      for code_line in lines.by_ref() {
+        if code_line.trim_start().starts_with("```") {
+          break;
+        }
+        code_lines.push(code_line);
+      }
+      let mut attributes = AttributeHashMap::new();
+      attributes.insert("code", true);
+      let mut delta = DeltaTextOperations::new();
+      delta.insert(&code_lines.join("\n"), attributes);
+      NodeDataBuilder::new(TEXT_NODE_TYPE)
+        .insert_delta(delta)
+        .build()
+    } else {
+      decode_block(trimmed_line.trim_end())
+    };
+
+    while let Some((top_depth, _)) = stack.last() {
+      if *top_depth < depth {
+        break;
+      }
+      pop_node(&mut stack, &mut nodes);
+    }
+    stack.push((depth, node));
+  }
+
+  while !stack.is_empty() {
+    pop_node(&mut stack, &mut nodes);
+  }
+
+  NodeDataBuilder::new(EDITOR_NODE_TYPE)
+    .extend_node_data(nodes)
+    .build()
+}
+
+fn pop_node(stack: &mut Vec<(usize, NodeData)>, nodes: &mut Vec<NodeData>) {
+  if let Some((_, node)) = stack.pop() {
+    match stack.last_mut() {
+      None => nodes.push(node),
+      Some((_, parent)) => parent.children.push(node),
+    }
+  }
+}
+
+fn indent_depth(line: &str) -> usize {
+  let mut spaces = 0;
+  for c in line.chars() {
+    match c {
+      ' ' => spaces += 1,
+      '\t' => spaces += INDENT_SPACES,
+      _ => break,
+    }
+  }
+  spaces / INDENT_SPACES
+}
+
+fn decode_block(line: &str) -> NodeData {
+  if let Some(src) = decode_image(line) {
+    return NodeDataBuilder::new(IMAGE_NODE_TYPE)
+      .insert_attribute("image_src", src)
+      .insert_attribute("align", "center")
+      .build();
+  }
+
+  let mut attributes = AttributeHashMap::new();
+  let text = if let Some((level, text)) = decode_heading(line) {
+    attributes.insert("subtype", "heading");
+    attributes.insert("heading", format!("h{}", level));
+    text
+  } else if let Some((is_checked, text)) = decode_checkbox(line) {
+    attributes.insert("subtype", "checkbox");
+    attributes.insert("checkbox", is_checked);
+    text
+  } else if let Some(text) = strip_any_prefix(line, &["* ", "- ", "+ "]) {
+    attributes.insert("subtype", "bulleted-list");
+    text
+  } else if let Some((number, text)) = decode_number_list(line) {
+    attributes.insert("subtype", "number-list");
+    attributes.insert("number", number);
+    text
+  } else if let Some(text) = line.strip_prefix('>') {
+    attributes.insert("subtype", "quote");
+    text.trim_start()
+  } else {
+    line
+  };
+
+  let mut node = NodeDataBuilder::new(TEXT_NODE_TYPE)
+    .insert_delta(decode_inline(text))
+    .build();
+  node.attributes = attributes;
+  node
+}
+
+fn decode_image(line: &str) -> Option<&str> {
+  let rest = line.strip_prefix("![")?;
+  let (_, rest) = rest.split_once("](")?;
+  rest.strip_suffix(')')
+}
+
+fn decode_heading(line: &str) -> Option<(usize, &str)> {
+  let level = line.chars().take_while(|c| *c == '#').count();
+  if level == 0 || level > 6 {
+    return None;
+  }
+  line[level..].strip_prefix(' ').map(|text| (level, text))
+}
+
+fn decode_checkbox(line: &str) -> Option<(bool, &str)> {
+  let rest = strip_any_prefix(line, &["- ", "* ", "+ "])?;
+  if let Some(text) = strip_any_prefix(rest, &["[x] ", "[X] "]) {
+    return Some((true, text));
+  }
+  rest.strip_prefix("[ ] ").map(|text| (false, text))
+}
+
+fn decode_number_list(line: &str) -> Option<(i64, &str)> {
+  let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+  if digits == 0 {
+    return None;
+  }
+  let number = line[..digits].parse::<i64>().ok()?;
+  line[digits..].strip_prefix(". ").map(|text| (number, text))
+}
+
+fn strip_any_prefix<'a>(line: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+  prefixes.iter().find_map(|prefix| line.strip_prefix(prefix))
+}
+
+/// The inline styles that are toggled by the paired marks.
+const INLINE_MARKS: &[(&str, &str, &str)] = &[
+  ("**", "**", "bold"),
+  ("~~", "~~", "strikethrough"),
+  ("<u>", "</u>", "underline"),
+  ("_", "_", "italic"),
+  ("*", "*", "italic"),
+];
+
+/// Decodes the inline styles of the Markdown text into the text delta.
+pub fn decode_inline(text: &str) -> DeltaTextOperations {
+  let mut delta = DeltaTextOperations::new();
+  InlineDecoder::new(&mut delta).decode(text, &AttributeHashMap::new());
+  delta
+}
+
+struct InlineDecoder<'a> {
+  delta: &'a mut DeltaTextOperations,
+  buffer: String,
+}
+
+impl<'a> InlineDecoder<'a> {
+  fn new(delta: &'a mut DeltaTextOperations) -> Self {
+    Self {
+      delta,
+      buffer: String::new(),
+    }
+  }
+
+  fn decode(&mut self, text: &str, base_attributes: &AttributeHashMap) {
+    let mut attributes = base_attributes.clone();
+    let mut index = 0;
+    while index < text.len() {
+      let rest = &text[index..];
+
+      // Escaped character
+      if let Some(escaped) = rest.strip_prefix('\\') {
+        if let Some(c) = escaped.chars().next() {
+          self.buffer.push(c);
+          index += 1 + c.len_utf8();
+          continue;
+        }
+      }
+
+      // Code span
+      if let Some(code_rest) = rest.strip_prefix('`') {
+        if let Some(end) = code_rest.find('`') {
+          self.flush(&attributes);
+          let mut code_attributes = attributes.clone();
+          code_attributes.insert("code", true);
+          self.buffer.push_str(&code_rest[..end]);
+          self.flush(&code_attributes);
+          index += end + 2;
+          continue;
+        }
+      }
+
+      // Link: [text](href)
+      if let Some((label, href, len)) = decode_link(rest) {
+        self.flush(&attributes);
+        let mut link_attributes = attributes.clone();
+        link_attributes.insert("href", href);
+        self.decode(label, &link_attributes);
+        index += len;
+        continue;
+      }
+
+      // Paired marks
+      if let Some((mark_len, key)) = self.toggle_mark(rest, &attributes) {
+        self.flush(&attributes);
+        if attributes.contains_key(key) && !base_attributes.contains_key(key) {
+          attributes.remove_key(key);
+        } else {
+          attributes.insert(key, true);
+        }
+        index += mark_len;
+        continue;
+      }
+
+      let c = rest.chars().next().unwrap();
+      self.buffer.push(c);
+      index += c.len_utf8();
+    }
+    self.flush(&attributes);
+  }
+
+  /// Returns the length of the mark and the attribute key if the text starts with an opening
+  /// mark that has a closing mark, or a closing mark of an opened style.
+  fn toggle_mark(
+    &self,
+    text: &str,
+    attributes: &AttributeHashMap,
+  ) -> Option<(usize, &'static str)> {
+    for (open, close, key) in INLINE_MARKS {
+      if attributes.contains_key(*key) {
+        if text.starts_with(close) {
+          return Some((close.len(), *key));
+        }
+      } else if let Some(rest) = text.strip_prefix(open) {
+        let is_word_start = rest.chars().next().map_or(false, |c| !c.is_whitespace());
+        if is_word_start && rest.contains(close) {
+          return Some((open.len(), *key));
+        }
+      }
+    }
+    None
+  }
+
+  fn flush(&mut self, attributes: &AttributeHashMap) {
+    if !self.buffer.is_empty() {
+      let text = std::mem::take(&mut self.buffer);
+      self.delta.insert(&text, attributes.clone());
+    }
+  }
+}
+
+/// Returns the label, the href and the length of the link if the text starts with a link.
+fn decode_link(text: &str) -> Option<(&str, &str, usize)> {
+  let rest = text.strip_prefix('[')?;
+  let label_end = rest.find("](")?;
+  let label = &rest[..label_end];
+  let href_start = label_end + 2;
+  let href_len = rest[href_start..].find(')')?;
+  let href = &rest[href_start..href_start + href_len];
+  Some((label, href, 1 + href_start + href_len + 1))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::codec::markdown::markdown_decoder;
+  use crate::core::{AttributeHashMap, Body, NodeData};
+
+  fn delta_json(node: &NodeData) -> String {
+    match &node.body {
+      Body::Delta(delta) => delta.json_str(),
+      _ => "".to_owned(),
+    }
+  }
+
+  fn subtype(node: &NodeData) -> Option<String> {
+    node
+      .attributes
+      .get("subtype")
+      .and_then(|value| value.str_value())
+  }
+
+  #[test]
+  fn markdown_decoder_heading_test() {
+    let node = markdown_decoder("# heading 1\n### heading 3");
+    assert_eq!(node.node_type, "editor");
+    assert_eq!(node.children.len(), 2);
+    assert_eq!(subtype(&node.children[0]).unwrap(), "heading");
+    assert_eq!(
+      node.children[1]
+        .attributes
+        .get("heading")
+        .unwrap()
+        .str_value()
+        .unwrap(),
+      "h3"
+    );
+    assert_eq!(delta_json(&node.children[0]), r#"[{"insert":"heading 1"}]"#);
+  }
+
+  #[test]
+  fn markdown_decoder_paragraph_test() {
+    let node = markdown_decoder("AppFlowy\n\nis open source");
+    assert_eq!(node.children.len(), 3);
+    assert_eq!(node.children[0].attributes, AttributeHashMap::new());
+    assert_eq!(delta_json(&node.children[1]), "[]");
+    assert_eq!(
+      delta_json(&node.children[2]),
+      r#"[{"insert":"is open source"}]"#
+    );
+  }
+
+  #[test]
+  fn markdown_decoder_list_test() {
+    let node = markdown_decoder("* bullet\n1. first\n2. second\n- [x] done\n- [ ] todo\n> quote");
+    let subtypes = node
+      .children
+      .iter()
+      .map(|node| subtype(node).unwrap())
+      .collect::<Vec<String>>();
+    assert_eq!(
+      subtypes,
+      vec![
+        "bulleted-list",
+        "number-list",
+        "number-list",
+        "checkbox",
+        "checkbox",
+        "quote"
+      ]
+    );
+    assert_eq!(
+      node.children[2]
+        .attributes
+        .get("number")
+        .unwrap()
+        .int_value()
+        .unwrap(),
+      2
+    );
+    let is_checked = |node: &NodeData| {
+      node
+        .attributes
+        .get("checkbox")
+        .and_then(|value| value.bool_value())
+        .unwrap()
+    };
+    assert!(is_checked(&node.children[3]));
+    assert!(!is_checked(&node.children[4]));
+    assert_eq!(delta_json(&node.children[5]), r#"[{"insert":"quote"}]"#);
+  }
+
+  #[test]
+  fn markdown_decoder_nested_list_test() {
+    let node = markdown_decoder("* parent\n  * child\n    * grandchild\n* sibling");
+    assert_eq!(node.children.len(), 2);
+    assert_eq!(node.children[0].children.len(), 1);
+    assert_eq!(node.children[0].children[0].children.len(), 1);
+    assert_eq!(
+      delta_json(&node.children[0].children[0].children[0]),
+      r#"[{"insert":"grandchild"}]"#
+    );
+  }
+
+  #[test]
+  fn markdown_decoder_image_test() {
+    let node = markdown_decoder("![](https://appflowy.io/logo.png)");
+    assert_eq!(node.children[0].node_type, "image");
+    assert_eq!(
+      node.children[0]
+        .attributes
+        .get("image_src")
+        .unwrap()
+        .str_value()
+        .unwrap(),
+      "https://appflowy.io/logo.png"
+    );
+  }
+
+  #[test]
+  fn markdown_decoder_inline_style_test() {
+    let node = markdown_decoder("**bold** _italic_ ~~strike~~ <u>underline</u> `code`");
+    assert_eq!(
+      delta_json(&node.children[0]),
+      r#"[{"insert":"bold","attributes":{"bold":true}},{"insert":" "},{"insert":"italic","attributes":{"italic":true}},{"insert":" "},{"insert":"strike","attributes":{"strikethrough":true}},{"insert":" "},{"insert":"underline","attributes":{"underline":true}},{"insert":" "},{"insert":"code","attributes":{"code":true}}]"#
+    );
+  }
+
+  #[test]
+  fn markdown_decoder_link_test() {
+    let node = markdown_decoder("visit [**AppFlowy**](https://appflowy.io)");
+    assert_eq!(
+      delta_json(&node.children[0]),
+      r#"[{"insert":"visit "},{"insert":"AppFlowy","attributes":{"href":"https://appflowy.io","bold":true}}]"#
+    );
+  }
+
+  #[test]
+  fn markdown_decoder_unpaired_mark_test() {
+    let node = markdown_decoder("snake_case and 2 * 3");
+    assert_eq!(
+      delta_json(&node.children[0]),
+      r#"[{"insert":"snake_case and 2 * 3"}]"#
+    );
+  }
+
+  #[test]
+  fn markdown_decoder_code_block_test() {
+    let node = markdown_decoder("```\nfn main() {}\n```\nafter");
+    assert_eq!(node.children.len(), 2);
+    assert_eq!(
+      delta_json(&node.children[0]),
+      r#"[{"insert":"fn main() {}","attributes":{"code":true}}]"#
+    );
+  }
+}
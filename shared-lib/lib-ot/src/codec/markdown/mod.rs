@@ -1 +1,5 @@
-// pub mod markdown_encoder;
+mod markdown_decoder;
+mod markdown_encoder;
+
+pub use markdown_decoder::*;
+pub use markdown_encoder::*;
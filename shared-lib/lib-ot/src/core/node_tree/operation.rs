@@ -15,6 +15,11 @@ pub enum NodeOperation {
 
   #[serde(rename = "delete")]
   Delete { path: Path, nodes: Vec<NodeData> },
+
+  /// Moves the node at `from` to `to`. The `to` path references to the position in the tree
+  /// that the node is removed from. The moved node keeps its identity and its descendants.
+  #[serde(rename = "move")]
+  Move { from: Path, to: Path },
 }
 
 impl NodeOperation {
@@ -23,6 +28,7 @@ impl NodeOperation {
       NodeOperation::Insert { path, .. } => path,
       NodeOperation::Delete { path, .. } => path,
       NodeOperation::Update { path, .. } => path,
+      NodeOperation::Move { from, .. } => from,
     }
   }
 
@@ -31,6 +37,7 @@ impl NodeOperation {
       NodeOperation::Insert { path, .. } => path,
       NodeOperation::Delete { path, .. } => path,
       NodeOperation::Update { path, .. } => path,
+      NodeOperation::Move { from, .. } => from,
     }
   }
 
@@ -39,6 +46,7 @@ impl NodeOperation {
       NodeOperation::Insert { .. } => false,
       NodeOperation::Update { path: _, changeset } => changeset.is_delta(),
       NodeOperation::Delete { .. } => false,
+      NodeOperation::Move { .. } => false,
     }
   }
 
@@ -47,6 +55,7 @@ impl NodeOperation {
       NodeOperation::Insert { .. } => false,
       NodeOperation::Update { path: _, changeset } => changeset.is_attribute(),
       NodeOperation::Delete { .. } => false,
+      NodeOperation::Move { .. } => false,
     }
   }
   pub fn is_insert(&self) -> bool {
@@ -54,9 +63,15 @@ impl NodeOperation {
       NodeOperation::Insert { .. } => true,
       NodeOperation::Update { .. } => false,
       NodeOperation::Delete { .. } => false,
+      NodeOperation::Move { .. } => false,
     }
   }
   pub fn can_compose(&self, other: &NodeOperation) -> bool {
+    // Moving the node that was just moved can be composed into one move.
+    if let (NodeOperation::Move { to, .. }, NodeOperation::Move { from, .. }) = (self, other) {
+      return to == from;
+    }
+
     if self.get_path() != other.get_path() {
       return false;
     }
@@ -103,6 +118,16 @@ impl NodeOperation {
           changeset: other_changeset,
         },
       ) => changeset.compose(other_changeset),
+      (
+        NodeOperation::Move { from: _, to },
+        NodeOperation::Move {
+          from: _,
+          to: other_to,
+        },
+      ) => {
+        *to = other_to.clone();
+        Ok(())
+      },
       (_left, _right) => Err(OTError::compose().context("Can't compose the operation")),
     }
   }
@@ -124,6 +149,10 @@ impl NodeOperation {
         path: path.clone(),
         changeset: body.inverted(),
       },
+      NodeOperation::Move { from, to } => NodeOperation::Move {
+        from: to.clone(),
+        to: from.clone(),
+      },
     }
   }
 
@@ -136,6 +165,9 @@ impl NodeOperation {
   ///
   /// * `other`: The operation that is going to be transformed
   ///
+  /// Returns false if the `other` operation has nothing to do after applying the `self` operation,
+  /// for example, moving the node that was deleted. The `other` operation should be dropped.
+  ///
   /// # Examples
   ///
   /// ```
@@ -159,20 +191,81 @@ impl NodeOperation {
   /// assert_eq!(serde_json::to_string(&op_2).unwrap(), r#"{"op":"insert","path":[0,2],"nodes":[{"type":"text_2"}]}"#);
   /// assert_eq!(serde_json::to_string(&op_1).unwrap(), r#"{"op":"insert","path":[0,1],"nodes":[{"type":"text_1"}]}"#);
   /// ```
-  pub fn transform(&self, other: &mut NodeOperation) {
+  pub fn transform(&self, other: &mut NodeOperation) -> bool {
     match self {
-      NodeOperation::Insert { path, nodes } => {
-        let new_path = path.transform(other.get_path(), nodes.len());
-        *other.get_mut_path() = new_path;
+      NodeOperation::Insert { path, nodes } => match other {
+        NodeOperation::Move { from, to } => {
+          // The `to` path of the move references to the tree that the moved node is removed
+          // from. So the inserted path needs to be transformed first.
+          let inserted_path = if *path == *from {
+            Some(path.clone())
+          } else {
+            from.transform_by_delete(path, 1)
+          };
+          if let Some(inserted_path) = inserted_path {
+            *to = inserted_path.transform(to, nodes.len());
+          }
+          *from = path.transform(from, nodes.len());
+        },
+        _ => {
+          let new_path = path.transform(other.get_path(), nodes.len());
+          *other.get_mut_path() = new_path;
+        },
       },
-      NodeOperation::Delete { path, nodes } => {
-        let new_path = path.transform(other.get_path(), nodes.len());
-        *other.get_mut_path() = new_path;
+      NodeOperation::Delete { path, nodes } => match other {
+        NodeOperation::Move { from, to } => match path.transform_by_delete(from, nodes.len()) {
+          None => {
+            // The moved node was deleted, so there is nothing to move.
+            return false;
+          },
+          Some(new_from) => {
+            if let Some(deleted_path) = from.transform_by_delete(path, 1) {
+              // If the target position was deleted, the node will be placed at the position of
+              // the deleted nodes.
+              *to = deleted_path
+                .transform_by_delete(to, nodes.len())
+                .unwrap_or(deleted_path);
+            }
+            *from = new_from;
+          },
+        },
+        _ => {
+          let new_path = path.transform(other.get_path(), nodes.len());
+          *other.get_mut_path() = new_path;
+        },
+      },
+      NodeOperation::Move { from, to } => match other {
+        NodeOperation::Move {
+          from: other_from,
+          to: other_to,
+        } => {
+          *other_to = transform_position_by_move(from, to, other_to);
+          *other_from = from.transform_by_move(to, other_from);
+        },
+        NodeOperation::Insert { path, .. } => {
+          *path = transform_position_by_move(from, to, path);
+        },
+        _ => {
+          let new_path = from.transform_by_move(to, other.get_path());
+          *other.get_mut_path() = new_path;
+        },
       },
-      _ => {
-        // Only insert/delete will change the path.
+      NodeOperation::Update { .. } => {
+        // Only insert/delete/move will change the path.
       },
     }
+    true
+  }
+}
+
+/// Transforms the position that is used to insert nodes after moving the node from `from` to
+/// `to`. Unlike the path of a node, inserting at the position of the moved node means inserting
+/// before the node that follows it, so the position doesn't follow the moved node.
+fn transform_position_by_move(from: &Path, to: &Path, position: &Path) -> Path {
+  if position == from {
+    to.transform(position, 1)
+  } else {
+    from.transform_by_move(to, position)
   }
 }
 
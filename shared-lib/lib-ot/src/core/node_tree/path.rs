@@ -222,4 +222,79 @@ impl Path {
     prefix.append(&mut suffix);
     Path(prefix)
   }
+
+  /// Returns the path that the `other` path references to after removing the `len` consecutive
+  /// nodes starting at the current path.
+  ///
+  /// Returns None if the `other` path references to one of the removed nodes or their
+  /// descendants.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lib_ot::core::Path;
+  /// let path = Path(vec![0, 1]);
+  /// assert_eq!(path.transform_by_delete(&Path(vec![0, 0]), 1), Some(Path(vec![0, 0])));
+  /// assert_eq!(path.transform_by_delete(&Path(vec![0, 1, 2]), 1), None);
+  /// assert_eq!(path.transform_by_delete(&Path(vec![0, 2, 2]), 1), Some(Path(vec![0, 1, 2])));
+  /// assert_eq!(path.transform_by_delete(&Path(vec![0, 3]), 2), Some(Path(vec![0, 1])));
+  /// assert_eq!(path.transform_by_delete(&Path(vec![1]), 1), Some(Path(vec![1])));
+  /// ```
+  pub fn transform_by_delete(&self, other: &Path, len: usize) -> Option<Path> {
+    if self.is_empty() || self.len() > other.len() {
+      return Some(other.clone());
+    }
+
+    let last_index = self.len() - 1;
+    if self.0[0..last_index] != other.0[0..last_index] {
+      return Some(other.clone());
+    }
+
+    let deleted_index = self.0[last_index];
+    let other_index = other.0[last_index];
+    if other_index < deleted_index {
+      return Some(other.clone());
+    }
+    if other_index < deleted_index + len {
+      return None;
+    }
+
+    let mut path = other.clone();
+    path.0[last_index] = other_index - len;
+    Some(path)
+  }
+
+  /// Returns the path that the `other` path references to after moving the node from the
+  /// current path to the `to` path. The `to` path references to the position in the tree that
+  /// the node is removed from.
+  ///
+  /// If the `other` path references to the moved node or its descendants, it will follow the
+  /// moved node.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use lib_ot::core::Path;
+  /// let from = Path(vec![0]);
+  /// let to = Path(vec![2]);
+  /// // The moved node and its descendants
+  /// assert_eq!(from.transform_by_move(&to, &Path(vec![0])), Path(vec![2]));
+  /// assert_eq!(from.transform_by_move(&to, &Path(vec![0, 1])), Path(vec![2, 1]));
+  /// // The siblings of the moved node
+  /// assert_eq!(from.transform_by_move(&to, &Path(vec![1])), Path(vec![0]));
+  /// assert_eq!(from.transform_by_move(&to, &Path(vec![2])), Path(vec![1]));
+  /// assert_eq!(from.transform_by_move(&to, &Path(vec![3])), Path(vec![3]));
+  /// ```
+  pub fn transform_by_move(&self, to: &Path, other: &Path) -> Path {
+    if other.starts_with(&self.0) {
+      let mut path = to.clone();
+      path.extend_from_slice(&other.0[self.len()..]);
+      return path;
+    }
+
+    match self.transform_by_delete(other, 1) {
+      None => other.clone(),
+      Some(path) => to.transform(&path, 1),
+    }
+  }
 }
@@ -68,16 +68,19 @@ impl Transaction {
   ///
   /// The semantics of transform is used when editing conflicts occur, which is often determined by the version id。
   /// the operations of the transaction will be transformed into the conflict operations.
+  /// The operations of `other` that have nothing to do after the transform are dropped.
   pub fn transform(&self, other: &Transaction) -> Result<Transaction, OTError> {
     let mut other = other.clone();
     other.extension = self.extension.clone();
 
-    for other_operation in other.operations.values_mut() {
+    other.operations.values_mut().retain_mut(|other_operation| {
       let other_operation = Arc::make_mut(other_operation);
-      for operation in self.operations.values() {
-        operation.transform(other_operation);
-      }
-    }
+      self
+        .operations
+        .values()
+        .iter()
+        .all(|operation| operation.transform(other_operation))
+    });
 
     Ok(other)
   }
@@ -252,6 +255,33 @@ impl TransactionBuilder {
     self
   }
 
+  /// Moves the node at `from` to `to`. The `to` path references to the position in the tree
+  /// that the node is removed from.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// // -- 0 (root)
+  /// //      0 -- text_1
+  /// //      1 -- text_2
+  /// use lib_ot::core::{NodeTree, NodeData, TransactionBuilder};
+  /// let mut node_tree = NodeTree::default();
+  /// let transaction = TransactionBuilder::new()
+  ///     .insert_nodes_at_path(0, vec![NodeData::new("text_1"), NodeData::new("text_2")])
+  ///     .move_node_at_path(0, 1)
+  ///     .build();
+  ///  node_tree.apply_transaction(transaction).unwrap();
+  ///  let node = node_tree.get_node_data_at_path(&1.into()).unwrap();
+  ///  assert_eq!(node.node_type, "text_1");
+  /// ```
+  pub fn move_node_at_path<T: Into<Path>>(mut self, from: T, to: T) -> Self {
+    self.operations.push_op(NodeOperation::Move {
+      from: from.into(),
+      to: to.into(),
+    });
+    self
+  }
+
   fn get_deleted_node_data(&self, node_tree: &NodeTree, node_id: NodeId) -> NodeData {
     recursive_get_deleted_node_data(node_tree, node_id)
   }
@@ -300,6 +300,7 @@ impl NodeTree {
           self.delete_nodes(&path, nodes)
         }
       },
+      NodeOperation::Move { from, to } => self.move_node(&from, &to),
     }
   }
   /// Inserts nodes at given path
@@ -488,6 +489,59 @@ impl NodeTree {
     Ok(())
   }
 
+  /// Moves the node at `from` to `to`. The node is detached from the tree with its descendants
+  /// and then attached at the `to` path, which references to the position in the tree after
+  /// the node is detached.
+  ///
+  /// Do nothing if there is no node at the `from` path. Returns error if the parent of the
+  /// `to` path doesn't exist, the tree is kept unchanged in this case.
+  fn move_node(&mut self, from: &Path, to: &Path) -> Result<(), OTError> {
+    if !from.is_valid() || !to.is_valid() {
+      return Err(OTErrorCode::InvalidPath.into());
+    }
+
+    let node_id = match self.node_id_at_path(from) {
+      None => {
+        tracing::warn!("Can't find any node at path: {:?}", from);
+        return Ok(());
+      },
+      Some(node_id) => node_id,
+    };
+
+    let (old_parent, old_next_sibling) = match self.arena.get(node_id) {
+      None => return Ok(()),
+      Some(node) => (node.parent(), node.next_sibling()),
+    };
+    node_id.detach(&mut self.arena);
+
+    let (parent_path, last_path) = to.split_at(to.len() - 1);
+    let parent = if parent_path.is_empty() {
+      Some(self.root)
+    } else {
+      self.node_id_at_path(parent_path)
+    };
+
+    match parent {
+      Some(parent) => {
+        let index = *last_path.first().unwrap();
+        match self.node_id_from_parent_at_index(parent, index) {
+          None => parent.append(node_id, &mut self.arena),
+          Some(sibling) => sibling.insert_before(node_id, &mut self.arena),
+        }
+        Ok(())
+      },
+      None => {
+        // Restores the node to its original position.
+        match (old_next_sibling, old_parent) {
+          (Some(sibling), _) => sibling.insert_before(node_id, &mut self.arena),
+          (None, Some(parent)) => parent.append(node_id, &mut self.arena),
+          (None, None) => {},
+        }
+        Err(OTError::path_not_found().context(format!("Can't find the parent of path: {:?}", to)))
+      },
+    }
+  }
+
   /// Update the node at path with the `changeset`
   ///
   /// Do nothing if there is no node at the path.
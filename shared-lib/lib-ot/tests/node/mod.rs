@@ -4,6 +4,7 @@ mod operation_compose_test;
 mod operation_delete_test;
 mod operation_delta_test;
 mod operation_insert_test;
+mod operation_move_test;
 mod script;
 mod serde_test;
 mod transaction_compose_test;
@@ -0,0 +1,384 @@
+use crate::node::script::NodeScript::*;
+use crate::node::script::NodeTest;
+
+use lib_ot::core::{NodeData, NodeDataBuilder, NodeOperation, NodeTree, Path, TransactionBuilder};
+
+fn make_nodes() -> (NodeData, NodeData, NodeData) {
+  (
+    NodeData::new("text_1"),
+    NodeData::new("text_2"),
+    NodeData::new("text_3"),
+  )
+}
+
+#[test]
+fn operation_move_node_forward_test() {
+  let mut test = NodeTest::new();
+  let (text_1, text_2, text_3) = make_nodes();
+  let scripts = vec![
+    InsertNodes {
+      path: 0.into(),
+      node_data_list: vec![text_1.clone(), text_2.clone(), text_3.clone()],
+      rev_id: 1,
+    },
+    MoveNode {
+      from: 0.into(),
+      to: 2.into(),
+      rev_id: 2,
+    },
+    AssertNodesAtRoot {
+      expected: vec![text_2, text_3, text_1],
+    },
+  ];
+  test.run_scripts(scripts);
+}
+
+#[test]
+fn operation_move_node_backward_test() {
+  let mut test = NodeTest::new();
+  let (text_1, text_2, text_3) = make_nodes();
+  let scripts = vec![
+    InsertNodes {
+      path: 0.into(),
+      node_data_list: vec![text_1.clone(), text_2.clone(), text_3.clone()],
+      rev_id: 1,
+    },
+    MoveNode {
+      from: 2.into(),
+      to: 0.into(),
+      rev_id: 2,
+    },
+    AssertNodesAtRoot {
+      expected: vec![text_3, text_1, text_2],
+    },
+  ];
+  test.run_scripts(scripts);
+}
+
+#[test]
+fn operation_move_node_with_children_test() {
+  let mut test = NodeTest::new();
+  let image_a = NodeData::new("image_a");
+  let image_b = NodeData::new("image_b");
+  let text_1 = NodeDataBuilder::new("text_1")
+    .add_node_data(image_a.clone())
+    .add_node_data(image_b.clone())
+    .build();
+  let text_2 = NodeData::new("text_2");
+  let scripts = vec![
+    InsertNodes {
+      path: 0.into(),
+      node_data_list: vec![text_1.clone(), text_2.clone()],
+      rev_id: 1,
+    },
+    // 0:text_1
+    //      0:image_a
+    //      1:image_b
+    // 1:text_2
+    MoveNode {
+      from: 0.into(),
+      to: 1.into(),
+      rev_id: 2,
+    },
+    // 0:text_2
+    // 1:text_1
+    //      0:image_a
+    //      1:image_b
+    AssertNodesAtRoot {
+      expected: vec![text_2, text_1],
+    },
+    // Move the image_b into text_2
+    MoveNode {
+      from: vec![1, 1].into(),
+      to: vec![0, 0].into(),
+      rev_id: 3,
+    },
+    AssertNode {
+      path: vec![0, 0].into(),
+      expected: Some(image_b),
+    },
+    AssertNode {
+      path: vec![1, 0].into(),
+      expected: Some(image_a),
+    },
+    AssertNumberOfChildrenAtPath {
+      path: Some(1.into()),
+      expected: 1,
+    },
+  ];
+  test.run_scripts(scripts);
+}
+
+#[test]
+fn operation_move_node_to_not_exist_parent_test() {
+  let (text_1, text_2, _) = make_nodes();
+  let mut node_tree = NodeTree::default();
+  let transaction = TransactionBuilder::new()
+    .insert_nodes_at_path(0, vec![text_1.clone(), text_2.clone()])
+    .build();
+  node_tree.apply_transaction(transaction).unwrap();
+
+  // The parent of the `to` path doesn't exist, the tree is kept unchanged.
+  let result = node_tree.apply_op(NodeOperation::Move {
+    from: 0.into(),
+    to: Path(vec![5, 0]),
+  });
+  assert!(result.is_err());
+  assert_eq!(
+    node_tree.get_node_data_at_root().unwrap().children,
+    vec![text_1, text_2]
+  );
+}
+
+#[test]
+fn operation_move_node_inverted_test() {
+  let text_1 = NodeDataBuilder::new("text_1")
+    .add_node_data(NodeData::new("image_a"))
+    .build();
+  let (_, text_2, text_3) = make_nodes();
+  let mut node_tree = NodeTree::default();
+  let transaction = TransactionBuilder::new()
+    .insert_nodes_at_path(0, vec![text_1.clone(), text_2.clone(), text_3.clone()])
+    .build();
+  node_tree.apply_transaction(transaction).unwrap();
+
+  let operation = NodeOperation::Move {
+    from: Path(vec![0]),
+    to: Path(vec![0, 0]),
+  };
+  node_tree.apply_op(operation.clone()).unwrap();
+  assert_eq!(
+    node_tree.get_node_data_at_root().unwrap().children,
+    vec![
+      NodeDataBuilder::new("text_2")
+        .add_node_data(text_1.clone())
+        .build(),
+      text_3.clone()
+    ]
+  );
+
+  node_tree.apply_op(operation.inverted()).unwrap();
+  assert_eq!(
+    node_tree.get_node_data_at_root().unwrap().children,
+    vec![text_1, text_2, text_3]
+  );
+}
+
+#[test]
+fn operation_move_node_compose_test() {
+  let transaction = TransactionBuilder::new()
+    .move_node_at_path(0, 1)
+    .move_node_at_path(1, 2)
+    .move_node_at_path(0, 1)
+    .build();
+
+  assert_eq!(
+    serde_json::to_string(&transaction.operations).unwrap(),
+    r#"[{"op":"move","from":[0],"to":[2]},{"op":"move","from":[0],"to":[1]}]"#
+  );
+}
+
+#[test]
+fn operation_insert_node_when_moving_node_test() {
+  let mut test = NodeTest::new();
+  let (text_1, text_2, text_3) = make_nodes();
+  let text_4 = NodeData::new("text_4");
+  let scripts = vec![
+    InsertNodes {
+      path: 0.into(),
+      node_data_list: vec![text_1.clone(), text_2.clone(), text_3.clone()],
+      rev_id: 1,
+    },
+    InsertNode {
+      path: 0.into(),
+      node_data: text_4.clone(),
+      rev_id: 2,
+    },
+    // The move action is happened concurrently with the insert action. It wants to move the
+    // text_1 to the end, but the text_1 was pushed to index 1.
+    MoveNode {
+      from: 0.into(),
+      to: 2.into(),
+      rev_id: 2,
+    },
+    AssertNodesAtRoot {
+      expected: vec![text_4, text_2, text_3, text_1],
+    },
+  ];
+  test.run_scripts(scripts);
+}
+
+#[test]
+fn operation_move_node_when_inserting_node_test() {
+  let mut test = NodeTest::new();
+  let (text_1, text_2, text_3) = make_nodes();
+  let text_4 = NodeData::new("text_4");
+  let scripts = vec![
+    InsertNodes {
+      path: 0.into(),
+      node_data_list: vec![text_1.clone(), text_2.clone(), text_3.clone()],
+      rev_id: 1,
+    },
+    MoveNode {
+      from: 0.into(),
+      to: 2.into(),
+      rev_id: 2,
+    },
+    // Insert the text_4 before the text_2 concurrently. The inserted path follows the text_2
+    // instead of the moved text_1.
+    InsertNode {
+      path: 1.into(),
+      node_data: text_4.clone(),
+      rev_id: 2,
+    },
+    AssertNodesAtRoot {
+      expected: vec![text_4, text_2, text_3, text_1],
+    },
+  ];
+  test.run_scripts(scripts);
+}
+
+#[test]
+fn operation_insert_into_moved_node_test() {
+  let mut test = NodeTest::new();
+  let image_a = NodeData::new("image_a");
+  let image_b = NodeData::new("image_b");
+  let text_1 = NodeDataBuilder::new("text_1")
+    .add_node_data(image_a)
+    .build();
+  let text_2 = NodeData::new("text_2");
+  let scripts = vec![
+    InsertNodes {
+      path: 0.into(),
+      node_data_list: vec![text_1, text_2],
+      rev_id: 1,
+    },
+    MoveNode {
+      from: 0.into(),
+      to: 1.into(),
+      rev_id: 2,
+    },
+    // The inserted node follows its parent.
+    InsertNode {
+      path: vec![0, 1].into(),
+      node_data: image_b.clone(),
+      rev_id: 2,
+    },
+    AssertNode {
+      path: vec![1, 1].into(),
+      expected: Some(image_b),
+    },
+  ];
+  test.run_scripts(scripts);
+}
+
+#[test]
+fn operation_delete_node_when_moving_node_test() {
+  let mut test = NodeTest::new();
+  let (text_1, text_2, text_3) = make_nodes();
+  let scripts = vec![
+    InsertNodes {
+      path: 0.into(),
+      node_data_list: vec![text_1, text_2.clone(), text_3.clone()],
+      rev_id: 1,
+    },
+    DeleteNode {
+      path: 0.into(),
+      rev_id: 2,
+    },
+    // Move the text_3 to the front concurrently.
+    MoveNode {
+      from: 2.into(),
+      to: 0.into(),
+      rev_id: 2,
+    },
+    AssertNodesAtRoot {
+      expected: vec![text_3, text_2],
+    },
+  ];
+  test.run_scripts(scripts);
+}
+
+#[test]
+fn operation_move_deleted_node_test() {
+  let mut test = NodeTest::new();
+  let (text_1, text_2, text_3) = make_nodes();
+  let scripts = vec![
+    InsertNodes {
+      path: 0.into(),
+      node_data_list: vec![text_1.clone(), text_2, text_3.clone()],
+      rev_id: 1,
+    },
+    DeleteNode {
+      path: 1.into(),
+      rev_id: 2,
+    },
+    // The text_2 was deleted concurrently, so there is nothing to move.
+    MoveNode {
+      from: 1.into(),
+      to: 0.into(),
+      rev_id: 2,
+    },
+    AssertNodesAtRoot {
+      expected: vec![text_1, text_3],
+    },
+  ];
+  test.run_scripts(scripts);
+}
+
+#[test]
+fn operation_transform_move_by_delete_test() {
+  let (text_1, text_2, text_3) = make_nodes();
+  let mut node_tree = NodeTree::default();
+  let transaction = TransactionBuilder::new()
+    .insert_nodes_at_path(0, vec![text_1.clone(), text_2, text_3.clone()])
+    .build();
+  node_tree.apply_transaction(transaction).unwrap();
+
+  let delete = TransactionBuilder::new()
+    .delete_node_at_path(&node_tree, &Path(vec![1]))
+    .build();
+  let move_node = TransactionBuilder::new().move_node_at_path(1, 0).build();
+  node_tree.apply_transaction(delete.clone()).unwrap();
+
+  // The moved node was deleted, so the move is dropped instead of touching the other nodes.
+  let transformed = delete.transform(&move_node).unwrap();
+  assert!(transformed.operations.is_empty());
+  node_tree.apply_transaction(transformed).unwrap();
+  assert_eq!(
+    node_tree.get_node_data_at_root().unwrap().children,
+    vec![text_1, text_3]
+  );
+}
+
+#[test]
+fn operation_move_node_when_moving_node_test() {
+  let mut test = NodeTest::new();
+  let (text_1, text_2, text_3) = make_nodes();
+  let scripts = vec![
+    InsertNodes {
+      path: 0.into(),
+      node_data_list: vec![text_1.clone(), text_2.clone(), text_3.clone()],
+      rev_id: 1,
+    },
+    MoveNode {
+      from: 0.into(),
+      to: 2.into(),
+      rev_id: 2,
+    },
+    // 0:text_2
+    // 1:text_3
+    // 2:text_1
+    //
+    // Move the text_3 to the front concurrently. The text_3 was moved to index 1.
+    MoveNode {
+      from: 2.into(),
+      to: 0.into(),
+      rev_id: 2,
+    },
+    AssertNodesAtRoot {
+      expected: vec![text_3, text_2, text_1],
+    },
+  ];
+  test.run_scripts(scripts);
+}
@@ -36,6 +36,11 @@ pub enum NodeScript {
     node_data_list: Vec<NodeData>,
     rev_id: usize,
   },
+  MoveNode {
+    from: Path,
+    to: Path,
+    rev_id: usize,
+  },
   AssertNumberOfChildrenAtPath {
     path: Option<Path>,
     expected: usize,
@@ -153,6 +158,13 @@ impl NodeTest {
         self.transform_transaction_if_need(&mut transaction, rev_id);
         self.apply_transaction(transaction);
       },
+      NodeScript::MoveNode { from, to, rev_id } => {
+        let mut transaction = TransactionBuilder::new()
+          .move_node_at_path(from, to)
+          .build();
+        self.transform_transaction_if_need(&mut transaction, rev_id);
+        self.apply_transaction(transaction);
+      },
       NodeScript::AssertNode { path, expected } => {
         let node = self.node_tree.get_node_data_at_path(&path);
         assert_eq!(node, expected.map(|e| e.into()));
@@ -67,6 +67,19 @@ fn operation_update_node_body_serialize_test() {
   );
 }
 
+#[test]
+fn operation_move_node_serde_test() {
+  let operation = NodeOperation::Move {
+    from: Path(vec![0, 1]),
+    to: Path(vec![2]),
+  };
+  let json = serde_json::to_string(&operation).unwrap();
+  assert_eq!(json, r#"{"op":"move","from":[0,1],"to":[2]}"#);
+
+  let operation: NodeOperation = serde_json::from_str(&json).unwrap();
+  assert_eq!(serde_json::to_string(&operation).unwrap(), json);
+}
+
 #[test]
 fn operation_update_node_body_deserialize_test() {
   let json_1 = r#"{"op":"update","path":[0,1],"changeset":{"delta":{"delta":[{"insert":"AppFlowy..."}],"inverted":[{"delete":11}]}}}"#;